@@ -0,0 +1,70 @@
+// Stand-in for a Minecraft server, launched by the runner tests (src/test_support.rs) like a
+// Bedrock binary. It prints the lines the runner parses and obeys a few console commands:
+//   stop            "Stopping the server", exit 0
+//   crash           exit 1 without the stop message
+//   spam <n>        print n numbered lines
+//   join <name>     vanilla join line; `leave <name>` the matching leave line
+// fake_server.txt in the working directory (the server folder) changes its behavior, one
+// option per line:
+//   crash_on_start  exit 1 right after starting
+//   ignore_stop     keep running on "stop" and after stdin closes, so only a kill ends it
+//   startup_ms=<n>  wait before printing the "Done" line
+// Every start appends a line to starts.log, so tests can count restarts.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let options = fs::read_to_string("fake_server.txt").unwrap_or_default();
+    let has = |name: &str| options.lines().any(|l| l.trim() == name);
+    let startup_ms = options.lines()
+        .find_map(|l| l.trim().strip_prefix("startup_ms="))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    if let Ok(mut starts) = OpenOptions::new().create(true).append(true).open("starts.log") {
+        let _ = writeln!(starts, "{}", process::id());
+    }
+
+    let mut out = io::stdout().lock();
+    let _ = writeln!(out, "[Server thread/INFO]: Starting minecraft server version fake");
+    let _ = out.flush();
+    if has("crash_on_start") {
+        eprintln!("[Server thread/ERROR]: Failed to start the minecraft server");
+        process::exit(1);
+    }
+    thread::sleep(Duration::from_millis(startup_ms));
+    let _ = writeln!(out, "[Server thread/INFO]: Done (0.01s)! For help, type \"help\"");
+    let _ = out.flush();
+
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        let (command, arg) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        match command {
+            "stop" => {
+                let _ = writeln!(out, "[Server thread/INFO]: Stopping the server");
+                let _ = out.flush();
+                if !has("ignore_stop") {
+                    process::exit(0);
+                }
+            }
+            "crash" => process::exit(1),
+            "spam" => {
+                for i in 0..arg.parse::<u32>().unwrap_or(0) {
+                    let _ = writeln!(out, "[Server thread/INFO]: line {}", i);
+                }
+            }
+            "join" => { let _ = writeln!(out, "[Server thread/INFO]: {} joined the game", arg); }
+            "leave" => { let _ = writeln!(out, "[Server thread/INFO]: {} left the game", arg); }
+            _ => { let _ = writeln!(out, "[Server thread/INFO]: Unknown command. Type \"/help\" for help."); }
+        }
+        let _ = out.flush();
+    }
+    // stdin closed: the runner is gone, or dropped its end after "stop"
+    while has("ignore_stop") {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use super::registry::record_change;
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Activity feed (~/Mineserver/activity.log, one JSON object per line): jar downloads, plugin and
+// mod installs/updates, world imports and backups, so users can see what changed on a server.
+
+static ACTIVITY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    pub timestamp: String,
+    pub kind: String, // "server_download", "plugin_install", "plugin_update", "mod_install", "mod_update", "world_import", "backup", "restore"
+    pub server_path: String,
+    pub title: String,
+    pub success: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    #[serde(default)]
+    pub backup_id: Option<String>, // Pre-update backup to roll back to
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityFilter {
+    pub server_path: Option<String>,
+    pub kind: Option<String>,
+    pub since: Option<String>, // RFC 3339
+    pub success: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+fn get_activity_file() -> PathBuf {
+    app_data_dir().join("activity.log")
+}
+
+fn append(entry: &ActivityEntry) {
+    let line = match serde_json::to_string(entry) {
+        Ok(l) => l,
+        Err(_) => return,
+    };
+
+    let _guard = ACTIVITY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let file = get_activity_file();
+    let _ = fs::create_dir_all(file.parent().unwrap());
+    match OpenOptions::new().create(true).append(true).open(&file) {
+        Ok(mut f) => { let _ = writeln!(f, "{}", line); },
+        Err(e) => eprintln!("[Activity] Failed to write activity log: {}", e),
+    }
+}
+
+/// An activity in progress. It is written to the feed when dropped: as succeeded after
+/// `complete`, as failed otherwise (including early returns through `?`).
+pub struct Activity {
+    entry: ActivityEntry,
+}
+
+impl Activity {
+    pub fn begin(kind: &str, server_path: &str, title: impl Into<String>) -> Self {
+        Self {
+            entry: ActivityEntry {
+                timestamp: chrono::Local::now().to_rfc3339(),
+                kind: kind.to_string(),
+                server_path: server_path.to_string(),
+                title: title.into(),
+                success: false,
+                error: None,
+                size_bytes: None,
+                backup_id: None,
+            },
+        }
+    }
+
+    /// Renames the entry once the actual file or version is known.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.entry.title = title.into();
+    }
+
+    pub fn set_backup_id(&mut self, backup_id: Option<String>) {
+        self.entry.backup_id = backup_id;
+    }
+
+    pub fn complete(mut self, size_bytes: Option<u64>) {
+        self.entry.success = true;
+        self.entry.size_bytes = size_bytes;
+    }
+
+    pub fn fail(mut self, error: impl ToString) {
+        self.entry.error = Some(error.to_string());
+    }
+
+    /// Records the outcome of `result`, keeping its error message on failure.
+    pub fn finish<T, E: ToString>(self, result: &Result<T, E>, size_bytes: Option<u64>) {
+        match result {
+            Ok(_) => self.complete(size_bytes),
+            Err(e) => self.fail(e.to_string()),
+        }
+    }
+}
+
+impl Drop for Activity {
+    fn drop(&mut self) {
+        append(&self.entry);
+        // Finished changes also go into the server's changelog; backups change nothing
+        if self.entry.success && self.entry.kind != "backup" {
+            record_change(&self.entry.server_path, &self.entry.kind, &self.entry.title);
+        }
+    }
+}
+
+/// Newest entries first.
+#[tauri::command]
+pub fn get_activity_log(filter: Option<ActivityFilter>) -> Result<Vec<ActivityEntry>, MineServerError> {
+    let filter = filter.unwrap_or_default();
+    let file = get_activity_file();
+    if !file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read activity log: {}", e))?;
+    let since = match filter.since.as_deref() {
+        Some(s) => Some(chrono::DateTime::parse_from_rfc3339(s)
+            .map_err(|_| MineServerError::validation(format!("Invalid date: {}", s)))?),
+        None => None,
+    };
+
+    let entries = content.lines()
+        .rev()
+        .filter_map(|l| serde_json::from_str::<ActivityEntry>(l).ok())
+        .filter(|e| filter.server_path.as_ref().map(|p| &e.server_path == p).unwrap_or(true))
+        .filter(|e| filter.kind.as_ref().map(|k| &e.kind == k).unwrap_or(true))
+        .filter(|e| filter.success.map(|s| e.success == s).unwrap_or(true))
+        // Entries are appended when they finish but stamped when they start, so the file is only
+        // roughly chronological and `since` can't stop at the first older entry
+        .filter(|e| since.map(|since| chrono::DateTime::parse_from_rfc3339(&e.timestamp).map(|t| t >= since).unwrap_or(true)).unwrap_or(true))
+        .take(filter.limit.unwrap_or(500))
+        .collect();
+    Ok(entries)
+}
@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Cursor, Seek, Write};
+use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use zip::write::FileOptions;
+use zip::result::ZipError;
+use zip::{ZipArchive, ZipWriter};
+use super::settings::load_settings;
+use super::operations::CancelToken;
+
+// Shared zip writing for backups, world exports and file manager archives. Files are streamed
+// through a fixed buffer instead of being read into memory, and entries nearing 4 GiB are
+// written as ZIP64 (zip-rs refuses them otherwise). Archives with more than 65535 entries or
+// over 4 GiB get a ZIP64 central directory automatically.
+
+const COPY_BUFFER: usize = 256 * 1024;
+// Deflate can make incompressible data slightly bigger, and region files keep growing while a
+// running server is backed up, so entries switch to ZIP64 well before the 4 GiB limit
+const ZIP64_THRESHOLD: u64 = 3 * 1024 * 1024 * 1024;
+
+fn needs_zip64(size: u64) -> bool {
+    size >= ZIP64_THRESHOLD
+}
+
+/// Error text for a failed archive write. File systems that can't hold files of 4 GiB or more
+/// (FAT32 drives) fail with a bare "file too large", so that case says what to do instead.
+pub fn write_error(context: &str, e: &io::Error) -> String {
+    // EFBIG on Linux and macOS, ERROR_FILE_TOO_LARGE on Windows
+    let too_large_code = if cfg!(windows) { 223 } else { 27 };
+    if e.raw_os_error() == Some(too_large_code) {
+        format!("{}: the archive is larger than the destination drive allows (FAT32 stops at 4 GB). Save it to an NTFS, exFAT or ext4 drive instead.", context)
+    } else {
+        format!("{}: {}", context, e)
+    }
+}
+
+/// `write_error` for zip-rs results, e.g. from `ZipWriter::finish`.
+pub fn zip_write_error(context: &str, e: ZipError) -> String {
+    match e {
+        ZipError::Io(io) => write_error(context, &io),
+        other => format!("{}: {}", context, other),
+    }
+}
+
+/// Streams `path` into the archive as `name`. Returns the number of bytes read.
+pub fn zip_file<W: Write + Seek>(zip: &mut ZipWriter<W>, name: &str, path: &Path, options: FileOptions) -> Result<u64, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let options = options.large_file(needs_zip64(size));
+
+    zip.start_file(name, options)
+        .map_err(|e| zip_write_error(&format!("Failed to add {} to zip", name), e))?;
+    let mut reader = BufReader::with_capacity(COPY_BUFFER, file);
+    io::copy(&mut reader, zip)
+        .map_err(|e| write_error(&format!("Failed to write {} to zip", name), &e))
+}
+
+/// Files at least this big are compressed on the writer thread; buffering them in memory
+/// for a worker would cost more than the parallelism gains.
+const PARALLEL_MAX_FILE: u64 = 128 * 1024 * 1024;
+
+/// Worker threads for archive compression: `compression_workers` from settings, 0 = all cores.
+pub fn compression_workers() -> usize {
+    match load_settings().compression_workers {
+        0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        n => n as usize,
+    }
+}
+
+/// Compresses `files` (archive name, path on disk) on `workers` threads. Each worker deflates a
+/// file into an in-memory single-entry zip, which the calling thread copies into `zip` without
+/// recompressing. `on_progress(bytes_done, name)` is called after every file. Stops between
+/// files once `cancel` fires. Returns the total uncompressed size.
+pub fn zip_files_parallel<W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    files: Vec<(String, PathBuf)>,
+    options: FileOptions,
+    workers: usize,
+    cancel: &CancelToken,
+    mut on_progress: impl FnMut(u64, &str),
+) -> Result<u64, String> {
+    let (small, large): (Vec<_>, Vec<_>) = files.into_iter()
+        .map(|(name, path)| {
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            (name, path, size)
+        })
+        .partition(|(_, _, size)| *size < PARALLEL_MAX_FILE);
+    let mut done: u64 = 0;
+
+    if workers <= 1 {
+        for (name, path, _) in small.into_iter().chain(large) {
+            cancel.check()?;
+            done += zip_file(zip, &name, &path, options)?;
+            on_progress(done, &name);
+        }
+        return Ok(done);
+    }
+
+    let next = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let small = &small;
+    std::thread::scope(|scope| -> Result<(), String> {
+        // Bounded so workers can't run far ahead of the writer and fill memory
+        let (tx, rx) = mpsc::sync_channel::<(usize, Result<Vec<u8>, String>)>(workers * 2);
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, cancelled) = (&next, &cancelled);
+            scope.spawn(move || {
+                while !cancelled.load(Ordering::Relaxed) && !cancel.is_cancelled() {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some((name, path, _)) = small.get(index) else { break };
+                    let result = compress_to_memory(name, path, options);
+                    if tx.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for (index, result) in rx.iter() {
+            let copied = cancel.check().and(result).and_then(|buffer| {
+                let mut single = ZipArchive::new(Cursor::new(buffer)).map_err(|e| e.to_string())?;
+                let entry = single.by_index_raw(0).map_err(|e| e.to_string())?;
+                zip.raw_copy_file(entry).map_err(|e| zip_write_error(&format!("Failed to write {} to zip", small[index].0), e))
+            });
+            if let Err(e) = copied {
+                cancelled.store(true, Ordering::Relaxed);
+                return Err(e); // Dropping rx unblocks the workers
+            }
+            done += small[index].2;
+            on_progress(done, &small[index].0);
+        }
+        Ok(())
+    })?;
+    cancel.check()?; // Workers stop quietly when cancelled, so the writer may have run out early
+
+    for (name, path, _) in large {
+        cancel.check()?;
+        done += zip_file(zip, &name, &path, options)?;
+        on_progress(done, &name);
+    }
+    Ok(done)
+}
+
+fn compress_to_memory(name: &str, path: &Path, options: FileOptions) -> Result<Vec<u8>, String> {
+    let mut single = ZipWriter::new(Cursor::new(Vec::new()));
+    zip_file(&mut single, name, path, options)?;
+    single.finish()
+        .map(|cursor| cursor.into_inner())
+        .map_err(|e| format!("Failed to compress {}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::SeekFrom;
+
+    /// Discards the archive but tracks its length, so multi-GiB archives can be written quickly.
+    #[derive(Default)]
+    struct NullSink {
+        pos: u64,
+        len: u64,
+    }
+
+    impl Write for NullSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.pos += buf.len() as u64;
+            self.len = self.len.max(self.pos);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for NullSink {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.pos = match pos {
+                SeekFrom::Start(p) => p,
+                SeekFrom::End(d) => (self.len as i64 + d) as u64,
+                SeekFrom::Current(d) => (self.pos as i64 + d) as u64,
+            };
+            Ok(self.pos)
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("mineserver-archive-test-{}-{}", uuid::Uuid::new_v4(), name))
+    }
+
+    #[test]
+    fn entries_switch_to_zip64_before_the_4gib_limit() {
+        assert!(!needs_zip64(100 * 1024 * 1024));
+        assert!(needs_zip64(u32::MAX as u64 - 1024));
+        assert!(needs_zip64(5 * 1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn sparse_file_over_4gib_is_archived() {
+        let path = temp_path("region.mca");
+        let size = 4 * 1024 * 1024 * 1024 + 1024 * 1024;
+        File::create(&path).and_then(|f| f.set_len(size)).expect("create sparse file");
+
+        let mut zip = ZipWriter::new(NullSink::default());
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        let written = zip_file(&mut zip, "world/region/r.0.0.mca", &path, options);
+        let sink = zip.finish();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(written.expect("entry written"), size);
+        assert!(sink.expect("archive finished").len > size);
+    }
+
+    #[test]
+    fn zip64_entry_round_trips() {
+        let source = temp_path("level.dat");
+        let archive = temp_path("backup.zip");
+        fs::write(&source, b"level data").unwrap();
+
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated).large_file(true);
+        zip_file(&mut zip, "world/level.dat", &source, options).unwrap();
+        zip.finish().unwrap();
+
+        let mut read = ZipArchive::new(File::open(&archive).unwrap()).unwrap();
+        let mut content = String::new();
+        io::Read::read_to_string(&mut read.by_name("world/level.dat").unwrap(), &mut content).unwrap();
+        let _ = fs::remove_file(&source);
+        let _ = fs::remove_file(&archive);
+        assert_eq!(content, "level data");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_too_large_error_names_the_drive_limit() {
+        let message = write_error("Failed to write backup", &io::Error::from_raw_os_error(27));
+        assert!(message.contains("4 GB"), "{}", message);
+        let other = write_error("Failed to write backup", &io::Error::from_raw_os_error(28));
+        assert!(!other.contains("4 GB"), "{}", other);
+    }
+}
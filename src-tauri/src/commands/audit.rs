@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Append-only record of destructive operations (~/Mineserver/audit.log, one JSON object per line)
+
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub action: String,
+    pub params: serde_json::Value,
+    pub success: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+fn get_audit_file() -> PathBuf {
+    app_data_dir().join("audit.log")
+}
+
+pub fn record<T>(action: &str, params: serde_json::Value, result: &Result<T, String>) {
+    let entry = AuditEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        action: action.to_string(),
+        params,
+        success: result.is_ok(),
+        error: result.as_ref().err().cloned(),
+    };
+
+    let line = match serde_json::to_string(&entry) {
+        Ok(l) => l,
+        Err(_) => return,
+    };
+
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let file = get_audit_file();
+    let _ = fs::create_dir_all(file.parent().unwrap());
+    match OpenOptions::new().create(true).append(true).open(&file) {
+        Ok(mut f) => { let _ = writeln!(f, "{}", line); },
+        Err(e) => eprintln!("[Audit] Failed to write audit log: {}", e),
+    }
+}
+
+/// Runs `f` and records the attempt together with its outcome.
+pub fn audited<T>(action: &str, params: serde_json::Value, f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let result = f();
+    record(action, params, &result);
+    result
+}
+
+/// Newest entries first, optionally filtered by action.
+#[tauri::command]
+pub fn get_audit_log(limit: Option<usize>, action: Option<String>) -> Result<Vec<AuditEntry>, MineServerError> {
+    let file = get_audit_file();
+    if !file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read audit log: {}", e))?;
+
+    let entries = content.lines()
+        .rev()
+        .filter_map(|l| serde_json::from_str::<AuditEntry>(l).ok())
+        .filter(|e| action.as_ref().map(|a| &e.action == a).unwrap_or(true))
+        .take(limit.unwrap_or(500))
+        .collect();
+    Ok(entries)
+}
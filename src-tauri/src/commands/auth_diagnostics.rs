@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::Serialize;
+use serde_yaml::Value as YamlValue;
+use super::path_guard::validate_path;
+use super::registry::load_registry;
+use super::server_config::read_server_properties;
+use super::error::MineServerError;
+
+// Checks the settings that decide who authenticates players, which cause most "invalid
+// session", "If you wish to use IP forwarding..." and UUID mismatch reports. A proxy and its
+// backends are linked through the registry: a registered server belongs to a proxy when its
+// server-port is one of the addresses in the proxy's server list.
+//   Velocity   velocity.toml: online-mode, player-info-forwarding-mode, forwarding.secret, [servers]
+//   BungeeCord config.yml (also Waterfall): online_mode, ip_forward, servers.*.address
+//   backends   server.properties online-mode, spigot.yml settings.bungeecord, Paper's velocity
+//              settings (config/paper-global.yml proxies.*, before 1.19 paper.yml settings.*)
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthIssue {
+    pub severity: String, // "error", "warning" or "info"
+    pub code: String,
+    pub server: String,
+    pub message: String,
+    pub fix: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthReport {
+    pub role: String, // "proxy", "backend" or "standalone"
+    pub online_mode: Option<bool>,
+    pub forwarding: Option<String>, // Proxy forwarding mode: "none", "legacy", "bungeeguard" or "modern"
+    pub related: Vec<String>, // Proxies of a backend, or backends of a proxy
+    pub issues: Vec<AuthIssue>,
+}
+
+pub(super) struct ProxyConfig {
+    pub name: String,
+    pub kind: &'static str, // "Velocity" or "BungeeCord"
+    pub port: u16, // Port players connect to
+    online_mode: bool,
+    forwarding: String,
+    secret: Option<String>,
+    pub servers: Vec<(String, u16)>, // Server name in the proxy config and its port
+}
+
+impl ProxyConfig {
+    pub fn forwards_to(&self, port: u16) -> bool {
+        self.servers.iter().any(|(_, p)| *p == port)
+    }
+}
+
+pub(super) struct BackendConfig {
+    pub name: String,
+    online_mode: bool,
+    pub port: u16,
+    local_only: bool, // server-ip binds to loopback, so players can't skip the proxy
+    bungeecord: bool,
+    has_paper_config: bool, // Paper can read Velocity modern forwarding
+    velocity_enabled: bool,
+    velocity_online_mode: Option<bool>,
+    velocity_secret: Option<String>,
+    bungee_online_mode: Option<bool>,
+}
+
+fn issue(severity: &str, code: &str, server: &str, message: String, fix: impl Into<String>) -> AuthIssue {
+    AuthIssue { severity: severity.to_string(), code: code.to_string(), server: server.to_string(), message, fix: fix.into() }
+}
+
+fn read_yaml(file: &Path) -> Option<YamlValue> {
+    serde_yaml::from_str(&fs::read_to_string(file).ok()?).ok()
+}
+
+fn yaml_at<'a>(value: &'a YamlValue, path: &[&str]) -> Option<&'a YamlValue> {
+    path.iter().try_fold(value, |v, key| v.get(*key))
+}
+
+/// Port of a "host:port" address, 25565 when it has none.
+fn address_port(address: &str) -> Option<u16> {
+    match address.rsplit_once(':') {
+        Some((_, port)) => port.trim().parse().ok(),
+        None => Some(25565),
+    }
+}
+
+pub(super) fn read_proxy(root: &Path, name: &str) -> Option<ProxyConfig> {
+    if let Ok(content) = fs::read_to_string(root.join("velocity.toml")) {
+        let doc = content.parse::<toml_edit::DocumentMut>().ok()?;
+        let secret_file = doc.get("forwarding-secret-file").and_then(|i| i.as_str()).unwrap_or("forwarding.secret");
+        // Velocity 1.x kept the secret in the config itself
+        let secret = fs::read_to_string(root.join(secret_file)).ok()
+            .or_else(|| doc.get("forwarding-secret").and_then(|i| i.as_str()).map(str::to_string))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let servers = doc.get("servers").and_then(|i| i.as_table())
+            .map(|servers| servers.iter()
+                .filter(|(key, _)| *key != "try")
+                .filter_map(|(key, value)| Some((key.to_string(), address_port(value.as_str()?)?)))
+                .collect())
+            .unwrap_or_default();
+        return Some(ProxyConfig {
+            name: name.to_string(),
+            kind: "Velocity",
+            port: doc.get("bind").and_then(|i| i.as_str()).and_then(address_port).unwrap_or(25577),
+            online_mode: doc.get("online-mode").and_then(|i| i.as_bool()).unwrap_or(true),
+            forwarding: doc.get("player-info-forwarding-mode").and_then(|i| i.as_str()).unwrap_or("none").to_lowercase(),
+            secret,
+            servers,
+        });
+    }
+
+    let config = read_yaml(&root.join("config.yml"))?;
+    config.get("listeners")?; // Any other plugin-style config.yml
+    let servers = config.get("servers").and_then(|s| s.as_mapping())
+        .map(|servers| servers.iter()
+            .filter_map(|(key, s)| Some((key.as_str()?.to_string(), address_port(s.get("address")?.as_str()?)?)))
+            .collect())
+        .unwrap_or_default();
+    let port = config.get("listeners").and_then(|l| l.get(0)).and_then(|l| l.get("host"))
+        .and_then(|h| h.as_str()).and_then(address_port).unwrap_or(25577);
+    let ip_forward = config.get("ip_forward").and_then(|v| v.as_bool()).unwrap_or(false);
+    Some(ProxyConfig {
+        name: name.to_string(),
+        kind: "BungeeCord",
+        port,
+        online_mode: config.get("online_mode").and_then(|v| v.as_bool()).unwrap_or(true),
+        forwarding: if ip_forward { "legacy" } else { "none" }.to_string(),
+        secret: None,
+        servers,
+    })
+}
+
+pub(super) fn read_backend(root: &Path, name: &str) -> Option<BackendConfig> {
+    let props = read_server_properties(root.to_string_lossy().to_string()).ok().filter(|p| !p.is_empty())?;
+    let server_ip = props.get("server-ip").map(|s| s.trim()).unwrap_or("");
+
+    let spigot = read_yaml(&root.join("spigot.yml"));
+    let paper_global = read_yaml(&root.join("config").join("paper-global.yml"));
+    let paper_legacy = read_yaml(&root.join("paper.yml"));
+    let paper_value = |new_path: &[&str], old_path: &[&str]| {
+        paper_global.as_ref().and_then(|c| yaml_at(c, new_path)).cloned()
+            .or_else(|| paper_legacy.as_ref().and_then(|c| yaml_at(c, old_path)).cloned())
+    };
+
+    Some(BackendConfig {
+        name: name.to_string(),
+        online_mode: props.get("online-mode").map(|v| v.trim() != "false").unwrap_or(true),
+        port: props.get("server-port").and_then(|p| p.trim().parse().ok()).unwrap_or(25565),
+        local_only: server_ip == "127.0.0.1" || server_ip == "localhost",
+        bungeecord: spigot.as_ref().and_then(|s| yaml_at(s, &["settings", "bungeecord"])).and_then(|v| v.as_bool()).unwrap_or(false),
+        has_paper_config: paper_global.is_some() || paper_legacy.is_some(),
+        velocity_enabled: paper_value(&["proxies", "velocity", "enabled"], &["settings", "velocity-support", "enabled"])
+            .and_then(|v| v.as_bool()).unwrap_or(false),
+        velocity_online_mode: paper_value(&["proxies", "velocity", "online-mode"], &["settings", "velocity-support", "online-mode"])
+            .and_then(|v| v.as_bool()),
+        velocity_secret: paper_value(&["proxies", "velocity", "secret"], &["settings", "velocity-support", "secret"])
+            .and_then(|v| v.as_str().map(|s| s.trim().to_string()))
+            .filter(|s| !s.is_empty()),
+        bungee_online_mode: paper_value(&["proxies", "bungee-cord", "online-mode"], &["settings", "bungee-online-mode"])
+            .and_then(|v| v.as_bool()),
+    })
+}
+
+/// Checks one backend against one proxy in front of it.
+fn check_pair(proxy: &ProxyConfig, backend: &BackendConfig, issues: &mut Vec<AuthIssue>) {
+    let (p, b) = (proxy.name.as_str(), backend.name.as_str());
+    if backend.online_mode {
+        issues.push(issue("error", "backend-online-mode", b,
+            format!("{} has online-mode=true behind {}. The proxy already logged players in, so they are kicked with \"invalid session\" or told to enable IP forwarding.", b, p),
+            "Set online-mode=false in server.properties; the proxy authenticates players."));
+    }
+
+    match proxy.forwarding.as_str() {
+        "modern" => {
+            if !backend.has_paper_config {
+                issues.push(issue("error", "modern-unsupported", b,
+                    format!("{} uses Velocity modern forwarding, which {} can't read (it needs Paper 1.13 or newer).", p, b),
+                    "Run Paper on the backend, or switch Velocity to legacy forwarding and set settings.bungeecord: true in spigot.yml."));
+            } else if !backend.velocity_enabled {
+                issues.push(issue("error", "velocity-disabled", b,
+                    format!("{} forwards players with modern forwarding, but {} has Velocity support turned off, so players get offline UUIDs or are kicked.", p, b),
+                    "Set proxies.velocity.enabled: true and the secret from forwarding.secret in config/paper-global.yml (settings.velocity-support in paper.yml before 1.19)."));
+            } else {
+                match (&proxy.secret, &backend.velocity_secret) {
+                    (Some(expected), Some(actual)) if expected != actual => issues.push(issue("error", "secret-mismatch", b,
+                        format!("The Velocity secret of {} doesn't match {}'s forwarding.secret; players are kicked with \"Unable to verify player details\".", b, p),
+                        "Copy the content of the proxy's forwarding.secret into proxies.velocity.secret.")),
+                    (_, None) => issues.push(issue("error", "secret-missing", b,
+                        format!("{} has Velocity support enabled without a secret.", b),
+                        "Copy the content of the proxy's forwarding.secret into proxies.velocity.secret.")),
+                    _ => {},
+                }
+                if backend.velocity_online_mode.is_some_and(|online| online != proxy.online_mode) {
+                    issues.push(issue("warning", "velocity-online-mode", b,
+                        format!("Paper's proxies.velocity.online-mode on {} doesn't match online-mode of {}; plugins may see the wrong UUID type.", b, p),
+                        format!("Set proxies.velocity.online-mode to {}.", proxy.online_mode)));
+                }
+            }
+            if backend.bungeecord {
+                issues.push(issue("warning", "bungeecord-with-modern", b,
+                    format!("{} has settings.bungeecord enabled while {} uses modern forwarding.", b, p),
+                    "Set settings.bungeecord: false in spigot.yml."));
+            }
+        },
+        "legacy" | "bungeeguard" => {
+            if backend.velocity_enabled {
+                issues.push(issue("error", "velocity-with-legacy", b,
+                    format!("{} expects Velocity modern forwarding, but {} sends legacy (BungeeCord) forwarding, so every login is rejected.", b, p),
+                    "Turn proxies.velocity.enabled off, or set player-info-forwarding-mode = \"modern\" on the proxy."));
+            } else if !backend.bungeecord {
+                issues.push(issue("error", "ip-forward-ignored", b,
+                    format!("{} forwards player info, but {} ignores it: players get offline UUIDs (lost inventories, ops and whitelist entries) and all share the proxy's IP.", p, b),
+                    "Set settings.bungeecord: true in spigot.yml."));
+            }
+            if backend.bungeecord && backend.bungee_online_mode.is_some_and(|online| online != proxy.online_mode) {
+                issues.push(issue("warning", "bungee-online-mode", b,
+                    format!("Paper's bungee-cord online-mode on {} doesn't match online_mode of {}; plugins may see the wrong UUID type.", b, p),
+                    format!("Set proxies.bungee-cord.online-mode to {}.", proxy.online_mode)));
+            }
+        },
+        _ => {
+            let fix = if proxy.kind == "Velocity" {
+                "Set player-info-forwarding-mode = \"modern\" in velocity.toml and enable Velocity support on the backends."
+            } else {
+                "Set ip_forward: true in the proxy's config.yml and settings.bungeecord: true in the backends' spigot.yml."
+            };
+            issues.push(issue("warning", "no-forwarding", b,
+                format!("{} doesn't forward player info, so {} sees every player with the proxy's IP and an offline UUID that doesn't match their account.", p, b),
+                fix));
+        },
+    }
+
+    if !backend.online_mode && !backend.local_only {
+        issues.push(issue("info", "backend-reachable", b,
+            format!("{} runs in offline mode and listens on all addresses; anyone who reaches port {} directly can join as any player.", b, backend.port),
+            "Set server-ip=127.0.0.1 when the proxy runs on the same machine, or block the port in the firewall."));
+    }
+}
+
+fn display_name(root: &Path, registered: Option<&str>) -> String {
+    registered.map(str::to_string)
+        .or_else(|| root.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| root.to_string_lossy().to_string())
+}
+
+/// Looks for online-mode and proxy forwarding mistakes on a server and, through the registry,
+/// on the proxies in front of it or the backends behind it.
+#[tauri::command]
+pub fn diagnose_auth(server_path: String) -> Result<AuthReport, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let registry: Vec<(PathBuf, String)> = load_registry()?.into_iter()
+        .filter_map(|s| Some((fs::canonicalize(&s.path).ok()?, s.name)))
+        .collect();
+    let name = display_name(&root, registry.iter().find(|(p, _)| *p == root).map(|(_, n)| n.as_str()));
+    let others = || registry.iter().filter(|(p, _)| *p != root);
+    let mut issues = Vec::new();
+
+    if let Some(proxy) = read_proxy(&root, &name) {
+        let backends: Vec<BackendConfig> = others()
+            .filter(|(p, _)| read_proxy(p, "").is_none())
+            .filter_map(|(p, n)| read_backend(p, n))
+            .filter(|b| proxy.forwards_to(b.port))
+            .collect();
+        if !proxy.online_mode {
+            issues.push(issue("warning", "proxy-offline-mode", &name,
+                format!("{} runs in offline mode: anyone can join with any username, and UUIDs change if you turn it on later.", name),
+                "Set online-mode to true unless you know you need offline mode."));
+        }
+        if backends.is_empty() {
+            issues.push(issue("info", "no-backends", &name,
+                "None of the registered servers listens on a port from the proxy's server list.".to_string(),
+                "Check the server addresses in the proxy config against the backends' server-port."));
+        }
+        for backend in &backends {
+            check_pair(&proxy, backend, &mut issues);
+        }
+        return Ok(AuthReport {
+            role: "proxy".to_string(),
+            online_mode: Some(proxy.online_mode),
+            forwarding: Some(proxy.forwarding.clone()),
+            related: backends.into_iter().map(|b| b.name).collect(),
+            issues,
+        });
+    }
+
+    let backend = read_backend(&root, &name)
+        .ok_or_else(|| MineServerError::validation("No server.properties or proxy config found. Start the server once so it is created."))?;
+    let proxies: Vec<ProxyConfig> = others()
+        .filter_map(|(p, n)| read_proxy(p, n))
+        .filter(|p| p.forwards_to(backend.port))
+        .collect();
+    for proxy in &proxies {
+        check_pair(proxy, &backend, &mut issues);
+    }
+
+    if proxies.is_empty() {
+        let expects_proxy = backend.bungeecord || backend.velocity_enabled;
+        if expects_proxy && !backend.online_mode && !backend.local_only {
+            issues.push(issue("warning", "proxy-expected", &name,
+                format!("{} is set up for a proxy, but no registered proxy forwards to port {}. If players can reach it directly, they can join as anyone.", name, backend.port),
+                "Register the proxy in the app, bind the server to 127.0.0.1 or block the port in the firewall."));
+        } else if !expects_proxy && !backend.online_mode {
+            issues.push(issue("warning", "offline-mode", &name,
+                format!("{} runs in offline mode: anyone can join with any username, and switching to online mode later changes every player's UUID.", name),
+                "Set online-mode=true unless players without a Minecraft account must join."));
+        }
+    }
+
+    Ok(AuthReport {
+        role: if proxies.is_empty() { "standalone" } else { "backend" }.to_string(),
+        online_mode: Some(backend.online_mode),
+        forwarding: None,
+        related: proxies.into_iter().map(|p| p.name).collect(),
+        issues,
+    })
+}
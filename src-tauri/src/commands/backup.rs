@@ -1,9 +1,25 @@
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::io::{Read, Write};
+use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 use zip::write::FileOptions;
 use walkdir::WalkDir;
+use super::archive::{compression_workers, zip_files_parallel, zip_write_error};
+use super::extract::{extract_zip_file, ExtractLimits};
+use super::audit::audited;
+use super::trash::{move_to_trash, TRASH_DIR};
+use super::world_manager::resolve_world_path;
+use super::operations::{Operation, OperationState};
+use super::runner::{ServerEventSink, ServerProcessState, ensure_not_running};
+use tauri::{AppHandle, State};
+use serde_json::json;
+use super::activity::Activity;
+use super::network_manager::assign_server_port;
+use super::registry::{load_registry, register_server, unregister_server, RegisteredServer};
+use super::settings::load_settings;
+use super::json_store::{read_json, update_json, write_json};
+use super::path_guard::{app_data_dir, validate_path};
+use super::error::MineServerError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +31,75 @@ pub struct BackupInfo {
     pub size_bytes: u64,
     pub backup_type: String, // "manual", "auto", "pre-update"
     pub file_path: String,
+    #[serde(default = "default_scope")]
+    pub scope: String, // "full", "worlds", "configs" or "world"
+    #[serde(default)]
+    pub world_name: Option<String>, // Set for "world" backups
+}
+
+fn default_scope() -> String {
+    "full".to_string()
+}
+
+/// A backup that is being written right now.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningBackup {
+    pub server_path: String,
+    pub backup_type: String,
+    pub scope: String,
+    pub started_at: String,
+    pub operation_id: String,
+}
+
+// One backup per server at a time: two zips of the same folder would both be slow and race on
+// the index. Keyed by the canonical server path. Backups of different servers (and deletes)
+// still finish concurrently, so the index is only changed through `update_backups_index`.
+static RUNNING_BACKUPS: Mutex<Vec<(PathBuf, RunningBackup)>> = Mutex::new(Vec::new());
+
+fn backup_key(server_path: &str) -> PathBuf {
+    fs::canonicalize(server_path).unwrap_or_else(|_| PathBuf::from(server_path))
+}
+
+/// Holds a server's backup slot; released when dropped.
+struct BackupSlot {
+    key: PathBuf,
+}
+
+impl BackupSlot {
+    fn claim(backup: RunningBackup) -> Result<Self, String> {
+        let key = backup_key(&backup.server_path);
+        let mut running = RUNNING_BACKUPS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, current)) = running.iter().find(|(k, _)| *k == key) {
+            return Err(format!(
+                "A {} backup of this server is already running (started {}). Wait for it to finish.",
+                current.backup_type, current.started_at
+            ));
+        }
+        running.push((key.clone(), backup));
+        Ok(Self { key })
+    }
+}
+
+impl Drop for BackupSlot {
+    fn drop(&mut self) {
+        RUNNING_BACKUPS.lock().unwrap_or_else(|e| e.into_inner()).retain(|(k, _)| *k != self.key);
+    }
+}
+
+/// Whether a backup of the server is being written, e.g. so the scheduler can skip its run.
+pub fn backup_running(server_path: &str) -> bool {
+    let key = backup_key(server_path);
+    RUNNING_BACKUPS.lock().map(|r| r.iter().any(|(k, _)| *k == key)).unwrap_or(false)
+}
+
+/// Runs `update` on the backups index under the store lock, then saves it. Entries whose zip
+/// is gone are dropped on the way.
+fn update_backups_index<T>(update: impl FnOnce(&mut Vec<BackupInfo>) -> Result<T, String>) -> Result<T, String> {
+    update_json(&get_backups_index_file(), "backups index", |backups: &mut Vec<BackupInfo>| {
+        backups.retain(|b| Path::new(&b.file_path).exists());
+        update(backups)
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,44 +107,164 @@ pub struct BackupInfo {
 pub struct ScheduledTask {
     pub id: String,
     pub name: String,
-    pub task_type: String, // "restart", "backup", "command"
+    pub task_type: String, // "restart", "backup", "command", "announcement", "player_sync", "map_cleanup"
     pub server_id: String,
     pub server_name: String,
     pub server_path: String,
     pub cron_expression: String,
     pub enabled: bool,
     pub last_run: Option<String>,
-    pub command: Option<String>,
+    pub command: Option<String>, // Console command, or the template of an "announcement"
+    #[serde(default)]
+    pub max_runtime_minutes: Option<u64>, // Backups running longer than this are cancelled
+    #[serde(default = "default_skip_if_running")]
+    pub skip_if_running: bool, // Skip a run while the previous one is still executing
+    #[serde(default)]
+    pub sync_group_id: Option<String>, // Group synced by "player_sync" tasks
+    #[serde(default)]
+    pub max_age_days: Option<u32>, // "map_cleanup" deletes tiles older than this (all when None)
 }
 
-fn get_backups_dir() -> PathBuf {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .unwrap_or_else(|_| ".".to_string());
-    Path::new(&home).join("Mineserver").join("Backups")
+fn default_skip_if_running() -> bool {
+    true
+}
+
+pub(crate) fn get_backups_dir() -> PathBuf {
+    app_data_dir().join("Backups")
 }
 
 fn get_tasks_file() -> PathBuf {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .unwrap_or_else(|_| ".".to_string());
-    Path::new(&home).join("Mineserver").join("scheduled_tasks.json")
+    app_data_dir().join("scheduled_tasks.json")
 }
 
 fn get_backups_index_file() -> PathBuf {
     get_backups_dir().join("backups_index.json")
 }
 
+// Backup scopes. Entries are always stored relative to the server folder, so a scoped backup
+// restores in place: "worlds" holds every world folder, "world" one world (plus its Bukkit
+// dimension folders) and "configs" only configuration files.
+
+const CONFIG_EXTENSIONS: &[&str] = &["properties", "yml", "yaml", "json", "json5", "toml", "txt", "conf", "cfg", "ini"];
+const CONFIG_DIRS: &[&str] = &["config", "defaultconfigs", "plugins"];
+const PRE_UPDATE_BACKUP_REUSE: chrono::TimeDelta = chrono::TimeDelta::minutes(10);
+
+/// World folders (relative to the server) covered by a "worlds" or "world" backup.
+fn world_roots(server_dir: &Path, scope: &str, world_name: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    match scope {
+        "full" | "configs" => Ok(vec![]),
+        "worlds" => {
+            let mut roots = Vec::new();
+            if server_dir.join("worlds").is_dir() {
+                roots.push(PathBuf::from("worlds")); // Bedrock keeps all worlds here
+            }
+            for entry in fs::read_dir(server_dir).map_err(|e| e.to_string())?.flatten() {
+                if entry.path().join("level.dat").is_file() {
+                    roots.push(PathBuf::from(entry.file_name()));
+                }
+            }
+            if roots.is_empty() {
+                return Err("No worlds found in this server".to_string());
+            }
+            Ok(roots)
+        },
+        "world" => {
+            let name = world_name.map(str::trim).filter(|n| !n.is_empty())
+                .ok_or("A world name is required for a single-world backup")?;
+            if name.contains(['/', '\\']) || name == ".." {
+                return Err(format!("Invalid world name: {}", name));
+            }
+            let world = resolve_world_path(server_dir, name);
+            if !world.is_dir() {
+                return Err(format!("World {} not found", name));
+            }
+            let mut roots = vec![world.strip_prefix(server_dir).unwrap_or(Path::new(name)).to_path_buf()];
+            // Bukkit keeps the other dimensions of a world in sibling folders
+            for suffix in ["_nether", "_the_end"] {
+                let dimension = format!("{}{}", name, suffix);
+                if server_dir.join(&dimension).is_dir() {
+                    roots.push(PathBuf::from(dimension));
+                }
+            }
+            Ok(roots)
+        },
+        other => Err(format!("Unknown backup scope: {}", other)),
+    }
+}
+
+/// Config files at the top level or under config/, defaultconfigs/ and plugins/.
+fn is_config_file(relative: &Path) -> bool {
+    let is_config_ext = relative.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| CONFIG_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false);
+    if !is_config_ext {
+        return false;
+    }
+    let mut components = relative.components();
+    let first = components.next().map(|c| c.as_os_str().to_string_lossy().to_string()).unwrap_or_default();
+    components.next().is_none() || CONFIG_DIRS.contains(&first.as_str())
+}
+
+fn scope_includes(scope: &str, roots: &[PathBuf], relative: &Path) -> bool {
+    match scope {
+        "full" => true,
+        "configs" => is_config_file(relative),
+        _ => roots.iter().any(|r| relative.starts_with(r)),
+    }
+}
+
+/// Top-level world folders stored in a scoped archive ("worlds/<name>" on Bedrock).
+fn archive_world_roots(zip_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let file = fs::File::open(zip_path).map_err(|e| e.to_string())?;
+    let archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for name in archive.file_names() {
+        let mut parts = name.split('/').filter(|p| !p.is_empty() && *p != "." && *p != "..");
+        let root = match (parts.next(), parts.next()) {
+            (Some("worlds"), Some(world)) => Path::new("worlds").join(world),
+            (Some(first), Some(_)) => PathBuf::from(first),
+            _ => continue, // Top-level files aren't world folders
+        };
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    Ok(roots)
+}
+
 #[tauri::command]
 pub async fn create_backup(
+    app: AppHandle,
+    ops: State<'_, OperationState>,
     server_path: String,
     server_name: String,
     backup_type: String,
+    operation_id: Option<String>,
+    scope: Option<String>,
+    world_name: Option<String>,
+) -> Result<BackupInfo, MineServerError> {
+    create_backup_direct(app, &ops, server_path, server_name, backup_type, operation_id, scope, world_name).await.map_err(Into::into)
+}
+
+/// `create_backup` without Tauri state, for the scheduler (desktop and headless).
+pub async fn create_backup_direct<E: ServerEventSink>(
+    sink: E,
+    ops: &OperationState,
+    server_path: String,
+    server_name: String,
+    backup_type: String,
+    operation_id: Option<String>,
+    scope: Option<String>,
+    world_name: Option<String>,
 ) -> Result<BackupInfo, String> {
-    let server_dir = Path::new(&server_path);
+    let server_dir = PathBuf::from(&server_path);
     if !server_dir.exists() {
         return Err("Server path does not exist".to_string());
     }
+    let scope = scope.unwrap_or_else(default_scope);
+    let roots = world_roots(&server_dir, &scope, world_name.as_deref())?;
+    let world_name = world_name.filter(|_| scope == "world");
 
     let backups_dir = get_backups_dir();
     fs::create_dir_all(&backups_dir)
@@ -68,42 +273,39 @@ pub async fn create_backup(
     // Generate backup filename
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
     let safe_name = server_name.replace(" ", "_").replace("/", "_").replace("\\", "_");
-    let backup_filename = format!("{}_{}.zip", safe_name, timestamp);
+    let backup_filename = match (scope.as_str(), &world_name) {
+        ("full", _) => format!("{}_{}.zip", safe_name, timestamp),
+        ("world", Some(world)) => format!("{}_world-{}_{}.zip", safe_name, world.replace(' ', "_"), timestamp),
+        (scope, _) => format!("{}_{}_{}.zip", safe_name, scope, timestamp),
+    };
     let backup_path = backups_dir.join(&backup_filename);
 
-    // Create zip file
-    let file = fs::File::create(&backup_path)
-        .map_err(|e| format!("Failed to create backup file: {}", e))?;
-    let mut zip = zip::ZipWriter::new(file);
-    let options = FileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated);
-
-    // Add all files from server directory
-    for entry in WalkDir::new(server_dir).into_iter().filter_map(|e| e.ok()) {
-        let path = entry.path();
-        let relative_path = path.strip_prefix(server_dir).unwrap();
-
-        if path.is_file() {
-            let relative_str = relative_path.to_string_lossy().replace("\\", "/");
-            zip.start_file(&relative_str, options.clone())
-                .map_err(|e| format!("Failed to add file to zip: {}", e))?;
-
-            let mut file = fs::File::open(path)
-                .map_err(|e| format!("Failed to open file: {}", e))?;
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)
-                .map_err(|e| format!("Failed to read file: {}", e))?;
-            zip.write_all(&buffer)
-                .map_err(|e| format!("Failed to write to zip: {}", e))?;
-        } else if path.is_dir() && relative_path.to_string_lossy() != "" {
-            let relative_str = format!("{}/", relative_path.to_string_lossy().replace("\\", "/"));
-            zip.add_directory(&relative_str, options.clone())
-                .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
-        }
+    // Walking and compressing the server folder takes minutes on big worlds
+    let operation_id = operation_id.filter(|i| !i.trim().is_empty()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let slot = BackupSlot::claim(RunningBackup {
+        server_path: server_path.clone(),
+        backup_type: backup_type.clone(),
+        scope: scope.clone(),
+        started_at: chrono::Local::now().to_rfc3339(),
+        operation_id: operation_id.clone(),
+    })?;
+    let operation = ops.begin(Some(operation_id), "backup")?.with_events(sink);
+    let activity = Activity::begin("backup", &server_path, format!("{} backup ({})", backup_type, scope));
+    let zip_path = backup_path.clone();
+    let zip_scope = scope.clone();
+    let (operation, written) = tokio::task::spawn_blocking(move || {
+        let include = |relative: &Path| scope_includes(&zip_scope, &roots, relative);
+        let written = write_backup_zip(&server_dir, &zip_path, &operation, include);
+        (operation, written)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    if let Err(e) = written {
+        let _ = fs::remove_file(&backup_path); // Never leave a half-written backup behind
+        activity.fail(&e);
+        return Err(e);
     }
 
-    zip.finish().map_err(|e| format!("Failed to finish zip: {}", e))?;
-
     // Get file size
     let metadata = fs::metadata(&backup_path)
         .map_err(|e| format!("Failed to get backup size: {}", e))?;
@@ -116,31 +318,101 @@ pub async fn create_backup(
         size_bytes: metadata.len(),
         backup_type,
         file_path: backup_path.to_string_lossy().to_string(),
+        scope,
+        world_name,
     };
 
-    // Update index
-    let mut backups = list_backups_internal()?;
-    backups.insert(0, backup_info.clone());
-    save_backups_index(&backups)?;
+    update_backups_index(|backups| {
+        backups.insert(0, backup_info.clone());
+        Ok(())
+    })?;
+    drop(slot);
 
+    activity.complete(Some(backup_info.size_bytes));
+    operation.complete();
     Ok(backup_info)
 }
 
-#[tauri::command]
-pub async fn list_backups() -> Result<Vec<BackupInfo>, String> {
-    list_backups_internal()
+/// Backup taken before an update changes a server, unless turned off in the settings. A
+/// pre-update backup of the same scope from the last few minutes is reused, so updating several
+/// plugins in a row costs one backup. Returns the backup id to link in the activity log.
+pub async fn pre_update_backup<E: ServerEventSink>(sink: E, ops: &OperationState, server_path: &str, scope: &str) -> Result<Option<String>, String> {
+    if !load_settings().pre_update_backup {
+        return Ok(None);
+    }
+    let recent = list_backups_internal()?.into_iter().find(|b| {
+        b.server_path == server_path && b.backup_type == "pre-update" && b.scope == scope
+            && chrono::DateTime::parse_from_rfc3339(&b.created_at)
+                .map(|t| chrono::Local::now().signed_duration_since(t) < PRE_UPDATE_BACKUP_REUSE)
+                .unwrap_or(false)
+    });
+    if let Some(backup) = recent {
+        return Ok(Some(backup.id));
+    }
+
+    let server_name = load_registry().ok()
+        .and_then(|servers| servers.into_iter().find(|s| s.path == server_path).map(|s| s.name))
+        .or_else(|| Path::new(server_path).file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "server".to_string());
+    create_backup_direct(sink, ops, server_path.to_string(), server_name, "pre-update".into(), None, Some(scope.to_string()), None)
+        .await
+        .map(|info| Some(info.id))
+        .map_err(|e| format!("Pre-update backup failed: {}", e))
 }
 
-fn list_backups_internal() -> Result<Vec<BackupInfo>, String> {
-    let index_file = get_backups_index_file();
-    if !index_file.exists() {
-        return Ok(vec![]);
+fn write_backup_zip(server_dir: &Path, backup_path: &Path, op: &Operation, include: impl Fn(&Path) -> bool) -> Result<(), String> {
+    let file = fs::File::create(backup_path)
+        .map_err(|e| format!("Failed to create backup file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    // Add all files from server directory: directories right away, files compressed in parallel
+    let mut files = Vec::new();
+    for entry in WalkDir::new(server_dir).into_iter()
+        .filter_entry(|e| e.file_name() != TRASH_DIR)
+        .filter_map(|e| e.ok())
+    {
+        op.token.check()?;
+        let path = entry.path();
+        let relative_path = path.strip_prefix(server_dir).unwrap();
+        if !include(relative_path) {
+            continue;
+        }
+
+        if path.is_file() {
+            let relative_str = relative_path.to_string_lossy().replace("\\", "/");
+            files.push((relative_str, path.to_path_buf()));
+        } else if path.is_dir() && relative_path.to_string_lossy() != "" {
+            let relative_str = format!("{}/", relative_path.to_string_lossy().replace("\\", "/"));
+            zip.add_directory(&relative_str, options.clone())
+                .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
+        }
     }
+    let total_bytes: u64 = files.iter().map(|(_, p)| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).sum();
+    zip_files_parallel(&mut zip, files, options, compression_workers(), &op.token, |done, name| {
+        let percentage = if total_bytes == 0 { 100 } else { ((done as f64 / total_bytes as f64) * 100.0) as u8 };
+        op.progress(percentage.min(99), done, format!("Backing up: {}", name));
+    })?;
 
-    let content = fs::read_to_string(&index_file)
-        .map_err(|e| format!("Failed to read backups index: {}", e))?;
-    let backups: Vec<BackupInfo> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse backups index: {}", e))?;
+    zip.finish().map_err(|e| zip_write_error("Failed to finish zip", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_backups() -> Result<Vec<BackupInfo>, MineServerError> {
+    list_backups_internal().map_err(Into::into)
+}
+
+/// Backups in progress, manual and scheduled.
+#[tauri::command]
+pub fn list_running_backups() -> Result<Vec<RunningBackup>, MineServerError> {
+    let running = RUNNING_BACKUPS.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(running.iter().map(|(_, backup)| backup.clone()).collect())
+}
+
+fn list_backups_internal() -> Result<Vec<BackupInfo>, String> {
+    let backups: Vec<BackupInfo> = read_json(&get_backups_index_file(), "backups index")?.unwrap_or_default();
 
     // Filter out backups that no longer exist
     let valid_backups: Vec<BackupInfo> = backups
@@ -151,78 +423,205 @@ fn list_backups_internal() -> Result<Vec<BackupInfo>, String> {
     Ok(valid_backups)
 }
 
-fn save_backups_index(backups: &Vec<BackupInfo>) -> Result<(), String> {
-    let index_file = get_backups_index_file();
-    fs::create_dir_all(index_file.parent().unwrap())
-        .map_err(|e| format!("Failed to create directory: {}", e))?;
-    let content = serde_json::to_string_pretty(backups)
-        .map_err(|e| format!("Failed to serialize backups: {}", e))?;
-    fs::write(&index_file, content)
-        .map_err(|e| format!("Failed to write backups index: {}", e))?;
-    Ok(())
+/// Creation time of the newest backup recorded for a server directory.
+pub fn latest_backup_time(server_path: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    list_backups_internal().ok()?
+        .iter()
+        .filter(|b| b.server_path == server_path)
+        .filter_map(|b| chrono::DateTime::parse_from_rfc3339(&b.created_at).ok())
+        .max()
 }
 
 #[tauri::command]
-pub async fn delete_backup(backup_id: String) -> Result<(), String> {
-    let mut backups = list_backups_internal()?;
-    
-    if let Some(pos) = backups.iter().position(|b| b.id == backup_id) {
-        let backup = &backups[pos];
-        if Path::new(&backup.file_path).exists() {
-            fs::remove_file(&backup.file_path)
-                .map_err(|e| format!("Failed to delete backup file: {}", e))?;
-        }
-        backups.remove(pos);
-        save_backups_index(&backups)?;
-    }
+pub async fn delete_backup(backup_id: String) -> Result<(), MineServerError> {
+    audited("delete_backup", json!({ "backupId": backup_id }), || {
+        update_backups_index(|backups| {
+            if let Some(pos) = backups.iter().position(|b| b.id == backup_id) {
+                let backup = &backups[pos];
+                if Path::new(&backup.file_path).exists() {
+                    fs::remove_file(&backup.file_path)
+                        .map_err(|e| format!("Failed to delete backup file: {}", e))?;
+                }
+                backups.remove(pos);
+            }
+            Ok(())
+        })
+    }).map_err(Into::into)
+}
 
-    Ok(())
+/// Limits for restoring a backup: the total size limit is skipped for an archive the app wrote
+/// itself, i.e. one whose file still has the size recorded in the index. The file size is a weak
+/// identity check, so the per-entry ratio cap stays on regardless.
+fn restore_limits(backup: &BackupInfo) -> ExtractLimits {
+    match fs::metadata(&backup.file_path) {
+        Ok(meta) if meta.len() == backup.size_bytes => ExtractLimits::own_archive(),
+        _ => ExtractLimits::from_settings(),
+    }
 }
 
 #[tauri::command]
-pub async fn restore_backup(backup_id: String, target_path: String) -> Result<(), String> {
-    let backups = list_backups_internal()?;
-    let backup = backups.iter().find(|b| b.id == backup_id)
-        .ok_or("Backup not found")?;
+pub async fn restore_backup(state: State<'_, ServerProcessState>, backup_id: String, target_path: String) -> Result<(), MineServerError> {
+    let target = validate_path(&target_path)?;
+    ensure_not_running(state.inner(), &target)?;
+    let mut activity = Activity::begin("restore", &target_path, "Restore backup");
+    let result = tokio::task::spawn_blocking(move || {
+        audited("restore_backup", json!({ "backupId": backup_id, "targetPath": target_path }), || {
+            let backups = list_backups_internal()?;
+            let backup = backups.iter().find(|b| b.id == backup_id)
+                .ok_or("Backup not found")?;
+
+            // World snapshots replace the worlds they contain, so chunks created since the
+            // backup don't survive the restore. The current copies go to the trash.
+            if backup.scope == "worlds" || backup.scope == "world" {
+                for root in archive_world_roots(Path::new(&backup.file_path))? {
+                    let existing = target.join(&root);
+                    if existing.exists() {
+                        move_to_trash(&existing, Some(&target))?;
+                    }
+                }
+            }
+
+            extract_zip_file(Path::new(&backup.file_path), &target, &restore_limits(backup))
+                .map_err(|e| format!("Failed to extract backup: {}", e))?;
+
+            Ok(format!("Restore {} backup from {}", backup.server_name, backup.created_at))
+        })
+    })
+    .await?;
+    match &result {
+        Ok(title) => {
+            activity.set_title(title.clone());
+            activity.complete(None);
+        },
+        Err(e) => activity.fail(e),
+    }
+    result.map(|_| ()).map_err(Into::into)
+}
 
-    let backup_file = fs::File::open(&backup.file_path)
-        .map_err(|e| format!("Failed to open backup: {}", e))?;
-    let mut archive = zip::ZipArchive::new(backup_file)
-        .map_err(|e| format!("Failed to read backup archive: {}", e))?;
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoredServer {
+    pub server: RegisteredServer,
+    pub port: u16,
+}
 
-    archive.extract(&target_path)
-        .map_err(|e| format!("Failed to extract backup: {}", e))?;
+/// Folder for a copy named `name`, next to the original server.
+fn new_server_dir(original: &Path, name: &str) -> Result<PathBuf, String> {
+    let slug: String = name.trim().to_lowercase().chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        return Err("The name needs at least one letter or digit".to_string());
+    }
+    let parent = original.parent().ok_or("The backup has no server folder")?;
+    let dir = parent.join(slug);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    Ok(dir)
+}
 
-    Ok(())
+/// Restores a full backup into a new server folder and registers it as a separate server, with
+/// the original's launch settings and a free port. The original server is not touched.
+#[tauri::command]
+pub async fn restore_backup_as_new(state: State<'_, ServerProcessState>, backup_id: String, new_name: String) -> Result<RestoredServer, MineServerError> {
+    if new_name.trim().is_empty() {
+        return Err(MineServerError::validation("Server name is required"));
+    }
+    let backup = list_backups_internal()?
+        .into_iter()
+        .find(|b| b.id == backup_id)
+        .ok_or_else(|| MineServerError::not_found("Backup not found"))?;
+    if backup.scope != "full" {
+        return Err(MineServerError::validation("Only full backups contain a complete server"));
+    }
+    let target = new_server_dir(Path::new(&backup.server_path), &new_name).map_err(MineServerError::validation)?;
+
+    let target_path = target.to_string_lossy().to_string();
+    let activity = Activity::begin("restore", &target_path, format!("Restore {} backup from {} as {}", backup.server_name, backup.created_at, new_name.trim()));
+    let extract_target = target.clone();
+    let limits = restore_limits(&backup);
+    let result = tokio::task::spawn_blocking(move || {
+        audited("restore_backup_as_new", json!({ "backupId": backup.id, "targetPath": extract_target }), || {
+            fs::create_dir_all(&extract_target).map_err(|e| format!("Failed to create server folder: {}", e))?;
+            extract_zip_file(Path::new(&backup.file_path), &extract_target, &limits)
+                .map_err(|e| format!("Failed to extract backup: {}", e))
+        })
+    })
+    .await?;
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&target);
+        activity.fail(&e);
+        return Err(e.into());
+    }
+
+    // Launch settings come from the original; without it, the usual defaults of a new server
+    let original = load_registry()?.into_iter().find(|s| s.path == backup.server_path);
+    let server = RegisteredServer {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: new_name.trim().to_string(),
+        path: target_path.clone(),
+        server_type: original.as_ref().map(|s| s.server_type.clone()).unwrap_or_default(),
+        version: original.as_ref().map(|s| s.version.clone()).unwrap_or_default(),
+        jar_file: original.as_ref().map(|s| s.jar_file.clone()).unwrap_or_else(|| "server.jar".to_string()),
+        ram: original.as_ref().map(|s| s.ram).unwrap_or(4096),
+        java_path: original.as_ref().and_then(|s| s.java_path.clone()),
+        startup_flags: original.as_ref().and_then(|s| s.startup_flags.clone()),
+        auto_restart: original.as_ref().map(|s| s.auto_restart).unwrap_or(false),
+        auto_start: false,
+        auto_network_setup: false,
+        tunnel_autostart: false,
+        gc_logging: original.as_ref().map(|s| s.gc_logging).unwrap_or(false),
+        network: Default::default(),
+        notes: original.as_ref().map(|s| s.notes.clone()).unwrap_or_default(),
+        changelog: Vec::new(),
+    };
+    // Registered first: the port lookup only accepts known server folders
+    let registered = register_server(state.clone(), server.clone());
+    let is_registered = registered.is_ok();
+    let port = registered.and_then(|_| assign_server_port(target_path, server.server_type == "bedrock", None));
+    let port = match port {
+        Ok(port) => port,
+        Err(e) => {
+            // Leave nothing behind that looks like a working copy
+            if is_registered {
+                let _ = unregister_server(state, server.id.clone());
+            }
+            let _ = fs::remove_dir_all(&target);
+            activity.fail(&e);
+            return Err(e);
+        },
+    };
+    activity.complete(None);
+    Ok(RestoredServer { server, port })
 }
 
 // Scheduled Tasks
 
 #[tauri::command]
-pub async fn save_scheduled_tasks(tasks: Vec<ScheduledTask>) -> Result<(), String> {
-    let tasks_file = get_tasks_file();
-    fs::create_dir_all(tasks_file.parent().unwrap())
-        .map_err(|e| format!("Failed to create directory: {}", e))?;
-    let content = serde_json::to_string_pretty(&tasks)
-        .map_err(|e| format!("Failed to serialize tasks: {}", e))?;
-    fs::write(&tasks_file, content)
-        .map_err(|e| format!("Failed to write tasks: {}", e))?;
-    Ok(())
+pub async fn save_scheduled_tasks(tasks: Vec<ScheduledTask>) -> Result<(), MineServerError> {
+    write_json(&get_tasks_file(), &tasks, "scheduled tasks").map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn load_scheduled_tasks() -> Result<Vec<ScheduledTask>, String> {
-    load_scheduled_tasks_sync()
+pub async fn load_scheduled_tasks() -> Result<Vec<ScheduledTask>, MineServerError> {
+    load_scheduled_tasks_sync().map_err(Into::into)
 }
 
 pub fn load_scheduled_tasks_sync() -> Result<Vec<ScheduledTask>, String> {
-    let tasks_file = get_tasks_file();
-    if !tasks_file.exists() {
-        return Ok(vec![]);
-    }
-    let content = fs::read_to_string(&tasks_file)
-        .map_err(|e| format!("Failed to read tasks: {}", e))?;
-    let tasks: Vec<ScheduledTask> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse tasks: {}", e))?;
-    Ok(tasks)
+    Ok(read_json(&get_tasks_file(), "scheduled tasks")?.unwrap_or_default())
+}
+
+/// Stores when tasks (id, RFC 3339 time) last ran without touching anything else, so edits
+/// saved from the UI meanwhile are kept.
+pub fn record_task_runs(runs: &[(String, String)]) -> Result<(), String> {
+    update_json(&get_tasks_file(), "scheduled tasks", |tasks: &mut Vec<ScheduledTask>| {
+        for task in tasks.iter_mut() {
+            if let Some((_, at)) = runs.iter().find(|(id, _)| *id == task.id) {
+                task.last_run = Some(at.clone());
+            }
+        }
+        Ok(())
+    })
 }
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use super::http::HttpState;
+use super::path_guard::{app_data_dir, validate_path};
+use super::runner::{ServerProcessState, send_server_command_direct};
+use super::error::MineServerError;
+
+// Bedrock Dedicated Server keeps access control in allowlist.json (gamertags, optional XUID)
+// and permissions.json (XUID -> operator/member/visitor). permissions.json only understands
+// XUIDs, so gamertags are resolved from join lines seen in the console
+// ("Player connected: Steve, xuid: 2535...") and, failing that, from an online lookup.
+
+static XUID_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowlistEntry {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xuid: Option<String>,
+    #[serde(default)]
+    pub ignores_player_limit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionEntry {
+    pub permission: String, // "operator", "member" or "visitor"
+    pub xuid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockPermission {
+    pub xuid: String,
+    pub permission: String,
+    #[serde(default)]
+    pub name: Option<String>, // Filled from known XUIDs when reading; optional when writing
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KnownPlayer {
+    name: String,
+    xuid: String,
+}
+
+fn get_xuid_file() -> PathBuf {
+    app_data_dir().join("xuids.json")
+}
+
+fn load_known_players() -> HashMap<String, KnownPlayer> {
+    fs::read_to_string(get_xuid_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// XUID from a Bedrock join line, e.g. "Player connected: Steve, xuid: 2535416173...".
+pub fn parse_xuid(line: &str) -> Option<String> {
+    let idx = line.find("xuid: ")?;
+    let xuid: String = line[idx + "xuid: ".len()..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    if xuid.is_empty() { None } else { Some(xuid) }
+}
+
+/// Remembers a gamertag/XUID pair seen in console output.
+pub fn remember_xuid(name: &str, xuid: &str) {
+    let _guard = XUID_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut known = load_known_players();
+    let key = name.to_lowercase();
+    if known.get(&key).map(|p| p.xuid == xuid).unwrap_or(false) {
+        return;
+    }
+    known.insert(key, KnownPlayer { name: name.to_string(), xuid: xuid.to_string() });
+
+    let file = get_xuid_file();
+    let _ = fs::create_dir_all(file.parent().unwrap());
+    if let Ok(content) = serde_json::to_string_pretty(&known) {
+        let _ = fs::write(&file, content);
+    }
+}
+
+fn read_json_list<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Vec<T>, String> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if content.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn write_json_list<T: Serialize>(path: &Path, items: &[T]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(items)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    fs::write(path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Tells a running server to pick up the new file. Not running is fine; BDS reads it on start.
+fn reload_if_running(state: &ServerProcessState, server_path: &Path, command: &str) {
+    let id = state.configs.lock().ok().and_then(|configs| {
+        configs.iter()
+            .find(|(_, cfg)| fs::canonicalize(&cfg.path).map(|p| p == server_path).unwrap_or(false))
+            .map(|(id, _)| id.clone())
+    });
+    if let Some(id) = id {
+        let _ = send_server_command_direct(state, id, command.to_string());
+    }
+}
+
+#[tauri::command]
+pub fn get_bedrock_allowlist(server_path: String) -> Result<Vec<AllowlistEntry>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    Ok(read_json_list(&root.join("allowlist.json"))?)
+}
+
+/// Replaces allowlist.json. Missing XUIDs are filled in from known players.
+#[tauri::command]
+pub fn set_bedrock_allowlist(state: State<'_, ServerProcessState>, server_path: String, entries: Vec<AllowlistEntry>) -> Result<Vec<AllowlistEntry>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let known = load_known_players();
+
+    let mut seen = std::collections::HashSet::new();
+    let entries: Vec<AllowlistEntry> = entries.into_iter()
+        .filter(|e| !e.name.trim().is_empty() && seen.insert(e.name.to_lowercase()))
+        .map(|mut e| {
+            e.name = e.name.trim().to_string();
+            if e.xuid.is_none() {
+                e.xuid = known.get(&e.name.to_lowercase()).map(|p| p.xuid.clone());
+            }
+            e
+        })
+        .collect();
+
+    write_json_list(&root.join("allowlist.json"), &entries)?;
+    reload_if_running(&state, &root, "allowlist reload");
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn get_bedrock_permissions(server_path: String) -> Result<Vec<BedrockPermission>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let entries: Vec<PermissionEntry> = read_json_list(&root.join("permissions.json"))?;
+    let names: HashMap<String, String> = load_known_players()
+        .into_values()
+        .map(|p| (p.xuid, p.name))
+        .collect();
+
+    Ok(entries.into_iter().map(|e| BedrockPermission {
+        name: names.get(&e.xuid).cloned(),
+        xuid: e.xuid,
+        permission: e.permission,
+    }).collect())
+}
+
+/// Replaces permissions.json. Every entry needs an XUID (see `resolve_xuid`).
+#[tauri::command]
+pub fn set_bedrock_permissions(state: State<'_, ServerProcessState>, server_path: String, entries: Vec<BedrockPermission>) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+
+    let mut out: Vec<PermissionEntry> = Vec::new();
+    for entry in entries {
+        if !matches!(entry.permission.as_str(), "operator" | "member" | "visitor") {
+            return Err(MineServerError::validation(format!("Invalid permission level: {}", entry.permission)));
+        }
+        if entry.xuid.is_empty() || !entry.xuid.chars().all(|c| c.is_ascii_digit()) {
+            return Err(MineServerError::validation(format!("Invalid XUID for {}", entry.name.unwrap_or(entry.xuid))));
+        }
+        out.retain(|e| e.xuid != entry.xuid);
+        out.push(PermissionEntry { permission: entry.permission, xuid: entry.xuid });
+    }
+
+    write_json_list(&root.join("permissions.json"), &out)?;
+    reload_if_running(&state, &root, "permission reload");
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct GeyserXuidResponse {
+    xuid: Option<serde_json::Value>,
+}
+
+/// XUID for a gamertag: players seen on any local Bedrock server first, then GeyserMC's
+/// global API (which knows players that joined a Geyser server). None if neither knows it.
+#[tauri::command]
+pub async fn resolve_xuid(http: State<'_, HttpState>, gamertag: String) -> Result<Option<String>, MineServerError> {
+    let gamertag = gamertag.trim().to_string();
+    if let Some(player) = load_known_players().get(&gamertag.to_lowercase()) {
+        return Ok(Some(player.xuid.clone()));
+    }
+
+    let url = format!("https://api.geysermc.org/v2/xbox/xuid/{}", urlencoding::encode(&gamertag));
+    let resp = http.client()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("XUID lookup failed: {}", e))?;
+    if !resp.status().is_success() {
+        return Ok(None);
+    }
+    let body: GeyserXuidResponse = resp.json()
+        .await
+        .map_err(|e| format!("Invalid XUID lookup response: {}", e))?;
+
+    let xuid = body.xuid.map(|v| match v {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    });
+    if let Some(xuid) = &xuid {
+        remember_xuid(&gamertag, xuid);
+    }
+    Ok(xuid)
+}
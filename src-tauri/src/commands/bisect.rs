@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use super::registry::{find_registered_server, RegisteredServer};
+use super::runner::{ServerProcessState, start_server_direct, stop_server_direct};
+use super::safe_mode::{enabled_addons, safe_mode_active, set_addon_enabled};
+use super::error::MineServerError;
+
+// Plugin/mod bisection: finds the addon behind a crash or bug by binary search. Each round keeps
+// half of the remaining suspects enabled, restarts the server and asks the user whether the
+// issue is still there. Addons cleared in earlier rounds stay enabled. Progress is kept in
+// `<server>/.mineserver-bisect.json` and every addon is re-enabled when the search ends.
+//
+// An addon whose dependency is disabled fails to load on its own, which can look like the issue;
+// judge each round by the original symptom.
+
+const BISECT_FILE: &str = ".mineserver-bisect.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectState {
+    pub server_id: String,
+    pub started_at: String,
+    pub round: u32,
+    pub addons: Vec<String>, // Every addon that was enabled when the search started ("plugins/Foo.jar")
+    pub suspects: Vec<String>, // Addons that may still cause the issue
+    pub testing: Vec<String>, // Suspects enabled this round; the other suspects are disabled
+    #[serde(default)]
+    pub culprit: Option<String>, // Set once the search is over
+}
+
+impl BisectState {
+    fn next_round(&mut self) {
+        self.round += 1;
+        self.testing = self.suspects[..self.suspects.len().div_ceil(2)].to_vec();
+    }
+
+    /// Suspects stay enabled while they are tested, cleared addons always.
+    fn is_enabled(&self, addon: &str) -> bool {
+        !self.suspects.iter().any(|a| a == addon) || self.testing.iter().any(|a| a == addon)
+    }
+}
+
+fn bisect_file(root: &Path) -> PathBuf {
+    root.join(BISECT_FILE)
+}
+
+fn read_state(root: &Path) -> Option<BisectState> {
+    fs::read_to_string(bisect_file(root)).ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+fn write_state(root: &Path, state: &BisectState) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize bisect state: {}", e))?;
+    fs::write(bisect_file(root), content)
+        .map_err(|e| format!("Failed to save bisect state: {}", e))
+}
+
+pub(crate) fn bisect_active(root: &Path) -> bool {
+    bisect_file(root).exists()
+}
+
+fn registered(id: &str) -> Result<(RegisteredServer, PathBuf), MineServerError> {
+    let server = find_registered_server(id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))?;
+    let root = fs::canonicalize(&server.path)
+        .map_err(|e| MineServerError::not_found(format!("Server folder not found: {}", e)))?;
+    Ok((server, root))
+}
+
+/// Stops the server if it runs, applies the round's enabled set and, unless `finished`, starts it.
+/// Jars can't be renamed on Windows while the server has them open, hence the stop first.
+async fn apply(app: AppHandle, processes: &ServerProcessState, server: RegisteredServer, root: PathBuf, state: BisectState, finished: bool) -> Result<(), MineServerError> {
+    let processes = processes.clone();
+    tokio::task::spawn_blocking(move || {
+        let running = processes.processes.lock().map(|p| p.contains_key(&server.id)).unwrap_or(false);
+        if running {
+            stop_server_direct(&processes, server.id.clone())?;
+        }
+        for addon in &state.addons {
+            set_addon_enabled(&root, addon, finished || state.is_enabled(addon))?;
+        }
+        if finished {
+            return fs::remove_file(bisect_file(&root)).map_err(|e| format!("Failed to clear bisect state: {}", e).into());
+        }
+        write_state(&root, &state)?;
+
+        let cfg = server.launch_config();
+        start_server_direct(app, &processes, cfg.id, cfg.path, cfg.jar_file, cfg.ram, cfg.java_path, cfg.startup_flags, Some(false))
+            .map(|_| ())
+    })
+    .await?
+}
+
+#[tauri::command]
+pub fn get_bisect_status(id: String) -> Result<Option<BisectState>, MineServerError> {
+    let (_, root) = registered(&id)?;
+    Ok(read_state(&root))
+}
+
+/// Starts a search over the enabled plugins and mods and restarts the server with the first half.
+/// Auto-restart is off while searching so a crash stays visible.
+#[tauri::command]
+pub async fn bisect_plugins(app: AppHandle, processes: State<'_, ServerProcessState>, id: String) -> Result<BisectState, MineServerError> {
+    let (server, root) = registered(&id)?;
+    if bisect_active(&root) {
+        return Err(MineServerError::conflict("A plugin search is already running for this server"));
+    }
+    if safe_mode_active(&root) {
+        return Err(MineServerError::conflict("Restore the server from safe mode first"));
+    }
+    let addons = enabled_addons(&root);
+    if addons.len() < 2 {
+        return Err(MineServerError::validation("At least two enabled plugins or mods are needed to search"));
+    }
+
+    let mut state = BisectState {
+        server_id: id.clone(),
+        started_at: chrono::Local::now().to_rfc3339(),
+        round: 0,
+        suspects: addons.clone(),
+        addons,
+        testing: vec![],
+        culprit: None,
+    };
+    state.next_round();
+    apply(app, processes.inner(), server, root, state.clone(), false).await?;
+    Ok(state)
+}
+
+/// Answers the current round. With one suspect left the search ends, every addon is re-enabled
+/// and the server stays stopped; `culprit` names the addon.
+#[tauri::command]
+pub async fn report_bisect_result(app: AppHandle, processes: State<'_, ServerProcessState>, id: String, issue_persists: bool) -> Result<BisectState, MineServerError> {
+    let (server, root) = registered(&id)?;
+    let mut state = read_state(&root)
+        .ok_or_else(|| MineServerError::not_found("No plugin search is running for this server"))?;
+
+    state.suspects = if issue_persists {
+        state.testing.clone()
+    } else {
+        state.suspects.iter().filter(|a| !state.testing.contains(a)).cloned().collect()
+    };
+    let finished = state.suspects.len() <= 1;
+    if finished {
+        state.culprit = state.suspects.first().cloned();
+        state.testing.clear();
+    } else {
+        state.next_round();
+    }
+    apply(app, processes.inner(), server, root, state.clone(), finished).await?;
+    Ok(state)
+}
+
+/// Ends the search early and re-enables every addon it disabled. The server is left stopped.
+#[tauri::command]
+pub async fn cancel_bisect(app: AppHandle, processes: State<'_, ServerProcessState>, id: String) -> Result<(), MineServerError> {
+    let (server, root) = registered(&id)?;
+    let state = read_state(&root)
+        .ok_or_else(|| MineServerError::not_found("No plugin search is running for this server"))?;
+    apply(app, processes.inner(), server, root, state, true).await
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::fs;
+use std::time::Duration;
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, State};
+use super::eula::accept_eula;
+use super::http::HttpState;
+use super::network_manager::assign_server_port;
+use super::registry::{find_registered_server, RegisteredServer};
+use super::runner::{ServerProcessState, console_output_since, ensure_not_running, is_ready_line, start_server_direct, stop_server_direct};
+use super::server_config::{read_server_properties, write_server_properties};
+use super::error::MineServerError;
+
+// First-run setup in one call for the setup wizard: EULA, basic server.properties, the owner as
+// op and on the whitelist, a free port, and optionally one start to generate the spawn area.
+// Every step is reported (and emitted as "server-bootstrap") so the wizard can show a checklist;
+// a failed step doesn't stop the others, except that nothing is started without the EULA.
+
+const GAMEMODES: [&str; 4] = ["survival", "creative", "adventure", "spectator"];
+const PREGEN_TIMEOUT: Duration = Duration::from_secs(600);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapOptions {
+    #[serde(default)]
+    pub accept_eula: bool, // The user ticked the EULA box in the wizard
+    pub motd: Option<String>,
+    pub max_players: Option<u32>,
+    pub gamemode: Option<String>,
+    pub owner_username: Option<String>,
+    #[serde(default)]
+    pub whitelist: bool, // Turn the whitelist on (the owner is added either way)
+    pub preferred_port: Option<u16>,
+    #[serde(default)]
+    pub pregenerate_spawn: bool, // Start the server once so the spawn chunks exist, then stop it
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapStep {
+    pub server_id: String,
+    pub step: String, // "eula", "properties", "owner", "port" or "pregenerate"
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapReport {
+    pub port: Option<u16>,
+    pub steps: Vec<BootstrapStep>,
+}
+
+impl BootstrapReport {
+    fn record(&mut self, app: &AppHandle, server_id: &str, step: &str, result: Result<String, String>) -> bool {
+        let success = result.is_ok();
+        let step = BootstrapStep {
+            server_id: server_id.to_string(),
+            step: step.to_string(),
+            success,
+            message: result.unwrap_or_else(|e| e),
+        };
+        let _ = app.emit("server-bootstrap", step.clone());
+        self.steps.push(step);
+        success
+    }
+}
+
+/// The UUID an offline-mode server gives `name`: a v3 UUID of "OfflinePlayer:<name>".
+fn offline_uuid(name: &str) -> String {
+    let mut bytes: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", name).as_bytes()).into();
+    bytes[6] = (bytes[6] & 0x0f) | 0x30;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    uuid::Uuid::from_bytes(bytes).to_string()
+}
+
+/// Exact name and UUID of a premium account; Mojang answers 204 for unknown names.
+async fn mojang_profile(http: &HttpState, name: &str) -> Result<(String, String), String> {
+    let url = format!("https://api.mojang.com/users/profiles/minecraft/{}", urlencoding::encode(name));
+    let profile: Value = http.get_json_cached(&url).await
+        .map_err(|_| format!("No Minecraft account named {} was found", name))?;
+    let id = profile["id"].as_str()
+        .and_then(|id| uuid::Uuid::parse_str(id).ok())
+        .ok_or_else(|| format!("Unexpected profile response for {}", name))?;
+    let name = profile["name"].as_str().unwrap_or(name).to_string();
+    Ok((name, id.to_string()))
+}
+
+/// Adds `entry` to a player list file unless a player with the same UUID or name is on it.
+fn add_to_list(root: &Path, file: &str, entry: Value) -> Result<(), String> {
+    let path = root.join(file);
+    let mut list: Vec<Value> = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", file, e))?,
+        Err(_) => vec![],
+    };
+    let same = |e: &Value, key: &str| match (e[key].as_str(), entry[key].as_str()) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => false,
+    };
+    if list.iter().any(|e| same(e, "uuid") || same(e, "name")) {
+        return Ok(());
+    }
+    list.push(entry);
+    let content = serde_json::to_string_pretty(&list).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", file, e))
+}
+
+fn properties_step(server: &RegisteredServer, options: &BootstrapOptions) -> Result<String, String> {
+    let bedrock = server.server_type == "bedrock";
+    let mut properties = HashMap::new();
+    if let Some(motd) = options.motd.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+        properties.insert(if bedrock { "server-name" } else { "motd" }.to_string(), motd.to_string());
+    }
+    if let Some(max) = options.max_players {
+        if max == 0 {
+            return Err("Max players must be at least 1".to_string());
+        }
+        properties.insert("max-players".to_string(), max.to_string());
+    }
+    if let Some(mode) = options.gamemode.as_deref() {
+        let mode = mode.to_lowercase();
+        if !GAMEMODES.contains(&mode.as_str()) {
+            return Err(format!("Unknown gamemode: {}", mode));
+        }
+        properties.insert("gamemode".to_string(), mode);
+    }
+    if options.whitelist {
+        let key = if bedrock { "allow-list" } else { "white-list" };
+        properties.insert(key.to_string(), "true".to_string());
+        if !bedrock {
+            properties.insert("enforce-whitelist".to_string(), "true".to_string());
+        }
+    }
+    if properties.is_empty() {
+        return Ok("Nothing to change".to_string());
+    }
+    let count = properties.len();
+    write_server_properties(server.path.clone(), properties)?;
+    Ok(format!("Updated {} setting(s)", count))
+}
+
+async fn owner_step(http: &HttpState, server: &RegisteredServer, username: &str) -> Result<String, String> {
+    let root = Path::new(&server.path);
+    if server.server_type == "bedrock" {
+        // permissions.json needs the XUID, which only the server learns when the player joins
+        add_to_list(root, "allowlist.json", json!({ "ignoresPlayerLimit": false, "name": username }))?;
+        return Ok(format!("Added {} to the allowlist; make them operator with `op {}` once they joined", username, username));
+    }
+
+    let online = read_server_properties(server.path.clone())
+        .map(|p| p.get("online-mode").map(|v| v != "false").unwrap_or(true))
+        .unwrap_or(true);
+    let (name, uuid) = if online {
+        mojang_profile(http, username).await?
+    } else {
+        (username.to_string(), offline_uuid(username))
+    };
+    add_to_list(root, "ops.json", json!({ "uuid": uuid, "name": name, "level": 4, "bypassesPlayerLimit": false }))?;
+    add_to_list(root, "whitelist.json", json!({ "uuid": uuid, "name": name }))?;
+    Ok(format!("{} is operator and whitelisted", name))
+}
+
+/// Starts the server, waits until it finished loading (the spawn area is generated by then) and
+/// stops it again.
+async fn pregenerate_step(app: &AppHandle, state: &ServerProcessState, server: &RegisteredServer) -> Result<String, String> {
+    let cfg = server.launch_config();
+    let id = cfg.id.clone();
+    start_server_direct(app.clone(), state, cfg.id, cfg.path, cfg.jar_file, cfg.ram, cfg.java_path, cfg.startup_flags, Some(false))?;
+
+    let mut seq = 0;
+    let deadline = tokio::time::Instant::now() + PREGEN_TIMEOUT;
+    let mut outcome = Err("The server did not finish starting in time".to_string());
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let (next_seq, lines) = console_output_since(state, &id, seq);
+        seq = next_seq;
+        if lines.iter().any(|l| is_ready_line(l)) {
+            outcome = Ok("Generated the spawn area".to_string());
+            break;
+        }
+        let running = state.processes.lock().map(|p| p.contains_key(&id)).unwrap_or(false);
+        if !running {
+            return Err("The server stopped during its first start; check the console".to_string());
+        }
+    }
+
+    let state = state.clone();
+    tokio::task::spawn_blocking(move || stop_server_direct(&state, id)).await
+        .map_err(|e| e.to_string())??;
+    outcome
+}
+
+/// Runs the first-run setup of a registered, stopped server. Steps whose options are unset are
+/// skipped; the port step always runs so a new server never clashes with an existing one.
+#[tauri::command]
+pub async fn bootstrap_server(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    state: State<'_, ServerProcessState>,
+    id: String,
+    options: BootstrapOptions,
+) -> Result<BootstrapReport, MineServerError> {
+    let server = find_registered_server(&id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))?;
+    ensure_not_running(state.inner(), Path::new(&server.path))?;
+    let bedrock = server.server_type == "bedrock";
+    let mut report = BootstrapReport { port: None, steps: vec![] };
+
+    // Bedrock has no eula.txt; its terms are accepted by downloading the server
+    let eula_ok = if bedrock {
+        true
+    } else if options.accept_eula {
+        let result = accept_eula(server.path.clone(), true).map(|_| "EULA accepted".to_string()).map_err(|e| e.to_string());
+        report.record(&app, &id, "eula", result)
+    } else {
+        report.record(&app, &id, "eula", Err("The EULA was not accepted; the server can't start until it is".to_string()))
+    };
+
+    report.record(&app, &id, "properties", properties_step(&server, &options));
+
+    if let Some(username) = options.owner_username.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+        let result = owner_step(http.inner(), &server, username).await;
+        report.record(&app, &id, "owner", result);
+    }
+
+    let port = assign_server_port(server.path.clone(), bedrock, options.preferred_port);
+    if let Ok(port) = port {
+        report.port = Some(port);
+    }
+    report.record(&app, &id, "port", port.map(|p| format!("Using port {}", p)).map_err(|e| e.to_string()));
+
+    if options.pregenerate_spawn {
+        let result = if eula_ok {
+            pregenerate_step(&app, state.inner(), &server).await
+        } else {
+            Err("Skipped because the EULA was not accepted".to_string())
+        };
+        report.record(&app, &id, "pregenerate", result);
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+use std::fs::File;
+use std::io::Read;
+use std::collections::HashMap;
+use serde::Serialize;
+use sha2::{Digest, Sha256, Sha512};
+use walkdir::WalkDir;
+use super::path_guard::validate_path;
+use super::trash::TRASH_DIR;
+use super::upload::to_hex;
+use super::error::MineServerError;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub hash: String,
+    pub size: u64,
+    pub paths: Vec<String>, // Relative to the server folder
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateReport {
+    pub groups: Vec<DuplicateGroup>,
+    pub wasted_bytes: u64, // Space freed by keeping one copy of each group
+    pub files_scanned: usize,
+}
+
+fn digest_file<D: Digest>(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+        if n == 0 { break; }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Hex digest of a file. `algo`: "md5", "sha1", "sha256" or "sha512".
+pub fn hash_path(path: &Path, algo: &str) -> Result<String, String> {
+    match algo.to_lowercase().replace('-', "").as_str() {
+        "md5" => digest_file::<md5::Md5>(path),
+        "sha1" => digest_file::<sha1::Sha1>(path),
+        "sha256" => digest_file::<Sha256>(path),
+        "sha512" => digest_file::<Sha512>(path),
+        other => Err(format!("Unsupported hash algorithm: {}", other)),
+    }
+}
+
+#[tauri::command]
+pub async fn hash_file(path: String, algo: String) -> Result<String, MineServerError> {
+    let file_path = validate_path(&path)?;
+    tokio::task::spawn_blocking(move || hash_path(&file_path, &algo))
+        .await?
+        .map_err(Into::into)
+}
+
+fn scan_duplicates(root: &Path) -> DuplicateReport {
+    // Only files sharing a size can be identical, so most files are never hashed
+    let mut by_size: HashMap<u64, Vec<std::path::PathBuf>> = HashMap::new();
+    let mut files_scanned = 0;
+    for entry in WalkDir::new(root).into_iter()
+        .filter_entry(|e| e.file_name() != TRASH_DIR)
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        files_scanned += 1;
+        if let Ok(meta) = entry.metadata() {
+            if meta.len() > 0 {
+                by_size.entry(meta.len()).or_default().push(entry.into_path());
+            }
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, p)| p.len() > 1) {
+        let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+        for path in paths {
+            if let Ok(hash) = digest_file::<Sha256>(&path) {
+                let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string();
+                by_hash.entry(hash).or_default().push(relative);
+            }
+        }
+        for (hash, mut paths) in by_hash.into_iter().filter(|(_, p)| p.len() > 1) {
+            paths.sort();
+            groups.push(DuplicateGroup { hash, size, paths });
+        }
+    }
+
+    // Biggest waste first
+    groups.sort_by_key(|g| std::cmp::Reverse(g.size * (g.paths.len() as u64 - 1)));
+    let wasted_bytes = groups.iter().map(|g| g.size * (g.paths.len() as u64 - 1)).sum();
+    DuplicateReport { groups, wasted_bytes, files_scanned }
+}
+
+/// Groups identical files (same size and SHA-256) inside a server folder.
+#[tauri::command]
+pub async fn find_duplicate_files(server_path: String) -> Result<DuplicateReport, MineServerError> {
+    let root = validate_path(&server_path)?;
+    tokio::task::spawn_blocking(move || scan_duplicates(&root))
+        .await
+        .map_err(Into::into)
+}
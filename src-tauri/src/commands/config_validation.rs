@@ -0,0 +1,107 @@
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use super::error::MineServerError;
+
+// Syntax checks for config files edited in the file manager. A broken paper.yml or
+// server.properties keeps the server from starting, so writes are rejected up front.
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSyntaxError {
+    pub format: String,
+    pub line: usize, // 1-based
+    pub column: usize, // 1-based
+    pub message: String,
+}
+
+impl ConfigSyntaxError {
+    fn new(format: &str, line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self { format: format.to_string(), line, column, message: message.into() }
+    }
+
+    /// Validation error for commands: `CONFIG_SYNTAX_ERROR:` followed by this struct as JSON.
+    pub fn to_command_error(&self) -> MineServerError {
+        MineServerError::validation(format!("CONFIG_SYNTAX_ERROR:{}", serde_json::to_string(self).unwrap_or_default()))
+    }
+}
+
+/// 1-based line/column of a byte offset.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    (line, column)
+}
+
+fn validate_yaml(content: &str) -> Result<(), ConfigSyntaxError> {
+    // Multi-document files are valid YAML too
+    for document in serde_yaml::Deserializer::from_str(content) {
+        if let Err(e) = serde_yaml::Value::deserialize(document) {
+            let (line, column) = e.location().map(|l| (l.line(), l.column())).unwrap_or((1, 1));
+            return Err(ConfigSyntaxError::new("yaml", line, column, e.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn validate_json(content: &str) -> Result<(), ConfigSyntaxError> {
+    serde_json::from_str::<serde_json::Value>(content)
+        .map(|_| ())
+        .map_err(|e| ConfigSyntaxError::new("json", e.line(), e.column(), e.to_string()))
+}
+
+fn validate_toml(content: &str) -> Result<(), ConfigSyntaxError> {
+    content.parse::<toml_edit::DocumentMut>()
+        .map(|_| ())
+        .map_err(|e| {
+            let (line, column) = e.span().map(|s| line_column(content, s.start)).unwrap_or((1, 1));
+            ConfigSyntaxError::new("toml", line, column, e.message().to_string())
+        })
+}
+
+/// Java's Properties loader accepts almost anything, but a malformed `\uXXXX` escape throws
+/// and the server refuses to start.
+fn validate_properties(content: &str) -> Result<(), ConfigSyntaxError> {
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') || trimmed.starts_with('!') {
+            continue;
+        }
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] == '\\' {
+                if chars.get(i + 1) == Some(&'u') {
+                    let hex: String = chars.iter().skip(i + 2).take(4).collect();
+                    if hex.len() < 4 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                        return Err(ConfigSyntaxError::new("properties", index + 1, i + 1, "Malformed \\uXXXX escape"));
+                    }
+                }
+                i += 2;
+                continue;
+            }
+            i += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Checks `content` if `path` is a config format we understand; other files always pass.
+pub fn validate_config(path: &Path, content: &str) -> Result<(), ConfigSyntaxError> {
+    let extension = path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "yml" | "yaml" => validate_yaml(content),
+        "json" | "mcmeta" => validate_json(content),
+        "toml" => validate_toml(content),
+        "properties" => validate_properties(content),
+        _ => Ok(()),
+    }
+}
+
+/// Lint without saving, e.g. while typing in the editor.
+#[tauri::command]
+pub fn validate_config_file(path: String, content: String) -> Option<ConfigSyntaxError> {
+    validate_config(Path::new(&path), &content).err()
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::State;
+use super::backup::get_backups_dir;
+use super::disk_usage::drives_for;
+use super::http::HttpState;
+use super::network_manager::find_gateway;
+use super::path_guard::app_data_dir;
+use super::registry::load_registry;
+use super::server_config::get_java_versions;
+use super::settings::PROXY_TEST_URL;
+use super::error::MineServerError;
+use crate::scheduler::since_last_tick;
+
+// App self-check for bug reports: can the app write where it needs to, find Java, reach the
+// internet and the router, is the scheduler alive and is there disk space left. Every check
+// reports instead of failing, so one broken area doesn't hide the others.
+
+const LOW_DISK_WARNING: u64 = 5 * 1024 * 1024 * 1024;
+const LOW_DISK_ERROR: u64 = 1024 * 1024 * 1024;
+const SCHEDULER_STALL: Duration = Duration::from_secs(3 * 60); // It wakes up once a minute
+const INTERNET_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: String, // "ok", "warning" or "error"
+    pub detail: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: impl Into<String>, status: &str, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status: status.to_string(), detail: detail.into() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    pub generated_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub healthy: bool, // No check failed (warnings allowed)
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+/// Creates and removes a probe file in `dir`, creating the folder if needed.
+fn check_writable(name: String, dir: &Path) -> DiagnosticCheck {
+    let probe = dir.join(format!(".mineserver-write-test-{}", uuid::Uuid::new_v4()));
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => DiagnosticCheck::new(name, "ok", dir.display().to_string()),
+        Err(e) => DiagnosticCheck::new(name, "error", format!("Cannot write to {}: {}", dir.display(), e)),
+    }
+}
+
+fn check_java() -> DiagnosticCheck {
+    let installs = get_java_versions();
+    if installs.is_empty() {
+        return DiagnosticCheck::new("Java", "warning", "No Java found. Java Edition servers need it; Bedrock servers don't");
+    }
+    let found: Vec<String> = installs.iter().map(|j| format!("{} ({})", j.version, j.path)).collect();
+    DiagnosticCheck::new("Java", "ok", found.join("; "))
+}
+
+async fn check_internet(http: &HttpState) -> DiagnosticCheck {
+    let started = Instant::now();
+    let result = http.client().head(PROXY_TEST_URL).timeout(INTERNET_TIMEOUT).send().await;
+    match result {
+        Ok(resp) if resp.status().is_success() => {
+            DiagnosticCheck::new("Internet", "ok", format!("Mojang reachable in {} ms", started.elapsed().as_millis()))
+        }
+        Ok(resp) => DiagnosticCheck::new("Internet", "warning", format!("Mojang answered HTTP {}", resp.status().as_u16())),
+        Err(e) => DiagnosticCheck::new("Internet", "error", format!("Cannot reach Mojang (check the proxy settings): {}", e)),
+    }
+}
+
+async fn check_upnp() -> DiagnosticCheck {
+    match tokio::task::spawn_blocking(find_gateway).await {
+        Ok(Ok(gateway)) => DiagnosticCheck::new("UPnP gateway", "ok", gateway.addr.to_string()),
+        Ok(Err(e)) => DiagnosticCheck::new("UPnP gateway", "warning", format!("{} Port forwarding has to be set up by hand or with a tunnel", e)),
+        Err(e) => DiagnosticCheck::new("UPnP gateway", "error", e.to_string()),
+    }
+}
+
+fn check_scheduler() -> DiagnosticCheck {
+    match since_last_tick() {
+        None => DiagnosticCheck::new("Scheduler", "error", "The scheduler thread is not running; scheduled tasks won't run"),
+        Some(age) if age > SCHEDULER_STALL => {
+            DiagnosticCheck::new("Scheduler", "error", format!("Last active {} s ago; scheduled tasks aren't running", age.as_secs()))
+        }
+        Some(age) => DiagnosticCheck::new("Scheduler", "ok", format!("Last active {} s ago", age.as_secs())),
+    }
+}
+
+fn check_disks(paths: &[PathBuf]) -> Vec<DiagnosticCheck> {
+    drives_for(paths).into_iter()
+        .map(|drive| {
+            let free_gb = drive.free_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+            let status = if drive.free_bytes < LOW_DISK_ERROR {
+                "error"
+            } else if drive.free_bytes < LOW_DISK_WARNING {
+                "warning"
+            } else {
+                "ok"
+            };
+            DiagnosticCheck::new(format!("Disk space ({})", drive.mount_point), status, format!("{:.1} GB free", free_gb))
+        })
+        .collect()
+}
+
+/// Runs every self-check and returns a report the user can attach to a bug report.
+#[tauri::command]
+pub async fn run_diagnostics(http: State<'_, HttpState>) -> Result<DiagnosticsReport, MineServerError> {
+    let local = tokio::task::spawn_blocking(|| {
+        let servers = load_registry().unwrap_or_default();
+        let backups_dir = get_backups_dir();
+
+        let mut checks = vec![
+            check_writable("App data folder".to_string(), &app_data_dir()),
+            check_writable("Backups folder".to_string(), &backups_dir),
+        ];
+        for server in &servers {
+            checks.push(check_writable(format!("Server folder: {}", server.name), Path::new(&server.path)));
+        }
+        checks.push(check_java());
+        checks.push(check_scheduler());
+
+        let mut paths: Vec<PathBuf> = servers.iter().map(|s| PathBuf::from(&s.path)).collect();
+        paths.push(app_data_dir());
+        paths.push(backups_dir);
+        checks.extend(check_disks(&paths));
+        checks
+    });
+    let (local, internet, upnp) = tokio::join!(local, check_internet(&http), check_upnp());
+
+    let mut checks = local?;
+    checks.push(internet);
+    checks.push(upnp);
+
+    Ok(DiagnosticsReport {
+        generated_at: chrono::Local::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        healthy: checks.iter().all(|c| c.status != "error"),
+        checks,
+    })
+}
@@ -0,0 +1,249 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use serde::Serialize;
+use sysinfo::Disks;
+use tauri::{AppHandle, State};
+use super::path_guard::validate_path;
+use super::runner::ServerEventSink;
+use super::server::path_size;
+use super::trash::TRASH_DIR;
+use super::backup::get_backups_dir;
+use super::registry::{load_registry, RegisteredServer};
+use super::world_manager::get_level_name;
+use super::error::MineServerError;
+
+// A folder's mtime only changes when direct children are added or removed, not when a
+// nested file grows (e.g. region files), so cached sizes also expire after a while.
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Clone)]
+struct CachedSize {
+    mtime: Option<SystemTime>,
+    computed_at: Instant,
+    size_bytes: u64,
+}
+
+pub struct DirSizeCache {
+    entries: Arc<Mutex<HashMap<PathBuf, CachedSize>>>,
+}
+
+impl DirSizeCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectorySize {
+    pub name: String,
+    pub size_bytes: u64,
+    pub cached: bool,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DirectorySizeProgress {
+    server_path: String,
+    entry: DirectorySize,
+    done: usize,
+    total: usize,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Size of a folder from the cache when still fresh, computing (and caching) it otherwise.
+/// The flag tells whether the cached value was used.
+fn cached_size(cache: &Mutex<HashMap<PathBuf, CachedSize>>, folder: &Path) -> Result<(u64, bool), String> {
+    let mtime = modified(folder);
+    let cached = cache.lock().map_err(|e| e.to_string())?
+        .get(folder)
+        .filter(|c| c.mtime == mtime && c.computed_at.elapsed() < CACHE_TTL)
+        .map(|c| c.size_bytes);
+    if let Some(size_bytes) = cached {
+        return Ok((size_bytes, true));
+    }
+
+    let size_bytes = path_size(folder);
+    cache.lock().map_err(|e| e.to_string())?.insert(folder.to_path_buf(), CachedSize {
+        mtime,
+        computed_at: Instant::now(),
+        size_bytes,
+    });
+    Ok((size_bytes, false))
+}
+
+/// Sizes of the top-level folders of a server. Cached folders are returned as-is; the rest are
+/// computed off the main thread and reported one by one as `directory-sizes-progress` events.
+#[tauri::command]
+pub async fn get_directory_sizes(app: AppHandle, state: State<'_, DirSizeCache>, server_path: String) -> Result<Vec<DirectorySize>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let cache = state.entries.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let mut folders: Vec<PathBuf> = fs::read_dir(&root)
+            .map_err(|e| format!("Failed to read directory: {}", e))?
+            .flatten()
+            .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .filter(|e| e.file_name() != TRASH_DIR)
+            .map(|e| e.path())
+            .collect();
+        folders.sort();
+
+        let total = folders.len();
+        let mut results = Vec::with_capacity(total);
+        for (i, folder) in folders.into_iter().enumerate() {
+            let (size_bytes, cached) = cached_size(&cache, &folder)?;
+            let entry = DirectorySize {
+                name: folder.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                size_bytes,
+                cached,
+            };
+
+            app.emit("directory-sizes-progress", DirectorySizeProgress {
+                server_path: server_path.clone(),
+                entry: entry.clone(),
+                done: i + 1,
+                total,
+            });
+            results.push(entry);
+        }
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Drops cached sizes for a server, e.g. after a restore or a large delete.
+#[tauri::command]
+pub fn clear_directory_size_cache(state: State<'_, DirSizeCache>, server_path: String) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+    state.entries.lock().map_err(|e| e.to_string())?.retain(|path, _| !path.starts_with(&root));
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStorage {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub world_bytes: u64,
+    pub plugins_bytes: u64, // plugins/ and mods/
+    pub backups_bytes: u64, // Backups kept inside the server folder
+    pub logs_bytes: u64, // logs/ and crash-reports/
+    pub other_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveSpace {
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageOverview {
+    pub servers: Vec<ServerStorage>,
+    pub backup_store_bytes: u64, // ~/Mineserver/Backups
+    pub drives: Vec<DriveSpace>, // Only drives holding a server or the backup store
+}
+
+fn sum_sizes(cache: &Mutex<HashMap<PathBuf, CachedSize>>, folders: &[PathBuf]) -> Result<u64, String> {
+    let mut total = 0;
+    for folder in folders.iter().filter(|f| f.is_dir()) {
+        total += cached_size(cache, folder)?.0;
+    }
+    Ok(total)
+}
+
+fn server_storage(cache: &Mutex<HashMap<PathBuf, CachedSize>>, server: &RegisteredServer) -> Result<ServerStorage, String> {
+    let root = Path::new(&server.path);
+    let level_name = get_level_name(root);
+    let worlds = [
+        root.join(&level_name),
+        root.join(format!("{}_nether", level_name)),
+        root.join(format!("{}_the_end", level_name)),
+        root.join("worlds"), // Bedrock
+    ];
+
+    let total_bytes = if root.is_dir() { cached_size(cache, root)?.0 } else { 0 };
+    let world_bytes = sum_sizes(cache, &worlds)?;
+    let plugins_bytes = sum_sizes(cache, &[root.join("plugins"), root.join("mods")])?;
+    let backups_bytes = sum_sizes(cache, &[root.join("backups")])?;
+    let logs_bytes = sum_sizes(cache, &[root.join("logs"), root.join("crash-reports")])?;
+
+    Ok(ServerStorage {
+        id: server.id.clone(),
+        name: server.name.clone(),
+        path: server.path.clone(),
+        total_bytes,
+        world_bytes,
+        plugins_bytes,
+        backups_bytes,
+        logs_bytes,
+        // Sizes are cached separately, so keep a stale total from going negative
+        other_bytes: total_bytes.saturating_sub(world_bytes + plugins_bytes + backups_bytes + logs_bytes),
+    })
+}
+
+/// Free space on the drives that hold the given paths (longest matching mount point wins).
+pub(super) fn drives_for(paths: &[PathBuf]) -> Vec<DriveSpace> {
+    let disks = Disks::new_with_refreshed_list();
+    let mut drives: Vec<DriveSpace> = Vec::new();
+    for path in paths {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+        let disk = disks.iter()
+            .filter(|d| path.starts_with(d.mount_point()))
+            .max_by_key(|d| d.mount_point().as_os_str().len());
+        if let Some(disk) = disk {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            if !drives.iter().any(|d| d.mount_point == mount_point) {
+                drives.push(DriveSpace {
+                    mount_point,
+                    total_bytes: disk.total_space(),
+                    free_bytes: disk.available_space(),
+                });
+            }
+        }
+    }
+    drives
+}
+
+/// Where the disk space goes: every registered server broken down by category, the backup
+/// store, and free space on the drives involved. Sizes share the folder size cache.
+#[tauri::command]
+pub async fn get_storage_overview(state: State<'_, DirSizeCache>) -> Result<StorageOverview, MineServerError> {
+    let cache = state.entries.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let servers = load_registry()?;
+        let backups_dir = get_backups_dir();
+
+        let storage = servers.iter()
+            .map(|server| server_storage(&cache, server))
+            .collect::<Result<Vec<_>, _>>()?;
+        let backup_store_bytes = sum_sizes(&cache, &[backups_dir.clone()])?;
+
+        let mut paths: Vec<PathBuf> = servers.iter().map(|s| PathBuf::from(&s.path)).collect();
+        paths.push(backups_dir);
+
+        Ok::<_, String>(StorageOverview {
+            servers: storage,
+            backup_store_bytes,
+            drives: drives_for(&paths),
+        })
+    })
+    .await?
+    .map_err(Into::into)
+}
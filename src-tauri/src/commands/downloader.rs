@@ -1,13 +1,31 @@
 use std::path::Path;
-use std::fs::File;
-use std::io::Write;
+use tokio::io::AsyncWriteExt;
 use reqwest::Client;
-use tauri::{Window, Emitter};
+use tauri::{AppHandle, Emitter, State};
 use futures_util::StreamExt;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use super::extract::{extract_zip_file, ExtractLimits};
+use super::eula::write_eula_for_new_server;
+use super::http::{HttpState, BROWSER_USER_AGENT};
+use super::operations::{Operation, OperationState, CANCELLED};
+use super::runner::{ServerProcessState, ensure_not_running};
+use super::activity::Activity;
+use super::backup::pre_update_backup;
+use super::settings::load_settings;
+use super::error::MineServerError;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt; // For chmod later
 
+// GetBukkit answers versions it doesn't have (and outages) with an HTML page and status 200, so
+// Spigot downloads are checked and the next source is tried, ending with a BuildTools build
+const SPIGOT_MIRRORS: [&str; 2] = [
+    "https://download.getbukkit.org/spigot/spigot-{version}.jar",
+    "https://cdn.getbukkit.org/spigot/spigot-{version}.jar",
+];
+const BUILDTOOLS_URL: &str = "https://hub.spigotmc.org/jenkins/job/BuildTools/lastSuccessfulBuild/artifact/target/BuildTools.jar";
+// SpongeVanilla and SpongeForge builds, tagged with the Minecraft, SpongeAPI and Forge versions
+pub(crate) const SPONGE_DOWNLOADS_API: &str = "https://dl-api.spongepowered.org/v2/groups/org.spongepowered/artifacts";
+
 #[derive(Debug, Deserialize)]
 struct MojangManifest {
     versions: Vec<MojangVersion>,
@@ -34,6 +52,35 @@ struct DownloadEntry {
     url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct SpongeVersions {
+    artifacts: serde_json::Map<String, serde_json::Value>, // Build version -> tags
+}
+
+#[derive(Debug, Deserialize)]
+struct SpongeArtifact {
+    assets: Vec<SpongeAsset>,
+    #[serde(default)]
+    tags: std::collections::HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpongeAsset {
+    #[serde(default)]
+    classifier: String,
+    extension: String,
+    #[serde(rename = "downloadUrl")]
+    download_url: String,
+}
+
+/// The newest Sponge build for a Minecraft version.
+pub(crate) struct SpongeBuild {
+    pub version: String, // "1.20.6-11.0.0-RC1593"
+    pub jar_url: String,
+    pub api: Option<String>, // SpongeAPI version plugins are built against, "11.0.0"
+    pub forge: Option<String>, // Forge version SpongeForge runs on
+}
+
 #[derive(Debug, Deserialize)]
 struct PaperBuilds {
     builds: Vec<PaperBuild>,
@@ -55,37 +102,46 @@ struct PaperApplication {
     name: String,
 }
 
-#[derive(Clone, Serialize)]
-struct DownloadProgress {
-    percentage: u64,
-    current: u64,
-    total: u64,
-}
-
 #[tauri::command]
 pub async fn download_server(
-    window: Window,
-    app_handle: tauri::AppHandle,
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    state: State<'_, ServerProcessState>,
     server_type: String,
     version: String,
     server_path: String,
     preserve_config: Option<bool>,
-) -> Result<String, String> {
+    operation_id: Option<String>,
+) -> Result<String, MineServerError> {
+    ensure_not_running(state.inner(), Path::new(&server_path))?;
     let preserve = preserve_config.unwrap_or(false);
-    
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| e.to_string())?;
-    // ... (rest is same until post-processing)
-    let url = resolve_url(&client, &server_type, &version).await?;
-    
+    // Downloading into an existing server is an upgrade: worlds get converted on the next start
+    let upgrading = std::fs::read_dir(&server_path).map(|mut d| d.next().is_some()).unwrap_or(false);
+    let backup_id = if upgrading {
+        pre_update_backup(app.clone(), ops.inner(), &server_path, &load_settings().pre_update_backup_scope).await?
+    } else {
+        None
+    };
+    let operation = ops.begin(operation_id, "download")?.with_events(app.clone());
+    let mut activity = Activity::begin("server_download", &server_path, format!("Download {} {}", server_type, version));
+    activity.set_backup_id(backup_id);
+
+    let client = http.client();
+
     // Ensure directory exists
     let path = Path::new(&server_path);
     if !path.exists() {
         std::fs::create_dir_all(path).map_err(|e| e.to_string())?;
     }
 
+    // SpongeForge is a mod: install the Forge version it was built for, then add it to mods/
+    let sponge_forge = if server_type == "spongeforge" {
+        Some(sponge_build(&client, "spongeforge", &version).await?)
+    } else {
+        None
+    };
+
     let file_name = if server_type == "bedrock" { 
         "bedrock-server.zip" 
     } else if server_type == "nukkit" {
@@ -96,26 +152,26 @@ pub async fn download_server(
     let file_path = path.join(file_name);
     
     // Download
-    let res = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let total_size = res.content_length().unwrap_or(0);
-    
-    let mut file = File::create(&file_path).map_err(|e| e.to_string())?;
-    let mut downloaded: u64 = 0;
-    let mut stream = res.bytes_stream();
-
-    while let Some(item) = stream.next().await {
-        let chunk = item.map_err(|e| e.to_string())?;
-        file.write_all(&chunk).map_err(|e| e.to_string())?;
-        downloaded += chunk.len() as u64;
-        
-        if total_size > 0 {
-             let _ = window.emit("download-progress", DownloadProgress {
-                percentage: (downloaded * 100) / total_size,
-                current: downloaded,
-                total: total_size,
-            });
+    let downloaded = if server_type == "spigot" {
+        download_spigot(&client, &app, &operation, &version, path, &file_path).await?
+    } else if let Some(build) = &sponge_forge {
+        let forge = build.forge.as_deref().ok_or("This SpongeForge build doesn't say which Forge version it needs")?;
+        let url = format!(
+            "https://maven.minecraftforge.net/net/minecraftforge/forge/{0}-{1}/forge-{0}-{1}-installer.jar",
+            version, forge
+        );
+        let res = client.get(&url).send().await.map_err(|e| e.to_string())?;
+        stream_to_file(res, &file_path, &operation, file_name, true).await?
+    } else {
+        let url = resolve_url(&client, &server_type, &version).await?;
+        let mut request = client.get(&url);
+        if server_type == "bedrock" {
+            request = request.header(reqwest::header::USER_AGENT, BROWSER_USER_AGENT);
         }
-    }
+        let res = request.send().await.map_err(|e| e.to_string())?;
+        // PocketMine ships a .phar; everything else is a jar or zip
+        stream_to_file(res, &file_path, &operation, file_name, server_type != "pocketmine").await?
+    };
 
     // Runtime Download (PocketMine Only for now)
     if server_type == "pocketmine" {
@@ -126,7 +182,7 @@ pub async fn download_server(
     // Post-Processing
     if server_type == "bedrock" {
         // Backup configs if preserve is true
-        let configs = ["server.properties", "whitelist.json", "permissions.json"];
+        let configs = ["server.properties", "allowlist.json", "whitelist.json", "permissions.json"];
         if preserve {
             for config in configs.iter() {
                 let config_path = path.join(config);
@@ -136,10 +192,12 @@ pub async fn download_server(
             }
         }
 
-        // Unzip
-        let file = File::open(&file_path).map_err(|e| e.to_string())?;
-        let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
-        archive.extract(&path).map_err(|e| e.to_string())?;
+        operation.progress(100, downloaded, "Extracting...");
+        // Unzip (Bedrock zips are a few hundred MB; keep it off the async runtime)
+        let (zip_path, target) = (file_path.clone(), path.to_path_buf());
+        tokio::task::spawn_blocking(move || extract_zip_file(&zip_path, &target, &ExtractLimits::from_settings()))
+            .await
+            .map_err(|e| e.to_string())??;
         
         // Restore configs
         if preserve {
@@ -160,18 +218,14 @@ pub async fn download_server(
 
         // Remove zip
         std::fs::remove_file(&file_path).map_err(|e| e.to_string())?;
-    } else if server_type == "neoforge" || server_type == "forge" {
+    } else if server_type == "neoforge" || server_type == "forge" || server_type == "spongeforge" {
         // NeoForge/Forge: Run the installer automatically
-        let _ = window.emit("download-progress", DownloadProgress {
-            percentage: 95,
-            current: 95,
-            total: 100,
-        });
-        let _ = window.emit("server-log", format!("[{}] Running installer...", server_type.to_uppercase()));
+        operation.progress(95, downloaded, "Running installer...");
+        let _ = app.emit("server-log", format!("[{}] Running installer...", server_type.to_uppercase()));
         
         // The downloaded file is the installer jar
         // Run: java -jar <installer>.jar --installServer
-        use std::process::Command;
+        use tokio::process::Command;
         
         let java_path = "java"; // Use system Java, could be configurable later
         
@@ -181,48 +235,183 @@ pub async fn download_server(
             .arg(&file_path)
             .arg("--installServer")
             .current_dir(&path)
-            .output();
+            .output()
+            .await;
         
         match output {
             Ok(result) => {
                 if result.status.success() {
-                    let _ = window.emit("server-log", format!("[{}] Installer completed successfully!", server_type.to_uppercase()));
+                    let _ = app.emit("server-log", format!("[{}] Installer completed successfully!", server_type.to_uppercase()));
                     
                     // Clean up installer jar (optional, keep for re-install)
                     // let _ = std::fs::remove_file(&file_path);
                 } else {
                     let stderr = String::from_utf8_lossy(&result.stderr);
-                    let _ = window.emit("server-log", format!("[{}] Installer warning: {}", server_type.to_uppercase(), stderr));
+                    let _ = app.emit("server-log", format!("[{}] Installer warning: {}", server_type.to_uppercase(), stderr));
                     // Don't fail - the installer might have worked partially
                 }
             },
             Err(e) => {
                 // Java not found or other issue - log but don't fail
-                let _ = window.emit("server-log", format!("[{}] Could not run installer automatically ({}). Please run manually with: java -jar {} --installServer", server_type.to_uppercase(), e, file_name));
+                let _ = app.emit("server-log", format!("[{}] Could not run installer automatically ({}). Please run manually with: java -jar {} --installServer", server_type.to_uppercase(), e, file_name));
             }
         }
         
-        // Create EULA
-        let eula_path = path.join("eula.txt");
-        let mut eula_file = File::create(eula_path).map_err(|e| e.to_string())?;
-        eula_file.write_all(b"eula=true").map_err(|e| e.to_string())?;
+        // EULA (only true if the user accepted it before)
+        write_eula_for_new_server(&path)?;
         
         // Create mods folder for convenience
         let mods_path = path.join("mods");
-        let _ = std::fs::create_dir_all(mods_path);
+        let _ = std::fs::create_dir_all(&mods_path);
+
+        if let Some(build) = &sponge_forge {
+            operation.progress(98, downloaded, "Downloading SpongeForge...");
+            let res = client.get(&build.jar_url).send().await.map_err(|e| e.to_string())?;
+            let sponge_jar = mods_path.join(format!("spongeforge-{}.jar", build.version));
+            stream_to_file(res, &sponge_jar, &operation, "SpongeForge", true).await?;
+        }
         
     } else {
         // Standard servers (Vanilla, Paper, Spigot, Fabric, Purpur)
-        // EULA
-        let eula_path = path.join("eula.txt");
-        let mut eula_file = File::create(eula_path).map_err(|e| e.to_string())?;
-        eula_file.write_all(b"eula=true").map_err(|e| e.to_string())?;
+        // EULA (only true if the user accepted it before)
+        write_eula_for_new_server(&path)?;
     }
 
+    activity.complete(Some(downloaded));
+    operation.complete();
     Ok("Download complete".into())
 }
 
-async fn resolve_url(client: &Client, server_type: &str, version: &str) -> Result<String, String> {
+/// Streams a download into `file_path` with progress. With `expect_archive`, a response that is
+/// an HTML page or doesn't start like a zip/jar is rejected before anything is kept. The file is
+/// removed on any error.
+async fn stream_to_file(res: reqwest::Response, file_path: &Path, operation: &Operation, label: &str, expect_archive: bool) -> Result<u64, String> {
+    if !res.status().is_success() {
+        return Err(format!("Download failed with status: {}", res.status()));
+    }
+    let is_html = res.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("text/html"))
+        .unwrap_or(false);
+    if expect_archive && is_html {
+        return Err("The server sent a web page instead of the file".to_string());
+    }
+    let total_size = res.content_length().unwrap_or(0);
+
+    // Written next to the target and renamed over it at the end, so a failed or cancelled
+    // download (or a mirror's error page) never replaces a working jar
+    let mut part_name = file_path.file_name().unwrap_or_default().to_os_string();
+    part_name.push(".part");
+    let part_path = file_path.with_file_name(part_name);
+
+    let mut file = tokio::fs::File::create(&part_path).await.map_err(|e| e.to_string())?;
+    let mut downloaded: u64 = 0;
+    let mut head: Vec<u8> = Vec::with_capacity(2); // First bytes, for the zip signature
+    let mut stream = res.bytes_stream();
+    let result = async {
+        while let Some(item) = stream.next().await {
+            operation.token.check()?;
+            let chunk = item.map_err(|e| e.to_string())?;
+            if expect_archive && head.len() < 2 {
+                head.extend(chunk.iter().take(2 - head.len()));
+                if !b"PK".starts_with(&head) {
+                    return Err("The download is not a jar or zip file (probably an error page)".to_string());
+                }
+            }
+            file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+            downloaded += chunk.len() as u64;
+
+            let percentage = if total_size > 0 { ((downloaded * 100) / total_size) as u8 } else { 0 };
+            operation.progress(percentage, downloaded, format!("Downloading {}", label));
+        }
+        if expect_archive && head.len() < 2 {
+            return Err("The download is empty".to_string());
+        }
+        file.flush().await.map_err(|e| e.to_string())?;
+        file.sync_all().await.map_err(|e| e.to_string())
+    }.await;
+    drop(file);
+
+    let result = match result {
+        Ok(()) => tokio::fs::rename(&part_path, file_path).await.map_err(|e| format!("Failed to save {}: {}", label, e)),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&part_path).await;
+        return Err(e);
+    }
+    Ok(downloaded)
+}
+
+/// Tries each GetBukkit mirror and falls back to building the jar with BuildTools.
+async fn download_spigot(client: &Client, app: &AppHandle, operation: &Operation, version: &str, server_dir: &Path, file_path: &Path) -> Result<u64, String> {
+    for template in SPIGOT_MIRRORS {
+        let url = template.replace("{version}", version);
+        let result = match client.get(&url).send().await {
+            Ok(res) => stream_to_file(res, file_path, operation, "server.jar", true).await,
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(size) => return Ok(size),
+            Err(e) if e == CANCELLED => return Err(e),
+            Err(e) => { let _ = app.emit("server-log", format!("[SPIGOT] {} failed: {}", url, e)); },
+        }
+    }
+
+    let _ = app.emit("server-log", format!("[SPIGOT] No mirror has Spigot {}, building it with BuildTools...", version));
+    build_spigot(client, operation, version, server_dir, file_path).await
+        .map_err(|e| format!("No mirror has Spigot {} and BuildTools failed: {}", version, e))
+}
+
+/// Builds Spigot from source in `<server>/.buildtools`. Needs Java (and git outside Windows,
+/// where BuildTools fetches its own) and takes several minutes.
+async fn build_spigot(client: &Client, operation: &Operation, version: &str, server_dir: &Path, file_path: &Path) -> Result<u64, String> {
+    let dir = server_dir.join(".buildtools");
+    let result = run_buildtools(client, operation, version, &dir, file_path).await;
+    // BuildTools leaves several hundred MB of sources and caches behind, whether it worked or not
+    if let Err(e) = std::fs::remove_dir_all(&dir) {
+        eprintln!("[Download] Failed to remove {}: {}", dir.display(), e);
+    }
+    result
+}
+
+async fn run_buildtools(client: &Client, operation: &Operation, version: &str, dir: &Path, file_path: &Path) -> Result<u64, String> {
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create .buildtools: {}", e))?;
+    let tools = dir.join("BuildTools.jar");
+    let res = client.get(BUILDTOOLS_URL).send().await.map_err(|e| e.to_string())?;
+    stream_to_file(res, &tools, operation, "BuildTools.jar", true).await?;
+
+    operation.progress(0, 0, format!("Building Spigot {} with BuildTools, this takes several minutes", version));
+    let mut child = tokio::process::Command::new("java")
+        .arg("-jar")
+        .arg(&tools)
+        .args(["--rev", version, "--output-dir"])
+        .arg(dir)
+        .current_dir(dir)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not run Java: {}", e))?;
+    let status = loop {
+        tokio::select! {
+            status = child.wait() => break status.map_err(|e| e.to_string())?,
+            _ = tokio::time::sleep(std::time::Duration::from_millis(500)) => operation.token.check()?,
+        }
+    };
+    if !status.success() {
+        // BuildTools logs next to itself; the last lines say what went wrong
+        let log = std::fs::read_to_string(dir.join("BuildTools.log.txt")).unwrap_or_default();
+        let tail: Vec<&str> = log.lines().rev().take(5).collect();
+        return Err(tail.into_iter().rev().collect::<Vec<_>>().join(" | "));
+    }
+
+    let built = dir.join(format!("spigot-{}.jar", version));
+    std::fs::rename(&built, file_path).map_err(|e| format!("BuildTools finished but {} is missing: {}", built.display(), e))?;
+    std::fs::metadata(file_path).map(|m| m.len()).map_err(|e| e.to_string())
+}
+
+pub(crate) async fn resolve_url(client: &Client, server_type: &str, version: &str) -> Result<String, String> {
     match server_type {
         "vanilla" => {
             let manifest: MojangManifest = client.get("https://launchermeta.mojang.com/mc/game/version_manifest.json")
@@ -330,9 +519,8 @@ async fn resolve_url(client: &Client, server_type: &str, version: &str) -> Resul
             ))
         },
         "spigot" => {
-            // Spigot requires BuildTools, but we can use GetBukkit mirrors
-            // Or direct download from GetBukkit
-            Ok(format!("https://download.getbukkit.org/spigot/spigot-{}.jar", version))
+            // First mirror only; `download_server` checks the file and tries the others
+            Ok(SPIGOT_MIRRORS[0].replace("{version}", version))
         },
         "purpur" => {
             // Purpur API - similar to Paper
@@ -355,6 +543,9 @@ async fn resolve_url(client: &Client, server_type: &str, version: &str) -> Resul
                 version
             ))
         },
+        "spongevanilla" => {
+            Ok(sponge_build(client, "spongevanilla", version).await?.jar_url)
+        },
         "nukkit" => {
             // Cloudburst Nukkit - Java-based Bedrock Server
             // Use CI for latest stable build
@@ -363,3 +554,35 @@ async fn resolve_url(client: &Client, server_type: &str, version: &str) -> Resul
         _ => Err("Unsupported server type".to_string())
     }
 }
+
+/// The newest recommended Sponge build (`artifact` is "spongevanilla" or "spongeforge") for a
+/// Minecraft version, or the newest build if none is recommended yet.
+pub(crate) async fn sponge_build(client: &Client, artifact: &str, mc_version: &str) -> Result<SpongeBuild, String> {
+    let mut found = None;
+    for recommended in ["&recommended=true", ""] {
+        let url = format!("{}/{}/versions?tags=minecraft:{}&limit=1{}", SPONGE_DOWNLOADS_API, artifact, mc_version, recommended);
+        let versions: SpongeVersions = client.get(&url)
+            .send().await.map_err(|e| e.to_string())?
+            .json().await.map_err(|e| format!("Failed to read Sponge versions: {}", e))?;
+        found = versions.artifacts.keys().next().cloned();
+        if found.is_some() {
+            break;
+        }
+    }
+    let version = found.ok_or_else(|| format!("No {} build for Minecraft {}", artifact, mc_version))?;
+
+    let details: SpongeArtifact = client.get(format!("{}/{}/versions/{}", SPONGE_DOWNLOADS_API, artifact, version))
+        .send().await.map_err(|e| e.to_string())?
+        .json().await.map_err(|e| format!("Failed to read Sponge build {}: {}", version, e))?;
+    let jar = details.assets.iter()
+        .find(|a| a.classifier == "universal" && a.extension == "jar")
+        .or_else(|| details.assets.iter().find(|a| a.classifier.is_empty() && a.extension == "jar"))
+        .ok_or_else(|| format!("Sponge build {} has no server jar", version))?;
+
+    Ok(SpongeBuild {
+        jar_url: jar.download_url.clone(),
+        api: details.tags.get("api").cloned(),
+        forge: details.tags.get("forge").cloned(),
+        version,
+    })
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+use serde::Serialize;
+
+// Error type returned by commands. Serialized as `{ kind, message, context?, service? }` so the
+// frontend can branch on `kind` (e.g. offer "Retry" for network errors) and still show `message`.
+//
+// Older helpers still return `Result<_, String>`; `?` turns those into `Internal` errors and
+// typed errors back into their message, so modules can be migrated one at a time.
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum MineServerError {
+    NotFound { message: String, context: Option<String> },
+    PermissionDenied { message: String, context: Option<String> },
+    Network { message: String, context: Option<String> },
+    Conflict { message: String, context: Option<String> },
+    Validation { message: String, context: Option<String> },
+    External { service: String, message: String, context: Option<String> },
+    Internal { message: String, context: Option<String> },
+}
+
+impl MineServerError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound { message: message.into(), context: None }
+    }
+
+    pub fn permission_denied(message: impl Into<String>) -> Self {
+        Self::PermissionDenied { message: message.into(), context: None }
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::Network { message: message.into(), context: None }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict { message: message.into(), context: None }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation { message: message.into(), context: None }
+    }
+
+    /// A third-party API (Modrinth, Mojang, playit...) answered with an error.
+    pub fn external(service: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::External { service: service.into(), message: message.into(), context: None }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal { message: message.into(), context: None }
+    }
+
+    /// Attaches what was being worked on, e.g. the file path.
+    pub fn with_context(mut self, value: impl Into<String>) -> Self {
+        match &mut self {
+            Self::NotFound { context, .. }
+            | Self::PermissionDenied { context, .. }
+            | Self::Network { context, .. }
+            | Self::Conflict { context, .. }
+            | Self::Validation { context, .. }
+            | Self::External { context, .. }
+            | Self::Internal { context, .. } => *context = Some(value.into()),
+        }
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound { message, .. }
+            | Self::PermissionDenied { message, .. }
+            | Self::Network { message, .. }
+            | Self::Conflict { message, .. }
+            | Self::Validation { message, .. }
+            | Self::External { message, .. }
+            | Self::Internal { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for MineServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for MineServerError {}
+
+impl From<String> for MineServerError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for MineServerError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<MineServerError> for String {
+    fn from(error: MineServerError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<std::io::Error> for MineServerError {
+    fn from(error: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        let message = error.to_string();
+        match error.kind() {
+            ErrorKind::NotFound => Self::not_found(message),
+            ErrorKind::PermissionDenied => Self::permission_denied(message),
+            ErrorKind::AlreadyExists => Self::conflict(message),
+            ErrorKind::InvalidInput | ErrorKind::InvalidData => Self::validation(message),
+            _ => Self::internal(message),
+        }
+    }
+}
+
+impl From<reqwest::Error> for MineServerError {
+    fn from(error: reqwest::Error) -> Self {
+        let service = error.url().and_then(|u| u.host_str()).unwrap_or("remote server").to_string();
+        match error.status() {
+            // The request went through; the service itself refused it
+            Some(status) => Self::external(service, format!("Request failed with status {}", status)),
+            None if error.is_decode() => Self::external(service, format!("Unexpected response: {}", error)),
+            None => Self::network(error.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for MineServerError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::validation(error.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for MineServerError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        Self::internal(error.to_string())
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+use std::fs;
+use serde::Serialize;
+use super::path_guard::validate_path;
+use super::settings::{load_settings, update_settings};
+use super::error::MineServerError;
+
+// Minecraft EULA consent. The app only writes `eula=true` after the user accepted it once;
+// that choice is remembered in the backend settings.
+
+pub const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+const EULA_SUMMARY: &str = "Running a Minecraft server requires accepting the Minecraft End User License Agreement (EULA). \
+By accepting, you confirm that you have read and agree to it.";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EulaStatus {
+    pub accepted: bool, // User consented in MineServer
+    pub accepted_at: Option<String>,
+    pub server_accepted: bool, // eula.txt in this server says true
+    pub url: String,
+    pub text: String,
+}
+
+fn eula_file_accepts(server_path: &Path) -> bool {
+    fs::read_to_string(server_path.join("eula.txt"))
+        .map(|c| c.lines().any(|l| l.trim().eq_ignore_ascii_case("eula=true")))
+        .unwrap_or(false)
+}
+
+fn write_eula_file(server_path: &Path, accepted: bool) -> Result<(), String> {
+    let content = format!(
+        "#By changing the setting below to TRUE you are indicating your agreement to our EULA ({}).\n#{}\neula={}\n",
+        EULA_URL,
+        chrono::Local::now().format("%a %b %d %H:%M:%S %Z %Y"),
+        accepted
+    );
+    fs::write(server_path.join("eula.txt"), content)
+        .map_err(|e| format!("Failed to write eula.txt: {}", e))
+}
+
+/// Called after a download: writes eula=true only if the user consented before, eula=false otherwise.
+pub fn write_eula_for_new_server(server_path: &Path) -> Result<(), String> {
+    write_eula_file(server_path, load_settings().eula_accepted)
+}
+
+/// Called before launching a Java server. Never accepts on the user's behalf.
+pub fn ensure_eula(server_path: &Path) -> Result<(), MineServerError> {
+    if eula_file_accepts(server_path) {
+        return Ok(());
+    }
+    if load_settings().eula_accepted {
+        return write_eula_file(server_path, true).map_err(Into::into);
+    }
+    Err(MineServerError::validation(format!("You need to accept the Minecraft EULA ({}) before starting this server.", EULA_URL))
+        .with_context("EULA_NOT_ACCEPTED"))
+}
+
+#[tauri::command]
+pub fn get_eula_status(server_path: String) -> Result<EulaStatus, MineServerError> {
+    let path = validate_path(&server_path)?;
+    let settings = load_settings();
+    Ok(EulaStatus {
+        accepted: settings.eula_accepted,
+        accepted_at: settings.eula_accepted_at,
+        server_accepted: eula_file_accepts(&path),
+        url: EULA_URL.to_string(),
+        text: EULA_SUMMARY.to_string(),
+    })
+}
+
+/// Records the user's decision and updates eula.txt for the server.
+#[tauri::command]
+pub fn accept_eula(server_path: String, accepted: bool) -> Result<(), MineServerError> {
+    let path = validate_path(&server_path)?;
+    update_settings(|settings| {
+        settings.eula_accepted = accepted;
+        settings.eula_accepted_at = if accepted { Some(chrono::Local::now().to_rfc3339()) } else { None };
+        Ok(())
+    })?;
+
+    if path.is_dir() {
+        write_eula_file(&path, accepted)?;
+    }
+    Ok(())
+}
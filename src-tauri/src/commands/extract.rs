@@ -0,0 +1,587 @@
+use std::path::{Component, Path, PathBuf};
+use std::fs::{self, File};
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+use super::settings::load_settings;
+use super::operations::CancelToken;
+
+// Shared archive extraction used by every import path (file manager, world upload, backup
+// restore, Bedrock install). Guards against zip-slip, zip bombs and symlink tricks.
+// Zip is the main format; tar(.gz) and 7z go through the same checks.
+
+const RATIO_CHECK_MIN_BYTES: u64 = 1024 * 1024; // Tiny entries legitimately compress extremely well
+const MAX_ENTRIES: usize = 500_000;
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+pub struct ExtractLimits {
+    pub max_total_bytes: u64,
+    pub max_ratio: u64,
+    pub allow_symlinks: bool,
+    pub cancel: CancelToken,
+}
+
+impl ExtractLimits {
+    pub fn from_settings() -> Self {
+        let settings = load_settings();
+        Self {
+            max_total_bytes: settings.max_extract_size_mb.saturating_mul(1024 * 1024),
+            max_ratio: settings.max_compression_ratio.max(1),
+            allow_symlinks: settings.allow_archive_symlinks,
+            cancel: CancelToken::default(),
+        }
+    }
+
+    /// For archives the app wrote itself (backups in the index): no total size limit, since a
+    /// large server legitimately exceeds it. The ratio cap, paths and symlinks are still checked.
+    pub fn own_archive() -> Self {
+        Self {
+            max_total_bytes: u64::MAX,
+            allow_symlinks: false,
+            ..Self::from_settings()
+        }
+    }
+
+    /// Stops extraction with an error once `cancel` fires.
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+}
+
+/// Relative path made only of normal components, or None if the entry tries to escape.
+fn sanitize_entry_path(name: &str) -> Option<PathBuf> {
+    let normalized = name.replace('\\', "/");
+    let mut out = PathBuf::new();
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {},
+            _ => return None, // `..`, root or drive prefix
+        }
+    }
+    if out.as_os_str().is_empty() { None } else { Some(out) }
+}
+
+/// Lexically resolves `target` against `base` without touching the filesystem.
+#[cfg(unix)]
+fn resolve_link_target(base: &Path, target: &Path) -> Option<PathBuf> {
+    let mut out = if target.is_absolute() { PathBuf::new() } else { base.to_path_buf() };
+    for component in target.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::ParentDir => { if !out.pop() { return None; } },
+            Component::RootDir => out.push("/"),
+            _ => {},
+        }
+    }
+    Some(out)
+}
+
+/// The single top-level folder every entry lives in (e.g. "my_world/"), if any.
+pub fn common_root_prefix<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Option<String> {
+    let mut root_prefix: Option<String> = None;
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).ok()?;
+        let name = file.name();
+        let first_slash = name.find('/')?;
+        let prefix = &name[..first_slash + 1];
+        match &root_prefix {
+            Some(existing) if existing != prefix => return None,
+            Some(_) => {},
+            None => root_prefix = Some(prefix.to_string()),
+        }
+    }
+    root_prefix
+}
+
+/// Checks entry count, declared sizes and compression ratios before anything is written.
+/// Returns the total uncompressed size.
+fn preflight<R: Read + Seek>(archive: &mut ZipArchive<R>, limits: &ExtractLimits) -> Result<u64, String> {
+    if archive.len() > MAX_ENTRIES {
+        return Err(format!("Archive has too many entries ({})", archive.len()));
+    }
+
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).map_err(|e| e.to_string())?;
+        let size = file.size();
+        if size >= RATIO_CHECK_MIN_BYTES && size / file.compressed_size().max(1) > limits.max_ratio {
+            return Err(format!("Archive entry {} has a suspicious compression ratio (possible zip bomb)", file.name()));
+        }
+        total = total.saturating_add(size);
+    }
+
+    check_total_size(total, limits)?;
+    Ok(total)
+}
+
+fn check_total_size(total: u64, limits: &ExtractLimits) -> Result<(), String> {
+    if total > limits.max_total_bytes {
+        return Err(format!(
+            "Archive expands to {} MB, above the {} MB limit",
+            total / 1024 / 1024,
+            limits.max_total_bytes / 1024 / 1024
+        ));
+    }
+    Ok(())
+}
+
+/// Formats without per-entry compressed sizes (tar.gz, solid 7z) are checked as a whole.
+fn check_archive_ratio(total: u64, archive_path: &Path, limits: &ExtractLimits) -> Result<(), String> {
+    let archive_size = fs::metadata(archive_path).map(|m| m.len()).unwrap_or(0).max(1);
+    if total >= RATIO_CHECK_MIN_BYTES && total / archive_size > limits.max_ratio {
+        return Err("Archive has a suspicious compression ratio (possible zip bomb)".to_string());
+    }
+    Ok(())
+}
+
+/// Progress and byte accounting shared by all formats while writing entries into `dest_root`.
+struct EntryWriter<'a, F: FnMut(u64, u64, &str)> {
+    dest_root: PathBuf,
+    limits: &'a ExtractLimits,
+    total: u64,
+    extracted: u64,
+    buffer: Vec<u8>,
+    on_progress: F,
+}
+
+impl<'a, F: FnMut(u64, u64, &str)> EntryWriter<'a, F> {
+    fn new(dest: &Path, limits: &'a ExtractLimits, total: u64, on_progress: F) -> Result<Self, String> {
+        fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+        Ok(Self {
+            dest_root: fs::canonicalize(dest).map_err(|e| e.to_string())?,
+            limits,
+            total,
+            extracted: 0,
+            buffer: vec![0u8; 64 * 1024],
+            on_progress,
+        })
+    }
+
+    /// Output path for `relative`, or an error if it would land outside the extraction folder.
+    fn output_path(&self, name: &str, relative: &str) -> Result<PathBuf, String> {
+        let relative_path = sanitize_entry_path(relative)
+            .ok_or_else(|| format!("Security Warning: potential path traversal detected for file {}", name))?;
+        Ok(self.dest_root.join(relative_path))
+    }
+
+    fn create_dir(&self, outpath: &Path) -> Result<(), String> {
+        fs::create_dir_all(outpath).map_err(|e| e.to_string())
+    }
+
+    fn prepare_parent(&self, name: &str, outpath: &Path) -> Result<(), String> {
+        if let Some(parent) = outpath.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            // An existing symlinked folder on disk must not redirect writes outside the target
+            let real_parent = fs::canonicalize(parent).map_err(|e| e.to_string())?;
+            if !real_parent.starts_with(&self.dest_root) {
+                return Err(format!("Security Warning: {} resolves outside the extraction folder", name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a symlink entry if allowed and it stays inside the extraction folder.
+    #[allow(unused_variables)]
+    fn write_symlink(&self, name: &str, outpath: &Path, target: &str) -> Result<(), String> {
+        if !self.limits.allow_symlinks {
+            return Ok(());
+        }
+        self.prepare_parent(name, outpath)?;
+        #[cfg(unix)]
+        {
+            let resolved = resolve_link_target(outpath.parent().unwrap_or(&self.dest_root), Path::new(target));
+            // Only links that stay inside the extraction folder are created
+            if resolved.map(|r| r.starts_with(&self.dest_root)).unwrap_or(false) {
+                let _ = fs::remove_file(outpath);
+                std::os::unix::fs::symlink(target, outpath).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Streams `reader` into `outpath`, never writing more than `declared` bytes.
+    fn write_file(&mut self, name: &str, relative: &str, outpath: &Path, reader: &mut dyn Read, declared: u64, mode: Option<u32>) -> Result<(), String> {
+        self.prepare_parent(name, outpath)?;
+
+        // Never write through a symlink that is already on disk
+        if fs::symlink_metadata(outpath).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+            fs::remove_file(outpath).map_err(|e| e.to_string())?;
+        }
+
+        let mut outfile = File::create(outpath).map_err(|e| e.to_string())?;
+        let mut written: u64 = 0;
+        loop {
+            let n = reader.read(&mut self.buffer).map_err(|e| e.to_string())?;
+            if n == 0 { break; }
+            if let Err(e) = self.limits.cancel.check() {
+                drop(outfile);
+                let _ = fs::remove_file(outpath);
+                return Err(e);
+            }
+            written += n as u64;
+            self.extracted += n as u64;
+            // Declared sizes can lie; stop as soon as the real data exceeds them
+            if written > declared || self.extracted > self.limits.max_total_bytes {
+                drop(outfile);
+                let _ = fs::remove_file(outpath);
+                return Err(format!("Archive entry {} is larger than declared (possible zip bomb)", name));
+            }
+            std::io::Write::write_all(&mut outfile, &self.buffer[..n]).map_err(|e| e.to_string())?;
+            (self.on_progress)(self.extracted, self.total, relative);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Some(mode) = mode.filter(|m| m & 0o777 != 0) {
+                // Keep rwx bits only (no setuid/setgid/sticky)
+                fs::set_permissions(outpath, fs::Permissions::from_mode(mode & 0o777)).ok();
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        Ok(())
+    }
+}
+
+/// Extracts `archive` into `dest`. `strip_prefix` removes a leading folder from every entry.
+/// `on_progress(extracted_bytes, total_bytes, entry_name)` is called after each chunk.
+pub fn extract_zip<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    dest: &Path,
+    strip_prefix: Option<&str>,
+    limits: &ExtractLimits,
+    on_progress: impl FnMut(u64, u64, &str),
+) -> Result<(), String> {
+    let total = preflight(archive, limits)?;
+    let mut writer = EntryWriter::new(dest, limits, total, on_progress)?;
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = file.name().to_string();
+
+        let relative = match strip_prefix {
+            Some(prefix) => name.strip_prefix(prefix).unwrap_or(&name),
+            None => &name,
+        };
+        if relative.is_empty() {
+            continue;
+        }
+        let outpath = writer.output_path(&name, relative)?;
+
+        if name.ends_with('/') || file.is_dir() {
+            writer.create_dir(&outpath)?;
+            continue;
+        }
+
+        let mode = file.unix_mode();
+        if mode.map(|m| m & S_IFMT == S_IFLNK).unwrap_or(false) {
+            let mut target = String::new();
+            file.read_to_string(&mut target).map_err(|e| e.to_string())?;
+            writer.write_symlink(&name, &outpath, &target)?;
+            continue;
+        }
+
+        let declared = file.size();
+        writer.write_file(&name, relative, &outpath, &mut file, declared, mode)?;
+    }
+
+    Ok(())
+}
+
+fn open_tar(archive_path: &Path, gzipped: bool) -> Result<tar::Archive<Box<dyn Read>>, String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let reader: Box<dyn Read> = if gzipped {
+        Box::new(flate2::read::GzDecoder::new(std::io::BufReader::new(file)))
+    } else {
+        Box::new(std::io::BufReader::new(file))
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+/// Extracts a .tar or .tar.gz. The stream is read twice: once to check sizes, once to write.
+pub fn extract_tar(
+    archive_path: &Path,
+    gzipped: bool,
+    dest: &Path,
+    limits: &ExtractLimits,
+    on_progress: impl FnMut(u64, u64, &str),
+) -> Result<(), String> {
+    let mut total: u64 = 0;
+    let mut count: usize = 0;
+    let mut archive = open_tar(archive_path, gzipped)?;
+    for entry in archive.entries().map_err(|e| format!("Invalid tar archive: {}", e))? {
+        let entry = entry.map_err(|e| format!("Invalid tar archive: {}", e))?;
+        count += 1;
+        total = total.saturating_add(entry.header().size().unwrap_or(0));
+        if count > MAX_ENTRIES {
+            return Err(format!("Archive has too many entries ({})", count));
+        }
+        // Bail out early instead of decompressing the whole bomb
+        check_total_size(total, limits)?;
+    }
+    if gzipped {
+        check_archive_ratio(total, archive_path, limits)?;
+    }
+
+    let mut writer = EntryWriter::new(dest, limits, total, on_progress)?;
+    let mut archive = open_tar(archive_path, gzipped)?;
+    for entry in archive.entries().map_err(|e| format!("Invalid tar archive: {}", e))? {
+        let mut entry = entry.map_err(|e| format!("Invalid tar archive: {}", e))?;
+        let name = entry.path().map_err(|e| e.to_string())?.to_string_lossy().to_string();
+        if sanitize_entry_path(&name).is_none() && name.trim_matches(|c| c == '.' || c == '/').is_empty() {
+            continue; // "./" root entry
+        }
+        let outpath = writer.output_path(&name, &name)?;
+        let header = entry.header().clone();
+
+        match header.entry_type() {
+            tar::EntryType::Directory => writer.create_dir(&outpath)?,
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                let declared = header.size().map_err(|e| e.to_string())?;
+                let mode = header.mode().ok();
+                writer.write_file(&name, &name, &outpath, &mut entry, declared, mode)?;
+            },
+            tar::EntryType::Symlink => {
+                if let Some(target) = entry.link_name().map_err(|e| e.to_string())? {
+                    writer.write_symlink(&name, &outpath, &target.to_string_lossy())?;
+                }
+            },
+            _ => {}, // Hard links, devices and FIFOs are never created
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts a .7z archive (no password support).
+pub fn extract_7z(
+    archive_path: &Path,
+    dest: &Path,
+    limits: &ExtractLimits,
+    on_progress: impl FnMut(u64, u64, &str),
+) -> Result<(), String> {
+    let mut reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())
+        .map_err(|e| format!("Invalid 7z archive: {}", e))?;
+
+    let entries = &reader.archive().files;
+    if entries.len() > MAX_ENTRIES {
+        return Err(format!("Archive has too many entries ({})", entries.len()));
+    }
+    let total = entries.iter().fold(0u64, |acc, e| acc.saturating_add(e.size()));
+    check_total_size(total, limits)?;
+    check_archive_ratio(total, archive_path, limits)?;
+
+    let mut writer = EntryWriter::new(dest, limits, total, on_progress)?;
+    let mut failure: Option<String> = None;
+    reader.for_each_entries(|entry, data| {
+        let name = entry.name().to_string();
+        let result = writer.output_path(&name, &name).and_then(|outpath| {
+            if entry.is_directory() {
+                writer.create_dir(&outpath)
+            } else {
+                writer.write_file(&name, &name, &outpath, data, entry.size(), None)
+            }
+        });
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                failure = Some(e);
+                Ok(false)
+            }
+        }
+    }).map_err(|e| format!("Failed to extract 7z archive: {}", e))?;
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    SevenZip,
+}
+
+/// Detects the archive type from its magic bytes, falling back to the file extension.
+pub fn detect_format(path: &Path) -> Option<ArchiveFormat> {
+    let mut header = [0u8; 512];
+    let read = File::open(path).and_then(|mut f| f.read(&mut header)).unwrap_or(0);
+    let header = &header[..read];
+
+    if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+        return Some(ArchiveFormat::Zip);
+    }
+    if header.starts_with(&[0x1f, 0x8b]) {
+        return Some(ArchiveFormat::TarGz);
+    }
+    if header.starts_with(&[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C]) {
+        return Some(ArchiveFormat::SevenZip);
+    }
+    if header.len() >= 262 && &header[257..262] == b"ustar" {
+        return Some(ArchiveFormat::Tar);
+    }
+
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveFormat::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveFormat::Tar)
+    } else if name.ends_with(".7z") {
+        Some(ArchiveFormat::SevenZip)
+    } else if name.ends_with(".zip") || name.ends_with(".jar") || name.ends_with(".mcworld") || name.ends_with(".mrpack") {
+        Some(ArchiveFormat::Zip)
+    } else {
+        None
+    }
+}
+
+/// Extracts any supported archive into `dest` with the configured limits.
+pub fn extract_archive_file(archive_path: &Path, dest: &Path, on_progress: impl FnMut(u64, u64, &str)) -> Result<(), String> {
+    let limits = ExtractLimits::from_settings();
+    match detect_format(archive_path) {
+        Some(ArchiveFormat::Zip) => {
+            let file = File::open(archive_path).map_err(|e| e.to_string())?;
+            let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+            extract_zip(&mut archive, dest, None, &limits, on_progress)
+        },
+        Some(ArchiveFormat::Tar) => extract_tar(archive_path, false, dest, &limits, on_progress),
+        Some(ArchiveFormat::TarGz) => extract_tar(archive_path, true, dest, &limits, on_progress),
+        Some(ArchiveFormat::SevenZip) => extract_7z(archive_path, dest, &limits, on_progress),
+        None => Err("Unsupported archive format (supported: zip, tar, tar.gz, tgz, 7z)".to_string()),
+    }
+}
+
+/// Opens `zip_path` and extracts it into `dest` with the given limits.
+pub fn extract_zip_file(zip_path: &Path, dest: &Path, limits: &ExtractLimits) -> Result<(), String> {
+    let file = File::open(zip_path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+    extract_zip(&mut archive, dest, None, limits, |_, _, _| {})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::FileOptions;
+    use crate::test_support::temp_dir;
+
+    fn limits() -> ExtractLimits {
+        ExtractLimits {
+            max_total_bytes: 64 * 1024 * 1024,
+            max_ratio: 100,
+            allow_symlinks: false,
+            cancel: CancelToken::default(),
+        }
+    }
+
+    /// Builds a zip in memory with `add` writing the entries.
+    fn zip_with(add: impl FnOnce(&mut zip::ZipWriter<Cursor<Vec<u8>>>)) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        add(&mut writer);
+        let bytes = writer.finish().unwrap().into_inner();
+        ZipArchive::new(Cursor::new(bytes)).unwrap()
+    }
+
+    fn add_file(zip: &mut zip::ZipWriter<Cursor<Vec<u8>>>, name: &str, content: &[u8]) {
+        zip.start_file(name, FileOptions::default()).unwrap();
+        zip.write_all(content).unwrap();
+    }
+
+    /// Extraction folder inside a parent, so anything escaping it lands in the parent.
+    fn target() -> (PathBuf, PathBuf) {
+        let parent = temp_dir("extract");
+        let dest = parent.join("dest");
+        (parent, dest)
+    }
+
+    fn entries_outside(parent: &Path) -> Vec<PathBuf> {
+        fs::read_dir(parent).unwrap().flatten().map(|e| e.path()).filter(|p| !p.ends_with("dest")).collect()
+    }
+
+    #[test]
+    fn extracts_plain_entries() {
+        let (_, dest) = target();
+        let mut archive = zip_with(|zip| {
+            add_file(zip, "world/level.dat", b"level");
+            add_file(zip, "server.properties", b"motd=hi");
+        });
+        extract_zip(&mut archive, &dest, None, &limits(), |_, _, _| {}).unwrap();
+        assert_eq!(fs::read(dest.join("world/level.dat")).unwrap(), b"level");
+        assert_eq!(fs::read(dest.join("server.properties")).unwrap(), b"motd=hi");
+    }
+
+    #[test]
+    fn rejects_parent_dir_entry() {
+        let (parent, dest) = target();
+        let mut archive = zip_with(|zip| add_file(zip, "../evil.txt", b"pwned"));
+        assert!(extract_zip(&mut archive, &dest, None, &limits(), |_, _, _| {}).is_err());
+        assert!(entries_outside(&parent).is_empty());
+    }
+
+    #[test]
+    fn rejects_absolute_entry() {
+        let (parent, dest) = target();
+        let absolute = parent.join("abs.txt");
+        let name = absolute.to_string_lossy().to_string();
+        let mut archive = zip_with(|zip| add_file(zip, &name, b"pwned"));
+        assert!(extract_zip(&mut archive, &dest, None, &limits(), |_, _, _| {}).is_err());
+        assert!(!absolute.exists());
+        assert!(entries_outside(&parent).is_empty());
+    }
+
+    #[test]
+    fn rejects_entry_over_ratio_cap() {
+        let (parent, dest) = target();
+        // 4 MiB of zeros deflates to a few KiB, far above a 100:1 ratio
+        let mut archive = zip_with(|zip| add_file(zip, "bomb.bin", &vec![0u8; 4 * 1024 * 1024]));
+        let err = extract_zip(&mut archive, &dest, None, &limits(), |_, _, _| {}).unwrap_err();
+        assert!(err.contains("compression ratio"), "{}", err);
+        assert!(!dest.join("bomb.bin").exists());
+        assert!(entries_outside(&parent).is_empty());
+    }
+
+    #[test]
+    fn rejects_archive_over_size_cap() {
+        let (_, dest) = target();
+        let small = ExtractLimits { max_total_bytes: 1024, ..limits() };
+        let mut archive = zip_with(|zip| add_file(zip, "big.txt", &[b'x'; 4096]));
+        let err = extract_zip(&mut archive, &dest, None, &small, |_, _, _| {}).unwrap_err();
+        assert!(err.contains("limit"), "{}", err);
+        assert!(!dest.join("big.txt").exists());
+    }
+
+    #[test]
+    fn skips_symlink_entries() {
+        let (parent, dest) = target();
+        let mut archive = zip_with(|zip| {
+            zip.add_symlink("link", "../outside", FileOptions::default()).unwrap();
+            add_file(zip, "ok.txt", b"ok");
+        });
+        extract_zip(&mut archive, &dest, None, &limits(), |_, _, _| {}).unwrap();
+        assert!(fs::symlink_metadata(dest.join("link")).is_err());
+        assert!(dest.join("ok.txt").is_file());
+        assert!(entries_outside(&parent).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skips_escaping_symlink_even_when_allowed() {
+        let (parent, dest) = target();
+        let allowed = ExtractLimits { allow_symlinks: true, ..limits() };
+        let mut archive = zip_with(|zip| {
+            zip.add_symlink("escape", "../outside", FileOptions::default()).unwrap();
+            zip.add_symlink("inside", "ok.txt", FileOptions::default()).unwrap();
+            add_file(zip, "ok.txt", b"ok");
+        });
+        extract_zip(&mut archive, &dest, None, &allowed, |_, _, _| {}).unwrap();
+        assert!(fs::symlink_metadata(dest.join("escape")).is_err());
+        assert!(fs::symlink_metadata(dest.join("inside")).unwrap().file_type().is_symlink());
+        assert!(entries_outside(&parent).is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::fs;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use super::ping::{ping_bedrock, ping_java, PingResult};
+use super::runner::ServerEventSink;
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Favorites: external servers the user plays on, kept in ~/Mineserver/favorites.json and pinged
+// with the same Server List Ping clients as the uptime monitor. SRV records aren't resolved,
+// so addresses that rely on one need the explicit port.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteServer {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub address: String, // "host" or "host:port"
+    #[serde(default)]
+    pub bedrock: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteStatus {
+    pub id: String,
+    pub online: bool,
+    pub ping: Option<PingResult>,
+    pub error: Option<String>,
+}
+
+fn get_favorites_file() -> PathBuf {
+    app_data_dir().join("favorites.json")
+}
+
+fn load_favorites() -> Result<Vec<FavoriteServer>, String> {
+    let file = get_favorites_file();
+    if !file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read favorites: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse favorites: {}", e))
+}
+
+fn save_favorites(favorites: &Vec<FavoriteServer>) -> Result<(), String> {
+    let file = get_favorites_file();
+    fs::create_dir_all(file.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let content = serde_json::to_string_pretty(favorites)
+        .map_err(|e| format!("Failed to serialize favorites: {}", e))?;
+    fs::write(&file, content)
+        .map_err(|e| format!("Failed to write favorites: {}", e))
+}
+
+/// Splits "host:port" ("[v6]:port" for IPv6), falling back to the default game port.
+fn parse_address(address: &str, bedrock: bool) -> Result<(String, u16), String> {
+    let default_port = if bedrock { 19132 } else { 25565 };
+    let address = address.trim();
+    if address.is_empty() {
+        return Err("Address is empty".to_string());
+    }
+
+    let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+        let (host, after) = rest.split_once(']').ok_or("Invalid IPv6 address")?;
+        (host, after.strip_prefix(':'))
+    } else if address.matches(':').count() == 1 {
+        let (host, port) = address.split_once(':').unwrap_or((address, ""));
+        (host, Some(port))
+    } else {
+        (address, None)
+    };
+    let port = match port {
+        Some(p) => p.parse().map_err(|_| format!("Invalid port: {}", p))?,
+        None => default_port,
+    };
+    Ok((host.to_string(), port))
+}
+
+fn ping_favorite(favorite: &FavoriteServer) -> FavoriteStatus {
+    let result = parse_address(&favorite.address, favorite.bedrock).and_then(|(host, port)| {
+        if favorite.bedrock { ping_bedrock(&host, port) } else { ping_java(&host, port) }
+    });
+    FavoriteStatus {
+        id: favorite.id.clone(),
+        online: result.is_ok(),
+        error: result.as_ref().err().cloned(),
+        ping: result.ok(),
+    }
+}
+
+#[tauri::command]
+pub async fn list_favorites() -> Result<Vec<FavoriteServer>, MineServerError> {
+    load_favorites().map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_favorite(favorite: FavoriteServer) -> Result<FavoriteServer, MineServerError> {
+    let mut favorite = favorite;
+    parse_address(&favorite.address, favorite.bedrock).map_err(MineServerError::validation)?;
+    if favorite.name.trim().is_empty() {
+        favorite.name = favorite.address.trim().to_string();
+    }
+    if favorite.id.is_empty() {
+        favorite.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    // Saving an existing id updates it in place
+    let mut favorites = load_favorites()?;
+    if let Some(existing) = favorites.iter_mut().find(|f| f.id == favorite.id) {
+        *existing = favorite.clone();
+    } else {
+        favorites.push(favorite.clone());
+    }
+
+    save_favorites(&favorites)?;
+    Ok(favorite)
+}
+
+#[tauri::command]
+pub async fn delete_favorite(id: String) -> Result<(), MineServerError> {
+    let mut favorites = load_favorites()?;
+    favorites.retain(|f| f.id != id);
+    save_favorites(&favorites).map_err(Into::into)
+}
+
+/// Pings every favorite in parallel. Each result is also emitted as `favorite-status` as soon
+/// as it arrives, so slow or unreachable servers don't hold up the rest of the list.
+#[tauri::command]
+pub async fn ping_favorites(app: AppHandle) -> Result<Vec<FavoriteStatus>, MineServerError> {
+    let favorites = load_favorites()?;
+    let handles: Vec<_> = favorites.into_iter()
+        .map(|favorite| {
+            let app = app.clone();
+            tokio::task::spawn_blocking(move || {
+                let status = ping_favorite(&favorite);
+                app.emit("favorite-status", status.clone());
+                status
+            })
+        })
+        .collect();
+
+    let mut statuses = Vec::with_capacity(handles.len());
+    for handle in handles {
+        statuses.push(handle.await?);
+    }
+    Ok(statuses)
+}
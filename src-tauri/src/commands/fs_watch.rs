@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::{AppHandle, State};
+use super::path_guard::validate_path;
+use super::runner::ServerEventSink;
+use super::trash::TRASH_DIR;
+use super::error::MineServerError;
+
+// Pushes file changes under a server folder to the UI so the file manager and plugin list
+// refresh on their own. Events are batched so a world save doesn't flood the frontend.
+
+const BATCH_WINDOW: Duration = Duration::from_millis(300);
+
+pub struct DirectoryWatchState {
+    watchers: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>, // watch id -> cancel flag
+}
+
+impl DirectoryWatchState {
+    pub fn new() -> Self {
+        Self {
+            watchers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FsChangeEvent {
+    watch_id: String,
+    server_path: String,
+    created: Vec<String>, // Paths relative to the server folder
+    modified: Vec<String>,
+    removed: Vec<String>,
+}
+
+#[derive(Default)]
+struct PendingChanges {
+    created: BTreeSet<String>,
+    modified: BTreeSet<String>,
+    removed: BTreeSet<String>,
+}
+
+impl PendingChanges {
+    fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    fn add(&mut self, root: &Path, event: notify::Event) {
+        use notify::event::{EventKind, ModifyKind, RenameMode};
+
+        for path in event.paths {
+            let relative = match path.strip_prefix(root) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+            if relative.starts_with(TRASH_DIR) || relative.as_os_str().is_empty() {
+                continue;
+            }
+            let relative = relative.to_string_lossy().to_string();
+
+            match event.kind {
+                EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                    self.removed.remove(&relative);
+                    self.created.insert(relative);
+                },
+                EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                    self.created.remove(&relative);
+                    self.modified.remove(&relative);
+                    self.removed.insert(relative);
+                },
+                // Some platforms report renames as a single event; check what is on disk
+                EventKind::Modify(ModifyKind::Name(_)) => {
+                    if path.exists() { self.created.insert(relative); } else { self.removed.insert(relative); }
+                },
+                EventKind::Modify(_) => {
+                    if !self.created.contains(&relative) {
+                        self.modified.insert(relative);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+/// Watches a server folder recursively and emits `fs-change` events with created, modified and
+/// removed paths until `unwatch_directory` is called. Returns the watch id.
+#[tauri::command]
+pub fn watch_directory(app: AppHandle, state: State<'_, DirectoryWatchState>, server_path: String) -> Result<String, MineServerError> {
+    use notify::{RecursiveMode, Watcher};
+
+    let root = validate_path(&server_path)?;
+    if !root.is_dir() {
+        return Err(MineServerError::not_found("Directory not found"));
+    }
+    let watch_id = uuid::Uuid::new_v4().to_string();
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.watchers.lock().map_err(|e| e.to_string())?.insert(watch_id.clone(), cancel.clone());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| format!("Failed to watch directory: {}", e))?;
+    watcher.watch(&root, RecursiveMode::Recursive).map_err(|e| format!("Failed to watch directory: {}", e))?;
+
+    let watchers = state.watchers.clone();
+    let id = watch_id.clone();
+    std::thread::spawn(move || {
+        let _watcher = watcher; // Dropped (and unregistered) when the loop ends
+        // Events carry absolute paths; compare against the canonical root
+        let root: PathBuf = std::fs::canonicalize(&root).unwrap_or(root);
+        let mut pending = PendingChanges::default();
+        let mut batch_started: Option<Instant> = None;
+
+        while !cancel.load(Ordering::SeqCst) {
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(Ok(event)) => {
+                    pending.add(&root, event);
+                    batch_started.get_or_insert_with(Instant::now);
+                },
+                Ok(Err(_)) | Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {},
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            if batch_started.map(|t| t.elapsed() >= BATCH_WINDOW).unwrap_or(false) {
+                let changes = std::mem::take(&mut pending);
+                batch_started = None;
+                if changes.is_empty() {
+                    continue;
+                }
+                app.emit("fs-change", FsChangeEvent {
+                    watch_id: id.clone(),
+                    server_path: server_path.clone(),
+                    created: changes.created.into_iter().collect(),
+                    modified: changes.modified.into_iter().collect(),
+                    removed: changes.removed.into_iter().collect(),
+                });
+            }
+        }
+
+        if let Ok(mut w) = watchers.lock() {
+            w.remove(&id);
+        }
+    });
+
+    Ok(watch_id)
+}
+
+#[tauri::command]
+pub fn unwatch_directory(state: State<'_, DirectoryWatchState>, watch_id: String) -> Result<(), MineServerError> {
+    if let Some(cancel) = state.watchers.lock().map_err(|e| e.to_string())?.remove(&watch_id) {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
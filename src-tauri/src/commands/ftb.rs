@@ -0,0 +1,349 @@
+use std::path::{Component, Path, PathBuf};
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tauri::{AppHandle, State};
+use super::eula::write_eula_for_new_server;
+use super::http::HttpState;
+use super::path_guard::validate_path;
+use super::runner::ServerEventSink;
+use super::upload::to_hex;
+use super::error::MineServerError;
+
+// Feed The Beast modpacks from the public modpacks.ch API. A pack version lists every file
+// with its target path; files hosted on CurseForge come with a project/file id instead of a
+// URL and are fetched from CurseForge's CDN. The loader (Forge, NeoForge, Fabric) is
+// installed from the pack's targets.
+
+const FTB_API: &str = "https://api.modpacks.ch/public/modpack";
+const PARALLEL_DOWNLOADS: usize = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FtbPackVersion {
+    pub id: u64,
+    pub name: String,
+    #[serde(rename(deserialize = "type"), default)]
+    pub version_type: String, // "Release", "Beta" or "Alpha"
+    #[serde(default)]
+    pub updated: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FtbPack {
+    pub id: u64,
+    pub name: String,
+    pub synopsis: String,
+    pub icon_url: Option<String>,
+    pub installs: u64,
+    pub versions: Vec<FtbPackVersion>, // Newest first
+}
+
+#[derive(Debug, Deserialize)]
+struct PackResponse {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    synopsis: String,
+    #[serde(default)]
+    art: Vec<PackArt>,
+    #[serde(default)]
+    installs: u64,
+    #[serde(default)]
+    versions: Vec<FtbPackVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackArt {
+    url: String,
+    #[serde(rename = "type")]
+    art_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    packs: Vec<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionManifest {
+    files: Vec<ManifestFile>,
+    targets: Vec<ManifestTarget>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ManifestFile {
+    path: String,
+    name: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    sha1: String,
+    #[serde(default)]
+    clientonly: bool,
+    #[serde(default)]
+    curseforge: Option<CurseForgeRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CurseForgeRef {
+    file: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestTarget {
+    name: String, // "minecraft", "forge", "neoforge", "fabric"
+    version: String,
+    #[serde(rename = "type")]
+    target_type: String, // "game" or "modloader"
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FtbInstallProgress {
+    stage: String, // "files" or "loader"
+    current: usize,
+    total: usize,
+    file: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FtbInstallResult {
+    pub game_version: String,
+    pub loader: String,
+    pub loader_version: String,
+    pub jar_file: String, // What to launch; Forge/NeoForge servers start through run.bat/run.sh
+    pub files_installed: usize,
+    pub skipped_client_only: usize,
+}
+
+async fn fetch_pack(client: &reqwest::Client, id: u64) -> Result<FtbPack, String> {
+    let pack: PackResponse = client.get(format!("{}/{}", FTB_API, id))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse pack: {}", e))?;
+
+    let mut versions = pack.versions;
+    versions.sort_by(|a, b| b.updated.cmp(&a.updated));
+    Ok(FtbPack {
+        id: pack.id,
+        name: pack.name,
+        synopsis: pack.synopsis,
+        icon_url: pack.art.into_iter().find(|a| a.art_type == "square").map(|a| a.url),
+        installs: pack.installs,
+        versions,
+    })
+}
+
+#[tauri::command]
+pub async fn search_ftb_packs(http: State<'_, HttpState>, query: String) -> Result<Vec<FtbPack>, MineServerError> {
+    let client = http.client();
+    let url = if query.trim().is_empty() {
+        format!("{}/popular/installs/20", FTB_API)
+    } else {
+        format!("{}/search/20?term={}", FTB_API, urlencoding::encode(query.trim()))
+    };
+    let search: SearchResponse = client.get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    // Search only returns ids; details are one request per pack
+    let packs: Vec<Result<FtbPack, String>> = futures_util::stream::iter(search.packs)
+        .map(|id| {
+            let client = client.clone();
+            async move { fetch_pack(&client, id).await }
+        })
+        .buffered(PARALLEL_DOWNLOADS)
+        .collect()
+        .await;
+    Ok(packs.into_iter().filter_map(|p| p.ok()).collect())
+}
+
+#[tauri::command]
+pub async fn get_ftb_pack_versions(http: State<'_, HttpState>, pack_id: u64) -> Result<Vec<FtbPackVersion>, MineServerError> {
+    Ok(fetch_pack(&http.client(), pack_id).await?.versions)
+}
+
+/// Target of a manifest file inside the server folder. Rejects absolute paths and `..`.
+fn target_path(root: &Path, file: &ManifestFile) -> Result<PathBuf, String> {
+    let relative = Path::new(file.path.trim_start_matches("./")).join(&file.name);
+    if relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(format!("Invalid file path in pack: {}/{}", file.path, file.name));
+    }
+    Ok(root.join(relative))
+}
+
+fn download_url(file: &ManifestFile) -> Option<String> {
+    if !file.url.is_empty() {
+        return Some(file.url.clone());
+    }
+    // CurseForge CDN layout: files/<id / 1000>/<id % 1000>/<name>
+    file.curseforge.as_ref().map(|cf| format!(
+        "https://mediafilez.forgecdn.net/files/{}/{}/{}",
+        cf.file / 1000,
+        cf.file % 1000,
+        urlencoding::encode(&file.name)
+    ))
+}
+
+async fn download_file(client: &reqwest::Client, file: &ManifestFile, target: &Path) -> Result<(), String> {
+    let url = download_url(file).ok_or_else(|| format!("No download for {}", file.name))?;
+    let resp = client.get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", file.name, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", file.name, resp.status()));
+    }
+    let bytes = resp.bytes()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", file.name, e))?;
+
+    if !file.sha1.is_empty() && !to_hex(&Sha1::digest(&bytes)).eq_ignore_ascii_case(&file.sha1) {
+        return Err(format!("Checksum mismatch for {}", file.name));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+    tokio::fs::write(target, &bytes).await.map_err(|e| format!("Failed to write {}: {}", file.name, e))
+}
+
+/// Downloads and runs the Forge/NeoForge installer, or fetches the Fabric server launcher.
+/// Returns the jar to launch.
+async fn install_loader(client: &reqwest::Client, root: &Path, loader: &str, loader_version: &str, game_version: &str) -> Result<String, String> {
+    let (url, file_name) = match loader {
+        "forge" => (format!(
+            "https://maven.minecraftforge.net/net/minecraftforge/forge/{0}-{1}/forge-{0}-{1}-installer.jar",
+            game_version, loader_version
+        ), "forge-installer.jar"),
+        "neoforge" => (format!(
+            "https://maven.neoforged.net/releases/net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar",
+            loader_version
+        ), "neoforge-installer.jar"),
+        "fabric" => {
+            let installers: Vec<serde_json::Value> = client.get("https://meta.fabricmc.net/v2/versions/installer")
+                .send().await.map_err(|e| e.to_string())?
+                .json().await.map_err(|e| e.to_string())?;
+            let installer_version = installers.first()
+                .and_then(|i| i.get("version").and_then(|v| v.as_str()))
+                .ok_or("Fabric installer not found")?;
+            (format!(
+                "https://meta.fabricmc.net/v2/versions/loader/{}/{}/{}/server/jar",
+                game_version, loader_version, installer_version
+            ), "server.jar")
+        },
+        other => return Err(format!("Unsupported mod loader: {}", other)),
+    };
+
+    let bytes = client.get(&url)
+        .send().await.map_err(|e| format!("Failed to download {} installer: {}", loader, e))?
+        .error_for_status().map_err(|e| format!("Failed to download {} installer: {}", loader, e))?
+        .bytes().await.map_err(|e| format!("Failed to download {} installer: {}", loader, e))?;
+    let installer = root.join(file_name);
+    tokio::fs::write(&installer, &bytes).await.map_err(|e| format!("Failed to write installer: {}", e))?;
+    if loader == "fabric" {
+        return Ok(file_name.to_string());
+    }
+
+    let dir = root.to_path_buf();
+    let output = tokio::task::spawn_blocking(move || {
+        std::process::Command::new("java")
+            .arg("-jar")
+            .arg(&installer)
+            .arg("--installServer")
+            .current_dir(&dir)
+            .output()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Could not run the {} installer (is Java installed?): {}", loader, e))?;
+    if !output.status.success() {
+        return Err(format!("{} installer failed: {}", loader, String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(file_name.to_string())
+}
+
+/// Installs an FTB pack version into `server_path`: server-side files, then the loader.
+/// Emits `ftb-install-progress`.
+#[tauri::command]
+pub async fn install_ftb_pack(app: AppHandle, http: State<'_, HttpState>, pack_id: u64, version_id: u64, server_path: String) -> Result<FtbInstallResult, MineServerError> {
+    let root = validate_path(&server_path)?;
+    fs::create_dir_all(&root).map_err(|e| format!("Failed to create server folder: {}", e))?;
+    let client = http.client();
+
+    let manifest: VersionManifest = client.get(format!("{}/{}/{}", FTB_API, pack_id, version_id))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse pack version: {}", e))?;
+
+    let game_version = manifest.targets.iter()
+        .find(|t| t.target_type == "game")
+        .map(|t| t.version.clone())
+        .ok_or("Pack does not specify a Minecraft version")?;
+    let loader = manifest.targets.iter()
+        .find(|t| t.target_type == "modloader")
+        .ok_or("Pack does not specify a mod loader")?;
+    let (loader_name, loader_version) = (loader.name.to_lowercase(), loader.version.clone());
+
+    let skipped_client_only = manifest.files.iter().filter(|f| f.clientonly).count();
+    let files: Vec<(ManifestFile, PathBuf)> = manifest.files.into_iter()
+        .filter(|f| !f.clientonly)
+        .map(|f| target_path(&root, &f).map(|p| (f, p)))
+        .collect::<Result<_, _>>()?;
+    let total = files.len();
+    let done = Arc::new(AtomicUsize::new(0));
+
+    let results: Vec<Result<(), String>> = futures_util::stream::iter(files)
+        .map(|(file, target)| {
+            let client = client.clone();
+            let app = app.clone();
+            let done = done.clone();
+            async move {
+                let result = download_file(&client, &file, &target).await;
+                let current = done.fetch_add(1, Ordering::SeqCst) + 1;
+                app.emit("ftb-install-progress", FtbInstallProgress {
+                    stage: "files".to_string(),
+                    current,
+                    total,
+                    file: Some(file.name.clone()),
+                });
+                result
+            }
+        })
+        .buffer_unordered(PARALLEL_DOWNLOADS)
+        .collect()
+        .await;
+    let errors: Vec<String> = results.into_iter().filter_map(|r| r.err()).collect();
+    if !errors.is_empty() {
+        return Err(format!("{} of {} files failed to download: {}", errors.len(), total, errors.join("; ")).into());
+    }
+
+    app.emit("ftb-install-progress", FtbInstallProgress { stage: "loader".to_string(), current: 0, total: 1, file: None });
+    let jar_file = install_loader(&client, &root, &loader_name, &loader_version, &game_version).await?;
+    write_eula_for_new_server(&root)?;
+
+    Ok(FtbInstallResult {
+        game_version,
+        loader: loader_name,
+        loader_version,
+        jar_file,
+        files_installed: total,
+        skipped_client_only,
+    })
+}
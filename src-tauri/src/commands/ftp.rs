@@ -0,0 +1,653 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use hmac::Hmac;
+use sha2::{Digest, Sha256};
+use tauri::State;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use super::path_guard::{validate_path, validate_server_root};
+use super::settings::{load_settings, update_settings, FtpUser};
+use super::trash::{move_to_trash, TRASH_DIR};
+use super::upload::to_hex;
+use super::http_api::constant_time_eq;
+use super::error::MineServerError;
+
+// Optional built-in FTP server (passive mode only) so files can be managed with FileZilla or
+// WinSCP. Every account is chrooted to one server folder, and each path still goes through
+// the same path guard as the file manager. Off until the user starts it; localhost by default.
+
+const DATA_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_LOGIN_ATTEMPTS: u32 = 3;
+// Failed logins from one address across connections before it is locked out for a while
+const MAX_FAILED_LOGINS_PER_IP: u32 = 10;
+const LOGIN_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+const MAX_COMMAND_LINE: usize = 4096;
+// PBKDF2-HMAC-SHA256 work factor for stored passwords
+const KDF_ROUNDS: u32 = 600_000;
+
+/// Failed logins per client address: count and time of the last one.
+type LoginFailures = Arc<Mutex<HashMap<IpAddr, (u32, Instant)>>>;
+
+pub struct FtpState {
+    pub server: Mutex<Option<RunningFtp>>,
+    login_failures: LoginFailures,
+}
+
+pub struct RunningFtp {
+    pub port: u16,
+    pub bind_address: String,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl FtpState {
+    pub fn new() -> Self {
+        Self {
+            server: Mutex::new(None),
+            login_failures: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FtpStatus {
+    running: bool,
+    port: Option<u16>,
+    bind_address: Option<String>,
+    users: Vec<FtpUserInfo>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FtpUserInfo {
+    username: String,
+    server_path: String,
+    read_only: bool,
+}
+
+fn hash_password(salt: &str, password: &str, rounds: u32) -> String {
+    if rounds == 0 {
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_bytes());
+        hasher.update(password.as_bytes());
+        return to_hex(&hasher.finalize());
+    }
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt.as_bytes(), rounds, &mut hash);
+    to_hex(&hash)
+}
+
+fn locked_out(failures: &LoginFailures, ip: IpAddr) -> bool {
+    let Ok(mut failures) = failures.lock() else { return false };
+    match failures.get(&ip) {
+        Some((_, last)) if last.elapsed() >= LOGIN_LOCKOUT => {
+            failures.remove(&ip);
+            false
+        }
+        Some((count, _)) => *count >= MAX_FAILED_LOGINS_PER_IP,
+        None => false,
+    }
+}
+
+fn record_login(failures: &LoginFailures, ip: IpAddr, success: bool) {
+    if let Ok(mut failures) = failures.lock() {
+        if success {
+            failures.remove(&ip);
+        } else {
+            let entry = failures.entry(ip).or_insert((0, Instant::now()));
+            *entry = (entry.0 + 1, Instant::now());
+        }
+    }
+}
+
+/// Checks the password; blocks for a while by design, so call it off the async runtime.
+fn authenticate(username: &str, password: &str) -> Option<FtpUser> {
+    let user = load_settings().ftp_users.into_iter().find(|u| u.username == username)?;
+    let hash = hash_password(&user.salt, password, user.kdf_rounds);
+    if !constant_time_eq(hash.as_bytes(), user.password_hash.as_bytes()) {
+        return None;
+    }
+    if user.kdf_rounds < KDF_ROUNDS {
+        // Upgrade an account saved with the old single SHA-256 round
+        let upgraded = new_ftp_user(&user.username, password, user.server_path.clone(), user.read_only);
+        let _ = update_settings(|settings| {
+            if let Some(existing) = settings.ftp_users.iter_mut().find(|u| u.username == user.username) {
+                *existing = upgraded;
+            }
+            Ok(())
+        });
+    }
+    Some(user)
+}
+
+fn new_ftp_user(username: &str, password: &str, server_path: String, read_only: bool) -> FtpUser {
+    let salt = uuid::Uuid::new_v4().to_string();
+    FtpUser {
+        password_hash: hash_password(&salt, password, KDF_ROUNDS),
+        salt,
+        kdf_rounds: KDF_ROUNDS,
+        username: username.to_string(),
+        server_path,
+        read_only,
+    }
+}
+
+struct Session {
+    peer_ip: IpAddr, // Data connections must come from the same address as the control connection
+    user: Option<FtpUser>,
+    pending_user: Option<String>,
+    failed_logins: u32,
+    root: PathBuf, // Canonical server folder of the logged in user
+    cwd: String, // Virtual path, always starts with "/"
+    passive: Option<TcpListener>,
+    rename_from: Option<PathBuf>,
+    restart_offset: u64,
+}
+
+/// Lexically joins `arg` onto the virtual `cwd`; `..` can never climb above "/".
+fn virtual_path(cwd: &str, arg: &str) -> String {
+    let mut parts: Vec<String> = if arg.starts_with('/') {
+        Vec::new()
+    } else {
+        cwd.split('/').filter(|p| !p.is_empty()).map(|p| p.to_string()).collect()
+    };
+    for component in Path::new(&arg.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::ParentDir => { parts.pop(); },
+            _ => {},
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+impl Session {
+    /// Real path for a client path, checked against the user's root and the global path guard.
+    fn resolve(&self, arg: &str) -> Result<PathBuf, String> {
+        let virt = virtual_path(&self.cwd, arg);
+        let real = self.root.join(virt.trim_start_matches('/'));
+        let resolved = validate_path(&real.to_string_lossy())?;
+        // Symlinks inside the folder must not lead out of this user's root
+        if !resolved.starts_with(&self.root) {
+            return Err("Permission denied".to_string());
+        }
+        Ok(resolved)
+    }
+
+    /// Like `resolve`, for operations that change files: the root itself is off limits and
+    /// read-only accounts are refused.
+    fn resolve_writable(&self, arg: &str) -> Result<PathBuf, String> {
+        if self.user.as_ref().map(|u| u.read_only).unwrap_or(true) {
+            return Err("Permission denied (read-only account)".to_string());
+        }
+        let path = self.resolve(arg)?;
+        if path == self.root {
+            return Err("Permission denied".to_string());
+        }
+        Ok(path)
+    }
+}
+
+async fn reply(writer: &mut OwnedWriteHalf, code: u16, message: &str) -> std::io::Result<()> {
+    writer.write_all(format!("{} {}\r\n", code, message).as_bytes()).await
+}
+
+async fn open_data(session: &mut Session) -> Result<TcpStream, String> {
+    let listener = session.passive.take().ok_or("Use PASV or EPSV first")?;
+    match tokio::time::timeout(DATA_CONNECT_TIMEOUT, listener.accept()).await {
+        // Someone else racing the client to the passive port must not get its data
+        Ok(Ok((stream, peer))) if peer.ip().to_canonical() == session.peer_ip => Ok(stream),
+        Ok(Ok(_)) => Err("Data connection from a different address refused".to_string()),
+        _ => Err("Data connection failed".to_string()),
+    }
+}
+
+enum CommandLine {
+    Line(String),
+    TooLong,
+    Closed,
+}
+
+/// Reads one command line, at most MAX_COMMAND_LINE bytes; the rest of a longer line is skipped.
+async fn read_command(reader: &mut BufReader<OwnedReadHalf>) -> std::io::Result<CommandLine> {
+    let mut buf = Vec::new();
+    let n = (&mut *reader).take(MAX_COMMAND_LINE as u64).read_until(b'\n', &mut buf).await?;
+    if n == 0 {
+        return Ok(CommandLine::Closed);
+    }
+    if buf.len() >= MAX_COMMAND_LINE && !buf.ends_with(b"\n") {
+        loop {
+            buf.clear();
+            let n = (&mut *reader).take(MAX_COMMAND_LINE as u64).read_until(b'\n', &mut buf).await?;
+            if n == 0 || buf.ends_with(b"\n") {
+                return Ok(CommandLine::TooLong);
+            }
+        }
+    }
+    let line = String::from_utf8_lossy(&buf);
+    Ok(CommandLine::Line(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+fn list_line(path: &Path) -> Option<String> {
+    let meta = std::fs::metadata(path).ok()?;
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let modified: chrono::DateTime<chrono::Local> = meta.modified().ok()?.into();
+    Some(format!(
+        "{} 1 owner group {:>12} {} {}\r\n",
+        if meta.is_dir() { "drwxr-xr-x" } else { "-rw-r--r--" },
+        if meta.is_dir() { 0 } else { meta.len() },
+        modified.format("%b %d %H:%M"),
+        name
+    ))
+}
+
+fn listing(dir: &Path, names_only: bool) -> Result<String, String> {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter(|e| e.file_name() != TRASH_DIR)
+        .map(|e| e.path())
+        .collect();
+    entries.sort();
+    Ok(entries.iter()
+        .filter_map(|p| if names_only {
+            p.file_name().map(|n| format!("{}\r\n", n.to_string_lossy()))
+        } else {
+            list_line(p)
+        })
+        .collect())
+}
+
+async fn send_file(session: &mut Session, path: &Path) -> Result<(), String> {
+    let mut file = tokio::fs::File::open(path).await.map_err(|e| e.to_string())?;
+    if session.restart_offset > 0 {
+        use tokio::io::AsyncSeekExt;
+        file.seek(std::io::SeekFrom::Start(session.restart_offset)).await.map_err(|e| e.to_string())?;
+    }
+    let mut data = open_data(session).await?;
+    tokio::io::copy(&mut file, &mut data).await.map_err(|e| e.to_string())?;
+    data.shutdown().await.map_err(|e| e.to_string())
+}
+
+async fn receive_file(session: &mut Session, path: &Path, append: bool) -> Result<(), String> {
+    let mut options = tokio::fs::OpenOptions::new();
+    options.create(true).write(true);
+    if append {
+        options.append(true);
+    } else if session.restart_offset == 0 {
+        options.truncate(true);
+    }
+    let mut file = options.open(path).await.map_err(|e| e.to_string())?;
+    if !append && session.restart_offset > 0 {
+        use tokio::io::AsyncSeekExt;
+        file.seek(std::io::SeekFrom::Start(session.restart_offset)).await.map_err(|e| e.to_string())?;
+    }
+    let mut data = open_data(session).await?;
+    tokio::io::copy(&mut data, &mut file).await.map_err(|e| e.to_string())?;
+    file.flush().await.map_err(|e| e.to_string())
+}
+
+async fn handle_connection(stream: TcpStream, login_failures: LoginFailures) -> std::io::Result<()> {
+    let local_ip = stream.local_addr()?.ip();
+    let peer_ip = stream.peer_addr()?.ip().to_canonical();
+    let (read_half, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(read_half);
+
+    if locked_out(&login_failures, peer_ip) {
+        reply(&mut writer, 421, "Too many failed logins, try again later").await?;
+        return Ok(());
+    }
+
+    let mut session = Session {
+        peer_ip,
+        user: None,
+        pending_user: None,
+        failed_logins: 0,
+        root: PathBuf::new(),
+        cwd: "/".to_string(),
+        passive: None,
+        rename_from: None,
+        restart_offset: 0,
+    };
+
+    reply(&mut writer, 220, "MineServer FTP ready").await?;
+
+    loop {
+        let line = match read_command(&mut reader).await? {
+            CommandLine::Line(line) => line,
+            CommandLine::TooLong => {
+                reply(&mut writer, 500, "Command line too long").await?;
+                continue;
+            }
+            CommandLine::Closed => break,
+        };
+        let line = line.as_str();
+        let (command, arg) = match line.split_once(' ') {
+            Some((c, a)) => (c.to_uppercase(), a.to_string()),
+            None => (line.to_uppercase(), String::new()),
+        };
+        let logged_in = session.user.is_some();
+
+        // Anything else needs a login first
+        if !logged_in && !matches!(command.as_str(), "USER" | "PASS" | "QUIT" | "FEAT" | "SYST" | "OPTS" | "NOOP" | "AUTH") {
+            reply(&mut writer, 530, "Please log in with USER and PASS").await?;
+            continue;
+        }
+
+        match command.as_str() {
+            "USER" => {
+                session.pending_user = Some(arg);
+                reply(&mut writer, 331, "Password required").await?;
+            },
+            "PASS" => {
+                if locked_out(&login_failures, peer_ip) {
+                    reply(&mut writer, 421, "Too many failed logins, try again later").await?;
+                    break;
+                }
+                let username = session.pending_user.take().unwrap_or_default();
+                let password = arg.to_string();
+                let user = tokio::task::spawn_blocking(move || authenticate(&username, &password)).await.ok().flatten();
+                let root = user.as_ref().and_then(|u| validate_server_root(&u.server_path).ok());
+                match (user, root) {
+                    (Some(user), Some(root)) => {
+                        record_login(&login_failures, peer_ip, true);
+                        session.user = Some(user);
+                        session.root = root;
+                        session.cwd = "/".to_string();
+                        reply(&mut writer, 230, "Logged in").await?;
+                    },
+                    _ => {
+                        record_login(&login_failures, peer_ip, false);
+                        session.failed_logins += 1;
+                        tokio::time::sleep(Duration::from_secs(1)).await; // Slow down guessing
+                        reply(&mut writer, 530, "Login incorrect").await?;
+                        if session.failed_logins >= MAX_LOGIN_ATTEMPTS {
+                            break;
+                        }
+                    }
+                }
+            },
+            "AUTH" => reply(&mut writer, 502, "TLS is not supported").await?,
+            "SYST" => reply(&mut writer, 215, "UNIX Type: L8").await?,
+            "FEAT" => writer.write_all(b"211-Features:\r\n SIZE\r\n MDTM\r\n REST STREAM\r\n EPSV\r\n PASV\r\n UTF8\r\n211 End\r\n").await?,
+            "OPTS" => reply(&mut writer, 200, "OK").await?,
+            "NOOP" => reply(&mut writer, 200, "OK").await?,
+            "TYPE" | "MODE" | "STRU" => reply(&mut writer, 200, "OK").await?,
+            "QUIT" => {
+                reply(&mut writer, 221, "Bye").await?;
+                break;
+            },
+            "PWD" | "XPWD" => reply(&mut writer, 257, &format!("\"{}\" is the current directory", session.cwd)).await?,
+            "CWD" | "XCWD" | "CDUP" | "XCUP" => {
+                let target = if command.contains("UP") { "..".to_string() } else { arg };
+                match session.resolve(&target) {
+                    Ok(path) if path.is_dir() => {
+                        session.cwd = virtual_path(&session.cwd, &target);
+                        reply(&mut writer, 250, "Directory changed").await?;
+                    },
+                    _ => reply(&mut writer, 550, "No such directory").await?,
+                }
+            },
+            "PASV" | "EPSV" => {
+                let listener = match TcpListener::bind((local_ip, 0)).await {
+                    Ok(l) => l,
+                    Err(_) => {
+                        reply(&mut writer, 425, "Cannot open data connection").await?;
+                        continue;
+                    }
+                };
+                let port = listener.local_addr()?.port();
+                session.passive = Some(listener);
+                if command == "EPSV" {
+                    reply(&mut writer, 229, &format!("Entering Extended Passive Mode (|||{}|)", port)).await?;
+                } else {
+                    let ip = match local_ip {
+                        std::net::IpAddr::V4(v4) => v4.octets(),
+                        std::net::IpAddr::V6(_) => {
+                            reply(&mut writer, 522, "Use EPSV for IPv6").await?;
+                            continue;
+                        }
+                    };
+                    reply(&mut writer, 227, &format!(
+                        "Entering Passive Mode ({},{},{},{},{},{})",
+                        ip[0], ip[1], ip[2], ip[3], port >> 8, port & 0xff
+                    )).await?;
+                }
+            },
+            "PORT" | "EPRT" => reply(&mut writer, 502, "Active mode is not supported, use passive mode").await?,
+            "LIST" | "NLST" | "MLSD" => {
+                // Clients often pass flags like "-la"
+                let target = if arg.starts_with('-') { String::new() } else { arg };
+                let result = match session.resolve(&target) {
+                    Ok(path) if path.is_dir() => listing(&path, command == "NLST"),
+                    Ok(path) if path.is_file() => list_line(&path).ok_or_else(|| "Not found".to_string()),
+                    Ok(_) => Err("Not found".to_string()),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(text) => {
+                        reply(&mut writer, 150, "Opening data connection").await?;
+                        match open_data(&mut session).await {
+                            Ok(mut data) => {
+                                let _ = data.write_all(text.as_bytes()).await;
+                                let _ = data.shutdown().await;
+                                reply(&mut writer, 226, "Transfer complete").await?;
+                            },
+                            Err(e) => reply(&mut writer, 425, &e).await?,
+                        }
+                    },
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+            },
+            "REST" => {
+                session.restart_offset = arg.trim().parse().unwrap_or(0);
+                reply(&mut writer, 350, "Restart position accepted").await?;
+            },
+            "RETR" => {
+                match session.resolve(&arg) {
+                    Ok(path) if path.is_file() => {
+                        reply(&mut writer, 150, "Opening data connection").await?;
+                        match send_file(&mut session, &path).await {
+                            Ok(()) => reply(&mut writer, 226, "Transfer complete").await?,
+                            Err(e) => reply(&mut writer, 451, &e).await?,
+                        }
+                    },
+                    Ok(_) => reply(&mut writer, 550, "Not a file").await?,
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+                session.restart_offset = 0;
+            },
+            "STOR" | "APPE" => {
+                match session.resolve_writable(&arg) {
+                    Ok(path) if !path.is_dir() => {
+                        reply(&mut writer, 150, "Opening data connection").await?;
+                        match receive_file(&mut session, &path, command == "APPE").await {
+                            Ok(()) => reply(&mut writer, 226, "Transfer complete").await?,
+                            Err(e) => reply(&mut writer, 451, &e).await?,
+                        }
+                    },
+                    Ok(_) => reply(&mut writer, 550, "Is a directory").await?,
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+                session.restart_offset = 0;
+            },
+            "DELE" | "RMD" | "XRMD" => {
+                // Same as the file manager: deleted items go to the server's trash
+                match session.resolve_writable(&arg).and_then(|p| move_to_trash(&p, Some(&session.root))) {
+                    Ok(_) => reply(&mut writer, 250, "Deleted").await?,
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+            },
+            "MKD" | "XMKD" => {
+                match session.resolve_writable(&arg).and_then(|p| std::fs::create_dir_all(&p).map_err(|e| e.to_string())) {
+                    Ok(()) => reply(&mut writer, 257, &format!("\"{}\" created", virtual_path(&session.cwd, &arg))).await?,
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+            },
+            "RNFR" => {
+                match session.resolve_writable(&arg) {
+                    Ok(path) if path.exists() => {
+                        session.rename_from = Some(path);
+                        reply(&mut writer, 350, "Ready for RNTO").await?;
+                    },
+                    Ok(_) => reply(&mut writer, 550, "Not found").await?,
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+            },
+            "RNTO" => {
+                let from = match session.rename_from.take() {
+                    Some(f) => f,
+                    None => {
+                        reply(&mut writer, 503, "RNFR first").await?;
+                        continue;
+                    }
+                };
+                match session.resolve_writable(&arg).and_then(|to| std::fs::rename(&from, &to).map_err(|e| e.to_string())) {
+                    Ok(()) => reply(&mut writer, 250, "Renamed").await?,
+                    Err(e) => reply(&mut writer, 550, &e).await?,
+                }
+            },
+            "SIZE" => {
+                match session.resolve(&arg).and_then(|p| std::fs::metadata(&p).map_err(|e| e.to_string())) {
+                    Ok(meta) if meta.is_file() => reply(&mut writer, 213, &meta.len().to_string()).await?,
+                    _ => reply(&mut writer, 550, "Not a file").await?,
+                }
+            },
+            "MDTM" => {
+                match session.resolve(&arg).and_then(|p| std::fs::metadata(&p).and_then(|m| m.modified()).map_err(|e| e.to_string())) {
+                    Ok(modified) => {
+                        let utc: chrono::DateTime<chrono::Utc> = modified.into();
+                        reply(&mut writer, 213, &utc.format("%Y%m%d%H%M%S").to_string()).await?
+                    },
+                    Err(_) => reply(&mut writer, 550, "Not found").await?,
+                }
+            },
+            _ => reply(&mut writer, 502, "Command not implemented").await?,
+        }
+    }
+    Ok(())
+}
+
+async fn serve(listener: TcpListener, login_failures: LoginFailures) {
+    // Sessions live in the set, so stopping the server (aborting this task) ends them too
+    let mut sessions = tokio::task::JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _addr)) => {
+                    let login_failures = login_failures.clone();
+                    sessions.spawn(async move {
+                        if let Err(e) = handle_connection(stream, login_failures).await {
+                            eprintln!("[FTP] Connection error: {}", e);
+                        }
+                    });
+                },
+                Err(e) => {
+                    eprintln!("[FTP] Accept failed: {}", e);
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                }
+            },
+            Some(_) = sessions.join_next() => {},
+        }
+    }
+}
+
+fn ftp_status(state: &FtpState) -> FtpStatus {
+    let users = load_settings().ftp_users.into_iter()
+        .map(|u| FtpUserInfo { username: u.username, server_path: u.server_path, read_only: u.read_only })
+        .collect();
+    match state.server.lock().ok().as_ref().and_then(|g| g.as_ref()) {
+        Some(running) => FtpStatus {
+            running: true,
+            port: Some(running.port),
+            bind_address: Some(running.bind_address.clone()),
+            users,
+        },
+        None => FtpStatus { running: false, port: None, bind_address: None, users },
+    }
+}
+
+#[tauri::command]
+pub async fn start_ftp_server(ftp_state: State<'_, FtpState>, port: u16, bind_address: Option<String>) -> Result<FtpStatus, MineServerError> {
+    // Localhost by default; LAN access is an explicit choice (FTP is unencrypted)
+    let bind = bind_address.unwrap_or_else(|| "127.0.0.1".to_string());
+
+    if let Ok(guard) = ftp_state.server.lock() {
+        if guard.is_some() {
+            return Err(MineServerError::conflict("FTP server is already running"));
+        }
+    }
+    if load_settings().ftp_users.is_empty() {
+        return Err(MineServerError::validation("Add an FTP account first"));
+    }
+
+    let listener = TcpListener::bind((bind.as_str(), port))
+        .await
+        .map_err(|e| format!("Failed to bind {}:{}: {}", bind, port, e))?;
+
+    {
+        // Another start may have finished while this one was binding
+        let mut guard = ftp_state.server.lock().map_err(|e| e.to_string())?;
+        if guard.is_some() {
+            return Err(MineServerError::conflict("FTP server is already running"));
+        }
+        let handle = tauri::async_runtime::spawn(serve(listener, ftp_state.login_failures.clone()));
+        *guard = Some(RunningFtp { port, bind_address: bind, handle });
+    }
+    Ok(ftp_status(&ftp_state))
+}
+
+#[tauri::command]
+pub fn stop_ftp_server(ftp_state: State<'_, FtpState>) -> Result<(), MineServerError> {
+    let mut guard = ftp_state.server.lock().map_err(|e| e.to_string())?;
+    if let Some(running) = guard.take() {
+        running.handle.abort();
+        Ok(())
+    } else {
+        Err(MineServerError::conflict("FTP server is not running"))
+    }
+}
+
+#[tauri::command]
+pub fn get_ftp_status(ftp_state: State<'_, FtpState>) -> FtpStatus {
+    ftp_status(&ftp_state)
+}
+
+/// Adds or updates an account. The password is stored salted and hashed.
+#[tauri::command]
+pub fn set_ftp_user(username: String, password: String, server_path: String, read_only: Option<bool>) -> Result<(), MineServerError> {
+    let username = username.trim().to_string();
+    if username.is_empty() || username.contains(char::is_whitespace) {
+        return Err(MineServerError::validation("Invalid username"));
+    }
+    if password.len() < 8 {
+        return Err(MineServerError::validation("Password must be at least 8 characters"));
+    }
+    let root = validate_server_root(&server_path)?;
+
+    let user = new_ftp_user(&username, &password, root.to_string_lossy().to_string(), read_only.unwrap_or(false));
+
+    update_settings(|settings| {
+        settings.ftp_users.retain(|u| u.username != username);
+        settings.ftp_users.push(user);
+        Ok(())
+    }).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn remove_ftp_user(username: String) -> Result<(), MineServerError> {
+    let removed = update_settings(|settings| {
+        let before = settings.ftp_users.len();
+        settings.ftp_users.retain(|u| u.username != username);
+        Ok(settings.ftp_users.len() != before)
+    })?;
+    if !removed {
+        return Err(MineServerError::not_found("FTP account not found"));
+    }
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use std::path::Path;
+use std::fs;
+use serde::Serialize;
+use super::registry::find_registered_server;
+use super::error::MineServerError;
+
+// Optional GC logging for Java servers (`gc_logging` in the registry) and a summary of the log for
+// the startup tab: how much of the uptime goes to GC pauses, the longest pauses, and whether the
+// heap left after each collection keeps growing. The flag is passed through JDK_JAVA_OPTIONS,
+// which only Java 9+ launchers read, so Java 8 servers (no unified logging) still start normally.
+
+const GC_LOG_FILE: &str = "logs/gc.log";
+// The JVM starts a fresh gc.log on every start and keeps the previous runs as gc.log.0..4
+const GC_LOG_OPTION: &str = "-Xlog:gc:file=logs/gc.log:time,uptime:filecount=5,filesize=10m";
+const MAX_SAMPLES: usize = 200;
+
+const GC_TIME_WARN_PERCENT: f64 = 5.0;
+const GC_TIME_HIGH_PERCENT: f64 = 10.0;
+const LONG_PAUSE_MS: f64 = 500.0;
+const HEAP_FULL_PERCENT: f64 = 80.0;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcHeapSample {
+    pub uptime_secs: f64,
+    pub before_mb: f64,
+    pub after_mb: f64,
+    pub capacity_mb: f64,
+    pub pause_ms: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcSummary {
+    pub enabled: bool,
+    pub log_found: bool,
+    pub uptime_secs: f64, // Covered by the log, i.e. the current or last run
+    pub pauses: u32,
+    pub full_gcs: u32,
+    pub total_pause_ms: f64,
+    pub avg_pause_ms: f64,
+    pub max_pause_ms: f64,
+    pub gc_time_percent: f64,
+    pub heap_trend: String, // "rising", "stable", "falling" or "unknown": heap left after GC over the run
+    pub samples: Vec<GcHeapSample>, // At most MAX_SAMPLES, evenly spread over the run
+    pub insights: Vec<String>,
+}
+
+/// The GC logging option for a server start, or None if it is off or the user's own flags
+/// already configure GC logging. Creates the logs folder, which the JVM won't.
+pub fn java_options(id: &str, server_path: &Path, startup_flags: Option<&str>) -> Option<String> {
+    let enabled = find_registered_server(id).map(|s| s.gc_logging).unwrap_or(false);
+    let flags = startup_flags.unwrap_or_default();
+    if !enabled || flags.contains("-Xlog:gc") || flags.contains("-Xloggc") {
+        return None;
+    }
+    fs::create_dir_all(server_path.join("logs")).ok()?;
+    let options = match std::env::var("JDK_JAVA_OPTIONS") {
+        Ok(existing) if !existing.trim().is_empty() => format!("{} {}", existing, GC_LOG_OPTION),
+        _ => GC_LOG_OPTION.to_string(),
+    };
+    Some(options)
+}
+
+/// "512M", "1G" or "2048K" in MB.
+fn parse_size(value: &str) -> Option<f64> {
+    let (number, unit) = value.split_at(value.len().checked_sub(1)?);
+    let number: f64 = number.parse().ok()?;
+    match unit {
+        "K" => Some(number / 1024.0),
+        "M" => Some(number),
+        "G" => Some(number * 1024.0),
+        _ => None,
+    }
+}
+
+/// Before, after and capacity from "100M->20M(1024M)".
+fn parse_heap(token: &str) -> Option<(f64, f64, f64)> {
+    let (before, rest) = token.split_once("->")?;
+    let (after, capacity) = rest.split_once('(')?;
+    Some((parse_size(before)?, parse_size(after)?, parse_size(capacity.strip_suffix(')')?)?))
+}
+
+/// Seconds since JVM start from the "[12.345s]" decorator.
+fn parse_uptime(line: &str) -> Option<f64> {
+    line.split('[')
+        .filter_map(|part| part.split(']').next())
+        .find_map(|inner| inner.strip_suffix('s')?.parse().ok())
+}
+
+struct Pause {
+    uptime_secs: f64,
+    duration_ms: f64,
+    full: bool,
+    heap: Option<(f64, f64, f64)>,
+}
+
+/// A stop-the-world pause line such as
+/// "[2024-05-01T10:00:00.123+0200][12.345s] GC(3) Pause Young (Normal) (G1 Evacuation Pause) 100M->20M(1024M) 5.123ms".
+/// Concurrent phases and the "Using G1" header are skipped.
+fn parse_pause(line: &str) -> Option<Pause> {
+    let event = &line[line.find("GC(")?..];
+    if !event.contains(" Pause ") {
+        return None;
+    }
+    let duration_ms = event.trim_end().strip_suffix("ms")?.rsplit(' ').next()?.parse().ok()?;
+    Some(Pause {
+        uptime_secs: parse_uptime(line).unwrap_or(0.0),
+        duration_ms,
+        full: event.contains("Pause Full"),
+        heap: event.split_whitespace().find(|t| t.contains("->")).and_then(parse_heap),
+    })
+}
+
+fn average(values: &[f64]) -> f64 {
+    if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 }
+}
+
+/// Compares the heap left after GC in the first and last third of the run. Early samples are
+/// skipped because the heap always grows while the world loads.
+fn heap_trend(samples: &[GcHeapSample]) -> String {
+    if samples.len() < 9 {
+        return "unknown".to_string();
+    }
+    let third = samples.len() / 3;
+    let first = average(&samples[third / 2..third].iter().map(|s| s.after_mb).collect::<Vec<_>>());
+    let last = average(&samples[samples.len() - third..].iter().map(|s| s.after_mb).collect::<Vec<_>>());
+    let trend = if first <= 0.0 {
+        "unknown"
+    } else if last > first * 1.25 {
+        "rising"
+    } else if last < first * 0.8 {
+        "falling"
+    } else {
+        "stable"
+    };
+    trend.to_string()
+}
+
+fn insights(summary: &GcSummary, allocated_mb: u32) -> Vec<String> {
+    let mut insights = Vec::new();
+    if summary.gc_time_percent >= GC_TIME_HIGH_PERCENT {
+        insights.push(format!(
+            "Your server is spending {:.0}% of its time in GC pauses. Increase its RAM (currently {} MB) to give the collector room.",
+            summary.gc_time_percent, allocated_mb
+        ));
+    } else if summary.gc_time_percent >= GC_TIME_WARN_PERCENT {
+        insights.push(format!(
+            "{:.1}% of the uptime goes to GC pauses. That is noticeable; a bit more RAM would help.",
+            summary.gc_time_percent
+        ));
+    }
+    if summary.full_gcs > 0 {
+        insights.push(format!(
+            "{} full GC(s) stopped the server completely. They happen when the heap runs out, so the server needs more RAM or fewer loaded chunks.",
+            summary.full_gcs
+        ));
+    }
+    if summary.max_pause_ms >= LONG_PAUSE_MS {
+        insights.push(format!(
+            "The longest pause took {:.0} ms, long enough for a lag spike. Aikar's flags tune G1 for shorter pauses.",
+            summary.max_pause_ms
+        ));
+    }
+    let recent: Vec<_> = summary.samples.iter().rev().take(10).collect();
+    let fill = average(&recent.iter().filter(|s| s.capacity_mb > 0.0).map(|s| s.after_mb / s.capacity_mb * 100.0).collect::<Vec<_>>());
+    if fill >= HEAP_FULL_PERCENT {
+        insights.push(format!("Even right after GC the heap is {:.0}% full. The server is close to running out of memory.", fill));
+    }
+    if summary.heap_trend == "rising" {
+        insights.push("The memory still in use after each GC keeps growing. A plugin or mod may be leaking memory, or chunks are piling up; a restart frees it for now.".to_string());
+    }
+    if insights.is_empty() && summary.pauses > 0 {
+        insights.push("GC looks healthy: pauses are short and rare.".to_string());
+    }
+    insights
+}
+
+fn summarize(content: &str, allocated_mb: u32) -> GcSummary {
+    let pauses: Vec<Pause> = content.lines().filter_map(parse_pause).collect();
+    let uptime_secs = content.lines().rev().find_map(parse_uptime).unwrap_or(0.0);
+    let durations: Vec<f64> = pauses.iter().map(|p| p.duration_ms).collect();
+    let total_pause_ms: f64 = durations.iter().sum();
+
+    let all_samples: Vec<GcHeapSample> = pauses.iter()
+        .filter_map(|p| p.heap.map(|(before_mb, after_mb, capacity_mb)| GcHeapSample {
+            uptime_secs: p.uptime_secs,
+            before_mb,
+            after_mb,
+            capacity_mb,
+            pause_ms: p.duration_ms,
+        }))
+        .collect();
+    let step = all_samples.len().div_ceil(MAX_SAMPLES).max(1);
+
+    let mut summary = GcSummary {
+        enabled: true,
+        log_found: true,
+        uptime_secs,
+        pauses: pauses.len() as u32,
+        full_gcs: pauses.iter().filter(|p| p.full).count() as u32,
+        total_pause_ms,
+        avg_pause_ms: average(&durations),
+        max_pause_ms: durations.iter().cloned().fold(0.0, f64::max),
+        gc_time_percent: if uptime_secs > 0.0 { total_pause_ms / (uptime_secs * 1000.0) * 100.0 } else { 0.0 },
+        heap_trend: heap_trend(&all_samples),
+        samples: all_samples.into_iter().step_by(step).collect(),
+        insights: vec![],
+    };
+    summary.insights = insights(&summary, allocated_mb);
+    summary
+}
+
+/// Summarizes the GC log of the server's current (or last) run.
+#[tauri::command]
+pub async fn analyze_gc_log(id: String) -> Result<GcSummary, MineServerError> {
+    let server = find_registered_server(&id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))?;
+    let path = Path::new(&server.path).join(GC_LOG_FILE);
+    if !path.exists() {
+        let hint = if server.gc_logging {
+            "No GC log yet. It is written from the next start on, and needs Java 9 or newer."
+        } else {
+            "Turn on GC logging and restart the server to collect data."
+        };
+        return Ok(GcSummary {
+            enabled: server.gc_logging,
+            heap_trend: "unknown".to_string(),
+            insights: vec![hint.to_string()],
+            ..Default::default()
+        });
+    }
+
+    let content = tokio::task::spawn_blocking(move || fs::read_to_string(&path)).await?
+        .map_err(|e| format!("Failed to read GC log: {}", e))?;
+    let mut summary = summarize(&content, server.ram);
+    summary.enabled = server.gc_logging;
+    Ok(summary)
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use reqwest::{Client, StatusCode};
+use reqwest::header::{ETAG, IF_NONE_MATCH, RETRY_AFTER};
+use serde::de::DeserializeOwned;
+use super::settings::{load_settings, AppSettings};
+
+// One reqwest client for the whole app so connections (and TLS sessions) to Modrinth, Mojang,
+// PaperMC etc. are reused. Built from settings and rebuilt when the proxy/timeouts change.
+
+// Modrinth asks for "project/version (contact)"; the user's own contact goes next to ours
+const USER_AGENT_BASE: &str = concat!("MF9CODING/MineServer/", env!("CARGO_PKG_VERSION"));
+const PROJECT_URL: &str = "github.com/MF9CODING/MineServer";
+const MAX_CONTACT_LEN: usize = 128;
+/// minecraft.net rejects non-browser agents, so Bedrock downloads send this instead.
+pub const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+// Search-style GETs (typing in the plugin search box) go through a short TTL cache and a
+// per-host spacing so we don't collect 429s from Modrinth/Spiget; stale entries are revalidated
+// with If-None-Match where the API sends ETags.
+const CACHE_TTL: Duration = Duration::from_secs(120);
+const CACHE_MAX_ENTRIES: usize = 256;
+const MIN_REQUEST_SPACING: Duration = Duration::from_millis(250);
+
+struct CachedResponse {
+    body: String,
+    etag: Option<String>,
+    fetched_at: Instant,
+}
+
+pub struct HttpState {
+    client: RwLock<Client>,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+    next_slot: Mutex<HashMap<String, Instant>>, // host -> earliest time for the next request
+}
+
+impl HttpState {
+    pub fn new() -> Self {
+        let settings = load_settings();
+        // A bad proxy setting must not keep the app from starting
+        let client = build_client(&settings).unwrap_or_else(|_| Client::new());
+        Self {
+            client: RwLock::new(client),
+            cache: Mutex::new(HashMap::new()),
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The shared client. Cloning is cheap; clones share the connection pool.
+    pub fn client(&self) -> Client {
+        self.client.read().map(|c| c.clone()).unwrap_or_else(|e| e.into_inner().clone())
+    }
+
+    /// Waits until a request to `url`'s host is allowed. Callers are spaced out rather than
+    /// rejected, so a burst of keystrokes turns into a short queue.
+    pub async fn throttle(&self, url: &str) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let wait = {
+            let mut slots = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = slots.get(&host).copied().filter(|t| *t > now).unwrap_or(now);
+            slots.insert(host, slot + MIN_REQUEST_SPACING);
+            slot - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// GET `url` and parse the JSON body, served from cache while fresh.
+    pub async fn get_json_cached<T: DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        let body = self.get_text_cached(url).await?;
+        serde_json::from_str(&body).map_err(|e| format!("Failed to parse response: {}", e))
+    }
+
+    async fn get_text_cached(&self, url: &str) -> Result<String, String> {
+        let stale_etag = {
+            let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            match cache.get(url) {
+                Some(entry) if entry.fetched_at.elapsed() < CACHE_TTL => return Ok(entry.body.clone()),
+                Some(entry) => entry.etag.clone(),
+                None => None,
+            }
+        };
+
+        self.throttle(url).await;
+        let mut request = self.client().get(url);
+        if let Some(etag) = &stale_etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let resp = request.send().await.map_err(|e| format!("Request failed: {}", e))?;
+
+        match resp.status() {
+            StatusCode::NOT_MODIFIED => {
+                let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(entry) = cache.get_mut(url) {
+                    entry.fetched_at = Instant::now();
+                    return Ok(entry.body.clone());
+                }
+                Err("Server answered 304 without a cached copy".to_string())
+            },
+            StatusCode::TOO_MANY_REQUESTS => {
+                // Better an older result than an error while the user is typing
+                if let Some(entry) = self.cache.lock().unwrap_or_else(|e| e.into_inner()).get(url) {
+                    return Ok(entry.body.clone());
+                }
+                let retry = resp.headers().get(RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| format!(", try again in {}s", v))
+                    .unwrap_or_default();
+                Err(format!("Rate limited by the server{}", retry))
+            },
+            status if !status.is_success() => Err(format!("Request failed with status {}", status)),
+            _ => {
+                let etag = resp.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_string);
+                let body = resp.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+                let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+                if cache.len() >= CACHE_MAX_ENTRIES && !cache.contains_key(url) {
+                    if let Some(oldest) = cache.iter().min_by_key(|(_, e)| e.fetched_at).map(|(k, _)| k.clone()) {
+                        cache.remove(&oldest);
+                    }
+                }
+                cache.insert(url.to_string(), CachedResponse { body: body.clone(), etag, fetched_at: Instant::now() });
+                Ok(body)
+            },
+        }
+    }
+
+    /// Rebuilds the client after the network settings changed.
+    pub fn reconfigure(&self, settings: &AppSettings) -> Result<(), String> {
+        let client = build_client(settings)?;
+        *self.client.write().unwrap_or_else(|e| e.into_inner()) = client;
+        Ok(())
+    }
+}
+
+/// User-Agent for API requests, with the contact from the settings when one is set.
+pub fn user_agent(settings: &AppSettings) -> Result<String, String> {
+    let contact = settings.http_contact.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    let Some(contact) = contact else {
+        return Ok(format!("{} ({})", USER_AGENT_BASE, PROJECT_URL));
+    };
+    if contact.len() > MAX_CONTACT_LEN || contact.chars().any(|c| c.is_control() || c == '(' || c == ')') {
+        return Err(format!("The contact must be at most {} characters, without parentheses", MAX_CONTACT_LEN));
+    }
+    Ok(format!("{} ({}; {})", USER_AGENT_BASE, PROJECT_URL, contact))
+}
+
+/// A client with the current settings, for background code that has no `HttpState` at hand.
+pub fn standalone_client() -> Client {
+    build_client(&load_settings()).unwrap_or_default()
+}
+
+pub fn build_client(settings: &AppSettings) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .user_agent(user_agent(settings)?)
+        .connect_timeout(Duration::from_secs(settings.http_connect_timeout_secs.max(1)))
+        // Read timeout rather than a total one: server jars and modpacks take a while
+        .read_timeout(Duration::from_secs(settings.http_read_timeout_secs.max(1)))
+        .pool_idle_timeout(Duration::from_secs(90));
+
+    if let Some(proxy) = settings.http_proxy.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        builder = builder.proxy(build_proxy(proxy, settings)?);
+    }
+
+    builder.build().map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// HTTP(S) or SOCKS5 proxy. Credentials from the settings go into the URL, which is where
+/// reqwest reads them for both kinds (SOCKS has no header to put them in).
+fn build_proxy(proxy: &str, settings: &AppSettings) -> Result<reqwest::Proxy, String> {
+    let mut url = reqwest::Url::parse(proxy).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https" | "socks5" | "socks5h") {
+        return Err(format!("Unsupported proxy type \"{}\" (use http, https, socks5 or socks5h)", url.scheme()));
+    }
+    if let Some(username) = settings.http_proxy_username.as_deref().filter(|u| !u.is_empty()) {
+        url.set_username(username).map_err(|_| "Invalid proxy URL".to_string())?;
+        url.set_password(settings.http_proxy_password.as_deref().filter(|p| !p.is_empty()))
+            .map_err(|_| "Invalid proxy URL".to_string())?;
+    }
+    reqwest::Proxy::all(url).map_err(|e| format!("Invalid proxy URL: {}", e))
+}
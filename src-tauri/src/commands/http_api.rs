@@ -0,0 +1,446 @@
+use tauri::{AppHandle, State};
+use std::sync::{Arc, Mutex};
+use std::fmt::Write as FmtWrite;
+use serde::Serialize;
+use sysinfo::System;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use super::runner::{ServerProcessState, ServerEventSink, collect_process_usage, start_server_direct, stop_server_direct, send_server_command_direct};
+use super::registry::{load_registry, find_registered_server};
+use super::backup::latest_backup_time;
+use super::error::MineServerError;
+
+// Optional local HTTP server: Prometheus metrics plus a small REST API used by headless mode.
+// Disabled until the user starts it.
+pub struct HttpApiState {
+    pub server: Mutex<Option<RunningApi>>,
+}
+
+pub struct RunningApi {
+    pub port: u16,
+    pub bind_address: String,
+    pub api_token: String,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl HttpApiState {
+    pub fn new() -> Self {
+        Self {
+            server: Mutex::new(None),
+        }
+    }
+}
+
+/// Everything a request handler may touch. Cloned into each connection task.
+#[derive(Clone)]
+pub struct ApiContext<E: ServerEventSink> {
+    pub processes: ServerProcessState,
+    // Dedicated instance so scrapes measure CPU between scrapes, independent of the UI's refreshes
+    pub sys: Arc<Mutex<System>>,
+    // Receives console output of servers started through the API
+    pub sink: E,
+    // Required as `Authorization: Bearer <token>` on every /api route, loopback included: any
+    // local process or a DNS-rebound web page could reach the port
+    pub api_token: String,
+    // Host header values besides IP literals and localhost that requests may carry
+    pub bind_address: String,
+}
+
+impl<E: ServerEventSink> ApiContext<E> {
+    /// Without a token, a random one is generated; read it back from `api_token`.
+    pub fn new(processes: ServerProcessState, sink: E, bind_address: &str, api_token: Option<String>) -> Self {
+        let api_token = api_token.filter(|t| !t.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+        Self {
+            processes,
+            sys: Arc::new(Mutex::new(System::new())),
+            sink,
+            api_token,
+            bind_address: bind_address.to_string(),
+        }
+    }
+
+    /// Whether the Host header names this machine. A DNS rebinding attack reaches the port
+    /// through an attacker's domain name, so only IP literals, localhost and the bind address pass.
+    fn host_allowed(&self, host: Option<&str>) -> bool {
+        let Some(host) = host else { return true }; // Browsers always send one
+        let name = match host.rsplit_once(':') {
+            Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) && !name.ends_with(':') => name,
+            _ => host,
+        };
+        let name = name.trim_start_matches('[').trim_end_matches(']');
+        name.eq_ignore_ascii_case("localhost")
+            || name.eq_ignore_ascii_case(&self.bind_address)
+            || name.parse::<std::net::IpAddr>().is_ok()
+    }
+}
+
+/// Compares secrets in time independent of where they differ.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiStatus {
+    running: bool,
+    port: Option<u16>,
+    bind_address: Option<String>,
+    api_token: Option<String>, // Shown in the app so scripts can be set up with it
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Renders gauges in the Prometheus text exposition format.
+pub fn render_metrics<E: ServerEventSink>(ctx: &ApiContext<E>) -> String {
+    let configs = ctx.processes.configs.lock().map(|c| c.clone()).unwrap_or_default();
+    let pids: std::collections::HashMap<String, u32> = ctx.processes.processes.lock()
+        .map(|p| p.iter().map(|(id, child)| (id.clone(), child.id())).collect())
+        .unwrap_or_default();
+    let stats = ctx.processes.live_stats.lock().map(|s| s.clone()).unwrap_or_default();
+    let now = chrono::Local::now();
+
+    let mut up = String::new();
+    let mut cpu = String::new();
+    let mut memory = String::new();
+    let mut tps = String::new();
+    let mut players = String::new();
+    let mut uptime = String::new();
+    let mut backup_age = String::new();
+
+    let mut ids: Vec<&String> = configs.keys().collect();
+    ids.sort();
+
+    for id in ids {
+        let cfg = &configs[id];
+        let name = std::path::Path::new(&cfg.path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| id.clone());
+        let labels = format!("server=\"{}\",name=\"{}\"", escape_label(id), escape_label(&name));
+
+        let running = pids.get(id);
+        let _ = writeln!(up, "mineserver_server_up{{{}}} {}", labels, if running.is_some() { 1 } else { 0 });
+
+        if let Some(pid) = running {
+            if let Ok(mut sys) = ctx.sys.lock() {
+                let (c, m) = collect_process_usage(&mut sys, *pid);
+                let _ = writeln!(cpu, "mineserver_server_cpu_percent{{{}}} {}", labels, c);
+                let _ = writeln!(memory, "mineserver_server_memory_bytes{{{}}} {}", labels, m);
+            }
+        }
+
+        if let Some(s) = stats.get(id) {
+            if let Some(t) = s.tps {
+                let _ = writeln!(tps, "mineserver_server_tps{{{}}} {}", labels, t);
+            }
+            let _ = writeln!(players, "mineserver_server_players_online{{{}}} {}", labels, s.online_players.len());
+            if let Some(started) = s.started_at {
+                let secs = now.signed_duration_since(started).num_seconds().max(0);
+                let _ = writeln!(uptime, "mineserver_server_uptime_seconds{{{}}} {}", labels, secs);
+            }
+        }
+
+        if let Some(last) = latest_backup_time(&cfg.path) {
+            let secs = now.signed_duration_since(last).num_seconds().max(0);
+            let _ = writeln!(backup_age, "mineserver_server_last_backup_age_seconds{{{}}} {}", labels, secs);
+        }
+    }
+
+    let mut out = String::new();
+    for (metric, help, body) in [
+        ("mineserver_server_up", "Whether the server process is running", &up),
+        ("mineserver_server_cpu_percent", "CPU usage of the server process tree (100 = one core)", &cpu),
+        ("mineserver_server_memory_bytes", "Resident memory of the server process tree", &memory),
+        ("mineserver_server_tps", "Last TPS reported by the server console", &tps),
+        ("mineserver_server_players_online", "Players currently online (from console join/leave)", &players),
+        ("mineserver_server_uptime_seconds", "Seconds since the server process was started", &uptime),
+        ("mineserver_server_last_backup_age_seconds", "Seconds since the newest backup of the server", &backup_age),
+    ] {
+        let _ = writeln!(out, "# HELP {} {}", metric, help);
+        let _ = writeln!(out, "# TYPE {} gauge", metric);
+        out.push_str(body);
+    }
+    out
+}
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    host: Option<String>,
+    origin: Option<String>,
+    body: String,
+}
+
+type Response = (u16, &'static str, String);
+
+fn json_response<T: Serialize>(code: u16, value: &T) -> Response {
+    (code, "application/json", serde_json::to_string(value).unwrap_or_else(|_| "null".to_string()))
+}
+
+fn json_error(code: u16, message: impl Into<String>) -> Response {
+    json_response(code, &serde_json::json!({ "error": message.into() }))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiServer {
+    id: String,
+    name: String,
+    path: String,
+    server_type: String,
+    version: String,
+    running: bool,
+}
+
+fn route_api<E: ServerEventSink>(req: &Request, ctx: &ApiContext<E>) -> Response {
+    // Scripts and curl send no Origin; a browser page does, whatever it claims to be
+    if req.origin.is_some() {
+        return json_error(403, "Cross-origin requests are not allowed");
+    }
+    let expected = format!("Bearer {}", ctx.api_token);
+    let presented = req.authorization.as_deref().unwrap_or("");
+    if !constant_time_eq(presented.as_bytes(), expected.as_bytes()) {
+        return json_error(401, "Missing or invalid API token");
+    }
+
+    let segments: Vec<&str> = req.path.trim_matches('/').split('/').collect();
+    let running: Vec<String> = ctx.processes.processes.lock()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+
+    match (req.method.as_str(), segments.as_slice()) {
+        ("GET", ["api", "servers"]) => {
+            match load_registry() {
+                Ok(servers) => {
+                    let list: Vec<ApiServer> = servers.into_iter().map(|s| ApiServer {
+                        running: running.contains(&s.id),
+                        id: s.id,
+                        name: s.name,
+                        path: s.path,
+                        server_type: s.server_type,
+                        version: s.version,
+                    }).collect();
+                    json_response(200, &list)
+                },
+                Err(e) => json_error(500, e),
+            }
+        },
+        ("GET", ["api", "servers", id, "status"]) => {
+            json_response(200, &serde_json::json!({ "id": id, "running": running.iter().any(|r| r == id) }))
+        },
+        ("POST", ["api", "servers", id, "start"]) => {
+            let server = match find_registered_server(id) {
+                Some(s) => s,
+                None => return json_error(404, "Server not registered"),
+            };
+            let cfg = server.launch_config();
+            match start_server_direct(
+                ctx.sink.clone(),
+                &ctx.processes,
+                cfg.id,
+                cfg.path,
+                cfg.jar_file,
+                cfg.ram,
+                cfg.java_path,
+                cfg.startup_flags,
+                Some(cfg.auto_restart),
+            ) {
+                Ok(msg) => json_response(200, &serde_json::json!({ "message": msg })),
+                Err(e) => json_error(409, e),
+            }
+        },
+        ("POST", ["api", "servers", id, "stop"]) => {
+            match stop_server_direct(&ctx.processes, id.to_string()) {
+                Ok(msg) => json_response(200, &serde_json::json!({ "message": msg })),
+                Err(e) => json_error(409, e),
+            }
+        },
+        ("POST", ["api", "servers", id, "command"]) => {
+            let command = req.body.trim();
+            if command.is_empty() {
+                return json_error(400, "Request body must contain the command");
+            }
+            match send_server_command_direct(&ctx.processes, id.to_string(), command.to_string()) {
+                Ok(_) => json_response(200, &serde_json::json!({ "message": "Command sent" })),
+                Err(e) => json_error(409, e),
+            }
+        },
+        _ => json_error(404, "Not Found"),
+    }
+}
+
+fn route<E: ServerEventSink>(req: &Request, ctx: &ApiContext<E>) -> Response {
+    if !ctx.host_allowed(req.host.as_deref()) {
+        return (403, "text/plain", "Forbidden\n".to_string());
+    }
+    match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/metrics") => (200, "text/plain; version=0.0.4", render_metrics(ctx)),
+        ("GET", "/") => (200, "text/plain", "MineServer API\n".to_string()),
+        (_, p) if p.starts_with("/api/") => route_api(req, ctx),
+        _ => (404, "text/plain", "Not Found\n".to_string()),
+    }
+}
+
+fn status_text(code: u16) -> &'static str {
+    match code {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        _ => "Internal Server Error",
+    }
+}
+
+fn find_header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n").map(|p| p + 4)
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    const MAX_REQUEST: usize = 64 * 1024;
+    let mut buf = Vec::with_capacity(8192);
+    let mut chunk = [0u8; 4096];
+
+    // Headers first
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 { return None; }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(end) = find_header_end(&buf) { break end; }
+        if buf.len() > MAX_REQUEST { return None; }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut first_line = lines.next().unwrap_or("").split_whitespace();
+    let method = first_line.next()?.to_string();
+    let path = first_line.next().unwrap_or("/").split('?').next().unwrap_or("/").to_string();
+
+    let mut content_length = 0usize;
+    let mut authorization = None;
+    let mut host = None;
+    let mut origin = None;
+    for line in lines {
+        if let Some((key, value)) = line.split_once(':') {
+            match key.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                "authorization" => authorization = Some(value.trim().to_string()),
+                "host" => host = Some(value.trim().to_string()),
+                "origin" => origin = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+    if content_length > MAX_REQUEST { return None; }
+
+    // Then the body, if any
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 { break; }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let body_end = (header_end + content_length).min(buf.len());
+    let body = String::from_utf8_lossy(&buf[header_end..body_end]).to_string();
+
+    Some(Request { method, path, authorization, host, origin, body })
+}
+
+async fn handle_connection<E: ServerEventSink>(mut stream: TcpStream, ctx: ApiContext<E>) {
+    let (code, content_type, body) = match read_request(&mut stream).await {
+        // Handlers take std locks and may block briefly (stopping a server waits for exit)
+        Some(req) => tokio::task::spawn_blocking(move || route(&req, &ctx))
+            .await
+            .unwrap_or_else(|_| (500, "text/plain", "Internal Server Error\n".to_string())),
+        None => (400, "text/plain", "Bad Request\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code, status_text(code), content_type, body.len(), body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Accept loop. Runs until the task is aborted.
+pub async fn serve<E: ServerEventSink>(listener: TcpListener, ctx: ApiContext<E>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _addr)) => {
+                let ctx = ctx.clone();
+                tokio::spawn(handle_connection(stream, ctx));
+            },
+            Err(e) => {
+                eprintln!("[HTTP API] Accept failed: {}", e);
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn start_http_server(
+    app: AppHandle,
+    proc_state: State<'_, ServerProcessState>,
+    api_state: State<'_, HttpApiState>,
+    port: u16,
+    bind_address: Option<String>,
+    api_token: Option<String>,
+) -> Result<HttpApiStatus, MineServerError> {
+    // Localhost by default; exposing metrics to the LAN is an explicit choice
+    let bind = bind_address.unwrap_or_else(|| "127.0.0.1".to_string());
+
+    if let Ok(guard) = api_state.server.lock() {
+        if guard.is_some() {
+            return Err(MineServerError::conflict("HTTP server is already running"));
+        }
+    }
+
+    let listener = TcpListener::bind((bind.as_str(), port))
+        .await
+        .map_err(|e| format!("Failed to bind {}:{}: {}", bind, port, e))?;
+
+    // Another start may have finished while this one was binding
+    let mut guard = api_state.server.lock().map_err(|e| e.to_string())?;
+    if guard.is_some() {
+        return Err(MineServerError::conflict("HTTP server is already running"));
+    }
+    let ctx = ApiContext::new(proc_state.inner().clone(), app, &bind, api_token);
+    let api_token = ctx.api_token.clone();
+    let handle = tauri::async_runtime::spawn(serve(listener, ctx));
+    *guard = Some(RunningApi { port, bind_address: bind.clone(), api_token: api_token.clone(), handle });
+
+    Ok(HttpApiStatus { running: true, port: Some(port), bind_address: Some(bind), api_token: Some(api_token) })
+}
+
+#[tauri::command]
+pub fn stop_http_server(api_state: State<'_, HttpApiState>) -> Result<(), MineServerError> {
+    let mut guard = api_state.server.lock().map_err(|e| e.to_string())?;
+    if let Some(running) = guard.take() {
+        running.handle.abort();
+        Ok(())
+    } else {
+        Err(MineServerError::conflict("HTTP server is not running"))
+    }
+}
+
+#[tauri::command]
+pub fn get_http_server_status(api_state: State<'_, HttpApiState>) -> HttpApiStatus {
+    match api_state.server.lock().ok().as_ref().and_then(|g| g.as_ref()) {
+        Some(running) => HttpApiStatus {
+            running: true,
+            port: Some(running.port),
+            bind_address: Some(running.bind_address.clone()),
+            api_token: Some(running.api_token.clone()),
+        },
+        None => HttpApiStatus { running: false, port: None, bind_address: None, api_token: None },
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Persistence for the JSON state files under ~/Mineserver (server registry, settings, backups
+// index, scheduled tasks). They are rewritten wholesale by commands and background threads
+// (scheduler, backups), so:
+//   - writes go to a temp file in the same folder that is synced and renamed over the target,
+//     so readers and crashes see the old or the new file, never a truncated one
+//   - writers hold an exclusive lock on `<file>.lock` for the whole read-modify-write, which
+//     also serializes the desktop app and headless mode sharing one home folder
+// Plain reads take no lock; the rename makes them safe.
+
+/// Exclusive lock on a store, released when dropped.
+pub struct StoreLock {
+    _file: File,
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    path.with_file_name(name)
+}
+
+/// Blocks until no other thread or process writes the store at `path`.
+pub fn lock(path: &Path) -> Result<StoreLock, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let file = OpenOptions::new().create(true).truncate(false).write(true).open(lock_path(path))
+        .map_err(|e| format!("Failed to open lock for {}: {}", path.display(), e))?;
+    file.lock().map_err(|e| format!("Failed to lock {}: {}", path.display(), e))?;
+    Ok(StoreLock { _file: file })
+}
+
+/// Reads a store; None if the file doesn't exist yet. `what` names it in errors.
+pub fn read_json<T: DeserializeOwned>(path: &Path, what: &str) -> Result<Option<T>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", what, e)),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", what, e))
+}
+
+/// Replaces the file with `value` through a synced temp file. The caller holds the lock.
+fn write_atomic<T: Serialize + ?Sized>(path: &Path, value: &T, what: &str) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", what, e))?;
+    write_text_atomic(path, &content, what)
+}
+
+/// Replaces any text file through a synced temp file in the same folder.
+pub fn write_text_atomic(path: &Path, content: &str, what: &str) -> Result<(), String> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    let temp = path.with_file_name(temp_name);
+
+    let written = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(format!("Failed to write {}: {}", what, e));
+    }
+    Ok(())
+}
+
+/// Replaces a store with `value`.
+pub fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T, what: &str) -> Result<(), String> {
+    let _lock = lock(path)?;
+    write_atomic(path, value, what)
+}
+
+/// Reads the store (the default if missing), lets `update` change it and writes it back, all
+/// under the lock. Nothing is written when `update` fails.
+pub fn update_json<T, R>(path: &Path, what: &str, update: impl FnOnce(&mut T) -> Result<R, String>) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let _lock = lock(path)?;
+    let mut value = read_json(path, what)?.unwrap_or_default();
+    let result = update(&mut value)?;
+    write_atomic(path, &value, what)?;
+    Ok(result)
+}
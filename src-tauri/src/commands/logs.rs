@@ -0,0 +1,389 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use super::http::HttpState;
+use super::path_guard::validate_path;
+use super::runner::ServerEventSink;
+use super::audit::audited;
+use super::error::MineServerError;
+
+// mclo.gs rejects logs above 25k lines / 10 MiB, so only the tail is uploaded
+const MCLOGS_MAX_LINES: usize = 25_000;
+const MCLOGS_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct MclogsResponse {
+    success: bool,
+    url: Option<String>,
+    raw: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedLog {
+    pub url: String,
+    pub raw_url: Option<String>,
+    pub file: String,
+    pub truncated: bool,
+}
+
+fn newest_file_in(dir: &Path, extension: &str) -> Option<PathBuf> {
+    fs::read_dir(dir).ok()?
+        .flatten()
+        .filter(|e| e.path().extension().map(|x| x == extension).unwrap_or(false))
+        .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+        .map(|e| e.path())
+}
+
+/// `which`: "latest" (logs/latest.log), "console" (server_console.log), "crash" (newest crash report)
+/// or a file name inside logs/ or crash-reports/.
+fn resolve_log_file(server_path: &Path, which: &str) -> Result<PathBuf, String> {
+    let path = match which {
+        "latest" => server_path.join("logs").join("latest.log"),
+        "console" => server_path.join("server_console.log"),
+        "crash" => newest_file_in(&server_path.join("crash-reports"), "txt")
+            .ok_or("No crash reports found")?,
+        name => {
+            if name.contains("..") || name.contains('/') || name.contains('\\') {
+                return Err("Invalid log file name".to_string());
+            }
+            let in_logs = server_path.join("logs").join(name);
+            if in_logs.exists() { in_logs } else { server_path.join("crash-reports").join(name) }
+        }
+    };
+
+    if !path.is_file() {
+        return Err(format!("Log file not found: {}", path.display()));
+    }
+    Ok(path)
+}
+
+fn is_gzipped(path: &Path) -> bool {
+    path.extension().map(|e| e == "gz").unwrap_or(false)
+}
+
+/// Reads a log, decompressing `.gz` archives on the fly. Returns at most `limit` bytes and
+/// whether the file was longer.
+fn read_log_bytes(path: &Path, limit: u64) -> Result<(Vec<u8>, bool), String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open log: {}", e))?;
+    let reader: Box<dyn Read> = if is_gzipped(path) {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    let mut out = Vec::new();
+    reader.take(limit.saturating_add(1)).read_to_end(&mut out)
+        .map_err(|e| format!("Failed to read log: {}", e))?;
+    let truncated = out.len() as u64 > limit;
+    out.truncate(limit as usize);
+    Ok((out, truncated))
+}
+
+fn read_log_tail(path: &Path) -> Result<(String, bool), String> {
+    let (bytes, _) = read_log_bytes(path, u64::MAX)?;
+    let content = String::from_utf8_lossy(&bytes);
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut truncated = lines.len() > MCLOGS_MAX_LINES;
+    let mut text = lines[lines.len().saturating_sub(MCLOGS_MAX_LINES)..].join("\n");
+
+    if text.len() > MCLOGS_MAX_BYTES {
+        let mut cut = text.len() - MCLOGS_MAX_BYTES;
+        while !text.is_char_boundary(cut) {
+            cut += 1;
+        }
+        text = text[cut..].to_string();
+        truncated = true;
+    }
+    Ok((text, truncated))
+}
+
+/// Uploads a server log to mclo.gs and returns the shareable link.
+#[tauri::command]
+pub async fn share_log(http: State<'_, HttpState>, server_path: String, which: String) -> Result<SharedLog, MineServerError> {
+    let path = resolve_log_file(Path::new(&server_path), &which)?;
+    let (content, truncated) = read_log_tail(&path)?;
+    if content.trim().is_empty() {
+        return Err(MineServerError::validation("Log file is empty"));
+    }
+
+    let resp = http.client()
+        .post("https://api.mclo.gs/1/log")
+        .form(&[("content", content)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload log: {}", e))?;
+
+    let body: MclogsResponse = resp.json()
+        .await
+        .map_err(|e| format!("Invalid response from mclo.gs: {}", e))?;
+
+    if !body.success {
+        return Err(MineServerError::external("mclo.gs", format!("mclo.gs rejected the log: {}", body.error.unwrap_or_else(|| "unknown error".to_string()))));
+    }
+
+    Ok(SharedLog {
+        url: body.url.ok_or("mclo.gs did not return a URL")?,
+        raw_url: body.raw,
+        file: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        truncated,
+    })
+}
+
+// --- Log archive ---
+// Rotated logs in logs/ are gzipped (2024-01-01-1.log.gz), so they are listed, read and cleaned
+// up here rather than through the file editor.
+
+const MAX_LOG_READ: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFileInfo {
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified: Option<String>,
+    pub compressed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFileContent {
+    pub name: String,
+    pub content: String,
+    pub truncated: bool, // Only the first 8 MiB (decompressed) are returned
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCleanupReport {
+    pub deleted: Vec<String>,
+    pub freed_bytes: u64,
+}
+
+/// Files in logs/ matching the filters, newest first. `latest.log` is always listed but never
+/// matches the age or size filters, since the server is still writing to it.
+fn collect_log_files(logs_dir: &Path, older_than_days: Option<u32>, min_size_bytes: Option<u64>) -> Vec<(PathBuf, LogFileInfo)> {
+    let cutoff = older_than_days.map(|d| std::time::SystemTime::now() - Duration::from_secs(d as u64 * 86_400));
+    let filtered = older_than_days.is_some() || min_size_bytes.is_some();
+    let mut files: Vec<(PathBuf, LogFileInfo, std::time::SystemTime)> = fs::read_dir(logs_dir)
+        .map(|rd| rd.flatten()
+            .filter_map(|e| {
+                let meta = e.metadata().ok().filter(|m| m.is_file())?;
+                let name = e.file_name().to_string_lossy().to_string();
+                let modified = meta.modified().ok()?;
+                if filtered && name == "latest.log" {
+                    return None;
+                }
+                if cutoff.map(|c| modified > c).unwrap_or(false) || min_size_bytes.map(|m| meta.len() < m).unwrap_or(false) {
+                    return None;
+                }
+                let info = LogFileInfo {
+                    compressed: is_gzipped(&e.path()),
+                    modified: Some(chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339()),
+                    size_bytes: meta.len(),
+                    name,
+                };
+                Some((e.path(), info, modified))
+            })
+            .collect())
+        .unwrap_or_default();
+    files.sort_by(|a, b| b.2.cmp(&a.2));
+    files.into_iter().map(|(path, info, _)| (path, info)).collect()
+}
+
+#[tauri::command]
+pub fn list_log_files(server_path: String, older_than_days: Option<u32>, min_size_bytes: Option<u64>) -> Result<Vec<LogFileInfo>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    Ok(collect_log_files(&root.join("logs"), older_than_days, min_size_bytes)
+        .into_iter()
+        .map(|(_, info)| info)
+        .collect())
+}
+
+/// Reads a file from logs/, gunzipping archived logs.
+#[tauri::command]
+pub async fn read_log_file(server_path: String, name: String) -> Result<LogFileContent, MineServerError> {
+    let root = validate_path(&server_path)?;
+    if name.contains("..") || name.contains('/') || name.contains('\\') {
+        return Err(MineServerError::validation("Invalid log file name"));
+    }
+    let path = root.join("logs").join(&name);
+    if !path.is_file() {
+        return Err(MineServerError::not_found(format!("Log file not found: {}", name)));
+    }
+
+    let (bytes, truncated) = tokio::task::spawn_blocking(move || read_log_bytes(&path, MAX_LOG_READ)).await??;
+    Ok(LogFileContent {
+        name,
+        content: String::from_utf8_lossy(&bytes).to_string(),
+        truncated,
+    })
+}
+
+/// Deletes the named files from logs/, or every file matching the age/size filters when no
+/// names are given. `latest.log` is never deleted.
+#[tauri::command]
+pub fn delete_log_files(
+    server_path: String,
+    names: Option<Vec<String>>,
+    older_than_days: Option<u32>,
+    min_size_bytes: Option<u64>,
+) -> Result<LogCleanupReport, MineServerError> {
+    let root = validate_path(&server_path)?;
+    if names.is_none() && older_than_days.is_none() && min_size_bytes.is_none() {
+        return Err(MineServerError::validation("Select log files or set an age or size filter"));
+    }
+
+    let params = serde_json::json!({ "serverPath": server_path, "names": names, "olderThanDays": older_than_days, "minSizeBytes": min_size_bytes });
+    audited("delete_log_files", params, || {
+        let mut report = LogCleanupReport { deleted: Vec::new(), freed_bytes: 0 };
+        for (path, info) in collect_log_files(&root.join("logs"), older_than_days, min_size_bytes) {
+            if info.name == "latest.log" || names.as_ref().map(|n| !n.contains(&info.name)).unwrap_or(false) {
+                continue;
+            }
+            fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", info.name, e))?;
+            report.freed_bytes += info.size_bytes;
+            report.deleted.push(info.name);
+        }
+        Ok(report)
+    }).map_err(Into::into)
+}
+
+// --- Tail & follow ---
+
+const TAIL_BLOCK: u64 = 64 * 1024;
+const MAX_TAIL_LINES: usize = 10_000;
+
+/// Last `lines` lines of a file, reading backwards so large logs stay cheap.
+pub fn read_last_lines(path: &Path, lines: usize) -> Result<Vec<String>, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let size = file.metadata().map_err(|e| e.to_string())?.len();
+
+    let mut pos = size;
+    let mut buffer: Vec<u8> = Vec::new();
+    // +1: the file usually ends with a newline
+    while pos > 0 && buffer.iter().filter(|&&b| b == b'\n').count() <= lines {
+        let read_size = TAIL_BLOCK.min(pos);
+        pos -= read_size;
+        file.seek(SeekFrom::Start(pos)).map_err(|e| e.to_string())?;
+        let mut block = vec![0u8; read_size as usize];
+        file.read_exact(&mut block).map_err(|e| e.to_string())?;
+        block.extend_from_slice(&buffer);
+        buffer = block;
+    }
+
+    let text = String::from_utf8_lossy(&buffer);
+    let all: Vec<&str> = text.lines().collect();
+    Ok(all[all.len().saturating_sub(lines)..].iter().map(|l| l.to_string()).collect())
+}
+
+#[tauri::command]
+pub fn tail_file(path: String, lines: usize) -> Result<Vec<String>, MineServerError> {
+    let file_path = validate_path(&path)?;
+    read_last_lines(&file_path, lines.min(MAX_TAIL_LINES)).map_err(Into::into)
+}
+
+pub struct LogFollowState {
+    followers: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>, // follow id -> cancel flag
+}
+
+impl LogFollowState {
+    pub fn new() -> Self {
+        Self {
+            followers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// Reads everything appended since `pos`. Handles truncation/rotation by starting over.
+fn read_appended(path: &Path, pos: &mut u64, partial: &mut String) -> Vec<String> {
+    let mut file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return vec![],
+    };
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if size < *pos {
+        *pos = 0;
+        partial.clear();
+    }
+    if size == *pos || file.seek(SeekFrom::Start(*pos)).is_err() {
+        return vec![];
+    }
+
+    let mut bytes = Vec::new();
+    if file.take(size - *pos).read_to_end(&mut bytes).is_err() {
+        return vec![];
+    }
+    *pos += bytes.len() as u64;
+    partial.push_str(&String::from_utf8_lossy(&bytes));
+
+    // Keep an unfinished last line for the next read
+    let mut lines: Vec<String> = partial.split('\n').map(|l| l.trim_end_matches('\r').to_string()).collect();
+    *partial = lines.pop().unwrap_or_default();
+    lines
+}
+
+/// Watches a file and emits appended lines as `file-tail:{followId}` events until
+/// `stop_follow_file` is called. Returns the follow id.
+#[tauri::command]
+pub fn follow_file(app: AppHandle, state: State<'_, LogFollowState>, path: String) -> Result<String, MineServerError> {
+    use notify::{RecursiveMode, Watcher};
+
+    let file_path = validate_path(&path)?;
+    let parent = file_path.parent().ok_or("Invalid path")?.to_path_buf();
+    let follow_id = uuid::Uuid::new_v4().to_string();
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.followers.lock().map_err(|e| e.to_string())?.insert(follow_id.clone(), cancel.clone());
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    // Watch the folder, not the file: latest.log is replaced on every server start
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| format!("Failed to watch file: {}", e))?;
+    watcher.watch(&parent, RecursiveMode::NonRecursive).map_err(|e| format!("Failed to watch file: {}", e))?;
+
+    let event_name = format!("file-tail:{}", follow_id);
+    let followers = state.followers.clone();
+    let id = follow_id.clone();
+    std::thread::spawn(move || {
+        let _watcher = watcher; // Dropped (and unregistered) when the loop ends
+        let mut pos = fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+        let mut partial = String::new();
+
+        while !cancel.load(Ordering::SeqCst) {
+            // Timeout doubles as a polling fallback for file systems without change events
+            let relevant = match rx.recv_timeout(Duration::from_millis(500)) {
+                Ok(Ok(event)) => event.paths.iter().any(|p| p.file_name() == file_path.file_name()),
+                Ok(Err(_)) => false,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => true,
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+            if !relevant {
+                continue;
+            }
+            let lines = read_appended(&file_path, &mut pos, &mut partial);
+            if !lines.is_empty() {
+                app.emit(&event_name, lines);
+            }
+        }
+
+        if let Ok(mut f) = followers.lock() {
+            f.remove(&id);
+        }
+    });
+
+    Ok(follow_id)
+}
+
+#[tauri::command]
+pub fn stop_follow_file(state: State<'_, LogFollowState>, follow_id: String) -> Result<(), MineServerError> {
+    if let Some(cancel) = state.followers.lock().map_err(|e| e.to_string())?.remove(&follow_id) {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::time::Duration;
+use tauri::State;
+use super::http::HttpState;
+use super::path_guard::validate_path;
+use super::runner::{ServerProcessState, send_server_command_direct, console_output_since};
+use super::error::MineServerError;
+
+// LuckPerms install and web editor. The editor session is created by running `lp editor` on
+// the live server, which uploads the permission data and prints a luckperms.net/editor link.
+
+const METADATA_URL: &str = "https://metadata.luckperms.net/data/all";
+const EDITOR_TIMEOUT: Duration = Duration::from_secs(20);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// LuckPerms download key and the folder the jar goes into.
+fn platform_target(platform: &str) -> Result<(&'static str, &'static str), MineServerError> {
+    match platform.to_lowercase().as_str() {
+        "paper" | "spigot" | "bukkit" | "purpur" | "folia" => Ok(("bukkit", "plugins")),
+        "velocity" => Ok(("velocity", "plugins")),
+        "bungee" | "bungeecord" | "waterfall" => Ok(("bungee", "plugins")),
+        "sponge" | "spongevanilla" | "spongeforge" => Ok(("sponge", "mods")),
+        "fabric" => Ok(("fabric", "mods")),
+        "forge" => Ok(("forge", "mods")),
+        "neoforge" => Ok(("neoforge", "mods")),
+        other => Err(MineServerError::validation(format!("LuckPerms is not available for {}", other))),
+    }
+}
+
+/// Console command prefix per platform (`lp` on backends, `lpv`/`lpb` on proxies).
+fn command_prefix(platform: Option<&str>) -> &'static str {
+    match platform.map(str::to_lowercase).as_deref() {
+        Some("velocity") => "lpv",
+        Some("bungee" | "bungeecord" | "waterfall") => "lpb",
+        _ => "lp",
+    }
+}
+
+/// Downloads the latest LuckPerms build for the platform into plugins/ or mods/.
+#[tauri::command]
+pub async fn install_luckperms(http: State<'_, HttpState>, server_path: String, platform: String) -> Result<String, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let (key, folder) = platform_target(&platform)?;
+
+    let client = http.client();
+    let metadata: serde_json::Value = client.get(METADATA_URL)
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| MineServerError::external("LuckPerms", e.to_string()))?
+        .json()
+        .await?;
+    let url = metadata["downloads"][key].as_str()
+        .ok_or_else(|| MineServerError::external("LuckPerms", format!("No {} download listed", key)))?;
+    let version = metadata["version"].as_str().unwrap_or("latest");
+
+    let resp = client.get(url).send().await?;
+    if !resp.status().is_success() {
+        return Err(MineServerError::external("LuckPerms", format!("Download failed with status: {}", resp.status())));
+    }
+    let bytes = resp.bytes().await?;
+
+    let target_dir = root.join(folder);
+    fs::create_dir_all(&target_dir)?;
+    // Replace older LuckPerms jars instead of loading two copies
+    if let Ok(entries) = fs::read_dir(&target_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if name.starts_with("luckperms") && name.ends_with(".jar") {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    let file_name = url.rsplit('/').next().filter(|n| n.ends_with(".jar")).unwrap_or("LuckPerms.jar");
+    tokio::fs::write(target_dir.join(file_name), &bytes).await
+        .map_err(|e| format!("File Write Error: {}", e))?;
+
+    Ok(format!("LuckPerms {} installed. Restart your server.", version))
+}
+
+/// Runs `lp editor` on a running server and returns the web editor URL it prints.
+#[tauri::command]
+pub async fn open_luckperms_editor(
+    state: State<'_, ServerProcessState>,
+    id: String,
+    platform: Option<String>,
+) -> Result<String, MineServerError> {
+    // Only look at output printed after the command was sent
+    let (mut seq, _) = console_output_since(state.inner(), &id, u64::MAX);
+    send_server_command_direct(state.inner(), id.clone(), format!("{} editor", command_prefix(platform.as_deref())))?;
+
+    let deadline = tokio::time::Instant::now() + EDITOR_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let (next_seq, lines) = console_output_since(state.inner(), &id, seq);
+        seq = next_seq;
+
+        for line in lines {
+            if let Some(start) = line.find("https://luckperms.net/editor/") {
+                let url: String = line[start..].chars().take_while(|c| !c.is_whitespace() && *c != '\u{1b}').collect();
+                return Ok(url);
+            }
+            if line.contains("Unknown command") || line.contains("Unknown or incomplete command") {
+                return Err(MineServerError::not_found("LuckPerms is not loaded on this server"));
+            }
+        }
+    }
+
+    Err(MineServerError::external("LuckPerms", "The server did not print an editor link. Check the console for errors."))
+}
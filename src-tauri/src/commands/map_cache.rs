@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::time::{Duration, SystemTime};
+use serde::Serialize;
+use walkdir::WalkDir;
+use super::path_guard::validate_path;
+use super::audit::audited;
+use super::error::MineServerError;
+
+// Render caches of the web map plugins, which grow to tens of GB on big worlds. Both re-render
+// missing tiles when they are viewed or the world changes, so deleting them only costs CPU.
+// Plugin and mod builds keep their web folder in different places (paths are relative to the
+// server folder): BlueMap has a tiles folder per map, Dynmap a folder per world under tiles/.
+const BLUEMAP_MAP_DIRS: [&str; 2] = ["bluemap/web/maps", "plugins/BlueMap/web/maps"];
+const DYNMAP_TILE_DIRS: [&str; 2] = ["plugins/dynmap/web/tiles", "dynmap/web/tiles"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapCache {
+    pub plugin: String, // "bluemap" or "dynmap"
+    pub map: String,
+    pub path: String, // Relative to the server folder; identifies the cache when pruning
+    pub size_bytes: u64,
+    pub file_count: u64,
+    pub oldest: Option<String>, // Modification time of the oldest tile
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapCleanupReport {
+    pub deleted_files: u64,
+    pub freed_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+/// Tile folders of the map plugins installed on a server, as (plugin, map, relative path).
+fn find_cache_dirs(root: &Path) -> Vec<(&'static str, String, String)> {
+    let mut dirs = Vec::new();
+    let mut subfolders = |plugin: &'static str, base: &str, suffix: &str| {
+        let Ok(entries) = fs::read_dir(root.join(base)) else { return };
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            let map = entry.file_name().to_string_lossy().to_string();
+            let relative = format!("{}/{}{}", base, map, suffix);
+            if root.join(&relative).is_dir() {
+                dirs.push((plugin, map, relative));
+            }
+        }
+    };
+    for base in BLUEMAP_MAP_DIRS {
+        subfolders("bluemap", base, "/tiles");
+    }
+    for base in DYNMAP_TILE_DIRS {
+        subfolders("dynmap", base, "");
+    }
+    dirs.sort_by(|a, b| a.2.cmp(&b.2));
+    dirs
+}
+
+fn files_in(dir: &Path) -> impl Iterator<Item = (PathBuf, fs::Metadata)> {
+    WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| Some((e.path().to_path_buf(), e.metadata().ok().filter(|m| m.is_file())?)))
+}
+
+fn measure(root: &Path, (plugin, map, relative): (&str, String, String)) -> MapCache {
+    let (mut size_bytes, mut file_count, mut oldest) = (0, 0, None::<SystemTime>);
+    for (_, meta) in files_in(&root.join(&relative)) {
+        size_bytes += meta.len();
+        file_count += 1;
+        if let Ok(modified) = meta.modified() {
+            oldest = Some(oldest.map_or(modified, |o| o.min(modified)));
+        }
+    }
+    MapCache {
+        plugin: plugin.to_string(),
+        map,
+        path: relative,
+        size_bytes,
+        file_count,
+        oldest: oldest.map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339()),
+    }
+}
+
+/// Deletes tiles older than `older_than_days` (all of them when None) from the given caches, or
+/// from every cache of the server. Empty folders are removed too; the cache folders stay.
+pub fn prune_map_caches_direct(root: &Path, caches: Option<&[String]>, older_than_days: Option<u32>) -> Result<MapCleanupReport, String> {
+    let cutoff = older_than_days.map(|d| SystemTime::now() - Duration::from_secs(d as u64 * 86_400));
+    let params = serde_json::json!({ "serverPath": root, "caches": caches, "olderThanDays": older_than_days });
+    audited("prune_map_caches", params, || {
+        let mut report = MapCleanupReport::default();
+        // Only folders found here are touched, whatever paths were passed in
+        let selected = find_cache_dirs(root).into_iter()
+            .filter(|(_, _, relative)| caches.map(|c| c.contains(relative)).unwrap_or(true));
+        for (_, _, relative) in selected {
+            let dir = root.join(&relative);
+            for (path, meta) in files_in(&dir) {
+                let old = match (cutoff, meta.modified()) {
+                    (None, _) => true,
+                    (Some(cutoff), Ok(modified)) => modified < cutoff,
+                    (Some(_), Err(_)) => false,
+                };
+                // A tile the plugin is writing right now may be locked; it is left for next time
+                if !old || fs::remove_file(&path).is_err() {
+                    report.remaining_bytes += meta.len();
+                    continue;
+                }
+                report.deleted_files += 1;
+                report.freed_bytes += meta.len();
+            }
+            for entry in WalkDir::new(&dir).min_depth(1).contents_first(true).into_iter().flatten() {
+                if entry.file_type().is_dir() {
+                    let _ = fs::remove_dir(entry.path()); // Fails while it still has tiles
+                }
+            }
+        }
+        Ok(report)
+    })
+}
+
+/// BlueMap and Dynmap tile caches of a server with their size.
+#[tauri::command]
+pub async fn get_map_caches(server_path: String) -> Result<Vec<MapCache>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    Ok(tokio::task::spawn_blocking(move || {
+        find_cache_dirs(&root).into_iter().map(|dir| measure(&root, dir)).collect()
+    }).await?)
+}
+
+/// Deletes map tiles by age from the selected caches (`paths` from `get_map_caches`). Without an
+/// age the selected caches are emptied, which makes the plugin render them again.
+#[tauri::command]
+pub async fn prune_map_caches(
+    server_path: String,
+    caches: Option<Vec<String>>,
+    older_than_days: Option<u32>,
+) -> Result<MapCleanupReport, MineServerError> {
+    let root = validate_path(&server_path)?;
+    if caches.is_none() && older_than_days.is_none() {
+        return Err(MineServerError::validation("Select map caches or set an age filter"));
+    }
+    tokio::task::spawn_blocking(move || prune_map_caches_direct(&root, caches.as_deref(), older_than_days))
+        .await?
+        .map_err(Into::into)
+}
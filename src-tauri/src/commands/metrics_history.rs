@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use super::runner::{ServerProcessState, collect_process_usage};
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Sampled CPU/RAM/TPS/player metrics in ~/Mineserver/metrics.db so graphs survive restarts.
+// Raw samples are kept for a day, then folded into 5-minute averages (kept a week),
+// then into hourly averages (kept 90 days).
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+const MAINTENANCE_EVERY: u32 = 240; // samples, ~1 hour
+
+const RES_RAW: i64 = 0;
+const RES_5MIN: i64 = 300;
+const RES_HOUR: i64 = 3600;
+
+const RAW_RETENTION_SECS: i64 = 24 * 3600;
+const FIVE_MIN_RETENTION_SECS: i64 = 7 * 24 * 3600;
+const HOUR_RETENTION_SECS: i64 = 90 * 24 * 3600;
+
+static RECORDER_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricSample {
+    pub timestamp: i64, // Unix seconds
+    pub cpu: f32,
+    pub memory: u64,
+    pub tps: Option<f32>,
+    pub players: u32,
+}
+
+fn get_metrics_db() -> PathBuf {
+    app_data_dir().join("metrics.db")
+}
+
+fn open_db() -> Result<Connection, String> {
+    let path = get_metrics_db();
+    std::fs::create_dir_all(path.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let conn = Connection::open(&path)
+        .map_err(|e| format!("Failed to open metrics database: {}", e))?;
+    conn.busy_timeout(Duration::from_secs(5)).map_err(|e| e.to_string())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         CREATE TABLE IF NOT EXISTS samples (
+             server_id TEXT NOT NULL,
+             ts INTEGER NOT NULL,
+             resolution INTEGER NOT NULL,
+             cpu REAL NOT NULL,
+             memory INTEGER NOT NULL,
+             tps REAL,
+             players INTEGER NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_samples_server_ts ON samples (server_id, ts);"
+    ).map_err(|e| format!("Failed to initialize metrics database: {}", e))?;
+    Ok(conn)
+}
+
+/// Folds rows of `from_res` older than `cutoff` into `to_res` buckets.
+fn downsample(conn: &Connection, from_res: i64, to_res: i64, cutoff: i64) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO samples (server_id, ts, resolution, cpu, memory, tps, players)
+         SELECT server_id, (ts / ?2) * ?2, ?2, AVG(cpu), CAST(AVG(memory) AS INTEGER), AVG(tps), MAX(players)
+         FROM samples WHERE resolution = ?1 AND ts < ?3
+         GROUP BY server_id, ts / ?2",
+        params![from_res, to_res, cutoff],
+    )?;
+    tx.execute(
+        "DELETE FROM samples WHERE resolution = ?1 AND ts < ?2",
+        params![from_res, cutoff],
+    )?;
+    tx.commit()
+}
+
+fn run_maintenance(conn: &Connection) -> rusqlite::Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    // Align cutoffs to bucket boundaries so a bucket is never split across two passes
+    let raw_cutoff = (now - RAW_RETENTION_SECS) / RES_5MIN * RES_5MIN;
+    let five_min_cutoff = (now - FIVE_MIN_RETENTION_SECS) / RES_HOUR * RES_HOUR;
+
+    downsample(conn, RES_RAW, RES_5MIN, raw_cutoff)?;
+    downsample(conn, RES_5MIN, RES_HOUR, five_min_cutoff)?;
+    conn.execute(
+        "DELETE FROM samples WHERE ts < ?1",
+        params![now - HOUR_RETENTION_SECS],
+    )?;
+    Ok(())
+}
+
+fn record_samples(conn: &Connection, proc_state: &ServerProcessState, sys: &mut sysinfo::System) -> Result<(), String> {
+    let running: Vec<(String, u32)> = {
+        let procs = proc_state.processes.lock().map_err(|e| e.to_string())?;
+        procs.iter().map(|(id, child)| (id.clone(), child.id())).collect()
+    };
+    if running.is_empty() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for (id, pid) in running {
+        let (cpu, memory) = collect_process_usage(sys, pid);
+        let (tps, players) = proc_state.live_stats.lock().ok()
+            .and_then(|s| s.get(&id).map(|l| (l.tps, l.online_players.len() as u32)))
+            .unwrap_or((None, 0));
+
+        conn.execute(
+            "INSERT INTO samples (server_id, ts, resolution, cpu, memory, tps, players) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![id, now, RES_RAW, cpu, memory as i64, tps, players],
+        ).map_err(|e| format!("Failed to record metrics: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Starts the background sampler (once per process).
+pub fn start_metrics_recorder(proc_state: ServerProcessState) {
+    if RECORDER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let conn = match open_db() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("[Metrics] {}", e);
+                return;
+            }
+        };
+        let mut sys = sysinfo::System::new();
+        let mut ticks: u32 = 0;
+
+        loop {
+            std::thread::sleep(SAMPLE_INTERVAL);
+
+            if let Err(e) = record_samples(&conn, &proc_state, &mut sys) {
+                eprintln!("[Metrics] {}", e);
+            }
+
+            ticks += 1;
+            if ticks % MAINTENANCE_EVERY == 1 {
+                if let Err(e) = run_maintenance(&conn) {
+                    eprintln!("[Metrics] Maintenance failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Samples for a server between `from` and `to` (Unix seconds). With `max_points`, rows are
+/// averaged into evenly sized buckets so long ranges stay light for the chart.
+#[tauri::command]
+pub async fn get_metrics_history(id: String, from: i64, to: i64, max_points: Option<u32>) -> Result<Vec<MetricSample>, MineServerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = open_db()?;
+        let bucket = match max_points {
+            Some(points) if points > 0 => ((to - from) / points as i64).max(1),
+            _ => 1,
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT MIN(ts), AVG(cpu), CAST(AVG(memory) AS INTEGER), AVG(tps), MAX(players)
+             FROM samples WHERE server_id = ?1 AND ts >= ?2 AND ts <= ?3
+             GROUP BY ts / ?4 ORDER BY MIN(ts)"
+        ).map_err(|e| format!("Failed to query metrics: {}", e))?;
+
+        let rows = stmt.query_map(params![id, from, to, bucket], |row| {
+            Ok(MetricSample {
+                timestamp: row.get(0)?,
+                cpu: row.get::<_, f64>(1)? as f32,
+                memory: row.get::<_, i64>(2)?.max(0) as u64,
+                tps: row.get::<_, Option<f64>>(3)?.map(|t| t as f32),
+                players: row.get(4)?,
+            })
+        }).map_err(|e| format!("Failed to query metrics: {}", e))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read metrics: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?.map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn clear_metrics_history(id: String) -> Result<(), MineServerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = open_db()?;
+        conn.execute("DELETE FROM samples WHERE server_id = ?1", params![id])
+            .map_err(|e| format!("Failed to clear metrics: {}", e))?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -8,3 +8,56 @@ pub mod network_manager;
 pub mod server_config;
 pub mod plugins;
 pub mod backup;
+pub mod notifications;
+pub mod http_api;
+pub mod registry;
+pub mod query;
+pub mod player_stats;
+pub mod player_actions;
+pub mod metrics_history;
+pub mod logs;
+pub mod path_guard;
+pub mod settings;
+pub mod extract;
+pub mod eula;
+pub mod audit;
+pub mod trash;
+pub mod upload;
+pub mod checksum;
+pub mod disk_usage;
+pub mod nbt;
+pub mod config_validation;
+pub mod fs_watch;
+pub mod ftp;
+pub mod bedrock_access;
+pub mod mod_config;
+pub mod mod_updates;
+pub mod modpack;
+pub mod ftb;
+pub mod http;
+pub mod archive;
+pub mod operations;
+pub mod error;
+pub mod ping;
+pub mod uptime;
+pub mod world_settings;
+pub mod motd;
+pub mod snippets;
+pub mod luckperms;
+pub mod power;
+pub mod favorites;
+pub mod voice_chat;
+pub mod activity;
+pub mod safe_mode;
+pub mod bisect;
+pub mod player_sync;
+pub mod bootstrap;
+pub mod gc_log;
+pub mod server_profile;
+pub mod quick_settings;
+pub mod json_store;
+pub mod map_cache;
+pub mod auth_diagnostics;
+pub mod network_players;
+pub mod rcon;
+pub mod diagnostics;
@@ -0,0 +1,221 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use toml_edit::{DocumentMut, Item, Table, Value};
+use walkdir::WalkDir;
+use super::path_guard::validate_path;
+use super::server_config::read_server_properties;
+use super::error::MineServerError;
+
+// Forge/NeoForge mod configs: config/**/*.toml, defaultconfigs/*.toml and the per-world
+// <world>/serverconfig/*.toml. Edited with toml_edit so comments and layout survive.
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConfigFile {
+    pub path: String, // Relative to the server folder, "/" separated
+    pub name: String,
+    pub source: String, // "config", "serverconfig" or "defaultconfigs"
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConfigEntry {
+    pub path: Vec<String>, // Table names followed by the key
+    pub value: JsonValue,
+    pub value_type: String, // "string", "integer", "float", "boolean", "array", "datetime" or "inlineTable"
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModConfigUpdate {
+    pub path: Vec<String>,
+    pub value: JsonValue,
+}
+
+fn serverconfig_dir(root: &Path) -> PathBuf {
+    let level_name = read_server_properties(root.to_string_lossy().to_string())
+        .ok()
+        .and_then(|p| p.get("level-name").cloned())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "world".to_string());
+    root.join(level_name).join("serverconfig")
+}
+
+/// Resolves `file` (relative) and makes sure it is a .toml inside one of the config folders.
+fn resolve_config_file(root: &Path, file: &str) -> Result<PathBuf, String> {
+    let path = validate_path(&root.join(file).to_string_lossy())?;
+    let is_toml = path.extension().map(|e| e.eq_ignore_ascii_case("toml")).unwrap_or(false);
+    let allowed = [root.join("config"), root.join("defaultconfigs"), serverconfig_dir(root)]
+        .iter()
+        .filter_map(|dir| fs::canonicalize(dir).ok())
+        .any(|dir| path.starts_with(dir));
+    if !is_toml || !allowed {
+        return Err("Only .toml files in config/, defaultconfigs/ or serverconfig/ can be edited here".to_string());
+    }
+    Ok(path)
+}
+
+fn comment_of(decor: &toml_edit::Decor) -> Option<String> {
+    let prefix = decor.prefix()?.as_str()?;
+    let lines: Vec<&str> = prefix.lines()
+        .map(str::trim)
+        .filter_map(|l| l.strip_prefix('#'))
+        .map(str::trim)
+        .collect();
+    if lines.is_empty() { None } else { Some(lines.join("\n")) }
+}
+
+fn value_to_json(value: &Value) -> (JsonValue, &'static str) {
+    match value {
+        Value::String(s) => (JsonValue::from(s.value().as_str()), "string"),
+        Value::Integer(i) => (JsonValue::from(*i.value()), "integer"),
+        Value::Float(f) => (serde_json::Number::from_f64(*f.value()).map(JsonValue::Number).unwrap_or(JsonValue::Null), "float"),
+        Value::Boolean(b) => (JsonValue::from(*b.value()), "boolean"),
+        Value::Datetime(d) => (JsonValue::from(d.value().to_string()), "datetime"),
+        Value::Array(a) => (JsonValue::Array(a.iter().map(|v| value_to_json(v).0).collect()), "array"),
+        Value::InlineTable(t) => (
+            JsonValue::Object(t.iter().map(|(k, v)| (k.to_string(), value_to_json(v).0)).collect()),
+            "inlineTable",
+        ),
+    }
+}
+
+fn collect_entries(table: &Table, prefix: &[String], out: &mut Vec<ModConfigEntry>) {
+    for (key, _) in table.iter() {
+        let (key_repr, item) = match table.get_key_value(key) {
+            Some(kv) => kv,
+            None => continue,
+        };
+        let mut path = prefix.to_vec();
+        path.push(key.to_string());
+        match item {
+            Item::Value(value) => {
+                let (json, value_type) = value_to_json(value);
+                out.push(ModConfigEntry {
+                    path,
+                    value: json,
+                    value_type: value_type.to_string(),
+                    comment: comment_of(key_repr.leaf_decor()),
+                });
+            },
+            Item::Table(child) => collect_entries(child, &path, out),
+            _ => {}, // Arrays of tables aren't used by mod configs
+        }
+    }
+}
+
+/// Converts `json` to a TOML value of the same kind as `current`.
+fn json_to_value(json: &JsonValue, current: &Value) -> Result<Value, String> {
+    Ok(match (current, json) {
+        (Value::String(_), JsonValue::String(s)) => Value::from(s.as_str()),
+        (Value::Integer(_), JsonValue::Number(n)) => Value::from(n.as_i64().ok_or("Expected a whole number")?),
+        (Value::Float(_), JsonValue::Number(n)) => Value::from(n.as_f64().ok_or("Expected a number")?),
+        (Value::Boolean(_), JsonValue::Bool(b)) => Value::from(*b),
+        (Value::Array(existing), JsonValue::Array(items)) => {
+            let mut array = toml_edit::Array::new();
+            for item in items {
+                // Element type follows the existing elements; plain JSON types for empty arrays
+                let converted = match existing.get(0) {
+                    Some(sample) => json_to_value(item, sample)?,
+                    None => match item {
+                        JsonValue::String(s) => Value::from(s.as_str()),
+                        JsonValue::Bool(b) => Value::from(*b),
+                        JsonValue::Number(n) if n.is_i64() => Value::from(n.as_i64().unwrap_or_default()),
+                        JsonValue::Number(n) => Value::from(n.as_f64().unwrap_or_default()),
+                        _ => return Err("Unsupported array element".to_string()),
+                    },
+                };
+                array.push(converted);
+            }
+            Value::Array(array)
+        },
+        (_, json) => {
+            let (_, expected) = value_to_json(current);
+            return Err(format!("Expected a {} value, got {}", expected, json));
+        },
+    })
+}
+
+fn apply_update(doc: &mut DocumentMut, update: &ModConfigUpdate) -> Result<(), String> {
+    let (key, tables) = update.path.split_last().ok_or("Empty config key")?;
+    let mut table: &mut Table = doc.as_table_mut();
+    for name in tables {
+        table = table.get_mut(name)
+            .and_then(|item| item.as_table_mut())
+            .ok_or_else(|| format!("Unknown section: {}", name))?;
+    }
+
+    let current = table.get_mut(key)
+        .and_then(|item| item.as_value_mut())
+        .ok_or_else(|| format!("Unknown setting: {}", update.path.join(".")))?;
+    let mut new_value = json_to_value(&update.value, current)
+        .map_err(|e| format!("{}: {}", update.path.join("."), e))?;
+    // Keep the original spacing/inline comment around the value
+    *new_value.decor_mut() = current.decor().clone();
+    *current = new_value;
+    Ok(())
+}
+
+/// Mod config files of a modded server.
+#[tauri::command]
+pub fn list_mod_configs(server_path: String) -> Result<Vec<ModConfigFile>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let mut files = Vec::new();
+
+    for (dir, source) in [
+        (root.join("config"), "config"),
+        (root.join("defaultconfigs"), "defaultconfigs"),
+        (serverconfig_dir(&root), "serverconfig"),
+    ] {
+        for entry in WalkDir::new(&dir).max_depth(4).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !entry.file_type().is_file() || !path.extension().map(|e| e.eq_ignore_ascii_case("toml")).unwrap_or(false) {
+                continue;
+            }
+            files.push(ModConfigFile {
+                path: path.strip_prefix(&root).unwrap_or(path).to_string_lossy().replace('\\', "/"),
+                name: entry.file_name().to_string_lossy().to_string(),
+                source: source.to_string(),
+                size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+#[tauri::command]
+pub fn read_mod_config(server_path: String, file: String) -> Result<Vec<ModConfigEntry>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let path = resolve_config_file(&root, &file)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let doc: DocumentMut = content.parse()
+        .map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+
+    let mut entries = Vec::new();
+    collect_entries(doc.as_table(), &[], &mut entries);
+    Ok(entries)
+}
+
+/// Changes existing settings only; comments, ordering and formatting are kept.
+#[tauri::command]
+pub fn update_mod_config(server_path: String, file: String, updates: Vec<ModConfigUpdate>) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+    let path = resolve_config_file(&root, &file)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let mut doc: DocumentMut = content.parse()
+        .map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+
+    for update in &updates {
+        apply_update(&mut doc, update)?;
+    }
+
+    fs::write(&path, doc.to_string())
+        .map_err(|e| format!("Failed to write {}: {}", file, e)).map_err(Into::into)
+}
@@ -0,0 +1,322 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::audit::audited;
+use tauri::{AppHandle, State};
+use super::checksum::hash_path;
+use super::http::HttpState;
+use super::activity::Activity;
+use super::backup::pre_update_backup;
+use super::operations::OperationState;
+use super::path_guard::validate_path;
+use super::error::MineServerError;
+
+// Update checks for mods/: installed jars are identified on Modrinth by their SHA-1, then asked
+// for the newest version matching the server's loader and game version. Replaced jars are kept
+// in `<server>/.mineserver-mod-backups/` so an update can be rolled back.
+
+pub const MOD_BACKUP_DIR: &str = ".mineserver-mod-backups";
+const ROLLBACK_FILE: &str = "rollback.json";
+
+#[derive(Debug, Clone, Deserialize)]
+struct ModrinthVersionFile {
+    url: String,
+    filename: String,
+    primary: bool,
+    hashes: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ModrinthVersionInfo {
+    id: String,
+    project_id: String,
+    name: String,
+    version_number: String,
+    files: Vec<ModrinthVersionFile>,
+}
+
+impl ModrinthVersionInfo {
+    fn primary_file(&self) -> Option<&ModrinthVersionFile> {
+        self.files.iter().find(|f| f.primary).or_else(|| self.files.first())
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdate {
+    pub filename: String,
+    pub enabled: bool,
+    pub project_id: String,
+    pub name: String,
+    pub current_version: String,
+    pub latest_version: Option<String>, // None if nothing matches the loader/game version
+    pub latest_version_id: Option<String>,
+    pub latest_filename: Option<String>,
+    pub update_available: bool,
+    pub can_rollback: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdateReport {
+    pub loader: String,
+    pub game_version: String,
+    pub mods: Vec<ModUpdate>,
+    pub unknown: Vec<String>, // Jars Modrinth doesn't know (CurseForge-only, custom builds...)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RollbackEntry {
+    filename: String, // Jar installed by the update
+    previous_filename: String, // Jar it replaced, now in the backup folder
+    replaced_at: u64,
+}
+
+fn load_rollbacks(root: &Path) -> Vec<RollbackEntry> {
+    fs::read_to_string(root.join(MOD_BACKUP_DIR).join(ROLLBACK_FILE))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_rollbacks(root: &Path, entries: &[RollbackEntry]) -> Result<(), String> {
+    let dir = root.join(MOD_BACKUP_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    fs::write(dir.join(ROLLBACK_FILE), content)
+        .map_err(|e| format!("Failed to save rollback info: {}", e))
+}
+
+/// Jars in mods/, enabled and `.jar.disabled`.
+fn installed_mods(root: &Path) -> Vec<(String, PathBuf)> {
+    let entries = match fs::read_dir(root.join("mods")) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let mut mods: Vec<(String, PathBuf)> = entries.flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter_map(|p| {
+            let name = p.file_name()?.to_str()?.to_string();
+            if name.ends_with(".jar") || name.ends_with(".jar.disabled") { Some((name, p)) } else { None }
+        })
+        .collect();
+    mods.sort_by(|a, b| a.0.cmp(&b.0));
+    mods
+}
+
+/// Reports the newest Modrinth version of each installed mod for `loader` (fabric, forge,
+/// neoforge, quilt) and `game_version`.
+#[tauri::command]
+pub async fn check_mod_updates(http: State<'_, HttpState>, server_path: String, loader: String, game_version: String) -> Result<ModUpdateReport, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let mods = installed_mods(&root);
+
+    let hashed: Vec<(String, String)> = tokio::task::spawn_blocking(move || {
+        mods.into_iter()
+            .filter_map(|(name, path)| hash_path(&path, "sha1").ok().map(|h| (name, h)))
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if hashed.is_empty() {
+        return Ok(ModUpdateReport { loader, game_version, mods: vec![], unknown: vec![] });
+    }
+    let hashes: Vec<&String> = hashed.iter().map(|(_, h)| h).collect();
+    let client = http.client();
+
+    // Batch form of the version_file endpoint: hash -> version that file belongs to
+    let current: HashMap<String, ModrinthVersionInfo> = client
+        .post("https://api.modrinth.com/v2/version_files")
+        .json(&json!({ "hashes": hashes, "algorithm": "sha1" }))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    // hash -> newest compatible version (missing when nothing matches)
+    let latest: HashMap<String, ModrinthVersionInfo> = client
+        .post("https://api.modrinth.com/v2/version_files/update")
+        .json(&json!({
+            "hashes": hashes,
+            "algorithm": "sha1",
+            "loaders": [loader.to_lowercase()],
+            "game_versions": [game_version],
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    let rollbacks = load_rollbacks(&root);
+    let mut report = ModUpdateReport { loader, game_version, mods: vec![], unknown: vec![] };
+    for (filename, hash) in hashed {
+        let version = match current.get(&hash) {
+            Some(v) => v,
+            None => {
+                report.unknown.push(filename);
+                continue;
+            },
+        };
+        let newest = latest.get(&hash);
+        report.mods.push(ModUpdate {
+            enabled: !filename.ends_with(".disabled"),
+            can_rollback: rollbacks.iter().any(|r| r.filename == filename),
+            project_id: version.project_id.clone(),
+            name: version.name.clone(),
+            current_version: version.version_number.clone(),
+            latest_version: newest.map(|v| v.version_number.clone()),
+            latest_version_id: newest.map(|v| v.id.clone()),
+            latest_filename: newest.and_then(|v| v.primary_file()).map(|f| f.filename.clone()),
+            update_available: newest.map(|v| v.id != version.id).unwrap_or(false),
+            filename,
+        });
+    }
+    Ok(report)
+}
+
+/// Replaces `filename` in mods/ with Modrinth version `version_id`. The old jar is kept for
+/// `rollback_mod`. A disabled mod stays disabled. Returns the new filename.
+#[tauri::command]
+pub async fn update_mod(app: AppHandle, http: State<'_, HttpState>, ops: State<'_, OperationState>, server_path: String, filename: String, version_id: String) -> Result<String, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let mods_dir = root.join("mods");
+    let old_path = validate_path(&mods_dir.join(&filename).to_string_lossy())?;
+    if old_path.parent() != Some(fs::canonicalize(&mods_dir).map_err(|e| e.to_string())?.as_path()) || !old_path.is_file() {
+        return Err(MineServerError::not_found("Mod not found"));
+    }
+
+    let backup_id = pre_update_backup(app, ops.inner(), &server_path, "configs").await?;
+    let mut activity = Activity::begin("mod_update", &server_path, format!("Update {}", filename));
+    activity.set_backup_id(backup_id);
+    let client = http.client();
+    let version: ModrinthVersionInfo = client
+        .get(format!("https://api.modrinth.com/v2/version/{}", urlencoding::encode(&version_id)))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse version: {}", e))?;
+    let file = version.primary_file().ok_or("No file found for this version")?.clone();
+
+    let bytes = client.get(&file.url)
+        .send()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read bytes: {}", e))?;
+
+    activity.set_title(format!("Update {} to {}", filename, file.filename));
+    let size = bytes.len() as u64;
+    let result = tokio::task::spawn_blocking(move || {
+        audited("update_mod", json!({ "serverPath": server_path, "filename": filename, "versionId": version_id }), || {
+            install_update(&root, &old_path, &filename, &file, &bytes)
+        })
+    })
+    .await?;
+    activity.finish(&result, Some(size));
+    result.map_err(Into::into)
+}
+
+fn install_update(root: &Path, old_path: &Path, filename: &str, file: &ModrinthVersionFile, bytes: &[u8]) -> Result<String, String> {
+    use sha1::{Digest, Sha1};
+
+    if let Some(expected) = file.hashes.get("sha1") {
+        let actual = super::upload::to_hex(&Sha1::digest(bytes));
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err("Downloaded file failed the checksum check".to_string());
+        }
+    }
+
+    let disabled = filename.ends_with(".disabled");
+    let new_filename = if disabled { format!("{}.disabled", file.filename) } else { file.filename.clone() };
+    if new_filename.contains(['/', '\\']) {
+        return Err("Invalid filename from Modrinth".to_string());
+    }
+    let mods_dir = old_path.parent().ok_or("Invalid mod path")?;
+    let new_path = mods_dir.join(&new_filename);
+    if new_path.exists() && new_filename != filename {
+        return Err(format!("{} already exists in mods/", new_filename));
+    }
+
+    let mut rollbacks = load_rollbacks(root);
+    // Updating an already updated mod keeps the original backup; the intermediate jar is dropped
+    let earlier = rollbacks.iter().position(|r| r.filename == filename).map(|i| rollbacks.remove(i));
+
+    // The new jar is written to a temp name first so a failure leaves mods/ intact
+    let backup_dir = root.join(MOD_BACKUP_DIR);
+    fs::create_dir_all(&backup_dir).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    let temp_path = mods_dir.join(format!("{}.part", new_filename));
+    fs::write(&temp_path, bytes).map_err(|e| format!("Failed to write mod: {}", e))?;
+    let set_aside = match &earlier {
+        Some(_) => mods_dir.join(format!("{}.old", filename)),
+        None => backup_dir.join(filename),
+    };
+    if let Err(e) = fs::rename(old_path, &set_aside) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to back up old mod: {}", e));
+    }
+    if let Err(e) = fs::rename(&temp_path, &new_path) {
+        let _ = fs::rename(&set_aside, old_path);
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to install mod: {}", e));
+    }
+    if earlier.is_some() {
+        let _ = fs::remove_file(&set_aside);
+    }
+
+    rollbacks.retain(|r| r.filename != new_filename);
+    rollbacks.push(RollbackEntry {
+        filename: new_filename.clone(),
+        previous_filename: earlier.map(|e| e.previous_filename).unwrap_or_else(|| filename.to_string()),
+        replaced_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    });
+    save_rollbacks(root, &rollbacks)?;
+    Ok(new_filename)
+}
+
+/// Puts back the jar that `update_mod` replaced. Returns the restored filename.
+#[tauri::command]
+pub fn rollback_mod(server_path: String, filename: String) -> Result<String, MineServerError> {
+    let root = validate_path(&server_path)?;
+    audited("rollback_mod", json!({ "serverPath": server_path, "filename": filename }), || {
+        let mut rollbacks = load_rollbacks(&root);
+        let index = rollbacks.iter().position(|r| r.filename == filename)
+            .ok_or("No earlier version of this mod was kept")?;
+        let entry = rollbacks[index].clone();
+
+        let mods_dir = root.join("mods");
+        let backup = root.join(MOD_BACKUP_DIR).join(&entry.previous_filename);
+        if !backup.is_file() {
+            return Err("The backed up jar is missing".to_string());
+        }
+        let restored = mods_dir.join(&entry.previous_filename);
+        if restored.exists() && entry.previous_filename != entry.filename {
+            return Err(format!("{} already exists in mods/", entry.previous_filename));
+        }
+
+        let current = mods_dir.join(&entry.filename);
+        if current.exists() {
+            fs::remove_file(&current).map_err(|e| format!("Failed to remove updated mod: {}", e))?;
+        }
+        fs::rename(&backup, &restored).map_err(|e| format!("Failed to restore mod: {}", e))?;
+
+        rollbacks.remove(index);
+        save_rollbacks(&root, &rollbacks)?;
+        Ok(entry.previous_filename)
+    }).map_err(Into::into)
+}
@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+use std::fs::{self, File};
+use std::io::Write;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use tauri::State;
+use super::archive::{zip_file, zip_write_error};
+use super::checksum::hash_path;
+use super::http::HttpState;
+use super::path_guard::validate_path;
+use super::registry::load_registry;
+use super::error::MineServerError;
+
+// Exports a Modrinth modpack (.mrpack) matching a Forge/NeoForge/Fabric server, so players
+// install one file in their launcher. Mods Modrinth knows are referenced by download URL;
+// anything else goes into overrides/ together with the config folders.
+// Format: https://support.modrinth.com/en/articles/8802351-modrinth-modpack-format-mrpack
+
+const CONFIG_DIRS: [&str; 2] = ["config", "defaultconfigs"];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientPackReport {
+    pub path: String,
+    pub loader: String,
+    pub loader_version: String,
+    pub game_version: String,
+    pub modrinth_mods: Vec<String>, // Downloaded by the launcher
+    pub bundled_mods: Vec<String>, // Not on Modrinth, copied into overrides/mods
+    pub server_only_mods: Vec<String>, // Left out: Modrinth marks them unsupported on clients
+    pub config_files: usize,
+}
+
+struct LoaderInfo {
+    loader: &'static str, // mrpack dependency key
+    version: String,
+    game_version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionFile {
+    url: String,
+    hashes: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionInfo {
+    project_id: String,
+    files: Vec<VersionFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectInfo {
+    id: String,
+    client_side: String, // "required", "optional", "unsupported" or "unknown"
+}
+
+/// Subfolder names of `dir`, e.g. the installed versions of a library in libraries/.
+fn subdirs(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| entries.flatten()
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// NeoForge versions encode the game version: 20.4.237 -> 1.20.4, 21.0.10 -> 1.21.
+fn neoforge_game_version(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major = parts.next()?;
+    let minor = parts.next()?;
+    Some(if minor == "0" { format!("1.{}", major) } else { format!("1.{}.{}", major, minor) })
+}
+
+/// Reads the loader from the libraries the installer put on disk.
+fn detect_loader(root: &Path) -> Result<LoaderInfo, String> {
+    let libraries = root.join("libraries");
+    let registered_version = load_registry().ok()
+        .and_then(|servers| servers.into_iter().find(|s| {
+            fs::canonicalize(&s.path).map(|p| p == root).unwrap_or(false)
+        }))
+        .map(|s| s.version);
+
+    if let Some(version) = subdirs(&libraries.join("net/neoforged/neoforge")).pop() {
+        let game_version = neoforge_game_version(&version).ok_or("Unrecognized NeoForge version")?;
+        return Ok(LoaderInfo { loader: "neoforge", version, game_version });
+    }
+    if let Some(dir) = subdirs(&libraries.join("net/minecraftforge/forge")).pop() {
+        // Folder is "<game version>-<forge version>"
+        let (game_version, version) = dir.split_once('-').ok_or("Unrecognized Forge version")?;
+        return Ok(LoaderInfo { loader: "forge", version: version.to_string(), game_version: game_version.to_string() });
+    }
+    if let Some(version) = subdirs(&libraries.join("net/fabricmc/fabric-loader")).pop() {
+        let game_version = subdirs(&libraries.join("net/fabricmc/intermediary")).pop()
+            .or(registered_version)
+            .ok_or("Could not determine the Minecraft version")?;
+        return Ok(LoaderInfo { loader: "fabric-loader", version, game_version });
+    }
+    if let Some(version) = subdirs(&libraries.join("org/quiltmc/quilt-loader")).pop() {
+        let game_version = subdirs(&libraries.join("net/fabricmc/intermediary")).pop()
+            .or(registered_version)
+            .ok_or("Could not determine the Minecraft version")?;
+        return Ok(LoaderInfo { loader: "quilt-loader", version, game_version });
+    }
+    Err("No Forge, NeoForge, Fabric or Quilt installation found. Start the server once so the loader installs its libraries.".to_string())
+}
+
+fn hash_mods(root: &Path) -> Result<Vec<(String, PathBuf, String, String, u64)>, String> {
+    let mut mods = Vec::new();
+    for entry in fs::read_dir(root.join("mods")).map_err(|e| format!("Failed to read mods folder: {}", e))?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || !name.ends_with(".jar") {
+            continue; // Disabled mods stay out of the pack
+        }
+        let sha1 = hash_path(&path, "sha1")?;
+        let sha512 = hash_path(&path, "sha512")?;
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        mods.push((name, path, sha1, sha512, size));
+    }
+    mods.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(mods)
+}
+
+/// Builds a .mrpack for the server's client-side mods and configs. Written to `output_path`,
+/// or `<server>/<folder name>-client.mrpack` when not given.
+#[tauri::command]
+pub async fn export_client_pack(http: State<'_, HttpState>, server_path: String, output_path: Option<String>) -> Result<ClientPackReport, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let loader = detect_loader(&root)?;
+
+    let scan_root = root.clone();
+    let mods = tokio::task::spawn_blocking(move || hash_mods(&scan_root))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let client = http.client();
+
+    let hashes: Vec<&String> = mods.iter().map(|m| &m.2).collect();
+    let versions: HashMap<String, VersionInfo> = if hashes.is_empty() {
+        HashMap::new()
+    } else {
+        client.post("https://api.modrinth.com/v2/version_files")
+            .json(&json!({ "hashes": hashes, "algorithm": "sha1" }))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?
+    };
+
+    let project_ids: Vec<&String> = versions.values().map(|v| &v.project_id).collect();
+    let projects: Vec<ProjectInfo> = if project_ids.is_empty() {
+        vec![]
+    } else {
+        let ids = serde_json::to_string(&project_ids).map_err(|e| e.to_string())?;
+        client.get(format!("https://api.modrinth.com/v2/projects?ids={}", urlencoding::encode(&ids)))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?
+    };
+    let client_side: HashMap<String, String> = projects.into_iter().map(|p| (p.id, p.client_side)).collect();
+
+    let pack_name = root.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "server".to_string());
+    let output = match output_path {
+        Some(p) => PathBuf::from(p),
+        None => root.join(format!("{}-client.mrpack", pack_name)),
+    };
+
+    let mut report = ClientPackReport {
+        path: output.to_string_lossy().to_string(),
+        loader: loader.loader.to_string(),
+        loader_version: loader.version.clone(),
+        game_version: loader.game_version.clone(),
+        modrinth_mods: vec![],
+        bundled_mods: vec![],
+        server_only_mods: vec![],
+        config_files: 0,
+    };
+
+    let mut index_files = Vec::new();
+    let mut bundled: Vec<PathBuf> = Vec::new();
+    for (name, path, sha1, sha512, size) in mods {
+        let version = match versions.get(&sha1) {
+            Some(v) => v,
+            None => {
+                report.bundled_mods.push(name);
+                bundled.push(path);
+                continue;
+            },
+        };
+        let side = client_side.get(&version.project_id).map(|s| s.as_str()).unwrap_or("unknown");
+        if side == "unsupported" {
+            report.server_only_mods.push(name);
+            continue;
+        }
+        let url = version.files.iter()
+            .find(|f| f.hashes.get("sha1") == Some(&sha1))
+            .map(|f| f.url.clone());
+        let url = match url {
+            Some(url) => url,
+            None => {
+                report.bundled_mods.push(name);
+                bundled.push(path);
+                continue;
+            },
+        };
+        index_files.push(json!({
+            "path": format!("mods/{}", name),
+            "hashes": { "sha1": sha1, "sha512": sha512 },
+            "env": { "client": if side == "optional" { "optional" } else { "required" }, "server": "required" },
+            "downloads": [url],
+            "fileSize": size,
+        }));
+        report.modrinth_mods.push(name);
+    }
+
+    let index = json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": chrono::Local::now().format("%Y.%m.%d-%H%M").to_string(),
+        "name": pack_name,
+        "summary": "Client pack exported from the server by Mineserver",
+        "files": index_files,
+        "dependencies": {
+            "minecraft": loader.game_version,
+            loader.loader: loader.version,
+        },
+    });
+
+    tokio::task::spawn_blocking(move || {
+        report.config_files = write_pack(&root, &output, &index, &bundled)?;
+        Ok(report)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Writes the archive and returns how many config files went into overrides/.
+fn write_pack(root: &Path, output: &Path, index: &serde_json::Value, bundled: &[PathBuf]) -> Result<usize, String> {
+    let file = File::create(output).map_err(|e| format!("Failed to create pack: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let index_json = serde_json::to_vec_pretty(index).map_err(|e| e.to_string())?;
+    zip.start_file("modrinth.index.json", options).map_err(|e| e.to_string())?;
+    zip.write_all(&index_json).map_err(|e| e.to_string())?;
+
+    for path in bundled {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        zip_file(&mut zip, &format!("overrides/mods/{}", name), path, options)?;
+    }
+
+    let mut config_files = 0;
+    for dir in CONFIG_DIRS {
+        for entry in WalkDir::new(root.join(dir)).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path()).to_string_lossy().replace('\\', "/");
+            zip_file(&mut zip, &format!("overrides/{}", relative), entry.path(), options)?;
+            config_files += 1;
+        }
+    }
+
+    zip.finish().map_err(|e| zip_write_error("Failed to finish pack", e))?;
+    Ok(config_files)
+}
@@ -0,0 +1,261 @@
+use std::path::Path;
+use serde::Serialize;
+use serde_json::Value;
+use super::ping::{java_status, ping_bedrock};
+use super::network_manager::server_listen_port;
+use super::registry::load_registry;
+use super::error::MineServerError;
+
+// "What players will see" in the multiplayer list: the MOTD split into styled segments plus
+// the favicon. A running server is asked via Server List Ping (so plugins that rewrite the
+// MOTD are reflected); otherwise server.properties and server-icon.png are read.
+//
+// MOTDs come either as chat components (`text`, `color`, `bold`, ..., nested `extra`) or as
+// text with legacy section-sign codes, and components can contain section codes too.
+
+const MAX_ICON_BYTES: u64 = 1024 * 1024;
+
+const LEGACY_COLORS: [(char, &str, &str); 16] = [
+    ('0', "black", "#000000"),
+    ('1', "dark_blue", "#0000AA"),
+    ('2', "dark_green", "#00AA00"),
+    ('3', "dark_aqua", "#00AAAA"),
+    ('4', "dark_red", "#AA0000"),
+    ('5', "dark_purple", "#AA00AA"),
+    ('6', "gold", "#FFAA00"),
+    ('7', "gray", "#AAAAAA"),
+    ('8', "dark_gray", "#555555"),
+    ('9', "blue", "#5555FF"),
+    ('a', "green", "#55FF55"),
+    ('b', "aqua", "#55FFFF"),
+    ('c', "red", "#FF5555"),
+    ('d', "light_purple", "#FF55FF"),
+    ('e', "yellow", "#FFFF55"),
+    ('f', "white", "#FFFFFF"),
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MotdStyle {
+    pub color: Option<String>, // "#RRGGBB"
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MotdSegment {
+    pub text: String, // May contain '\n'; the list shows two lines
+    #[serde(flatten)]
+    pub style: MotdStyle,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MotdPreview {
+    pub source: String, // "ping" or "files"
+    pub segments: Vec<MotdSegment>,
+    pub favicon: Option<String>, // data: URL
+    pub online_players: Option<u32>,
+    pub max_players: Option<u32>,
+    pub version: Option<String>,
+}
+
+fn push_segment(segments: &mut Vec<MotdSegment>, text: &str, style: &MotdStyle) {
+    if text.is_empty() {
+        return;
+    }
+    match segments.last_mut() {
+        Some(last) if last.style == *style => last.text.push_str(text),
+        _ => segments.push(MotdSegment { text: text.to_string(), style: style.clone() }),
+    }
+}
+
+/// Splits text on legacy codes, starting from (and updating) `style`.
+fn parse_legacy(text: &str, style: &mut MotdStyle, base: &MotdStyle, segments: &mut Vec<MotdSegment>) {
+    let mut buf = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{00A7}' {
+            buf.push(c);
+            continue;
+        }
+        let Some(code) = chars.next().map(|c| c.to_ascii_lowercase()) else { break };
+        push_segment(segments, &buf, style);
+        buf.clear();
+        if let Some((_, _, hex)) = LEGACY_COLORS.iter().find(|(k, _, _)| *k == code) {
+            // A color code also clears formatting
+            *style = MotdStyle { color: Some(hex.to_string()), ..MotdStyle::default() };
+            continue;
+        }
+        match code {
+            'k' => style.obfuscated = true,
+            'l' => style.bold = true,
+            'm' => style.strikethrough = true,
+            'n' => style.underlined = true,
+            'o' => style.italic = true,
+            'r' => *style = base.clone(),
+            _ => {}
+        }
+    }
+    push_segment(segments, &buf, style);
+}
+
+fn component_color(name: &str) -> Option<String> {
+    if name.starts_with('#') && name.len() == 7 {
+        return Some(name.to_uppercase());
+    }
+    LEGACY_COLORS.iter().find(|(_, n, _)| *n == name).map(|(_, _, hex)| hex.to_string())
+}
+
+fn parse_component(value: &Value, parent: &MotdStyle, segments: &mut Vec<MotdSegment>) {
+    match value {
+        Value::String(text) => parse_legacy(text, &mut parent.clone(), parent, segments),
+        Value::Array(parts) => {
+            for part in parts {
+                parse_component(part, parent, segments);
+            }
+        }
+        Value::Object(obj) => {
+            let flag = |key: &str, inherited: bool| obj.get(key).and_then(|v| v.as_bool()).unwrap_or(inherited);
+            let style = MotdStyle {
+                color: obj.get("color").and_then(|c| c.as_str()).and_then(component_color).or_else(|| parent.color.clone()),
+                bold: flag("bold", parent.bold),
+                italic: flag("italic", parent.italic),
+                underlined: flag("underlined", parent.underlined),
+                strikethrough: flag("strikethrough", parent.strikethrough),
+                obfuscated: flag("obfuscated", parent.obfuscated),
+            };
+            if let Some(text) = obj.get("text").and_then(|t| t.as_str()) {
+                parse_legacy(text, &mut style.clone(), &style, segments);
+            }
+            if let Some(extra) = obj.get("extra") {
+                parse_component(extra, &style, segments);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn parse_motd(description: &Value) -> Vec<MotdSegment> {
+    let mut segments = Vec::new();
+    parse_component(description, &MotdStyle::default(), &mut segments);
+    segments
+}
+
+/// Undoes the escaping Java uses when writing .properties files (`§`, `\n`, `\:` ...).
+fn unescape_property(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => out.push(decoded),
+                    None => out.push_str(&hex),
+                }
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+fn read_property(server_root: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(server_root.join("server.properties")).ok()?;
+    content.lines()
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| unescape_property(v.trim_start()))
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn read_icon(server_root: &Path) -> Option<String> {
+    let path = server_root.join("server-icon.png");
+    if std::fs::metadata(&path).ok()?.len() > MAX_ICON_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(&path).ok()?;
+    Some(format!("data:image/png;base64,{}", base64_encode(&bytes)))
+}
+
+fn preview_from_files(server_root: &Path, bedrock: bool) -> MotdPreview {
+    let (key, default) = if bedrock { ("server-name", "Dedicated Server") } else { ("motd", "A Minecraft Server") };
+    let motd = read_property(server_root, key).unwrap_or_else(|| default.to_string());
+    MotdPreview {
+        source: "files".to_string(),
+        segments: parse_motd(&Value::String(motd)),
+        favicon: if bedrock { None } else { read_icon(server_root) },
+        online_players: None,
+        max_players: read_property(server_root, "max-players").and_then(|v| v.trim().parse().ok()),
+        version: None,
+    }
+}
+
+/// Pings the local server when it answers, and falls back to the server's files otherwise.
+#[tauri::command]
+pub async fn get_motd_preview(id: String) -> Result<MotdPreview, MineServerError> {
+    let server = load_registry()?
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} not found", id)))?;
+
+    let preview = tokio::task::spawn_blocking(move || {
+        let (port, protocol) = server_listen_port(&server);
+        let root = Path::new(&server.path);
+        if protocol == "UDP" {
+            return match ping_bedrock("127.0.0.1", port) {
+                Ok(result) => MotdPreview {
+                    source: "ping".to_string(),
+                    segments: parse_motd(&Value::String(result.motd)),
+                    favicon: None,
+                    online_players: Some(result.online_players),
+                    max_players: Some(result.max_players),
+                    version: Some(result.version),
+                },
+                Err(_) => preview_from_files(root, true),
+            };
+        }
+        match java_status("127.0.0.1", port) {
+            Ok((status, _)) => MotdPreview {
+                source: "ping".to_string(),
+                segments: parse_motd(&status["description"]),
+                favicon: status["favicon"].as_str().map(str::to_string),
+                online_players: status["players"]["online"].as_u64().map(|v| v as u32),
+                max_players: status["players"]["max"].as_u64().map(|v| v as u32),
+                version: status["version"]["name"].as_str().map(str::to_string),
+            },
+            Err(_) => preview_from_files(root, false),
+        }
+    })
+    .await?;
+    Ok(preview)
+}
@@ -0,0 +1,540 @@
+use std::path::Path;
+use std::fs;
+use std::io::{Read, Write};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use super::path_guard::validate_path_inside_root;
+use super::error::MineServerError;
+
+// NBT <-> JSON conversion for level.dat, playerdata, villages.dat and friends.
+// Java files are big-endian (usually gzip'd); Bedrock files are little-endian, and Bedrock's
+// level.dat starts with an 8-byte header (storage version + payload length).
+//
+// JSON shape: every tag is {"type": "...", "value": ...}. Compound values are arrays of tags
+// with an extra "name" (keeps the original order), lists carry "elementType". Longs are strings
+// so JavaScript doesn't lose precision.
+
+const MAX_DEPTH: usize = 512;
+const MAX_NBT_FILE_BYTES: u64 = 64 * 1024 * 1024;
+// Cap on the decompressed size, so a small crafted gzip file cannot exhaust memory
+const MAX_NBT_DECODED_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+enum Tag {
+    End,
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(u8, Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+const TYPE_NAMES: [&str; 13] = [
+    "end", "byte", "short", "int", "long", "float", "double",
+    "byteArray", "string", "list", "compound", "intArray", "longArray",
+];
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::End => 0,
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(..) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NbtFormat {
+    pub compression: String, // "none", "gzip" or "zlib"
+    pub little_endian: bool, // Bedrock
+    pub bedrock_header: Option<i32>, // Storage version from Bedrock's level.dat header
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NbtDocument {
+    pub format: NbtFormat,
+    pub root_name: String,
+    pub root: Value,
+}
+
+// --- Binary reading ---
+
+struct NbtReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> NbtReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.data.len() - self.pos < n {
+            return Err("Unexpected end of NBT data".to_string());
+        }
+        let slice = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        if self.little_endian { out.reverse(); }
+        Ok(out) // Always big-endian from here on
+    }
+
+    fn u8(&mut self) -> Result<u8, String> { Ok(self.take(1)?[0]) }
+    fn i16(&mut self) -> Result<i16, String> { Ok(i16::from_be_bytes(self.array()?)) }
+    fn i32(&mut self) -> Result<i32, String> { Ok(i32::from_be_bytes(self.array()?)) }
+    fn i64(&mut self) -> Result<i64, String> { Ok(i64::from_be_bytes(self.array()?)) }
+
+    fn len(&mut self) -> Result<usize, String> {
+        let len = self.i32()?;
+        if len < 0 || len as usize > self.data.len() - self.pos {
+            return Err(format!("Invalid NBT length {}", len));
+        }
+        Ok(len as usize)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = u16::from_be_bytes(self.array()?) as usize;
+        let bytes = self.take(len)?;
+        Ok(if self.little_endian {
+            String::from_utf8_lossy(bytes).to_string() // Bedrock uses plain UTF-8
+        } else {
+            decode_mutf8(bytes)
+        })
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> Result<Tag, String> {
+        if depth > MAX_DEPTH {
+            return Err("NBT data is nested too deeply".to_string());
+        }
+        Ok(match id {
+            0 => Tag::End,
+            1 => Tag::Byte(self.u8()? as i8),
+            2 => Tag::Short(self.i16()?),
+            3 => Tag::Int(self.i32()?),
+            4 => Tag::Long(self.i64()?),
+            5 => Tag::Float(f32::from_be_bytes(self.array()?)),
+            6 => Tag::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let len = self.len()?;
+                Tag::ByteArray(self.take(len)?.iter().map(|&b| b as i8).collect())
+            },
+            8 => Tag::String(self.string()?),
+            9 => {
+                let element = self.u8()?;
+                if element as usize >= TYPE_NAMES.len() {
+                    return Err(format!("Unknown NBT tag type {}", element));
+                }
+                let len = self.len()?;
+                let mut items = Vec::with_capacity(len.min(4096));
+                for _ in 0..len {
+                    items.push(self.payload(element, depth + 1)?);
+                }
+                Tag::List(element, items)
+            },
+            10 => {
+                let mut entries = Vec::new();
+                loop {
+                    let child = self.u8()?;
+                    if child == 0 { break; }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(child, depth + 1)?));
+                }
+                Tag::Compound(entries)
+            },
+            11 => {
+                let len = self.len()?;
+                Tag::IntArray((0..len).map(|_| self.i32()).collect::<Result<_, _>>()?)
+            },
+            12 => {
+                let len = self.len()?;
+                Tag::LongArray((0..len).map(|_| self.i64()).collect::<Result<_, _>>()?)
+            },
+            other => return Err(format!("Unknown NBT tag type {}", other)),
+        })
+    }
+
+    fn root(&mut self) -> Result<(String, Tag), String> {
+        let id = self.u8()?;
+        if id != 10 && id != 9 {
+            return Err("Not an NBT file (root is not a compound)".to_string());
+        }
+        let name = self.string()?;
+        let tag = self.payload(id, 0)?;
+        Ok((name, tag))
+    }
+}
+
+/// Java's "modified UTF-8": NUL is 0xC0 0x80 and supplementary characters are surrogate pairs.
+fn decode_mutf8(bytes: &[u8]) -> String {
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        return s.to_string();
+    }
+    let mut units: Vec<u16> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b & 0x80 == 0 {
+            units.push(b as u16);
+            i += 1;
+        } else if b & 0xE0 == 0xC0 && i + 1 < bytes.len() {
+            units.push(((b as u16 & 0x1F) << 6) | (bytes[i + 1] as u16 & 0x3F));
+            i += 2;
+        } else if b & 0xF0 == 0xE0 && i + 2 < bytes.len() {
+            units.push(((b as u16 & 0x0F) << 12) | ((bytes[i + 1] as u16 & 0x3F) << 6) | (bytes[i + 2] as u16 & 0x3F));
+            i += 3;
+        } else {
+            units.push(0xFFFD);
+            i += 1;
+        }
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn encode_mutf8(s: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    for unit in s.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => out.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                out.push(0xC0 | ((unit >> 6) & 0x1F) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            },
+            _ => {
+                out.push(0xE0 | ((unit >> 12) & 0x0F) as u8);
+                out.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            },
+        }
+    }
+    out
+}
+
+// --- Binary writing ---
+
+struct NbtWriter {
+    out: Vec<u8>,
+    little_endian: bool,
+}
+
+impl NbtWriter {
+    fn bytes(&mut self, mut be_bytes: Vec<u8>) {
+        if self.little_endian { be_bytes.reverse(); }
+        self.out.extend_from_slice(&be_bytes);
+    }
+
+    fn len(&mut self, len: usize) -> Result<(), String> {
+        let len = i32::try_from(len).map_err(|_| "NBT array is too long".to_string())?;
+        self.bytes(len.to_be_bytes().to_vec());
+        Ok(())
+    }
+
+    fn string(&mut self, s: &str) -> Result<(), String> {
+        let encoded = if self.little_endian { s.as_bytes().to_vec() } else { encode_mutf8(s) };
+        let len = u16::try_from(encoded.len()).map_err(|_| "NBT string is too long".to_string())?;
+        self.bytes(len.to_be_bytes().to_vec());
+        self.out.extend_from_slice(&encoded);
+        Ok(())
+    }
+
+    fn payload(&mut self, tag: &Tag) -> Result<(), String> {
+        match tag {
+            Tag::End => {},
+            Tag::Byte(v) => self.out.push(*v as u8),
+            Tag::Short(v) => self.bytes(v.to_be_bytes().to_vec()),
+            Tag::Int(v) => self.bytes(v.to_be_bytes().to_vec()),
+            Tag::Long(v) => self.bytes(v.to_be_bytes().to_vec()),
+            Tag::Float(v) => self.bytes(v.to_be_bytes().to_vec()),
+            Tag::Double(v) => self.bytes(v.to_be_bytes().to_vec()),
+            Tag::ByteArray(values) => {
+                self.len(values.len())?;
+                self.out.extend(values.iter().map(|&b| b as u8));
+            },
+            Tag::String(s) => self.string(s)?,
+            Tag::List(element, items) => {
+                self.out.push(*element);
+                self.len(items.len())?;
+                for item in items {
+                    self.payload(item)?;
+                }
+            },
+            Tag::Compound(entries) => {
+                for (name, child) in entries {
+                    self.out.push(child.id());
+                    self.string(name)?;
+                    self.payload(child)?;
+                }
+                self.out.push(0);
+            },
+            Tag::IntArray(values) => {
+                self.len(values.len())?;
+                for v in values { self.bytes(v.to_be_bytes().to_vec()); }
+            },
+            Tag::LongArray(values) => {
+                self.len(values.len())?;
+                for v in values { self.bytes(v.to_be_bytes().to_vec()); }
+            },
+        }
+        Ok(())
+    }
+}
+
+// --- JSON conversion ---
+
+fn float_to_json(v: f64) -> Value {
+    if v.is_finite() { json!(v) } else { json!(v.to_string()) } // "NaN", "inf", "-inf"
+}
+
+fn tag_to_json(tag: &Tag) -> Value {
+    let value = match tag {
+        Tag::End => Value::Null,
+        Tag::Byte(v) => json!(v),
+        Tag::Short(v) => json!(v),
+        Tag::Int(v) => json!(v),
+        Tag::Long(v) => json!(v.to_string()),
+        Tag::Float(v) => float_to_json(*v as f64),
+        Tag::Double(v) => float_to_json(*v),
+        Tag::ByteArray(values) => json!(values),
+        Tag::String(s) => json!(s),
+        Tag::List(element, items) => {
+            return json!({
+                "type": "list",
+                "elementType": TYPE_NAMES[*element as usize],
+                "value": items.iter().map(tag_to_json).collect::<Vec<_>>(),
+            });
+        },
+        Tag::Compound(entries) => Value::Array(entries.iter().map(|(name, child)| {
+            let mut entry = tag_to_json(child);
+            entry["name"] = json!(name);
+            entry
+        }).collect()),
+        Tag::IntArray(values) => json!(values),
+        Tag::LongArray(values) => json!(values.iter().map(|v| v.to_string()).collect::<Vec<_>>()),
+    };
+    json!({ "type": TYPE_NAMES[tag.id() as usize], "value": value })
+}
+
+fn type_id(name: &str) -> Result<u8, String> {
+    TYPE_NAMES.iter()
+        .position(|t| *t == name)
+        .map(|i| i as u8)
+        .ok_or_else(|| format!("Unknown NBT type \"{}\"", name))
+}
+
+fn json_i64(value: &Value) -> Result<i64, String> {
+    match value {
+        Value::Number(n) => n.as_i64().ok_or_else(|| format!("{} is not an integer", n)),
+        Value::String(s) => s.trim().parse().map_err(|_| format!("\"{}\" is not an integer", s)),
+        other => Err(format!("Expected an integer, got {}", other)),
+    }
+}
+
+fn json_int<T: TryFrom<i64>>(value: &Value, type_name: &str) -> Result<T, String> {
+    let v = json_i64(value)?;
+    T::try_from(v).map_err(|_| format!("{} is out of range for {}", v, type_name))
+}
+
+fn json_f64(value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Number(n) => n.as_f64().ok_or_else(|| "Invalid number".to_string()),
+        Value::String(s) => s.trim().parse().map_err(|_| format!("\"{}\" is not a number", s)),
+        other => Err(format!("Expected a number, got {}", other)),
+    }
+}
+
+fn json_array(value: &Value) -> Result<&Vec<Value>, String> {
+    value.as_array().ok_or_else(|| "Expected an array".to_string())
+}
+
+fn tag_from_json(node: &Value, depth: usize) -> Result<Tag, String> {
+    if depth > MAX_DEPTH {
+        return Err("NBT data is nested too deeply".to_string());
+    }
+    let type_name = node["type"].as_str().ok_or("Every NBT node needs a \"type\"")?;
+    let value = &node["value"];
+
+    Ok(match type_name {
+        "end" => Tag::End,
+        "byte" => Tag::Byte(json_int(value, type_name)?),
+        "short" => Tag::Short(json_int(value, type_name)?),
+        "int" => Tag::Int(json_int(value, type_name)?),
+        "long" => Tag::Long(json_i64(value)?),
+        "float" => Tag::Float(json_f64(value)? as f32),
+        "double" => Tag::Double(json_f64(value)?),
+        "byteArray" => Tag::ByteArray(json_array(value)?.iter().map(|v| json_int(v, "byte")).collect::<Result<_, _>>()?),
+        "string" => Tag::String(value.as_str().ok_or("Expected a string value")?.to_string()),
+        "list" => {
+            let items = json_array(value)?.iter()
+                .map(|item| tag_from_json(item, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            let element = match node["elementType"].as_str() {
+                Some(name) => type_id(name)?,
+                None => items.first().map(|t| t.id()).unwrap_or(0),
+            };
+            if items.iter().any(|t| t.id() != element) {
+                return Err(format!("List items must all be of type {}", TYPE_NAMES[element as usize]));
+            }
+            Tag::List(element, items)
+        },
+        "compound" => Tag::Compound(json_array(value)?.iter().map(|entry| {
+            let name = entry["name"].as_str().ok_or("Compound entries need a \"name\"")?.to_string();
+            Ok((name, tag_from_json(entry, depth + 1)?))
+        }).collect::<Result<_, String>>()?),
+        "intArray" => Tag::IntArray(json_array(value)?.iter().map(|v| json_int(v, "int")).collect::<Result<_, _>>()?),
+        "longArray" => Tag::LongArray(json_array(value)?.iter().map(json_i64).collect::<Result<_, _>>()?),
+        other => return Err(format!("Unknown NBT type \"{}\"", other)),
+    })
+}
+
+// --- Files ---
+
+fn decompress(raw: &[u8]) -> Result<(Vec<u8>, &'static str), MineServerError> {
+    let too_large = || MineServerError::validation(format!("NBT data is over {} MB uncompressed", MAX_NBT_DECODED_BYTES / 1024 / 1024))
+        .with_context("FILE_TOO_LARGE");
+    if raw.starts_with(&[0x1f, 0x8b]) {
+        let mut out = Vec::new();
+        flate2::read::GzDecoder::new(raw).take(MAX_NBT_DECODED_BYTES + 1).read_to_end(&mut out)
+            .map_err(|e| format!("Failed to decompress NBT: {}", e))?;
+        if out.len() as u64 > MAX_NBT_DECODED_BYTES {
+            return Err(too_large());
+        }
+        return Ok((out, "gzip"));
+    }
+    // zlib header: CMF 0x78 and a valid FCHECK
+    if raw.len() > 2 && raw[0] == 0x78 && (u16::from(raw[0]) << 8 | u16::from(raw[1])) % 31 == 0 {
+        let mut out = Vec::new();
+        if flate2::read::ZlibDecoder::new(raw).take(MAX_NBT_DECODED_BYTES + 1).read_to_end(&mut out).is_ok() {
+            if out.len() as u64 > MAX_NBT_DECODED_BYTES {
+                return Err(too_large());
+            }
+            return Ok((out, "zlib"));
+        }
+    }
+    Ok((raw.to_vec(), "none"))
+}
+
+fn parse(data: &[u8], little_endian: bool) -> Result<(String, Tag), String> {
+    let mut reader = NbtReader { data, pos: 0, little_endian };
+    reader.root()
+}
+
+pub(crate) fn read_document(path: &Path) -> Result<NbtDocument, MineServerError> {
+    let size = fs::metadata(path).map_err(|e| format!("Failed to read file: {}", e))?.len();
+    if size > MAX_NBT_FILE_BYTES {
+        return Err(MineServerError::validation(format!("NBT file is {} MB", size / 1024 / 1024)).with_context("FILE_TOO_LARGE"));
+    }
+    let raw = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let (data, compression) = decompress(&raw)?;
+
+    // Bedrock level.dat: i32 version + i32 payload length (little-endian) before the NBT
+    let bedrock_header = if data.len() >= 8
+        && u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize == data.len() - 8
+    {
+        Some(i32::from_le_bytes([data[0], data[1], data[2], data[3]]))
+    } else {
+        None
+    };
+
+    let (little_endian, (root_name, root)) = match bedrock_header {
+        Some(_) => (true, parse(&data[8..], true)?),
+        None => match parse(&data, false) {
+            Ok(parsed) => (false, parsed),
+            Err(java_err) => (true, parse(&data, true).map_err(|_| java_err)?),
+        },
+    };
+
+    Ok(NbtDocument {
+        format: NbtFormat {
+            compression: compression.to_string(),
+            little_endian,
+            bedrock_header,
+        },
+        root_name,
+        root: tag_to_json(&root),
+    })
+}
+
+fn encode_document(doc: &NbtDocument) -> Result<Vec<u8>, String> {
+    let root = tag_from_json(&doc.root, 0)?;
+    if root.id() != 10 && root.id() != 9 {
+        return Err("The root tag must be a compound".to_string());
+    }
+
+    let mut writer = NbtWriter { out: Vec::new(), little_endian: doc.format.little_endian };
+    writer.out.push(root.id());
+    writer.string(&doc.root_name)?;
+    writer.payload(&root)?;
+    let mut data = writer.out;
+
+    if let Some(version) = doc.format.bedrock_header {
+        let mut with_header = Vec::with_capacity(data.len() + 8);
+        with_header.extend_from_slice(&version.to_le_bytes());
+        with_header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        with_header.extend_from_slice(&data);
+        data = with_header;
+    }
+
+    match doc.format.compression.as_str() {
+        "gzip" => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&data).map_err(|e| e.to_string())?;
+            encoder.finish().map_err(|e| e.to_string())
+        },
+        "zlib" => {
+            let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&data).map_err(|e| e.to_string())?;
+            encoder.finish().map_err(|e| e.to_string())
+        },
+        _ => Ok(data),
+    }
+}
+
+/// Reads an NBT file (Java or Bedrock, compressed or not) as a JSON tree.
+#[tauri::command]
+pub fn read_nbt(path: String) -> Result<NbtDocument, MineServerError> {
+    let file_path = validate_path_inside_root(&path)?;
+    read_document(&file_path)
+}
+
+/// Writes a JSON tree from `read_nbt` back to NBT, keeping the original compression/endianness.
+#[tauri::command]
+pub fn write_nbt(path: String, json: Value) -> Result<(), MineServerError> {
+    let file_path = validate_path_inside_root(&path)?;
+    let doc: NbtDocument = serde_json::from_value(json)
+        .map_err(|e| format!("Invalid NBT document: {}", e))?;
+    write_document(&file_path, &doc).map_err(Into::into)
+}
+
+pub(crate) fn write_document(path: &Path, doc: &NbtDocument) -> Result<(), String> {
+    let bytes = encode_document(doc)?;
+
+    // Write next to the file and swap, so a failed write never leaves a truncated level.dat
+    let temp = path.with_extension("mineserver-tmp");
+    fs::write(&temp, bytes).map_err(|e| format!("Failed to write NBT: {}", e))?;
+    fs::rename(&temp, path).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        format!("Failed to replace file: {}", e)
+    })
+}
@@ -1,26 +1,38 @@
-use tauri::{State, Window, Emitter};
+use tauri::{AppHandle, State};
 use igd_next::{search_gateway, PortMappingProtocol};
 use std::net::{SocketAddrV4, IpAddr};
 use std::process::{Command, Stdio, Child};
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use std::path::Path;
-use reqwest::Client;
+use super::http::HttpState;
 use std::io::Read;
 use std::thread;
+use super::error::MineServerError;
+use super::registry::{find_registered_server, update_server_network, PortMapping, RegisteredServer, ServerNetwork};
 
+#[derive(Clone)]
 pub struct NetworkState {
     pub tunnels: Arc<Mutex<HashMap<String, Child>>>,
+    // Tunnels that follow their server: restarted with backoff if the agent exits while the
+    // server runs. Value is the number of restarts in a row.
+    pub followed: Arc<Mutex<HashMap<String, u32>>>,
+    // Last claim link each agent printed, for windows that open after it was emitted
+    pub claim_urls: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl NetworkState {
     pub fn new() -> Self {
         Self {
             tunnels: Arc::new(Mutex::new(HashMap::new())),
+            followed: Arc::new(Mutex::new(HashMap::new())),
+            claim_urls: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
 
+const TUNNEL_MAX_RESTARTS: u32 = 5;
+
 #[tauri::command]
 pub async fn check_internet_connection() -> bool {
     // Simple check to google DNS
@@ -28,21 +40,24 @@ pub async fn check_internet_connection() -> bool {
 }
 
 #[tauri::command]
-pub async fn get_public_ip() -> Result<String, String> {
-    reqwest::get("https://api.ipify.org")
-        .await.map_err(|e| e.to_string())?
+pub async fn get_public_ip(http: State<'_, HttpState>) -> Result<String, MineServerError> {
+    let ip = http.client().get("https://api.ipify.org")
+        .send()
+        .await?
         .text()
-        .await.map_err(|e| e.to_string())
+        .await?;
+    Ok(ip)
 }
 
-#[tauri::command]
-pub async fn upnp_map_port(port: u16, protocol_str: String) -> Result<String, String> {
-    let protocol = match protocol_str.as_str() {
-        "TCP" => PortMappingProtocol::TCP,
-        "UDP" => PortMappingProtocol::UDP,
-        _ => return Err("Invalid protocol. Use TCP or UDP".to_string()),
-    };
+fn parse_protocol(protocol_str: &str) -> Result<PortMappingProtocol, MineServerError> {
+    match protocol_str {
+        "TCP" => Ok(PortMappingProtocol::TCP),
+        "UDP" => Ok(PortMappingProtocol::UDP),
+        _ => Err(MineServerError::validation("Invalid protocol. Use TCP or UDP")),
+    }
+}
 
+pub(super) fn find_gateway() -> Result<igd_next::Gateway, MineServerError> {
     use igd_next::SearchOptions;
     use std::time::Duration;
 
@@ -51,19 +66,24 @@ pub async fn upnp_map_port(port: u16, protocol_str: String) -> Result<String, St
         ..Default::default()
     };
 
-    let gateway = search_gateway(search_options)
-        .map_err(|e| format!("Search Failed (Timeout/Disabled?): {}. Ensure UPnP is enabled in your router settings.", e))?;
+    search_gateway(search_options)
+        .map_err(|e| MineServerError::network(format!("Search Failed (Timeout/Disabled?): {}. Ensure UPnP is enabled in your router settings.", e)))
+}
+
+/// Maps `port` on the router to this machine and returns the public IP.
+fn map_port_blocking(port: u16, protocol: PortMappingProtocol) -> Result<String, MineServerError> {
+    let gateway = find_gateway()?;
 
     let local_ip = local_ip_address::local_ip()
         .map_err(|e| format!("Failed to get local IP: {}", e))?;
     
     let local_addr = match local_ip {
         IpAddr::V4(addr) => SocketAddrV4::new(addr, port),
-        _ => return Err("IPv6 not supported for this UPnP implementation".to_string()),
+        _ => return Err(MineServerError::validation("IPv6 not supported for this UPnP implementation")),
     };
 
     gateway.add_port(protocol, port, std::net::SocketAddr::V4(local_addr), 0, "Mineserver")
-        .map_err(|e| format!("UPnP Mapping Failed: {}", e))?;
+        .map_err(|e| MineServerError::external("router", format!("UPnP Mapping Failed: {}", e)))?;
 
     let public_ip = gateway.get_external_ip()
         .map_err(|e| format!("Failed to get public IP: {}", e))?;
@@ -71,33 +91,306 @@ pub async fn upnp_map_port(port: u16, protocol_str: String) -> Result<String, St
     Ok(public_ip.to_string())
 }
 
+fn remove_port_blocking(port: u16, protocol: PortMappingProtocol) -> Result<(), MineServerError> {
+    let gateway = find_gateway()?;
+    gateway.remove_port(protocol, port)
+        .map_err(|e| MineServerError::external("router", format!("UPnP Removal Failed: {}", e)))?;
+    Ok(())
+}
+
+/// Maps a port via UPnP. With `server_id`, the mapping is recorded on that server so it is
+/// re-applied when the server starts and removed when it is deleted.
 #[tauri::command]
-pub async fn upnp_remove_port(port: u16, protocol_str: String) -> Result<(), String> {
-    let protocol = match protocol_str.as_str() {
-        "TCP" => PortMappingProtocol::TCP,
-        "UDP" => PortMappingProtocol::UDP,
-        _ => return Err("Invalid protocol".to_string()),
-    };
+pub async fn upnp_map_port(port: u16, protocol_str: String, server_id: Option<String>) -> Result<String, MineServerError> {
+    let protocol = parse_protocol(&protocol_str)?;
+    let public_ip = map_port_blocking(port, protocol)?;
+
+    if let Some(id) = server_id {
+        let mapping = PortMapping { port, protocol: protocol_str };
+        update_server_network(&id, |network| {
+            if !network.upnp.contains(&mapping) {
+                network.upnp.push(mapping);
+            }
+        })?;
+    }
+    Ok(public_ip)
+}
 
-    use igd_next::SearchOptions;
-    use std::time::Duration;
+#[tauri::command]
+pub async fn upnp_remove_port(port: u16, protocol_str: String, server_id: Option<String>) -> Result<(), MineServerError> {
+    let protocol = parse_protocol(&protocol_str)?;
+    remove_port_blocking(port, protocol)?;
+
+    if let Some(id) = server_id {
+        update_server_network(&id, |network| {
+            network.upnp.retain(|m| !(m.port == port && m.protocol == protocol_str));
+        })?;
+    }
+    Ok(())
+}
 
-    let search_options = SearchOptions {
-        timeout: Some(Duration::from_secs(5)),
-        ..Default::default()
+/// Called right after a server process starts: runs the full auto setup when the server has
+/// it enabled, otherwise just restores what was opened for it before.
+pub fn on_server_started<E: super::runner::ServerEventSink>(sink: E, network: &NetworkState, id: &str) {
+    let Some(server) = find_registered_server(id) else { return };
+    if server.tunnel_autostart {
+        if let Ok(mut followed) = network.followed.lock() {
+            followed.insert(server.id.clone(), 0);
+        }
+        let already_running = network.tunnels.lock().map(|t| t.contains_key(id)).unwrap_or(false);
+        if !already_running {
+            if let Err(e) = start_tunnel_direct(sink.clone(), network, server.id.clone(), server.path.clone()) {
+                sink.emit(&format!("tunnel-log:{}", server.id), format!("Failed to start tunnel: {}", e));
+            }
+        }
+    }
+    if server.auto_network_setup {
+        thread::spawn(move || auto_network_setup(sink, server));
+    } else if !server.network.is_empty() {
+        thread::spawn(move || reapply_server_network(server));
+    }
+}
+
+/// Called once a server has stopped for good (not when it is auto-restarted): stops the
+/// tunnel if it follows the server.
+pub fn on_server_stopped(network: &NetworkState, id: &str) {
+    let followed = network.followed.lock().map(|mut f| f.remove(id).is_some()).unwrap_or(false);
+    if !followed {
+        return;
+    }
+    if let Ok(mut tunnels) = network.tunnels.lock() {
+        if let Some(mut child) = tunnels.remove(id) {
+            let _ = child.kill();
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkSetupEvent {
+    pub server_id: String,
+    pub step: String, // "upnp", "firewall" or "reachability"
+    pub success: bool,
+    pub message: String,
+}
+
+fn read_port_property(server_path: &Path, key: &str) -> Option<u16> {
+    let prefix = format!("{}=", key);
+    std::fs::read_to_string(server_path.join("server.properties"))
+        .ok()?
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix(prefix.as_str()))
+        .next()
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Port and protocol the server listens on, from its server.properties.
+pub fn server_listen_port(server: &RegisteredServer) -> (u16, &'static str) {
+    let bedrock = server.server_type == "bedrock";
+    let default_port = if bedrock { 19132 } else { 25565 };
+    let port = read_port_property(Path::new(&server.path), "server-port").unwrap_or(default_port);
+    (port, if bedrock { "UDP" } else { "TCP" })
+}
+
+// Port selection for new servers. A port is free when no other registered server is configured
+// for it (running or not) and the OS lets us bind it. Bedrock servers take a pair: IPv4 on an
+// even offset and IPv6 (`server-portv6`) right after it.
+
+const JAVA_PORT_RANGE: (u16, u16) = (25565, 25665);
+const BEDROCK_PORT_RANGE: (u16, u16) = (19132, 19232);
+
+/// Ports configured by registered servers, except the one at `exclude_path`.
+fn registered_ports(exclude_path: Option<&Path>) -> Vec<u16> {
+    let exclude = exclude_path.and_then(|p| std::fs::canonicalize(p).ok());
+    super::registry::load_registry().unwrap_or_default()
+        .iter()
+        .filter(|s| exclude.is_none() || std::fs::canonicalize(&s.path).ok() != exclude)
+        .flat_map(|s| {
+            let (port, _) = server_listen_port(s);
+            let v6 = if s.server_type == "bedrock" {
+                Some(read_port_property(Path::new(&s.path), "server-portv6").unwrap_or(19133))
+            } else {
+                None
+            };
+            std::iter::once(port).chain(v6)
+        })
+        .collect()
+}
+
+fn os_port_free(port: u16, bedrock: bool) -> bool {
+    if bedrock {
+        std::net::UdpSocket::bind(("0.0.0.0", port)).is_ok()
+    } else {
+        std::net::TcpListener::bind(("0.0.0.0", port)).is_ok()
+    }
+}
+
+fn port_available(port: u16, bedrock: bool, taken: &[u16]) -> bool {
+    let ports: &[u16] = if bedrock { &[port, port.saturating_add(1)] } else { &[port] };
+    ports.iter().all(|p| !taken.contains(p) && os_port_free(*p, bedrock))
+}
+
+fn find_free_port(bedrock: bool, range: (u16, u16), preferred: Option<u16>, exclude_path: Option<&Path>) -> Result<u16, MineServerError> {
+    let taken = registered_ports(exclude_path);
+    if let Some(port) = preferred.filter(|p| port_available(*p, bedrock, &taken)) {
+        return Ok(port);
+    }
+    let step = if bedrock { 2 } else { 1 };
+    (range.0..=range.1).step_by(step)
+        .find(|p| port_available(*p, bedrock, &taken))
+        .ok_or_else(|| MineServerError::conflict(format!("No free port between {} and {}", range.0, range.1)))
+}
+
+/// First free port in `range_start..=range_end` (default 25565-25665, or 19132-19232 for Bedrock).
+#[tauri::command]
+pub fn suggest_free_port(bedrock: bool, range_start: Option<u16>, range_end: Option<u16>) -> Result<u16, MineServerError> {
+    let default = if bedrock { BEDROCK_PORT_RANGE } else { JAVA_PORT_RANGE };
+    let range = (range_start.unwrap_or(default.0), range_end.unwrap_or(default.1));
+    if range.0 > range.1 {
+        return Err(MineServerError::validation("Port range start is after its end"));
+    }
+    find_free_port(bedrock, range, None, None)
+}
+
+/// Picks a free port for a new server (`preferred` if it is free) and writes it to
+/// server.properties, along with `server-portv6` for Bedrock. Returns the IPv4 port.
+#[tauri::command]
+pub fn assign_server_port(server_path: String, bedrock: bool, preferred: Option<u16>) -> Result<u16, MineServerError> {
+    let root = super::path_guard::validate_path(&server_path)?;
+    let range = if bedrock { BEDROCK_PORT_RANGE } else { JAVA_PORT_RANGE };
+    let port = find_free_port(bedrock, range, preferred, Some(&root))?;
+
+    let mut properties = HashMap::new();
+    properties.insert("server-port".to_string(), port.to_string());
+    if bedrock {
+        properties.insert("server-portv6".to_string(), port.saturating_add(1).to_string());
+    }
+    super::server_config::write_server_properties(server_path, properties)?;
+    Ok(port)
+}
+
+/// Maps the server port, opens the firewall and checks that the public address answers.
+/// Each step is reported as a `network-setup` event; a failed step doesn't stop the next one.
+fn auto_network_setup<E: super::runner::ServerEventSink>(sink: E, server: RegisteredServer) {
+    let (port, protocol_str) = server_listen_port(&server);
+    let report = |step: &str, result: Result<String, MineServerError>| {
+        let (success, message) = match result {
+            Ok(message) => (true, message),
+            Err(e) => (false, e.to_string()),
+        };
+        sink.emit("network-setup", NetworkSetupEvent {
+            server_id: server.id.clone(),
+            step: step.to_string(),
+            success,
+            message,
+        });
+        success
     };
 
-    let gateway = search_gateway(search_options)
-        .map_err(|e| format!("Failed to find IGD gateway: {}", e))?;
+    let mapped = map_server_port(&server.id, port, protocol_str);
+    let public_ip = mapped.as_ref().ok().cloned();
+    report("upnp", mapped.map(|ip| format!("{} port {} forwarded ({})", protocol_str, port, ip)));
 
-    gateway.remove_port(protocol, port)
-        .map_err(|e| format!("UPnP Removal Failed: {}", e))?;
+    let firewall = open_server_firewall(&server.id, port)
+        .map(|_| format!("Port {} allowed through the firewall", port));
+    report("firewall", firewall);
 
+    let reachability = match public_ip {
+        None => Err(MineServerError::network("Skipped: the port could not be forwarded")),
+        Some(_) if protocol_str == "UDP" => Err(MineServerError::validation("Bedrock (UDP) reachability can't be verified from here")),
+        Some(ip) => check_reachable(&ip, port),
+    };
+    report("reachability", reachability);
+}
+
+/// Forwards `port` on the router and records the mapping on the server, so it is re-applied on
+/// start and removed on delete. Returns the public IP.
+pub(crate) fn map_server_port(server_id: &str, port: u16, protocol_str: &str) -> Result<String, MineServerError> {
+    let public_ip = map_port_blocking(port, parse_protocol(protocol_str)?)?;
+    let mapping = PortMapping { port, protocol: protocol_str.to_string() };
+    update_server_network(server_id, |network| {
+        if !network.upnp.contains(&mapping) {
+            network.upnp.push(mapping);
+        }
+    })?;
+    Ok(public_ip)
+}
+
+/// Allows `port` through the firewall without prompting and records the rule on the server.
+pub(crate) fn open_server_firewall(server_id: &str, port: u16) -> Result<(), MineServerError> {
+    ensure_firewall_rule(port)?;
+    update_server_network(server_id, |network| {
+        if !network.firewall_ports.contains(&port) {
+            network.firewall_ports.push(port);
+        }
+    })?;
     Ok(())
 }
 
+/// Waits for the server to listen locally, then connects to it through the public address.
+/// Routers without NAT loopback fail this check even when outside players can join.
+fn check_reachable(public_ip: &str, port: u16) -> Result<String, MineServerError> {
+    use std::net::{SocketAddr, TcpStream};
+    use std::time::{Duration, Instant};
+
+    let local: SocketAddr = ([127, 0, 0, 1], port).into();
+    let started = Instant::now();
+    while TcpStream::connect_timeout(&local, Duration::from_secs(1)).is_err() {
+        if started.elapsed() > Duration::from_secs(180) {
+            return Err(MineServerError::network("The server did not start listening in time"));
+        }
+        thread::sleep(Duration::from_secs(2));
+    }
+
+    let public: SocketAddr = format!("{}:{}", public_ip, port).parse()
+        .map_err(|_| MineServerError::validation(format!("Invalid public address {}", public_ip)))?;
+    TcpStream::connect_timeout(&public, Duration::from_secs(5))
+        .map(|_| format!("Reachable at {}:{}", public_ip, port))
+        .map_err(|e| MineServerError::network(format!("{}:{} did not answer ({}). Your router may not support NAT loopback; ask a friend to test.", public_ip, port, e)))
+}
+
+/// Re-creates the router mappings and firewall rules recorded for a server. Failures are only
+/// logged; the gateway search alone can take seconds, so this runs on its own thread.
+fn reapply_server_network(server: RegisteredServer) {
+    for mapping in &server.network.upnp {
+        let result = parse_protocol(&mapping.protocol).and_then(|p| map_port_blocking(mapping.port, p));
+        if let Err(e) = result {
+            eprintln!("[Network] Failed to re-map {} port {} for {}: {}", mapping.protocol, mapping.port, server.name, e);
+        }
+    }
+    for port in &server.network.firewall_ports {
+        if let Err(e) = ensure_firewall_rule(*port) {
+            eprintln!("[Network] Failed to restore firewall rule for port {} ({}): {}", port, server.name, e);
+        }
+    }
+}
+
+/// Removes the mappings and firewall rules of a deleted server. Ports still recorded by one of
+/// the `remaining` servers are left open.
+pub fn release_server_network(network: ServerNetwork, remaining: Vec<RegisteredServer>) {
+    thread::spawn(move || {
+        for mapping in &network.upnp {
+            if remaining.iter().any(|s| s.network.upnp.contains(mapping)) {
+                continue;
+            }
+            let result = parse_protocol(&mapping.protocol).and_then(|p| remove_port_blocking(mapping.port, p));
+            if let Err(e) = result {
+                eprintln!("[Network] Failed to remove {} port {}: {}", mapping.protocol, mapping.port, e);
+            }
+        }
+        for port in &network.firewall_ports {
+            if remaining.iter().any(|s| s.network.firewall_ports.contains(port)) {
+                continue;
+            }
+            if let Err(e) = remove_firewall_rule(*port) {
+                eprintln!("[Network] Failed to remove firewall rule for port {}: {}", port, e);
+            }
+        }
+    });
+}
+
 #[tauri::command]
-pub async fn install_playit(server_path: String) -> Result<String, String> {
+pub async fn install_playit(http: State<'_, HttpState>, server_path: String) -> Result<String, MineServerError> {
     let path = Path::new(&server_path);
     // Use .playit subdirectory for the binary too, keeping root clean
     let playit_dir = path.join(".playit");
@@ -123,17 +416,17 @@ pub async fn install_playit(server_path: String) -> Result<String, String> {
         "https://github.com/playit-cloud/playit-agent/releases/latest/download/playit-linux-x86_64"
     };
 
-    let client = Client::new();
+    let client = http.client();
     let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
     let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
     
     // Create direct write with Error 32 handling
-    match std::fs::write(&binary_path, &bytes) {
+    match tokio::fs::write(&binary_path, &bytes).await {
         Ok(_) => {},
         Err(e) if e.raw_os_error() == Some(32) => {
             return Ok("Playit updated (Locked/Running)".to_string());
         },
-        Err(e) => return Err(format!("Failed to write Playit binary: {}", e))
+        Err(e) => return Err(format!("Failed to write Playit binary: {}", e).into())
     }
 
     #[cfg(target_family = "unix")]
@@ -149,10 +442,20 @@ pub async fn install_playit(server_path: String) -> Result<String, String> {
 
 #[tauri::command]
 pub fn start_playit_tunnel(
-    window: Window,
+    app: AppHandle,
     state: State<'_, NetworkState>,
     id: String,
     server_path: String
+) -> Result<String, MineServerError> {
+    start_tunnel_direct(app, state.inner(), id, server_path).map_err(Into::into)
+}
+
+/// `start_playit_tunnel` without Tauri state, so the tunnel can follow the server lifecycle.
+pub fn start_tunnel_direct<E: super::runner::ServerEventSink>(
+    window: E,
+    state: &NetworkState,
+    id: String,
+    server_path: String
 ) -> Result<String, String> {
     let mut tunnels = state.tunnels.lock().map_err(|e| e.to_string())?;
 
@@ -176,93 +479,42 @@ pub fn start_playit_tunnel(
     let log_path = playit_dir.join("playit_agent.log");
     let _ = std::fs::remove_file(&log_path);
 
-    // Run playit in a visible terminal window for now (workaround for pipe issues)
-    // The user will see the claim link in that window
-    let mut cmd = if cfg!(target_os = "windows") {
-        let mut c = Command::new("cmd");
-        // We use /D "path" to set CWD for the start command to ensure playit.toml is written there
-        c.args([
-            "/c", 
-            "start", 
-            "/D", playit_dir.to_str().unwrap(), 
-            "Playit Tunnel", 
-            binary_path.to_str().unwrap()
-        ]);
-        c.current_dir(&playit_dir);
-        c
-    } else {
-        let mut c = Command::new(&binary_path);
-        c.current_dir(&playit_dir);
-        c.stdout(Stdio::piped())
-         .stderr(Stdio::piped())
-         .stdin(Stdio::null());
-        c
-    };
+    if let Ok(mut claims) = state.claim_urls.lock() {
+        claims.remove(&id);
+    }
+
+    // Hidden with piped output on every platform, so the claim link reaches the app
+    let mut cmd = Command::new(&binary_path);
+    cmd.current_dir(&playit_dir); // playit.toml is written to the working directory
+    cmd.stdout(Stdio::piped())
+       .stderr(Stdio::piped())
+       .stdin(Stdio::null());
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
 
     let mut child = cmd.spawn().map_err(|e| format!("Failed to start Playit: {}", e))?;
     let pid = child.id();
-    
-    let window_clone = window.clone();
-    let id_clone = id.clone();
-    
-    let _ = window_clone.emit(&format!("tunnel-log:{}", id_clone), format!("Agent (PID: {}) started. Check the terminal window for the claim link.", pid));
+    window.emit(&format!("tunnel-log:{}", id), format!("Agent (PID: {}) started. The claim link will appear here.", pid));
 
-    // Only monitor stdout/stderr on non-Windows (Windows uses visible terminal)
-    #[cfg(not(target_os = "windows"))]
-    {
-        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-        let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
-        
-        // Monitor stdout
-        let w1 = window.clone();
-        let i1 = id.clone();
-        thread::spawn(move || {
-            let mut reader = stdout;
-            let mut buffer = [0u8; 1024];
-            let mut current_line = Vec::new();
-            while let Ok(n) = reader.read(&mut buffer) {
-                if n == 0 { break; }
-                for i in 0..n {
-                    let byte = buffer[i];
-                    if byte == b'\n' || byte == b'\r' {
-                        if !current_line.is_empty() {
-                            if let Ok(l) = String::from_utf8(current_line.clone()) {
-                                let clean = l.trim().to_string();
-                                if !clean.is_empty() {
-                                    let _ = w1.emit(&format!("tunnel-log:{}", i1), clean.clone());
-                                    if clean.contains("playit.gg/claim/") {
-                                        let _ = w1.emit(&format!("tunnel-claim:{}", i1), clean);
-                                    }
-                                }
-                            }
-                            current_line.clear();
-                        }
-                    } else {
-                        current_line.push(byte);
-                    }
-                }
-            }
-        });
-    
-        // Monitor stderr
-        let w2 = window.clone();
-        let i2 = id.clone();
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    for output in [Box::new(stdout) as Box<dyn Read + Send>, Box::new(stderr)] {
+        let (w, i, s) = (window.clone(), id.clone(), state.clone());
         thread::spawn(move || {
-            let mut reader = stderr;
+            let mut reader = output;
             let mut buffer = [0u8; 1024];
             let mut current_line = Vec::new();
             while let Ok(n) = reader.read(&mut buffer) {
                 if n == 0 { break; }
-                for i in 0..n {
-                    let byte = buffer[i];
+                for &byte in &buffer[..n] {
                     if byte == b'\n' || byte == b'\r' {
                         if !current_line.is_empty() {
-                            if let Ok(l) = String::from_utf8(current_line.clone()) {
-                                let clean = l.trim().to_string();
-                                if !clean.is_empty() {
-                                    let _ = w2.emit(&format!("tunnel-log:{}", i2), clean);
-                                }
-                            }
+                            tunnel_output_line(&w, &s, &i, &String::from_utf8_lossy(&current_line));
                             current_line.clear();
                         }
                     } else {
@@ -271,10 +523,12 @@ pub fn start_playit_tunnel(
                 }
             }
         });
-    } // Close #[cfg(not(target_os = "windows"))]
-    // Monitor log file (Fallback for Windows pipe buffering)
+    }
+
+    // Monitor log file (the agent may buffer its piped output)
     let w4 = window.clone();
     let i4 = id.clone();
+    let s4 = state.clone();
     let log_path_tail = log_path.clone();
     thread::spawn(move || {
         use std::io::{Seek, SeekFrom};
@@ -286,15 +540,9 @@ pub fn start_playit_tunnel(
             if let Ok(mut file) = std::fs::File::open(&log_path_tail) {
                 let _ = file.seek(SeekFrom::Start(last_pos));
                 let mut buffer = String::new();
-                if let Ok(_) = file.read_to_string(&mut buffer) {
+                if file.read_to_string(&mut buffer).is_ok() {
                     for line in buffer.lines() {
-                        let clean = line.trim().to_string();
-                        if !clean.is_empty() {
-                            let _ = w4.emit(&format!("tunnel-log:{}", i4), clean.clone());
-                            if clean.contains("playit.gg/claim/") {
-                                let _ = w4.emit(&format!("tunnel-claim:{}", i4), clean);
-                            }
-                        }
+                        tunnel_output_line(&w4, &s4, &i4, line);
                     }
                 }
                 if let Ok(meta) = file.metadata() {
@@ -308,44 +556,189 @@ pub fn start_playit_tunnel(
     // Monitor exit
     let w3 = window.clone();
     let i3 = id.clone();
-    let state_clone = state.tunnels.clone();
+    let state_clone = state.clone();
+    let started = std::time::Instant::now();
+    tunnels.insert(id.clone(), child);
     thread::spawn(move || {
-        match child.wait() {
-            Ok(status) => {
-                let _ = w3.emit(&format!("tunnel-log:{}", i3), format!("Agent exited with status: {}", status));
-                let mut tunnels = state_clone.lock().unwrap();
-                tunnels.remove(&i3);
-            },
-            Err(e) => {
-                let _ = w3.emit(&format!("tunnel-log:{}", i3), format!("Error waiting for agent: {}", e));
+        loop {
+            thread::sleep(std::time::Duration::from_secs(1));
+            let mut tunnels = state_clone.tunnels.lock().unwrap();
+            let Some(child) = tunnels.get_mut(&i3) else { return }; // Stopped
+            match child.try_wait() {
+                Ok(None) => continue,
+                Ok(Some(status)) => {
+                    w3.emit(&format!("tunnel-log:{}", i3), format!("Agent exited with status: {}", status));
+                },
+                Err(e) => {
+                    w3.emit(&format!("tunnel-log:{}", i3), format!("Error waiting for agent: {}", e));
+                }
             }
+            tunnels.remove(&i3);
+            break;
         }
+        restart_followed_tunnel(w3, state_clone, i3, server_path, started.elapsed());
     });
 
     Ok("Tunnel started".into())
 }
 
+/// Forwards an agent output line as `tunnel-log:<id>`, and a claim link seen for the first time
+/// as `tunnel-claim:<id>`.
+fn tunnel_output_line<E: super::runner::ServerEventSink>(window: &E, state: &NetworkState, id: &str, line: &str) {
+    let clean = line.trim();
+    if clean.is_empty() {
+        return;
+    }
+    window.emit(&format!("tunnel-log:{}", id), clean.to_string());
+    if let Some(url) = parse_claim_url(clean) {
+        // stdout and the log file print the same link
+        let is_new = state.claim_urls.lock()
+            .map(|mut claims| claims.insert(id.to_string(), url.clone()).as_ref() != Some(&url))
+            .unwrap_or(true);
+        if is_new {
+            window.emit(&format!("tunnel-claim:{}", id), url);
+        }
+    }
+}
+
+/// Extracts "https://playit.gg/claim/<code>" from an agent line (which may carry color codes).
+fn parse_claim_url(line: &str) -> Option<String> {
+    const MARKER: &str = "playit.gg/claim/";
+    let rest = &line[line.find(MARKER)? + MARKER.len()..];
+    let code: String = rest.chars().take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_').collect();
+    if code.is_empty() {
+        return None;
+    }
+    Some(format!("https://{}{}", MARKER, code))
+}
+
+/// Claim link printed by the agent of a server, if it is waiting to be claimed.
+#[tauri::command]
+pub fn get_tunnel_claim_url(state: State<'_, NetworkState>, id: String) -> Option<String> {
+    let running = state.tunnels.lock().map(|t| t.contains_key(&id)).unwrap_or(false);
+    if !running {
+        return None;
+    }
+    state.claim_urls.lock().ok().and_then(|claims| claims.get(&id).cloned())
+}
+
+/// Restarts a tunnel that follows its server after the agent exited, backing off 5s, 10s,
+/// 20s... An agent that stayed up for a few minutes resets the count.
+fn restart_followed_tunnel<E: super::runner::ServerEventSink>(
+    window: E,
+    state: NetworkState,
+    id: String,
+    server_path: String,
+    uptime: std::time::Duration,
+) {
+    let attempt = {
+        let mut followed = state.followed.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(restarts) = followed.get_mut(&id) else { return }; // Stopped on purpose
+        if uptime > std::time::Duration::from_secs(300) {
+            *restarts = 0;
+        }
+        *restarts += 1;
+        *restarts
+    };
+    if attempt > TUNNEL_MAX_RESTARTS {
+        window.emit(&format!("tunnel-log:{}", id), format!("Agent keeps exiting; gave up after {} restarts.", TUNNEL_MAX_RESTARTS));
+        return;
+    }
+
+    let delay = std::time::Duration::from_secs(5 * 2u64.pow(attempt - 1));
+    window.emit(&format!("tunnel-log:{}", id), format!("Restarting agent in {}s (attempt {}/{})...", delay.as_secs(), attempt, TUNNEL_MAX_RESTARTS));
+    thread::sleep(delay);
+
+    // The server may have stopped while we waited
+    let still_followed = state.followed.lock().map(|f| f.contains_key(&id)).unwrap_or(false);
+    if still_followed {
+        if let Err(e) = start_tunnel_direct(window.clone(), &state, id.clone(), server_path) {
+            window.emit(&format!("tunnel-log:{}", id), format!("Failed to restart agent: {}", e));
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStatus {
+    pub server_running: bool,
+    pub tunnel: String, // "running", "restarting" or "stopped"
+    pub tunnel_autostart: bool,
+    pub tunnel_restarts: u32,
+    pub auto_network_setup: bool,
+    pub network: ServerNetwork,
+}
+
+/// Server, tunnel and port state of one server in a single call.
+#[tauri::command]
+pub fn get_network_status(
+    processes: State<'_, super::runner::ServerProcessState>,
+    state: State<'_, NetworkState>,
+    id: String
+) -> Result<NetworkStatus, MineServerError> {
+    let server_running = processes.processes.lock().map_err(|e| e.to_string())?.contains_key(&id);
+    let tunnel_running = state.tunnels.lock().map_err(|e| e.to_string())?.contains_key(&id);
+    let restarts = state.followed.lock().map_err(|e| e.to_string())?.get(&id).copied();
+    let server = find_registered_server(&id);
+
+    let tunnel = match (tunnel_running, restarts) {
+        (true, _) => "running",
+        (false, Some(_)) if server_running => "restarting",
+        _ => "stopped",
+    };
+    Ok(NetworkStatus {
+        server_running,
+        tunnel: tunnel.to_string(),
+        tunnel_autostart: server.as_ref().map(|s| s.tunnel_autostart).unwrap_or(false),
+        tunnel_restarts: restarts.unwrap_or(0),
+        auto_network_setup: server.as_ref().map(|s| s.auto_network_setup).unwrap_or(false),
+        network: server.map(|s| s.network).unwrap_or_default(),
+    })
+}
+
 #[tauri::command]
 pub fn stop_playit_tunnel(
     state: State<'_, NetworkState>,
     id: String
-) -> Result<String, String> {
+) -> Result<String, MineServerError> {
+    if let Ok(mut followed) = state.followed.lock() {
+        followed.remove(&id);
+    }
     let mut tunnels = state.tunnels.lock().map_err(|e| e.to_string())?;
 
     if let Some(mut child) = tunnels.remove(&id) {
         let _ = child.kill();
         Ok("Tunnel stopped".into())
     } else {
-        Err("Tunnel not running".into())
+        Err(MineServerError::conflict("Tunnel not running"))
     }
 }
 
+/// Kills playit agents started from this server folder, including ones left over from an
+/// earlier app run that we hold no handle to.
+#[cfg(target_os = "windows")]
+fn kill_playit_processes(server_path: &str) {
+    // Escape path for PowerShell
+    let clean_path = server_path.replace("'", "''"); 
+    let ps_cmd = format!(
+        "Get-Process playit -ErrorAction SilentlyContinue | Where-Object {{ $_.Path -like '*{}*' }} | Stop-Process -Force",
+        clean_path
+    );
+    
+    let _ = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &ps_cmd])
+        .output(); // Wait for it to finish
+}
+
 #[tauri::command]
 pub fn reset_playit_tunnel(
     state: State<'_, NetworkState>,
     id: String,
     server_path: String
-) -> Result<String, String> {
+) -> Result<String, MineServerError> {
+    if let Ok(mut claims) = state.claim_urls.lock() {
+        claims.remove(&id);
+    }
     // 1. Try to kill known child from HashMap
     let mut tunnels = state.tunnels.lock().map_err(|e| e.to_string())?;
     if let Some(mut child) = tunnels.remove(&id) {
@@ -357,18 +750,8 @@ pub fn reset_playit_tunnel(
     // We must kill any 'playit' process running from this specific server folder.
     #[cfg(target_os = "windows")]
     {
-        // Escape path for PowerShell
-        let clean_path = server_path.replace("'", "''"); 
-        let ps_cmd = format!(
-            "Get-Process playit -ErrorAction SilentlyContinue | Where-Object {{ $_.Path -like '*{}*' }} | Stop-Process -Force",
-            clean_path
-        );
-        
-        let _ = Command::new("powershell")
-            .args(["-NoProfile", "-Command", &ps_cmd])
-            .output(); // Wait for it to finish
-
-         std::thread::sleep(std::time::Duration::from_millis(1000));
+        kill_playit_processes(&server_path);
+        std::thread::sleep(std::time::Duration::from_millis(1000));
     }
 
     // 3. Delete Config Files (Scorched Earth Policy)
@@ -442,9 +825,87 @@ pub fn check_firewall_rule(_port: u16) -> bool {
     true // Assume open or managed externally
 }
 
+/// Adds the TCP and UDP rules for `port` under one name (Bedrock and voice chat use UDP), so
+/// checking and deleting by name covers both.
+#[cfg(target_os = "windows")]
+fn netsh_add_rule(port: u16) -> Result<std::process::Output, MineServerError> {
+    use std::process::Command;
+
+    let rule_name = format!("MineServer Port {}", port);
+    let add = |protocol: &str| Command::new("netsh")
+        .args([
+            "advfirewall", "firewall", "add", "rule", 
+            &format!("name=\"{}\"", rule_name), 
+            "dir=in", 
+            "action=allow", 
+            &format!("protocol={}", protocol), 
+            &format!("localport={}", port)
+        ])
+        .output();
+    let tcp = add("TCP")?;
+    if !tcp.status.success() {
+        return Ok(tcp);
+    }
+    Ok(add("UDP")?)
+}
+
+/// Re-adds a missing rule without prompting for elevation (used when a server starts).
 #[cfg(target_os = "windows")]
+fn ensure_firewall_rule(port: u16) -> Result<(), MineServerError> {
+    if check_firewall_rule(port) {
+        return Ok(());
+    }
+    let output = netsh_add_rule(port)?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(MineServerError::permission_denied(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn remove_firewall_rule(port: u16) -> Result<(), MineServerError> {
+    use std::process::Command;
+
+    if !check_firewall_rule(port) {
+        return Ok(());
+    }
+    let output = Command::new("netsh")
+        .args(["advfirewall", "firewall", "delete", "rule", &format!("name=\"MineServer Port {}\"", port)])
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(MineServerError::permission_denied(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn ensure_firewall_rule(_port: u16) -> Result<(), MineServerError> {
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn remove_firewall_rule(_port: u16) -> Result<(), MineServerError> {
+    Ok(())
+}
+
+/// Opens `port` in the firewall and, with `server_id`, records the rule on that server.
 #[tauri::command]
-pub async fn add_firewall_rule(port: u16) -> Result<String, String> {
+pub async fn add_firewall_rule(port: u16, server_id: Option<String>) -> Result<String, MineServerError> {
+    let message = add_firewall_rule_internal(port).await?;
+    if let Some(id) = server_id {
+        update_server_network(&id, |network| {
+            if !network.firewall_ports.contains(&port) {
+                network.firewall_ports.push(port);
+            }
+        })?;
+    }
+    Ok(message)
+}
+
+#[cfg(target_os = "windows")]
+async fn add_firewall_rule_internal(port: u16) -> Result<String, MineServerError> {
     use std::process::Command;
     
     let rule_name = format!("MineServer Port {}", port);
@@ -456,17 +917,7 @@ pub async fn add_firewall_rule(port: u16) -> Result<String, String> {
     
     // We try to run directly. If failed due to permissions, we try Powershell RunAs
     // Direct attempt:
-    let output = Command::new("netsh")
-        .args([
-            "advfirewall", "firewall", "add", "rule", 
-            &format!("name=\"{}\"", rule_name), 
-            "dir=in", 
-            "action=allow", 
-            "protocol=TCP", 
-            &format!("localport={}", port)
-        ])
-        .output()
-        .map_err(|e| e.to_string())?;
+    let output = netsh_add_rule(port)?;
         
     if output.status.success() {
         return Ok("Rule added successfully".to_string());
@@ -475,10 +926,13 @@ pub async fn add_firewall_rule(port: u16) -> Result<String, String> {
     let stderr = String::from_utf8_lossy(&output.stderr);
     if stderr.contains("Run as administrator") || stderr.contains("elevation") {
         // Try to trigger UAC via PowerShell
-        let ps_script = format!(
-            "Start-Process netsh -ArgumentList 'advfirewall firewall add rule name=\"{}\" dir=in action=allow protocol=TCP localport={}' -Verb RunAs -WindowStyle Hidden -Wait",
-            rule_name, port
-        );
+        let ps_script = ["TCP", "UDP"].iter()
+            .map(|protocol| format!(
+                "Start-Process netsh -ArgumentList 'advfirewall firewall add rule name=\"{}\" dir=in action=allow protocol={} localport={}' -Verb RunAs -WindowStyle Hidden -Wait",
+                rule_name, protocol, port
+            ))
+            .collect::<Vec<_>>()
+            .join("; ");
         
         let ps_output = Command::new("powershell")
             .args(["-NoProfile", "-Command", &ps_script])
@@ -490,19 +944,24 @@ pub async fn add_firewall_rule(port: u16) -> Result<String, String> {
              if check_firewall_rule(port) {
                  return Ok("Rule added via UAC prompt".to_string());
              } else {
-                 return Err("User cancelled UAC or operation failed".to_string());
+                 return Err(MineServerError::permission_denied("User cancelled UAC or operation failed"));
              }
         } else {
-            return Err("Failed to trigger UAC prompt".to_string());
+            return Err(MineServerError::permission_denied("Failed to trigger UAC prompt"));
         }
     }
     
-    Err(format!("Netsh failed: {}", stderr))
+    Err(format!("Netsh failed: {}", stderr).into())
+}
+
+#[cfg(not(target_os = "windows"))]
+async fn add_firewall_rule_internal(_port: u16) -> Result<String, MineServerError> {
+    Ok("Not supported on this OS".to_string())
 }
 
 #[cfg(target_os = "windows")]
 #[tauri::command]
-pub async fn set_tunnel_guard(id: String, port: u16, enabled: bool) -> Result<String, String> {
+pub async fn set_tunnel_guard(id: String, port: u16, enabled: bool) -> Result<String, MineServerError> {
     use std::process::Command;
     
     // We implement "Tunnel Guard" by simply removing the public ALLOW rule.
@@ -529,6 +988,9 @@ pub async fn set_tunnel_guard(id: String, port: u16, enabled: bool) -> Result<St
                  let _ = Command::new("powershell").args(["-NoProfile", "-Command", &ps_cmd]).output();
              }
         }
+
+        // The rule is gone on purpose, so don't restore it on the next start
+        let _ = update_server_network(&id, |network| network.firewall_ports.retain(|p| *p != port));
             
         return Ok("Tunnel Guard Active: Port Closed to Public.".to_string());
     } else {
@@ -536,12 +998,12 @@ pub async fn set_tunnel_guard(id: String, port: u16, enabled: bool) -> Result<St
         // We simply call the add_firewall_rule function logic
         // But we can't call async function from here easily without recursion?
         // We can just call it (same module).
-        return add_firewall_rule(port).await;
+        return add_firewall_rule(port, Some(id)).await;
     }
 }
 
 #[cfg(not(target_os = "windows"))]
 #[tauri::command]
-pub async fn set_tunnel_guard(_id: String, _port: u16, _enabled: bool) -> Result<String, String> {
+pub async fn set_tunnel_guard(_id: String, _port: u16, _enabled: bool) -> Result<String, MineServerError> {
     Ok("Not supported on this OS".to_string())
 }
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use tauri::State;
+use super::auth_diagnostics::{read_backend, read_proxy};
+use super::ping::java_status;
+use super::query::query_full_stat;
+use super::registry::{load_registry, RegisteredServer};
+use super::runner::ServerProcessState;
+use super::server_config::read_server_properties;
+use super::error::MineServerError;
+
+// Player counts of a proxy network. Behind Velocity or BungeeCord the proxy is the only place
+// that sees every player, so its status ping gives the total; the backends (registered servers
+// in the proxy's server list, the group of `diagnose_auth`) are asked one by one to attribute
+// players. Players the backends don't account for (servers not run from the app, query off,
+// players in limbo between servers) are reported as unattributed instead of being lost.
+
+const LOCAL_HOST: &str = "127.0.0.1";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendPlayers {
+    pub id: String,
+    pub name: String,
+    pub proxy_name: String, // Name of the server in the proxy config
+    pub port: u16,
+    pub online: Option<u32>, // None while the backend is unreachable
+    pub players: Vec<String>, // Empty when only the count is known
+    pub source: String, // "console", "query", "ping" or "offline"
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkPlayers {
+    pub proxy: String,
+    pub proxy_online: bool,
+    pub online: u32, // Network total: the proxy's count, or the backends' sum while it's down
+    pub max_players: u32,
+    pub backends: Vec<BackendPlayers>,
+    pub unattributed: u32,
+    pub unattributed_players: Vec<String>, // From the proxy's player sample, when it sends one
+}
+
+/// Names in the status ping's player sample. Proxies fill it with real players unless it is
+/// turned off; the placeholder entries some plugins add have the nil UUID.
+fn sample_names(status: &serde_json::Value) -> Vec<String> {
+    status["players"]["sample"].as_array()
+        .map(|sample| sample.iter()
+            .filter(|p| p["id"].as_str() != Some("00000000-0000-0000-0000-000000000000"))
+            .filter_map(|p| p["name"].as_str().map(str::to_string))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Players on one backend: from the console while the app runs it, otherwise over query when
+/// enabled, otherwise just the ping count.
+async fn backend_players(state: &ServerProcessState, server: &RegisteredServer, proxy_name: String, port: u16) -> BackendPlayers {
+    let mut result = BackendPlayers {
+        id: server.id.clone(),
+        name: server.name.clone(),
+        proxy_name,
+        port,
+        online: None,
+        players: Vec::new(),
+        source: "offline".to_string(),
+    };
+
+    let running = state.processes.lock().map(|p| p.contains_key(&server.id)).unwrap_or(false);
+    let console = state.live_stats.lock().ok().and_then(|s| s.get(&server.id).map(|s| s.online_players.clone()));
+    if let (true, Some(online)) = (running, console) {
+        let mut players: Vec<String> = online.into_iter().collect();
+        players.sort_by_key(|p| p.to_lowercase());
+        result.online = Some(players.len() as u32);
+        result.players = players;
+        result.source = "console".to_string();
+        return result;
+    }
+
+    let props = read_server_properties(server.path.clone()).unwrap_or_default();
+    if props.get("enable-query").map(|v| v.trim() == "true").unwrap_or(false) {
+        let query_port = props.get("query.port").and_then(|p| p.trim().parse().ok()).unwrap_or(port);
+        if let Ok(query) = query_full_stat(LOCAL_HOST, query_port).await {
+            result.online = Some(query.num_players);
+            result.players = query.players;
+            result.source = "query".to_string();
+            return result;
+        }
+    }
+
+    if let Ok(Ok((status, _))) = tokio::task::spawn_blocking(move || java_status(LOCAL_HOST, port)).await {
+        result.online = Some(status["players"]["online"].as_u64().unwrap_or(0) as u32);
+        result.players = sample_names(&status);
+        result.source = "ping".to_string();
+    }
+    result
+}
+
+/// Network-wide player count of a proxy group (`group_id` is the proxy's registered id),
+/// split by backend.
+#[tauri::command]
+pub async fn get_network_players(state: State<'_, ServerProcessState>, group_id: String) -> Result<NetworkPlayers, MineServerError> {
+    let registry = load_registry()?;
+    let proxy_server = registry.iter().find(|s| s.id == group_id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", group_id)))?;
+    let proxy = read_proxy(Path::new(&proxy_server.path), &proxy_server.name)
+        .ok_or_else(|| MineServerError::validation(format!("{} is not a Velocity or BungeeCord proxy", proxy_server.name)))?;
+
+    let proxy_root = fs::canonicalize(&proxy_server.path).ok();
+    let mut backends = Vec::new();
+    for server in &registry {
+        let root = Path::new(&server.path);
+        if server.id == group_id || fs::canonicalize(root).ok() == proxy_root || read_proxy(root, "").is_some() {
+            continue;
+        }
+        let Some(backend) = read_backend(root, &server.name) else { continue };
+        if let Some((proxy_name, _)) = proxy.servers.iter().find(|(_, p)| *p == backend.port) {
+            backends.push(backend_players(&state, server, proxy_name.clone(), backend.port).await);
+        }
+    }
+
+    let proxy_port = proxy.port;
+    let status = tokio::task::spawn_blocking(move || java_status(LOCAL_HOST, proxy_port)).await?.ok().map(|(s, _)| s);
+    let attributed: u32 = backends.iter().filter_map(|b| b.online).sum();
+    let (online, max_players, sample) = match &status {
+        Some(status) => (
+            status["players"]["online"].as_u64().unwrap_or(0) as u32,
+            status["players"]["max"].as_u64().unwrap_or(0) as u32,
+            sample_names(status),
+        ),
+        None => (attributed, 0, Vec::new()),
+    };
+    let unattributed_players = sample.into_iter()
+        .filter(|name| !backends.iter().any(|b| b.players.iter().any(|p| p.eq_ignore_ascii_case(name))))
+        .collect();
+
+    Ok(NetworkPlayers {
+        proxy: proxy.name,
+        proxy_online: status.is_some(),
+        online,
+        max_players,
+        backends,
+        unattributed: online.saturating_sub(attributed),
+        unattributed_players,
+    })
+}
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::fs;
+use serde::{Deserialize, Serialize};
+use super::error::MineServerError;
+use super::http::standalone_client;
+use super::path_guard::app_data_dir;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannel {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub kind: String, // "discord", "webhook", "telegram"
+    #[serde(default)]
+    pub webhook_url: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>, // Extra headers for generic webhooks (e.g. Authorization)
+    #[serde(default)]
+    pub bot_token: Option<String>, // Telegram
+    #[serde(default)]
+    pub chat_id: Option<String>, // Telegram
+    pub events: Vec<String>, // "server_started", "player_join", ... (empty = all)
+    pub enabled: bool,
+    #[serde(default)]
+    pub templates: HashMap<String, String>, // event -> message template override
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationEvent {
+    ServerStarted,
+    ServerStopped,
+    ServerCrashed,
+    PlayerJoin,
+    PlayerLeave,
+    BackupSuccess,
+    BackupFailure,
+    RestartWarning,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ServerStarted => "server_started",
+            Self::ServerStopped => "server_stopped",
+            Self::ServerCrashed => "server_crashed",
+            Self::PlayerJoin => "player_join",
+            Self::PlayerLeave => "player_leave",
+            Self::BackupSuccess => "backup_success",
+            Self::BackupFailure => "backup_failure",
+            Self::RestartWarning => "restart_warning",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Self::ServerStarted => "Server Started",
+            Self::ServerStopped => "Server Stopped",
+            Self::ServerCrashed => "Server Crashed",
+            Self::PlayerJoin => "Player Joined",
+            Self::PlayerLeave => "Player Left",
+            Self::BackupSuccess => "Backup Completed",
+            Self::BackupFailure => "Backup Failed",
+            Self::RestartWarning => "Scheduled Restart",
+        }
+    }
+
+    fn default_template(&self) -> &'static str {
+        match self {
+            Self::ServerStarted => "**{server}** is now online.",
+            Self::ServerStopped => "**{server}** has been stopped.",
+            Self::ServerCrashed => "**{server}** stopped unexpectedly. {details}",
+            Self::PlayerJoin => "**{player}** joined **{server}**.",
+            Self::PlayerLeave => "**{player}** left **{server}**.",
+            Self::BackupSuccess => "Backup of **{server}** finished. {details}",
+            Self::BackupFailure => "Backup of **{server}** failed: {details}",
+            Self::RestartWarning => "**{server}** is restarting (scheduled task).",
+        }
+    }
+
+    // Discord embed colors
+    fn color(&self) -> u32 {
+        match self {
+            Self::ServerStarted | Self::BackupSuccess => 0x22C55E,
+            Self::ServerStopped | Self::PlayerLeave => 0x64748B,
+            Self::ServerCrashed | Self::BackupFailure => 0xEF4444,
+            Self::PlayerJoin => 0x3B82F6,
+            Self::RestartWarning => 0xF59E0B,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub server: String,
+    pub player: Option<String>,
+    pub details: String,
+}
+
+impl Notification {
+    pub fn new(event: NotificationEvent, server: impl Into<String>) -> Self {
+        Self {
+            event,
+            server: server.into(),
+            player: None,
+            details: String::new(),
+        }
+    }
+
+    pub fn with_player(mut self, player: impl Into<String>) -> Self {
+        self.player = Some(player.into());
+        self
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = details.into();
+        self
+    }
+}
+
+fn get_channels_file() -> PathBuf {
+    app_data_dir().join("notifications.json")
+}
+
+fn load_channels() -> Result<Vec<NotificationChannel>, String> {
+    let file = get_channels_file();
+    if !file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read notification channels: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse notification channels: {}", e))
+}
+
+fn save_channels(channels: &Vec<NotificationChannel>) -> Result<(), String> {
+    let file = get_channels_file();
+    fs::create_dir_all(file.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let content = serde_json::to_string_pretty(channels)
+        .map_err(|e| format!("Failed to serialize notification channels: {}", e))?;
+    fs::write(&file, content)
+        .map_err(|e| format!("Failed to write notification channels: {}", e))
+}
+
+fn render_template(template: &str, notification: &Notification) -> String {
+    render_template_with(template, notification, str::to_string)
+}
+
+/// Fills in the placeholders, passing each value through `escape` first.
+fn render_template_with(template: &str, notification: &Notification, escape: impl Fn(&str) -> String) -> String {
+    template
+        .replace("{server}", &escape(&notification.server))
+        .replace("{player}", &escape(notification.player.as_deref().unwrap_or("")))
+        .replace("{details}", &escape(&notification.details))
+        .replace("{time}", &chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .replace("{event}", notification.event.as_str())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// The template as Telegram HTML: `**bold**` becomes `<b>bold</b>` and player names or
+/// details can't inject markup.
+fn render_telegram_html(template: &str, notification: &Notification) -> String {
+    let mut html = String::new();
+    for (i, part) in escape_html(template).split("**").enumerate() {
+        if i > 0 {
+            html.push_str(if i % 2 == 1 { "<b>" } else { "</b>" });
+        }
+        html.push_str(part);
+    }
+    if html.matches("<b>").count() > html.matches("</b>").count() {
+        html.push_str("</b>");
+    }
+    render_template_with(&html, notification, escape_html)
+}
+
+fn wants_event(channel: &NotificationChannel, event: NotificationEvent) -> bool {
+    channel.enabled && (channel.events.is_empty() || channel.events.iter().any(|e| e == event.as_str()))
+}
+
+async fn send_to_channel(channel: &NotificationChannel, notification: &Notification) -> Result<(), String> {
+    let template = channel.templates.get(notification.event.as_str())
+        .map(|t| t.as_str())
+        .unwrap_or(notification.event.default_template());
+    let description = render_template(template, notification);
+
+    match channel.kind.as_str() {
+        "discord" => {
+            let body = serde_json::json!({
+                "username": "MineServer",
+                "embeds": [{
+                    "title": notification.event.title(),
+                    "description": description,
+                    "color": notification.event.color(),
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                    "footer": { "text": notification.server },
+                }]
+            });
+
+            let resp = standalone_client()
+                .post(&channel.webhook_url)
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| format!("Webhook request failed: {}", e))?;
+
+            if !resp.status().is_success() {
+                return Err(format!("Discord rejected the webhook: HTTP {}", resp.status()));
+            }
+            Ok(())
+        },
+        "webhook" => {
+            let body = serde_json::json!({
+                "event": notification.event.as_str(),
+                "title": notification.event.title(),
+                "message": description.replace("**", ""),
+                "server": notification.server,
+                "player": notification.player,
+                "details": notification.details,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            });
+
+            let mut req = standalone_client()
+                .post(&channel.webhook_url)
+                .json(&body);
+            for (key, value) in &channel.headers {
+                req = req.header(key.as_str(), value.as_str());
+            }
+
+            let resp = req.send()
+                .await
+                .map_err(|e| format!("Webhook request failed: {}", e))?;
+
+            if !resp.status().is_success() {
+                return Err(format!("Webhook returned HTTP {}", resp.status()));
+            }
+            Ok(())
+        },
+        "telegram" => {
+            let token = channel.bot_token.as_deref().filter(|t| !t.is_empty())
+                .ok_or("Telegram bot token is missing")?;
+            let chat_id = channel.chat_id.as_deref().filter(|c| !c.is_empty())
+                .ok_or("Telegram chat id is missing")?;
+
+            let text = format!("<b>{}</b>\n{}", escape_html(notification.event.title()), render_telegram_html(template, notification));
+            let body = serde_json::json!({
+                "chat_id": chat_id,
+                "text": text,
+                "parse_mode": "HTML",
+                "disable_web_page_preview": true,
+            });
+
+            let resp = standalone_client()
+                .post(format!("https://api.telegram.org/bot{}/sendMessage", token))
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| format!("Telegram request failed: {}", e))?;
+
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let detail = resp.text().await.unwrap_or_default();
+                return Err(format!("Telegram returned HTTP {}: {}", status, detail));
+            }
+            Ok(())
+        },
+        other => Err(format!("Unsupported notification channel: {}", other)),
+    }
+}
+
+/// Fire-and-forget delivery to every enabled channel subscribed to the event.
+pub fn dispatch(notification: Notification) {
+    tauri::async_runtime::spawn(async move {
+        let channels = match load_channels() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("[Notifications] {}", e);
+                return;
+            }
+        };
+
+        for channel in channels.iter().filter(|c| wants_event(c, notification.event)) {
+            if let Err(e) = send_to_channel(channel, &notification).await {
+                eprintln!("[Notifications] {} ({}): {}", channel.name, channel.kind, e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn list_notification_channels() -> Result<Vec<NotificationChannel>, MineServerError> {
+    load_channels().map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn add_notification_channel(channel: NotificationChannel) -> Result<NotificationChannel, MineServerError> {
+    let mut channels = load_channels()?;
+    let mut channel = channel;
+
+    if channel.id.is_empty() {
+        channel.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    // Adding an existing id updates it in place
+    if let Some(existing) = channels.iter_mut().find(|c| c.id == channel.id) {
+        *existing = channel.clone();
+    } else {
+        channels.push(channel.clone());
+    }
+
+    save_channels(&channels)?;
+    Ok(channel)
+}
+
+#[tauri::command]
+pub async fn remove_notification_channel(id: String) -> Result<(), MineServerError> {
+    let mut channels = load_channels()?;
+    channels.retain(|c| c.id != id);
+    save_channels(&channels).map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn test_notification(id: String) -> Result<(), MineServerError> {
+    let channels = load_channels()?;
+    let channel = channels.iter().find(|c| c.id == id)
+        .ok_or("Notification channel not found")?;
+
+    let notification = Notification::new(NotificationEvent::ServerStarted, "MineServer (test)");
+
+    send_to_channel(channel, &notification).await.map_err(Into::into)
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::State;
+use super::runner::ServerEventSink;
+use super::error::MineServerError;
+
+// Registry of long-running operations (world uploads, archives, backups, downloads) so the UI
+// can cancel them. The frontend may pass its own `operationId` when starting one; long loops
+// poll the operation's CancelToken and remove their partial output once it fires.
+//
+// Progress for every kind goes out on one `operation-progress` event, so the UI can render any
+// operation the same way: "running" updates while it works, then exactly one terminal state.
+
+pub const CANCELLED: &str = "Operation cancelled";
+pub const PROGRESS_EVENT: &str = "operation-progress";
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shared flag checked by long loops. The default token is never cancelled.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(CANCELLED)` once cancelled, for use with `?` inside loops.
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() { Err(CANCELLED.to_string()) } else { Ok(()) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationInfo {
+    pub id: String,
+    pub kind: String, // "backup", "world_upload", "world_archive", "download", ...
+    pub started_at: String,
+    pub cancelling: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationProgress {
+    pub op_id: String,
+    pub kind: String,
+    pub percentage: u8,
+    pub bytes: u64, // Processed so far (downloaded, extracted or compressed)
+    pub detail: String,
+    pub state: String, // "running", "completed", "failed" or "cancelled"
+}
+
+struct Entry {
+    info: OperationInfo,
+    token: CancelToken,
+}
+
+#[derive(Clone)]
+pub struct OperationState {
+    operations: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl OperationState {
+    pub fn new() -> Self {
+        Self { operations: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// Registers an operation under `id`, or a fresh id when none is given. The entry is
+    /// removed again when the returned handle is dropped.
+    pub fn begin(&self, id: Option<String>, kind: &str) -> Result<Operation, String> {
+        let id = id.filter(|i| !i.trim().is_empty()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let token = CancelToken::default();
+        let mut operations = self.operations.lock().map_err(|e| e.to_string())?;
+        if operations.contains_key(&id) {
+            return Err(format!("Operation {} is already running", id));
+        }
+        operations.insert(id.clone(), Entry {
+            info: OperationInfo {
+                id: id.clone(),
+                kind: kind.to_string(),
+                started_at: chrono::Local::now().to_rfc3339(),
+                cancelling: false,
+            },
+            token: token.clone(),
+        });
+        Ok(Operation {
+            id,
+            kind: kind.to_string(),
+            token,
+            operations: self.operations.clone(),
+            events: None,
+            progress: Mutex::new(ProgressState { last_emit: None, percentage: 0, bytes: 0, completed: false }),
+        })
+    }
+
+    /// Asks a running operation to stop; its loops bail out at the next `token.check()`.
+    pub fn cancel(&self, id: &str) -> Result<(), String> {
+        let mut operations = self.operations.lock().map_err(|e| e.to_string())?;
+        let entry = operations.get_mut(id).ok_or("Operation not found (it may have already finished)")?;
+        entry.token.cancel();
+        entry.info.cancelling = true;
+        Ok(())
+    }
+}
+
+struct ProgressState {
+    last_emit: Option<Instant>,
+    percentage: u8,
+    bytes: u64,
+    completed: bool,
+}
+
+/// A running operation. Unregisters itself on drop, whether the work finished, failed or was
+/// cancelled, and sends the terminal progress event if events are enabled.
+pub struct Operation {
+    pub id: String,
+    pub kind: String,
+    pub token: CancelToken,
+    operations: Arc<Mutex<HashMap<String, Entry>>>,
+    events: Option<Box<dyn Fn(OperationProgress) + Send + Sync>>,
+    progress: Mutex<ProgressState>,
+}
+
+impl Operation {
+    /// Sends `operation-progress` events for this operation to `sink`.
+    pub fn with_events<E: ServerEventSink>(mut self, sink: E) -> Self {
+        self.events = Some(Box::new(move |progress| sink.emit(PROGRESS_EVENT, progress)));
+        self
+    }
+
+    /// Reports a "running" update. Updates closer than 100 ms to the previous one are dropped,
+    /// so this can be called from tight copy loops.
+    pub fn progress(&self, percentage: u8, bytes: u64, detail: impl Into<String>) {
+        let mut state = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        state.percentage = percentage.min(100);
+        state.bytes = bytes;
+        if state.last_emit.map(|t| t.elapsed() < PROGRESS_INTERVAL).unwrap_or(false) {
+            return;
+        }
+        state.last_emit = Some(Instant::now());
+        let (percentage, bytes) = (state.percentage, state.bytes);
+        drop(state);
+        self.emit(percentage, bytes, detail.into(), "running");
+    }
+
+    /// Marks the work as done; the "completed" event goes out when the handle is dropped.
+    pub fn complete(&self) {
+        let mut state = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        state.completed = true;
+        state.percentage = 100;
+    }
+
+    fn emit(&self, percentage: u8, bytes: u64, detail: String, state: &str) {
+        if let Some(events) = &self.events {
+            events(OperationProgress {
+                op_id: self.id.clone(),
+                kind: self.kind.clone(),
+                percentage,
+                bytes,
+                detail,
+                state: state.to_string(),
+            });
+        }
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if let Ok(mut operations) = self.operations.lock() {
+            operations.remove(&self.id);
+        }
+        let (percentage, bytes, completed) = {
+            let state = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+            (state.percentage, state.bytes, state.completed)
+        };
+        let (state, detail) = if completed {
+            ("completed", "Done")
+        } else if self.token.is_cancelled() {
+            ("cancelled", CANCELLED)
+        } else {
+            ("failed", "Failed")
+        };
+        self.emit(percentage, bytes, detail.to_string(), state);
+    }
+}
+
+#[tauri::command]
+pub fn cancel_operation(state: State<'_, OperationState>, op_id: String) -> Result<(), MineServerError> {
+    state.cancel(&op_id).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn list_operations(state: State<'_, OperationState>) -> Vec<OperationInfo> {
+    state.operations.lock()
+        .map(|ops| ops.values().map(|e| e.info.clone()).collect())
+        .unwrap_or_default()
+}
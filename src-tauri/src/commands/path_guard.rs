@@ -0,0 +1,143 @@
+use std::path::{Component, Path, PathBuf};
+use super::registry::load_registry;
+use super::error::MineServerError;
+use super::trash::TRASH_DIR;
+
+// Central check for file commands: paths are canonicalized (resolving `..` and symlinks)
+// and must land inside a registered server folder or the app data dir (~/Mineserver).
+
+pub fn app_data_dir() -> PathBuf {
+    let home = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join("Mineserver")
+}
+
+// The app's own state under the data dir: file commands must not read or overwrite these
+const APP_STATE_FILES: &[&str] = &[
+    "servers.json", "settings.json", "scheduled_tasks.json", "notifications.json", "sync_groups.json",
+    "snippets.json", "favorites.json", "xuids.json", "audit.log", "activity.log", "uptime.db", "metrics.db",
+];
+const BACKUP_INDEX: &str = "Backups/backups_index.json";
+// Folders of the data dir that belong to the app rather than to a server
+const APP_FOLDERS: &[&str] = &["Backups", "sessions"];
+
+fn registered_server_roots() -> Vec<PathBuf> {
+    load_registry()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|s| std::fs::canonicalize(&s.path).ok())
+        .collect()
+}
+
+/// Canonical roots that file commands may touch.
+pub fn allowed_roots() -> Vec<PathBuf> {
+    let mut roots = registered_server_roots();
+    if let Ok(app_dir) = std::fs::canonicalize(app_data_dir()) {
+        roots.push(app_dir);
+    }
+    roots
+}
+
+/// The registered server folder containing `path` (innermost match), if any.
+pub fn server_root_for(path: &Path) -> Option<PathBuf> {
+    let resolved = canonicalize_lenient(path).ok()?;
+    registered_server_roots()
+        .into_iter()
+        .filter(|root| resolved.starts_with(root))
+        .max_by_key(|root| root.components().count())
+}
+
+/// Canonicalizes a path that may not exist yet: the deepest existing ancestor is resolved
+/// and the remaining (plain) components are appended.
+pub(crate) fn canonicalize_lenient(path: &Path) -> Result<PathBuf, String> {
+    if let Ok(p) = std::fs::canonicalize(path) {
+        return Ok(p);
+    }
+
+    let mut missing = Vec::new();
+    let mut current = path;
+    loop {
+        match current.file_name() {
+            Some(name) => missing.push(name.to_os_string()),
+            None => return Err(format!("Invalid path: {}", path.display())),
+        }
+        // `file_name` is None for `..`, so anything collected here is a normal component
+        current = current.parent().ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+        if let Ok(base) = std::fs::canonicalize(current) {
+            let mut resolved = base;
+            for part in missing.iter().rev() {
+                resolved.push(part);
+            }
+            return Ok(resolved);
+        }
+    }
+}
+
+/// `resolved` relative to the canonical app data dir, if it lies inside it.
+fn relative_to_app_dir(resolved: &Path) -> Option<PathBuf> {
+    let app_dir = std::fs::canonicalize(app_data_dir()).ok()?;
+    resolved.strip_prefix(&app_dir).ok().map(Path::to_path_buf)
+}
+
+/// Whether `resolved` is one of the app's state files (or a temp copy written next to one).
+fn is_app_state_file(resolved: &Path) -> bool {
+    let Some(relative) = relative_to_app_dir(resolved) else { return false };
+    let name = relative.to_string_lossy().replace('\\', "/");
+    APP_STATE_FILES.iter().chain([&BACKUP_INDEX])
+        .any(|file| name == *file || name.starts_with(&format!("{}.", file)))
+}
+
+fn has_parent_component(path: &Path) -> bool {
+    path.components().any(|c| matches!(c, Component::ParentDir))
+}
+
+/// Returns the canonical form of `path` if it lies inside an allowed root.
+pub fn validate_path(path: &str) -> Result<PathBuf, MineServerError> {
+    let raw = Path::new(path);
+    if path.trim().is_empty() || !raw.is_absolute() {
+        return Err(MineServerError::validation("Safety check failed: path must be absolute"));
+    }
+    if has_parent_component(raw) && !raw.exists() {
+        return Err(MineServerError::permission_denied("Safety check failed: path traversal is not allowed"));
+    }
+
+    let resolved = canonicalize_lenient(raw)?;
+    if is_app_state_file(&resolved) {
+        return Err(MineServerError::permission_denied("Safety check failed: app state files cannot be accessed"));
+    }
+    if allowed_roots().iter().any(|root| resolved.starts_with(root)) {
+        Ok(resolved)
+    } else {
+        Err(MineServerError::permission_denied(format!("Safety check failed: {} is outside of the managed server folders", path)))
+    }
+}
+
+/// Like `validate_path`, but refuses the roots themselves (e.g. deleting a whole server
+/// folder through the file manager).
+pub fn validate_path_inside_root(path: &str) -> Result<PathBuf, MineServerError> {
+    let resolved = validate_path(path)?;
+    if allowed_roots().iter().any(|root| &resolved == root) {
+        return Err(MineServerError::permission_denied("Safety check failed: refusing to modify a server root folder"));
+    }
+    Ok(resolved)
+}
+
+/// A server folder that may be deleted as a whole: exactly a registered server, or a
+/// folder inside the app data dir (but not the app data dir itself nor one of the app's own
+/// folders such as Backups, or a trash folder).
+pub fn validate_server_root(path: &str) -> Result<PathBuf, MineServerError> {
+    let resolved = validate_path(path)?;
+    if registered_server_roots().contains(&resolved) {
+        return Ok(resolved);
+    }
+    let app_owned = |relative: &Path| {
+        let top = relative.components().next().map(|c| c.as_os_str().to_string_lossy().to_string());
+        top.is_some_and(|top| APP_FOLDERS.contains(&top.as_str()))
+            || relative.components().any(|c| c.as_os_str() == TRASH_DIR)
+    };
+    match relative_to_app_dir(&resolved) {
+        Some(relative) if relative.components().next().is_some() && !app_owned(&relative) && resolved.is_dir() => Ok(resolved),
+        _ => Err(MineServerError::permission_denied("Safety check failed: path is not a server folder")),
+    }
+}
@@ -0,0 +1,178 @@
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use super::error::MineServerError;
+
+// Server List Ping: the status request the multiplayer screen sends (Java over TCP, Bedrock
+// as a RakNet unconnected ping over UDP). Works without enable-query and without RCON.
+
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+const RAKNET_MAGIC: [u8; 16] = [0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingResult {
+    pub latency_ms: u64,
+    pub online_players: u32,
+    pub max_players: u32,
+    pub version: String,
+    pub motd: String,
+}
+
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buf.push(value as u8);
+            return;
+        }
+        buf.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+}
+
+fn read_varint(stream: &mut impl Read) -> Result<i32, String> {
+    let mut result: u32 = 0;
+    for i in 0..5 {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte).map_err(|e| format!("Ping read failed: {}", e))?;
+        result |= ((byte[0] & 0x7F) as u32) << (7 * i);
+        if byte[0] & 0x80 == 0 {
+            return Ok(result as i32);
+        }
+    }
+    Err("Invalid VarInt in ping response".to_string())
+}
+
+/// Prefixes a packet (id + body) with its VarInt length.
+fn frame(packet_id: i32, body: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(body.len() + 5);
+    write_varint(&mut packet, packet_id);
+    packet.extend_from_slice(body);
+    let mut framed = Vec::with_capacity(packet.len() + 5);
+    write_varint(&mut framed, packet.len() as i32);
+    framed.extend_from_slice(&packet);
+    framed
+}
+
+/// MOTDs are either a plain string or a chat component (`text` plus nested `extra`).
+fn flatten_chat(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Object(obj) => {
+            let mut text = obj.get("text").and_then(|t| t.as_str()).unwrap_or("").to_string();
+            if let Some(extra) = obj.get("extra").and_then(|e| e.as_array()) {
+                for part in extra {
+                    text.push_str(&flatten_chat(part));
+                }
+            }
+            text
+        }
+        serde_json::Value::Array(parts) => parts.iter().map(flatten_chat).collect(),
+        _ => String::new(),
+    }
+}
+
+pub fn ping_java(host: &str, port: u16) -> Result<PingResult, String> {
+    let (status, latency_ms) = java_status(host, port)?;
+    let players = &status["players"];
+    Ok(PingResult {
+        latency_ms,
+        online_players: players["online"].as_u64().unwrap_or(0) as u32,
+        max_players: players["max"].as_u64().unwrap_or(0) as u32,
+        version: status["version"]["name"].as_str().unwrap_or("").to_string(),
+        motd: flatten_chat(&status["description"]),
+    })
+}
+
+/// The raw status JSON (description, players, version, favicon) and the round-trip latency.
+pub fn java_status(host: &str, port: u16) -> Result<(serde_json::Value, u64), String> {
+    let addr = (host, port).to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("Failed to resolve {}", host))?;
+    let mut stream = TcpStream::connect_timeout(&addr, PING_TIMEOUT)
+        .map_err(|e| format!("Failed to reach {}:{}: {}", host, port, e))?;
+    stream.set_read_timeout(Some(PING_TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(PING_TIMEOUT)).map_err(|e| e.to_string())?;
+
+    // Handshake (protocol -1 = "just asking"), next state 1 = status, then the status request
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, -1);
+    write_varint(&mut handshake, host.len() as i32);
+    handshake.extend_from_slice(host.as_bytes());
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1);
+    stream.write_all(&frame(0x00, &handshake)).map_err(|e| format!("Ping send failed: {}", e))?;
+    stream.write_all(&frame(0x00, &[])).map_err(|e| format!("Ping send failed: {}", e))?;
+
+    let _length = read_varint(&mut stream)?;
+    if read_varint(&mut stream)? != 0x00 {
+        return Err("Unexpected status response".to_string());
+    }
+    let json_len = read_varint(&mut stream)?;
+    if !(0..=1 << 21).contains(&json_len) {
+        return Err("Invalid status response length".to_string());
+    }
+    let mut json = vec![0u8; json_len as usize];
+    stream.read_exact(&mut json).map_err(|e| format!("Ping read failed: {}", e))?;
+    let status: serde_json::Value = serde_json::from_slice(&json)
+        .map_err(|e| format!("Invalid status JSON: {}", e))?;
+
+    // Ping/pong round trip for latency
+    let started = Instant::now();
+    stream.write_all(&frame(0x01, &0i64.to_be_bytes())).map_err(|e| format!("Ping send failed: {}", e))?;
+    let _length = read_varint(&mut stream)?;
+    let _id = read_varint(&mut stream)?;
+    let mut payload = [0u8; 8];
+    stream.read_exact(&mut payload).map_err(|e| format!("Ping read failed: {}", e))?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    Ok((status, latency_ms))
+}
+
+pub fn ping_bedrock(host: &str, port: u16) -> Result<PingResult, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| e.to_string())?;
+    socket.set_read_timeout(Some(PING_TIMEOUT)).map_err(|e| e.to_string())?;
+    socket.connect((host, port)).map_err(|e| format!("Failed to reach {}:{}: {}", host, port, e))?;
+
+    // Unconnected ping: id, time, magic, client GUID
+    let mut packet = vec![0x01];
+    packet.extend_from_slice(&(chrono::Utc::now().timestamp_millis()).to_be_bytes());
+    packet.extend_from_slice(&RAKNET_MAGIC);
+    packet.extend_from_slice(&0u64.to_be_bytes());
+
+    let started = Instant::now();
+    socket.send(&packet).map_err(|e| format!("Ping send failed: {}", e))?;
+    let mut buf = [0u8; 2048];
+    let n = socket.recv(&mut buf).map_err(|e| format!("Ping read failed: {}", e))?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    // Unconnected pong: id(1) time(8) guid(8) magic(16) len(2) "MCPE;motd;protocol;version;online;max;..."
+    if n < 35 || buf[0] != 0x1c {
+        return Err("Unexpected Bedrock ping response".to_string());
+    }
+    let len = u16::from_be_bytes([buf[33], buf[34]]) as usize;
+    let info = String::from_utf8_lossy(&buf[35..(35 + len).min(n)]).to_string();
+    let fields: Vec<&str> = info.split(';').collect();
+    let field = |i: usize| fields.get(i).copied().unwrap_or("");
+
+    Ok(PingResult {
+        latency_ms,
+        online_players: field(4).parse().unwrap_or(0),
+        max_players: field(5).parse().unwrap_or(0),
+        version: field(3).to_string(),
+        motd: field(1).to_string(),
+    })
+}
+
+#[tauri::command]
+pub async fn ping_server(host: Option<String>, port: u16, bedrock: Option<bool>) -> Result<PingResult, MineServerError> {
+    let host = host.unwrap_or_else(|| "127.0.0.1".to_string());
+    let result = tokio::task::spawn_blocking(move || {
+        if bedrock.unwrap_or(false) { ping_bedrock(&host, port) } else { ping_java(&host, port) }
+    })
+    .await?;
+    result.map_err(MineServerError::network)
+}
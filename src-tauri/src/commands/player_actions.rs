@@ -0,0 +1,139 @@
+use std::time::Duration;
+use serde::Serialize;
+use tauri::State;
+use super::runner::{ServerProcessState, send_server_command_direct, console_output_since};
+use super::error::MineServerError;
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(3);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerActionResult {
+    pub success: bool,
+    pub confirmed: bool, // false = no matching console response before the timeout
+    pub message: String,
+}
+
+// Lowercase substrings that mark the console response of each action (Java + Bedrock wording)
+struct ResponsePatterns {
+    success: &'static [&'static str],
+    failure: &'static [&'static str],
+}
+
+const COMMON_FAILURES: &[&str] = &[
+    "no player was found",
+    "that player does not exist",
+    "no targets matched selector",
+    "unknown or incomplete command",
+    "unknown command",
+    "nothing changed",
+];
+
+fn patterns_for(action: &str) -> ResponsePatterns {
+    match action {
+        "kick" => ResponsePatterns { success: &["kicked "], failure: &[] },
+        "ban" => ResponsePatterns { success: &["banned "], failure: &["already banned"] },
+        "pardon" => ResponsePatterns { success: &["unbanned "], failure: &["isn't banned", "is not banned"] },
+        "op" => ResponsePatterns { success: &["a server operator", "opped: "], failure: &["already is an operator"] },
+        "deop" => ResponsePatterns { success: &["no longer a server operator", "de-opped: "], failure: &["is not an operator"] },
+        "whitelist_add" => ResponsePatterns {
+            success: &["to the whitelist", "added to allowlist", "added to whitelist"],
+            failure: &["already whitelisted", "already in the allowlist", "already in allowlist"],
+        },
+        "whitelist_remove" => ResponsePatterns {
+            success: &["from the whitelist", "removed from allowlist", "removed from whitelist"],
+            failure: &["not whitelisted", "not in allowlist", "not in the allowlist"],
+        },
+        _ => ResponsePatterns { success: &[], failure: &[] },
+    }
+}
+
+fn is_bedrock(state: &ServerProcessState, id: &str) -> bool {
+    state.configs.lock()
+        .ok()
+        .and_then(|c| c.get(id).map(|cfg| cfg.jar_file.contains("bedrock_server")))
+        .unwrap_or(false)
+}
+
+fn build_command(action: &str, player: &str, reason: Option<&str>, bedrock: bool) -> Result<String, String> {
+    // Bedrock gamertags may contain spaces and need quoting
+    let target = if bedrock && player.contains(' ') { format!("\"{}\"", player) } else { player.to_string() };
+    let with_reason = |base: String| match reason.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(r) => format!("{} {}", base, r),
+        None => base,
+    };
+    let list = if bedrock { "allowlist" } else { "whitelist" };
+
+    match action {
+        "kick" => Ok(with_reason(format!("kick {}", target))),
+        "ban" if bedrock => Err("Bedrock Dedicated Server has no ban command. Remove the player from the allowlist instead.".to_string()),
+        "ban" => Ok(with_reason(format!("ban {}", target))),
+        "pardon" if bedrock => Err("Bedrock Dedicated Server has no ban list".to_string()),
+        "pardon" => Ok(format!("pardon {}", target)),
+        "op" => Ok(format!("op {}", target)),
+        "deop" => Ok(format!("deop {}", target)),
+        "whitelist_add" => Ok(format!("{} add {}", list, target)),
+        "whitelist_remove" => Ok(format!("{} remove {}", list, target)),
+        other => Err(format!("Unknown player action: {}", other)),
+    }
+}
+
+fn classify_line(action: &str, player: &str, line: &str) -> Option<bool> {
+    let lower = line.to_lowercase();
+    let patterns = patterns_for(action);
+
+    if patterns.failure.iter().chain(COMMON_FAILURES).any(|p| lower.contains(p)) {
+        return Some(false);
+    }
+    // Success lines name the player, which avoids matching unrelated output
+    if lower.contains(&player.to_lowercase()) && patterns.success.iter().any(|p| lower.contains(p)) {
+        return Some(true);
+    }
+    None
+}
+
+/// Runs kick/ban/pardon/op/deop/whitelist_add/whitelist_remove on a running server and
+/// waits briefly for the console to confirm the result.
+#[tauri::command]
+pub async fn player_action(
+    state: State<'_, ServerProcessState>,
+    id: String,
+    player: String,
+    action: String,
+    reason: Option<String>,
+) -> Result<PlayerActionResult, MineServerError> {
+    let player = player.trim().to_string();
+    if player.is_empty() || player.chars().any(|c| c.is_control() || c == '"') {
+        return Err(MineServerError::validation("Invalid player name"));
+    }
+
+    let command = build_command(&action, &player, reason.as_deref(), is_bedrock(state.inner(), &id))?;
+
+    // Only look at output printed after the command was sent
+    let (mut seq, _) = console_output_since(state.inner(), &id, u64::MAX);
+    send_server_command_direct(state.inner(), id.clone(), command)?;
+
+    let deadline = tokio::time::Instant::now() + CONFIRM_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let (next_seq, lines) = console_output_since(state.inner(), &id, seq);
+        seq = next_seq;
+
+        for line in lines {
+            if let Some(success) = classify_line(&action, &player, &line) {
+                return Ok(PlayerActionResult {
+                    success,
+                    confirmed: true,
+                    message: line,
+                });
+            }
+        }
+    }
+
+    Ok(PlayerActionResult {
+        success: true,
+        confirmed: false,
+        message: "Command sent, but the server did not confirm it".to_string(),
+    })
+}
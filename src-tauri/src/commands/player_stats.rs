@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::fs;
+use std::sync::Mutex;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Player sessions recorded from console join/leave lines, one file per server:
+// ~/Mineserver/sessions/<server_id>.json
+
+const MAX_SESSIONS: usize = 10_000;
+
+// Join/leave (stdout thread) and close (monitor thread) can race on the same file
+static SESSION_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSession {
+    pub player: String,
+    pub joined_at: DateTime<Local>,
+    pub left_at: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionStore {
+    sessions: Vec<PlayerSession>,
+    peak_concurrent: u32,
+    peak_at: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    pub player: String,
+    pub total_playtime_secs: i64,
+    pub session_count: u32,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+    pub online: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerPlayerStats {
+    pub players: Vec<PlayerStats>, // Sorted by playtime, highest first
+    pub total_sessions: usize,
+    pub peak_concurrent: u32,
+    pub peak_at: Option<DateTime<Local>>,
+}
+
+fn get_sessions_file(server_id: &str) -> PathBuf {
+    app_data_dir().join("sessions").join(format!("{}.json", server_id))
+}
+
+fn load_store(server_id: &str) -> Result<SessionStore, String> {
+    let file = get_sessions_file(server_id);
+    if !file.exists() {
+        return Ok(SessionStore::default());
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read player sessions: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse player sessions: {}", e))
+}
+
+fn save_store(server_id: &str, store: &SessionStore) -> Result<(), String> {
+    let file = get_sessions_file(server_id);
+    fs::create_dir_all(file.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let content = serde_json::to_string(store)
+        .map_err(|e| format!("Failed to serialize player sessions: {}", e))?;
+    fs::write(&file, content)
+        .map_err(|e| format!("Failed to write player sessions: {}", e))
+}
+
+fn update_store(server_id: &str, f: impl FnOnce(&mut SessionStore)) {
+    let _guard = SESSION_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let result = load_store(server_id).and_then(|mut store| {
+        f(&mut store);
+        if store.sessions.len() > MAX_SESSIONS {
+            let excess = store.sessions.len() - MAX_SESSIONS;
+            store.sessions.drain(..excess);
+        }
+        save_store(server_id, &store)
+    });
+    if let Err(e) = result {
+        eprintln!("[Sessions] {}", e);
+    }
+}
+
+/// Opens a session. `online_count` is the number of players online after the join.
+pub fn record_join(server_id: &str, player: &str, online_count: usize) {
+    let now = Local::now();
+    update_store(server_id, |store| {
+        store.sessions.push(PlayerSession {
+            player: player.to_string(),
+            joined_at: now,
+            left_at: None,
+        });
+        if online_count as u32 > store.peak_concurrent {
+            store.peak_concurrent = online_count as u32;
+            store.peak_at = Some(now);
+        }
+    });
+}
+
+pub fn record_leave(server_id: &str, player: &str) {
+    let now = Local::now();
+    update_store(server_id, |store| {
+        if let Some(session) = store.sessions.iter_mut().rev()
+            .find(|s| s.player == player && s.left_at.is_none())
+        {
+            session.left_at = Some(now);
+        }
+    });
+}
+
+/// Closes every open session, used when the server process exits.
+pub fn close_open_sessions(server_id: &str) {
+    let now = Local::now();
+    update_store(server_id, |store| {
+        for session in store.sessions.iter_mut().filter(|s| s.left_at.is_none()) {
+            session.left_at = Some(now);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_player_stats(id: String) -> Result<ServerPlayerStats, MineServerError> {
+    let store = {
+        let _guard = SESSION_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        load_store(&id)?
+    };
+    let now = Local::now();
+
+    let mut by_player: HashMap<String, PlayerStats> = HashMap::new();
+    for session in &store.sessions {
+        let end = session.left_at.unwrap_or(now);
+        let duration = (end - session.joined_at).num_seconds().max(0);
+
+        let entry = by_player.entry(session.player.clone()).or_insert_with(|| PlayerStats {
+            player: session.player.clone(),
+            total_playtime_secs: 0,
+            session_count: 0,
+            first_seen: session.joined_at,
+            last_seen: end,
+            online: false,
+        });
+        entry.total_playtime_secs += duration;
+        entry.session_count += 1;
+        entry.first_seen = entry.first_seen.min(session.joined_at);
+        entry.last_seen = entry.last_seen.max(end);
+        entry.online |= session.left_at.is_none();
+    }
+
+    let mut players: Vec<PlayerStats> = by_player.into_values().collect();
+    players.sort_by(|a, b| b.total_playtime_secs.cmp(&a.total_playtime_secs));
+
+    Ok(ServerPlayerStats {
+        players,
+        total_sessions: store.sessions.len(),
+        peak_concurrent: store.peak_concurrent,
+        peak_at: store.peak_at,
+    })
+}
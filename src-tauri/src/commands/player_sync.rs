@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+use super::registry::{find_registered_server, RegisteredServer};
+use super::runner::{ServerProcessState, send_server_command_direct};
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Player list sync for proxy networks: whitelist.json, ops.json and banned-players.json of a source
+// server are copied to the other servers of a group (~/Mineserver/sync_groups.json). Stopped
+// targets get the file; running ones get the matching console commands, because a running server
+// keeps these lists in memory and overwrites the file with its own copy. Entries are matched by
+// UUID, falling back to the name.
+
+const SYNC_FILES: [&str; 3] = ["whitelist.json", "ops.json", "banned-players.json"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncGroup {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub source_server_id: String,
+    pub target_server_ids: Vec<String>,
+    #[serde(default = "default_files")]
+    pub files: Vec<String>, // Subset of SYNC_FILES
+    #[serde(default)]
+    pub last_sync: Option<String>,
+}
+
+fn default_files() -> Vec<String> {
+    SYNC_FILES.iter().map(|f| f.to_string()).collect()
+}
+
+/// Differences between the source list and one target's list.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDiff {
+    pub server_id: String,
+    pub server_name: String,
+    pub file: String,
+    pub added: Vec<String>, // Player names
+    pub removed: Vec<String>,
+    pub changed: Vec<String>, // Same player, different op level or ban reason
+}
+
+impl SyncDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResult {
+    pub diff: SyncDiff,
+    pub method: String, // "file", "console" or "none" (already in sync)
+    pub error: Option<String>,
+}
+
+fn get_groups_file() -> PathBuf {
+    app_data_dir().join("sync_groups.json")
+}
+
+fn load_groups() -> Result<Vec<SyncGroup>, String> {
+    let file = get_groups_file();
+    if !file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read sync groups: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse sync groups: {}", e))
+}
+
+fn save_groups(groups: &Vec<SyncGroup>) -> Result<(), String> {
+    let file = get_groups_file();
+    fs::create_dir_all(file.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let content = serde_json::to_string_pretty(groups)
+        .map_err(|e| format!("Failed to serialize sync groups: {}", e))?;
+    fs::write(&file, content)
+        .map_err(|e| format!("Failed to write sync groups: {}", e))
+}
+
+/// Entries of a player list file; a missing file is an empty list.
+fn read_list(server: &RegisteredServer, file: &str) -> Result<Vec<Value>, String> {
+    let path = Path::new(&server.path).join(file);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {} of {}: {}", file, server.name, e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {} of {}: {}", file, server.name, e))
+}
+
+fn entry_key(entry: &Value) -> String {
+    entry["uuid"].as_str()
+        .or_else(|| entry["name"].as_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn entry_name(entry: &Value) -> String {
+    entry["name"].as_str().or_else(|| entry["uuid"].as_str()).unwrap_or("?").to_string()
+}
+
+/// The fields that matter per list; ban dates and sources differ between servers by design.
+fn entry_settings(file: &str, entry: &Value) -> Value {
+    match file {
+        "ops.json" => serde_json::json!([entry["level"], entry["bypassesPlayerLimit"]]),
+        "banned-players.json" => serde_json::json!([entry["reason"], entry["expires"]]),
+        _ => Value::Null,
+    }
+}
+
+fn diff_lists(file: &str, source: &[Value], target: &[Value], server: &RegisteredServer) -> SyncDiff {
+    let find = |list: &[Value], key: &str| list.iter().find(|e| entry_key(e) == key).cloned();
+    let mut diff = SyncDiff {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        file: file.to_string(),
+        added: vec![],
+        removed: vec![],
+        changed: vec![],
+    };
+    for entry in source {
+        match find(target, &entry_key(entry)) {
+            None => diff.added.push(entry_name(entry)),
+            Some(existing) if entry_settings(file, &existing) != entry_settings(file, entry) => diff.changed.push(entry_name(entry)),
+            Some(_) => {},
+        }
+    }
+    for entry in target {
+        if find(source, &entry_key(entry)).is_none() {
+            diff.removed.push(entry_name(entry));
+        }
+    }
+    diff
+}
+
+/// Console commands that bring a running server's list in line. Op levels can't be set from the
+/// console, so a changed level only syncs while the target is stopped.
+fn console_commands(file: &str, diff: &SyncDiff, source: &[Value]) -> Vec<String> {
+    let reason = |name: &str| source.iter()
+        .find(|e| entry_name(e) == name)
+        .and_then(|e| e["reason"].as_str())
+        .filter(|r| !r.is_empty() && *r != "Banned by an operator.")
+        .map(|r| format!(" {}", r))
+        .unwrap_or_default();
+    match file {
+        "whitelist.json" => vec!["whitelist reload".to_string()], // The file is written first
+        "ops.json" => diff.added.iter().map(|n| format!("op {}", n))
+            .chain(diff.removed.iter().map(|n| format!("deop {}", n)))
+            .collect(),
+        "banned-players.json" => diff.added.iter().chain(diff.changed.iter()).map(|n| format!("ban {}{}", n, reason(n)))
+            .chain(diff.removed.iter().map(|n| format!("pardon {}", n)))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+struct ResolvedGroup {
+    source: RegisteredServer,
+    targets: Vec<RegisteredServer>,
+    files: Vec<String>,
+}
+
+fn resolve_group(group: &SyncGroup) -> Result<ResolvedGroup, String> {
+    let source = find_registered_server(&group.source_server_id)
+        .ok_or("The source server is not registered")?;
+    let targets = group.target_server_ids.iter()
+        .filter(|id| **id != group.source_server_id)
+        .map(|id| find_registered_server(id).ok_or_else(|| format!("Server {} is not registered", id)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let files = group.files.iter().filter(|f| SYNC_FILES.contains(&f.as_str())).cloned().collect();
+    Ok(ResolvedGroup { source, targets, files })
+}
+
+fn preview(group: &SyncGroup) -> Result<Vec<SyncDiff>, String> {
+    let resolved = resolve_group(group)?;
+    let mut diffs = Vec::new();
+    for file in &resolved.files {
+        let source = read_list(&resolved.source, file)?;
+        for target in &resolved.targets {
+            diffs.push(diff_lists(file, &source, &read_list(target, file)?, target));
+        }
+    }
+    Ok(diffs)
+}
+
+/// Applies the source lists to every target and records the sync time. Used by the command and
+/// by scheduled "player_sync" tasks.
+pub fn run_sync(state: &ServerProcessState, group_id: &str) -> Result<Vec<SyncResult>, String> {
+    let mut groups = load_groups()?;
+    let group = groups.iter_mut().find(|g| g.id == group_id)
+        .ok_or_else(|| format!("Sync group {} not found", group_id))?;
+    let resolved = resolve_group(group)?;
+    let running: Vec<String> = state.processes.lock()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let mut results = Vec::new();
+    for file in &resolved.files {
+        let source = read_list(&resolved.source, file)?;
+        let content = serde_json::to_string_pretty(&source).map_err(|e| e.to_string())?;
+        for target in &resolved.targets {
+            let diff = diff_lists(file, &source, &read_list(target, file)?, target);
+            if diff.is_empty() {
+                results.push(SyncResult { diff, method: "none".to_string(), error: None });
+                continue;
+            }
+            let is_running = running.contains(&target.id);
+            let write = || fs::write(Path::new(&target.path).join(file), &content)
+                .map_err(|e| format!("Failed to write {}: {}", file, e));
+            let outcome = if is_running {
+                let write_first = if file == "whitelist.json" { write() } else { Ok(()) };
+                write_first.and_then(|_| {
+                    console_commands(file, &diff, &source).into_iter()
+                        .try_for_each(|cmd| send_server_command_direct(state, target.id.clone(), cmd))
+                })
+            } else {
+                write()
+            };
+            results.push(SyncResult {
+                diff,
+                method: if is_running { "console" } else { "file" }.to_string(),
+                error: outcome.err(),
+            });
+        }
+    }
+
+    group.last_sync = Some(chrono::Local::now().to_rfc3339());
+    save_groups(&groups)?;
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn list_sync_groups() -> Result<Vec<SyncGroup>, MineServerError> {
+    load_groups().map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_sync_group(group: SyncGroup) -> Result<SyncGroup, MineServerError> {
+    let mut group = group;
+    if group.name.trim().is_empty() {
+        return Err(MineServerError::validation("Group name is required"));
+    }
+    if group.target_server_ids.iter().all(|id| *id == group.source_server_id) {
+        return Err(MineServerError::validation("Pick at least one server to sync to"));
+    }
+    if let Some(file) = group.files.iter().find(|f| !SYNC_FILES.contains(&f.as_str())) {
+        return Err(MineServerError::validation(format!("{} can't be synced", file)));
+    }
+    resolve_group(&group).map_err(MineServerError::validation)?;
+    if group.id.is_empty() {
+        group.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    // Saving an existing id updates it in place
+    let mut groups = load_groups()?;
+    if let Some(existing) = groups.iter_mut().find(|g| g.id == group.id) {
+        group.last_sync = existing.last_sync.clone();
+        *existing = group.clone();
+    } else {
+        groups.push(group.clone());
+    }
+
+    save_groups(&groups)?;
+    Ok(group)
+}
+
+#[tauri::command]
+pub async fn delete_sync_group(id: String) -> Result<(), MineServerError> {
+    let mut groups = load_groups()?;
+    groups.retain(|g| g.id != id);
+    save_groups(&groups).map_err(Into::into)
+}
+
+/// What `sync_player_lists` would change, per target and file. Targets already in sync are included
+/// with empty lists.
+#[tauri::command]
+pub async fn preview_player_sync(id: String) -> Result<Vec<SyncDiff>, MineServerError> {
+    let group = load_groups()?.into_iter().find(|g| g.id == id)
+        .ok_or_else(|| MineServerError::not_found("Sync group not found"))?;
+    tokio::task::spawn_blocking(move || preview(&group)).await?.map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn sync_player_lists(state: State<'_, ServerProcessState>, id: String) -> Result<Vec<SyncResult>, MineServerError> {
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || run_sync(&state, &id)).await?.map_err(Into::into)
+}
@@ -1,7 +1,23 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
-use reqwest::Client;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use futures_util::StreamExt;
+use tauri::{AppHandle, State};
+use super::http::HttpState;
+use super::activity::Activity;
+use super::backup::pre_update_backup;
+use super::operations::{Operation, OperationState};
 use serde::{Deserialize, Serialize};
+use super::audit::audited;
+use serde_json::json;
+use super::path_guard::validate_path;
+use super::runner::{ServerProcessState, running_server_roots};
+use super::registry::load_registry;
+use super::downloader::sponge_build;
+use super::error::MineServerError;
+
+const SPONGE_TYPES: [&str; 2] = ["spongevanilla", "spongeforge"];
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +26,39 @@ pub struct InstalledPlugin {
     pub filename: String,
     pub enabled: bool,
     pub size: u64,
+    pub staged_update: bool, // A jar with the same name waits in the update folder
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUpdateResult {
+    pub filename: String,
+    pub staged: bool, // Applied on the next restart rather than right away
+}
+
+// Bukkit/Paper update folder: on startup, jars in `plugins/update/` replace the plugin jar with the
+// same file name. Updates are staged there while the server runs, since the loaded jar can't be
+// swapped safely (or at all, on Windows). Paper also keeps remapped copies of plugins in
+// `plugins/.paper-remapped/`; it regenerates them itself, so that folder is never listed or touched.
+
+/// `plugins/<settings.update-folder>` from bukkit.yml, "update" by default.
+fn update_folder(server_root: &Path) -> std::path::PathBuf {
+    let name = fs::read_to_string(server_root.join("bukkit.yml")).ok()
+        .and_then(|yml| yml.lines()
+            .find_map(|l| l.trim().strip_prefix("update-folder:").map(|v| v.trim().trim_matches(['\'', '"']).to_string())))
+        .filter(|v| !v.is_empty() && !v.contains(['/', '\\']) && v != "..")
+        .unwrap_or_else(|| "update".to_string());
+    server_root.join("plugins").join(name)
+}
+
+/// Sponge loads plugins from mods/, everything else from plugins/. Servers are looked up in the
+/// registry by path.
+fn plugins_dir(server_root: &Path) -> PathBuf {
+    // Callers pass validated (canonical) paths, the registry keeps them as entered
+    let root = fs::canonicalize(server_root).unwrap_or_else(|_| server_root.to_path_buf());
+    let sponge = load_registry().unwrap_or_default().iter()
+        .any(|s| fs::canonicalize(&s.path).map(|p| p == root).unwrap_or(false) && SPONGE_TYPES.contains(&s.server_type.as_str()));
+    server_root.join(if sponge { "mods" } else { "plugins" })
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,15 +97,16 @@ pub struct ModrinthFile {
 }
 
 #[tauri::command]
-pub async fn list_plugins(server_path: String) -> Result<Vec<InstalledPlugin>, String> {
-    let plugins_dir = Path::new(&server_path).join("plugins");
+pub async fn list_plugins(server_path: String) -> Result<Vec<InstalledPlugin>, MineServerError> {
+    let plugins_dir = plugins_dir(Path::new(&server_path));
     
     if !plugins_dir.exists() {
         return Ok(vec![]);
     }
 
     let mut plugins = Vec::new();
-    
+    let staged = update_folder(Path::new(&server_path));
+
     let entries = fs::read_dir(&plugins_dir)
         .map_err(|e| format!("Failed to read plugins directory: {}", e))?;
 
@@ -72,10 +122,12 @@ pub async fn list_plugins(server_path: String) -> Result<Vec<InstalledPlugin>, S
 
             if filename.ends_with(".jar") {
                 let name = filename.trim_end_matches(".jar").to_string();
-                plugins.push(InstalledPlugin { name, filename, enabled: true, size });
+                let staged_update = staged.join(&filename).is_file();
+                plugins.push(InstalledPlugin { name, filename, enabled: true, size, staged_update });
             } else if filename.ends_with(".jar.disabled") {
                 let name = filename.trim_end_matches(".jar.disabled").to_string();
-                plugins.push(InstalledPlugin { name, filename, enabled: false, size });
+                let staged_update = staged.join(filename.trim_end_matches(".disabled")).is_file();
+                plugins.push(InstalledPlugin { name, filename, enabled: false, size, staged_update });
             }
         }
     }
@@ -84,8 +136,7 @@ pub async fn list_plugins(server_path: String) -> Result<Vec<InstalledPlugin>, S
 }
 
 #[tauri::command]
-pub async fn search_modrinth_plugins(query: String, offset: Option<u64>) -> Result<PaginatedResult<ModrinthHit>, String> {
-    let client = Client::new();
+pub async fn search_modrinth_plugins(http: State<'_, HttpState>, query: String, offset: Option<u64>) -> Result<PaginatedResult<ModrinthHit>, MineServerError> {
     let off = offset.unwrap_or(0);
     
     let url = format!(
@@ -94,15 +145,7 @@ pub async fn search_modrinth_plugins(query: String, offset: Option<u64>) -> Resu
         off
     );
 
-    let resp = client.get(&url)
-        .header("User-Agent", "Mineserver/1.0.0 (contact@mineserver.app)")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-
-    let search_result: ModrinthSearchResponse = resp.json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let search_result: ModrinthSearchResponse = http.get_json_cached(&url).await?;
 
     Ok(PaginatedResult {
         items: search_result.hits,
@@ -110,18 +153,68 @@ pub async fn search_modrinth_plugins(query: String, offset: Option<u64>) -> Resu
     })
 }
 
+/// Reads a download body chunk by chunk, reporting `operation-progress` and stopping on cancel.
+async fn read_with_progress(resp: reqwest::Response, op: &Operation, name: &str) -> Result<Vec<u8>, String> {
+    let total = resp.content_length().unwrap_or(0);
+    let mut bytes = Vec::with_capacity(total as usize);
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        op.token.check()?;
+        let chunk = chunk.map_err(|e| format!("Failed to read bytes: {}", e))?;
+        bytes.extend_from_slice(&chunk);
+        let percentage = if total > 0 { (bytes.len() as u64 * 100 / total) as u8 } else { 0 };
+        op.progress(percentage, bytes.len() as u64, format!("Downloading {}", name));
+    }
+    Ok(bytes)
+}
+
+/// Modrinth loaders a server platform can run, best match first, and the folder plugins go in.
+fn modrinth_plugin_target(platform: &str) -> Result<(&'static [&'static str], &'static str), MineServerError> {
+    match platform.to_lowercase().as_str() {
+        "paper" => Ok((&["paper", "spigot", "bukkit"], "plugins")),
+        "purpur" => Ok((&["purpur", "paper", "spigot", "bukkit"], "plugins")),
+        "folia" => Ok((&["folia"], "plugins")), // Regular Paper plugins don't run on Folia
+        "spigot" | "bukkit" => Ok((&["spigot", "bukkit"], "plugins")),
+        "velocity" => Ok((&["velocity"], "plugins")),
+        "bungeecord" | "bungee" | "waterfall" => Ok((&["bungeecord", "waterfall"], "plugins")),
+        "sponge" | "spongevanilla" | "spongeforge" => Ok((&["sponge"], "mods")),
+        "fabric" | "quilt" => Ok((&["fabric"], "mods")),
+        other => Err(MineServerError::validation(format!("Modrinth plugins can't be installed on {} servers", other))),
+    }
+}
+
+/// Installs the newest Modrinth version built for the server's platform (default Paper) and,
+/// when given, its game version. Fails with the project's supported loaders otherwise.
 #[tauri::command]
-pub async fn install_modrinth_plugin(project_id: String, server_path: String) -> Result<String, String> {
-    let client = Client::new();
+pub async fn install_modrinth_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    project_id: String,
+    server_path: String,
+    platform: Option<String>,
+    game_version: Option<String>,
+    operation_id: Option<String>,
+) -> Result<String, MineServerError> {
+    let client = http.client();
+    let platform = platform.unwrap_or_else(|| "paper".to_string());
+    let (loaders, folder) = modrinth_plugin_target(&platform)?;
+    // Proxy versions (Velocity 3.3.0, ...) aren't Minecraft versions
+    let game_version = game_version.filter(|_| !matches!(loaders[0], "velocity" | "bungeecord"));
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("plugin_install", &server_path, format!("Modrinth plugin {}", project_id));
     
     // Get latest version
-    let versions_url = format!(
-        "https://api.modrinth.com/v2/project/{}/version?loaders=[\"paper\",\"spigot\",\"bukkit\"]",
-        project_id
+    let mut versions_url = format!(
+        "https://api.modrinth.com/v2/project/{}/version?loaders={}",
+        project_id,
+        urlencoding::encode(&serde_json::to_string(loaders).unwrap_or_default())
     );
+    if let Some(version) = game_version.as_deref().filter(|v| !v.trim().is_empty()) {
+        versions_url.push_str(&format!("&game_versions={}", urlencoding::encode(&format!("[\"{}\"]", version))));
+    }
 
     let resp = client.get(&versions_url)
-        .header("User-Agent", "Mineserver/1.0.0 (contact@mineserver.app)")
         .send()
         .await
         .map_err(|e| format!("Request failed: {}", e))?;
@@ -130,8 +223,20 @@ pub async fn install_modrinth_plugin(project_id: String, server_path: String) ->
         .await
         .map_err(|e| format!("Failed to parse versions: {}", e))?;
 
-    let version = versions.first()
-        .ok_or("No compatible version found")?;
+    let Some(version) = versions.first() else {
+        // Say what the project does support instead of a bare "not found"
+        let project: serde_json::Value = client.get(format!("https://api.modrinth.com/v2/project/{}", project_id))
+            .send().await.map_err(|e| format!("Request failed: {}", e))?
+            .json().await.unwrap_or_default();
+        let supported: Vec<&str> = project["loaders"].as_array()
+            .map(|l| l.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let for_version = game_version.map(|v| format!(" for Minecraft {}", v)).unwrap_or_default();
+        return Err(MineServerError::validation(format!(
+            "This plugin has no {} build{}. Supported: {}",
+            platform, for_version, if supported.is_empty() { "unknown".to_string() } else { supported.join(", ") }
+        )));
+    };
 
     let file = version.files.iter()
         .find(|f| f.primary)
@@ -139,35 +244,41 @@ pub async fn install_modrinth_plugin(project_id: String, server_path: String) ->
         .ok_or("No file found for this version")?;
 
     // Download the jar
-    let jar_bytes = client.get(&file.url)
-        .header("User-Agent", "Mineserver/1.0.0 (contact@mineserver.app)")
+    let resp = client.get(&file.url)
         .send()
         .await
-        .map_err(|e| format!("Download failed: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read bytes: {}", e))?;
+        .map_err(|e| format!("Download failed: {}", e))?;
+    let jar_bytes = read_with_progress(resp, &op, &file.filename).await?;
 
     // Ensure plugins directory exists
-    let plugins_dir = Path::new(&server_path).join("plugins");
+    let plugins_dir = Path::new(&server_path).join(folder);
     fs::create_dir_all(&plugins_dir)
         .map_err(|e| format!("Failed to create plugins directory: {}", e))?;
 
     // Write the jar file
     let jar_path = plugins_dir.join(&file.filename);
-    fs::write(&jar_path, &jar_bytes)
+    tokio::fs::write(&jar_path, &jar_bytes).await
         .map_err(|e| format!("Failed to write plugin: {}", e))?;
 
+    activity.set_title(format!("Installed {}", file.filename));
+    activity.complete(Some(jar_bytes.len() as u64));
+    op.complete();
     Ok(file.filename.clone())
 }
 
+/// A bare file name, so a plugin name from the frontend can't point outside the plugins folder.
+fn is_plugin_file_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && name != "." && name != ".."
+}
+
 #[tauri::command]
-pub async fn toggle_plugin(server_path: String, filename: String) -> Result<String, String> {
-    let plugins_dir = Path::new(&server_path).join("plugins");
+pub async fn toggle_plugin(server_path: String, filename: String) -> Result<String, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let plugins_dir = plugins_dir(&root);
     let old_path = plugins_dir.join(&filename);
     
-    if !old_path.exists() {
-        return Err("File not found".to_string());
+    if !is_plugin_file_name(&filename) || !old_path.is_file() {
+        return Err(MineServerError::not_found("File not found"));
     }
 
     let new_filename = if filename.ends_with(".disabled") {
@@ -185,28 +296,100 @@ pub async fn toggle_plugin(server_path: String, filename: String) -> Result<Stri
 }
 
 #[tauri::command]
-pub async fn delete_plugin(server_path: String, filename: String) -> Result<(), String> {
-    let plugin_path = Path::new(&server_path).join("plugins").join(&filename);
+pub async fn delete_plugin(server_path: String, filename: String) -> Result<(), MineServerError> {
+    audited("delete_plugin", json!({ "serverPath": server_path, "filename": filename }), || {
+        let root = validate_path(&server_path)?;
+        let plugin_path = plugins_dir(&root).join(&filename);
     
-    if !plugin_path.exists() {
-        return Err("Plugin not found".to_string());
+        if !is_plugin_file_name(&filename) || !plugin_path.is_file() {
+            return Err("Plugin not found".to_string());
+        }
+
+        fs::remove_file(&plugin_path)
+            .map_err(|e| format!("Failed to delete plugin: {}", e))?;
+        // A staged update would otherwise install the plugin again on the next start
+        let _ = fs::remove_file(update_folder(&root).join(filename.trim_end_matches(".disabled")));
+
+        Ok(())
+    }).map_err(Into::into)
+}
+
+/// Replaces an installed plugin jar with the one at `download_url`, keeping its file name. With
+/// `stage` (the default while the server runs) the jar goes into the update folder instead and
+/// the server swaps it in on its next start.
+#[tauri::command]
+pub async fn update_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    state: State<'_, ServerProcessState>,
+    server_path: String,
+    filename: String,
+    download_url: String,
+    stage: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<PluginUpdateResult, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let plugins_dir = plugins_dir(&root);
+    let installed = plugins_dir.join(&filename);
+    if !is_plugin_file_name(&filename) || !installed.is_file() {
+        return Err(MineServerError::not_found("Plugin not found"));
+    }
+    let running = running_server_roots(state.inner()).contains(&root);
+    let staged = stage.unwrap_or(running);
+    if !staged && running {
+        return Err(MineServerError::conflict("Stop the server or stage the update for the next restart"));
     }
 
-    fs::remove_file(&plugin_path)
-        .map_err(|e| format!("Failed to delete plugin: {}", e))?;
+    let backup_id = pre_update_backup(app.clone(), ops.inner(), &server_path, "configs").await?;
+    let op = ops.begin(operation_id, "plugin_update")?.with_events(app);
+    let mut activity = Activity::begin("plugin_update", &server_path, format!("Update {}", filename));
+    activity.set_backup_id(backup_id);
+    let resp = http.client().get(&download_url).send().await?;
+    if !resp.status().is_success() {
+        return Err(MineServerError::external("Plugin download", format!("Download failed with status: {}", resp.status())));
+    }
+    let jar_bytes = read_with_progress(resp, &op, &filename).await?;
 
-    Ok(())
+    // Disabled plugins keep their `.disabled` suffix; the staged copy carries the jar name
+    let jar_name = filename.trim_end_matches(".disabled").to_string();
+    let result = audited("update_plugin", json!({ "serverPath": server_path, "filename": filename, "staged": staged }), || {
+        if staged {
+            let folder = update_folder(&root);
+            fs::create_dir_all(&folder).map_err(|e| format!("Failed to create update folder: {}", e))?;
+            return fs::write(folder.join(&jar_name), &jar_bytes).map_err(|e| format!("Failed to stage update: {}", e));
+        }
+        let temp = plugins_dir.join(format!("{}.part", jar_name));
+        fs::write(&temp, &jar_bytes).map_err(|e| format!("Failed to write plugin: {}", e))?;
+        fs::rename(&temp, &installed).map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            format!("Failed to replace plugin: {}", e)
+        })
+    });
+    if staged {
+        activity.set_title(format!("Staged update for {}", jar_name));
+    }
+    activity.finish(&result, Some(jar_bytes.len() as u64));
+    result?;
+    op.complete();
+    Ok(PluginUpdateResult { filename, staged })
+}
+
+/// Removes a staged update before the server picks it up.
+#[tauri::command]
+pub async fn cancel_staged_plugin_update(server_path: String, filename: String) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+    let staged = update_folder(&root).join(filename.trim_end_matches(".disabled"));
+    if !is_plugin_file_name(&filename) || !staged.is_file() {
+        return Err(MineServerError::not_found("No staged update for this plugin"));
+    }
+    fs::remove_file(&staged).map_err(|e| MineServerError::from(format!("Failed to remove staged update: {}", e)))
 }
 
 // --- Mod Support (for Forge/Fabric) ---
 
 #[tauri::command]
-pub async fn search_modrinth_mods(query: String, loader: String, offset: Option<u64>) -> Result<PaginatedResult<ModrinthHit>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0 (contact@mineserver.app)")
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+pub async fn search_modrinth_mods(http: State<'_, HttpState>, query: String, loader: String, offset: Option<u64>) -> Result<PaginatedResult<ModrinthHit>, MineServerError> {
     let off = offset.unwrap_or(0);
     // For mods, we filter by project_type:mod and the loader (forge or fabric)
     let facets = format!("[[\"project_type:mod\"],[\"categories:{}\"]]", loader);
@@ -217,8 +400,7 @@ pub async fn search_modrinth_mods(query: String, loader: String, offset: Option<
         off
     );
     
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let search_result: ModrinthSearchResponse = resp.json().await.map_err(|e| e.to_string())?;
+    let search_result: ModrinthSearchResponse = http.get_json_cached(&url).await?;
     
     Ok(PaginatedResult {
         items: search_result.hits,
@@ -228,15 +410,18 @@ pub async fn search_modrinth_mods(query: String, loader: String, offset: Option<
 
 #[tauri::command]
 pub async fn install_modrinth_mod(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
     project_id: String, 
     server_path: String,
     loader: String,
     game_version: String,
-) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let client = http.client();
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("mod_install", &server_path, format!("Modrinth mod {}", project_id));
     
     // Fetch versions for this loader and game version
     let versions_url = format!(
@@ -260,10 +445,13 @@ pub async fn install_modrinth_mod(
     
     let jar_path = mods_dir.join(&file.filename);
     let jar_resp = client.get(&file.url).send().await.map_err(|e| e.to_string())?;
-    let jar_bytes = jar_resp.bytes().await.map_err(|e| e.to_string())?;
+    let jar_bytes = read_with_progress(jar_resp, &op, &file.filename).await?;
     
-    fs::write(&jar_path, &jar_bytes).map_err(|e| e.to_string())?;
+    tokio::fs::write(&jar_path, &jar_bytes).await.map_err(|e| e.to_string())?;
     
+    activity.set_title(format!("Installed {}", file.filename));
+    activity.complete(Some(jar_bytes.len() as u64));
+    op.complete();
     Ok(())
 }
 
@@ -307,19 +495,13 @@ struct HangarStats {
 }
 
 #[tauri::command]
-pub async fn search_hangar_plugins(query: String) -> Result<Vec<HangarPlugin>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+pub async fn search_hangar_plugins(http: State<'_, HttpState>, query: String) -> Result<Vec<HangarPlugin>, MineServerError> {
     let url = format!(
         "https://hangar.papermc.io/api/v1/projects?q={}&limit=20",
         urlencoding::encode(&query)
     );
     
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let search: HangarSearchResponse = resp.json().await.map_err(|e| e.to_string())?;
+    let search: HangarSearchResponse = http.get_json_cached(&url).await?;
     
     let plugins: Vec<HangarPlugin> = search.result.into_iter().map(|p| HangarPlugin {
         id: p.namespace.slug.clone(),
@@ -334,38 +516,110 @@ pub async fn search_hangar_plugins(query: String) -> Result<Vec<HangarPlugin>, S
     Ok(plugins)
 }
 
+/// Hangar platform for a Hangar platform name or a server type ("purpur" -> PAPER).
+fn hangar_platform(platform: &str) -> Result<&'static str, MineServerError> {
+    match platform.to_lowercase().as_str() {
+        "paper" | "purpur" | "folia" | "spigot" | "bukkit" => Ok("PAPER"),
+        "waterfall" | "bungee" | "bungeecord" => Ok("WATERFALL"),
+        "velocity" => Ok("VELOCITY"),
+        other => Err(MineServerError::validation(format!("Hangar has no plugins for {}", other))),
+    }
+}
+
+/// Platforms a Hangar version has downloads for, e.g. ["PAPER", "VELOCITY"].
+fn hangar_version_platforms(version: &serde_json::Value) -> Vec<String> {
+    version["downloads"].as_object()
+        .map(|d| d.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Installs a Hangar plugin. `platform` (PAPER, WATERFALL, VELOCITY or a server type) defaults
+/// to PAPER; without `version`, the newest version on `channel` (any channel if unset) that
+/// has a download for that platform is installed.
 #[tauri::command]
-pub async fn install_hangar_plugin(slug: String, server_path: String) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    // Get latest version
-    let versions_url = format!("https://hangar.papermc.io/api/v1/projects/{}/versions?limit=1", slug);
-    let versions_resp = client.get(&versions_url).send().await.map_err(|e| e.to_string())?;
-    let versions: serde_json::Value = versions_resp.json().await.map_err(|e| e.to_string())?;
-    
-    let version_name = versions["result"][0]["name"].as_str().ok_or("No version found")?;
-    
-    // Download PAPER platform jar
-    let download_url = format!(
-        "https://hangar.papermc.io/api/v1/projects/{}/versions/{}/PAPER/download",
-        slug, version_name
-    );
-    
+pub async fn install_hangar_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    slug: String,
+    server_path: String,
+    platform: Option<String>,
+    channel: Option<String>,
+    version: Option<String>,
+    operation_id: Option<String>,
+) -> Result<String, MineServerError> {
+    let client = http.client();
+    let platform = hangar_platform(platform.as_deref().unwrap_or("PAPER"))?;
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("plugin_install", &server_path, format!("Hangar plugin {}", slug));
+
+    let version: serde_json::Value = match version.filter(|v| !v.trim().is_empty()) {
+        Some(name) => {
+            let url = format!("https://hangar.papermc.io/api/v1/projects/{}/versions/{}", slug, urlencoding::encode(&name));
+            let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
+            if !resp.status().is_success() {
+                return Err(MineServerError::not_found(format!("Version {} of {} not found on Hangar", name, slug)));
+            }
+            resp.json().await.map_err(|e| e.to_string())?
+        },
+        None => {
+            let url = format!("https://hangar.papermc.io/api/v1/projects/{}/versions?limit=25&platform={}", slug, platform);
+            let versions: serde_json::Value = client.get(&url).send().await.map_err(|e| e.to_string())?
+                .json().await.map_err(|e| e.to_string())?;
+            versions["result"].as_array()
+                .and_then(|list| list.iter().find(|v| {
+                    let on_channel = channel.as_deref()
+                        .map(|c| v["channel"]["name"].as_str().map(|n| n.eq_ignore_ascii_case(c)).unwrap_or(false))
+                        .unwrap_or(true);
+                    on_channel && !v["downloads"][platform].is_null()
+                }))
+                .cloned()
+                .ok_or_else(|| MineServerError::not_found(match &channel {
+                    Some(c) => format!("No {} version of {} on the {} channel", platform, slug, c),
+                    None => format!("No {} version of {} found", platform, slug),
+                }))?
+        },
+    };
+
+    let version_name = version["name"].as_str().ok_or("No version found")?;
+    let download = &version["downloads"][platform];
+    if download.is_null() {
+        return Err(MineServerError::validation(format!(
+            "{} {} has no {} download (available: {})",
+            slug, version_name, platform, hangar_version_platforms(&version).join(", ")
+        )));
+    }
+
+    // External downloads (GitHub releases, ...) are linked instead of hosted on Hangar
+    let download_url = download["downloadUrl"].as_str()
+        .or_else(|| download["externalUrl"].as_str())
+        .map(String::from)
+        .unwrap_or_else(|| format!(
+            "https://hangar.papermc.io/api/v1/projects/{}/versions/{}/{}/download",
+            slug, version_name, platform
+        ));
+
     let jar_resp = client.get(&download_url).send().await.map_err(|e| e.to_string())?;
-    let jar_bytes = jar_resp.bytes().await.map_err(|e| e.to_string())?;
+    if !jar_resp.status().is_success() {
+        return Err(MineServerError::external("Hangar", format!("Download failed with status: {}", jar_resp.status())));
+    }
+    let jar_bytes = read_with_progress(jar_resp, &op, &slug).await?;
     
     // Save to plugins folder
     let plugins_dir = Path::new(&server_path).join("plugins");
     fs::create_dir_all(&plugins_dir).map_err(|e| e.to_string())?;
     
-    let filename = slug.split('/').last().unwrap_or("plugin");
-    let jar_path = plugins_dir.join(format!("{}.jar", filename));
-    fs::write(&jar_path, &jar_bytes).map_err(|e| e.to_string())?;
+    let filename = download["fileInfo"]["name"].as_str()
+        .filter(|n| n.ends_with(".jar") && !n.contains(['/', '\\']))
+        .map(String::from)
+        .unwrap_or_else(|| format!("{}.jar", slug.split('/').last().unwrap_or("plugin")));
+    let jar_path = plugins_dir.join(&filename);
+    tokio::fs::write(&jar_path, &jar_bytes).await.map_err(|e| e.to_string())?;
     
-    Ok(())
+    activity.set_title(format!("Installed {} {} from Hangar", slug, version_name));
+    activity.complete(Some(jar_bytes.len() as u64));
+    op.complete();
+    Ok(filename)
 }
 
 // --- Spigot Support ---
@@ -383,12 +637,7 @@ pub struct SpigotPlugin {
 }
 
 #[tauri::command]
-pub async fn search_spigot_plugins(query: String, page: Option<u32>) -> Result<Vec<SpigotPlugin>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+pub async fn search_spigot_plugins(http: State<'_, HttpState>, query: String, page: Option<u32>) -> Result<Vec<SpigotPlugin>, MineServerError> {
     let page_num = page.unwrap_or(1);
     
     // Spiget.org API - use different endpoint for empty query
@@ -406,8 +655,7 @@ pub async fn search_spigot_plugins(query: String, page: Option<u32>) -> Result<V
         )
     };
     
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let resources: Vec<serde_json::Value> = resp.json().await.unwrap_or_default();
+    let resources: Vec<serde_json::Value> = http.get_json_cached(&url).await.unwrap_or_default();
     
     let plugins: Vec<SpigotPlugin> = resources.into_iter().filter_map(|r| {
         Some(SpigotPlugin {
@@ -425,11 +673,17 @@ pub async fn search_spigot_plugins(query: String, page: Option<u32>) -> Result<V
 }
 
 #[tauri::command]
-pub async fn install_spigot_plugin(resource_id: String, server_path: String) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn install_spigot_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    resource_id: String,
+    server_path: String,
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let client = http.client();
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("plugin_install", &server_path, format!("Spigot resource {}", resource_id));
     
     // Get resource info
     let info_url = format!("https://api.spiget.org/v2/resources/{}", resource_id);
@@ -440,18 +694,188 @@ pub async fn install_spigot_plugin(resource_id: String, server_path: String) ->
     // Download
     let download_url = format!("https://api.spiget.org/v2/resources/{}/download", resource_id);
     let jar_resp = client.get(&download_url).send().await.map_err(|e| e.to_string())?;
-    let jar_bytes = jar_resp.bytes().await.map_err(|e| e.to_string())?;
+    let jar_bytes = read_with_progress(jar_resp, &op, name).await?;
     
     // Save
     let plugins_dir = Path::new(&server_path).join("plugins");
     fs::create_dir_all(&plugins_dir).map_err(|e| e.to_string())?;
     
     let jar_path = plugins_dir.join(format!("{}.jar", name.replace(" ", "-")));
-    fs::write(&jar_path, &jar_bytes).map_err(|e| e.to_string())?;
+    tokio::fs::write(&jar_path, &jar_bytes).await.map_err(|e| e.to_string())?;
     
+    activity.set_title(format!("Installed {} from SpigotMC", name));
+    activity.complete(Some(jar_bytes.len() as u64));
+    op.complete();
     Ok(())
 }
 
+// --- Ore Support (Sponge) ---
+// Ore wants a session even for anonymous requests. Public sessions last a few hours; one is
+// reused for an hour.
+
+const ORE_API: &str = "https://ore.spongepowered.org/api/v2";
+const ORE_SESSION_TTL: Duration = Duration::from_secs(60 * 60);
+static ORE_SESSION: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OrePlugin {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub downloads: u64,
+    pub icon_url: Option<String>,
+    pub source: String,
+}
+
+async fn ore_session(client: &reqwest::Client) -> Result<String, String> {
+    if let Some((session, created)) = ORE_SESSION.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+        if created.elapsed() < ORE_SESSION_TTL {
+            return Ok(session);
+        }
+    }
+    let resp = client.post(format!("{}/authenticate", ORE_API))
+        .send().await.map_err(|e| format!("Ore is unreachable: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Ore refused a session: {}", resp.status()));
+    }
+    let body: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let session = body["session"].as_str().ok_or("Ore returned no session")?.to_string();
+    *ORE_SESSION.lock().unwrap_or_else(|e| e.into_inner()) = Some((session.clone(), Instant::now()));
+    Ok(session)
+}
+
+async fn ore_get(client: &reqwest::Client, url: &str) -> Result<reqwest::Response, String> {
+    let session = ore_session(client).await?;
+    let resp = client.get(url)
+        .header(reqwest::header::AUTHORIZATION, format!("OreApi session=\"{}\"", session))
+        .send().await.map_err(|e| e.to_string())?;
+    if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+        // Expired early; the next request authenticates again
+        *ORE_SESSION.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        return Err("The Ore session expired, try again".to_string());
+    }
+    Ok(resp)
+}
+
+#[tauri::command]
+pub async fn search_ore_plugins(http: State<'_, HttpState>, query: String, offset: Option<u64>) -> Result<PaginatedResult<OrePlugin>, MineServerError> {
+    let client = http.client();
+    let url = format!(
+        "{}/projects?q={}&limit=20&offset={}",
+        ORE_API, urlencoding::encode(&query), offset.unwrap_or(0)
+    );
+    let resp = ore_get(&client, &url).await.map_err(|e| MineServerError::external("Ore", e))?;
+    if !resp.status().is_success() {
+        return Err(MineServerError::external("Ore", format!("Search failed with status: {}", resp.status())));
+    }
+    let body: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+
+    let items = body["result"].as_array().map(|projects| projects.iter().filter_map(|p| {
+        Some(OrePlugin {
+            id: p["plugin_id"].as_str()?.to_string(),
+            slug: format!("{}/{}", p["namespace"]["owner"].as_str()?, p["namespace"]["slug"].as_str()?),
+            title: p["name"].as_str()?.to_string(),
+            description: p["description"].as_str().unwrap_or("").to_string(),
+            downloads: p["stats"]["downloads"].as_u64().unwrap_or(0),
+            icon_url: p["icon_url"].as_str().map(String::from),
+            source: "ore".to_string(),
+        })
+    }).collect()).unwrap_or_default();
+
+    Ok(PaginatedResult {
+        items,
+        total: body["pagination"]["count"].as_u64().unwrap_or(0),
+    })
+}
+
+/// SpongeAPI versions an Ore version was built for, e.g. ["8.0.0"].
+fn ore_api_versions(version: &serde_json::Value) -> Vec<String> {
+    version["tags"]["platforms"].as_array()
+        .map(|platforms| platforms.iter()
+            .filter(|p| p["platform"] == "spongeapi")
+            .filter_map(|p| p["platform_version"].as_str().map(String::from))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Installs the newest version of an Ore plugin into mods/. With `game_version` (and
+/// `platform`, "spongevanilla" by default), only versions built for the SpongeAPI major of the
+/// server's Sponge build qualify, since plugins break across API majors.
+#[tauri::command]
+pub async fn install_ore_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    plugin_id: String,
+    server_path: String,
+    platform: Option<String>,
+    game_version: Option<String>,
+    operation_id: Option<String>,
+) -> Result<String, MineServerError> {
+    let client = http.client();
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("plugin_install", &server_path, format!("Ore plugin {}", plugin_id));
+
+    let artifact = platform.filter(|p| SPONGE_TYPES.contains(&p.as_str())).unwrap_or_else(|| "spongevanilla".to_string());
+    let api_major = match game_version.filter(|v| !v.trim().is_empty()) {
+        Some(version) => sponge_build(&client, &artifact, &version).await.ok()
+            .and_then(|build| build.api)
+            .and_then(|api| api.split('.').next().map(String::from)),
+        None => None,
+    };
+
+    let url = format!("{}/projects/{}/versions?limit=25", ORE_API, urlencoding::encode(&plugin_id));
+    let resp = ore_get(&client, &url).await.map_err(|e| MineServerError::external("Ore", e))?;
+    if !resp.status().is_success() {
+        return Err(MineServerError::not_found(format!("{} was not found on Ore", plugin_id)));
+    }
+    let versions: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let versions = versions["result"].as_array().cloned().unwrap_or_default();
+    let version = versions.iter()
+        .find(|v| match &api_major {
+            Some(major) => ore_api_versions(v).iter().any(|api| api.split('.').next() == Some(major)),
+            None => true,
+        })
+        .ok_or_else(|| {
+            let mut supported: Vec<String> = versions.iter().flat_map(ore_api_versions).collect();
+            supported.dedup();
+            MineServerError::validation(match &api_major {
+                Some(major) => format!(
+                    "{} has no version for SpongeAPI {}. Available: {}",
+                    plugin_id, major, if supported.is_empty() { "none".to_string() } else { supported.join(", ") }
+                ),
+                None => format!("{} has no versions on Ore", plugin_id),
+            })
+        })?;
+    let version_name = version["name"].as_str().ok_or("No version found")?;
+
+    let download_url = format!("{}/projects/{}/versions/{}/download", ORE_API, urlencoding::encode(&plugin_id), urlencoding::encode(version_name));
+    let jar_resp = ore_get(&client, &download_url).await.map_err(|e| MineServerError::external("Ore", e))?;
+    if !jar_resp.status().is_success() {
+        return Err(MineServerError::external("Ore", format!("Download failed with status: {}", jar_resp.status())));
+    }
+    let jar_bytes = read_with_progress(jar_resp, &op, &plugin_id).await?;
+    if !jar_bytes.starts_with(b"PK") {
+        return Err(MineServerError::external("Ore", format!("{} {} is not a jar file", plugin_id, version_name)));
+    }
+
+    // Sponge loads plugins from mods/
+    let mods_dir = Path::new(&server_path).join("mods");
+    fs::create_dir_all(&mods_dir).map_err(|e| format!("Failed to create mods directory: {}", e))?;
+    let filename = version["file_info"]["name"].as_str()
+        .filter(|n| n.ends_with(".jar") && !n.contains(['/', '\\']))
+        .map(String::from)
+        .unwrap_or_else(|| format!("{}-{}.jar", plugin_id, version_name));
+    tokio::fs::write(mods_dir.join(&filename), &jar_bytes).await
+        .map_err(|e| format!("Failed to write plugin: {}", e))?;
+
+    activity.set_title(format!("Installed {} {} from Ore", plugin_id, version_name));
+    activity.complete(Some(jar_bytes.len() as u64));
+    op.complete();
+    Ok(filename)
+}
+
 // --- Poggit Support (PocketMine) ---
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -466,12 +890,7 @@ pub struct PoggitPlugin {
 }
 
 #[tauri::command]
-pub async fn search_poggit_plugins(query: String) -> Result<Vec<PoggitPlugin>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+pub async fn search_poggit_plugins(http: State<'_, HttpState>, query: String) -> Result<Vec<PoggitPlugin>, MineServerError> {
     // Poggit API
     let url = if query.is_empty() {
         "https://poggit.pmmp.io/releases.json?top".to_string()
@@ -479,8 +898,7 @@ pub async fn search_poggit_plugins(query: String) -> Result<Vec<PoggitPlugin>, S
         format!("https://poggit.pmmp.io/releases.json?name={}", urlencoding::encode(&query))
     };
     
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let releases: Vec<serde_json::Value> = resp.json().await.map_err(|e| e.to_string())?;
+    let releases: Vec<serde_json::Value> = http.get_json_cached(&url).await?;
     
     let plugins: Vec<PoggitPlugin> = releases.into_iter().take(20).filter_map(|r| {
         Some(PoggitPlugin {
@@ -498,16 +916,21 @@ pub async fn search_poggit_plugins(query: String) -> Result<Vec<PoggitPlugin>, S
 }
 
 #[tauri::command]
-pub async fn install_poggit_plugin(plugin_name: String, server_path: String) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn install_poggit_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    plugin_name: String,
+    server_path: String,
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let client = http.client();
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("plugin_install", &server_path, format!("Poggit plugin {}", plugin_name));
     
     // Get plugin info
     let url = format!("https://poggit.pmmp.io/releases.json?name={}", urlencoding::encode(&plugin_name));
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let releases: Vec<serde_json::Value> = resp.json().await.map_err(|e| e.to_string())?;
+    let releases: Vec<serde_json::Value> = http.get_json_cached(&url).await?;
     
     let release = releases.first().ok_or("Plugin not found")?;
     let artifact_url = release["artifact_url"].as_str().ok_or("No download URL")?;
@@ -515,15 +938,18 @@ pub async fn install_poggit_plugin(plugin_name: String, server_path: String) ->
     
     // Download phar
     let phar_resp = client.get(artifact_url).send().await.map_err(|e| e.to_string())?;
-    let phar_bytes = phar_resp.bytes().await.map_err(|e| e.to_string())?;
+    let phar_bytes = read_with_progress(phar_resp, &op, name).await?;
     
     // Save to plugins folder
     let plugins_dir = Path::new(&server_path).join("plugins");
     fs::create_dir_all(&plugins_dir).map_err(|e| e.to_string())?;
     
     let phar_path = plugins_dir.join(format!("{}.phar", name));
-    fs::write(&phar_path, &phar_bytes).map_err(|e| e.to_string())?;
+    tokio::fs::write(&phar_path, &phar_bytes).await.map_err(|e| e.to_string())?;
     
+    activity.set_title(format!("Installed {} from Poggit", name));
+    activity.complete(Some(phar_bytes.len() as u64));
+    op.complete();
     Ok(())
 }
 
@@ -531,11 +957,8 @@ pub async fn install_poggit_plugin(plugin_name: String, server_path: String) ->
 // Note: CurseForge requires API key, using fallback
 
 #[tauri::command]
-pub async fn search_curseforge_plugins(query: String, page: Option<u32>) -> Result<Vec<SpigotPlugin>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn search_curseforge_plugins(http: State<'_, HttpState>, query: String, page: Option<u32>) -> Result<Vec<SpigotPlugin>, MineServerError> {
+    let client = http.client();
     
     // CurseForge API requires key, using public search
     let _page = page.unwrap_or(1);
@@ -563,17 +986,15 @@ pub async fn search_curseforge_plugins(query: String, page: Option<u32>) -> Resu
 // --- Polymart Support ---
 
 #[tauri::command]
-pub async fn search_polymart_plugins(query: String, page: Option<u32>) -> Result<Vec<SpigotPlugin>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn search_polymart_plugins(http: State<'_, HttpState>, query: String, page: Option<u32>) -> Result<Vec<SpigotPlugin>, MineServerError> {
+    let client = http.client();
     
     let page_num = page.unwrap_or(1);
     
-    // Polymart uses POST requests
+    // Polymart uses POST requests, so only the rate limit applies (no caching)
     let url = "https://api.polymart.org/v1/search";
     
+    http.throttle(url).await;
     let resp = client.post(url)
         .form(&[
             ("query", query.as_str()),
@@ -621,17 +1042,23 @@ pub async fn search_polymart_plugins(query: String, page: Option<u32>) -> Result
 }
 
 #[tauri::command]
-pub async fn install_polymart_plugin(resource_id: String, server_path: String) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn install_polymart_plugin(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    resource_id: String,
+    server_path: String,
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let client = http.client();
+    let op = ops.begin(operation_id, "plugin_install")?.with_events(app);
+    let mut activity = Activity::begin("plugin_install", &server_path, format!("Polymart resource {}", resource_id));
         
     let download_url = format!("https://polymart.org/resource/{}/download", resource_id);
-    let resp = client.get(&download_url).send().await.map_err(|e| e.to_string())?;
+    let resp = client.get(&download_url).send().await?;
     
     if !resp.status().is_success() {
-        return Err(format!("Download failed: HTTP {}", resp.status()));
+        return Err(MineServerError::external("Polymart", format!("Download failed: HTTP {}", resp.status())));
     }
     
     // Try to infer filename from header
@@ -647,19 +1074,22 @@ pub async fn install_polymart_plugin(resource_id: String, server_path: String) -
         })
         .unwrap_or_else(|| format!("polymart-{}.jar", resource_id));
 
-    let jar_bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+    let jar_bytes = read_with_progress(resp, &op, &filename).await?;
     
     // Check if HTML (login wall)
     if jar_bytes.starts_with(b"<!DOCTYPE html") || jar_bytes.starts_with(b"<html") {
-         return Err("Failed to download: Plugin requires login or is paid.".to_string());
+         return Err(MineServerError::external("Spiget", "Failed to download: Plugin requires login or is paid."));
     }
 
     let plugins_dir = Path::new(&server_path).join("plugins");
     fs::create_dir_all(&plugins_dir).map_err(|e| e.to_string())?;
     
     let jar_path = plugins_dir.join(&filename);
-    fs::write(&jar_path, &jar_bytes).map_err(|e| e.to_string())?;
+    tokio::fs::write(&jar_path, &jar_bytes).await.map_err(|e| e.to_string())?;
     
+    activity.set_title(format!("Installed {} from Polymart", filename));
+    activity.complete(Some(jar_bytes.len() as u64));
+    op.complete();
     Ok(())
 }
 
@@ -681,12 +1111,7 @@ pub struct VersionInfo {
 }
 
 #[tauri::command]
-pub async fn get_plugin_versions(source: String, project_id: String, slug: String) -> Result<Vec<VersionInfo>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mineserver/1.0.0")
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+pub async fn get_plugin_versions(http: State<'_, HttpState>, source: String, project_id: String, slug: String) -> Result<Vec<VersionInfo>, MineServerError> {
     match source.as_str() {
         "polymart" => {
             Ok(vec![VersionInfo {
@@ -701,8 +1126,7 @@ pub async fn get_plugin_versions(source: String, project_id: String, slug: Strin
         },
         "modrinth" => {
             let url = format!("https://api.modrinth.com/v2/project/{}/version", project_id);
-            let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-            let versions: Vec<serde_json::Value> = resp.json().await.map_err(|e| e.to_string())?;
+            let versions: Vec<serde_json::Value> = http.get_json_cached(&url).await?;
             
             let result: Vec<VersionInfo> = versions.into_iter().take(10).filter_map(|v| {
                 Some(VersionInfo {
@@ -720,22 +1144,27 @@ pub async fn get_plugin_versions(source: String, project_id: String, slug: Strin
         },
         "hangar" => {
             let url = format!("https://hangar.papermc.io/api/v1/projects/{}/versions?limit=10", slug);
-            let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-            let data: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+            let data: serde_json::Value = http.get_json_cached(&url).await?;
             
             let versions = data["result"].as_array().map(|a| a.to_vec()).unwrap_or_default();
             let result: Vec<VersionInfo> = versions.into_iter().filter_map(|v| {
                 let name = v["name"].as_str()?.to_string();
-                let game_versions: Vec<String> = v["platformDependencies"]["PAPER"]
-                    .as_array()
-                    .map(|arr| arr.iter().filter_map(|g| g.as_str().map(|s| s.to_string())).collect())
-                    .unwrap_or_default();
+                // Loaders are the Hangar platforms this version ships for
+                let platforms = hangar_version_platforms(&v);
+                let mut game_versions: Vec<String> = Vec::new();
+                for platform in &platforms {
+                    for g in v["platformDependencies"][platform].as_array().into_iter().flatten().filter_map(|g| g.as_str()) {
+                        if !game_versions.iter().any(|x| x == g) {
+                            game_versions.push(g.to_string());
+                        }
+                    }
+                }
                 
                 Some(VersionInfo {
                     id: name.clone(),
                     name,
                     game_versions,
-                    loaders: vec!["paper".to_string(), "spigot".to_string()],
+                    loaders: platforms.iter().map(|p| p.to_lowercase()).collect(),
                     download_url: "".to_string(),
                     date_published: v["createdAt"].as_str().unwrap_or("").to_string(),
                     version_type: v["channel"]["name"].as_str().unwrap_or("Release").to_string(),
@@ -747,8 +1176,7 @@ pub async fn get_plugin_versions(source: String, project_id: String, slug: Strin
         "spigot" => {
             // Spiget versions
             let url = format!("https://api.spiget.org/v2/resources/{}/versions?size=10", project_id);
-            let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-            let versions: Vec<serde_json::Value> = resp.json().await.unwrap_or_default();
+            let versions: Vec<serde_json::Value> = http.get_json_cached(&url).await.unwrap_or_default();
             
             let result: Vec<VersionInfo> = versions.into_iter().filter_map(|v| {
                 Some(VersionInfo {
@@ -0,0 +1,161 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use super::runner::ServerEventSink;
+use super::settings::load_settings;
+
+// Battery awareness for servers hosted on laptops. Power state comes from sysfs on Linux,
+// `pmset` on macOS and Win32_Battery on Windows, and is cached briefly because the Windows
+// query spawns PowerShell.
+//
+// Policies (settings): `pauseOnBattery` skips auto-restarts and scheduled tasks while unplugged,
+// `warnOnBatteryStart` emits `power-warning` when a server is started unplugged.
+
+const CACHE_TTL: Duration = Duration::from_secs(20);
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+static CACHE: Mutex<Option<(Instant, PowerState)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerState {
+    pub has_battery: bool,
+    pub on_battery: bool, // Running from the battery (unplugged)
+    pub percentage: Option<u8>,
+    pub charging: bool,
+}
+
+#[cfg(target_os = "linux")]
+fn query_power_state() -> PowerState {
+    let mut state = PowerState::default();
+    let mut ac_online: Option<bool> = None;
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else { return state };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let read = |name: &str| std::fs::read_to_string(path.join(name)).map(|s| s.trim().to_string()).ok();
+        match read("type").as_deref() {
+            Some("Battery") if read("scope").as_deref() != Some("Device") => {
+                state.has_battery = true;
+                state.percentage = read("capacity").and_then(|c| c.parse().ok());
+                let status = read("status").unwrap_or_default();
+                state.charging = status == "Charging";
+                state.on_battery = status == "Discharging";
+            }
+            Some("Mains") => {
+                ac_online = Some(ac_online.unwrap_or(false) || read("online").as_deref() == Some("1"));
+            }
+            _ => {}
+        }
+    }
+    // "Not charging"/"Unknown" batteries are on AC if an adapter says so
+    if let Some(online) = ac_online {
+        state.on_battery = state.has_battery && !online;
+    }
+    state
+}
+
+#[cfg(target_os = "macos")]
+fn query_power_state() -> PowerState {
+    // "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=...)	85%; discharging; ..."
+    let Ok(output) = std::process::Command::new("pmset").args(["-g", "batt"]).output() else {
+        return PowerState::default();
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let Some(line) = text.lines().find(|l| l.contains("InternalBattery")) else {
+        return PowerState::default();
+    };
+    PowerState {
+        has_battery: true,
+        on_battery: text.contains("'Battery Power'"),
+        percentage: line.split_whitespace()
+            .find_map(|w| w.strip_suffix("%;"))
+            .and_then(|p| p.parse().ok()),
+        charging: line.contains("; charging;"),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn query_power_state() -> PowerState {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    // BatteryStatus: 1 = discharging, 2 = on AC, 6-9 = charging
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile", "-Command",
+            "Get-CimInstance Win32_Battery | Select-Object -First 1 BatteryStatus, EstimatedChargeRemaining | ConvertTo-Json",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    let Ok(output) = output else { return PowerState::default() };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return PowerState::default(); // No battery: empty output
+    };
+    let status = json["BatteryStatus"].as_u64().unwrap_or(2);
+    PowerState {
+        has_battery: true,
+        on_battery: status == 1,
+        percentage: json["EstimatedChargeRemaining"].as_u64().map(|p| p.min(100) as u8),
+        charging: (6..=9).contains(&status),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn query_power_state() -> PowerState {
+    PowerState::default()
+}
+
+/// Current power state, at most `CACHE_TTL` old.
+pub fn current_power_state() -> PowerState {
+    if let Ok(cache) = CACHE.lock() {
+        if let Some((_, state)) = cache.as_ref().filter(|(at, _)| at.elapsed() < CACHE_TTL) {
+            return state.clone();
+        }
+    }
+    let state = query_power_state();
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = Some((Instant::now(), state.clone()));
+    }
+    state
+}
+
+/// True when `pauseOnBattery` is on and the machine is unplugged.
+pub fn paused_for_battery() -> bool {
+    load_settings().pause_on_battery && current_power_state().on_battery
+}
+
+/// Emits `power-warning` when `warnOnBatteryStart` is on and the machine is unplugged.
+pub fn warn_if_on_battery<E: ServerEventSink>(sink: &E, server_id: &str) {
+    if !load_settings().warn_on_battery_start {
+        return;
+    }
+    let state = current_power_state();
+    if state.on_battery {
+        sink.emit("power-warning", serde_json::json!({ "serverId": server_id, "power": state }));
+    }
+}
+
+/// Emits `power-state-changed` whenever the machine is plugged in or unplugged.
+pub fn start_power_monitor<E: ServerEventSink>(sink: E) {
+    std::thread::spawn(move || {
+        let mut last = current_power_state();
+        if !last.has_battery {
+            return; // Desktops never change
+        }
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let state = current_power_state();
+            if state.on_battery != last.on_battery {
+                eprintln!("[Power] {}", if state.on_battery { "Running on battery" } else { "Plugged in" });
+                sink.emit("power-state-changed", state.clone());
+            }
+            last = state;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_power_state() -> PowerState {
+    current_power_state()
+}
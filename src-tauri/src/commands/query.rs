@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use serde::Serialize;
+use tokio::net::UdpSocket;
+use super::server_config::{read_server_properties, write_server_properties};
+use super::error::MineServerError;
+
+// GS4 / UT3 Query protocol (Java Edition `enable-query`).
+// Unlike console parsing, this returns the full player list straight from the server.
+
+const QUERY_MAGIC: [u8; 2] = [0xFE, 0xFD];
+const TYPE_HANDSHAKE: u8 = 0x09;
+const TYPE_STAT: u8 = 0x00;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    pub motd: String,
+    pub game_type: String,
+    pub version: String,
+    pub server_mod: String,
+    pub plugins: Vec<String>,
+    pub map: String,
+    pub num_players: u32,
+    pub max_players: u32,
+    pub host_port: u16,
+    pub host_ip: String,
+    pub players: Vec<String>,
+}
+
+async fn send_and_receive(socket: &UdpSocket, packet: &[u8]) -> Result<Vec<u8>, String> {
+    socket.send(packet).await.map_err(|e| format!("Query send failed: {}", e))?;
+    let mut buf = vec![0u8; 8192];
+    let n = tokio::time::timeout(QUERY_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .map_err(|_| "Query timed out. Is enable-query=true and the query port reachable?".to_string())?
+        .map_err(|e| format!("Query receive failed: {}", e))?;
+    buf.truncate(n);
+    Ok(buf)
+}
+
+fn read_cstring(data: &[u8], pos: &mut usize) -> String {
+    let start = *pos;
+    while *pos < data.len() && data[*pos] != 0 {
+        *pos += 1;
+    }
+    let s = String::from_utf8_lossy(&data[start..*pos]).to_string();
+    *pos += 1; // skip terminator
+    s
+}
+
+/// Splits the `plugins` value: "Paper on Bukkit 1.20.4: WorldEdit 7.2; Essentials 2.20"
+fn parse_plugins(raw: &str) -> (String, Vec<String>) {
+    match raw.split_once(": ") {
+        Some((server_mod, list)) => (
+            server_mod.trim().to_string(),
+            list.split("; ").map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect(),
+        ),
+        None => (raw.trim().to_string(), vec![]),
+    }
+}
+
+pub async fn query_full_stat(host: &str, port: u16) -> Result<QueryResult, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await.map_err(|e| e.to_string())?;
+    socket.connect((host, port)).await.map_err(|e| format!("Failed to reach {}:{}: {}", host, port, e))?;
+
+    let session_id: u32 = (chrono::Utc::now().timestamp_subsec_nanos()) & 0x0F0F_0F0F;
+    let session = session_id.to_be_bytes();
+
+    // 1. Handshake -> challenge token (ASCII number)
+    let mut handshake = Vec::with_capacity(7);
+    handshake.extend_from_slice(&QUERY_MAGIC);
+    handshake.push(TYPE_HANDSHAKE);
+    handshake.extend_from_slice(&session);
+
+    let resp = send_and_receive(&socket, &handshake).await?;
+    if resp.len() < 6 || resp[0] != TYPE_HANDSHAKE {
+        return Err("Invalid handshake response".to_string());
+    }
+    let mut pos = 5;
+    let token: i32 = read_cstring(&resp, &mut pos).trim().parse()
+        .map_err(|_| "Invalid challenge token".to_string())?;
+
+    // 2. Full stat request (challenge + 4 padding bytes)
+    let mut stat = Vec::with_capacity(15);
+    stat.extend_from_slice(&QUERY_MAGIC);
+    stat.push(TYPE_STAT);
+    stat.extend_from_slice(&session);
+    stat.extend_from_slice(&token.to_be_bytes());
+    stat.extend_from_slice(&[0, 0, 0, 0]);
+
+    let resp = send_and_receive(&socket, &stat).await?;
+    if resp.len() < 16 || resp[0] != TYPE_STAT {
+        return Err("Invalid stat response".to_string());
+    }
+
+    // type(1) + session(4) + "splitnum\0\x80\0"(11)
+    let mut pos = 16;
+    let mut kv: HashMap<String, String> = HashMap::new();
+    while pos < resp.len() {
+        let key = read_cstring(&resp, &mut pos);
+        if key.is_empty() { break; }
+        let value = read_cstring(&resp, &mut pos);
+        kv.insert(key, value);
+    }
+
+    // "\x01player_\0\0" (10 bytes), then null-terminated names
+    pos += 10;
+    let mut players = Vec::new();
+    while pos < resp.len() {
+        let name = read_cstring(&resp, &mut pos);
+        if name.is_empty() { break; }
+        players.push(name);
+    }
+
+    let get = |k: &str| kv.get(k).cloned().unwrap_or_default();
+    let (server_mod, plugins) = parse_plugins(&get("plugins"));
+
+    Ok(QueryResult {
+        motd: get("hostname"),
+        game_type: get("gametype"),
+        version: get("version"),
+        server_mod,
+        plugins,
+        map: get("map"),
+        num_players: get("numplayers").parse().unwrap_or(0),
+        max_players: get("maxplayers").parse().unwrap_or(0),
+        host_port: get("hostport").parse().unwrap_or(port),
+        host_ip: get("hostip"),
+        players,
+    })
+}
+
+#[tauri::command]
+pub async fn query_server(host: Option<String>, port: u16) -> Result<QueryResult, MineServerError> {
+    let host = host.unwrap_or_else(|| "127.0.0.1".to_string());
+    query_full_stat(&host, port).await.map_err(Into::into)
+}
+
+/// Turns on `enable-query` and sets `query.port` (defaults to the game port; query is UDP so they don't clash).
+/// Returns the port in use. Takes effect on next restart.
+#[tauri::command]
+pub fn enable_query(server_path: String, port: Option<u16>) -> Result<u16, MineServerError> {
+    let props = read_server_properties(server_path.clone())?;
+    let query_port = port
+        .or_else(|| props.get("query.port").and_then(|p| p.parse().ok()))
+        .or_else(|| props.get("server-port").and_then(|p| p.parse().ok()))
+        .unwrap_or(25565);
+
+    let mut updates = HashMap::new();
+    updates.insert("enable-query".to_string(), "true".to_string());
+    updates.insert("query.port".to_string(), query_port.to_string());
+    write_server_properties(server_path, updates)?;
+
+    Ok(query_port)
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::Serialize;
+use serde_yaml::Value as YamlValue;
+use super::registry::{find_registered_server, RegisteredServer};
+use super::server_config::{read_server_properties, write_server_properties};
+use super::error::MineServerError;
+
+// The few performance and anti-cheat settings users change most, written to whichever file holds
+// them for the server's platform and version:
+//   view/simulation distance  server.properties (Bedrock: view-distance and tick-distance), with
+//                             spigot.yml's per-world overrides reset to "default"
+//   anti-xray                 Paper 1.19+: config/paper-world-defaults.yml, older: paper.yml
+//   spawn limits              bukkit.yml
+// YAML files are edited line by line so comments and layout survive. Paper and Spigot create
+// their files on the first start, so these need a server that started once. Changes apply on
+// the next start.
+
+const PAPER_FAMILY: [&str; 4] = ["paper", "purpur", "folia", "pufferfish"];
+const SPAWN_CATEGORIES: [&str; 7] = ["monsters", "animals", "water-animals", "water-ambient", "water-underground-creature", "axolotls", "ambient"];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AntiXraySettings {
+    pub enabled: bool,
+    pub engine_mode: u8,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickSettings {
+    pub view_distance: Option<u32>,
+    pub simulation_distance: Option<u32>,
+    pub anti_xray: Option<AntiXraySettings>, // None if the platform has no anti-xray
+    pub spawn_limits: Option<HashMap<String, i64>>, // None without bukkit.yml
+    pub files: HashMap<String, String>, // Setting -> file it lives in, relative to the server
+}
+
+fn server(id: &str) -> Result<RegisteredServer, MineServerError> {
+    find_registered_server(id).ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))
+}
+
+fn is_paper(server: &RegisteredServer) -> bool {
+    PAPER_FAMILY.contains(&server.server_type.as_str())
+}
+
+fn is_bukkit(server: &RegisteredServer) -> bool {
+    is_paper(server) || matches!(server.server_type.as_str(), "spigot" | "bukkit")
+}
+
+/// 20 for "1.20.4".
+fn minor_version(version: &str) -> Option<u32> {
+    version.strip_prefix("1.")?.split(['.', '-']).next()?.parse().ok()
+}
+
+/// The Paper file with world settings and the path of the anti-xray section in it. Paper 1.19
+/// moved paper.yml's world-settings.default to config/paper-world-defaults.yml.
+fn paper_anti_xray(root: &Path, version: &str) -> (PathBuf, Vec<&'static str>) {
+    let new_layout = root.join("config").join("paper-world-defaults.yml");
+    let old_layout = root.join("paper.yml");
+    let use_new = new_layout.exists() || (!old_layout.exists() && minor_version(version).map(|v| v >= 19).unwrap_or(true));
+    if use_new {
+        (new_layout, vec!["anticheat", "anti-xray"])
+    } else {
+        (old_layout, vec!["world-settings", "default", "anti-xray"])
+    }
+}
+
+fn relative(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root).unwrap_or(file).to_string_lossy().replace('\\', "/")
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Key of a "key: value" line, without quotes.
+fn yaml_key(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+        return None;
+    }
+    Some(trimmed.split_once(':')?.0.trim().trim_matches(|c| c == '"' || c == '\''))
+}
+
+/// Replaces the value at `path` in a block-style YAML document, keeping everything else as it
+/// is. None if the path doesn't exist.
+fn set_yaml_value(content: &str, path: &[&str], value: &str) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut level = 0;
+    let mut parent_indent: Option<usize> = None;
+    let mut child_indent: Option<usize> = None;
+
+    for i in 0..lines.len() {
+        let Some(key) = yaml_key(&lines[i]) else { continue };
+        let indent = indent_of(&lines[i]);
+        if let Some(parent) = parent_indent {
+            if indent <= parent {
+                return None; // Left the parent's block without finding the key
+            }
+        }
+        match child_indent {
+            None => child_indent = Some(indent),
+            Some(child) if indent > child => continue, // Deeper than the level we look at
+            _ => {},
+        }
+        if key != path[level] {
+            continue;
+        }
+        if level == path.len() - 1 {
+            let (raw_key, old_value) = lines[i].trim_start().split_once(':')?;
+            let comment = old_value.find(" #").map(|at| old_value[at..].to_string()).unwrap_or_default();
+            lines[i] = format!("{}{}: {}{}", " ".repeat(indent), raw_key, value, comment);
+            let mut updated = lines.join("\n");
+            if content.ends_with('\n') {
+                updated.push('\n');
+            }
+            return Some(updated);
+        }
+        level += 1;
+        parent_indent = Some(indent);
+        child_indent = None;
+    }
+    None
+}
+
+fn read_yaml(file: &Path) -> Option<YamlValue> {
+    serde_yaml::from_str(&fs::read_to_string(file).ok()?).ok()
+}
+
+fn yaml_at<'a>(value: &'a YamlValue, path: &[&str]) -> Option<&'a YamlValue> {
+    path.iter().try_fold(value, |v, key| v.get(*key))
+}
+
+/// Writes `values` (path below `prefix` -> YAML scalar) to an existing file.
+fn update_yaml_file(root: &Path, file: &Path, prefix: &[&str], values: &[(&str, String)]) -> Result<String, String> {
+    let name = relative(root, file);
+    let mut content = fs::read_to_string(file)
+        .map_err(|_| format!("{} doesn't exist yet. Start the server once so it is created.", name))?;
+    for (key, value) in values {
+        let path: Vec<&str> = prefix.iter().copied().chain(key.split('.')).collect();
+        content = set_yaml_value(&content, &path, value)
+            .ok_or_else(|| format!("{} has no {} setting", name, path.join(".")))?;
+    }
+    fs::write(file, content).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    Ok(name)
+}
+
+/// Sets spigot.yml's per-world override of `key` back to "default", so server.properties
+/// decides. Skipped if there is no spigot.yml or it doesn't override the setting.
+fn reset_spigot_override(root: &Path, key: &str) -> Option<String> {
+    let file = root.join("spigot.yml");
+    let path = ["world-settings", "default", key];
+    let current = yaml_at(&read_yaml(&file)?, &path)?.clone();
+    if current.as_str() == Some("default") {
+        return None;
+    }
+    let content = set_yaml_value(&fs::read_to_string(&file).ok()?, &path, "default")?;
+    fs::write(&file, content).ok()?;
+    Some("spigot.yml".to_string())
+}
+
+/// Writes a distance to server.properties and clears spigot.yml overrides of it.
+fn set_distance(server: &RegisteredServer, key: &str, chunks: u32) -> Result<Vec<String>, MineServerError> {
+    let root = Path::new(&server.path);
+    write_server_properties(server.path.clone(), HashMap::from([(key.to_string(), chunks.to_string())]))?;
+    let mut files = vec!["server.properties".to_string()];
+    if is_bukkit(server) {
+        files.extend(reset_spigot_override(root, key));
+    }
+    Ok(files)
+}
+
+#[tauri::command]
+pub fn get_quick_settings(id: String) -> Result<QuickSettings, MineServerError> {
+    let server = server(&id)?;
+    let root = Path::new(&server.path);
+    let bedrock = server.server_type == "bedrock";
+    let properties = read_server_properties(server.path.clone()).unwrap_or_default();
+    let number = |key: &str| properties.get(key).and_then(|v| v.trim().parse().ok());
+    let mut files = HashMap::new();
+
+    let simulation_key = if bedrock { "tick-distance" } else { "simulation-distance" };
+    files.insert("viewDistance".to_string(), "server.properties".to_string());
+    files.insert("simulationDistance".to_string(), "server.properties".to_string());
+
+    let anti_xray = if is_paper(&server) {
+        let (file, prefix) = paper_anti_xray(root, &server.version);
+        files.insert("antiXray".to_string(), relative(root, &file));
+        let section = read_yaml(&file).and_then(|doc| yaml_at(&doc, &prefix).cloned());
+        Some(AntiXraySettings {
+            enabled: section.as_ref().and_then(|s| s.get("enabled")?.as_bool()).unwrap_or(false),
+            engine_mode: section.as_ref().and_then(|s| s.get("engine-mode")?.as_u64()).unwrap_or(1) as u8,
+        })
+    } else {
+        None
+    };
+
+    let spawn_limits = if is_bukkit(&server) {
+        files.insert("spawnLimits".to_string(), "bukkit.yml".to_string());
+        read_yaml(&root.join("bukkit.yml"))
+            .and_then(|doc| doc.get("spawn-limits")?.as_mapping().cloned())
+            .map(|limits| limits.iter()
+                .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_i64()?)))
+                .collect())
+    } else {
+        None
+    };
+
+    Ok(QuickSettings {
+        view_distance: number("view-distance"),
+        simulation_distance: number(simulation_key),
+        anti_xray,
+        spawn_limits,
+        files,
+    })
+}
+
+/// Returns the files that were changed.
+#[tauri::command]
+pub fn set_view_distance(id: String, chunks: u32) -> Result<Vec<String>, MineServerError> {
+    let server = server(&id)?;
+    let range = if server.server_type == "bedrock" { 5..=96 } else { 2..=32 };
+    if !range.contains(&chunks) {
+        return Err(MineServerError::validation(format!("View distance must be between {} and {} chunks", range.start(), range.end())));
+    }
+    set_distance(&server, "view-distance", chunks)
+}
+
+/// Bedrock calls it tick-distance.
+#[tauri::command]
+pub fn set_simulation_distance(id: String, chunks: u32) -> Result<Vec<String>, MineServerError> {
+    let server = server(&id)?;
+    if server.server_type == "bedrock" {
+        if !(4..=12).contains(&chunks) {
+            return Err(MineServerError::validation("Tick distance must be between 4 and 12 chunks"));
+        }
+        return set_distance(&server, "tick-distance", chunks);
+    }
+    if minor_version(&server.version).map(|v| v < 18).unwrap_or(false) {
+        return Err(MineServerError::validation("Simulation distance exists since Minecraft 1.18"));
+    }
+    if !(2..=32).contains(&chunks) {
+        return Err(MineServerError::validation("Simulation distance must be between 2 and 32 chunks"));
+    }
+    set_distance(&server, "simulation-distance", chunks)
+}
+
+/// Paper's anti-xray for all worlds. Engine mode 1 hides ores, 2 and 3 also add fake ores.
+#[tauri::command]
+pub fn set_anti_xray(id: String, enabled: bool, engine_mode: u8) -> Result<Vec<String>, MineServerError> {
+    let server = server(&id)?;
+    if !is_paper(&server) {
+        return Err(MineServerError::validation("Anti-xray is built into Paper and its forks only"));
+    }
+    if !(1..=3).contains(&engine_mode) {
+        return Err(MineServerError::validation("Engine mode must be 1, 2 or 3"));
+    }
+    let root = Path::new(&server.path);
+    let (file, prefix) = paper_anti_xray(root, &server.version);
+    let values = [("enabled", enabled.to_string()), ("engine-mode", engine_mode.to_string())];
+    Ok(vec![update_yaml_file(root, &file, &prefix, &values)?])
+}
+
+/// Mob caps per category from bukkit.yml; -1 uses the vanilla default.
+#[tauri::command]
+pub fn set_spawn_limits(id: String, limits: HashMap<String, i64>) -> Result<Vec<String>, MineServerError> {
+    let server = server(&id)?;
+    if !is_bukkit(&server) {
+        return Err(MineServerError::validation("Spawn limits can only be changed on Spigot, Paper and their forks"));
+    }
+    if let Some(category) = limits.keys().find(|k| !SPAWN_CATEGORIES.contains(&k.as_str())) {
+        return Err(MineServerError::validation(format!("Unknown spawn category: {}", category)));
+    }
+    if let Some((category, _)) = limits.iter().find(|(_, v)| !(-1..=1000).contains(*v)) {
+        return Err(MineServerError::validation(format!("The {} limit must be between -1 and 1000", category)));
+    }
+    let root = Path::new(&server.path);
+    let values: Vec<(&str, String)> = limits.iter().map(|(k, v)| (k.as_str(), v.to_string())).collect();
+    Ok(vec![update_yaml_file(root, &root.join("bukkit.yml"), &["spawn-limits"], &values)?])
+}
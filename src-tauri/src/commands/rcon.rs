@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::State;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use super::registry::find_registered_server;
+use super::server_config::{read_server_properties, write_server_properties};
+use super::error::MineServerError;
+
+// Source RCON protocol (Java Edition `enable-rcon`). Unlike a write to stdin, a command sent
+// over RCON answers with its own output, so the UI can show what that command did.
+
+const TYPE_RESPONSE: i32 = 0;
+const TYPE_COMMAND: i32 = 2; // Also the type of the auth response
+const TYPE_AUTH: i32 = 3;
+// Minecraft answers packets of unknown type with "Unknown request"; sent right after a command,
+// that answer marks the end of a response split over several packets
+const TYPE_END_MARKER: i32 = 200;
+const MAX_COMMAND_LEN: usize = 1446; // Longest body Minecraft reads
+const MAX_PACKET_LEN: i32 = 64 * 1024;
+const RCON_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_RCON_PORT: u16 = 25575;
+const LOCAL_HOST: &str = "127.0.0.1";
+
+struct Packet {
+    id: i32,
+    kind: i32,
+    body: String,
+}
+
+/// An authenticated RCON connection.
+pub struct RconClient {
+    stream: TcpStream,
+    next_id: i32,
+}
+
+impl RconClient {
+    pub async fn connect(host: &str, port: u16, password: &str) -> Result<Self, String> {
+        let stream = timeout(RCON_TIMEOUT, TcpStream::connect((host, port)))
+            .await
+            .map_err(|_| format!("Connecting to RCON on {}:{} timed out", host, port))?
+            .map_err(|e| format!("Failed to reach RCON on {}:{}: {}. Is the server running with enable-rcon=true?", host, port, e))?;
+        let mut client = Self { stream, next_id: 1 };
+
+        let id = client.send(TYPE_AUTH, password).await?;
+        loop {
+            // Some servers send an empty response packet ahead of the auth result
+            let packet = client.read().await?;
+            if packet.kind != TYPE_COMMAND {
+                continue;
+            }
+            if packet.id == -1 {
+                return Err("RCON password rejected".to_string());
+            }
+            if packet.id == id {
+                return Ok(client);
+            }
+        }
+    }
+
+    /// Runs a console command (without the leading "/") and returns its output.
+    pub async fn command(&mut self, command: &str) -> Result<String, String> {
+        if command.len() > MAX_COMMAND_LEN {
+            return Err(format!("Command is too long for RCON ({} bytes, at most {})", command.len(), MAX_COMMAND_LEN));
+        }
+        let id = self.send(TYPE_COMMAND, command).await?;
+        let end = self.send(TYPE_END_MARKER, "").await?;
+
+        let mut output = String::new();
+        loop {
+            let packet = self.read().await?;
+            if packet.id == end {
+                return Ok(output);
+            }
+            if packet.id == id && packet.kind == TYPE_RESPONSE {
+                output.push_str(&packet.body);
+            }
+        }
+    }
+
+    /// Whether the server closed the connection, e.g. when it restarted. Minecraft sends
+    /// nothing unasked, so unread data means the connection is out of step too.
+    fn is_closed(&self) -> bool {
+        !matches!(self.stream.try_read(&mut [0u8; 1]), Err(e) if e.kind() == std::io::ErrorKind::WouldBlock)
+    }
+
+    async fn send(&mut self, kind: i32, body: &str) -> Result<i32, String> {
+        let id = self.next_id;
+        self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+
+        // length (id + type + body + two NULs), id, type, body, NUL, NUL; little-endian
+        let mut packet = Vec::with_capacity(14 + body.len());
+        packet.extend_from_slice(&(10 + body.len() as i32).to_le_bytes());
+        packet.extend_from_slice(&id.to_le_bytes());
+        packet.extend_from_slice(&kind.to_le_bytes());
+        packet.extend_from_slice(body.as_bytes());
+        packet.extend_from_slice(&[0, 0]);
+
+        timeout(RCON_TIMEOUT, self.stream.write_all(&packet))
+            .await
+            .map_err(|_| "RCON send timed out".to_string())?
+            .map_err(|e| format!("RCON send failed: {}", e))?;
+        Ok(id)
+    }
+
+    async fn read(&mut self) -> Result<Packet, String> {
+        let stream = &mut self.stream;
+        let read = async {
+            let len = stream.read_i32_le().await?;
+            if !(10..=MAX_PACKET_LEN).contains(&len) {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid packet length {}", len)));
+            }
+            let mut buf = vec![0u8; len as usize];
+            stream.read_exact(&mut buf).await?;
+            Ok(Packet {
+                id: i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]),
+                kind: i32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]),
+                body: String::from_utf8_lossy(&buf[8..buf.len() - 2]).to_string(),
+            })
+        };
+        timeout(RCON_TIMEOUT, read)
+            .await
+            .map_err(|_| "RCON response timed out".to_string())?
+            .map_err(|e| format!("RCON receive failed: {}", e))
+    }
+}
+
+type SharedClient = Arc<tokio::sync::Mutex<RconClient>>;
+
+pub struct RconState {
+    connections: Arc<Mutex<HashMap<String, SharedClient>>>, // server id -> open connection
+}
+
+impl RconState {
+    pub fn new() -> Self {
+        Self {
+            connections: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<SharedClient> {
+        self.connections.lock().ok()?.get(id).cloned()
+    }
+
+    fn insert(&self, id: &str, client: RconClient) -> SharedClient {
+        let client = Arc::new(tokio::sync::Mutex::new(client));
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.insert(id.to_string(), client.clone());
+        }
+        client
+    }
+
+    fn remove(&self, id: &str) {
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.remove(id);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RconSettings {
+    pub port: u16,
+    pub password: String,
+}
+
+/// RCON port and password of a registered server; None unless enable-rcon is on with a password.
+fn server_rcon_settings(id: &str) -> Result<Option<RconSettings>, MineServerError> {
+    let server = find_registered_server(id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))?;
+    let props = read_server_properties(server.path)?;
+    let enabled = props.get("enable-rcon").map(|v| v.trim() == "true").unwrap_or(false);
+    let password = props.get("rcon.password").map(|p| p.trim().to_string()).unwrap_or_default();
+    if !enabled || password.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(RconSettings {
+        port: props.get("rcon.port").and_then(|p| p.trim().parse().ok()).unwrap_or(DEFAULT_RCON_PORT),
+        password,
+    }))
+}
+
+/// Connects to the server's own RCON from its server.properties.
+async fn connect_registered(state: &RconState, id: &str) -> Result<SharedClient, MineServerError> {
+    let settings = server_rcon_settings(id)?
+        .ok_or_else(|| MineServerError::not_found("RCON is not enabled for this server"))?;
+    let client = RconClient::connect(LOCAL_HOST, settings.port, &settings.password).await
+        .map_err(MineServerError::network)?;
+    Ok(state.insert(id, client))
+}
+
+/// Opens (or replaces) the RCON connection of a server. Host, port and password default to
+/// 127.0.0.1 and the server's server.properties.
+#[tauri::command]
+pub async fn rcon_connect(state: State<'_, RconState>, id: String, host: Option<String>, port: Option<u16>, password: Option<String>) -> Result<(), MineServerError> {
+    let settings = server_rcon_settings(&id)?;
+    let port = port.or(settings.as_ref().map(|s| s.port)).unwrap_or(DEFAULT_RCON_PORT);
+    let password = password.or(settings.map(|s| s.password))
+        .ok_or_else(|| MineServerError::not_found("RCON is not enabled for this server"))?;
+    let host = host.unwrap_or_else(|| LOCAL_HOST.to_string());
+
+    state.remove(&id);
+    let client = RconClient::connect(&host, port, &password).await.map_err(MineServerError::network)?;
+    state.insert(&id, client);
+    Ok(())
+}
+
+/// Runs a command over RCON and returns its output, connecting first if needed (again after
+/// the server restarted).
+#[tauri::command]
+pub async fn rcon_send(state: State<'_, RconState>, id: String, command: String) -> Result<String, MineServerError> {
+    let command = command.trim().trim_start_matches('/').to_string();
+    if command.is_empty() {
+        return Err(MineServerError::validation("Command is empty"));
+    }
+
+    let client = match state.get(&id) {
+        Some(client) if !client.lock().await.is_closed() => client,
+        _ => connect_registered(&state, &id).await?,
+    };
+    let output = client.lock().await.command(&command).await;
+    output.map_err(|e| {
+        state.remove(&id);
+        MineServerError::internal(e)
+    })
+}
+
+#[tauri::command]
+pub fn rcon_disconnect(state: State<'_, RconState>, id: String) {
+    state.remove(&id);
+}
+
+/// Turns on `enable-rcon`, sets `rcon.port` and generates `rcon.password` unless one is set.
+/// Takes effect on next restart.
+#[tauri::command]
+pub fn enable_rcon(server_path: String, port: Option<u16>) -> Result<RconSettings, MineServerError> {
+    let props = read_server_properties(server_path.clone())?;
+    let port = port
+        .or_else(|| props.get("rcon.port").and_then(|p| p.trim().parse().ok()))
+        .unwrap_or(DEFAULT_RCON_PORT);
+    let password = props.get("rcon.password").map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+
+    let mut updates = HashMap::new();
+    updates.insert("enable-rcon".to_string(), "true".to_string());
+    updates.insert("rcon.port".to_string(), port.to_string());
+    updates.insert("rcon.password".to_string(), password.clone());
+    write_server_properties(server_path, updates)?;
+
+    Ok(RconSettings { port, password })
+}
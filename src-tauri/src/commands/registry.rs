@@ -0,0 +1,328 @@
+use std::path::PathBuf;
+use std::fs;
+use super::json_store::{read_json, update_json};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use super::runner::{ServerConfig, ServerProcessState, start_server_direct, stop_server_direct};
+use super::network_manager::release_server_network;
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Backend copy of the servers the user created, so the backend (scheduler, headless mode)
+// knows about servers without the frontend passing everything in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredServer {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    #[serde(default)]
+    pub server_type: String,
+    #[serde(default)]
+    pub version: String,
+    pub jar_file: String,
+    pub ram: u32,
+    #[serde(default)]
+    pub java_path: Option<String>,
+    #[serde(default)]
+    pub startup_flags: Option<String>,
+    #[serde(default)]
+    pub auto_restart: bool,
+    #[serde(default)]
+    pub auto_start: bool, // Started automatically in headless mode
+    #[serde(default)]
+    pub auto_network_setup: bool, // Map the port, open the firewall and check reachability on start
+    #[serde(default)]
+    pub tunnel_autostart: bool, // Playit tunnel starts and stops with the server
+    #[serde(default)]
+    pub gc_logging: bool, // Java servers write logs/gc.log for the GC analysis
+    #[serde(default)]
+    pub network: ServerNetwork, // Kept by the backend; the frontend never sends it
+    #[serde(default)]
+    pub notes: String, // Free-form notes; kept by the backend like `network`
+    #[serde(default)]
+    pub changelog: Vec<ChangelogEntry>, // Oldest first; kept by the backend like `network`
+}
+
+/// One change to a server: added automatically for upgrades, addon installs and restores
+/// (from the activity feed), or written by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogEntry {
+    pub timestamp: String,
+    pub kind: String, // Activity kind ("server_download", "plugin_install", "restore", ...) or "note"
+    pub summary: String,
+    #[serde(default)]
+    pub author: Option<String>, // Who wrote a manual entry, when given
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerHistory {
+    pub notes: String,
+    pub changelog: Vec<ChangelogEntry>, // Newest first
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortMapping {
+    pub port: u16,
+    pub protocol: String, // "TCP" or "UDP"
+}
+
+/// Ports opened for a server (router mappings and firewall rules), so they can be re-applied
+/// when it starts and removed when it is deleted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerNetwork {
+    #[serde(default)]
+    pub upnp: Vec<PortMapping>,
+    #[serde(default)]
+    pub firewall_ports: Vec<u16>,
+}
+
+impl ServerNetwork {
+    pub fn is_empty(&self) -> bool {
+        self.upnp.is_empty() && self.firewall_ports.is_empty()
+    }
+}
+
+impl RegisteredServer {
+    pub fn launch_config(&self) -> ServerConfig {
+        ServerConfig {
+            id: self.id.clone(),
+            path: self.path.clone(),
+            jar_file: self.jar_file.clone(),
+            ram: self.ram,
+            java_path: self.java_path.clone(),
+            startup_flags: self.startup_flags.clone(),
+            auto_restart: self.auto_restart,
+        }
+    }
+}
+
+const MAX_CHANGELOG_ENTRIES: usize = 1000;
+const MAX_NOTES_LEN: usize = 64 * 1024;
+const MAX_SUMMARY_LEN: usize = 1000;
+
+fn get_registry_file() -> PathBuf {
+    app_data_dir().join("servers.json")
+}
+
+pub fn load_registry() -> Result<Vec<RegisteredServer>, String> {
+    Ok(read_json(&get_registry_file(), "server registry")?.unwrap_or_default())
+}
+
+/// Changes the registry under its lock, so concurrent updates don't overwrite each other.
+pub fn update_registry<R>(update: impl FnOnce(&mut Vec<RegisteredServer>) -> Result<R, String>) -> Result<R, String> {
+    update_json(&get_registry_file(), "server registry", update)
+}
+
+pub fn find_registered_server(id: &str) -> Option<RegisteredServer> {
+    load_registry().ok()?.into_iter().find(|s| s.id == id)
+}
+
+/// Changes the recorded network state of a registered server.
+pub fn update_server_network(id: &str, update: impl FnOnce(&mut ServerNetwork)) -> Result<(), String> {
+    update_registry(|servers| {
+        let server = servers.iter_mut().find(|s| s.id == id)
+            .ok_or_else(|| format!("Server {} is not registered", id))?;
+        update(&mut server.network);
+        Ok(())
+    })
+}
+
+/// Adds an entry to the changelog of the registered server at `server_path`. Paths that
+/// aren't registered (servers being created, backups restored elsewhere) are ignored.
+pub fn record_change(server_path: &str, kind: &str, summary: &str) {
+    let Ok(target) = fs::canonicalize(server_path) else { return };
+    let result = update_registry(|servers| {
+        let server = servers.iter_mut().find(|s| fs::canonicalize(&s.path).ok().as_ref() == Some(&target));
+        if let Some(server) = server {
+            push_change(server, kind, summary.to_string(), None);
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("[Registry] Failed to record change for {}: {}", server_path, e);
+    }
+}
+
+fn push_change(server: &mut RegisteredServer, kind: &str, summary: String, author: Option<String>) {
+    server.changelog.push(ChangelogEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        kind: kind.to_string(),
+        summary,
+        author,
+    });
+    let excess = server.changelog.len().saturating_sub(MAX_CHANGELOG_ENTRIES);
+    server.changelog.drain(..excess);
+}
+
+#[tauri::command]
+pub fn list_registered_servers() -> Result<Vec<RegisteredServer>, MineServerError> {
+    load_registry().map_err(Into::into)
+}
+
+/// Inserts or updates a server entry (matched by id).
+#[tauri::command]
+pub fn register_server(state: State<'_, ServerProcessState>, server: RegisteredServer) -> Result<(), MineServerError> {
+    remember_launch_config(&state, server.launch_config());
+    update_registry(|servers| {
+        if let Some(existing) = servers.iter_mut().find(|s| s.id == server.id) {
+            let network = std::mem::take(&mut existing.network);
+            let notes = std::mem::take(&mut existing.notes);
+            let changelog = std::mem::take(&mut existing.changelog);
+            *existing = server;
+            existing.network = network;
+            existing.notes = notes;
+            existing.changelog = changelog;
+        } else {
+            servers.push(server);
+        }
+        Ok(())
+    }).map_err(Into::into)
+}
+
+/// Changes the RAM, JVM flags and Java of a registered server. They apply on its next start,
+/// auto-restarts and scheduled restarts included; `restart` applies them right away if it runs.
+#[tauri::command]
+pub async fn update_server_launch_config(
+    app: AppHandle,
+    state: State<'_, ServerProcessState>,
+    id: String,
+    ram: u32,
+    flags: Option<String>,
+    java_path: Option<String>,
+    restart: Option<bool>,
+) -> Result<(), MineServerError> {
+    if ram < 512 {
+        return Err(MineServerError::validation("At least 512 MB of RAM is required"));
+    }
+    let flags = flags.map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+    let java_path = java_path.map(|j| j.trim().to_string()).filter(|j| !j.is_empty());
+
+    if find_registered_server(&id).is_none() {
+        return Err(MineServerError::not_found(format!("Server {} is not registered", id)));
+    }
+    let config = update_registry(|servers| {
+        let server = servers.iter_mut().find(|s| s.id == id)
+            .ok_or_else(|| format!("Server {} is not registered", id))?;
+        server.ram = ram;
+        server.startup_flags = flags;
+        server.java_path = java_path;
+        Ok(server.launch_config())
+    })?;
+
+    // The runner keeps the config a server was started with for auto-restarts
+    if let Ok(mut configs) = state.configs.lock() {
+        if let Some(stored) = configs.get_mut(&id) {
+            stored.ram = config.ram;
+            stored.startup_flags = config.startup_flags.clone();
+            stored.java_path = config.java_path.clone();
+        }
+    }
+
+    let running = state.processes.lock().map(|p| p.contains_key(&id)).unwrap_or(false);
+    if !(restart.unwrap_or(false) && running) {
+        return Ok(());
+    }
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        stop_server_direct(&state, config.id.clone())?;
+        start_server_direct(
+            app,
+            &state,
+            config.id,
+            config.path,
+            config.jar_file,
+            config.ram,
+            config.java_path,
+            config.startup_flags,
+            Some(config.auto_restart),
+        )
+        .map(|_| ())
+    })
+    .await?
+}
+
+/// Keeps the runner's copy of a stopped server's launch config current; a running server keeps
+/// the config it was started with until it stops.
+fn remember_launch_config(state: &ServerProcessState, config: ServerConfig) {
+    let running = state.processes.lock().map(|p| p.contains_key(&config.id)).unwrap_or(false);
+    if running {
+        return;
+    }
+    if let Ok(mut configs) = state.configs.lock() {
+        configs.insert(config.id.clone(), config);
+    }
+}
+
+#[tauri::command]
+pub fn unregister_server(state: State<'_, ServerProcessState>, id: String) -> Result<(), MineServerError> {
+    let running = state.processes.lock().map(|p| p.contains_key(&id)).unwrap_or(false);
+    if !running {
+        if let Ok(mut configs) = state.configs.lock() {
+            configs.remove(&id);
+        }
+    }
+    let (removed, servers) = update_registry(|servers| {
+        let removed = servers.iter().position(|s| s.id == id).map(|pos| servers.remove(pos));
+        Ok((removed, servers.clone()))
+    })?;
+
+    // Close the ports this server opened, unless another server still uses them
+    if let Some(server) = removed {
+        if !server.network.is_empty() {
+            release_server_network(server.network, servers);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_server_network(id: String) -> Result<ServerNetwork, MineServerError> {
+    find_registered_server(&id)
+        .map(|s| s.network)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))
+}
+
+/// Notes and changelog of a registered server.
+#[tauri::command]
+pub fn get_server_history(id: String) -> Result<ServerHistory, MineServerError> {
+    let server = find_registered_server(&id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))?;
+    Ok(ServerHistory {
+        notes: server.notes,
+        changelog: server.changelog.into_iter().rev().collect(),
+    })
+}
+
+#[tauri::command]
+pub fn set_server_notes(id: String, notes: String) -> Result<(), MineServerError> {
+    if notes.len() > MAX_NOTES_LEN {
+        return Err(MineServerError::validation(format!("Notes are limited to {} KB", MAX_NOTES_LEN / 1024)));
+    }
+    update_registry(|servers| {
+        let server = servers.iter_mut().find(|s| s.id == id)
+            .ok_or_else(|| format!("Server {} is not registered", id))?;
+        server.notes = notes;
+        Ok(())
+    }).map_err(Into::into)
+}
+
+/// Adds a hand-written changelog entry, e.g. a config change made outside the app.
+#[tauri::command]
+pub fn add_changelog_entry(id: String, summary: String, author: Option<String>) -> Result<(), MineServerError> {
+    let summary = summary.trim().to_string();
+    if summary.is_empty() || summary.len() > MAX_SUMMARY_LEN {
+        return Err(MineServerError::validation(format!("The entry must have 1 to {} characters", MAX_SUMMARY_LEN)));
+    }
+    let author = author.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    update_registry(|servers| {
+        let server = servers.iter_mut().find(|s| s.id == id)
+            .ok_or_else(|| format!("Server {} is not registered", id))?;
+        push_change(server, "note", summary, author);
+        Ok(())
+    }).map_err(Into::into)
+}
@@ -1,10 +1,19 @@
-use tauri::{State, WebviewWindow, Emitter};
+use tauri::{State, WebviewWindow, Emitter, AppHandle, Runtime};
 use std::process::{Command, Stdio, Child};
-use std::sync::{Arc, Mutex};
-use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc, Mutex};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{BufReader, BufRead, Write};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use super::notifications::{self, Notification, NotificationEvent};
+use super::player_stats;
+use super::server_config;
+use super::bedrock_access;
+use super::network_manager::{self, NetworkState};
+use super::eula::ensure_eula;
+use super::power;
+use super::gc_log;
+use super::error::MineServerError;
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct ServerConfig {
@@ -17,10 +26,104 @@ pub struct ServerConfig {
     pub auto_restart: bool,
 }
 
+/// Where runner/scheduler events go: the whole app, or stdout in headless mode. Events go out
+/// app-wide rather than to the window that started the server, so they keep flowing to windows
+/// opened or reloaded later; per-server streams use channels like `server-log:<id>`.
+pub trait ServerEventSink: Clone + Send + Sync + 'static {
+    fn emit<S: serde::Serialize + Clone>(&self, event: &str, payload: S);
+}
+
+impl<R: Runtime> ServerEventSink for AppHandle<R> {
+    fn emit<S: serde::Serialize + Clone>(&self, event: &str, payload: S) {
+        let _ = Emitter::emit(self, event, payload);
+    }
+}
+
+/// Headless sink: prints events to stdout.
+#[derive(Clone)]
+pub struct ConsoleSink;
+
+impl ServerEventSink for ConsoleSink {
+    fn emit<S: serde::Serialize + Clone>(&self, event: &str, payload: S) {
+        if event == super::operations::PROGRESS_EVENT {
+            return; // Ten lines a second during a backup; the scheduler logs the outcome itself
+        }
+        match serde_json::to_value(&payload) {
+            Ok(serde_json::Value::String(text)) => println!("[{}] {}", event, text),
+            // Batched console lines
+            Ok(serde_json::Value::Array(items)) if items.iter().all(|i| i.is_string()) => {
+                for item in items {
+                    println!("[{}] {}", event, item.as_str().unwrap_or_default());
+                }
+            }
+            Ok(value) => println!("[{}] {}", event, value),
+            Err(_) => println!("[{}]", event),
+        }
+    }
+}
+
+// Runtime data gathered from the console while a server runs
+#[derive(Clone, Default)]
+pub struct LiveStats {
+    pub started_at: Option<chrono::DateTime<chrono::Local>>,
+    pub online_players: HashSet<String>,
+    pub tps: Option<f32>,
+    pub recent_output: VecDeque<String>, // Last console lines, used to confirm command results
+    pub output_seq: u64, // Total lines seen since start
+    pub ready: bool, // Printed its "Done" line
+}
+
+/// Start time and restart count of a server; kept after it stops so the last start stays known.
+#[derive(Clone)]
+pub struct ServerLifecycle {
+    pub started_at: chrono::DateTime<chrono::Local>, // Current (or last) process
+    pub restarts: u32, // Auto-restarts since the user started it
+    pub stopping: bool,
+}
+
+const RECENT_OUTPUT_LINES: usize = 200;
+
+// A server that exits this soon after starting counts as a failed start. After this many failed
+// auto-restarts in a row the monitor gives up and emits `server-crash-loop`, so the user can
+// start it in safe mode instead.
+const QUICK_CRASH_WINDOW: Duration = Duration::from_secs(60);
+const MAX_QUICK_RESTARTS: u32 = 3;
+
+// The pause before an auto-restart and how long a stop waits before killing the server. Short
+// in tests, which run against the fake server.
+#[cfg(not(test))]
+const RESTART_DELAY: Duration = Duration::from_secs(3);
+#[cfg(not(test))]
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+#[cfg(test)]
+const RESTART_DELAY: Duration = Duration::from_millis(50);
+#[cfg(test)]
+const STOP_TIMEOUT: Duration = Duration::from_millis(500);
+
+// The monitor learns of an exit when the console pipes close. A process can close them a moment
+// before it can be reaped, so it is checked for this long before the monitor goes back to waiting.
+const EXIT_GRACE: Duration = Duration::from_secs(2);
+
+/// How often the monitor checks the process without being woken: a run script can exit while
+/// the Java it started keeps the console open. Tests use a long interval, so they fail if exits
+/// are only noticed by polling.
+#[cfg(not(test))]
+fn poll_interval() -> Duration {
+    Duration::from_secs(super::settings::load_settings().process_poll_secs.max(1))
+}
+#[cfg(test)]
+fn poll_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+#[derive(Clone)]
 pub struct ServerProcessState {
     pub processes: Arc<Mutex<HashMap<String, Child>>>,
     pub explicit_stops: Arc<Mutex<HashSet<String>>>,
-    pub configs: Arc<Mutex<HashMap<String, ServerConfig>>>,
+    pub configs: Arc<Mutex<HashMap<String, ServerConfig>>>, // Registered servers from startup on, replaced when one starts
+    pub live_stats: Arc<Mutex<HashMap<String, LiveStats>>>,
+    pub lifecycle: Arc<Mutex<HashMap<String, ServerLifecycle>>>,
+    pub network: NetworkState, // Shared with the managed NetworkState, for tunnels that follow a server
 }
 
 impl ServerProcessState {
@@ -29,30 +132,245 @@ impl ServerProcessState {
             processes: Arc::new(Mutex::new(HashMap::new())),
             explicit_stops: Arc::new(Mutex::new(HashSet::new())),
             configs: Arc::new(Mutex::new(HashMap::new())),
+            live_stats: Arc::new(Mutex::new(HashMap::new())),
+            lifecycle: Arc::new(Mutex::new(HashMap::new())),
+            network: NetworkState::new(),
+        }
+    }
+
+    /// Loads the launch configs of the registered servers, so servers not started this session
+    /// are known too (scheduled restarts, lookups by folder).
+    pub fn load_registered_configs(&self) {
+        let servers = match super::registry::load_registry() {
+            Ok(servers) => servers,
+            Err(e) => return eprintln!("[Runner] Failed to load launch configs: {}", e),
+        };
+        if let Ok(mut configs) = self.configs.lock() {
+            for server in servers {
+                configs.entry(server.id.clone()).or_insert_with(|| server.launch_config());
+            }
         }
     }
 }
 
-// Internal helper to spawn process
-fn spawn_process_internal(
-    window: WebviewWindow,
-    config: &ServerConfig
-) -> Result<Child, String> {
-    let server_path = std::path::Path::new(&config.path);
-    if !server_path.exists() {
-        return Err("Server directory not found".to_string());
+/// Ids and canonical folders of the servers whose process is currently running.
+fn running_servers(state: &ServerProcessState) -> Vec<(String, std::path::PathBuf)> {
+    let running: Vec<String> = match state.processes.lock() {
+        Ok(p) => p.keys().cloned().collect(),
+        Err(_) => return vec![],
+    };
+    let configs = match state.configs.lock() {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+    running.into_iter()
+        .filter_map(|id| {
+            let root = std::fs::canonicalize(&configs.get(&id)?.path).ok()?;
+            Some((id, root))
+        })
+        .collect()
+}
+
+/// Canonical folders of the servers whose process is currently running.
+pub fn running_server_roots(state: &ServerProcessState) -> Vec<std::path::PathBuf> {
+    running_servers(state).into_iter().map(|(_, root)| root).collect()
+}
+
+/// Central guard for commands that rewrite or remove server files: fails with a Conflict
+/// (context = the running server's id, so the UI can offer to stop it) when `path` is inside
+/// a running server's folder or contains one.
+pub fn ensure_not_running(state: &ServerProcessState, path: &std::path::Path) -> Result<(), MineServerError> {
+    let Ok(target) = super::path_guard::canonicalize_lenient(path) else { return Ok(()) };
+    match running_servers(state).into_iter().find(|(_, root)| target.starts_with(root) || root.starts_with(&target)) {
+        Some((id, _)) => Err(MineServerError::conflict("The server is running. Stop it first.").with_context(id)),
+        None => Ok(()),
+    }
+}
+
+/// Id of the running server whose folder is `path`.
+pub fn running_server_at(state: &ServerProcessState, path: &std::path::Path) -> Option<String> {
+    let target = std::fs::canonicalize(path).ok()?;
+    running_servers(state).into_iter().find(|(_, root)| *root == target).map(|(id, _)| id)
+}
+
+// Folder name doubles as the display name for notifications
+fn server_display_name(config: &ServerConfig) -> String {
+    std::path::Path::new(&config.path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| config.id.clone())
+}
+
+/// Detects player join/leave lines in console output.
+/// Returns `(joined, player_name)`.
+pub fn parse_player_event(line: &str) -> Option<(bool, String)> {
+    // Bedrock: "[... INFO] Player connected: Steve, xuid: 2535..."
+    if let Some(idx) = line.find("Player connected: ") {
+        let rest = &line[idx + "Player connected: ".len()..];
+        let name = rest.split(',').next().unwrap_or("").trim();
+        return if name.is_empty() { None } else { Some((true, name.to_string())) };
+    }
+    if let Some(idx) = line.find("Player disconnected: ") {
+        let rest = &line[idx + "Player disconnected: ".len()..];
+        let name = rest.split(',').next().unwrap_or("").trim();
+        return if name.is_empty() { None } else { Some((false, name.to_string())) };
     }
 
-    // Auto-Accept EULA
-    let eula_path = server_path.join("eula.txt");
-    // Check if it exists or if it explicitly says false
-    // We just force write eula=true to be robust
-    if let Ok(content) = std::fs::read_to_string(&eula_path) {
-        if !content.contains("eula=true") {
-             let _ = std::fs::write(&eula_path, "eula=true");
+    // Java: "[12:00:00] [Server thread/INFO]: Steve joined the game"
+    let message = line.rsplit("]: ").next().unwrap_or(line).trim();
+    for (suffix, joined) in [(" joined the game", true), (" left the game", false)] {
+        if let Some(name) = message.strip_suffix(suffix) {
+            // Chat messages can't spoof this since they're prefixed with <name>
+            if !name.is_empty() && !name.contains(' ') && !name.starts_with('<') {
+                return Some((joined, name.to_string()));
+            }
         }
-    } else {
-         let _ = std::fs::write(&eula_path, "eula=true");
+    }
+    None
+}
+
+/// Whether a console line says the server finished starting (Java "Done (...)!", Bedrock "Server started.").
+pub fn is_ready_line(line: &str) -> bool {
+    line.contains("Done (") || line.contains("Server started.")
+}
+
+/// Parses the first value of Paper/Spigot `tps` output:
+/// "TPS from last 1m, 5m, 15m: 20.0, 19.98, 19.95" (may contain color codes).
+pub fn parse_tps_line(line: &str) -> Option<f32> {
+    let idx = line.find("TPS from last")?;
+    let values = line[idx..].split_once(": ")?.1;
+    let first = values.split(',').next()?;
+    let cleaned: String = first.chars().filter(|c| c.is_ascii_digit() || *c == '.').collect();
+    cleaned.parse().ok()
+}
+
+/// Sums CPU/RAM of a server process and its direct children (run scripts spawn java as a child).
+pub fn collect_process_usage(sys: &mut sysinfo::System, pid: u32) -> (f32, u64) {
+    use sysinfo::Pid;
+    sys.refresh_processes();
+    let sys_pid = Pid::from_u32(pid);
+
+    let mut total_cpu: f32 = 0.0;
+    let mut total_ram: u64 = 0;
+
+    if let Some(proc) = sys.process(sys_pid) {
+        total_cpu += proc.cpu_usage();
+        total_ram += proc.memory();
+    }
+
+    for (_proc_pid, proc) in sys.processes() {
+        if let Some(parent_pid) = proc.parent() {
+            if parent_pid == sys_pid {
+                total_cpu += proc.cpu_usage();
+                total_ram += proc.memory();
+            }
+        }
+    }
+
+    (total_cpu, total_ram)
+}
+
+// Console lines go to the UI in batches: one event per line lags the webview when a server
+// prints thousands of lines a second (world pregeneration, big modpacks)
+const LOG_BATCH_LINES: usize = 100;
+const LOG_BATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Sends console lines to `server-log:<id>` as arrays, at most LOG_BATCH_INTERVAL after the
+/// first line of a batch, and appends them to server_console.log. stdout and stderr feed the
+/// same thread, so the UI and the file get the lines in one order. `closed` is dropped once both
+/// pipes are at EOF, which wakes the monitor.
+fn spawn_log_writer<E: ServerEventSink>(window: E, id: String, log_path: std::path::PathBuf, closed: mpsc::Sender<()>) -> mpsc::Sender<String> {
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        let _closed = closed;
+        let channel = format!("server-log:{}", id);
+        let mut batch: Vec<String> = Vec::new();
+        let mut deadline: Option<Instant> = None;
+        loop {
+            let received = match deadline {
+                Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            };
+            let closed = match received {
+                Ok(line) => {
+                    deadline.get_or_insert_with(|| Instant::now() + LOG_BATCH_INTERVAL);
+                    batch.push(line);
+                    false
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => false,
+                Err(mpsc::RecvTimeoutError::Disconnected) => true,
+            };
+            let due = deadline.map(|d| Instant::now() >= d).unwrap_or(false);
+            if !batch.is_empty() && (closed || due || batch.len() >= LOG_BATCH_LINES) {
+                // Opened per batch so clearing or deleting the log file doesn't break it
+                if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&log_path) {
+                    let _ = writeln!(file, "{}", batch.join("\n"));
+                }
+                window.emit(&channel, std::mem::take(&mut batch));
+                deadline = None;
+            }
+            if closed {
+                break;
+            }
+        }
+    });
+    sender
+}
+
+const USER_JVM_ARGS_FILE: &str = "user_jvm_args.txt";
+const JVM_ARGS_BEGIN: &str = "# --- MineServer: RAM and startup flags, set in the app (edits here are replaced) ---";
+const JVM_ARGS_END: &str = "# --- end of MineServer settings ---";
+
+/// Puts the RAM setting and startup flags into user_jvm_args.txt, which Forge/NeoForge run
+/// scripts pass to Java. They go in a marked block that is replaced on every start; the
+/// user's other lines stay, except -Xmx/-Xms lines that would fight with the RAM setting.
+fn write_user_jvm_args(server_path: &std::path::Path, ram: u32, flags: Option<&str>) -> Result<(), String> {
+    let file = server_path.join(USER_JVM_ARGS_FILE);
+    let existing = std::fs::read_to_string(&file).unwrap_or_default();
+
+    let mut lines = Vec::new();
+    let mut in_block = false;
+    for line in existing.lines() {
+        let trimmed = line.trim();
+        if trimmed == JVM_ARGS_BEGIN {
+            in_block = true;
+        } else if trimmed == JVM_ARGS_END {
+            in_block = false;
+        } else if !in_block && !trimmed.starts_with("-Xmx") && !trimmed.starts_with("-Xms") {
+            lines.push(line.to_string());
+        }
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(JVM_ARGS_BEGIN.to_string());
+    lines.push(format!("-Xmx{}M", ram));
+    lines.push(format!("-Xms{}M", ram));
+    if let Some(flags) = flags.map(str::trim).filter(|f| !f.is_empty()) {
+        lines.push(flags.to_string());
+    }
+    lines.push(JVM_ARGS_END.to_string());
+
+    let content = lines.join("\n") + "\n";
+    if content != existing {
+        std::fs::write(&file, content).map_err(|e| format!("Failed to write {}: {}", USER_JVM_ARGS_FILE, e))?;
+    }
+    Ok(())
+}
+
+// Internal helper to spawn process
+fn spawn_process_internal<E: ServerEventSink>(
+    window: E,
+    config: &ServerConfig,
+    live_stats: Arc<Mutex<HashMap<String, LiveStats>>>,
+) -> Result<(Child, mpsc::Receiver<()>), MineServerError> {
+    let server_path = std::path::Path::new(&config.path);
+    if !server_path.exists() {
+        return Err(MineServerError::not_found("Server directory not found"));
     }
 
     let mut cmd;
@@ -66,10 +384,16 @@ fn spawn_process_internal(
     } else {
         run_script_unix.exists()
     };
+
+    // EULA: Java servers refuse to start without it. Only written if the user accepted it in the app.
+    if has_run_script || config.jar_file.ends_with(".jar") {
+        ensure_eula(server_path)?;
+    }
+    let gc_options = gc_log::java_options(&config.id, server_path, config.startup_flags.as_deref());
     
     if has_run_script {
         // NeoForge/Forge server - use the bundled run script
-        let _ = window.emit("debug-log", format!("[NeoForge/Forge] Detected run script, using it to start server"));
+        window.emit("debug-log", format!("[NeoForge/Forge] Detected run script, using it to start server"));
         
         #[cfg(target_os = "windows")]
         {
@@ -87,14 +411,19 @@ fn spawn_process_internal(
         
         // Set JVM memory args via environment variable (NeoForge respects this)
         cmd.env("JVM_ARGS", format!("-Xmx{}M -Xms{}M", config.ram, config.ram));
+        if let Some(options) = &gc_options {
+            cmd.env("JDK_JAVA_OPTIONS", options);
+        }
         
-        // Also set JAVA_TOOL_OPTIONS as fallback
-        let mut java_opts = format!("-Xmx{}M -Xms{}M", config.ram, config.ram);
-        if let Some(flags) = &config.startup_flags {
-            java_opts.push_str(" ");
-            java_opts.push_str(flags);
+        // Forge 1.17+ and NeoForge scripts ignore JVM_ARGS and read user_jvm_args.txt instead
+        let script = std::fs::read_to_string(if cfg!(target_os = "windows") { &run_script_win } else { &run_script_unix }).unwrap_or_default();
+        if script.contains(USER_JVM_ARGS_FILE) {
+            if let Err(e) = write_user_jvm_args(server_path, config.ram, config.startup_flags.as_deref()) {
+                window.emit("debug-log", format!("[NeoForge/Forge] RAM and startup flags not applied: {}", e));
+            }
+        } else {
+            window.emit("debug-log", "[NeoForge/Forge] The run script doesn't read user_jvm_args.txt; RAM and startup flags depend on the script".to_string());
         }
-        // Note: We set env but some scripts override. User can edit user_jvm_args.txt for persistent settings.
         
     } else if config.jar_file.ends_with(".jar") || config.jar_file.ends_with(".phar") {
         // Standard server (Vanilla, Paper, Spigot, Fabric, PocketMine)
@@ -109,7 +438,7 @@ fn spawn_process_internal(
              final_bin = local_php.to_string_lossy().to_string();
         }
 
-        let _ = window.emit("debug-log", format!("Launching: {} CWD: {:?}", final_bin, server_path));
+        window.emit("debug-log", format!("Launching: {} CWD: {:?}", final_bin, server_path));
         
         cmd = Command::new(&final_bin);
         cmd.current_dir(server_path); 
@@ -118,6 +447,9 @@ fn spawn_process_internal(
         if !config.jar_file.ends_with(".phar") {
             cmd.arg(format!("-Xmx{}M", config.ram));
             cmd.arg(format!("-Xms{}M", config.ram));
+            if let Some(options) = &gc_options {
+                cmd.env("JDK_JAVA_OPTIONS", options);
+            }
         }
         
         // Add Custom Flags
@@ -150,50 +482,88 @@ fn spawn_process_internal(
     }
 
     let mut child = cmd.spawn().map_err(|e| format!("Failed to start server: {}", e))?;
+
+    if let Ok(mut stats) = live_stats.lock() {
+        stats.insert(config.id.clone(), LiveStats {
+            started_at: Some(chrono::Local::now()),
+            ..Default::default()
+        });
+    }
     
     // Wire up logs
     let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
     let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
     
-    let window_clone = window.clone();
     let id_clone = config.id.clone();
-    let log_path = server_path.join("server_console.log");
+    let server_name = server_display_name(config);
+    let (closed, output_closed) = mpsc::channel();
+    let log_lines = spawn_log_writer(window.clone(), config.id.clone(), server_path.join("server_console.log"), closed);
     
     // Stdout Thread
-    let lp = log_path.clone();
+    let log_lines_out = log_lines.clone();
     thread::spawn(move || {
         let reader = BufReader::new(stdout);
         for line in reader.lines() {
             if let Ok(l) = line {
-                let _ = window_clone.emit(&format!("server-log:{}", id_clone), &l);
-                if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&lp) {
-                    let _ = writeln!(file, "{}", l);
+                if let Some((joined, player)) = parse_player_event(&l) {
+                    let online_count = if let Ok(mut stats) = live_stats.lock() {
+                        let entry = stats.entry(id_clone.clone()).or_default();
+                        if joined {
+                            entry.online_players.insert(player.clone());
+                        } else {
+                            entry.online_players.remove(&player);
+                        }
+                        entry.online_players.len()
+                    } else {
+                        0
+                    };
+                    if joined {
+                        if let Some(xuid) = bedrock_access::parse_xuid(&l) {
+                            bedrock_access::remember_xuid(&player, &xuid);
+                        }
+                        player_stats::record_join(&id_clone, &player, online_count);
+                    } else {
+                        player_stats::record_leave(&id_clone, &player);
+                    }
+                    let event = if joined { NotificationEvent::PlayerJoin } else { NotificationEvent::PlayerLeave };
+                    notifications::dispatch(Notification::new(event, server_name.clone()).with_player(player));
+                } else if let Some(tps) = parse_tps_line(&l) {
+                    if let Ok(mut stats) = live_stats.lock() {
+                        stats.entry(id_clone.clone()).or_default().tps = Some(tps);
+                    }
+                } else if is_ready_line(&l) {
+                    if let Ok(mut stats) = live_stats.lock() {
+                        stats.entry(id_clone.clone()).or_default().ready = true;
+                    }
                 }
+                if let Ok(mut stats) = live_stats.lock() {
+                    let entry = stats.entry(id_clone.clone()).or_default();
+                    if entry.recent_output.len() >= RECENT_OUTPUT_LINES {
+                        entry.recent_output.pop_front();
+                    }
+                    entry.recent_output.push_back(l.clone());
+                    entry.output_seq += 1;
+                }
+                let _ = log_lines_out.send(l);
             }
         }
     });
 
     // Stderr Thread
-    let window_clone_err = window.clone();
-    let id_clone_err = config.id.clone();
-    let lp_err = log_path.clone();
     thread::spawn(move || {
         let reader = BufReader::new(stderr);
         for line in reader.lines() {
             if let Ok(l) = line {
-                 let _ = window_clone_err.emit(&format!("server-log:{}", id_clone_err), &l);
-                 if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&lp_err) {
-                    let _ = writeln!(file, "{}", l);
-                }
+                let _ = log_lines.send(l);
             }
         }
     });
 
-    Ok(child)
+    Ok((child, output_closed))
 }
 
-pub fn start_server_direct(
-    window: WebviewWindow,
+pub fn start_server_direct<E: ServerEventSink>(
+    window: E,
     state: &ServerProcessState,
     id: String,
     path: String,
@@ -202,11 +572,11 @@ pub fn start_server_direct(
     java_path: Option<String>,
     startup_flags: Option<String>,
     auto_restart: Option<bool>,
-) -> Result<String, String> {
+) -> Result<String, MineServerError> {
     let mut processes = state.processes.lock().map_err(|e| e.to_string())?;
 
     if processes.contains_key(&id) {
-        return Err("Server is already running".to_string());
+        return Err(MineServerError::conflict("Server is already running"));
     }
 
     // Reset explicit stop flag
@@ -229,21 +599,32 @@ pub fn start_server_direct(
         configs.insert(id.clone(), config.clone());
     }
 
+    power::warn_if_on_battery(&window, &id);
+
     // Spawn Process
-    let child = spawn_process_internal(window.clone(), &config)?;
+    let (child, output_closed) = spawn_process_internal(window.clone(), &config, state.live_stats.clone())?;
     
     // Store process
     processes.insert(id.clone(), child);
+    if let Ok(mut lifecycle) = state.lifecycle.lock() {
+        lifecycle.insert(id.clone(), ServerLifecycle { started_at: chrono::Local::now(), restarts: 0, stopping: false });
+    }
+    notifications::dispatch(Notification::new(NotificationEvent::ServerStarted, server_display_name(&config)));
+    network_manager::on_server_started(window.clone(), &state.network, &id);
     
     // Spawn Monitor Thread
     let processes_arc = state.processes.clone();
     let explicit_stops_arc = state.explicit_stops.clone();
     let configs_arc = state.configs.clone();
+    let live_stats_arc = state.live_stats.clone();
+    let lifecycle_arc = state.lifecycle.clone();
+    let network = state.network.clone();
     let window_monitor = window.clone();
     let monitor_id = id.clone();
 
     thread::spawn(move || {
-        monitor_server_loop(monitor_id, window_monitor, processes_arc, explicit_stops_arc, configs_arc);
+        monitor_server_loop(monitor_id.clone(), window_monitor, output_closed, processes_arc, explicit_stops_arc, configs_arc, live_stats_arc, lifecycle_arc);
+        network_manager::on_server_stopped(&network, &monitor_id);
     });
 
     Ok("Server started".into())
@@ -251,7 +632,7 @@ pub fn start_server_direct(
 
 #[tauri::command]
 pub fn start_server(
-    window: WebviewWindow,
+    app: AppHandle,
     state: State<'_, ServerProcessState>,
     id: String,
     path: String,
@@ -260,46 +641,46 @@ pub fn start_server(
     java_path: Option<String>,
     startup_flags: Option<String>,
     auto_restart: Option<bool>,
-) -> Result<String, String> {
-    start_server_direct(window, state.inner(), id, path, jar_file, ram, java_path, startup_flags, auto_restart)
+) -> Result<String, MineServerError> {
+    start_server_direct(app, state.inner(), id, path, jar_file, ram, java_path, startup_flags, auto_restart)
 }
 
 // Logic to monitor and restart
-fn monitor_server_loop(
+fn monitor_server_loop<E: ServerEventSink>(
     id: String,
-    window: WebviewWindow,
+    window: E,
+    output_closed: mpsc::Receiver<()>,
     processes: Arc<Mutex<HashMap<String, Child>>>,
     explicit_stops: Arc<Mutex<HashSet<String>>>,
-    configs: Arc<Mutex<HashMap<String, ServerConfig>>>
+    configs: Arc<Mutex<HashMap<String, ServerConfig>>>,
+    live_stats: Arc<Mutex<HashMap<String, LiveStats>>>,
+    lifecycle: Arc<Mutex<HashMap<String, ServerLifecycle>>>,
 ) {
+    let mut started_at = std::time::Instant::now();
+    let mut quick_crashes: u32 = 0;
+    let mut output_closed = Some(output_closed);
     loop {
-        // Polling loop
-        thread::sleep(Duration::from_secs(2));
-
-        let mut is_running = false;
-        
-        // Check Status
-        {
-            if let Ok(mut procs) = processes.lock() {
-                if let Some(child) = procs.get_mut(&id) {
-                    match child.try_wait() {
-                        Ok(Some(_)) => {
-                            is_running = false; // Exited
-                        },
-                        Ok(None) => {
-                            is_running = true; // Still running
-                        },
-                        Err(_) => {
-                            is_running = false;
-                        }
-                    }
-                } else {
-                    // Removed from map -> likely stopped or crashed and cleaned up already
-                    return; 
-                }
+        // Sleeps until the console closes, or for the fallback interval
+        let exiting = match &output_closed {
+            Some(closed) => matches!(closed.recv_timeout(poll_interval()), Err(mpsc::RecvTimeoutError::Disconnected)),
+            None => {
+                thread::sleep(poll_interval());
+                false
             }
+        };
+        if exiting {
+            output_closed = None;
         }
 
+        let deadline = Instant::now() + if exiting { EXIT_GRACE } else { Duration::ZERO };
+        let is_running = loop {
+            match process_exited(&processes, &id) {
+                None => return, // Removed from map -> stopped by the app, which cleans up itself
+                Some(true) => break false,
+                Some(false) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+                Some(false) => break true,
+            }
+        };
         if is_running {
             continue;
         }
@@ -311,8 +692,13 @@ fn monitor_server_loop(
                 procs.remove(&id);
             }
         }
+        if let Ok(mut stats) = live_stats.lock() {
+            stats.remove(&id);
+        }
+        player_stats::close_open_sessions(&id);
+        server_config::flush_pending_properties(&id);
 
-        let _ = window.emit("server-stopped", &id);
+        window.emit("server-stopped", &id);
 
         // Check if explicit stop
         let was_explicit_stop = {
@@ -323,34 +709,68 @@ fn monitor_server_loop(
             }
         };
 
-        if was_explicit_stop {
-            let _ = window.emit(&format!("server-log:{}", id), format!("Server {} stopped (User Initiated).", id));
-            break; // Exit monitor
-        }
-
         // Check Auto Restart
         let config = {
             let confs = configs.lock().unwrap();
             confs.get(&id).cloned()
         };
+        let server_name = config.as_ref().map(server_display_name).unwrap_or_else(|| id.clone());
+
+        if was_explicit_stop {
+            window.emit(&format!("server-log:{}", id), format!("Server {} stopped (User Initiated).", id));
+            notifications::dispatch(Notification::new(NotificationEvent::ServerStopped, server_name));
+            break; // Exit monitor
+        }
+
+        let auto_restart = config.as_ref().map(|c| c.auto_restart).unwrap_or(false);
+        let paused_for_battery = auto_restart && power::paused_for_battery();
+        quick_crashes = if started_at.elapsed() < QUICK_CRASH_WINDOW { quick_crashes + 1 } else { 0 };
+        let crash_loop = auto_restart && quick_crashes > MAX_QUICK_RESTARTS;
+        notifications::dispatch(
+            Notification::new(NotificationEvent::ServerCrashed, server_name.clone())
+                .with_details(match (auto_restart, paused_for_battery, crash_loop) {
+                    (true, _, true) => "Stopped after repeated crashes. Try starting it in safe mode.",
+                    (true, false, false) => "Auto-restarting...",
+                    (true, true, false) => "Auto-restart is paused while on battery.",
+                    _ => "Auto-restart is disabled.",
+                })
+        );
 
         if let Some(cfg) = config {
+            if crash_loop {
+                window.emit(&format!("server-log:{}", id), format!("Server {} crashed {} times in a row. Auto-restart stopped.", id, quick_crashes));
+                window.emit("server-crash-loop", serde_json::json!({ "serverId": id, "crashes": quick_crashes }));
+                break;
+            }
+            if paused_for_battery {
+                window.emit(&format!("server-log:{}", id), format!("Server {} stopped. Auto-restart is paused while on battery.", id));
+                break;
+            }
             if cfg.auto_restart {
-                let _ = window.emit(&format!("server-log:{}", id), format!("Server {} crashed/stopped. Auto-restarting in 3s...", id));
+                window.emit(&format!("server-log:{}", id), format!("Server {} crashed/stopped. Auto-restarting in {}s...", id, RESTART_DELAY.as_secs()));
                 // Wait
-                thread::sleep(Duration::from_secs(3));
+                thread::sleep(RESTART_DELAY);
                 
                 // Restart
-                match spawn_process_internal(window.clone(), &cfg) {
-                    Ok(new_child) => {
-                        let _ = window.emit("server-started", &id); // Notify UI
+                match spawn_process_internal(window.clone(), &cfg, live_stats.clone()) {
+                    Ok((new_child, closed)) => {
+                        started_at = std::time::Instant::now();
+                        output_closed = Some(closed);
+                        window.emit("server-started", &id); // Notify UI
+                        notifications::dispatch(Notification::new(NotificationEvent::ServerStarted, server_name.clone()));
                         if let Ok(mut procs) = processes.lock() {
                             procs.insert(id.clone(), new_child);
                         }
+                        if let Ok(mut lifecycle) = lifecycle.lock() {
+                            if let Some(entry) = lifecycle.get_mut(&id) {
+                                entry.started_at = chrono::Local::now();
+                                entry.restarts += 1;
+                            }
+                        }
                         // Loop continues to monitor new process
                     },
                     Err(e) => {
-                        let _ = window.emit(&format!("server-log:{}", id), format!("Failed to auto-restart: {}", e));
+                        window.emit(&format!("server-log:{}", id), format!("Failed to auto-restart: {}", e));
                         break;
                     }
                 }
@@ -364,45 +784,91 @@ fn monitor_server_loop(
     }
 }
 
+/// Whether a monitored process has exited; None once it is no longer in the map.
+fn process_exited(processes: &Mutex<HashMap<String, Child>>, id: &str) -> Option<bool> {
+    let Ok(mut procs) = processes.lock() else { return Some(true) };
+    let child = procs.get_mut(id)?;
+    Some(!matches!(child.try_wait(), Ok(None)))
+}
+
 pub fn stop_server_direct(
     state: &ServerProcessState,
     id: String
-) -> Result<String, String> {
+) -> Result<String, MineServerError> {
     // 1. Mark as explicit stop
     {
         let mut express = state.explicit_stops.lock().map_err(|e| e.to_string())?;
         express.insert(id.clone());
     }
 
+    // The process map stays locked while waiting for the exit, so status reads check this first
+    let _stopping = StoppingFlag::set(state, &id);
     let mut processes = state.processes.lock().map_err(|e| e.to_string())?;
 
     if let Some(mut child) = processes.remove(&id) {
+        network_manager::on_server_stopped(&state.network, &id);
+
         // Try graceful stop
         if let Some(mut stdin) = child.stdin.take() {
              // For Java servers, "stop" is standard. For Bedrock, also "stop".
             let _ = writeln!(stdin, "stop");
         }
 
-        // Wait up to 10 seconds
-        let start = std::time::Instant::now();
-        loop {
-            match child.try_wait() {
-                Ok(Some(_)) => return Ok("Server stopped gracefully".into()),
-                Ok(None) => {
-                    if start.elapsed().as_secs() > 10 {
-                        let _ = child.kill();
-                        return Ok("Server stopped (Forced)".into());
-                    }
-                    thread::sleep(Duration::from_millis(500));
-                },
-                Err(_) => {
-                     let _ = child.kill();
-                     return Ok("Server stopped".into());
+        let message = wait_for_exit(&mut child, STOP_TIMEOUT);
+        server_config::flush_pending_properties(&id);
+        Ok(message.into())
+    } else {
+        Err(MineServerError::conflict("Server not running"))
+    }
+}
+
+/// Waits for a server that was told to stop, killing it after `timeout`.
+fn wait_for_exit(child: &mut Child, timeout: Duration) -> &'static str {
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return "Server stopped gracefully",
+            Ok(None) => {
+                if start.elapsed() > timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return "Server stopped (Forced)";
                 }
+                thread::sleep(Duration::from_millis(500).min(timeout / 10));
+            },
+            Err(_) => {
+                let _ = child.kill();
+                return "Server stopped";
             }
         }
-    } else {
-        Err("Server not running".into())
+    }
+}
+
+/// Marks a server as stopping until dropped.
+struct StoppingFlag<'a> {
+    lifecycle: &'a Mutex<HashMap<String, ServerLifecycle>>,
+    id: &'a str,
+}
+
+impl<'a> StoppingFlag<'a> {
+    fn set(state: &'a ServerProcessState, id: &'a str) -> Self {
+        let flag = Self { lifecycle: &state.lifecycle, id };
+        flag.mark(true);
+        flag
+    }
+
+    fn mark(&self, stopping: bool) {
+        if let Ok(mut lifecycle) = self.lifecycle.lock() {
+            if let Some(entry) = lifecycle.get_mut(self.id) {
+                entry.stopping = stopping;
+            }
+        }
+    }
+}
+
+impl Drop for StoppingFlag<'_> {
+    fn drop(&mut self) {
+        self.mark(false);
     }
 }
 
@@ -410,7 +876,7 @@ pub fn stop_server_direct(
 pub fn stop_server(
     state: State<'_, ServerProcessState>,
     id: String
-) -> Result<String, String> {
+) -> Result<String, MineServerError> {
     stop_server_direct(state.inner(), id)
 }
 
@@ -430,13 +896,49 @@ pub fn send_server_command_direct(
     Err("Server not running or stdin unavailable".into())
 }
 
+/// Console lines printed after `seq` (as returned by a previous call), plus the new sequence number.
+pub fn console_output_since(state: &ServerProcessState, id: &str, seq: u64) -> (u64, Vec<String>) {
+    let stats = match state.live_stats.lock() {
+        Ok(s) => s,
+        Err(_) => return (seq, vec![]),
+    };
+    match stats.get(id) {
+        Some(entry) => {
+            let new_lines = (entry.output_seq.saturating_sub(seq) as usize).min(entry.recent_output.len());
+            let lines = entry.recent_output.iter().skip(entry.recent_output.len() - new_lines).cloned().collect();
+            (entry.output_seq, lines)
+        },
+        None => (seq, vec![]),
+    }
+}
+
+/// Re-sends a running server's status and recent console lines to the calling window only, so a
+/// window opened after the server started catches up. Call it once subscribed to the channels.
+#[tauri::command]
+pub fn replay_server_events(window: WebviewWindow, state: State<'_, ServerProcessState>, id: String) -> Result<(), MineServerError> {
+    let running = state.processes.lock().map(|p| p.contains_key(&id)).unwrap_or(false);
+    if !running {
+        return Ok(());
+    }
+    let lines: Vec<String> = state.live_stats.lock().ok()
+        .and_then(|stats| stats.get(&id).map(|s| s.recent_output.iter().cloned().collect()))
+        .unwrap_or_default();
+
+    let label = window.label().to_string();
+    window.emit_to(label.as_str(), "server-started", &id).map_err(|e| e.to_string())?;
+    if !lines.is_empty() {
+        window.emit_to(label.as_str(), &format!("server-log:{}", id), lines).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub fn send_server_command(
     state: State<'_, ServerProcessState>,
     id: String,
     command: String
-) -> Result<(), String> {
-    send_server_command_direct(state.inner(), id, command)
+) -> Result<(), MineServerError> {
+    send_server_command_direct(state.inner(), id, command).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -452,9 +954,18 @@ pub fn is_server_running(
 }
 
 #[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ResourceUsage {
     cpu: f32,
     ram: u64,
+    uptime_secs: u64,
+}
+
+/// Seconds since the running process of a server started.
+fn uptime_secs(state: &ServerProcessState, id: &str) -> u64 {
+    state.lifecycle.lock().ok()
+        .and_then(|l| l.get(id).map(|e| chrono::Local::now().signed_duration_since(e.started_at).num_seconds().max(0) as u64))
+        .unwrap_or(0)
 }
 
 #[tauri::command]
@@ -462,41 +973,63 @@ pub fn get_server_resource_usage(
     proc_state: State<'_, ServerProcessState>,
     sys_state: State<'_, super::system::SystemState>,
     id: String
-) -> Result<ResourceUsage, String> {
+) -> Result<ResourceUsage, MineServerError> {
     let processes = proc_state.processes.lock().map_err(|e| e.to_string())?;
     
     if let Some(child) = processes.get(&id) {
         let pid = child.id(); 
         
         let mut sys = sys_state.sys.lock().map_err(|e| e.to_string())?;
-        use sysinfo::Pid;
-        sys.refresh_processes();
-        let sys_pid = Pid::from_u32(pid);
-        
-        let mut total_cpu: f32 = 0.0;
-        let mut total_ram: u64 = 0;
-        
-        if let Some(proc) = sys.process(sys_pid) {
-            total_cpu += proc.cpu_usage();
-            total_ram += proc.memory();
-        }
-        
-        for (_proc_pid, proc) in sys.processes() {
-            if let Some(parent_pid) = proc.parent() {
-                if parent_pid == sys_pid {
-                    total_cpu += proc.cpu_usage();
-                    total_ram += proc.memory();
-                }
-            }
-        }
+        let (total_cpu, total_ram) = collect_process_usage(&mut sys, pid);
         
         return Ok(ResourceUsage {
             cpu: total_cpu,
             ram: total_ram,
+            uptime_secs: uptime_secs(&proc_state, &id),
         });
     }
     
-    Ok(ResourceUsage { cpu: 0.0, ram: 0 })
+    Ok(ResourceUsage { cpu: 0.0, ram: 0, uptime_secs: 0 })
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub id: String,
+    pub state: String, // "stopped", "starting", "running" or "stopping"
+    pub pid: Option<u32>,
+    pub started_at: Option<String>, // Last start, also once stopped
+    pub uptime_secs: u64,
+    pub restart_count: u32, // Auto-restarts since the user started it
+}
+
+pub fn server_status(state: &ServerProcessState, id: &str) -> ServerStatus {
+    let lifecycle = state.lifecycle.lock().ok().and_then(|l| l.get(id).cloned());
+    let stopping = lifecycle.as_ref().is_some_and(|l| l.stopping);
+    // A stop holds the process map until the server exits, so don't wait on it then
+    let pid = if stopping { None } else { state.processes.lock().ok().and_then(|p| p.get(id).map(|c| c.id())) };
+    let ready = state.live_stats.lock().ok().and_then(|s| s.get(id).map(|s| s.ready)).unwrap_or(false);
+
+    let status = match (stopping, pid.is_some(), ready) {
+        (true, _, _) => "stopping",
+        (false, true, true) => "running",
+        (false, true, false) => "starting",
+        (false, false, _) => "stopped",
+    };
+    ServerStatus {
+        id: id.to_string(),
+        state: status.to_string(),
+        pid,
+        started_at: lifecycle.as_ref().map(|l| l.started_at.to_rfc3339()),
+        uptime_secs: if pid.is_some() { uptime_secs(state, id) } else { 0 },
+        restart_count: lifecycle.map(|l| l.restarts).unwrap_or(0),
+    }
+}
+
+/// State, PID, uptime and restart count of a server.
+#[tauri::command]
+pub fn get_server_status(state: State<'_, ServerProcessState>, id: String) -> ServerStatus {
+    server_status(state.inner(), &id)
 }
 
 #[tauri::command]
@@ -511,9 +1044,157 @@ pub fn get_running_servers(
 }
 
 #[tauri::command]
-pub fn clear_log_file(path: String) -> Result<(), String> {
+pub fn clear_log_file(path: String) -> Result<(), MineServerError> {
     let log_path = std::path::Path::new(&path).join("server_console.log");
     std::fs::File::create(&log_path)
         .map_err(|e| format!("Failed to clear log file: {}", e))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{isolated_home, temp_dir, wait_until, FakeServer, RecordingSink};
+
+    const WAIT: Duration = Duration::from_secs(10);
+
+    fn start(state: &ServerProcessState, sink: &RecordingSink, server: &FakeServer) {
+        let c = server.config.clone();
+        start_server_direct(sink.clone(), state, c.id, c.path, c.jar_file, c.ram, c.java_path, c.startup_flags, Some(c.auto_restart))
+            .expect("start fake server");
+    }
+
+    fn status(state: &ServerProcessState, server: &FakeServer) -> String {
+        server_status(state, &server.config.id).state
+    }
+
+    #[test]
+    fn parses_player_events() {
+        assert_eq!(parse_player_event("[12:00:00] [Server thread/INFO]: Steve joined the game"), Some((true, "Steve".to_string())));
+        assert_eq!(parse_player_event("[12:00:00] [Server thread/INFO]: Steve left the game"), Some((false, "Steve".to_string())));
+        assert_eq!(parse_player_event("[INFO] Player connected: Alex, xuid: 2535"), Some((true, "Alex".to_string())));
+        // Chat can't fake a join
+        assert_eq!(parse_player_event("[Server thread/INFO]: <Steve> Bob joined the game"), None);
+        assert!(is_ready_line("[Server thread/INFO]: Done (3.2s)! For help, type \"help\""));
+        assert_eq!(parse_tps_line("TPS from last 1m, 5m, 15m: §a19.5, 20.0, 20.0"), Some(19.5));
+    }
+
+    #[test]
+    fn user_jvm_args_keep_user_lines() {
+        let dir = temp_dir("jvm-args");
+        std::fs::write(dir.join(USER_JVM_ARGS_FILE), "# Xmx and Xms set the memory\n-Xmx2G\n-XX:+UseG1GC\n").unwrap();
+        write_user_jvm_args(&dir, 4096, Some("-Dfoo=1")).unwrap();
+        write_user_jvm_args(&dir, 6144, None).unwrap();
+
+        let content = std::fs::read_to_string(dir.join(USER_JVM_ARGS_FILE)).unwrap();
+        assert!(content.contains("# Xmx and Xms set the memory") && content.contains("-XX:+UseG1GC"));
+        assert!(!content.contains("-Xmx2G") && !content.contains("-Dfoo=1"));
+        assert_eq!(content.matches(JVM_ARGS_BEGIN).count(), 1);
+        assert!(content.contains("-Xmx6144M\n-Xms6144M\n"));
+    }
+
+    #[test]
+    fn starts_and_becomes_ready() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&["startup_ms=200"]));
+        start(&state, &sink, &server);
+        assert!(matches!(status(&state, &server).as_str(), "starting" | "running"));
+        assert!(wait_until(WAIT, || status(&state, &server) == "running"));
+        assert!(start_server_direct(sink.clone(), &state, server.config.id.clone(), server.config.path.clone(), server.config.jar_file.clone(), 512, None, None, None).is_err());
+
+        stop_server_direct(&state, server.config.id.clone()).unwrap();
+        assert_eq!(status(&state, &server), "stopped");
+    }
+
+    #[test]
+    fn stop_is_graceful() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&[]));
+        start(&state, &sink, &server);
+        assert!(wait_until(WAIT, || status(&state, &server) == "running"));
+
+        assert_eq!(stop_server_direct(&state, server.config.id.clone()).unwrap(), "Server stopped gracefully");
+        assert!(stop_server_direct(&state, server.config.id.clone()).is_err());
+    }
+
+    #[test]
+    fn stop_kills_a_server_that_ignores_it() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&["ignore_stop"]));
+        start(&state, &sink, &server);
+        assert!(wait_until(WAIT, || status(&state, &server) == "running"));
+
+        let started = Instant::now();
+        assert_eq!(stop_server_direct(&state, server.config.id.clone()).unwrap(), "Server stopped (Forced)");
+        assert!(started.elapsed() >= STOP_TIMEOUT);
+        assert_eq!(status(&state, &server), "stopped");
+    }
+
+    #[test]
+    fn crash_is_auto_restarted() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&[]).with_auto_restart());
+        start(&state, &sink, &server);
+        assert!(wait_until(WAIT, || status(&state, &server) == "running"));
+
+        send_server_command_direct(&state, server.config.id.clone(), "crash".to_string()).unwrap();
+        assert!(wait_until(WAIT, || server.starts() == 2 && status(&state, &server) == "running"));
+        assert_eq!(server_status(&state, &server.config.id).restart_count, 1);
+
+        // A stop by the user is not a crash
+        stop_server_direct(&state, server.config.id.clone()).unwrap();
+        std::thread::sleep(RESTART_DELAY + Duration::from_millis(500));
+        assert_eq!(server.starts(), 2);
+        assert_eq!(status(&state, &server), "stopped");
+    }
+
+    #[test]
+    fn crash_without_auto_restart_stays_stopped() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&[]));
+        start(&state, &sink, &server);
+        assert!(wait_until(WAIT, || status(&state, &server) == "running"));
+
+        send_server_command_direct(&state, server.config.id.clone(), "crash".to_string()).unwrap();
+        assert!(wait_until(WAIT, || !sink.events("server-stopped").is_empty()));
+        std::thread::sleep(RESTART_DELAY + Duration::from_millis(500));
+        assert_eq!(server.starts(), 1);
+        assert_eq!(status(&state, &server), "stopped");
+    }
+
+    #[test]
+    fn crash_loop_gives_up() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&["crash_on_start"]).with_auto_restart());
+        start(&state, &sink, &server);
+
+        assert!(wait_until(WAIT, || !sink.events("server-crash-loop").is_empty()));
+        assert_eq!(server.starts() as u32, MAX_QUICK_RESTARTS + 1);
+        assert_eq!(status(&state, &server), "stopped");
+    }
+
+    #[test]
+    fn console_lines_arrive_in_order_and_track_players() {
+        isolated_home();
+        let (state, sink, server) = (ServerProcessState::new(), RecordingSink::default(), FakeServer::new(&[]));
+        let id = server.config.id.clone();
+        start(&state, &sink, &server);
+        assert!(wait_until(WAIT, || status(&state, &server) == "running"));
+
+        send_server_command_direct(&state, id.clone(), "join Steve".to_string()).unwrap();
+        send_server_command_direct(&state, id.clone(), "spam 500".to_string()).unwrap();
+        assert!(wait_until(WAIT, || sink.console_lines(&id).iter().any(|l| l.ends_with("line 499"))));
+        let online = state.live_stats.lock().unwrap().get(&id).map(|s| s.online_players.clone()).unwrap_or_default();
+        assert!(online.contains("Steve"));
+
+        let lines = sink.console_lines(&id);
+        let numbers: Vec<u32> = lines.iter().filter_map(|l| l.rsplit_once("line ")?.1.parse().ok()).collect();
+        assert_eq!(numbers, (0..500).collect::<Vec<_>>());
+        // Batched, not one event per line
+        assert!(sink.events(&format!("server-log:{}", id)).len() < lines.len() / 10);
+
+        stop_server_direct(&state, id.clone()).unwrap();
+        let log = std::fs::read_to_string(server.dir.join("server_console.log")).unwrap();
+        assert!(log.contains("Steve joined the game") && log.contains("line 499"));
+    }
+}
@@ -0,0 +1,207 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::State;
+use super::audit::audited;
+use super::bisect::bisect_active;
+use super::downloader::resolve_url;
+use super::http::HttpState;
+use super::path_guard::validate_path;
+use super::registry::load_registry;
+use super::runner::{ServerProcessState, ensure_not_running};
+use super::error::MineServerError;
+
+// Safe mode for servers stuck in a crash loop: either every plugin and mod is disabled (renamed
+// to `.jar.disabled`), or the server jar is swapped for the vanilla jar of the same version.
+// What was changed is recorded in `<server>/.mineserver-safe-mode.json` so
+// `restore_from_safe_mode` can undo exactly that, leaving addons the user disabled alone.
+
+const SAFE_MODE_FILE: &str = ".mineserver-safe-mode.json";
+const ADDON_DIRS: [&str; 2] = ["plugins", "mods"];
+const SAVED_JAR_SUFFIX: &str = ".safe-mode";
+// Server types without a vanilla Java jar to swap in
+const NON_JAVA_TYPES: [&str; 3] = ["bedrock", "pocketmine", "nukkit"];
+// Start scripts the runner prefers over the jar (Forge/NeoForge)
+const RUN_SCRIPTS: [&str; 2] = ["run.sh", "run.bat"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeModeInfo {
+    pub mode: String, // "no_addons" or "vanilla"
+    pub entered_at: String,
+    #[serde(default)]
+    pub disabled: Vec<String>, // Relative paths of the jars that were enabled, e.g. "plugins/Foo.jar"
+    #[serde(default)]
+    pub server_jar: Option<String>, // Jar replaced by the vanilla one, kept as "<jar>.safe-mode"
+}
+
+fn read_info(root: &Path) -> Option<SafeModeInfo> {
+    fs::read_to_string(root.join(SAFE_MODE_FILE)).ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+fn write_info(root: &Path, info: &SafeModeInfo) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(info)
+        .map_err(|e| format!("Failed to serialize safe mode state: {}", e))?;
+    fs::write(root.join(SAFE_MODE_FILE), content)
+        .map_err(|e| format!("Failed to save safe mode state: {}", e))
+}
+
+pub(crate) fn safe_mode_active(root: &Path) -> bool {
+    root.join(SAFE_MODE_FILE).exists()
+}
+
+/// Enabled plugin and mod jars, as paths relative to the server root ("plugins/Foo.jar").
+pub(crate) fn enabled_addons(root: &Path) -> Vec<String> {
+    let mut addons: Vec<String> = ADDON_DIRS.iter()
+        .flat_map(|dir| {
+            fs::read_dir(root.join(dir))
+                .map(|rd| rd.flatten()
+                    .filter(|e| e.path().is_file())
+                    .filter_map(|e| e.file_name().to_str().map(String::from))
+                    .filter(|name| name.ends_with(".jar"))
+                    .map(|name| format!("{}/{}", dir, name))
+                    .collect::<Vec<_>>())
+                .unwrap_or_default()
+        })
+        .collect();
+    addons.sort();
+    addons
+}
+
+/// Renames `plugins/Foo.jar` to `plugins/Foo.jar.disabled` and back.
+pub(crate) fn set_addon_enabled(root: &Path, addon: &str, enabled: bool) -> Result<(), String> {
+    let jar = root.join(addon);
+    let disabled = root.join(format!("{}.disabled", addon));
+    let (from, to) = if enabled { (disabled, jar) } else { (jar, disabled) };
+    if !from.exists() {
+        return Ok(()); // Deleted or toggled by hand since
+    }
+    fs::rename(&from, &to).map_err(|e| format!("Failed to rename {}: {}", addon, e))
+}
+
+/// The server type, the jar the server starts with and its game version, from the registry.
+fn registered_jar_and_version(root: &Path) -> Option<(String, String, String)> {
+    load_registry().ok()?
+        .into_iter()
+        .find(|s| fs::canonicalize(&s.path).map(|p| p == root).unwrap_or(false))
+        .map(|s| (s.server_type, s.jar_file, s.version))
+}
+
+fn saved_jar_path(root: &Path, jar: &str) -> PathBuf {
+    root.join(format!("{}{}", jar, SAVED_JAR_SUFFIX))
+}
+
+#[tauri::command]
+pub fn get_safe_mode_status(server_path: String) -> Result<Option<SafeModeInfo>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    Ok(read_info(&root))
+}
+
+/// Puts a stopped server into safe mode. `mode` is "no_addons" (disable every plugin and mod)
+/// or "vanilla" (run the vanilla jar of the registered game version). Start it as usual afterwards.
+#[tauri::command]
+pub async fn enter_safe_mode(
+    http: State<'_, HttpState>,
+    state: State<'_, ServerProcessState>,
+    server_path: String,
+    mode: String,
+) -> Result<SafeModeInfo, MineServerError> {
+    let root = validate_path(&server_path)?;
+    ensure_not_running(state.inner(), &root)?;
+    if let Some(info) = read_info(&root) {
+        return Err(MineServerError::conflict(format!("The server is already in safe mode ({})", info.mode)));
+    }
+    if bisect_active(&root) {
+        return Err(MineServerError::conflict("Finish or cancel the plugin search first"));
+    }
+
+    let mut info = SafeModeInfo {
+        mode: mode.clone(),
+        entered_at: chrono::Local::now().to_rfc3339(),
+        disabled: vec![],
+        server_jar: None,
+    };
+    match mode.as_str() {
+        "no_addons" => {
+            info.disabled = enabled_addons(&root);
+            if info.disabled.is_empty() {
+                return Err(MineServerError::validation("There are no enabled plugins or mods to disable"));
+            }
+            audited("enter_safe_mode", json!({ "serverPath": server_path, "mode": mode }), || {
+                // Recorded first, so jars renamed before a failure can still be restored
+                write_info(&root, &info)?;
+                for addon in &info.disabled {
+                    set_addon_enabled(&root, addon, false)?;
+                }
+                Ok(())
+            })?;
+        },
+        "vanilla" => {
+            let (server_type, jar, version) = registered_jar_and_version(&root)
+                .filter(|(_, _, version)| !version.is_empty())
+                .ok_or_else(|| MineServerError::validation("The server's game version is unknown"))?;
+            if NON_JAVA_TYPES.contains(&server_type.as_str()) || !jar.ends_with(".jar") {
+                return Err(MineServerError::validation("Vanilla safe mode is only available for Java servers"));
+            }
+            if RUN_SCRIPTS.iter().any(|script| root.join(script).is_file()) {
+                // The runner would keep starting the modded server through its script
+                return Err(MineServerError::validation("This server starts through run.sh/run.bat (Forge/NeoForge), so the vanilla jar would not be used. Disable the mods instead"));
+            }
+            if !root.join(&jar).is_file() {
+                return Err(MineServerError::not_found(format!("{} not found", jar)));
+            }
+
+            let client = http.client();
+            let url = resolve_url(&client, "vanilla", &version).await?;
+            let resp = client.get(&url).send().await?;
+            if !resp.status().is_success() {
+                return Err(MineServerError::external("Mojang", format!("Download failed with status: {}", resp.status())));
+            }
+            let bytes = resp.bytes().await?;
+
+            info.server_jar = Some(jar.clone());
+            audited("enter_safe_mode", json!({ "serverPath": server_path, "mode": mode, "version": version }), || {
+                write_info(&root, &info)?;
+                if let Err(e) = fs::rename(root.join(&jar), saved_jar_path(&root, &jar)) {
+                    let _ = fs::remove_file(root.join(SAFE_MODE_FILE));
+                    return Err(format!("Failed to set aside {}: {}", jar, e));
+                }
+                if let Err(e) = fs::write(root.join(&jar), &bytes) {
+                    let _ = fs::rename(saved_jar_path(&root, &jar), root.join(&jar));
+                    let _ = fs::remove_file(root.join(SAFE_MODE_FILE));
+                    return Err(format!("Failed to write the vanilla jar: {}", e));
+                }
+                Ok(())
+            })?;
+        },
+        other => return Err(MineServerError::validation(format!("Unknown safe mode: {}", other))),
+    }
+    Ok(info)
+}
+
+/// Undoes `enter_safe_mode`: re-enables the addons it disabled or puts the original jar back.
+#[tauri::command]
+pub fn restore_from_safe_mode(state: State<'_, ServerProcessState>, server_path: String) -> Result<SafeModeInfo, MineServerError> {
+    let root = validate_path(&server_path)?;
+    ensure_not_running(state.inner(), &root)?;
+    let info = read_info(&root)
+        .ok_or_else(|| MineServerError::not_found("The server is not in safe mode"))?;
+
+    audited("restore_from_safe_mode", json!({ "serverPath": server_path, "mode": info.mode }), || {
+        for addon in &info.disabled {
+            set_addon_enabled(&root, addon, true)?;
+        }
+        if let Some(jar) = &info.server_jar {
+            let saved = saved_jar_path(&root, jar);
+            if saved.is_file() {
+                fs::rename(&saved, root.join(jar))
+                    .map_err(|e| format!("Failed to restore {}: {}", jar, e))?;
+            }
+        }
+        fs::remove_file(root.join(SAFE_MODE_FILE))
+            .map_err(|e| format!("Failed to clear safe mode state: {}", e))
+    })?;
+    Ok(info)
+}
@@ -2,8 +2,19 @@ use std::path::Path;
 use std::fs;
 use serde::Serialize;
 use zip::write::FileOptions;
-use std::io::{Read, Write};
+use std::io::Read;
 use walkdir::WalkDir;
+use super::path_guard::{validate_path, validate_path_inside_root, validate_server_root};
+use super::archive::{zip_file, zip_write_error};
+use super::extract::extract_archive_file;
+use super::config_validation::validate_config;
+use super::audit::audited;
+use super::trash::{move_to_trash, TRASH_DIR};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use super::operations::{CancelToken, Operation, OperationState};
+use super::runner::{ServerProcessState, ensure_not_running};
+use super::error::MineServerError;
 
 #[derive(Serialize)]
 pub struct FileEntry {
@@ -13,35 +24,43 @@ pub struct FileEntry {
 }
 
 #[tauri::command]
-pub fn delete_server(path: String) -> Result<(), String> {
-    let server_path = Path::new(&path);
-    
-    // Safety check: ensure we are deleting something that looks like a server in our expected location
-    // This is a basic check; you might want to make it more robust
-    if !path.contains("Servers") && !path.contains("servers") {
-        return Err("Safety check failed: Path does not appear to be in a Servers directory".to_string());
-    }
+pub async fn delete_server(state: State<'_, ServerProcessState>, path: String) -> Result<(), MineServerError> {
+    ensure_not_running(state.inner(), Path::new(&path))?;
+    tokio::task::spawn_blocking(move || delete_server_sync(path))
+        .await?
+        .map_err(Into::into)
+}
 
-    if server_path.exists() {
+fn delete_server_sync(path: String) -> Result<(), String> {
+    audited("delete_server", json!({ "path": path }), || {
+        if !Path::new(&path).exists() {
+            return Ok(());
+        }
+
+        // Safety check: only registered server folders (or folders in the app data dir)
+        let server_path = validate_server_root(&path)?;
         std::fs::remove_dir_all(server_path).map_err(|e| e.to_string())?;
-    }
     
-    Ok(())
+        Ok(())
+    })
 }
 
 #[tauri::command]
-pub fn get_server_files(path: String) -> Result<Vec<FileEntry>, String> {
+pub fn get_server_files(path: String) -> Result<Vec<FileEntry>, MineServerError> {
     let mut entries = Vec::new();
-    let dir_path = Path::new(&path);
+    let dir_path = validate_path(&path)?;
 
     if !dir_path.exists() {
-        return Err("Directory not found".to_string());
+        return Err(MineServerError::not_found("Directory not found"));
     }
 
-    let read_dir = fs::read_dir(dir_path).map_err(|e| e.to_string())?;
+    let read_dir = fs::read_dir(&dir_path).map_err(|e| e.to_string())?;
 
     for entry in read_dir {
         if let Ok(entry) = entry {
+            if entry.file_name() == TRASH_DIR {
+                continue;
+            }
             let metadata = entry.metadata().map_err(|e| e.to_string())?;
             entries.push(FileEntry {
                 name: entry.file_name().to_string_lossy().to_string(),
@@ -63,127 +82,277 @@ pub fn get_server_files(path: String) -> Result<Vec<FileEntry>, String> {
     Ok(entries)
 }
 
+// Files above this size must be read with read_file_range
+const MAX_FULL_READ_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_RANGE_BYTES: u64 = 4 * 1024 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChunk {
+    content: String,
+    offset: u64,
+    next_offset: u64, // Where the next read should start (may stop short of a split UTF-8 character)
+    total_size: u64,
+    eof: bool,
+}
+
+// NUL bytes never appear in the text files the editor handles (configs, logs, scripts)
+fn looks_binary(sample: &[u8]) -> bool {
+    sample.contains(&0)
+}
+
 #[tauri::command]
-pub fn read_server_file(path: String) -> Result<String, String> {
-    let file_path = Path::new(&path);
+pub fn read_server_file(path: String) -> Result<String, MineServerError> {
+    let file_path = validate_path(&path)?;
     if !file_path.exists() {
-        return Err("File not found".to_string());
+        return Err(MineServerError::not_found("File not found"));
     }
-    
-    // Basic text file check could go here, for now assuming text
-    std::fs::read_to_string(file_path).map_err(|e| e.to_string())
+
+    let size = fs::metadata(&file_path).map_err(|e| e.to_string())?.len();
+    if size > MAX_FULL_READ_BYTES {
+        return Err(MineServerError::validation(format!("File is {} MB, open it in chunks instead", size / 1024 / 1024)).with_context("FILE_TOO_LARGE"));
+    }
+
+    let bytes = fs::read(&file_path).map_err(|e| e.to_string())?;
+    if looks_binary(&bytes[..bytes.len().min(BINARY_SNIFF_BYTES)]) {
+        return Err(MineServerError::validation("This file is not a text file").with_context("BINARY_FILE"));
+    }
+    String::from_utf8(bytes).map_err(|_| MineServerError::validation("This file is not valid UTF-8 text").with_context("BINARY_FILE"))
 }
 
+/// Reads up to `length` bytes (max 4 MB) of a text file starting at `offset`.
 #[tauri::command]
-pub fn write_server_file(path: String, content: String) -> Result<(), String> {
-    let file_path = Path::new(&path);
-    // Simple safety check again
-    if !path.contains("Servers") && !path.contains("servers") {
-        return Err("Safety check failed: Path does not appear to be in a Servers directory".to_string());
+pub fn read_file_range(path: String, offset: u64, length: u64) -> Result<FileChunk, MineServerError> {
+    use std::io::{Seek, SeekFrom};
+
+    let file_path = validate_path(&path)?;
+    let mut file = fs::File::open(&file_path).map_err(|e| e.to_string())?;
+    let total_size = file.metadata().map_err(|e| e.to_string())?.len();
+    let offset = offset.min(total_size);
+
+    file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+    let mut buffer = Vec::new();
+    file.take(length.min(MAX_RANGE_BYTES)).read_to_end(&mut buffer).map_err(|e| e.to_string())?;
+
+    if offset == 0 && looks_binary(&buffer[..buffer.len().min(BINARY_SNIFF_BYTES)]) {
+        return Err(MineServerError::validation("This file is not a text file").with_context("BINARY_FILE"));
     }
 
-    std::fs::write(file_path, content).map_err(|e| e.to_string())
+    // Drop a trailing partial UTF-8 sequence; the next chunk starts with it
+    let valid_len = match std::str::from_utf8(&buffer) {
+        Ok(_) => buffer.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => buffer.len(),
+    };
+    let content = String::from_utf8_lossy(&buffer[..valid_len]).to_string();
+    let next_offset = offset + valid_len as u64;
+
+    Ok(FileChunk {
+        content,
+        offset,
+        next_offset,
+        total_size,
+        eof: next_offset >= total_size,
+    })
 }
 
+/// Config files (.yml/.json/.toml/.properties) are syntax-checked first; a broken one is
+/// rejected with a `CONFIG_SYNTAX_ERROR:` error unless `skip_validation` is set.
 #[tauri::command]
-pub fn write_binary_file(path: String, content: Vec<u8>) -> Result<(), String> {
-    let file_path = Path::new(&path);
-    if !path.contains("Servers") && !path.contains("servers") {
-        return Err("Safety check failed".to_string());
+pub fn write_server_file(path: String, content: String, skip_validation: Option<bool>) -> Result<(), MineServerError> {
+    let file_path = validate_path(&path)?;
+    if !skip_validation.unwrap_or(false) {
+        validate_config(&file_path, &content).map_err(|e| e.to_command_error())?;
     }
-    std::fs::write(file_path, content).map_err(|e| e.to_string())
+    std::fs::write(file_path, content).map_err(Into::into)
 }
 
 #[tauri::command]
-pub fn create_directory(path: String) -> Result<(), String> {
-    if !path.contains("Servers") && !path.contains("servers") {
-        return Err("Safety check failed".to_string());
-    }
-    std::fs::create_dir_all(&path).map_err(|e| e.to_string())
+pub fn write_binary_file(path: String, content: Vec<u8>) -> Result<(), MineServerError> {
+    let file_path = validate_path(&path)?;
+    std::fs::write(file_path, content).map_err(Into::into)
 }
 
 #[tauri::command]
-pub fn delete_file(path: String) -> Result<(), String> {
-    if !path.contains("Servers") && !path.contains("servers") {
-        return Err("Safety check failed".to_string());
-    }
-    let file_path = Path::new(&path);
-    if !file_path.exists() {
-        return Err("File not found".to_string());
-    }
-    std::fs::remove_file(file_path).map_err(|e| e.to_string())
+pub fn create_directory(path: String) -> Result<(), MineServerError> {
+    let dir_path = validate_path(&path)?;
+    std::fs::create_dir_all(dir_path).map_err(Into::into)
 }
 
 #[tauri::command]
-pub fn delete_directory(path: String) -> Result<(), String> {
-    if !path.contains("Servers") && !path.contains("servers") {
-        return Err("Safety check failed".to_string());
-    }
-    let dir_path = Path::new(&path);
-    if !dir_path.exists() {
-        return Err("Directory not found".to_string());
-    }
-    std::fs::remove_dir_all(dir_path).map_err(|e| e.to_string())
+pub fn delete_file(path: String) -> Result<(), MineServerError> {
+    audited("delete_file", json!({ "path": path }), || {
+        let file_path = validate_path_inside_root(&path)?;
+        if !file_path.exists() {
+            return Err("File not found".to_string());
+        }
+        move_to_trash(&file_path, None).map(|_| ())
+    }).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn delete_directory(path: String) -> Result<(), MineServerError> {
+    audited("delete_directory", json!({ "path": path }), || {
+        let dir_path = validate_path_inside_root(&path)?;
+        if !dir_path.exists() {
+            return Err("Directory not found".to_string());
+        }
+        move_to_trash(&dir_path, None).map(|_| ())
+    }).map_err(Into::into)
 }
 
 #[tauri::command]
-pub fn rename_file(old_path: String, new_path: String) -> Result<(), String> {
-    if !old_path.contains("Servers") && !old_path.contains("servers") {
-        return Err("Safety check failed".to_string());
+pub fn rename_file(old_path: String, new_path: String) -> Result<(), MineServerError> {
+    let old = validate_path_inside_root(&old_path)?;
+    let new = validate_path_inside_root(&new_path)?;
+    if !old.exists() {
+        return Err(MineServerError::not_found("Original file/folder not found"));
     }
-    if !new_path.contains("Servers") && !new_path.contains("servers") {
-        return Err("Safety check failed".to_string());
+    std::fs::rename(old, new).map_err(Into::into)
+}
+
+pub fn path_size(path: &Path) -> u64 {
+    WalkDir::new(path).into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Copies a file or folder tree. `on_progress(copied_bytes, current_file)` runs after each file.
+pub fn copy_recursive(src: &Path, dst: &Path, mut on_progress: impl FnMut(u64, &Path)) -> Result<u64, String> {
+    if !src.is_dir() {
+        let copied = fs::copy(src, dst).map_err(|e| e.to_string())?;
+        on_progress(copied, src);
+        return Ok(copied);
     }
-    let old = Path::new(&old_path);
-    if !old.exists() {
-        return Err("Original file/folder not found".to_string());
+
+    let mut copied: u64 = 0;
+    for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+        let relative = entry.path().strip_prefix(src).map_err(|e| e.to_string())?;
+        let target = dst.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+        } else {
+            copied += fs::copy(entry.path(), &target).map_err(|e| e.to_string())?;
+            on_progress(copied, entry.path());
+        }
     }
-    std::fs::rename(old_path, new_path).map_err(|e| e.to_string())
+    Ok(copied)
 }
 
-#[tauri::command]
-pub fn duplicate_file(path: String, new_path: String) -> Result<(), String> {
-    if (!path.contains("Servers") && !path.contains("servers")) || 
-       (!new_path.contains("Servers") && !new_path.contains("servers")) {
-        return Err("Safety check failed".to_string());
+/// Rename, falling back to copy + delete when source and target are on different volumes.
+pub fn move_path_any(src: &Path, dst: &Path) -> Result<(), String> {
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
     }
-    
-    let path_obj = Path::new(&path);
+    copy_recursive(src, dst, |_, _| {})?;
+    if src.is_dir() {
+        fs::remove_dir_all(src).map_err(|e| e.to_string())
+    } else {
+        fs::remove_file(src).map_err(|e| e.to_string())
+    }
+}
+
+/// Copies a file or folder next to itself. Folder copies report `operation-progress` events.
+#[tauri::command]
+pub async fn duplicate_file(app: AppHandle, ops: State<'_, OperationState>, path: String, new_path: String, operation_id: Option<String>) -> Result<(), MineServerError> {
+    let operation = ops.begin(operation_id, "duplicate")?.with_events(app);
+    tokio::task::spawn_blocking(move || duplicate_file_sync(path, new_path, &operation))
+        .await?
+        .map_err(Into::into)
+}
+
+fn duplicate_file_sync(path: String, new_path: String, op: &Operation) -> Result<(), String> {
+    let path_obj = validate_path(&path)?;
+    let new_path = validate_path(&new_path)?;
     if !path_obj.exists() {
         return Err("Source file not found".to_string());
     }
+    let is_dir = path_obj.is_dir();
+    if is_dir && new_path.starts_with(&path_obj) {
+        return Err("Cannot copy a folder into itself".to_string());
+    }
+
+    // Safe duplication logic
+    let mut final_new_path = new_path.clone();
+    let mut counter = 1;
 
-    if path_obj.is_dir() {
-         return Err("Duplicating directories is not supported yet".to_string());
+    // Extract stem and extension for incrementing (folders keep their full name)
+    let (file_stem, extension) = if is_dir {
+        (final_new_path.file_name().unwrap_or_default().to_string_lossy().to_string(), None)
     } else {
-        // Safe duplication logic
-        let mut final_new_path = std::path::PathBuf::from(&new_path);
-        let mut counter = 1;
-
-        // Extract stem and extension for incrementing
-        let file_stem = final_new_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
-        let extension = final_new_path.extension().map(|e| e.to_string_lossy().to_string());
-        // Fix: Clone parent to PathBuf to avoid borrowing final_new_path which changes
-        let parent = final_new_path.parent().unwrap_or(Path::new("")).to_path_buf();
-
-        // Loop until we find a free name
-        while final_new_path.exists() {
-            let new_name = if let Some(ref ext) = extension {
-                format!("{} (copy {}).{}", file_stem, counter, ext)
-            } else {
-                format!("{} (copy {})", file_stem, counter)
-            };
-            final_new_path = parent.join(new_name);
-            counter += 1;
-        }
+        (
+            final_new_path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            final_new_path.extension().map(|e| e.to_string_lossy().to_string()),
+        )
+    };
+    // Fix: Clone parent to PathBuf to avoid borrowing final_new_path which changes
+    let parent = final_new_path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    // Loop until we find a free name
+    while final_new_path.exists() {
+        let new_name = if let Some(ref ext) = extension {
+            format!("{} (copy {}).{}", file_stem, counter, ext)
+        } else {
+            format!("{} (copy {})", file_stem, counter)
+        };
+        final_new_path = parent.join(new_name);
+        counter += 1;
+    }
 
-        std::fs::copy(path, final_new_path).map_err(|e| e.to_string())?;
+    if is_dir {
+        let total_bytes = path_size(&path_obj);
+        copy_recursive(&path_obj, &final_new_path, |copied_bytes, current| {
+            let percentage = if total_bytes > 0 { (copied_bytes * 100 / total_bytes) as u8 } else { 0 };
+            op.progress(percentage.min(99), copied_bytes, format!("Copying: {}", current.to_string_lossy()));
+        })?;
+    } else {
+        std::fs::copy(&path_obj, final_new_path).map_err(|e| e.to_string())?;
     }
+    op.complete();
     Ok(())
 }
 
+/// Moves a file or folder. If `dst` is an existing folder, the item is moved into it.
+/// Returns the final path.
 #[tauri::command]
-pub fn copy_file_path(path: String) -> Result<String, String> {
+pub async fn move_path(src: String, dst: String) -> Result<String, MineServerError> {
+    tokio::task::spawn_blocking(move || move_path_sync(src, dst))
+        .await?
+        .map_err(Into::into)
+}
+
+fn move_path_sync(src: String, dst: String) -> Result<String, String> {
+    let source = validate_path_inside_root(&src)?;
+    if !source.exists() {
+        return Err("Source not found".to_string());
+    }
+
+    let mut target = validate_path(&dst)?;
+    if target.is_dir() {
+        target = target.join(source.file_name().ok_or("Invalid source path")?);
+    }
+    let target = validate_path_inside_root(&target.to_string_lossy())?;
+
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+    if target.starts_with(&source) {
+        return Err("Cannot move a folder into itself".to_string());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    move_path_any(&source, &target)?;
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn copy_file_path(path: String) -> Result<String, MineServerError> {
     // Just return the path back, user wants to copy to clipboard in frontend
     // but we can return absolute path here if needed
     let abs_path = std::fs::canonicalize(&path).map_err(|e| e.to_string())?;
@@ -191,92 +360,165 @@ pub fn copy_file_path(path: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub fn archive_files(server_path: String, files: Vec<String>, archive_name: String) -> Result<(), String> {
-    let root = Path::new(&server_path);
+pub async fn archive_files(
+    ops: State<'_, OperationState>,
+    server_path: String,
+    files: Vec<String>,
+    archive_name: String,
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let operation = ops.begin(operation_id, "archive")?;
+    let cancel = operation.token.clone();
+    tokio::task::spawn_blocking(move || archive_files_sync(server_path, files, archive_name, cancel))
+        .await?
+        .map_err(Into::into)
+}
+
+fn archive_files_sync(server_path: String, files: Vec<String>, archive_name: String, cancel: CancelToken) -> Result<(), String> {
+    // Only zip is written (ZIP64 where needed); a .tar or .7z name would hold a zip
+    if !archive_name.to_lowercase().ends_with(".zip") {
+        return Err("Archives are created as .zip; other formats can only be extracted".to_string());
+    }
+    let root = validate_path(&server_path)?;
+    let root = root.as_path();
     if !root.exists() {
         return Err("Server path not found".to_string());
     }
 
-    let archive_path = root.join(&archive_name);
+    let archive_path = validate_path(&root.join(&archive_name).to_string_lossy())?;
     let file = fs::File::create(&archive_path).map_err(|e| e.to_string())?;
     let mut zip = zip::ZipWriter::new(file);
+    let written = write_archive(&mut zip, root, files, &cancel).and_then(|_| zip.finish().map(|_| ()).map_err(|e| zip_write_error("Failed to finish archive", e)));
+    if written.is_err() {
+        drop(zip);
+        let _ = fs::remove_file(&archive_path);
+    }
+    written
+}
+
+fn write_archive(zip: &mut zip::ZipWriter<fs::File>, root: &Path, files: Vec<String>, cancel: &CancelToken) -> Result<(), String> {
     let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
     for file_name in files {
-        let full_path = root.join(&file_name);
+        let full_path = match validate_path(&root.join(&file_name).to_string_lossy()) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
         if !full_path.exists() { continue; }
 
+        cancel.check()?;
         if full_path.is_file() {
-             zip.start_file(&file_name, options.clone()).map_err(|e| e.to_string())?;
-             let mut f = fs::File::open(&full_path).map_err(|e| e.to_string())?;
-             let mut buffer = Vec::new();
-             f.read_to_end(&mut buffer).map_err(|e| e.to_string())?;
-             zip.write_all(&buffer).map_err(|e| e.to_string())?;
+             zip_file(zip, &file_name, &full_path, options)?;
         } else if full_path.is_dir() {
             for entry in WalkDir::new(&full_path).into_iter().filter_map(|e| e.ok()) {
+                cancel.check()?;
                 let path = entry.path();
                 let relative = path.strip_prefix(root).unwrap();
                 let relative_str = relative.to_string_lossy().replace("\\", "/");
 
                 if path.is_file() {
-                    zip.start_file(&relative_str, options.clone()).map_err(|e| e.to_string())?;
-                    let mut f = fs::File::open(path).map_err(|e| e.to_string())?;
-                    let mut buffer = Vec::new();
-                    f.read_to_end(&mut buffer).map_err(|e| e.to_string())?;
-                    zip.write_all(&buffer).map_err(|e| e.to_string())?;
+                    zip_file(zip, &relative_str, path, options)?;
                 } else if path.is_dir() {
                      zip.add_directory(&relative_str, options.clone()).map_err(|e| e.to_string())?;
                 }
             }
         }
     }
-
-    zip.finish().map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Extracts a zip, tar, tar.gz/tgz or 7z archive into the server folder.
+/// Reports `operation-progress` events while large archives are unpacked.
 #[tauri::command]
-pub fn extract_file(server_path: String, file_name: String) -> Result<(), String> {
-    let root = Path::new(&server_path);
-    // Basic safety check for server path context
-    if !server_path.contains("Servers") && !server_path.contains("servers") {
-         return Err("Safety check failed: Invalid server path".to_string());
+pub async fn extract_file(app: AppHandle, ops: State<'_, OperationState>, server_path: String, file_name: String, operation_id: Option<String>) -> Result<(), MineServerError> {
+    let operation = ops.begin(operation_id, "extract")?.with_events(app);
+    tokio::task::spawn_blocking(move || extract_file_sync(server_path, file_name, &operation))
+        .await?
+        .map_err(Into::into)
+}
+
+fn extract_file_sync(server_path: String, file_name: String, op: &Operation) -> Result<(), String> {
+    let root = validate_path(&server_path)?;
+    let root = root.as_path();
+
+    let archive_path = validate_path(&root.join(&file_name).to_string_lossy())?;
+
+    // Zip-slip, size and symlink checks live in the shared extractor
+    extract_archive_file(&archive_path, root, |copied_bytes, total_bytes, entry| {
+        let percentage = if total_bytes > 0 { (copied_bytes * 100 / total_bytes) as u8 } else { 0 };
+        op.progress(percentage.min(99), copied_bytes, format!("Extracting: {}", entry));
+    })?;
+    op.complete();
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilePermissions {
+    pub supported: bool, // false on Windows: mode bits don't apply there
+    pub mode: Option<u32>, // e.g. 0o755
+    pub mode_string: Option<String>, // e.g. "rwxr-xr-x"
+    pub executable: bool,
+    pub readonly: bool,
+}
+
+#[cfg(unix)]
+fn mode_string(mode: u32) -> String {
+    let flags = ['r', 'w', 'x'];
+    (0..9).map(|i| if mode & (0o400 >> i) != 0 { flags[i % 3] } else { '-' }).collect()
+}
+
+fn read_permissions(path: &Path) -> Result<FilePermissions, String> {
+    let metadata = fs::metadata(path).map_err(|e| format!("Failed to read permissions: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        Ok(FilePermissions {
+            supported: true,
+            mode: Some(mode),
+            mode_string: Some(mode_string(mode)),
+            executable: mode & 0o111 != 0,
+            readonly: metadata.permissions().readonly(),
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        // Windows decides executability by extension
+        let executable = path.extension()
+            .map(|e| ["exe", "bat", "cmd"].contains(&e.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false);
+        Ok(FilePermissions {
+            supported: false,
+            mode: None,
+            mode_string: None,
+            executable,
+            readonly: metadata.permissions().readonly(),
+        })
     }
+}
 
-    let archive_path = root.join(&file_name);
-    
-    let file = fs::File::open(&archive_path).map_err(|e| e.to_string())?;
-    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
-
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
-        
-        // SECURITY: "Zip Slip" protection using enclosed_name()
-        // This ensures the path doesn't escape the target directory
-        let outpath = match file.enclosed_name() {
-            Some(path) => path.to_owned(),
-            None => continue, // Skip suspicious paths
-        };
+#[tauri::command]
+pub fn get_permissions(path: String) -> Result<FilePermissions, MineServerError> {
+    let file_path = validate_path(&path)?;
+    read_permissions(&file_path).map_err(Into::into)
+}
 
-        let dest_path = root.join(outpath);
-        
-        // Final Double Check: destination must start with root
-        if !dest_path.starts_with(root) {
-             return Err(format!("Security Warning: potential path traversal detected for file {}", file.name()));
-        }
+/// Sets the rwx bits (e.g. 0o755) so start scripts and bedrock_server can be run.
+/// Does nothing on Windows; the returned `supported: false` tells the UI why.
+#[tauri::command]
+pub fn set_permissions(path: String, mode: u32) -> Result<FilePermissions, MineServerError> {
+    let file_path = validate_path_inside_root(&path)?;
+    if mode > 0o777 {
+        return Err(MineServerError::validation("Invalid mode: only rwx bits (000-777) can be set"));
+    }
 
-        if file.name().ends_with('/') {
-            fs::create_dir_all(&dest_path).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(p) = dest_path.parent() {
-                if !p.exists() {
-                    fs::create_dir_all(p).map_err(|e| e.to_string())?;
-                }
-            }
-            let mut outfile = fs::File::create(&dest_path).map_err(|e| e.to_string())?;
-            std::io::copy(&mut file, &mut outfile).map_err(|e| e.to_string())?;
-        }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(mode))
+            .map_err(|e| format!("Failed to set permissions: {}", e))?;
     }
-    
-    Ok(())
+    read_permissions(&file_path).map_err(Into::into)
 }
@@ -3,8 +3,8 @@ use walkdir::WalkDir;
 
 #[derive(serde::Serialize)]
 pub struct JavaInstall {
-    path: String,
-    version: String,
+    pub path: String,
+    pub version: String,
     arch: String,
 }
 
@@ -63,12 +63,32 @@ pub fn get_java_versions() -> Vec<JavaInstall> {
 
 // --- Server Properties Support ---
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
+use std::sync::Mutex;
+use serde::Serialize;
+use tauri::State;
+use super::http::HttpState;
+use super::registry::find_registered_server;
+use super::runner::{running_server_at, send_server_command_direct, ServerProcessState};
+use super::error::MineServerError;
+
+// Properties changed while their server ran, by server id: (server folder, changed values).
+// A running server keeps its own copy and may save it over the file, so they are written again
+// once the process exits.
+static PENDING_PROPERTIES: Mutex<BTreeMap<String, (String, HashMap<String, String>)>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertiesUpdate {
+    pub running: bool,
+    pub applied: Vec<String>, // Console commands sent so changes take effect right away
+    pub restart_required: Vec<String>, // Changed keys the server only reads on start
+}
 
 #[tauri::command]
-pub fn read_server_properties(server_path: String) -> Result<HashMap<String, String>, String> {
+pub fn read_server_properties(server_path: String) -> Result<HashMap<String, String>, MineServerError> {
     let props_path = Path::new(&server_path).join("server.properties");
     
     if !props_path.exists() {
@@ -94,8 +114,99 @@ pub fn read_server_properties(server_path: String) -> Result<HashMap<String, Str
     Ok(properties)
 }
 
+/// Console command that applies a property to a running server, which also makes the server
+/// keep the value. Only values from a fixed list are passed on.
+fn live_command(key: &str, value: &str, bedrock: bool) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    let switch = |command: &str| match value.as_str() {
+        "true" => Some(format!("{} on", command)),
+        "false" => Some(format!("{} off", command)),
+        _ => None,
+    };
+    match key {
+        "difficulty" => {
+            let name = match value.as_str() {
+                "0" | "peaceful" => "peaceful",
+                "1" | "easy" => "easy",
+                "2" | "normal" => "normal",
+                "3" | "hard" => "hard",
+                _ => return None,
+            };
+            Some(format!("difficulty {}", name))
+        }
+        "gamemode" => {
+            let name = match value.as_str() {
+                "0" | "survival" => "survival",
+                "1" | "creative" => "creative",
+                "2" | "adventure" => "adventure",
+                "3" | "spectator" if !bedrock => "spectator",
+                _ => return None,
+            };
+            Some(format!("defaultgamemode {}", name))
+        }
+        "white-list" if !bedrock => switch("whitelist"),
+        "allow-list" if bedrock => switch("allowlist"),
+        _ => None,
+    }
+}
+
+/// Writes properties again that were changed while the server ran. Called once it has exited.
+pub fn flush_pending_properties(id: &str) {
+    let pending = PENDING_PROPERTIES.lock().ok().and_then(|mut p| p.remove(id));
+    if let Some((server_path, properties)) = pending {
+        if let Err(e) = write_server_properties(server_path, properties) {
+            eprintln!("[Properties] Failed to write changes made while {} was running: {}", id, e);
+        }
+    }
+}
+
+/// Writes server.properties. While the server runs, changes that have a console command are
+/// applied live when `apply_live` is set; all of them are written again after it stops, so the
+/// server can't overwrite them on shutdown.
 #[tauri::command]
-pub fn update_server_properties(server_path: String, properties: HashMap<String, String>) -> Result<(), String> {
+pub fn update_server_properties(
+    state: State<'_, ServerProcessState>,
+    server_path: String,
+    properties: HashMap<String, String>,
+    apply_live: Option<bool>,
+) -> Result<PropertiesUpdate, MineServerError> {
+    let Some(id) = running_server_at(state.inner(), Path::new(&server_path)) else {
+        write_server_properties(server_path, properties)?;
+        return Ok(PropertiesUpdate::default());
+    };
+
+    let current = read_server_properties(server_path.clone())?;
+    let mut changed: Vec<(String, String)> = properties.iter()
+        .filter(|(key, value)| current.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    changed.sort();
+    write_server_properties(server_path.clone(), properties)?;
+
+    let mut update = PropertiesUpdate { running: true, ..Default::default() };
+    if changed.is_empty() {
+        return Ok(update);
+    }
+    let bedrock = find_registered_server(&id).map(|s| s.server_type == "bedrock").unwrap_or(false);
+    for (key, value) in &changed {
+        match live_command(key, value, bedrock).filter(|_| apply_live.unwrap_or(false)) {
+            Some(command) => {
+                send_server_command_direct(state.inner(), id.clone(), command.clone())?;
+                update.applied.push(command);
+            }
+            None => update.restart_required.push(key.clone()),
+        }
+    }
+
+    if let Ok(mut pending) = PENDING_PROPERTIES.lock() {
+        let entry = pending.entry(id).or_insert_with(|| (server_path, HashMap::new()));
+        entry.1.extend(changed);
+    }
+    Ok(update)
+}
+
+/// Sets keys in server.properties, keeping comments, order and the other keys.
+pub fn write_server_properties(server_path: String, properties: HashMap<String, String>) -> Result<(), String> {
     let props_path = Path::new(&server_path).join("server.properties");
     
     // Read existing content
@@ -144,7 +255,7 @@ pub fn update_server_properties(server_path: String, properties: HashMap<String,
 }
 
 #[tauri::command]
-pub async fn install_grimac(server_path: String) -> Result<String, String> {
+pub async fn install_grimac(http: State<'_, HttpState>, server_path: String) -> Result<String, MineServerError> {
     let path = Path::new(&server_path).join("plugins");
     
     if !path.exists() {
@@ -159,20 +270,19 @@ pub async fn install_grimac(server_path: String) -> Result<String, String> {
     // Direct download from GitHub Releases
     let url = "https://github.com/GrimAnticheat/Grim/releases/download/2.3.61/GrimAC.jar";
 
-    let client = reqwest::Client::new();
+    let client = http.client();
     let resp = client.get(url)
-        .header("User-Agent", "Mineserver/1.0")
         .send()
         .await
-        .map_err(|e| format!("Network Error: {}", e))?;
+        .map_err(|e| MineServerError::network(format!("Network Error: {}", e)))?;
     
     if !resp.status().is_success() {
-        return Err(format!("Download failed with status: {}", resp.status()));
+        return Err(MineServerError::external("GitHub", format!("Download failed with status: {}", resp.status())));
     }
     
     let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
     
-    fs::write(&jar_path, &bytes).map_err(|e| format!("File Write Error: {}", e))?;
+    tokio::fs::write(&jar_path, &bytes).await.map_err(|e| format!("File Write Error: {}", e))?;
     
     Ok("GrimAC installed successfully! Restart your server.".to_string())
 }
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::fs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State};
+use super::activity::Activity;
+use super::downloader::download_server;
+use super::http::HttpState;
+use super::network_manager::assign_server_port;
+use super::operations::OperationState;
+use super::plugins::{install_hangar_plugin, install_modrinth_plugin, install_ore_plugin, install_spigot_plugin};
+use super::registry::{register_server, RegisteredServer};
+use super::runner::ServerProcessState;
+use super::server_config::write_server_properties;
+use super::error::MineServerError;
+
+// Server profiles: a JSON file (usually published at a URL by a community or modpack author)
+// describing a server to set up. Format version 1:
+//
+//   {
+//     "formatVersion": 1,
+//     "name": "Survival SMP",
+//     "description": "...", "author": "...",          optional
+//     "serverType": "paper", "version": "1.21.1",
+//     "ram": 4096,                                     optional, MB
+//     "jvmFlags": "-XX:+UseG1GC ...",                  optional
+//     "properties": { "difficulty": "hard", "view-distance": 8 },
+//     "plugins": [
+//       { "source": "modrinth", "id": "luckperms" },
+//       { "source": "hangar", "id": "ViaVersion" },
+//       { "source": "spigot", "id": "28140" },
+//       { "source": "ore", "id": "nucleus" },          Sponge servers
+//       { "source": "url", "url": "https://example.com/Plugin.jar", "fileName": "Plugin.jar" }
+//     ]
+//   }
+//
+// Importing downloads the server, writes the properties, installs the plugins and registers the
+// result. A plugin that fails to install is reported instead of failing the whole import. JVM
+// flags are limited to tuning options, because a profile from the internet must not be able to
+// run commands through -XX:OnError or load agents.
+
+pub const PROFILE_FORMAT_VERSION: u32 = 1;
+const MAX_PROFILE_SIZE: usize = 1024 * 1024;
+const DEFAULT_RAM: u32 = 4096;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilePlugin {
+    pub source: String, // "modrinth", "hangar", "spigot", "ore" or "url"
+    #[serde(default)]
+    pub id: Option<String>, // Project id or slug, Spigot resource id
+    #[serde(default)]
+    pub url: Option<String>, // Direct https download for "url"
+    #[serde(default)]
+    pub file_name: Option<String>,
+}
+
+impl ProfilePlugin {
+    fn label(&self) -> String {
+        self.id.clone()
+            .or_else(|| self.file_name.clone())
+            .or_else(|| self.url.clone())
+            .unwrap_or_else(|| self.source.clone())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerProfile {
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    pub server_type: String,
+    pub version: String,
+    #[serde(default)]
+    pub ram: Option<u32>,
+    #[serde(default)]
+    pub jvm_flags: Option<String>,
+    #[serde(default)]
+    pub properties: HashMap<String, Value>, // Strings, numbers or booleans
+    #[serde(default)]
+    pub plugins: Vec<ProfilePlugin>,
+}
+
+fn default_format_version() -> u32 {
+    PROFILE_FORMAT_VERSION
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedServer {
+    pub server: RegisteredServer,
+    pub port: u16,
+    pub installed: Vec<String>, // Plugin file names
+    pub warnings: Vec<String>, // Skipped flags, failed plugins and properties
+}
+
+fn parse_profile(content: &str) -> Result<ServerProfile, String> {
+    let profile: ServerProfile = serde_json::from_str(content)
+        .map_err(|e| format!("Not a valid server profile: {}", e))?;
+    if profile.format_version > PROFILE_FORMAT_VERSION {
+        return Err(format!("This profile needs a newer version of MineServer (format {})", profile.format_version));
+    }
+    if profile.server_type.trim().is_empty() || profile.version.trim().is_empty() {
+        return Err("The profile doesn't say which server type and version to use".to_string());
+    }
+    Ok(profile)
+}
+
+/// Reads a profile from an http(s) URL or a local file.
+async fn load_profile(http: &HttpState, source: &str) -> Result<ServerProfile, String> {
+    let source = source.trim();
+    let content = if source.starts_with("https://") || source.starts_with("http://") {
+        let res = http.client().get(source).send().await
+            .map_err(|e| format!("Failed to download profile: {}", e))?;
+        if !res.status().is_success() {
+            return Err(format!("Failed to download profile: HTTP {}", res.status()));
+        }
+        let bytes = res.bytes().await.map_err(|e| format!("Failed to download profile: {}", e))?;
+        if bytes.len() > MAX_PROFILE_SIZE {
+            return Err("The profile is too large".to_string());
+        }
+        String::from_utf8_lossy(&bytes).to_string()
+    } else {
+        let size = fs::metadata(source).map_err(|e| format!("Failed to read profile: {}", e))?.len();
+        if size as usize > MAX_PROFILE_SIZE {
+            return Err("The profile is too large".to_string());
+        }
+        fs::read_to_string(source).map_err(|e| format!("Failed to read profile: {}", e))?
+    };
+    parse_profile(&content)
+}
+
+/// Splits profile JVM flags into the ones that are safe to apply and warnings for the rest.
+/// Heap size comes from `ram`, so -Xmx/-Xms are dropped too.
+fn sanitize_flags(flags: Option<&str>) -> (Option<String>, Vec<String>) {
+    let mut kept = Vec::new();
+    let mut warnings = Vec::new();
+    for flag in flags.unwrap_or_default().split_whitespace() {
+        let tuning = flag.starts_with("-XX:") && !flag.contains("OnError") && !flag.contains("OnOutOfMemoryError");
+        let property = flag.starts_with("-D") && flag.len() > 2;
+        if tuning || property || flag.starts_with("-Xss") || flag.starts_with("-Xmn") {
+            kept.push(flag);
+        } else {
+            warnings.push(format!("Ignored JVM flag {}", flag));
+        }
+    }
+    let kept = if kept.is_empty() { None } else { Some(kept.join(" ")) };
+    (kept, warnings)
+}
+
+fn property_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Downloads a plugin from a direct link into plugins/ (or mods/ on mod loaders).
+async fn install_url_plugin(http: &HttpState, server_type: &str, server_path: &str, plugin: &ProfilePlugin) -> Result<String, String> {
+    let url = plugin.url.as_deref().ok_or("The plugin has no download URL")?;
+    if !url.starts_with("https://") {
+        return Err("Plugin downloads must use https".to_string());
+    }
+    let file_name = plugin.file_name.clone()
+        .or_else(|| url.split('?').next()?.rsplit('/').next().map(str::to_string))
+        .unwrap_or_default();
+    if !file_name.ends_with(".jar") || file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+        return Err(format!("{} is not a valid plugin file name", file_name));
+    }
+
+    let mut activity = Activity::begin("plugin_install", server_path, format!("Profile plugin {}", file_name));
+    let res = http.client().get(url).send().await.map_err(|e| format!("Download failed: {}", e))?;
+    if !res.status().is_success() {
+        return Err(format!("Download failed: HTTP {}", res.status()));
+    }
+    let bytes = res.bytes().await.map_err(|e| format!("Download failed: {}", e))?;
+    if !bytes.starts_with(b"PK") {
+        return Err(format!("{} is not a jar file", url));
+    }
+
+    let folder = match server_type {
+        "fabric" | "quilt" | "forge" | "neoforge" | "spongevanilla" | "spongeforge" => "mods",
+        _ => "plugins",
+    };
+    let dir = Path::new(server_path).join(folder);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {} folder: {}", folder, e))?;
+    fs::write(dir.join(&file_name), &bytes).map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+    activity.set_title(format!("Installed {}", file_name));
+    activity.complete(Some(bytes.len() as u64));
+    Ok(file_name)
+}
+
+/// Parses a profile without installing anything, so the user can see what it would set up.
+#[tauri::command]
+pub async fn read_server_profile(http: State<'_, HttpState>, source: String) -> Result<ServerProfile, MineServerError> {
+    load_profile(http.inner(), &source).await.map_err(MineServerError::validation)
+}
+
+/// Provisions a new server in `server_path` (which must be empty or missing) from a profile
+/// URL or file. `operation_id` tracks the server download.
+#[tauri::command]
+pub async fn import_profile(
+    app: AppHandle,
+    http: State<'_, HttpState>,
+    ops: State<'_, OperationState>,
+    state: State<'_, ServerProcessState>,
+    source: String,
+    server_path: String,
+    name: Option<String>,
+    operation_id: Option<String>,
+) -> Result<ImportedServer, MineServerError> {
+    let profile = load_profile(http.inner(), &source).await.map_err(MineServerError::validation)?;
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).unwrap_or_else(|| profile.name.clone());
+    if fs::read_dir(&server_path).map(|mut d| d.next().is_some()).unwrap_or(false) {
+        return Err(MineServerError::conflict(format!("{} is not empty", server_path)));
+    }
+    let (flags, mut warnings) = sanitize_flags(profile.jvm_flags.as_deref());
+    let server_type = profile.server_type.trim().to_lowercase();
+    let bedrock = server_type == "bedrock";
+
+    download_server(app.clone(), http.clone(), ops.clone(), state.clone(), server_type.clone(), profile.version.clone(), server_path.clone(), None, operation_id).await?;
+
+    let server = RegisteredServer {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        path: server_path.clone(),
+        server_type: server_type.clone(),
+        version: profile.version.clone(),
+        jar_file: if bedrock { "bedrock_server.exe" } else { "server.jar" }.to_string(),
+        ram: profile.ram.filter(|r| *r >= 512).unwrap_or(DEFAULT_RAM),
+        java_path: None,
+        startup_flags: flags,
+        auto_restart: false,
+        auto_start: false,
+        auto_network_setup: false,
+        tunnel_autostart: false,
+        gc_logging: false,
+        network: Default::default(),
+        notes: String::new(),
+        changelog: Vec::new(),
+    };
+    register_server(state.clone(), server.clone())?;
+
+    let mut properties = HashMap::new();
+    for (key, value) in &profile.properties {
+        match property_value(value) {
+            Some(v) if !key.contains(['=', '\n']) && !v.contains('\n') => { properties.insert(key.clone(), v); },
+            _ => warnings.push(format!("Ignored property {}", key)),
+        }
+    }
+    if !properties.is_empty() {
+        if let Err(e) = write_server_properties(server_path.clone(), properties) {
+            warnings.push(format!("Failed to write server.properties: {}", e));
+        }
+    }
+
+    let mut installed = Vec::new();
+    for plugin in &profile.plugins {
+        let id = plugin.id.clone().unwrap_or_default();
+        let result: Result<String, String> = match plugin.source.as_str() {
+            _ if plugin.source != "url" && id.is_empty() => Err("No id given".to_string()),
+            "modrinth" => install_modrinth_plugin(app.clone(), http.clone(), ops.clone(), id, server_path.clone(), Some(server_type.clone()), Some(profile.version.clone()), None).await
+                .map_err(|e| e.to_string()),
+            "hangar" => install_hangar_plugin(app.clone(), http.clone(), ops.clone(), id, server_path.clone(), Some(server_type.clone()), None, None, None).await
+                .map_err(|e| e.to_string()),
+            "spigot" => install_spigot_plugin(app.clone(), http.clone(), ops.clone(), id.clone(), server_path.clone(), None).await
+                .map(|_| format!("Spigot resource {}", id))
+                .map_err(|e| e.to_string()),
+            "ore" => install_ore_plugin(app.clone(), http.clone(), ops.clone(), id, server_path.clone(), Some(server_type.clone()), Some(profile.version.clone()), None).await
+                .map_err(|e| e.to_string()),
+            "url" => install_url_plugin(http.inner(), &server_type, &server_path, plugin).await,
+            other => Err(format!("Unknown plugin source {}", other)),
+        };
+        match result {
+            Ok(file) => installed.push(file),
+            Err(e) => warnings.push(format!("Plugin {} was not installed: {}", plugin.label(), e)),
+        }
+    }
+
+    let port = assign_server_port(server_path, bedrock, None)?;
+    Ok(ImportedServer { server, port, installed, warnings })
+}
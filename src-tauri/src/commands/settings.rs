@@ -0,0 +1,141 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use super::http::{build_client, HttpState};
+use super::json_store::{read_json, update_json, write_json};
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+pub(super) const PROXY_TEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+// Backend-side settings (~/Mineserver/settings.json). UI preferences stay in the frontend store;
+// this holds what the backend must enforce on its own (headless mode, safety limits).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub max_extract_size_mb: u64, // Total uncompressed size allowed per archive
+    pub max_compression_ratio: u64, // Per-entry uncompressed/compressed cap (zip bombs)
+    pub allow_archive_symlinks: bool, // Symlinks in archives are skipped unless enabled
+    pub eula_accepted: bool, // User accepted the Minecraft EULA in the app
+    pub eula_accepted_at: Option<String>,
+    pub trash_retention_days: u32, // Trash items older than this are purged automatically
+    pub ftp_users: Vec<FtpUser>, // Accounts for the built-in FTP server, each locked to one server folder
+    pub http_proxy: Option<String>, // e.g. "http://proxy.example:3128" or "socks5://host:1080", used for all outgoing requests
+    pub http_proxy_username: Option<String>,
+    pub http_proxy_password: Option<String>,
+    pub http_connect_timeout_secs: u64,
+    pub http_read_timeout_secs: u64,
+    pub http_contact: Option<String>, // Email or URL added to the User-Agent, as Modrinth and other APIs ask
+    pub compression_workers: u32, // Threads used to compress backups and world archives, 0 = all cores
+    pub uptime_monitor: bool, // Ping every registered server once a minute and record uptime
+    pub pause_on_battery: bool, // Skip auto-restarts and scheduled tasks while unplugged
+    pub warn_on_battery_start: bool, // Emit `power-warning` when a server starts unplugged
+    pub pre_update_backup: bool, // Back up before server upgrades and plugin/mod updates
+    pub pre_update_backup_scope: String, // Scope of the backup before a server upgrade; addon updates back up configs
+    pub process_poll_secs: u64, // Fallback exit check of running servers; exits are normally seen when the console closes
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FtpUser {
+    pub username: String,
+    pub password_hash: String, // PBKDF2-HMAC-SHA256 of the password, hex (sha256(salt + password) when kdf_rounds is 0)
+    pub salt: String,
+    #[serde(default)]
+    pub kdf_rounds: u32, // 0 for accounts saved before PBKDF2; rehashed on their next login
+    pub server_path: String, // FTP root ("/") for this account
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            max_extract_size_mb: 50 * 1024,
+            max_compression_ratio: 200,
+            allow_archive_symlinks: false,
+            eula_accepted: false,
+            eula_accepted_at: None,
+            trash_retention_days: 7,
+            ftp_users: Vec::new(),
+            http_proxy: None,
+            http_proxy_username: None,
+            http_proxy_password: None,
+            http_connect_timeout_secs: 15,
+            http_read_timeout_secs: 60,
+            http_contact: None,
+            compression_workers: 0,
+            uptime_monitor: false,
+            pause_on_battery: false,
+            warn_on_battery_start: true,
+            pre_update_backup: true,
+            pre_update_backup_scope: "worlds".to_string(),
+            process_poll_secs: 10,
+        }
+    }
+}
+
+fn get_settings_file() -> PathBuf {
+    app_data_dir().join("settings.json")
+}
+
+/// Current settings, falling back to defaults when the file is missing or unreadable.
+pub fn load_settings() -> AppSettings {
+    read_json(&get_settings_file(), "settings").ok().flatten().unwrap_or_default()
+}
+
+pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    write_json(&get_settings_file(), settings, "settings")
+}
+
+/// Changes some settings under the store lock, keeping changes other threads make meanwhile.
+pub fn update_settings<R>(update: impl FnOnce(&mut AppSettings) -> Result<R, String>) -> Result<R, String> {
+    update_json(&get_settings_file(), "settings", update)
+}
+
+#[tauri::command]
+pub fn get_app_settings() -> AppSettings {
+    load_settings()
+}
+
+#[tauri::command]
+pub fn update_app_settings(http: State<'_, HttpState>, settings: AppSettings) -> Result<(), MineServerError> {
+    // Validate the network settings before persisting them
+    http.reconfigure(&settings)?;
+    save_settings(&settings).map_err(Into::into)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyTestResult {
+    pub latency_ms: u64,
+    pub status: u16,
+}
+
+/// Sends one request through the given proxy settings (without saving them).
+#[tauri::command]
+pub async fn test_proxy(proxy: String, username: Option<String>, password: Option<String>) -> Result<ProxyTestResult, MineServerError> {
+    let settings = AppSettings {
+        http_proxy: Some(proxy),
+        http_proxy_username: username,
+        http_proxy_password: password,
+        ..load_settings()
+    };
+    let client = build_client(&settings).map_err(MineServerError::validation)?;
+
+    let started = Instant::now();
+    let resp = client.head(PROXY_TEST_URL)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .map_err(|e| MineServerError::network(format!("Request through the proxy failed: {}", e)))?;
+    if resp.status() == reqwest::StatusCode::PROXY_AUTHENTICATION_REQUIRED {
+        return Err(MineServerError::permission_denied("The proxy rejected the credentials"));
+    }
+
+    Ok(ProxyTestResult {
+        latency_ms: started.elapsed().as_millis() as u64,
+        status: resp.status().as_u16(),
+    })
+}
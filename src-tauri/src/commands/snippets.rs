@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::fs;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use super::runner::{ServerProcessState, send_server_command_direct};
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Console snippets shared by all servers, kept in ~/Mineserver/snippets.json. A snippet is one
+// console command per line; `{name}` placeholders are filled in when it runs
+// (e.g. "gamemode creative {player}").
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub command: String, // One console command per line
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub placeholders: Vec<String>, // Filled in on save
+}
+
+fn get_snippets_file() -> PathBuf {
+    app_data_dir().join("snippets.json")
+}
+
+fn load_snippets() -> Result<Vec<Snippet>, String> {
+    let file = get_snippets_file();
+    if !file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read snippets: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse snippets: {}", e))
+}
+
+fn save_snippets(snippets: &Vec<Snippet>) -> Result<(), String> {
+    let file = get_snippets_file();
+    fs::create_dir_all(file.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let content = serde_json::to_string_pretty(snippets)
+        .map_err(|e| format!("Failed to serialize snippets: {}", e))?;
+    fs::write(&file, content)
+        .map_err(|e| format!("Failed to write snippets: {}", e))
+}
+
+/// Placeholder names in order of first use, e.g. ["player", "item"].
+fn find_placeholders(command: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = command;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else { break };
+        let name = &rest[..end];
+        if !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !names.iter().any(|n| n == name)
+        {
+            names.push(name.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    names
+}
+
+fn render(command: &str, values: &HashMap<String, String>) -> Result<Vec<String>, MineServerError> {
+    let missing: Vec<String> = find_placeholders(command).into_iter()
+        .filter(|name| values.get(name).map(|v| v.trim().is_empty()).unwrap_or(true))
+        .collect();
+    if !missing.is_empty() {
+        return Err(MineServerError::validation(format!("Missing value for {}", missing.join(", "))));
+    }
+
+    let mut rendered = command.to_string();
+    for (name, value) in values {
+        // Values can't smuggle in extra console commands
+        if value.contains('\n') || value.contains('\r') {
+            return Err(MineServerError::validation(format!("Value for {} must be a single line", name)));
+        }
+        rendered = rendered.replace(&format!("{{{}}}", name), value.trim());
+    }
+
+    Ok(rendered.lines()
+        .map(|l| l.trim().trim_start_matches('/').to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+#[tauri::command]
+pub async fn list_snippets() -> Result<Vec<Snippet>, MineServerError> {
+    load_snippets().map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_snippet(snippet: Snippet) -> Result<Snippet, MineServerError> {
+    let mut snippet = snippet;
+    if snippet.name.trim().is_empty() || snippet.command.trim().is_empty() {
+        return Err(MineServerError::validation("A snippet needs a name and a command"));
+    }
+    if snippet.id.is_empty() {
+        snippet.id = uuid::Uuid::new_v4().to_string();
+    }
+    snippet.placeholders = find_placeholders(&snippet.command);
+
+    // Saving an existing id updates it in place
+    let mut snippets = load_snippets()?;
+    if let Some(existing) = snippets.iter_mut().find(|s| s.id == snippet.id) {
+        *existing = snippet.clone();
+    } else {
+        snippets.push(snippet.clone());
+    }
+
+    save_snippets(&snippets)?;
+    Ok(snippet)
+}
+
+#[tauri::command]
+pub async fn delete_snippet(id: String) -> Result<(), MineServerError> {
+    let mut snippets = load_snippets()?;
+    snippets.retain(|s| s.id != id);
+    save_snippets(&snippets).map_err(Into::into)
+}
+
+/// Sends a snippet to a running server's console. Returns the commands that were sent.
+#[tauri::command]
+pub async fn run_snippet(
+    state: State<'_, ServerProcessState>,
+    id: String,
+    server_id: String,
+    values: Option<HashMap<String, String>>,
+) -> Result<Vec<String>, MineServerError> {
+    let snippet = load_snippets()?
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| MineServerError::not_found(format!("Snippet {} not found", id)))?;
+
+    let commands = render(&snippet.command, &values.unwrap_or_default())?;
+    for command in &commands {
+        send_server_command_direct(&state, server_id.clone(), command.clone())?;
+    }
+    Ok(commands)
+}
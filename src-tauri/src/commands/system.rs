@@ -1,20 +1,95 @@
-use tauri::State;
-use sysinfo::{System, CpuRefreshKind, MemoryRefreshKind, Disks};
+use tauri::{AppHandle, Manager, State};
+use sysinfo::{System, CpuRefreshKind, MemoryRefreshKind, Disks, Components};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use local_ip_address::local_ip;
+use super::audit::audited;
+use serde_json::json;
+use super::error::MineServerError;
+
+use super::runner::{ServerEventSink, ServerProcessState, running_server_roots};
+use super::registry::load_registry;
+use super::path_guard::app_data_dir;
+use super::server::path_size;
+
+// How often the `system-metrics` event fires
+const METRICS_INTERVAL: Duration = Duration::from_secs(3);
 
 pub struct SystemState {
     pub sys: Mutex<System>,
+    pub components: Mutex<Components>, // Temperature sensors (empty where the OS doesn't expose any)
 }
 
 impl SystemState {
     pub fn new() -> Self {
         Self {
             sys: Mutex::new(System::new_all()),
+            components: Mutex::new(Components::new_with_refreshed_list()),
         }
     }
 }
 
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemperature {
+    label: String,
+    celsius: f32,
+    critical: Option<f32>,
+}
+
+/// Lightweight snapshot pushed as `system-metrics`, so the dashboard doesn't have to poll.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemMetrics {
+    cpu_usage: f32,
+    cpu_per_core: Vec<f32>,
+    cpu_frequency_mhz: u64,
+    used_memory: u64,
+    total_memory: u64,
+    temperatures: Vec<ComponentTemperature>,
+}
+
+fn read_temperatures(state: &SystemState) -> Vec<ComponentTemperature> {
+    let Ok(mut components) = state.components.lock() else { return vec![] };
+    components.refresh();
+    components.iter()
+        .filter(|c| c.temperature().is_finite() && c.temperature() > 0.0)
+        .map(|c| ComponentTemperature {
+            label: c.label().to_string(),
+            celsius: c.temperature(),
+            critical: c.critical(),
+        })
+        .collect()
+}
+
+fn cpu_frequency(sys: &System) -> u64 {
+    sys.cpus().iter().map(|c| c.frequency()).max().unwrap_or(0)
+}
+
+/// Emits `system-metrics` every few seconds for the app's lifetime.
+pub fn start_system_metrics_stream(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(METRICS_INTERVAL);
+        let state = app.state::<SystemState>();
+        let metrics = {
+            let Ok(mut sys) = state.sys.lock() else { continue };
+            sys.refresh_cpu_specifics(CpuRefreshKind::everything());
+            sys.refresh_memory_specifics(MemoryRefreshKind::new().with_ram());
+            SystemMetrics {
+                cpu_usage: sys.global_cpu_info().cpu_usage(),
+                cpu_per_core: sys.cpus().iter().map(|c| c.cpu_usage()).collect(),
+                cpu_frequency_mhz: cpu_frequency(&sys),
+                used_memory: sys.used_memory(),
+                total_memory: sys.total_memory(),
+                temperatures: Vec::new(),
+            }
+        };
+        let metrics = SystemMetrics { temperatures: read_temperatures(&state), ..metrics };
+        app.emit("system-metrics", metrics);
+    });
+}
+
 #[tauri::command]
 pub fn get_system_info(state: State<SystemState>) -> SystemInfo {
     let mut sys = state.sys.lock().unwrap();
@@ -44,6 +119,10 @@ pub fn get_system_info(state: State<SystemState>) -> SystemInfo {
         host_name: System::host_name().unwrap_or("Unknown".to_string()),
         disk_total_gb: total_disk / 1024 / 1024 / 1024,
         disk_free_gb: free_disk / 1024 / 1024 / 1024,
+        cpu_per_core: sys.cpus().iter().map(|c| c.cpu_usage()).collect(),
+        cpu_frequency_mhz: cpu_frequency(&sys),
+        cpu_brand: sys.cpus().first().map(|c| c.brand().trim().to_string()).unwrap_or_default(),
+        temperatures: read_temperatures(&state),
     }
 }
 
@@ -71,15 +150,139 @@ pub struct SystemInfo {
     host_name: String,
     disk_total_gb: u64,
     disk_free_gb: u64,
+    cpu_per_core: Vec<f32>,
+    cpu_frequency_mhz: u64,
+    cpu_brand: String,
+    temperatures: Vec<ComponentTemperature>,
 }
 
-#[tauri::command]
-pub fn factory_reset(paths: Vec<String>) -> Result<(), String> {
-    for path_str in paths {
-        let path = std::path::Path::new(&path_str);
-        if path.exists() {
-             std::fs::remove_dir_all(path).map_err(|e| format!("Failed to delete {}: {}", path_str, e))?;
+const RESET_TOKEN_TTL: Duration = Duration::from_secs(300);
+
+// The last preview: its token and the exact set it showed. Only that set is ever deleted.
+static RESET_PREVIEW: Mutex<Option<(String, Instant, Vec<ResetItem>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetItem {
+    kind: String, // "server", "backups", "settings", "data"
+    label: String,
+    path: String,
+    size_bytes: u64,
+    skipped: Option<String>, // Why it will be kept, e.g. the server is running
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryResetPreview {
+    items: Vec<ResetItem>,
+    total_bytes: u64,
+    token: String, // Pass to `factory_reset` within five minutes
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryResetReport {
+    deleted: Vec<String>,
+    skipped: Vec<ResetItem>,
+    failed: Vec<String>, // "path: error"
+}
+
+fn reset_item(kind: &str, label: String, path: &Path, running: &[PathBuf]) -> ResetItem {
+    let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let skipped = running.iter()
+        .any(|root| root.starts_with(&canonical) || canonical.starts_with(root))
+        .then(|| "A server in this folder is running".to_string());
+    ResetItem {
+        kind: kind.to_string(),
+        label,
+        path: path.to_string_lossy().to_string(),
+        size_bytes: if path.is_dir() { path_size(path) } else { std::fs::metadata(path).map(|m| m.len()).unwrap_or(0) },
+        skipped,
+    }
+}
+
+/// Everything a factory reset removes: registered servers and the app data dir (backups,
+/// settings, databases). Folders in the data dir that hold a server are covered per server.
+fn collect_reset_items(running: &[PathBuf]) -> Vec<ResetItem> {
+    let servers = load_registry().unwrap_or_default();
+    let server_roots: Vec<PathBuf> = servers.iter()
+        .filter_map(|s| std::fs::canonicalize(&s.path).ok())
+        .collect();
+
+    let mut items: Vec<ResetItem> = servers.iter()
+        .filter(|s| Path::new(&s.path).exists())
+        .map(|s| reset_item("server", s.name.clone(), Path::new(&s.path), running))
+        .collect();
+
+    let app_dir = app_data_dir();
+    if let Ok(entries) = std::fs::read_dir(&app_dir) {
+        let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+        entries.sort();
+        for path in entries {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            if name == "audit.log" {
+                continue; // Keeps a record of the reset itself
+            }
+            let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if server_roots.iter().any(|root| root.starts_with(&canonical)) {
+                continue;
+            }
+            let kind = if name == "Backups" { "backups" } else if path.is_dir() { "data" } else { "settings" };
+            items.push(reset_item(kind, name, &path, running));
         }
     }
-    Ok(())
+    items
+}
+
+/// Lists what `factory_reset` would delete, with sizes, and issues the confirmation token.
+#[tauri::command]
+pub async fn preview_factory_reset(state: State<'_, ServerProcessState>) -> Result<FactoryResetPreview, MineServerError> {
+    let running = running_server_roots(state.inner());
+    let items = tokio::task::spawn_blocking(move || collect_reset_items(&running)).await?;
+
+    let token = uuid::Uuid::new_v4().to_string();
+    *RESET_PREVIEW.lock().map_err(|e| e.to_string())? = Some((token.clone(), Instant::now(), items.clone()));
+
+    Ok(FactoryResetPreview {
+        total_bytes: items.iter().filter(|i| i.skipped.is_none()).map(|i| i.size_bytes).sum(),
+        items,
+        token,
+    })
+}
+
+/// Deletes the set shown by the last `preview_factory_reset`. Servers that started running
+/// since the preview are skipped.
+#[tauri::command]
+pub async fn factory_reset(state: State<'_, ServerProcessState>, token: String) -> Result<FactoryResetReport, MineServerError> {
+    let items = match RESET_PREVIEW.lock().map_err(|e| e.to_string())?.take() {
+        Some((expected, issued, items)) if expected == token && issued.elapsed() < RESET_TOKEN_TTL => items,
+        _ => return Err(MineServerError::permission_denied("The reset confirmation expired. Review the preview again.")),
+    };
+    let running = running_server_roots(state.inner());
+
+    let report = tokio::task::spawn_blocking(move || {
+        audited("factory_reset", json!({ "paths": items.iter().map(|i| &i.path).collect::<Vec<_>>() }), || {
+            let mut report = FactoryResetReport { deleted: vec![], skipped: vec![], failed: vec![] };
+            for item in items {
+                let item = match item.skipped {
+                    Some(_) => item,
+                    None => reset_item(&item.kind, item.label, Path::new(&item.path), &running),
+                };
+                if item.skipped.is_some() {
+                    report.skipped.push(item);
+                    continue;
+                }
+                let path = Path::new(&item.path);
+                let result = if path.is_dir() { std::fs::remove_dir_all(path) } else { std::fs::remove_file(path) };
+                match result {
+                    Ok(()) => report.deleted.push(item.path),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => report.deleted.push(item.path),
+                    Err(e) => report.failed.push(format!("{}: {}", item.path, e)),
+                }
+            }
+            Ok(report)
+        })
+    })
+    .await??;
+    Ok(report)
 }
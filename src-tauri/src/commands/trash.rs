@@ -0,0 +1,182 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use super::path_guard::{app_data_dir, server_root_for, validate_path};
+use super::registry::load_registry;
+use super::settings::load_settings;
+use super::audit::audited;
+use super::server::{move_path_any, path_size};
+use serde_json::json;
+use super::error::MineServerError;
+
+// Deleted files and worlds are moved into `<server>/.mineserver-trash/<id>/` instead of being
+// removed. Each item folder holds the deleted entry plus a meta.json describing it.
+
+pub const TRASH_DIR: &str = ".mineserver-trash";
+const META_FILE: &str = "meta.json";
+const PURGE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+static PURGE_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    pub id: String,
+    pub name: String,
+    pub original_path: String,
+    pub deleted_at: String,
+    pub is_dir: bool,
+    pub size_bytes: u64,
+}
+
+fn trash_dir_for(server_root: &Path) -> PathBuf {
+    server_root.join(TRASH_DIR)
+}
+
+fn read_items(trash_dir: &Path) -> Vec<(PathBuf, TrashItem)> {
+    let mut items: Vec<(PathBuf, TrashItem)> = fs::read_dir(trash_dir)
+        .map(|rd| rd.flatten()
+            .filter_map(|e| {
+                let meta = fs::read_to_string(e.path().join(META_FILE)).ok()?;
+                let item: TrashItem = serde_json::from_str(&meta).ok()?;
+                Some((e.path(), item))
+            })
+            .collect())
+        .unwrap_or_default();
+    items.sort_by(|a, b| b.1.deleted_at.cmp(&a.1.deleted_at));
+    items
+}
+
+/// Removes trash items older than `max_age_days`. Returns how many were purged.
+fn purge_older_than(trash_dir: &Path, max_age_days: u32) -> usize {
+    let cutoff = chrono::Local::now() - chrono::Duration::days(max_age_days as i64);
+    let mut purged = 0;
+    for (dir, item) in read_items(trash_dir) {
+        let expired = chrono::DateTime::parse_from_rfc3339(&item.deleted_at)
+            .map(|t| t < cutoff)
+            .unwrap_or(false);
+        if expired && fs::remove_dir_all(&dir).is_ok() {
+            purged += 1;
+        }
+    }
+    purged
+}
+
+/// Purges expired items from the trash of every registered server and of the app data dir.
+fn purge_all_expired() -> usize {
+    let days = load_settings().trash_retention_days;
+    let mut roots: Vec<PathBuf> = load_registry().unwrap_or_default()
+        .into_iter()
+        .map(|s| PathBuf::from(s.path))
+        .collect();
+    roots.push(app_data_dir());
+    roots.iter().map(|root| purge_older_than(&trash_dir_for(root), days)).sum()
+}
+
+/// Applies the trash retention at startup and every few hours, so servers nobody deletes
+/// anything from don't keep old items forever.
+pub fn start_trash_purge() {
+    if PURGE_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(|| loop {
+        let purged = purge_all_expired();
+        if purged > 0 {
+            eprintln!("[Trash] Purged {} expired item(s)", purged);
+        }
+        std::thread::sleep(PURGE_INTERVAL);
+    });
+}
+
+/// Moves `path` into the trash of `server_root`, or of the registered server that contains it.
+pub fn move_to_trash(path: &Path, server_root: Option<&Path>) -> Result<TrashItem, String> {
+    let path = &fs::canonicalize(path).map_err(|_| "File not found".to_string())?;
+    let server_root = server_root
+        .and_then(|r| fs::canonicalize(r).ok())
+        .or_else(|| server_root_for(path))
+        .unwrap_or_else(app_data_dir);
+    let trash_dir = trash_dir_for(&server_root);
+    if path.starts_with(&trash_dir) {
+        return Err("Item is already in the trash".to_string());
+    }
+
+    let name = path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid path")?;
+    let item = TrashItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.clone(),
+        original_path: path.to_string_lossy().to_string(),
+        deleted_at: chrono::Local::now().to_rfc3339(),
+        is_dir: path.is_dir(),
+        size_bytes: path_size(path),
+    };
+
+    let item_dir = trash_dir.join(&item.id);
+    fs::create_dir_all(&item_dir).map_err(|e| format!("Failed to create trash folder: {}", e))?;
+    move_path_any(path, &item_dir.join(&name))
+        .map_err(|e| format!("Failed to move to trash: {}", e))?;
+    let meta = serde_json::to_string_pretty(&item).map_err(|e| e.to_string())?;
+    fs::write(item_dir.join(META_FILE), meta).map_err(|e| format!("Failed to write trash metadata: {}", e))?;
+
+    purge_older_than(&trash_dir, load_settings().trash_retention_days);
+    Ok(item)
+}
+
+#[tauri::command]
+pub fn list_trash(server_path: String) -> Result<Vec<TrashItem>, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let trash_dir = trash_dir_for(&root);
+    purge_older_than(&trash_dir, load_settings().trash_retention_days);
+    Ok(read_items(&trash_dir).into_iter().map(|(_, item)| item).collect())
+}
+
+/// Moves an item back to where it was deleted from. Fails if something new exists there.
+#[tauri::command]
+pub fn restore_trash_item(server_path: String, id: String) -> Result<TrashItem, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let (item_dir, item) = read_items(&trash_dir_for(&root))
+        .into_iter()
+        .find(|(_, item)| item.id == id)
+        .ok_or("Trash item not found")?;
+
+    let target = validate_path(&item.original_path)?;
+    if target.exists() {
+        return Err(MineServerError::conflict(format!("Cannot restore: {} already exists", item.original_path)));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    move_path_any(&item_dir.join(&item.name), &target)
+        .map_err(|e| format!("Failed to restore item: {}", e))?;
+    fs::remove_dir_all(&item_dir).map_err(|e| e.to_string())?;
+    Ok(item)
+}
+
+/// Empties the trash, or only items older than `older_than_days`. Returns the number removed.
+#[tauri::command]
+pub async fn empty_trash(server_path: String, older_than_days: Option<u32>) -> Result<usize, MineServerError> {
+    tokio::task::spawn_blocking(move || empty_trash_sync(server_path, older_than_days))
+        .await?
+        .map_err(Into::into)
+}
+
+fn empty_trash_sync(server_path: String, older_than_days: Option<u32>) -> Result<usize, String> {
+    audited("empty_trash", json!({ "serverPath": server_path, "olderThanDays": older_than_days }), || {
+        let root = validate_path(&server_path)?;
+        let trash_dir = trash_dir_for(&root);
+        match older_than_days {
+            Some(days) => Ok(purge_older_than(&trash_dir, days)),
+            None => {
+                let count = read_items(&trash_dir).len();
+                if trash_dir.exists() {
+                    fs::remove_dir_all(&trash_dir).map_err(|e| format!("Failed to empty trash: {}", e))?;
+                }
+                Ok(count)
+            }
+        }
+    })
+}
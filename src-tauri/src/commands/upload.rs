@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::Write;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, State};
+use super::path_guard::validate_path;
+use super::runner::ServerEventSink;
+use super::error::MineServerError;
+
+// Chunked uploads: the frontend sends large files (world zips, modpacks, jars) in pieces
+// instead of one huge write_binary_file call. Data goes to a temp file next to the target
+// and is only moved into place once the size (and optional SHA-256) match.
+
+const MAX_CHUNK_BYTES: usize = 16 * 1024 * 1024;
+const TEMP_SUFFIX: &str = ".mineserver-upload";
+
+struct PendingUpload {
+    target: PathBuf,
+    temp: PathBuf,
+    file: File,
+    received: u64,
+    total: u64,
+    hasher: Sha256,
+    expected_sha256: Option<String>,
+}
+
+pub struct UploadState {
+    uploads: Mutex<HashMap<String, PendingUpload>>,
+}
+
+impl UploadState {
+    pub fn new() -> Self {
+        Self {
+            uploads: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgress {
+    pub upload_id: String,
+    pub received: u64,
+    pub total: u64,
+    pub percentage: u8,
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Starts an upload to `path`. Returns the upload id used by the chunk/finish calls.
+#[tauri::command]
+pub fn begin_upload(
+    state: State<'_, UploadState>,
+    path: String,
+    total_size: u64,
+    sha256: Option<String>,
+) -> Result<String, MineServerError> {
+    let target = validate_path(&path)?;
+    if target.is_dir() {
+        return Err(MineServerError::validation("Target is a directory"));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let upload_id = uuid::Uuid::new_v4().to_string();
+    let mut temp = target.clone().into_os_string();
+    temp.push(TEMP_SUFFIX);
+    let temp = PathBuf::from(temp);
+    let file = File::create(&temp).map_err(|e| format!("Failed to create upload file: {}", e))?;
+
+    state.uploads.lock().map_err(|e| e.to_string())?.insert(upload_id.clone(), PendingUpload {
+        target,
+        temp,
+        file,
+        received: 0,
+        total: total_size,
+        hasher: Sha256::new(),
+        expected_sha256: sha256.map(|h| h.trim().to_lowercase()),
+    });
+
+    Ok(upload_id)
+}
+
+/// Appends a chunk. Chunks must arrive in order; `offset` guards against duplicates and gaps.
+#[tauri::command]
+pub fn upload_chunk(
+    app: AppHandle,
+    state: State<'_, UploadState>,
+    upload_id: String,
+    offset: u64,
+    data: Vec<u8>,
+) -> Result<UploadProgress, MineServerError> {
+    if data.len() > MAX_CHUNK_BYTES {
+        return Err(MineServerError::validation(format!("Chunk too large (max {} MB)", MAX_CHUNK_BYTES / 1024 / 1024)));
+    }
+
+    let mut uploads = state.uploads.lock().map_err(|e| e.to_string())?;
+    let upload = uploads.get_mut(&upload_id).ok_or("Upload not found")?;
+
+    if offset != upload.received {
+        return Err(MineServerError::validation(format!("Unexpected chunk offset {} (expected {})", offset, upload.received)));
+    }
+    if upload.received + data.len() as u64 > upload.total {
+        return Err(MineServerError::validation("Upload exceeds the declared size"));
+    }
+
+    upload.file.write_all(&data).map_err(|e| format!("Failed to write chunk: {}", e))?;
+    upload.hasher.update(&data);
+    upload.received += data.len() as u64;
+
+    let progress = UploadProgress {
+        upload_id: upload_id.clone(),
+        received: upload.received,
+        total: upload.total,
+        percentage: if upload.total > 0 { (upload.received * 100 / upload.total) as u8 } else { 100 },
+    };
+    app.emit("upload-progress", progress.clone());
+    Ok(progress)
+}
+
+/// Verifies size and hash, then moves the file into place. Returns the SHA-256 of the data.
+#[tauri::command]
+pub fn finish_upload(state: State<'_, UploadState>, upload_id: String) -> Result<String, MineServerError> {
+    let upload = state.uploads.lock().map_err(|e| e.to_string())?
+        .remove(&upload_id)
+        .ok_or("Upload not found")?;
+
+    let PendingUpload { target, temp, mut file, received, total, hasher, expected_sha256 } = upload;
+    file.flush().map_err(|e| e.to_string())?;
+    drop(file);
+
+    if received != total {
+        let _ = fs::remove_file(&temp);
+        return Err(MineServerError::validation(format!("Upload incomplete: received {} of {} bytes", received, total)));
+    }
+
+    let digest = to_hex(&hasher.finalize());
+    if let Some(expected) = expected_sha256 {
+        if expected != digest {
+            let _ = fs::remove_file(&temp);
+            return Err(MineServerError::validation(format!("Checksum mismatch: expected {}, got {}", expected, digest)));
+        }
+    }
+
+    if target.exists() {
+        fs::remove_file(&target).map_err(|e| format!("Failed to replace existing file: {}", e))?;
+    }
+    fs::rename(&temp, &target).map_err(|e| format!("Failed to move upload into place: {}", e))?;
+    Ok(digest)
+}
+
+#[tauri::command]
+pub fn cancel_upload(state: State<'_, UploadState>, upload_id: String) -> Result<(), MineServerError> {
+    if let Some(upload) = state.uploads.lock().map_err(|e| e.to_string())?.remove(&upload_id) {
+        drop(upload.file);
+        let _ = fs::remove_file(&upload.temp);
+    }
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use super::ping::{ping_bedrock, ping_java};
+use super::network_manager::server_listen_port;
+use super::registry::load_registry;
+use super::runner::ServerProcessState;
+use super::settings::load_settings;
+use super::error::MineServerError;
+use super::path_guard::app_data_dir;
+
+// Optional uptime monitor (`uptimeMonitor` setting): pings every registered server once a
+// minute with Server List Ping and keeps the results in ~/Mineserver/uptime.db for 30 days.
+//
+// Each sample is "online", "down" (started in this session and not stopped by the user, but
+// not answering: crashed or hung) or "stopped" (stopped on purpose, or never started).
+// Stopped samples don't count against uptime.
+
+const PING_INTERVAL: Duration = Duration::from_secs(60);
+const RETENTION_SECS: i64 = 30 * 24 * 3600;
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingSample {
+    pub timestamp: i64, // Unix seconds
+    pub status: String,
+    pub latency_ms: Option<u64>,
+    pub players: Option<u32>,
+    pub max_players: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Incident {
+    pub started_at: i64,
+    pub ended_at: Option<i64>, // None while still down
+    pub duration_secs: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeReport {
+    pub uptime_percentage: Option<f64>, // None without any online/down samples
+    pub samples: u32,
+    pub average_latency_ms: Option<f64>,
+    pub incidents: Vec<Incident>,
+}
+
+fn get_uptime_db() -> PathBuf {
+    app_data_dir().join("uptime.db")
+}
+
+fn open_db() -> Result<Connection, String> {
+    let path = get_uptime_db();
+    std::fs::create_dir_all(path.parent().unwrap())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let conn = Connection::open(&path)
+        .map_err(|e| format!("Failed to open uptime database: {}", e))?;
+    conn.busy_timeout(Duration::from_secs(5)).map_err(|e| e.to_string())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         CREATE TABLE IF NOT EXISTS pings (
+             server_id TEXT NOT NULL,
+             ts INTEGER NOT NULL,
+             status TEXT NOT NULL,
+             latency_ms INTEGER,
+             players INTEGER,
+             max_players INTEGER
+         );
+         CREATE INDEX IF NOT EXISTS idx_pings_server_ts ON pings (server_id, ts);"
+    ).map_err(|e| format!("Failed to initialize uptime database: {}", e))?;
+    Ok(conn)
+}
+
+fn record_pings(conn: &Connection, proc_state: &ServerProcessState) -> Result<(), String> {
+    let now = chrono::Utc::now().timestamp();
+    for server in load_registry()? {
+        let running = proc_state.processes.lock().map(|p| p.contains_key(&server.id)).unwrap_or(false);
+        let (port, protocol) = server_listen_port(&server);
+        let ping = if protocol == "UDP" { ping_bedrock("127.0.0.1", port) } else { ping_java("127.0.0.1", port) };
+
+        let (status, result) = match ping {
+            Ok(result) => ("online", Some(result)),
+            Err(_) => {
+                let started_this_session = proc_state.lifecycle.lock().map(|l| l.contains_key(&server.id)).unwrap_or(false);
+                let stopped_by_user = proc_state.explicit_stops.lock().map(|s| s.contains(&server.id)).unwrap_or(false);
+                if running || (started_this_session && !stopped_by_user) { ("down", None) } else { ("stopped", None) }
+            }
+        };
+        conn.execute(
+            "INSERT INTO pings (server_id, ts, status, latency_ms, players, max_players) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                server.id,
+                now,
+                status,
+                result.as_ref().map(|r| r.latency_ms as i64),
+                result.as_ref().map(|r| r.online_players),
+                result.as_ref().map(|r| r.max_players),
+            ],
+        ).map_err(|e| format!("Failed to record ping: {}", e))?;
+    }
+    conn.execute("DELETE FROM pings WHERE ts < ?1", params![now - RETENTION_SECS])
+        .map_err(|e| format!("Failed to prune pings: {}", e))?;
+    Ok(())
+}
+
+/// Starts the background monitor (once per process). It idles while the setting is off.
+pub fn start_uptime_monitor(proc_state: ServerProcessState) {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let mut conn: Option<Connection> = None;
+        loop {
+            std::thread::sleep(PING_INTERVAL);
+            if !load_settings().uptime_monitor {
+                continue;
+            }
+            if conn.is_none() {
+                match open_db() {
+                    Ok(c) => conn = Some(c),
+                    Err(e) => {
+                        eprintln!("[Uptime] {}", e);
+                        continue;
+                    }
+                }
+            }
+            if let Some(conn) = &conn {
+                if let Err(e) = record_pings(conn, &proc_state) {
+                    eprintln!("[Uptime] {}", e);
+                }
+            }
+        }
+    });
+}
+
+fn load_samples(conn: &Connection, id: &str, from: i64, to: i64) -> Result<Vec<PingSample>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT ts, status, latency_ms, players, max_players FROM pings
+         WHERE server_id = ?1 AND ts >= ?2 AND ts <= ?3 ORDER BY ts"
+    ).map_err(|e| format!("Failed to query pings: {}", e))?;
+    let rows = stmt.query_map(params![id, from, to], |row| {
+        Ok(PingSample {
+            timestamp: row.get(0)?,
+            status: row.get(1)?,
+            latency_ms: row.get::<_, Option<i64>>(2)?.map(|l| l.max(0) as u64),
+            players: row.get(3)?,
+            max_players: row.get(4)?,
+        })
+    }).map_err(|e| format!("Failed to query pings: {}", e))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read pings: {}", e))
+}
+
+/// Consecutive "down" samples form one incident; it ends at the next online or stopped sample.
+fn find_incidents(samples: &[PingSample]) -> Vec<Incident> {
+    let mut incidents = Vec::new();
+    let mut open: Option<i64> = None;
+    for sample in samples {
+        match (sample.status.as_str(), open) {
+            ("down", None) => open = Some(sample.timestamp),
+            ("down", Some(_)) => {}
+            (_, Some(started_at)) => {
+                incidents.push(Incident { started_at, ended_at: Some(sample.timestamp), duration_secs: sample.timestamp - started_at });
+                open = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(started_at) = open {
+        let last = samples.last().map(|s| s.timestamp).unwrap_or(started_at);
+        incidents.push(Incident { started_at, ended_at: None, duration_secs: last - started_at + PING_INTERVAL.as_secs() as i64 });
+    }
+    incidents
+}
+
+/// Raw ping samples for a server between `from` and `to` (Unix seconds).
+#[tauri::command]
+pub async fn get_ping_history(id: String, from: i64, to: i64) -> Result<Vec<PingSample>, MineServerError> {
+    tokio::task::spawn_blocking(move || {
+        let conn = open_db()?;
+        load_samples(&conn, &id, from, to)
+    })
+    .await?
+    .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_uptime_report(id: String, from: i64, to: i64) -> Result<UptimeReport, MineServerError> {
+    tokio::task::spawn_blocking(move || {
+        let conn = open_db()?;
+        let samples = load_samples(&conn, &id, from, to)?;
+
+        let online = samples.iter().filter(|s| s.status == "online").count();
+        let down = samples.iter().filter(|s| s.status == "down").count();
+        let latencies: Vec<u64> = samples.iter().filter_map(|s| s.latency_ms).collect();
+
+        Ok::<_, String>(UptimeReport {
+            uptime_percentage: (online + down > 0).then(|| online as f64 * 100.0 / (online + down) as f64),
+            samples: samples.len() as u32,
+            average_latency_ms: (!latencies.is_empty()).then(|| latencies.iter().sum::<u64>() as f64 / latencies.len() as f64),
+            incidents: find_incidents(&samples),
+        })
+    })
+    .await?
+    .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn clear_ping_history(id: String) -> Result<(), MineServerError> {
+    tokio::task::spawn_blocking(move || {
+        let conn = open_db()?;
+        conn.execute("DELETE FROM pings WHERE server_id = ?1", params![id])
+            .map_err(|e| format!("Failed to clear ping history: {}", e))?;
+        Ok::<_, String>(())
+    })
+    .await?
+    .map_err(Into::into)
+}
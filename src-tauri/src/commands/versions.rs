@@ -1,5 +1,8 @@
-use reqwest::Client;
+use tauri::State;
+use super::http::HttpState;
+use super::downloader::SPONGE_DOWNLOADS_API;
 use serde::{Deserialize, Serialize};
+use super::error::MineServerError;
 // use std::collections::HashMap;
 
 #[derive(Debug, Deserialize)]
@@ -27,11 +30,8 @@ pub struct ServerVersion {
 }
 
 #[tauri::command]
-pub async fn get_vanilla_versions() -> Result<Vec<String>, String> {
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn get_vanilla_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
+    let client = http.client();
     let resp = client
         .get("https://launchermeta.mojang.com/mc/game/version_manifest.json")
         .send()
@@ -51,11 +51,8 @@ pub async fn get_vanilla_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_paper_versions() -> Result<Vec<String>, String> {
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn get_paper_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
+    let client = http.client();
     let resp = client
         .get("https://api.papermc.io/v2/projects/paper")
         .send()
@@ -73,14 +70,11 @@ pub async fn get_paper_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_bedrock_versions() -> Result<Vec<String>, String> {
+pub async fn get_bedrock_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
     // Use Bedrock-OSS API (maintained community list)
     // Source: https://github.com/Bedrock-OSS/BDS-Versions
     
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = http.client();
     
     let mut versions = Vec::new();
     
@@ -124,12 +118,9 @@ pub async fn get_bedrock_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_forge_versions() -> Result<Vec<String>, String> {
+pub async fn get_forge_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
     // Forge uses Maven for versions - fetch from their promotions API
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = http.client();
     
     let mut versions = Vec::new();
     
@@ -171,15 +162,12 @@ pub async fn get_forge_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_neoforge_versions() -> Result<Vec<String>, String> {
+pub async fn get_neoforge_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
     // NeoForge uses Maven repository for versions
     // API: https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge
     // Or simpler: fetch the loader versions from their meta API
     
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = http.client();
     
     let mut versions = Vec::new();
     
@@ -226,12 +214,9 @@ pub async fn get_neoforge_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_fabric_versions() -> Result<Vec<String>, String> {
+pub async fn get_fabric_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
     // Fabric uses their own meta API
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = http.client();
     
     let mut versions = Vec::new();
     
@@ -266,13 +251,9 @@ pub async fn get_fabric_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_spigot_versions() -> Result<Vec<String>, String> {
+pub async fn get_spigot_versions() -> Result<Vec<String>, MineServerError> {
     // Spigot uses the same PaperMC API structure (they mirror versions)
     // We'll use GetBukkit API or fallback to known versions
-    let _client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
-        .build()
-        .map_err(|e| e.to_string())?;
     
     // GetBukkit maintains a Spigot download mirror
     let versions = vec![
@@ -286,12 +267,9 @@ pub async fn get_spigot_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_purpur_versions() -> Result<Vec<String>, String> {
+pub async fn get_purpur_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
     // Purpur uses PaperMC-style API
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = http.client();
     
     let mut versions = Vec::new();
     
@@ -323,12 +301,9 @@ pub async fn get_purpur_versions() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn get_nukkit_versions() -> Result<Vec<String>, String> {
+pub async fn get_nukkit_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
     // Cloudburst Nukkit for Bedrock support
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = http.client();
     
     let mut versions = Vec::new();
     
@@ -359,3 +334,52 @@ pub async fn get_nukkit_versions() -> Result<Vec<String>, String> {
     
     Ok(versions)
 }
+
+/// Minecraft versions with a Sponge build, newest first. Snapshots and pre-releases are skipped.
+async fn sponge_versions(http: &HttpState, artifact: &str) -> Vec<String> {
+    let url = format!("{}/{}", SPONGE_DOWNLOADS_API, artifact);
+    let Ok(parsed) = http.get_json_cached::<serde_json::Value>(&url).await else {
+        return vec![];
+    };
+    let release_parts = |v: &str| -> Option<Vec<u32>> {
+        v.split('.').map(|part| part.parse().ok()).collect()
+    };
+    let mut versions: Vec<(Vec<u32>, String)> = parsed["tags"]["minecraft"].as_array()
+        .map(|list| list.iter()
+            .filter_map(|v| v.as_str())
+            .filter_map(|v| Some((release_parts(v)?, v.to_string())))
+            .collect())
+        .unwrap_or_default();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+    versions.into_iter().map(|(_, v)| v).collect()
+}
+
+#[tauri::command]
+pub async fn get_spongevanilla_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
+    let mut versions = sponge_versions(http.inner(), "spongevanilla").await;
+
+    // Fallback
+    if versions.is_empty() {
+        versions = vec![
+            "1.21.1".to_string(), "1.20.6".to_string(), "1.20.4".to_string(),
+            "1.19.4".to_string(), "1.18.2".to_string(), "1.16.5".to_string(),
+        ];
+    }
+
+    Ok(versions)
+}
+
+#[tauri::command]
+pub async fn get_spongeforge_versions(http: State<'_, HttpState>) -> Result<Vec<String>, MineServerError> {
+    let mut versions = sponge_versions(http.inner(), "spongeforge").await;
+
+    // Fallback
+    if versions.is_empty() {
+        versions = vec![
+            "1.21.1".to_string(), "1.20.6".to_string(), "1.20.4".to_string(),
+            "1.20.1".to_string(), "1.19.4".to_string(), "1.16.5".to_string(),
+        ];
+    }
+
+    Ok(versions)
+}
@@ -0,0 +1,234 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use tauri::State;
+use super::http::HttpState;
+use super::network_manager::{map_server_port, open_server_firewall, NetworkSetupEvent};
+use super::path_guard::validate_path;
+use super::plugins::ModrinthVersion;
+use super::registry::load_registry;
+use super::runner::{running_server_roots, ServerProcessState};
+use super::error::MineServerError;
+
+// Simple Voice Chat: installs the plugin or mod from Modrinth, sets its UDP port and opens that
+// port on the router and firewall. Voice runs on its own UDP port (24454 by default), separate
+// from the game port, which is the usual reason players can't connect after installing it.
+
+const PROJECT: &str = "simple-voice-chat";
+const DEFAULT_PORT: u16 = 24454;
+const CONFIG_FILE: &str = "voicechat-server.properties";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceChatSetup {
+    pub file_name: String,
+    pub port: u16,
+    pub steps: Vec<NetworkSetupEvent>, // "upnp", "firewall" and "reachability"
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceChatStatus {
+    pub installed: bool,
+    pub port: Option<u16>,
+    pub listening: bool, // Something holds the UDP port while the server runs
+    pub message: String,
+}
+
+/// Modrinth loaders and the folder the jar goes into.
+fn platform_target(platform: &str) -> Result<(&'static [&'static str], &'static str), MineServerError> {
+    match platform.to_lowercase().as_str() {
+        "paper" | "spigot" | "bukkit" | "purpur" | "folia" => Ok((&["paper", "bukkit", "spigot"], "plugins")),
+        "fabric" => Ok((&["fabric"], "mods")),
+        "quilt" => Ok((&["quilt", "fabric"], "mods")),
+        "forge" => Ok((&["forge"], "mods")),
+        "neoforge" => Ok((&["neoforge"], "mods")),
+        other => Err(MineServerError::validation(format!("Simple Voice Chat is not available for {}", other))),
+    }
+}
+
+/// Plugins keep their config in plugins/voicechat/, mods in config/voicechat/.
+fn config_path(server_root: &Path) -> PathBuf {
+    let folder = if server_root.join("plugins").is_dir() && !server_root.join("mods").is_dir() {
+        server_root.join("plugins")
+    } else {
+        server_root.join("config")
+    };
+    folder.join("voicechat").join(CONFIG_FILE)
+}
+
+fn configured_port(server_root: &Path) -> Option<u16> {
+    fs::read_to_string(config_path(server_root)).ok()?
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("port="))
+        .next()
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn is_installed(server_root: &Path) -> bool {
+    ["plugins", "mods"].iter().any(|folder| {
+        fs::read_dir(server_root.join(folder))
+            .map(|rd| rd.flatten().any(|e| is_voice_chat_jar(&e.file_name().to_string_lossy())))
+            .unwrap_or(false)
+    })
+}
+
+fn is_voice_chat_jar(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with("voicechat-") && name.ends_with(".jar")
+}
+
+/// Sets `port=` in the voice chat config. The plugin fills in the remaining defaults on start.
+fn write_port(server_root: &Path, port: u16) -> Result<(), String> {
+    let path = config_path(server_root);
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| format!("Failed to create config folder: {}", e))?;
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let mut found = false;
+    let mut lines: Vec<String> = existing.lines()
+        .map(|line| {
+            if line.trim().starts_with("port=") {
+                found = true;
+                format!("port={}", port)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !found {
+        lines.push(format!("port={}", port));
+    }
+    fs::write(&path, lines.join("\n") + "\n").map_err(|e| format!("Failed to write {}: {}", CONFIG_FILE, e))
+}
+
+/// Keeps the configured port, or picks the first one from 24454 up that no other registered
+/// server uses for voice chat and nothing else has bound.
+fn choose_port(server_root: &Path) -> u16 {
+    if let Some(port) = configured_port(server_root) {
+        return port;
+    }
+    let own = fs::canonicalize(server_root).ok();
+    let taken: Vec<u16> = load_registry().unwrap_or_default()
+        .iter()
+        .filter(|s| fs::canonicalize(&s.path).ok() != own)
+        .filter_map(|s| configured_port(Path::new(&s.path)))
+        .collect();
+    (DEFAULT_PORT..DEFAULT_PORT + 100)
+        .find(|p| !taken.contains(p) && std::net::UdpSocket::bind(("0.0.0.0", *p)).is_ok())
+        .unwrap_or(DEFAULT_PORT)
+}
+
+fn check_status(state: &ServerProcessState, server_root: &Path) -> VoiceChatStatus {
+    let installed = is_installed(server_root);
+    let port = configured_port(server_root);
+    let running = fs::canonicalize(server_root)
+        .map(|root| running_server_roots(state).contains(&root))
+        .unwrap_or(false);
+    // A failed bind while the server runs means the voice chat server holds the port
+    let listening = running && port.map(|p| std::net::UdpSocket::bind(("0.0.0.0", p)).is_err()).unwrap_or(false);
+    let message = match (installed, port, running, listening) {
+        (false, _, _, _) => "Simple Voice Chat is not installed".to_string(),
+        (true, None, _, _) => "Start the server once to generate the voice chat config".to_string(),
+        (true, Some(p), false, _) => format!("Start the server to check UDP port {}", p),
+        (true, Some(p), true, true) => format!("Voice chat is listening on UDP port {}", p),
+        (true, Some(p), true, false) => format!("Nothing is listening on UDP port {}. Check the server log for voice chat errors.", p),
+    };
+    VoiceChatStatus { installed, port, listening, message }
+}
+
+/// Downloads Simple Voice Chat for the platform, sets its UDP port and forwards/opens that port.
+/// Network steps that fail are reported in `steps` instead of failing the install.
+#[tauri::command]
+pub async fn install_voice_chat(
+    http: State<'_, HttpState>,
+    state: State<'_, ServerProcessState>,
+    server_id: String,
+    server_path: String,
+    platform: String,
+    game_version: Option<String>,
+) -> Result<VoiceChatSetup, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let (loaders, folder) = platform_target(&platform)?;
+
+    let loaders_json = serde_json::to_string(loaders).unwrap_or_default();
+    let mut versions_url = format!(
+        "https://api.modrinth.com/v2/project/{}/version?loaders={}",
+        PROJECT, urlencoding::encode(&loaders_json)
+    );
+    if let Some(version) = game_version.filter(|v| !v.trim().is_empty()) {
+        versions_url.push_str(&format!("&game_versions={}", urlencoding::encode(&format!("[\"{}\"]", version))));
+    }
+
+    let client = http.client();
+    let versions: Vec<ModrinthVersion> = client.get(&versions_url)
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| MineServerError::external("Modrinth", e.to_string()))?
+        .json()
+        .await?;
+    let version = versions.first()
+        .ok_or_else(|| MineServerError::not_found("No Simple Voice Chat build for this platform and version"))?;
+    let file = version.files.iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+        .ok_or("No file found for this version")?;
+
+    let resp = client.get(&file.url).send().await?;
+    if !resp.status().is_success() {
+        return Err(MineServerError::external("Modrinth", format!("Download failed with status: {}", resp.status())));
+    }
+    let bytes = resp.bytes().await?;
+
+    let target_dir = root.join(folder);
+    fs::create_dir_all(&target_dir)?;
+    // Replace older builds instead of loading two copies
+    if let Ok(entries) = fs::read_dir(&target_dir) {
+        for entry in entries.flatten() {
+            if is_voice_chat_jar(&entry.file_name().to_string_lossy()) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    tokio::fs::write(target_dir.join(&file.filename), &bytes).await
+        .map_err(|e| format!("File Write Error: {}", e))?;
+
+    let port = choose_port(&root);
+    write_port(&root, port)?;
+
+    // The gateway search alone can take seconds
+    let status_root = root.clone();
+    let process_state = state.inner().clone();
+    let steps = tokio::task::spawn_blocking(move || {
+        let step = |step: &str, result: Result<String, MineServerError>| {
+            let (success, message) = match result {
+                Ok(message) => (true, message),
+                Err(e) => (false, e.to_string()),
+            };
+            NetworkSetupEvent { server_id: server_id.clone(), step: step.to_string(), success, message }
+        };
+        let status = check_status(&process_state, &status_root);
+        vec![
+            step("upnp", map_server_port(&server_id, port, "UDP").map(|ip| format!("UDP port {} forwarded ({})", port, ip))),
+            step("firewall", open_server_firewall(&server_id, port).map(|_| format!("Port {} allowed through the firewall", port))),
+            step("reachability", if status.listening { Ok(status.message) } else { Err(MineServerError::validation(status.message)) }),
+        ]
+    }).await?;
+
+    Ok(VoiceChatSetup { file_name: file.filename.clone(), port, steps })
+}
+
+/// Changes the voice chat UDP port. Restart the server for it to take effect.
+#[tauri::command]
+pub fn set_voice_chat_port(server_path: String, port: u16) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+    if port == 0 {
+        return Err(MineServerError::validation("Port must be between 1 and 65535"));
+    }
+    write_port(&root, port).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn get_voice_chat_status(state: State<'_, ServerProcessState>, server_path: String) -> Result<VoiceChatStatus, MineServerError> {
+    let root = validate_path(&server_path)?;
+    Ok(check_status(state.inner(), &root))
+}
@@ -1,8 +1,27 @@
 use std::path::{Path, PathBuf};
 use std::fs::{self, File};
-use std::io::{Read, Write, Cursor};
+use std::io::{Write, Cursor};
 use std::collections::HashMap;
-use serde::Serialize;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use super::archive::{compression_workers, zip_files_parallel, zip_write_error};
+use super::server::path_size;
+use super::extract::{extract_zip, common_root_prefix, ExtractLimits};
+use super::audit::audited;
+use super::trash::move_to_trash;
+use super::operations::{Operation, OperationState};
+use tauri::State;
+use super::runner::{ServerProcessState, console_output_since, ensure_not_running, send_server_command_direct, start_server_direct, stop_server_direct};
+use super::registry::find_registered_server;
+use serde_json::json;
+use super::activity::Activity;
+use super::json_store::write_text_atomic;
+use super::error::MineServerError;
+
+// Loading a fresh world can take minutes on slow machines or with big modpacks
+const REGEN_TIMEOUT: Duration = Duration::from_secs(600);
+const SEED_TIMEOUT: Duration = Duration::from_secs(15);
+const CONSOLE_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 
 #[derive(Serialize)]
@@ -12,6 +31,15 @@ pub struct Dimension {
     size_bytes: u64,
 }
 
+#[derive(Serialize)]
+pub struct PluginWorld {
+    name: String,
+    environment: String, // "normal", "nether" or "the_end"
+    source: String,      // Plugin whose config lists it
+    size_bytes: u64,
+    exists: bool,
+}
+
 #[derive(Serialize)]
 pub struct WorldGroup {
     level_name: String,
@@ -19,9 +47,87 @@ pub struct WorldGroup {
     total_size: u64,
     exists: bool,
     path_debug: String,
+    plugin_worlds: Vec<PluginWorld>, // Worlds of Multiverse & co. besides the main level
+}
+
+// Multi-world plugins on Bukkit servers keep their worlds next to the main one and list them in
+// their own config: Multiverse-Core under `worlds:` (4.x) or at the top level (5.x), MultiWorld
+// under `worlds:` only (its config.yml has other settings at the top level).
+// (plugin, config, whether worlds may be listed at the top level)
+const WORLD_PLUGIN_CONFIGS: [(&str, &str, bool); 2] = [
+    ("Multiverse-Core", "plugins/Multiverse-Core/worlds.yml", true),
+    ("MultiWorld", "plugins/MultiWorld/config.yml", false),
+];
+
+/// Folder Bukkit keeps worlds in: the server root unless bukkit.yml sets `world-container`.
+fn world_container(server_path: &Path) -> PathBuf {
+    let configured = fs::read_to_string(server_path.join("bukkit.yml")).ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|bukkit| bukkit["settings"]["world-container"].as_str().map(|c| c.trim().to_string()))
+        .filter(|c| !c.is_empty());
+    match configured {
+        Some(container) => server_path.join(container),
+        None => server_path.to_path_buf(),
+    }
+}
+
+/// A plain folder name, so a name from a config can't point outside the world container.
+fn is_folder_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!((components.next(), components.next()), (Some(std::path::Component::Normal(_)), None))
 }
 
-fn get_level_name(server_path: &Path) -> String {
+/// World entries of a plugin config: (name, environment).
+fn listed_worlds(config: &serde_yaml::Value, top_level: bool) -> Vec<(String, String)> {
+    let worlds = match config["worlds"].as_mapping() {
+        Some(worlds) => worlds,
+        None => match config.as_mapping().filter(|_| top_level) {
+            Some(top) => top,
+            None => return Vec::new(),
+        },
+    };
+    worlds.iter()
+        .filter(|(_, world)| world.is_mapping())
+        .filter_map(|(name, world)| {
+            let environment = world["environment"].as_str().or_else(|| world["env"].as_str()).unwrap_or("NORMAL");
+            Some((name.as_str()?.to_string(), environment.to_lowercase()))
+        })
+        .collect()
+}
+
+/// Worlds the multi-world plugins manage besides the main level and its nether/end, as
+/// (name, environment, plugin).
+fn plugin_world_entries(server_path: &Path, level_name: &str) -> Vec<(String, String, &'static str)> {
+    let main = [level_name.to_string(), format!("{}_nether", level_name), format!("{}_the_end", level_name)];
+    let mut entries: Vec<(String, String, &'static str)> = Vec::new();
+    for (plugin, config, top_level) in WORLD_PLUGIN_CONFIGS {
+        let Some(config) = fs::read_to_string(server_path.join(config)).ok()
+            .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok()) else { continue };
+        for (name, environment) in listed_worlds(&config, top_level) {
+            if is_folder_name(&name) && !main.contains(&name) && !entries.iter().any(|(n, _, _)| *n == name) {
+                entries.push((name, environment, plugin));
+            }
+        }
+    }
+    entries
+}
+
+/// Folder of a world that isn't part of the main level group: one listed by a multi-world
+/// plugin, or any folder in the world container holding a level.dat.
+fn other_world_path(server_path: &Path, world: &str) -> Result<PathBuf, String> {
+    if !is_folder_name(world) {
+        return Err("Invalid world name".to_string());
+    }
+    let folder = world_container(server_path).join(world);
+    let listed = plugin_world_entries(server_path, &get_level_name(server_path)).iter().any(|(name, _, _)| name == world);
+    if listed || folder.join("level.dat").is_file() {
+        Ok(folder)
+    } else {
+        Err(format!("{} is not a world of this server", world))
+    }
+}
+
+pub(crate) fn get_level_name(server_path: &Path) -> String {
     let props_path = server_path.join("server.properties");
     if props_path.exists() {
          if let Ok(content) = fs::read_to_string(&props_path) {
@@ -36,24 +142,6 @@ fn get_level_name(server_path: &Path) -> String {
     "world".to_string()
 }
 
-fn get_dir_size(path: &Path) -> u64 {
-    let mut size = 0;
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                if let Ok(meta) = entry.metadata() {
-                    if meta.is_dir() {
-                        size += get_dir_size(&entry.path());
-                    } else {
-                        size += meta.len();
-                    }
-                }
-            }
-        }
-    }
-    size
-}
-
 fn parse_properties(path: &Path) -> HashMap<String, String> {
     let mut map = HashMap::new();
     if let Ok(content) = fs::read_to_string(path) {
@@ -67,7 +155,7 @@ fn parse_properties(path: &Path) -> HashMap<String, String> {
     map
 }
 
-fn resolve_world_path(server_path: &Path, level_name: &str) -> PathBuf {
+pub(crate) fn resolve_world_path(server_path: &Path, level_name: &str) -> PathBuf {
     // 1. Check strict Bedrock path (worlds/level_name)
     let bedrock_world = server_path.join("worlds").join(level_name);
     if bedrock_world.exists() && bedrock_world.is_dir() {
@@ -92,10 +180,10 @@ fn resolve_world_path(server_path: &Path, level_name: &str) -> PathBuf {
 }
 
 #[tauri::command]
-pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
+pub fn get_world_info(server_path: String) -> Result<WorldGroup, MineServerError> {
     let path = Path::new(&server_path);
     if !path.exists() {
-        return Err("Server path not found".to_string());
+        return Err(MineServerError::not_found("Server path not found"));
     }
 
     let level_name = get_level_name(path);
@@ -107,7 +195,7 @@ pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
     let ow_path = resolve_world_path(path, &level_name);
     
     if ow_path.exists() {
-        let size = get_dir_size(&ow_path);
+        let size = path_size(&ow_path);
         dimensions.push(Dimension {
             name: "Overworld".to_string(), // In Bedrock, this is the whole world (level.db)
             id: "overworld".to_string(),
@@ -123,7 +211,7 @@ pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
              dimensions.push(Dimension {
                  name: "Nether (Vanilla)".to_string(),
                  id: "nether_vanilla".to_string(),
-                 size_bytes: get_dir_size(&nether_path), 
+                 size_bytes: path_size(&nether_path), 
              });
         }
          let end_path = ow_path.join("DIM1");
@@ -131,7 +219,7 @@ pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
              dimensions.push(Dimension {
                  name: "The End (Vanilla)".to_string(),
                  id: "end_vanilla".to_string(),
-                 size_bytes: get_dir_size(&end_path), 
+                 size_bytes: path_size(&end_path), 
              });
         }
     }
@@ -145,7 +233,7 @@ pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
     
     let nether_folder = path.join(format!("{}_nether", level_name));
     if nether_folder.exists() {
-        let size = get_dir_size(&nether_folder);
+        let size = path_size(&nether_folder);
         dimensions.push(Dimension {
             name: "Nether".to_string(),
             id: "nether".to_string(),
@@ -156,7 +244,7 @@ pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
 
     let end_folder = path.join(format!("{}_the_end", level_name));
     if end_folder.exists() {
-        let size = get_dir_size(&end_folder);
+        let size = path_size(&end_folder);
         dimensions.push(Dimension {
             name: "The End".to_string(),
             id: "end".to_string(),
@@ -165,91 +253,267 @@ pub fn get_world_info(server_path: String) -> Result<WorldGroup, String> {
         total_size += size;
     }
 
+    let container = world_container(path);
+    let plugin_worlds = plugin_world_entries(path, &level_name).into_iter()
+        .map(|(name, environment, source)| {
+            let folder = container.join(&name);
+            PluginWorld {
+                size_bytes: if folder.exists() { path_size(&folder) } else { 0 },
+                exists: folder.exists(),
+                name,
+                environment,
+                source: source.to_string(),
+            }
+        })
+        .collect();
+
     Ok(WorldGroup {
         level_name,
         dimensions,
         total_size,
         exists,
         path_debug: ow_path.to_string_lossy().to_string(),
+        plugin_worlds,
     })
 }
 
 #[tauri::command]
-pub fn delete_world(server_path: String) -> Result<(), String> {
-    let path = Path::new(&server_path);
-    let level_name = get_level_name(path);
+pub fn delete_world(state: State<'_, ServerProcessState>, server_path: String) -> Result<(), MineServerError> {
+    ensure_not_running(state.inner(), Path::new(&server_path))?;
+    delete_world_files(&server_path, false).map_err(Into::into)
+}
+
+fn delete_world_files(server_path: &str, bedrock: bool) -> Result<(), String> {
+    audited("delete_world", json!({ "serverPath": server_path }), || {
+        let path = Path::new(server_path);
+        let level_name = get_level_name(path);
+
+        // BDS keeps all dimensions in one folder under worlds/
+        if bedrock {
+            let world_path = path.join("worlds").join(&level_name);
+            if world_path.exists() {
+                move_to_trash(&world_path, Some(path))?;
+            }
+            return Ok(());
+        }
     
-    // Delete Overworld (Resolved)
-    let ow_path = resolve_world_path(path, &level_name);
-    if ow_path.exists() {
-        fs::remove_dir_all(ow_path).map_err(|e| e.to_string())?;
-    }
+        // Delete Overworld (Resolved)
+        let ow_path = resolve_world_path(path, &level_name);
+        if ow_path.exists() {
+            move_to_trash(&ow_path, Some(path))?;
+        }
     
-    // Delete Paper Dimensions (Always root)
-    let nether_path = path.join(format!("{}_nether", level_name));
-    if nether_path.exists() {
-        fs::remove_dir_all(nether_path).map_err(|e| e.to_string())?;
-    }
+        // Delete Paper Dimensions (Always root)
+        let nether_path = path.join(format!("{}_nether", level_name));
+        if nether_path.exists() {
+            move_to_trash(&nether_path, Some(path))?;
+        }
     
-    let end_path = path.join(format!("{}_the_end", level_name));
-    if end_path.exists() {
-        fs::remove_dir_all(end_path).map_err(|e| e.to_string())?;
-    }
+        let end_path = path.join(format!("{}_the_end", level_name));
+        if end_path.exists() {
+            move_to_trash(&end_path, Some(path))?;
+        }
     
-    Ok(())
+        Ok(())
+    })
 }
 
 #[tauri::command]
-pub fn delete_dimension_folder(server_path: String, dimension: String) -> Result<(), String> {
-    let path = Path::new(&server_path);
-    let level_name = get_level_name(path);
+pub fn delete_dimension_folder(state: State<'_, ServerProcessState>, server_path: String, dimension: String) -> Result<(), MineServerError> {
+    ensure_not_running(state.inner(), Path::new(&server_path))?;
+    audited("delete_dimension_folder", json!({ "serverPath": server_path, "dimension": dimension }), || {
+        let path = Path::new(&server_path);
+        let level_name = get_level_name(path);
+
+        let target_path = match dimension.as_str() {
+            "overworld" => resolve_world_path(path, &level_name),
+            "nether" => path.join(format!("{}_nether", level_name)),
+            "end" => path.join(format!("{}_the_end", level_name)),
+            _ => return Err(format!("Unknown dimension: {}", dimension)),
+        };
 
-    let target_path = match dimension.as_str() {
-        "overworld" => resolve_world_path(path, &level_name),
-        "nether" => path.join(format!("{}_nether", level_name)),
-        "end" => path.join(format!("{}_the_end", level_name)),
-        _ => return Err(format!("Unknown dimension: {}", dimension)),
+        if target_path.exists() {
+            move_to_trash(&target_path, Some(path))?;
+        }
+
+        Ok(())
+    }).map_err(Into::into)
+}
+
+/// Moves one world of a multi-world plugin to the trash and drops it from the plugins' configs,
+/// so they don't recreate it empty. Leaves every other world alone; the main
+/// level and its dimensions go through `delete_world` / `delete_dimension_folder`.
+#[tauri::command]
+pub fn delete_plugin_world(state: State<'_, ServerProcessState>, server_path: String, world: String) -> Result<(), MineServerError> {
+    ensure_not_running(state.inner(), Path::new(&server_path))?;
+    audited("delete_plugin_world", json!({ "serverPath": server_path, "world": world }), || {
+        let path = Path::new(&server_path);
+        let level_name = get_level_name(path);
+        if !plugin_world_entries(path, &level_name).iter().any(|(name, _, _)| *name == world) {
+            return Err(format!("{} is not managed by a multi-world plugin", world));
+        }
+
+        let folder = other_world_path(path, &world)?;
+        if folder.exists() {
+            move_to_trash(&folder, Some(path))?;
+        }
+        forget_plugin_world(path, &world)
+    }).map_err(Into::into)
+}
+
+fn forget_plugin_world(server_path: &Path, world: &str) -> Result<(), String> {
+    for (plugin, config, top_level) in WORLD_PLUGIN_CONFIGS {
+        let file = server_path.join(config);
+        let Ok(content) = fs::read_to_string(&file) else { continue };
+        let Some(updated) = remove_yaml_world(&content, world, top_level) else { continue };
+
+        // The edit is by line; make sure the result is still the same config minus the world
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&updated)
+            .map_err(|e| format!("Could not remove {} from the {} config safely: {}", world, plugin, e))?;
+        if listed_worlds(&parsed, top_level).iter().any(|(name, _)| name == world) {
+            return Err(format!("Could not remove {} from the {} config", world, plugin));
+        }
+        write_text_atomic(&file, &updated, &format!("the {} config", plugin))?;
+    }
+    Ok(())
+}
+
+/// Key of a YAML mapping line (`name:` or `"name":`), None for comments, list items and values.
+fn yaml_key(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let (key, rest) = match trimmed.chars().next()? {
+        '#' | '-' => return None,
+        quote @ ('"' | '\'') => {
+            let end = trimmed[1..].find(quote)? + 1;
+            (&trimmed[1..end], &trimmed[end + 1..])
+        },
+        _ => {
+            let colon = trimmed.find(':')?;
+            (trimmed[..colon].trim_end(), &trimmed[colon..])
+        },
     };
+    rest.starts_with(':').then_some(key)
+}
+
+fn yaml_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn yaml_is_filler(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
 
-    if target_path.exists() {
-        fs::remove_dir_all(target_path).map_err(|e| e.to_string())?;
+/// Removes the block of `world` from a plugin config, leaving every other line (comments
+/// included) as it was. The world is looked up under `worlds:`, or at the top level when
+/// `top_level` is set and there is no `worlds:` section. None if it isn't listed.
+fn remove_yaml_world(content: &str, world: &str, top_level: bool) -> Option<String> {
+    let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let is_top_key = |line: &str| !yaml_is_filler(line) && yaml_indent(line) == 0;
+
+    let section = lines.iter().position(|l| is_top_key(l) && yaml_key(l) == Some("worlds"));
+    let (start, end) = match section {
+        Some(section) => {
+            let end = lines[section + 1..].iter().position(|l| is_top_key(l)).map_or(lines.len(), |i| section + 1 + i);
+            (section + 1, end)
+        },
+        None if top_level => (0, lines.len()),
+        None => return None,
+    };
+    let child_indent = lines[start..end].iter().find(|l| !yaml_is_filler(l)).map(|l| yaml_indent(l))?;
+
+    let key_line = (start..end).find(|&i| {
+        !yaml_is_filler(lines[i]) && yaml_indent(lines[i]) == child_indent && yaml_key(lines[i]) == Some(world)
+    })?;
+    let mut block_end = (key_line + 1..end)
+        .find(|&i| !yaml_is_filler(lines[i]) && yaml_indent(lines[i]) <= child_indent)
+        .unwrap_or(end);
+    // Comments and blank lines right before the next entry belong to it
+    while block_end > key_line + 1 && yaml_is_filler(lines[block_end - 1]) && yaml_indent(lines[block_end - 1]) <= child_indent {
+        block_end -= 1;
+    }
+    lines.drain(key_line..block_end);
+
+    let mut updated = lines.concat();
+    if let Some(section) = section {
+        // `worlds:` with nothing under it would read as null
+        let emptied = lines[section + 1..].iter().take_while(|l| !is_top_key(l)).all(|l| yaml_is_filler(l));
+        if emptied {
+            let line_start: usize = lines[..section].iter().map(|l| l.len()).sum();
+            let newline = if lines[section].ends_with("\r\n") { "\r\n" } else if lines[section].ends_with('\n') { "\n" } else { "" };
+            updated.replace_range(line_start..line_start + lines[section].len(), &format!("worlds: {{}}{}", newline));
+        }
     }
+    Some(updated)
+}
 
-    Ok(())
+/// World generation settings written to server.properties when a world is regenerated.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldSettings {
+    pub seed: String, // Empty for a random seed
+    #[serde(default)]
+    pub level_name: Option<String>, // Renames the new world; the old name is kept if unset
+    #[serde(default)]
+    pub gamemode: Option<String>,
+    pub level_type: String,
+    pub generate_structures: bool,
+    pub hardcore: bool,
+    pub difficulty: String,
+    pub spawn_animals: bool,
+    pub spawn_monsters: bool,
+    pub allow_nether: bool,
 }
 
-#[tauri::command]
-pub fn regenerate_world(
-    server_path: String, 
-    seed: String, 
-    level_type: String, 
-    generate_structures: bool, 
-    hardcore: bool,
-    difficulty: String,
-    spawn_animals: bool,
-    spawn_monsters: bool,
-    allow_nether: bool,
-) -> Result<(), String> {
-    let path = Path::new(&server_path);
-    let props_path = path.join("server.properties");
-    
-    // 1. Delete existing (all dimensions)
-    delete_world(server_path.clone())?;
-    
-    // 2. Update properties
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegenReport {
+    pub level_name: String,
+    pub seed: Option<String>, // The seed the server actually used, if it told us
+}
+
+impl WorldSettings {
+    fn new_level_name(&self) -> Option<String> {
+        self.level_name.as_deref().map(str::trim).filter(|n| !n.is_empty()).map(str::to_string)
+    }
+
+    /// Checked before the old world is deleted. The level name becomes a folder name, so it
+    /// can't contain separators or point to a parent folder.
+    fn validate(&self) -> Result<(), String> {
+        match self.new_level_name() {
+            Some(name) if name == "." || name == ".." || name.contains(['/', '\\', ':']) => Err(format!("Invalid world name: {}", name)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Writes `settings` to server.properties. Bedrock servers only get the keys BDS knows (seed,
+/// level name, gamemode and difficulty, which has the same key and values there); world type,
+/// structures, hardcore and the Nether can't be configured on BDS, and mob spawning is the
+/// `domobspawning` game rule instead of a property.
+fn write_world_settings(server_path: &Path, settings: WorldSettings, bedrock: bool) -> Result<(), String> {
+    let props_path = server_path.join("server.properties");
     let content = fs::read_to_string(&props_path).unwrap_or_default();
     let mut new_lines = Vec::new();
     
     // Track keys to update
     let mut updates = HashMap::new();
-    updates.insert("level-seed", seed);
-    updates.insert("level-type", level_type);
-    updates.insert("generate-structures", generate_structures.to_string());
-    updates.insert("hardcore", hardcore.to_string());
-    updates.insert("difficulty", difficulty);
-    updates.insert("spawn-animals", spawn_animals.to_string());
-    updates.insert("spawn-monsters", spawn_monsters.to_string());
-    updates.insert("allow-nether", allow_nether.to_string());
+    if let Some(name) = settings.new_level_name() {
+        updates.insert("level-name", name);
+    }
+    updates.insert("level-seed", settings.seed);
+    updates.insert("difficulty", settings.difficulty);
+    if let Some(mode) = settings.gamemode {
+        updates.insert("gamemode", mode);
+    }
+    if !bedrock {
+        updates.insert("level-type", settings.level_type);
+        updates.insert("generate-structures", settings.generate_structures.to_string());
+        updates.insert("hardcore", settings.hardcore.to_string());
+        updates.insert("spawn-animals", settings.spawn_animals.to_string());
+        updates.insert("spawn-monsters", settings.spawn_monsters.to_string());
+        updates.insert("allow-nether", settings.allow_nether.to_string());
+    }
     
     let mut seen_keys = Vec::new();
 
@@ -277,130 +541,241 @@ pub fn regenerate_world(
         }
     }
     
-    fs::write(&props_path, new_lines.join("\n")).map_err(|e| e.to_string())?;
+    fs::write(&props_path, new_lines.join("\n")).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn regenerate_world(
+    state: State<'_, ServerProcessState>,
+    server_path: String, 
+    seed: String, 
+    level_type: String, 
+    generate_structures: bool, 
+    hardcore: bool,
+    difficulty: String,
+    spawn_animals: bool,
+    spawn_monsters: bool,
+    allow_nether: bool,
+    level_name: Option<String>,
+    gamemode: Option<String>,
+    bedrock: Option<bool>,
+) -> Result<(), MineServerError> {
+    let path = Path::new(&server_path);
+    let bedrock = bedrock.unwrap_or(false);
+    let settings = WorldSettings { seed, level_name, gamemode, level_type, generate_structures, hardcore, difficulty, spawn_animals, spawn_monsters, allow_nether };
+    settings.validate().map_err(MineServerError::validation)?;
+    
+    // 1. Delete existing (all dimensions)
+    ensure_not_running(state.inner(), path)?;
+    delete_world_files(&server_path, bedrock)?;
     
-    Ok(())
+    // 2. Update properties
+    write_world_settings(path, settings, bedrock).map_err(Into::into)
 }
 
-#[derive(Clone, Serialize)]
-struct ProgressPayload {
-    percentage: u8,
-    details: String,
+/// "Seed: [-4172144997902289642]", the answer to the `seed` command.
+fn parse_seed(line: &str) -> Option<String> {
+    let start = line.find("Seed: [")? + "Seed: [".len();
+    let end = line[start..].find(']')? + start;
+    Some(line[start..end].to_string())
 }
 
+/// Regenerates the world and has the server generate the new one right away: stops it if it
+/// runs, deletes the world, starts it again and waits until it prepared the new level and
+/// finished loading. The seed is read back with the `seed` command, so a blank seed reports
+/// the random one the server picked. BDS has no such command, so Bedrock only reports a seed
+/// that was given.
 #[tauri::command]
-pub fn upload_world<R: tauri::Runtime>(window: tauri::Window<R>, server_path: String, zip_path: String) -> Result<(), String> {
-    use std::io::{Read, Write};
-    use tauri::Emitter;
+pub async fn regenerate_world_and_restart<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    state: State<'_, ServerProcessState>,
+    id: String,
+    settings: WorldSettings,
+) -> Result<RegenReport, MineServerError> {
+    let server = find_registered_server(&id)
+        .ok_or_else(|| MineServerError::not_found(format!("Server {} is not registered", id)))?;
+    let path = PathBuf::from(&server.path);
+    let bedrock = server.server_type == "bedrock";
+    settings.validate().map_err(MineServerError::validation)?;
+    let given_seed = Some(settings.seed.trim().to_string()).filter(|s| !s.is_empty());
+    // BDS has a single game rule for all mob spawning
+    let mob_spawning_off = bedrock && !settings.spawn_animals && !settings.spawn_monsters;
+
+    let running = state.processes.lock().map(|p| p.contains_key(&id)).unwrap_or(false);
+    if running {
+        let (processes, id) = (state.inner().clone(), id.clone());
+        tokio::task::spawn_blocking(move || stop_server_direct(&processes, id)).await??;
+    }
+    ensure_not_running(state.inner(), &path)?;
+    delete_world_files(&server.path, bedrock)?;
+    write_world_settings(&path, settings, bedrock)?;
+
+    let cfg = server.launch_config();
+    start_server_direct(app, state.inner(), cfg.id, cfg.path, cfg.jar_file, cfg.ram, cfg.java_path, cfg.startup_flags, Some(cfg.auto_restart))?;
+
+    // Both lines can arrive in the same batch, so they are looked for in one pass
+    let mut seq = 0;
+    let mut prepared = false;
+    let deadline = tokio::time::Instant::now() + REGEN_TIMEOUT;
+    loop {
+        if tokio::time::Instant::now() >= deadline {
+            return Err(MineServerError::external("Server", "The server did not finish generating the new world in time; check the console"));
+        }
+        tokio::time::sleep(CONSOLE_POLL_INTERVAL).await;
+        let (next_seq, lines) = console_output_since(state.inner(), &id, seq);
+        seq = next_seq;
+        // BDS prints "Opening level 'worlds/<name>/db'" and "Server started."
+        prepared |= lines.iter().any(|l| l.contains("Preparing level") || l.contains("Opening level"));
+        if lines.iter().any(|l| l.contains("Done (") || l.contains("Server started.")) {
+            break;
+        }
+        let still_running = state.processes.lock().map(|p| p.contains_key(&id)).unwrap_or(false);
+        if !still_running {
+            return Err(MineServerError::external("Server", "The server stopped while generating the new world; check the console"));
+        }
+    }
+    if !prepared {
+        return Err(MineServerError::external("Server", "The server started without preparing a new level; check level-name in server.properties"));
+    }
+
+    if bedrock {
+        if mob_spawning_off {
+            send_server_command_direct(state.inner(), id.clone(), "gamerule domobspawning false".to_string())?;
+        }
+        return Ok(RegenReport { level_name: get_level_name(&path), seed: given_seed });
+    }
+
+    send_server_command_direct(state.inner(), id.clone(), "seed".to_string())?;
+    let mut seed = None;
+    let deadline = tokio::time::Instant::now() + SEED_TIMEOUT;
+    while seed.is_none() && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(CONSOLE_POLL_INTERVAL).await;
+        let (next_seq, lines) = console_output_since(state.inner(), &id, seq);
+        seq = next_seq;
+        seed = lines.iter().find_map(|l| parse_seed(l));
+    }
+
+    Ok(RegenReport { level_name: get_level_name(&path), seed: seed.or(given_seed) })
+}
+
+/// File name of an uploaded archive, for the activity log.
+fn file_label(path: &str) -> String {
+    Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| path.to_string())
+}
 
+#[tauri::command]
+pub async fn upload_world<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    ops: State<'_, OperationState>,
+    state: State<'_, ServerProcessState>,
+    server_path: String,
+    zip_path: String,
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    ensure_not_running(state.inner(), Path::new(&server_path))?;
+    let operation = ops.begin(operation_id, "world_upload")?.with_events(app);
+    let activity = Activity::begin("world_import", &server_path, format!("Upload world {}", file_label(&zip_path)));
+    let size = fs::metadata(&zip_path).ok().map(|m| m.len());
+    let result = tokio::task::spawn_blocking(move || upload_world_sync(server_path, zip_path, &operation)).await?;
+    activity.finish(&result, size);
+    result.map_err(Into::into)
+}
+
+fn upload_world_sync(server_path: String, zip_path: String, op: &Operation) -> Result<(), String> {
     let path = Path::new(&server_path);
     let level_name = get_level_name(path);
     let world_path = resolve_world_path(path, &level_name);
 
     // emit start
-    let _ = window.emit("world_upload_progress", ProgressPayload {
-        percentage: 0,
-        details: "Preparing...".to_string(),
-    });
-    
-    // Delete existing
-    if world_path.exists() {
-         let _ = window.emit("world_upload_progress", ProgressPayload {
-            percentage: 0,
-            details: "Removing old world...".to_string(),
-        });
-        fs::remove_dir_all(&world_path).map_err(|e| e.to_string())?;
-    }
+    op.progress(0, 0, "Preparing...");
+    extract_and_swap(&zip_path, &world_path, true, op)?;
+
+    // finish
+    op.complete();
     
+    Ok(())
+}
+
+/// Extracts a world archive into a temporary folder next to `target` and only then swaps it in,
+/// so a corrupt or cancelled upload leaves the current world untouched. With `require_level_dat`
+/// the result must contain a level.dat. The old folder is kept as `<name>.old-<timestamp>` until
+/// the new one is in place.
+fn extract_and_swap(zip_path: &str, target: &Path, require_level_dat: bool, op: &Operation) -> Result<(), String> {
+    let parent = target.parent().ok_or("Invalid world path")?;
+    let name = target.file_name().ok_or("Invalid world path")?.to_string_lossy().to_string();
+    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+
     // Open Zip
-    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let file = File::open(zip_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
-    
-    // Calculate total uncompressed size
-    let mut total_size: u64 = 0;
-    for i in 0..archive.len() {
-        if let Ok(file) = archive.by_index(i) {
-             total_size += file.size();
-        }
-    }
-    
-    let mut extracted_bytes: u64 = 0;
-    let mut last_emit_time = std::time::Instant::now();
 
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
-        
-        // Sanitize path
-        let outpath = match file.enclosed_name() {
-            Some(path) => world_path.join(path),
-            None => continue,
-        };
-
-        if file.name().ends_with('/') {
-            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                    fs::create_dir_all(p).map_err(|e| e.to_string())?;
-                }
-            }
-            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
-            
-            // buffer copy with progress
-            let mut buffer = [0u8; 8192];
-            loop {
-                let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
-                if n == 0 { break; }
-                outfile.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
-                
-                extracted_bytes += n as u64;
-                
-                // Emit event every 100ms max to avoid spamming frontend
-                if last_emit_time.elapsed().as_millis() > 100 {
-                    let percentage = if total_size > 0 {
-                        ((extracted_bytes as f64 / total_size as f64) * 100.0) as u8
-                    } else { 0 };
-                    
-                    let _ = window.emit("world_upload_progress", ProgressPayload {
-                        percentage,
-                        details: format!("Extracting: {}", file.name()),
-                    });
-                    last_emit_time = std::time::Instant::now();
-                }
-            }
+    // Detect if all files are inside a single root folder (common for world zips)
+    // e.g., "my_world/level.dat" - we want to strip "my_world/" prefix
+    let strip_prefix = if archive.by_name("level.dat").is_ok() { None } else { common_root_prefix(&mut archive) };
+
+    let staging = parent.join(format!(".{}.upload-{}", name, uuid::Uuid::new_v4()));
+    // Shared extractor enforces zip-slip, size and symlink rules
+    let extracted = extract_zip(&mut archive, &staging, strip_prefix.as_deref(), &ExtractLimits::from_settings().with_cancel(op.token.clone()), |extracted_bytes, total_size, entry| {
+        let percentage = if total_size > 0 {
+            ((extracted_bytes as f64 / total_size as f64) * 100.0) as u8
+        } else { 0 };
+        op.progress(percentage, extracted_bytes, format!("Extracting: {}", entry));
+    }).and_then(|_| {
+        if require_level_dat && !staging.join("level.dat").is_file() {
+            return Err("The archive does not contain a world (level.dat is missing)".to_string());
         }
-        
-        // Get Unix permissions
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            if let Some(mode) = file.unix_mode() {
-                fs::set_permissions(&outpath, fs::Permissions::from_mode(mode)).unwrap();
-            }
+        Ok(())
+    });
+    if let Err(e) = extracted {
+        let _ = fs::remove_dir_all(&staging); // Drop the half-extracted world
+        return Err(e);
+    }
+
+    op.progress(100, 0, "Replacing old world...");
+    let old = parent.join(format!("{}.old-{}", name, chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    let had_old = target.exists();
+    if had_old {
+        if let Err(e) = fs::rename(target, &old) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(format!("Failed to move the old world aside: {}", e));
         }
     }
-    
-    // finish
-    let _ = window.emit("world_upload_progress", ProgressPayload {
-        percentage: 100,
-        details: "Done!".to_string(),
-    });
-    
+    if let Err(e) = fs::rename(&staging, target) {
+        if had_old {
+            let _ = fs::rename(&old, target);
+        }
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Failed to move the new world in place: {}", e));
+    }
+    if had_old {
+        fs::remove_dir_all(&old).map_err(|e| format!("Uploaded the world, but failed to remove the old one at {}: {}", old.display(), e))?;
+    }
     Ok(())
 }
 
 /// Upload a specific dimension (overworld, nether, end).
 /// For Nether/End, extracts to `{level-name}_nether` or `{level-name}_the_end`.
 #[tauri::command]
-pub fn upload_dimension<R: tauri::Runtime>(
-    window: tauri::Window<R>,
+pub async fn upload_dimension<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    ops: State<'_, OperationState>,
+    server_path: String,
+    zip_path: String,
+    dimension: String,
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let operation = ops.begin(operation_id, "world_upload")?.with_events(app);
+    tokio::task::spawn_blocking(move || upload_dimension_sync(server_path, zip_path, dimension, &operation))
+        .await?
+        .map_err(Into::into)
+}
+
+fn upload_dimension_sync(
     server_path: String,
     zip_path: String,
     dimension: String, // "overworld" | "nether" | "end"
+    op: &Operation,
 ) -> Result<(), String> {
-    use std::io::{Read, Write};
-    use tauri::Emitter;
-
     let path = Path::new(&server_path);
     let level_name = get_level_name(path);
 
@@ -412,159 +787,71 @@ pub fn upload_dimension<R: tauri::Runtime>(
         _ => return Err(format!("Unknown dimension: {}", dimension)),
     };
 
-    let _ = window.emit("world_upload_progress", ProgressPayload {
-        percentage: 0,
-        details: format!("Preparing {} upload...", dimension),
-    });
-
-    // Delete existing dimension folder
-    if target_path.exists() {
-        let _ = window.emit("world_upload_progress", ProgressPayload {
-            percentage: 5,
-            details: format!("Removing old {}...", dimension),
-        });
-        fs::remove_dir_all(&target_path).map_err(|e| e.to_string())?;
-    }
-
-    // Create target directory
-    fs::create_dir_all(&target_path).map_err(|e| e.to_string())?;
-
-    // Open Zip
-    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
-    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
-
-    // Detect if all files are inside a single root folder (common for world zips)
-    // e.g., "my_world/level.dat" - we want to strip "my_world/" prefix
-    let mut root_prefix: Option<String> = None;
-    let mut all_have_common_root = true;
-
-    for i in 0..archive.len() {
-        if let Ok(file) = archive.by_index(i) {
-            let name = file.name();
-            if let Some(first_slash) = name.find('/') {
-                let prefix = &name[..first_slash + 1];
-                if let Some(ref existing) = root_prefix {
-                    if existing != prefix {
-                        all_have_common_root = false;
-                        break;
-                    }
-                } else {
-                    root_prefix = Some(prefix.to_string());
-                }
-            } else {
-                // File at root level (no folder) - don't strip
-                all_have_common_root = false;
-                break;
-            }
-        }
-    }
+    op.progress(0, 0, format!("Preparing {} upload...", dimension));
+    extract_and_swap(&zip_path, &target_path, dimension == "overworld", op)?;
 
-    let strip_prefix = if all_have_common_root { root_prefix } else { None };
-
-    // Calculate total size
-    let mut total_size: u64 = 0;
-    for i in 0..archive.len() {
-        if let Ok(file) = archive.by_index(i) {
-            total_size += file.size();
-        }
-    }
-
-    let mut extracted_bytes: u64 = 0;
-    let mut last_emit_time = std::time::Instant::now();
-
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
-
-        let file_name = file.name().to_string();
-        
-        // Strip the common root prefix if detected
-        let relative_path = if let Some(ref prefix) = strip_prefix {
-            if file_name.starts_with(prefix) {
-                file_name.strip_prefix(prefix).unwrap_or(&file_name)
-            } else {
-                &file_name
-            }
-        } else {
-            &file_name
-        };
-
-        // Skip empty paths (the root folder itself)
-        if relative_path.is_empty() {
-            continue;
-        }
-
-        let outpath = target_path.join(relative_path);
-
-        if file_name.ends_with('/') {
-            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                    fs::create_dir_all(p).map_err(|e| e.to_string())?;
-                }
-            }
-            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
-
-            let mut buffer = [0u8; 8192];
-            loop {
-                let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
-                if n == 0 { break; }
-                outfile.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
-
-                extracted_bytes += n as u64;
-
-                if last_emit_time.elapsed().as_millis() > 100 {
-                    let percentage = if total_size > 0 {
-                        ((extracted_bytes as f64 / total_size as f64) * 100.0) as u8
-                    } else { 0 };
-
-                    let _ = window.emit("world_upload_progress", ProgressPayload {
-                        percentage,
-                        details: format!("Extracting: {}", relative_path),
-                    });
-                    last_emit_time = std::time::Instant::now();
-                }
-            }
-        }
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            if let Some(mode) = file.unix_mode() {
-                fs::set_permissions(&outpath, fs::Permissions::from_mode(mode)).ok();
-            }
-        }
-    }
-
-    let _ = window.emit("world_upload_progress", ProgressPayload {
-        percentage: 100,
-        details: "Done!".to_string(),
-    });
+    op.complete();
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn archive_world<R: tauri::Runtime>(
-    window: tauri::Window<R>,
+pub async fn archive_world<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    ops: State<'_, OperationState>,
     server_path: String,
     save_path: String,
+    world: Option<String>, // One other world folder instead of the main level group
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let operation = ops.begin(operation_id, "world_archive")?.with_events(app);
+    tokio::task::spawn_blocking(move || archive_world_sync(server_path, save_path, world, &operation))
+        .await?
+        .map_err(Into::into)
+}
+
+fn archive_world_sync(
+    server_path: String,
+    save_path: String,
+    world: Option<String>,
+    op: &Operation,
 ) -> Result<(), String> {
-    use std::io::Write;
-    use tauri::Emitter;
     use walkdir::WalkDir;
 
     let path = Path::new(&server_path);
     let level_name = get_level_name(path);
-    
-    // We want to archive the main world folder
-    // For Bedrock: "worlds/{level_name}"
-    // For Java: "{level_name}" (plus nether/end folders if they exist separately)
-    
-    let world_path = resolve_world_path(path, &level_name);
-    
-    if !world_path.exists() {
-        return Err("World folder not found".to_string());
+
+    // Folders to archive, each with the folder its zip paths are relative to
+    let mut roots: Vec<(PathBuf, PathBuf)> = Vec::new();
+    match world.filter(|w| *w != level_name) {
+        Some(world) => {
+            // Just that world: a Multiverse world or a level that isn't loaded
+            let world_path = other_world_path(path, &world)?;
+            if !world_path.exists() {
+                return Err("World folder not found".to_string());
+            }
+            let base = world_path.parent().unwrap_or(path).to_path_buf();
+            roots.push((world_path, base));
+        }
+        None => {
+            // We want to archive the main world folder
+            // For Bedrock: "worlds/{level_name}"
+            // For Java: "{level_name}" (plus nether/end folders if they exist separately)
+            let world_path = resolve_world_path(path, &level_name);
+            if !world_path.exists() {
+                return Err("World folder not found".to_string());
+            }
+            let base = world_path.parent().unwrap().to_path_buf();
+            roots.push((world_path, base));
+
+            // Java Dimensions (Nether/End) if they exist at root (Paper/Spigot style)
+            for dimension in [format!("{}_nether", level_name), format!("{}_the_end", level_name)] {
+                let dimension_path = path.join(dimension);
+                if dimension_path.exists() {
+                    roots.push((dimension_path, path.to_path_buf()));
+                }
+            }
+        }
     }
 
     let file = File::create(&save_path).map_err(|e| e.to_string())?;
@@ -574,31 +861,14 @@ pub fn archive_world<R: tauri::Runtime>(
         .unix_permissions(0o755);
 
     let mut files_to_add = Vec::new();
-    
-    // 1. Add Main World
-    for entry in WalkDir::new(&world_path).into_iter().filter_map(|e| e.ok()) {
-        files_to_add.push((entry.path().to_path_buf(), world_path.parent().unwrap().to_path_buf()));
-    }
-
-    // 2. Add Java Dimensions (Nether/End) if they exist at root (Paper/Spigot style)
-    let nether_path = path.join(format!("{}_nether", level_name));
-    if nether_path.exists() {
-         for entry in WalkDir::new(&nether_path).into_iter().filter_map(|e| e.ok()) {
-            files_to_add.push((entry.path().to_path_buf(), path.to_path_buf()));
-        }
-    }
-    
-    let end_path = path.join(format!("{}_the_end", level_name));
-    if end_path.exists() {
-         for entry in WalkDir::new(&end_path).into_iter().filter_map(|e| e.ok()) {
-            files_to_add.push((entry.path().to_path_buf(), path.to_path_buf()));
+    for (root, base) in roots {
+        for entry in WalkDir::new(&root).into_iter().filter_map(|e| e.ok()) {
+            files_to_add.push((entry.path().to_path_buf(), base.clone()));
         }
     }
 
-    let total_files = files_to_add.len();
-    let mut processed = 0;
-    let mut last_emit = std::time::Instant::now();
-
+    // Directories first, then the files are compressed in parallel
+    let mut files = Vec::new();
     for (full_path, base_path) in files_to_add {
         let path = full_path.strip_prefix(&base_path).unwrap();
         let path_str = path.to_string_lossy().replace("\\", "/"); // Zip requires forward slashes
@@ -606,44 +876,52 @@ pub fn archive_world<R: tauri::Runtime>(
         if full_path.is_dir() {
             let _ = zip.add_directory(&path_str, options);
         } else {
-            zip.start_file(&path_str, options).map_err(|e| e.to_string())?;
-            let mut f = File::open(&full_path).map_err(|e| e.to_string())?;
-            let mut buffer = Vec::new();
-            f.read_to_end(&mut buffer).map_err(|e: std::io::Error| e.to_string())?;
-            zip.write_all(&buffer).map_err(|e| e.to_string())?;
-        }
-        
-        processed += 1;
-        if last_emit.elapsed().as_millis() > 100 {
-             let percentage = ((processed as f64 / total_files as f64) * 100.0) as u8;
-             let _ = window.emit("world_archive_progress", ProgressPayload {
-                percentage,
-                details: format!("Archiving: {}", path_str),
-            });
-            last_emit = std::time::Instant::now();
+            files.push((path_str, full_path));
         }
     }
 
-    let _ = zip.finish().map_err(|e| e.to_string())?;
-
-    let _ = window.emit("world_archive_progress", ProgressPayload {
-        percentage: 100,
-        details: "Archive created successfully!".to_string(),
+    // Progress by bytes, not file count: one big region file shouldn't look like 1%
+    let total_bytes: u64 = files.iter().map(|(_, p)| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).sum();
+    let written = zip_files_parallel(&mut zip, files, options, compression_workers(), &op.token, |done, name| {
+        let percentage = if total_bytes == 0 { 100 } else { ((done as f64 / total_bytes as f64) * 100.0) as u8 };
+        op.progress(percentage.min(99), done, format!("Archiving: {}", name));
     });
+    if let Err(e) = written {
+        drop(zip);
+        let _ = fs::remove_file(&save_path); // Never leave a truncated archive behind
+        return Err(e);
+    }
+
+    let _ = zip.finish().map_err(|e| zip_write_error("Failed to finish world archive", e))?;
+
+    op.complete();
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn import_world<R: tauri::Runtime>(
-    window: tauri::Window<R>,
+pub async fn import_world<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    ops: State<'_, OperationState>,
     server_path: String,
     zip_path: String,
     new_level_name: String,
-) -> Result<(), String> {
-    use std::io::{Read, Write};
-    use tauri::Emitter;
+    operation_id: Option<String>,
+) -> Result<(), MineServerError> {
+    let operation = ops.begin(operation_id, "world_import")?.with_events(app);
+    let activity = Activity::begin("world_import", &server_path, format!("Import world {} as {}", file_label(&zip_path), new_level_name));
+    let size = fs::metadata(&zip_path).ok().map(|m| m.len());
+    let result = tokio::task::spawn_blocking(move || import_world_sync(server_path, zip_path, new_level_name, &operation)).await?;
+    activity.finish(&result, size);
+    result.map_err(Into::into)
+}
 
+fn import_world_sync(
+    server_path: String,
+    zip_path: String,
+    new_level_name: String,
+    op: &Operation,
+) -> Result<(), String> {
     let path = Path::new(&server_path);
     
     // Safety check: Don't allow empty name or path traversal
@@ -668,10 +946,7 @@ pub fn import_world<R: tauri::Runtime>(
         return Err(format!("A world named '{}' already exists.", new_level_name));
     }
 
-    let _ = window.emit("world_upload_progress", ProgressPayload {
-        percentage: 0,
-        details: format!("Importing into '{}'...", new_level_name),
-    });
+    op.progress(0, 0, format!("Importing into '{}'...", new_level_name));
 
     // Create target directory
     fs::create_dir_all(&target_world_path).map_err(|e| e.to_string())?;
@@ -680,105 +955,25 @@ pub fn import_world<R: tauri::Runtime>(
     let file = File::open(&zip_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
 
-    // Consolidated extraction logic (similar to upload_dimension but targeting a specific new folder)
-    let mut root_prefix: Option<String> = None;
-    let mut all_have_common_root = true;
-
-    // Check for common root folder in zip
-    for i in 0..archive.len() {
-        if let Ok(file) = archive.by_index(i) {
-            let name = file.name();
-            if let Some(first_slash) = name.find('/') {
-                let prefix = &name[..first_slash + 1];
-                if let Some(ref existing) = root_prefix {
-                    if existing != prefix {
-                        all_have_common_root = false;
-                        break;
-                    }
-                } else {
-                    root_prefix = Some(prefix.to_string());
-                }
-            } else {
-                all_have_common_root = false;
-                break;
-            }
-        }
-    }
-    
-    let strip_prefix = if all_have_common_root { root_prefix } else { None };
-
-    // Calculate total size
-    let mut total_size: u64 = 0;
-    for i in 0..archive.len() {
-        if let Ok(file) = archive.by_index(i) {
-            total_size += file.size();
-        }
-    }
-
-    let mut extracted_bytes: u64 = 0;
-    let mut last_emit_time = std::time::Instant::now();
-
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
-        let file_name = file.name().to_string();
-
-        // Strip prefix
-        let relative_path = if let Some(ref prefix) = strip_prefix {
-            if file_name.starts_with(prefix) {
-                file_name.strip_prefix(prefix).unwrap_or(&file_name)
-            } else {
-                &file_name
-            }
-        } else {
-            &file_name
-        };
-
-        if relative_path.is_empty() { continue; }
-
-        let outpath = target_world_path.join(relative_path);
-
-        if file_name.ends_with('/') {
-            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                     fs::create_dir_all(p).map_err(|e| e.to_string())?;
-                }
-            }
-            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
-            let mut buffer = [0u8; 8192];
-            loop {
-                let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
-                if n == 0 { break; }
-                outfile.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
-                extracted_bytes += n as u64;
-
-                if last_emit_time.elapsed().as_millis() > 100 {
-                    let percentage = if total_size > 0 {
-                        ((extracted_bytes as f64 / total_size as f64) * 100.0) as u8
-                    } else { 0 };
-                    let _ = window.emit("world_upload_progress", ProgressPayload {
-                        percentage,
-                        details: format!("Extracting: {}", relative_path),
-                    });
-                    last_emit_time = std::time::Instant::now();
-                }
-            }
-        }
-        
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-             if let Some(mode) = file.unix_mode() {
-                fs::set_permissions(&outpath, fs::Permissions::from_mode(mode)).ok();
-            }
+    // Detect if all files are inside a single root folder (common for world zips)
+    // e.g., "my_world/level.dat" - we want to strip "my_world/" prefix
+    let strip_prefix = common_root_prefix(&mut archive);
+
+    // Shared extractor enforces zip-slip, size and symlink rules
+    let extracted = extract_zip(&mut archive, &target_world_path, strip_prefix.as_deref(), &ExtractLimits::from_settings().with_cancel(op.token.clone()), |extracted_bytes, total_size, entry| {
+        let percentage = if total_size > 0 {
+            ((extracted_bytes as f64 / total_size as f64) * 100.0) as u8
+        } else { 0 };
+        op.progress(percentage, extracted_bytes, format!("Extracting: {}", entry));
+    });
+    if let Err(e) = extracted {
+        if op.token.is_cancelled() {
+            let _ = fs::remove_dir_all(&target_world_path); // Drop the half-extracted world
         }
+        return Err(e);
     }
 
-    let _ = window.emit("world_upload_progress", ProgressPayload {
-        percentage: 100,
-        details: "Import complete!".to_string(),
-    });
+    op.complete();
 
     Ok(())
 }
@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::State;
+use super::nbt::{read_document, write_document, NbtDocument};
+use super::path_guard::validate_path;
+use super::runner::{ServerProcessState, send_server_command_direct};
+use super::world_manager::{get_level_name, resolve_world_path};
+use super::error::MineServerError;
+
+// World border and world spawn. A running server gets the console commands (`worldborder`,
+// `setworldspawn`) so the change applies immediately; a stopped server gets level.dat edited
+// directly. Java keeps these under the root "Data" compound, Bedrock at the top level.
+// Vanilla Bedrock has no world border.
+
+const MAX_BORDER_SIZE: f64 = 59_999_968.0;
+const MAX_COORDINATE: f64 = 29_999_984.0;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldSettings {
+    pub bedrock: bool,
+    pub border_center_x: Option<f64>,
+    pub border_center_z: Option<f64>,
+    pub border_size: Option<f64>,
+    pub spawn_x: Option<i32>,
+    pub spawn_y: Option<i32>,
+    pub spawn_z: Option<i32>,
+}
+
+fn level_dat_path(server_root: &Path) -> Result<PathBuf, String> {
+    let level_name = get_level_name(server_root);
+    let path = resolve_world_path(server_root, &level_name).join("level.dat");
+    if !path.is_file() {
+        return Err(format!("level.dat not found for world '{}'. Start the server once to generate it.", level_name));
+    }
+    Ok(path)
+}
+
+fn is_running(state: &ServerProcessState, id: &str) -> bool {
+    state.processes.lock().map(|p| p.contains_key(id)).unwrap_or(false)
+}
+
+fn is_bedrock(state: &ServerProcessState, id: &str) -> bool {
+    state.configs.lock()
+        .ok()
+        .and_then(|c| c.get(id).map(|cfg| cfg.jar_file.contains("bedrock_server")))
+        .unwrap_or(false)
+}
+
+/// The compound holding the world settings: Java's "Data", or the root on Bedrock.
+fn settings_compound(doc: &mut NbtDocument) -> Result<&mut Value, String> {
+    if doc.format.bedrock_header.is_some() {
+        return Ok(&mut doc.root);
+    }
+    child_mut(&mut doc.root, "Data").ok_or_else(|| "level.dat has no Data compound".to_string())
+}
+
+fn child<'a>(compound: &'a Value, name: &str) -> Option<&'a Value> {
+    compound["value"].as_array()?.iter().find(|entry| entry["name"] == name)
+}
+
+fn child_mut<'a>(compound: &'a mut Value, name: &str) -> Option<&'a mut Value> {
+    compound["value"].as_array_mut()?.iter_mut().find(|entry| entry["name"] == name)
+}
+
+/// Sets a tag in a compound, adding it if missing. Existing tags keep their NBT type.
+fn set_child(compound: &mut Value, name: &str, tag_type: &str, value: Value) -> Result<(), String> {
+    if let Some(entry) = child_mut(compound, name) {
+        entry["value"] = value;
+        return Ok(());
+    }
+    compound["value"].as_array_mut()
+        .ok_or("Expected a compound")?
+        .push(json!({ "type": tag_type, "name": name, "value": value }));
+    Ok(())
+}
+
+fn read_f64(compound: &Value, name: &str) -> Option<f64> {
+    child(compound, name).and_then(|t| t["value"].as_f64())
+}
+
+fn read_i32(compound: &Value, name: &str) -> Option<i32> {
+    child(compound, name).and_then(|t| t["value"].as_i64()).map(|v| v as i32)
+}
+
+fn edit_level_dat(server_root: &Path, edit: impl FnOnce(&mut Value, bool) -> Result<(), String>) -> Result<(), String> {
+    let path = level_dat_path(server_root)?;
+    let mut doc = read_document(&path)?;
+    let bedrock = doc.format.bedrock_header.is_some();
+    edit(settings_compound(&mut doc)?, bedrock)?;
+    write_document(&path, &doc)
+}
+
+fn check_coordinate(label: &str, value: f64) -> Result<(), MineServerError> {
+    if !value.is_finite() || value.abs() > MAX_COORDINATE {
+        return Err(MineServerError::validation(format!("{} must be between -{} and {}", label, MAX_COORDINATE, MAX_COORDINATE)));
+    }
+    Ok(())
+}
+
+/// Border and spawn as stored in level.dat (a running server may have newer values in memory).
+#[tauri::command]
+pub fn get_world_settings(server_path: String) -> Result<WorldSettings, MineServerError> {
+    let root = validate_path(&server_path)?;
+    let mut doc = read_document(&level_dat_path(&root)?)?;
+    let bedrock = doc.format.bedrock_header.is_some();
+    let data = settings_compound(&mut doc)?;
+
+    Ok(WorldSettings {
+        bedrock,
+        border_center_x: read_f64(data, "BorderCenterX"),
+        border_center_z: read_f64(data, "BorderCenterZ"),
+        border_size: read_f64(data, "BorderSize"),
+        spawn_x: read_i32(data, "SpawnX"),
+        spawn_y: read_i32(data, "SpawnY"),
+        spawn_z: read_i32(data, "SpawnZ"),
+    })
+}
+
+#[tauri::command]
+pub fn set_world_border(
+    state: State<'_, ServerProcessState>,
+    id: String,
+    server_path: String,
+    center_x: f64,
+    center_z: f64,
+    size: f64,
+) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+    check_coordinate("Border center X", center_x)?;
+    check_coordinate("Border center Z", center_z)?;
+    if !size.is_finite() || !(1.0..=MAX_BORDER_SIZE).contains(&size) {
+        return Err(MineServerError::validation(format!("Border size must be between 1 and {}", MAX_BORDER_SIZE)));
+    }
+
+    if is_running(&state, &id) {
+        if is_bedrock(&state, &id) {
+            return Err(MineServerError::validation("Bedrock worlds have no world border"));
+        }
+        send_server_command_direct(&state, id.clone(), format!("worldborder center {} {}", center_x, center_z))?;
+        send_server_command_direct(&state, id, format!("worldborder set {}", size))?;
+        return Ok(());
+    }
+
+    edit_level_dat(&root, |data, bedrock| {
+        if bedrock {
+            return Err("Bedrock worlds have no world border".to_string());
+        }
+        set_child(data, "BorderCenterX", "double", json!(center_x))?;
+        set_child(data, "BorderCenterZ", "double", json!(center_z))?;
+        set_child(data, "BorderSize", "double", json!(size))?;
+        // Cancel any border transition still in progress so the new size sticks
+        set_child(data, "BorderSizeLerpTarget", "double", json!(size))?;
+        set_child(data, "BorderSizeLerpTime", "long", json!("0"))
+    }).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn set_world_spawn(
+    state: State<'_, ServerProcessState>,
+    id: String,
+    server_path: String,
+    x: i32,
+    y: i32,
+    z: i32,
+) -> Result<(), MineServerError> {
+    let root = validate_path(&server_path)?;
+    check_coordinate("Spawn X", x as f64)?;
+    check_coordinate("Spawn Z", z as f64)?;
+    if !(-2048..=2048).contains(&y) {
+        return Err(MineServerError::validation("Spawn Y must be between -2048 and 2048"));
+    }
+
+    if is_running(&state, &id) {
+        return send_server_command_direct(&state, id, format!("setworldspawn {} {} {}", x, y, z)).map_err(Into::into);
+    }
+
+    edit_level_dat(&root, |data, _| {
+        set_child(data, "SpawnX", "int", json!(x))?;
+        set_child(data, "SpawnY", "int", json!(y))?;
+        set_child(data, "SpawnZ", "int", json!(z))
+    }).map_err(Into::into)
+}
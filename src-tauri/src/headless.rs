@@ -0,0 +1,120 @@
+// Headless mode: `mineserver --headless [--port 8686] [--bind 127.0.0.1] [--token <secret>]`
+// Runs the scheduler, starts servers flagged `autoStart` in the registry and serves the
+// REST API, without creating a webview. Console output goes to stdout.
+// Release builds on Windows are GUI-subsystem binaries with no console of their own, so
+// headless mode attaches to the console it was started from (or opens one).
+
+use std::sync::{Arc, Mutex};
+use crate::commands::runner::{ServerProcessState, ConsoleSink, start_server_direct, stop_server_direct};
+use crate::commands::registry::load_registry;
+use crate::commands::http_api::{ApiContext, serve};
+use crate::commands::metrics_history::start_metrics_recorder;
+use crate::commands::uptime::start_uptime_monitor;
+use crate::commands::power::start_power_monitor;
+use crate::commands::trash::start_trash_purge;
+use crate::commands::operations::OperationState;
+use crate::scheduler::start_scheduler;
+
+pub struct HeadlessOptions {
+    pub port: u16,
+    pub bind_address: String,
+    pub api_token: Option<String>,
+}
+
+impl HeadlessOptions {
+    pub fn from_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+        };
+
+        Self {
+            port: value_of("--port").and_then(|p| p.parse().ok()).unwrap_or(8686),
+            bind_address: value_of("--bind").unwrap_or_else(|| "127.0.0.1".to_string()),
+            api_token: value_of("--token").or_else(|| std::env::var("MINESERVER_API_TOKEN").ok()),
+        }
+    }
+}
+
+pub fn is_headless(args: &[String]) -> bool {
+    args.iter().any(|a| a == "--headless")
+}
+
+/// Gives a Windows GUI-subsystem process a console so stdout and stderr are visible.
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+        fn AllocConsole() -> i32;
+    }
+    // SAFETY: plain Win32 calls without pointers; failing only means there is no console
+    unsafe {
+        if AttachConsole(ATTACH_PARENT_PROCESS) == 0 {
+            AllocConsole();
+        }
+    }
+}
+
+pub fn run(options: HeadlessOptions) {
+    #[cfg(windows)]
+    attach_console();
+    println!("[Headless] MineServer starting without UI.");
+    let proc_state = ServerProcessState::new();
+    proc_state.load_registered_configs();
+
+    start_scheduler(ConsoleSink, proc_state.clone(), OperationState::new(), Arc::new(Mutex::new(false)));
+    start_metrics_recorder(proc_state.clone());
+    start_uptime_monitor(proc_state.clone());
+    start_power_monitor(ConsoleSink);
+    start_trash_purge();
+
+    match load_registry() {
+        Ok(servers) => {
+            for server in servers.iter().filter(|s| s.auto_start) {
+                let cfg = server.launch_config();
+                match start_server_direct(
+                    ConsoleSink,
+                    &proc_state,
+                    cfg.id,
+                    cfg.path,
+                    cfg.jar_file,
+                    cfg.ram,
+                    cfg.java_path,
+                    cfg.startup_flags,
+                    Some(cfg.auto_restart),
+                ) {
+                    Ok(_) => println!("[Headless] Started {}", server.name),
+                    Err(e) => eprintln!("[Headless] Failed to start {}: {}", server.name, e),
+                }
+            }
+        },
+        Err(e) => eprintln!("[Headless] Could not load server registry: {}", e),
+    }
+
+    let api_state = proc_state.clone();
+    tauri::async_runtime::block_on(async move {
+        match tokio::net::TcpListener::bind((options.bind_address.as_str(), options.port)).await {
+            Ok(listener) => {
+                println!("[Headless] API listening on http://{}:{}", options.bind_address, options.port);
+                let generated = options.api_token.as_deref().is_none_or(str::is_empty);
+                let ctx = ApiContext::new(api_state, ConsoleSink, &options.bind_address, options.api_token);
+                if generated {
+                    println!("[Headless] API token (pass --token or MINESERVER_API_TOKEN to fix it): {}", ctx.api_token);
+                }
+                tauri::async_runtime::spawn(serve(listener, ctx));
+            },
+            Err(e) => eprintln!("[Headless] Failed to bind API on {}:{}: {}", options.bind_address, options.port, e),
+        }
+
+        let _ = tokio::signal::ctrl_c().await;
+    });
+
+    println!("[Headless] Shutting down, stopping servers...");
+    let ids: Vec<String> = proc_state.processes.lock()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+    for id in ids {
+        let _ = stop_server_direct(&proc_state, id);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod models;
 pub mod commands;
 pub mod scheduler;
+pub mod headless;
+#[cfg(test)]
+mod test_support;
 
 use tauri::Manager;
 
@@ -11,22 +14,43 @@ fn greet(name: &str) -> String {
 }
 
 pub fn run() {
+    // Tunnels started by hand and tunnels that follow a server live in the same map
+    let process_state = commands::runner::ServerProcessState::new();
+    process_state.load_registered_configs();
+    let network_state = process_state.network.clone();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
-        .manage(commands::runner::ServerProcessState::new())
+        .manage(process_state)
         .manage(commands::system::SystemState::new())
-        .manage(commands::network_manager::NetworkState::new())
+        .manage(network_state)
         .manage(scheduler::SchedulerState::new())
+        .manage(commands::http_api::HttpApiState::new())
+        .manage(commands::logs::LogFollowState::new())
+        .manage(commands::upload::UploadState::new())
+        .manage(commands::disk_usage::DirSizeCache::new())
+        .manage(commands::fs_watch::DirectoryWatchState::new())
+        .manage(commands::ftp::FtpState::new())
+        .manage(commands::http::HttpState::new())
+        .manage(commands::operations::OperationState::new())
+        .manage(commands::rcon::RconState::new())
         .setup(|app| {
             scheduler::init_scheduler(app.handle().clone());
+            commands::metrics_history::start_metrics_recorder(app.state::<commands::runner::ServerProcessState>().inner().clone());
+            commands::uptime::start_uptime_monitor(app.state::<commands::runner::ServerProcessState>().inner().clone());
+            commands::system::start_system_metrics_stream(app.handle().clone());
+            commands::power::start_power_monitor(app.handle().clone());
+            commands::trash::start_trash_purge();
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::system::get_system_info,
+            commands::power::get_power_state,
             commands::server::delete_server,
             commands::server::get_server_files,
             commands::server::read_server_file,
+            commands::server::read_file_range,
             commands::server::write_server_file,
             commands::server::create_directory,
             commands::server::delete_file,
@@ -34,6 +58,9 @@ pub fn run() {
 
             commands::server::rename_file,
             commands::server::duplicate_file,
+            commands::server::move_path,
+            commands::server::get_permissions,
+            commands::server::set_permissions,
 
             commands::server::copy_file_path,
             commands::server::archive_files,
@@ -41,10 +68,13 @@ pub fn run() {
             commands::server::write_binary_file,
             commands::system::get_system_info,
             commands::system::get_local_ip,
+            commands::system::preview_factory_reset,
             commands::system::factory_reset,
 
 
             commands::versions::get_nukkit_versions,
+            commands::versions::get_spongevanilla_versions,
+            commands::versions::get_spongeforge_versions,
             commands::versions::get_vanilla_versions,
             commands::versions::get_paper_versions,
             commands::versions::get_bedrock_versions,
@@ -56,17 +86,28 @@ pub fn run() {
             commands::downloader::download_server,
             commands::runner::start_server,
             commands::runner::stop_server,
+            commands::runner::replay_server_events,
             commands::runner::send_server_command,
+            commands::snippets::list_snippets,
+            commands::snippets::save_snippet,
+            commands::snippets::delete_snippet,
+            commands::snippets::run_snippet,
             commands::runner::get_server_resource_usage,
             commands::runner::is_server_running,
+            commands::runner::get_server_status,
             commands::runner::get_server_resource_usage,
             commands::runner::is_server_running,
             commands::runner::get_running_servers,
             commands::runner::clear_log_file,
             commands::world_manager::get_world_info,
+            commands::world_settings::get_world_settings,
+            commands::world_settings::set_world_border,
+            commands::world_settings::set_world_spawn,
             commands::world_manager::delete_world,
             commands::world_manager::delete_dimension_folder,
+            commands::world_manager::delete_plugin_world,
             commands::world_manager::regenerate_world,
+            commands::world_manager::regenerate_world_and_restart,
             commands::world_manager::upload_world,
             commands::world_manager::upload_dimension,
             commands::world_manager::archive_world,
@@ -76,26 +117,36 @@ pub fn run() {
             commands::network_manager::install_playit,
             commands::network_manager::start_playit_tunnel,
             commands::network_manager::stop_playit_tunnel,
+            commands::network_manager::get_tunnel_claim_url,
             commands::network_manager::reset_playit_tunnel,
+            commands::network_manager::get_network_status,
             commands::network_manager::check_internet_connection,
             commands::network_manager::get_public_ip,
             commands::network_manager::check_firewall_rule,
+            commands::network_manager::suggest_free_port,
+            commands::network_manager::assign_server_port,
             commands::network_manager::add_firewall_rule,
             commands::network_manager::set_tunnel_guard,
             commands::server_config::get_java_versions,
             commands::server_config::read_server_properties,
             commands::server_config::update_server_properties,
             commands::server_config::install_grimac,
+            commands::luckperms::install_luckperms,
+            commands::luckperms::open_luckperms_editor,
             commands::plugins::list_plugins,
             commands::plugins::search_modrinth_plugins,
             commands::plugins::install_modrinth_plugin,
             commands::plugins::delete_plugin,
+            commands::plugins::update_plugin,
+            commands::plugins::cancel_staged_plugin_update,
             commands::plugins::search_modrinth_mods,
             commands::plugins::install_modrinth_mod,
             commands::plugins::search_hangar_plugins,
             commands::plugins::install_hangar_plugin,
             commands::plugins::search_spigot_plugins,
             commands::plugins::install_spigot_plugin,
+            commands::plugins::search_ore_plugins,
+            commands::plugins::install_ore_plugin,
             commands::plugins::search_poggit_plugins,
             commands::plugins::install_poggit_plugin,
             commands::plugins::search_curseforge_plugins,
@@ -105,10 +156,128 @@ pub fn run() {
             commands::plugins::toggle_plugin,
             commands::backup::create_backup,
             commands::backup::list_backups,
+            commands::backup::list_running_backups,
             commands::backup::delete_backup,
             commands::backup::restore_backup,
+            commands::backup::restore_backup_as_new,
             commands::backup::save_scheduled_tasks,
-            commands::backup::load_scheduled_tasks
+            commands::backup::load_scheduled_tasks,
+            commands::notifications::list_notification_channels,
+            commands::notifications::add_notification_channel,
+            commands::notifications::remove_notification_channel,
+            commands::notifications::test_notification,
+            commands::http_api::start_http_server,
+            commands::http_api::stop_http_server,
+            commands::http_api::get_http_server_status,
+            commands::registry::list_registered_servers,
+            commands::registry::register_server,
+            commands::registry::update_server_launch_config,
+            commands::registry::unregister_server,
+            commands::registry::get_server_network,
+            commands::registry::get_server_history,
+            commands::registry::set_server_notes,
+            commands::registry::add_changelog_entry,
+            commands::query::query_server,
+            commands::query::enable_query,
+            commands::rcon::rcon_connect,
+            commands::rcon::rcon_send,
+            commands::rcon::rcon_disconnect,
+            commands::rcon::enable_rcon,
+            commands::diagnostics::run_diagnostics,
+            commands::player_stats::get_player_stats,
+            commands::player_actions::player_action,
+            commands::metrics_history::get_metrics_history,
+            commands::metrics_history::clear_metrics_history,
+            commands::gc_log::analyze_gc_log,
+            commands::ping::ping_server,
+            commands::motd::get_motd_preview,
+            commands::favorites::list_favorites,
+            commands::favorites::save_favorite,
+            commands::favorites::delete_favorite,
+            commands::favorites::ping_favorites,
+            commands::voice_chat::install_voice_chat,
+            commands::voice_chat::set_voice_chat_port,
+            commands::voice_chat::get_voice_chat_status,
+            commands::activity::get_activity_log,
+            commands::safe_mode::get_safe_mode_status,
+            commands::safe_mode::enter_safe_mode,
+            commands::safe_mode::restore_from_safe_mode,
+            commands::bisect::get_bisect_status,
+            commands::bisect::bisect_plugins,
+            commands::bisect::report_bisect_result,
+            commands::bisect::cancel_bisect,
+            commands::player_sync::list_sync_groups,
+            commands::player_sync::save_sync_group,
+            commands::player_sync::delete_sync_group,
+            commands::player_sync::preview_player_sync,
+            commands::player_sync::sync_player_lists,
+            commands::bootstrap::bootstrap_server,
+            commands::server_profile::read_server_profile,
+            commands::server_profile::import_profile,
+            commands::quick_settings::get_quick_settings,
+            commands::quick_settings::set_view_distance,
+            commands::quick_settings::set_simulation_distance,
+            commands::quick_settings::set_anti_xray,
+            commands::quick_settings::set_spawn_limits,
+            commands::uptime::get_ping_history,
+            commands::uptime::get_uptime_report,
+            commands::uptime::clear_ping_history,
+            commands::logs::share_log,
+            commands::logs::tail_file,
+            commands::logs::follow_file,
+            commands::logs::stop_follow_file,
+            commands::logs::list_log_files,
+            commands::logs::read_log_file,
+            commands::logs::delete_log_files,
+            commands::map_cache::get_map_caches,
+            commands::map_cache::prune_map_caches,
+            commands::auth_diagnostics::diagnose_auth,
+            commands::network_players::get_network_players,
+            commands::settings::get_app_settings,
+            commands::settings::update_app_settings,
+            commands::settings::test_proxy,
+            commands::eula::get_eula_status,
+            commands::eula::accept_eula,
+            commands::audit::get_audit_log,
+            commands::trash::list_trash,
+            commands::trash::restore_trash_item,
+            commands::trash::empty_trash,
+            commands::upload::begin_upload,
+            commands::upload::upload_chunk,
+            commands::upload::finish_upload,
+            commands::upload::cancel_upload,
+            commands::checksum::hash_file,
+            commands::checksum::find_duplicate_files,
+            commands::disk_usage::get_directory_sizes,
+            commands::disk_usage::clear_directory_size_cache,
+            commands::disk_usage::get_storage_overview,
+            commands::nbt::read_nbt,
+            commands::nbt::write_nbt,
+            commands::config_validation::validate_config_file,
+            commands::fs_watch::watch_directory,
+            commands::fs_watch::unwatch_directory,
+            commands::ftp::start_ftp_server,
+            commands::ftp::stop_ftp_server,
+            commands::ftp::get_ftp_status,
+            commands::ftp::set_ftp_user,
+            commands::ftp::remove_ftp_user,
+            commands::bedrock_access::get_bedrock_allowlist,
+            commands::bedrock_access::set_bedrock_allowlist,
+            commands::bedrock_access::get_bedrock_permissions,
+            commands::bedrock_access::set_bedrock_permissions,
+            commands::bedrock_access::resolve_xuid,
+            commands::mod_config::list_mod_configs,
+            commands::mod_config::read_mod_config,
+            commands::mod_config::update_mod_config,
+            commands::mod_updates::check_mod_updates,
+            commands::mod_updates::update_mod,
+            commands::mod_updates::rollback_mod,
+            commands::modpack::export_client_pack,
+            commands::ftb::search_ftb_packs,
+            commands::ftb::get_ftb_pack_versions,
+            commands::ftb::install_ftb_pack,
+            commands::operations::cancel_operation,
+            commands::operations::list_operations
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
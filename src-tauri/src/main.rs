@@ -5,5 +5,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
-    mineserver_lib::run()
+    let args: Vec<String> = std::env::args().collect();
+    if mineserver_lib::headless::is_headless(&args) {
+        mineserver_lib::headless::run(mineserver_lib::headless::HeadlessOptions::from_args(&args));
+    } else {
+        mineserver_lib::run()
+    }
 }
@@ -1,9 +1,31 @@
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use chrono::{Local, Timelike};
-use crate::commands::backup::{load_scheduled_tasks_sync, ScheduledTask, save_scheduled_tasks, create_backup};
-use crate::commands::runner::{ServerProcessState, stop_server_direct, start_server_direct, send_server_command_direct};
+use crate::commands::backup::{load_scheduled_tasks_sync, record_task_runs, ScheduledTask, create_backup_direct, backup_running};
+use crate::commands::runner::{ServerProcessState, ServerEventSink, stop_server_direct, start_server_direct, send_server_command_direct};
+use crate::commands::notifications::{self, Notification, NotificationEvent};
+use crate::commands::operations::OperationState;
+use crate::commands::power;
+use crate::commands::registry::find_registered_server;
+use crate::commands::player_sync::run_sync;
+use crate::commands::map_cache::prune_map_caches_direct;
+
+// When the scheduler thread last woke up. Task runs are spawned, so a tick is never held up
+// for long; an old value means the thread died.
+static LAST_TICK: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Time since the scheduler thread last woke up; None if it never started.
+pub fn since_last_tick() -> Option<Duration> {
+    LAST_TICK.lock().ok()?.map(|tick| tick.elapsed())
+}
+
+fn record_tick() {
+    if let Ok(mut tick) = LAST_TICK.lock() {
+        *tick = Some(Instant::now());
+    }
+}
 
 pub struct SchedulerState {
     pub running: Arc<Mutex<bool>>,
@@ -17,10 +39,53 @@ impl SchedulerState {
     }
 }
 
+/// Counts in-flight runs per task id, so a run that is still executing can be detected by the
+/// next tick. Each run holds a `RunGuard`, which releases its slot when the run ends.
+type ActiveRuns = Arc<Mutex<HashMap<String, usize>>>;
+
+struct RunGuard {
+    task_id: String,
+    active: ActiveRuns,
+}
+
+impl RunGuard {
+    fn new(active: &ActiveRuns, task_id: &str) -> Self {
+        if let Ok(mut runs) = active.lock() {
+            *runs.entry(task_id.to_string()).or_insert(0) += 1;
+        }
+        Self { task_id: task_id.to_string(), active: active.clone() }
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Ok(mut runs) = self.active.lock() {
+            if let Some(count) = runs.get_mut(&self.task_id) {
+                *count -= 1;
+                if *count == 0 {
+                    runs.remove(&self.task_id);
+                }
+            }
+        }
+    }
+}
+
+fn is_running(active: &ActiveRuns, task_id: &str) -> bool {
+    active.lock().map(|runs| runs.contains_key(task_id)).unwrap_or(false)
+}
+
 pub fn init_scheduler(app: AppHandle) {
     let state = app.state::<SchedulerState>();
     let running = state.running.clone();
-    
+    let proc_state = app.state::<ServerProcessState>().inner().clone();
+    let ops = app.state::<OperationState>().inner().clone();
+
+    start_scheduler(app.clone(), proc_state, ops, running);
+}
+
+/// Spawns the scheduler thread. `app` receives log events and is used to (re)start servers,
+/// so the same loop drives both the desktop app and headless mode.
+pub fn start_scheduler<E: ServerEventSink>(app: E, proc_state: ServerProcessState, ops: OperationState, running: Arc<Mutex<bool>>) {
     // Ensure only one thread runs
     {
         let mut r = running.lock().unwrap();
@@ -28,63 +93,119 @@ pub fn init_scheduler(app: AppHandle) {
         *r = true;
     }
 
+    let active: ActiveRuns = Arc::new(Mutex::new(HashMap::new()));
+
     std::thread::spawn(move || {
         println!("[Scheduler] Thread started.");
+        record_tick();
         loop {
             // Tick every 60 seconds
             std::thread::sleep(Duration::from_secs(60));
+            record_tick();
             
             let now = Local::now();
             
             match load_scheduled_tasks_sync() {
                 Ok(mut tasks) => {
+                    let mut runs = Vec::new();
+                    // Restart schedules, for the {next_restart} placeholder of announcements
+                    let restart_crons: Vec<(String, String)> = tasks.iter()
+                        .filter(|t| t.enabled && t.task_type == "restart")
+                        .map(|t| (t.server_id.clone(), t.cron_expression.clone()))
+                        .collect();
                     for task in tasks.iter_mut() {
-                        if !task.enabled { continue; }
-                        
-                        if is_time_to_run(&task.cron_expression, now) {
-                            // Check recent run
-                            let last_run_time = task.last_run.as_ref().and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok());
-                            let recent_run = if let Some(last) = last_run_time {
-                                let diff: chrono::TimeDelta = now.signed_duration_since(last.with_timezone(&Local));
-                                diff.num_seconds() < 90 // Buffer
-                            } else {
-                                false
-                            };
-                            
-                            if !recent_run {
+                        let check = check_task(
+                            task,
+                            now,
+                            power::paused_for_battery,
+                            || is_running(&active, &task.id),
+                            || backup_running(&task.server_path),
+                        );
+                        match check {
+                            Err(Skip::OnBattery) => {
+                                println!("[Scheduler] Skipping Task on battery: {}", task.name);
+                            }
+                            Err(Skip::PreviousRunActive) => {
+                                println!("[Scheduler] Skipping Task, previous run still executing: {}", task.name);
+                                app.emit("server-log", format!("[Scheduler] Skipped {}: the previous run is still executing", task.name));
+                            }
+                            Err(Skip::BackupRunning) => {
+                                // Another backup of this server (manual or from another task) is being written
+                                println!("[Scheduler] Skipping Task, a backup of the server is running: {}", task.name);
+                                app.emit("server-log", format!("[Scheduler] Skipped {}: another backup of {} is still running", task.name, task.server_name));
+                            }
+                            Err(_) => {}
+                            Ok(()) => {
                                 println!("[Scheduler] Executing Task: {}", task.name);
                                 
                                 // Execute Task Async
-                                let _task_id = task.id.clone();
+                                let guard = RunGuard::new(&active, &task.id);
+                                // Only backups can run long; restarts and commands finish in seconds
+                                let max_runtime = task.max_runtime_minutes.filter(|m| *m > 0).map(|m| Duration::from_secs(m * 60));
                                 let server_id = task.server_id.clone();
                                 let server_name = task.server_name.clone();
                                 let server_path = task.server_path.clone();
                                 let task_type = task.task_type.clone();
                                 let command_payload = task.command.clone();
+                                let sync_group_id = task.sync_group_id.clone();
+                                let max_age_days = task.max_age_days;
+                                let restart_crons = restart_crons.clone();
+                                let sync_state = proc_state.clone();
                                 let app_handle = app.clone();
+                                let ops_handle = ops.clone();
                                 
                                 // Get state BEFORE thread spawn and clone Arc fields
-                                let state_proc = app.state::<ServerProcessState>();
+                                let state_proc = &proc_state;
                                 let processes_arc = state_proc.processes.clone();
                                 let explicit_stops_arc = state_proc.explicit_stops.clone();
                                 let configs_arc = state_proc.configs.clone();
+                                let live_stats_arc = state_proc.live_stats.clone();
+                                let lifecycle_arc = state_proc.lifecycle.clone();
+                                let network_state = state_proc.network.clone();
                                 
                                 // Update Last Run
                                 task.last_run = Some(now.to_rfc3339());
+                                runs.push((task.id.clone(), now.to_rfc3339()));
                                 
                                 std::thread::spawn(move || {
+                                    let mut guard = Some(guard); // Released when this run ends
                                     match task_type.as_str() {
                                         "backup" => {
-                                            let _ = app_handle.emit("server-log", format!("[Scheduler] Starting Backup for {}", server_name));
+                                            app_handle.emit("server-log", format!("[Scheduler] Starting Backup for {}", server_name));
+                                            let guard = guard.take();
                                             tauri::async_runtime::spawn(async move {
-                                                match create_backup(server_path, server_name.clone(), "auto".into()).await {
-                                                    Ok(_) => { let _ = app_handle.emit("server-log", format!("[Scheduler] Backup Success: {}", server_name)); },
-                                                    Err(e) => { let _ = app_handle.emit("server-log", format!("[Scheduler] Backup Failed: {}", e)); }
+                                                let _guard = guard;
+                                                let op_id = uuid::Uuid::new_v4().to_string();
+                                                let mut backup = std::pin::pin!(create_backup_direct(app_handle.clone(), &ops_handle, server_path, server_name.clone(), "auto".into(), Some(op_id.clone()), None, None));
+                                                let result = match max_runtime {
+                                                    Some(limit) => match tokio::time::timeout(limit, &mut backup).await {
+                                                        Ok(result) => result,
+                                                        Err(_) => {
+                                                            // Cancel instead of dropping the future so the partial zip is cleaned up
+                                                            let _ = ops_handle.cancel(&op_id);
+                                                            backup.await.map_err(|_| format!("Timed out after {} minutes", limit.as_secs() / 60))
+                                                        }
+                                                    },
+                                                    None => backup.await,
+                                                };
+                                                match result {
+                                                    Ok(info) => {
+                                                        app_handle.emit("server-log", format!("[Scheduler] Backup Success: {}", server_name));
+                                                        notifications::dispatch(
+                                                            Notification::new(NotificationEvent::BackupSuccess, server_name.clone())
+                                                                .with_details(format!("Size: {:.1} MB", info.size_bytes as f64 / 1024.0 / 1024.0))
+                                                        );
+                                                    },
+                                                    Err(e) => {
+                                                        app_handle.emit("server-log", format!("[Scheduler] Backup Failed: {}", e));
+                                                        notifications::dispatch(Notification::new(NotificationEvent::BackupFailure, server_name.clone()).with_details(e));
+                                                    }
                                                 }
                                             });
                                         },
                                         "restart" => {
-                                            let _ = app_handle.emit("server-log", format!("[Scheduler] Restarting {}", server_name));
+                                            app_handle.emit("server-log", format!("[Scheduler] Restarting {}", server_name));
+                                            notifications::dispatch(Notification::new(NotificationEvent::RestartWarning, server_name.clone()));
                                             
                                             // Use cloned Arcs directly instead of state_proc
                                             // Stop
@@ -104,29 +225,36 @@ pub fn init_scheduler(app: AppHandle) {
                                             
                                             std::thread::sleep(Duration::from_secs(5));
                                             
-                                            // Start
-                                            if let Ok(configs) = configs_arc.lock() {
-                                                if let Some(cfg) = configs.get(&server_id) {
-                                                    if let Some(window) = app_handle.get_webview_window("main") {
-                                                         // Build a temporary state struct for start_server_direct
-                                                         let temp_state = ServerProcessState {
-                                                             processes: processes_arc.clone(),
-                                                             explicit_stops: explicit_stops_arc.clone(),
-                                                             configs: configs_arc.clone(),
-                                                         };
-                                                         let _ = start_server_direct(
-                                                             window,
-                                                             &temp_state,
-                                                             cfg.id.clone(),
-                                                             cfg.path.clone(),
-                                                             cfg.jar_file.clone(),
-                                                             cfg.ram,
-                                                             cfg.java_path.clone(),
-                                                             cfg.startup_flags.clone(),
-                                                             Some(cfg.auto_restart)
-                                                         );
-                                                    }
-                                                }
+                                            // Start with the registered launch config, which has the latest RAM and flags
+                                            // (clone the runner's copy first: start_server_direct locks `configs` itself)
+                                            let cfg = find_registered_server(&server_id)
+                                                .map(|s| s.launch_config())
+                                                .or_else(|| configs_arc.lock().ok().and_then(|c| c.get(&server_id).cloned()));
+                                            if let Some(cfg) = cfg {
+                                                 // Build a temporary state struct for start_server_direct
+                                                 let temp_state = ServerProcessState {
+                                                     processes: processes_arc.clone(),
+                                                     explicit_stops: explicit_stops_arc.clone(),
+                                                     configs: configs_arc.clone(),
+                                                     live_stats: live_stats_arc.clone(),
+                                                     lifecycle: lifecycle_arc.clone(),
+                                                     network: network_state.clone(),
+                                                 };
+                                                 if let Err(e) = start_server_direct(
+                                                     app_handle.clone(),
+                                                     &temp_state,
+                                                     cfg.id.clone(),
+                                                     cfg.path.clone(),
+                                                     cfg.jar_file.clone(),
+                                                     cfg.ram,
+                                                     cfg.java_path.clone(),
+                                                     cfg.startup_flags.clone(),
+                                                     Some(cfg.auto_restart)
+                                                 ) {
+                                                     app_handle.emit("server-log", format!("[Scheduler] Restart of {} failed: {}", server_name, e));
+                                                 }
+                                            } else {
+                                                app_handle.emit("server-log", format!("[Scheduler] Cannot restart {}: it is not registered and has no launch config", server_name));
                                             }
                                         },
                                         "command" => {
@@ -139,6 +267,44 @@ pub fn init_scheduler(app: AppHandle) {
                                                  }
                                              }
                                         },
+                                        "announcement" => {
+                                            let Some(template) = command_payload else { return };
+                                            let message = render_announcement(&template, &sync_state, &server_id, &restart_crons, Local::now());
+                                            // A template starting with "/" is a full command (e.g. tellraw); anything else is said
+                                            let cmd = match message.strip_prefix('/') {
+                                                Some(command) => command.to_string(),
+                                                None => format!("say {}", message),
+                                            };
+                                            if let Err(e) = send_server_command_direct(&sync_state, server_id.clone(), cmd) {
+                                                app_handle.emit("server-log", format!("[Scheduler] Announcement for {} not sent: {}", server_name, e));
+                                            }
+                                        },
+                                        "player_sync" => {
+                                            let Some(group_id) = sync_group_id else { return };
+                                            match run_sync(&sync_state, &group_id) {
+                                                Ok(results) => {
+                                                    let failed = results.iter().filter(|r| r.error.is_some()).count();
+                                                    let changed = results.iter().filter(|r| r.method != "none").count();
+                                                    app_handle.emit("server-log", format!("[Scheduler] Player lists synced: {} updated, {} failed", changed - failed, failed));
+                                                },
+                                                Err(e) => {
+                                                    app_handle.emit("server-log", format!("[Scheduler] Player list sync failed: {}", e));
+                                                }
+                                            }
+                                        },
+                                        "map_cleanup" => {
+                                            match prune_map_caches_direct(std::path::Path::new(&server_path), None, max_age_days) {
+                                                Ok(report) => {
+                                                    app_handle.emit("server-log", format!(
+                                                        "[Scheduler] Map cleanup for {}: deleted {} tiles, freed {:.1} MB, {:.1} MB left",
+                                                        server_name, report.deleted_files, report.freed_bytes as f64 / 1024.0 / 1024.0, report.remaining_bytes as f64 / 1024.0 / 1024.0
+                                                    ));
+                                                },
+                                                Err(e) => {
+                                                    app_handle.emit("server-log", format!("[Scheduler] Map cleanup for {} failed: {}", server_name, e));
+                                                }
+                                            }
+                                        },
                                         _ => {}
                                     }
                                 });
@@ -147,7 +313,11 @@ pub fn init_scheduler(app: AppHandle) {
                     }
                     // Save timestamps
                     // Ideally we should do this.
-                    let _ = save_scheduled_tasks(tasks);
+                    if !runs.is_empty() {
+                        if let Err(e) = record_task_runs(&runs) {
+                            eprintln!("[Scheduler] Failed to save task runs: {}", e);
+                        }
+                    }
                 }
                 Err(e) => eprintln!("[Scheduler] Failed to load tasks: {}", e),
             }
@@ -155,6 +325,78 @@ pub fn init_scheduler(app: AppHandle) {
     });
 }
 
+/// Why a task doesn't run on a tick.
+#[derive(Debug, PartialEq)]
+enum Skip {
+    Disabled,
+    NotDue,
+    RanRecently, // Already ran in this minute (ticks drift, so a cron can match twice)
+    OnBattery,
+    PreviousRunActive,
+    BackupRunning,
+}
+
+/// Decides whether a task runs on the tick at `now`. The state checks are only called when the
+/// earlier ones pass, in this order.
+fn check_task(
+    task: &ScheduledTask,
+    now: chrono::DateTime<Local>,
+    on_battery: impl FnOnce() -> bool,
+    previous_run_active: impl FnOnce() -> bool,
+    backup_running: impl FnOnce() -> bool,
+) -> Result<(), Skip> {
+    if !task.enabled {
+        return Err(Skip::Disabled);
+    }
+    if !is_time_to_run(&task.cron_expression, now) {
+        return Err(Skip::NotDue);
+    }
+    let last_run = task.last_run.as_ref().and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok());
+    if last_run.is_some_and(|last| now.signed_duration_since(last.with_timezone(&Local)).num_seconds() < 90) {
+        return Err(Skip::RanRecently);
+    }
+    if on_battery() {
+        return Err(Skip::OnBattery);
+    }
+    if task.skip_if_running && previous_run_active() {
+        return Err(Skip::PreviousRunActive);
+    }
+    if task.task_type == "backup" && backup_running() {
+        return Err(Skip::BackupRunning);
+    }
+    Ok(())
+}
+
+/// Fills in an announcement template when it runs: {time} (HH:MM), {players_online} and
+/// {next_restart} (e.g. "15 minutes", from the server's enabled restart tasks).
+fn render_announcement(template: &str, state: &ServerProcessState, server_id: &str, restart_crons: &[(String, String)], now: chrono::DateTime<Local>) -> String {
+    let players = state.live_stats.lock().ok()
+        .and_then(|stats| stats.get(server_id).map(|s| s.online_players.len()))
+        .unwrap_or(0);
+    // Restart crons only match minutes and hours, so two days ahead covers every schedule
+    let next_restart = (1..=2 * 24 * 60)
+        .find(|m| {
+            let at = now + chrono::TimeDelta::minutes(*m);
+            restart_crons.iter().any(|(id, cron)| id == server_id && is_time_to_run(cron, at))
+        })
+        .map(describe_minutes)
+        .unwrap_or_else(|| "not scheduled".to_string());
+
+    template
+        .replace("{time}", &now.format("%H:%M").to_string())
+        .replace("{players_online}", &players.to_string())
+        .replace("{next_restart}", &next_restart)
+}
+
+fn describe_minutes(minutes: i64) -> String {
+    let plural = |n: i64, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    match (minutes / 60, minutes % 60) {
+        (0, m) => plural(m, "minute"),
+        (h, 0) => plural(h, "hour"),
+        (h, m) => format!("{} {}", plural(h, "hour"), plural(m, "minute")),
+    }
+}
+
 fn is_time_to_run(cron: &str, now: chrono::DateTime<Local>) -> bool {
     let parts: Vec<&str> = cron.split_whitespace().collect();
     if parts.len() != 5 { return false; }
@@ -169,3 +411,97 @@ fn is_time_to_run(cron: &str, now: chrono::DateTime<Local>) -> bool {
     
     matches(min, now.minute()) && matches(hour, now.hour())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 14, hour, minute, 0).unwrap()
+    }
+
+    fn task(task_type: &str, cron: &str) -> ScheduledTask {
+        serde_json::from_value(serde_json::json!({
+            "id": "task-1",
+            "name": "Test task",
+            "taskType": task_type,
+            "serverId": "server-1",
+            "serverName": "Test",
+            "serverPath": "/tmp/test-server",
+            "cronExpression": cron,
+            "enabled": true,
+            "lastRun": null,
+            "command": null,
+        })).expect("task from json")
+    }
+
+    fn check(task: &ScheduledTask, now: chrono::DateTime<Local>) -> Result<(), Skip> {
+        check_task(task, now, || false, || false, || false)
+    }
+
+    #[test]
+    fn cron_matches_minute_and_hour() {
+        assert!(is_time_to_run("30 4 * * *", at(4, 30)));
+        assert!(!is_time_to_run("30 4 * * *", at(4, 31)));
+        assert!(is_time_to_run("0 * * * *", at(17, 0)));
+        assert!(is_time_to_run("* * * * *", at(23, 59)));
+        assert!(!is_time_to_run("0 4 * *", at(4, 0)));
+        assert!(!is_time_to_run("*/5 * * * *", at(4, 5)));
+    }
+
+    #[test]
+    fn due_task_runs_once_per_minute() {
+        let mut t = task("restart", "0 4 * * *");
+        assert_eq!(check(&t, at(4, 0)), Ok(()));
+        assert_eq!(check(&t, at(4, 1)), Err(Skip::NotDue));
+
+        t.last_run = Some(at(4, 0).to_rfc3339());
+        assert_eq!(check(&t, at(4, 0) + chrono::TimeDelta::seconds(30)), Err(Skip::RanRecently));
+        assert_eq!(check(&t, at(4, 0) + chrono::TimeDelta::days(1)), Ok(()));
+
+        t.enabled = false;
+        assert_eq!(check(&t, at(4, 0) + chrono::TimeDelta::days(1)), Err(Skip::Disabled));
+    }
+
+    #[test]
+    fn state_checks_skip_a_due_task() {
+        let backup = task("backup", "0 4 * * *");
+        assert_eq!(check_task(&backup, at(4, 0), || true, || false, || false), Err(Skip::OnBattery));
+        assert_eq!(check_task(&backup, at(4, 0), || false, || true, || false), Err(Skip::PreviousRunActive));
+        assert_eq!(check_task(&backup, at(4, 0), || false, || false, || true), Err(Skip::BackupRunning));
+
+        let mut restart = task("restart", "0 4 * * *");
+        assert_eq!(check_task(&restart, at(4, 0), || false, || false, || true), Ok(()));
+        restart.skip_if_running = false;
+        assert_eq!(check_task(&restart, at(4, 0), || false, || true, || false), Ok(()));
+    }
+
+    #[test]
+    fn state_checks_are_not_called_for_tasks_that_are_not_due() {
+        let t = task("backup", "0 4 * * *");
+        fn never() -> bool { panic!("state check called") }
+        assert_eq!(check_task(&t, at(5, 0), never, never, never), Err(Skip::NotDue));
+    }
+
+    #[test]
+    fn announcements_fill_in_placeholders() {
+        let state = ServerProcessState::new();
+        let mut stats = crate::commands::runner::LiveStats::default();
+        stats.online_players.insert("Steve".to_string());
+        state.live_stats.lock().unwrap().insert("server-1".to_string(), stats);
+        let restarts = vec![("server-1".to_string(), "0 6 * * *".to_string()), ("server-2".to_string(), "15 4 * * *".to_string())];
+
+        let text = render_announcement("{time}: {players_online} online, restart in {next_restart}", &state, "server-1", &restarts, at(4, 30));
+        assert_eq!(text, "04:30: 1 online, restart in 1 hour 30 minutes");
+        assert_eq!(render_announcement("{next_restart}", &state, "server-3", &restarts, at(4, 30)), "not scheduled");
+    }
+
+    #[test]
+    fn minutes_are_described_in_words() {
+        assert_eq!(describe_minutes(1), "1 minute");
+        assert_eq!(describe_minutes(45), "45 minutes");
+        assert_eq!(describe_minutes(60), "1 hour");
+        assert_eq!(describe_minutes(125), "2 hours 5 minutes");
+    }
+}
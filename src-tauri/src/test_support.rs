@@ -0,0 +1,120 @@
+// Shared pieces of the unit tests: an isolated home folder, an event sink that records what the
+// runner emits, and server folders that launch examples/fake_server.rs instead of Minecraft.
+// `cargo test` builds the example along with the tests; `cargo test --lib` alone doesn't, so
+// build it first with `cargo build --example fake_server` in that case.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Once};
+use std::time::{Duration, Instant};
+use crate::commands::runner::{ServerConfig, ServerEventSink};
+
+/// Points HOME/USERPROFILE at a temporary folder once per test run, so the registry, settings,
+/// activity log and player stats written by the code under test stay out of the real
+/// ~/Mineserver.
+pub fn isolated_home() -> PathBuf {
+    static INIT: Once = Once::new();
+    let home = std::env::temp_dir().join(format!("mineserver-test-home-{}", std::process::id()));
+    INIT.call_once(|| {
+        let _ = std::fs::create_dir_all(&home);
+        std::env::set_var("HOME", &home);
+        std::env::set_var("USERPROFILE", &home);
+    });
+    home
+}
+
+/// A fresh, empty folder for one test.
+pub fn temp_dir(label: &str) -> PathBuf {
+    let dir = isolated_home().join("tmp").join(format!("{}-{}", label, uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create test folder");
+    dir
+}
+
+/// Polls `condition` every 20 ms until it holds or `timeout` passes.
+pub fn wait_until(timeout: Duration, mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if condition() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Sink that keeps every event, for assertions on what the runner and scheduler emitted.
+#[derive(Clone, Default)]
+pub struct RecordingSink {
+    events: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+}
+
+impl ServerEventSink for RecordingSink {
+    fn emit<S: serde::Serialize + Clone>(&self, event: &str, payload: S) {
+        let payload = serde_json::to_value(&payload).unwrap_or(serde_json::Value::Null);
+        self.events.lock().unwrap().push((event.to_string(), payload));
+    }
+}
+
+impl RecordingSink {
+    pub fn events(&self, event: &str) -> Vec<serde_json::Value> {
+        self.events.lock().unwrap().iter()
+            .filter(|(name, _)| name == event)
+            .map(|(_, payload)| payload.clone())
+            .collect()
+    }
+
+    /// Console lines of a server in the order they were emitted, batches flattened.
+    pub fn console_lines(&self, id: &str) -> Vec<String> {
+        self.events(&format!("server-log:{}", id)).into_iter()
+            .flat_map(|payload| match payload {
+                serde_json::Value::Array(lines) => lines,
+                line => vec![line],
+            })
+            .filter_map(|line| line.as_str().map(str::to_string))
+            .collect()
+    }
+}
+
+fn fake_server_binary() -> PathBuf {
+    // Test binaries live in target/<profile>/deps, examples in target/<profile>/examples
+    let exe = std::env::current_exe().expect("test binary path");
+    let binary = exe.parent().and_then(Path::parent)
+        .map(|profile| profile.join("examples").join(format!("fake_server{}", std::env::consts::EXE_SUFFIX)))
+        .expect("target folder");
+    assert!(binary.exists(), "{} is missing; run `cargo build --example fake_server` or the full `cargo test`", binary.display());
+    binary
+}
+
+/// A server folder whose "server" is the fake server, with the given fake_server.txt options.
+pub struct FakeServer {
+    pub dir: PathBuf,
+    pub config: ServerConfig,
+}
+
+impl FakeServer {
+    pub fn new(options: &[&str]) -> Self {
+        let dir = temp_dir("server");
+        std::fs::write(dir.join("fake_server.txt"), options.join("\n")).expect("write fake_server.txt");
+        let config = ServerConfig {
+            id: format!("test-{}", uuid::Uuid::new_v4()),
+            path: dir.to_string_lossy().to_string(),
+            jar_file: fake_server_binary().to_string_lossy().to_string(), // Absolute, so it isn't joined to the folder
+            ram: 512,
+            java_path: None,
+            startup_flags: None,
+            auto_restart: false,
+        };
+        Self { dir, config }
+    }
+
+    pub fn with_auto_restart(mut self) -> Self {
+        self.config.auto_restart = true;
+        self
+    }
+
+    /// How often the fake server was started in this folder.
+    pub fn starts(&self) -> usize {
+        std::fs::read_to_string(self.dir.join("starts.log")).map(|s| s.lines().count()).unwrap_or(0)
+    }
+}
@@ -9,14 +9,37 @@ import { ScheduledTasks } from './pages/ScheduledTasks';
 import { Settings } from './pages/Settings';
 import { CreateServer } from './pages/CreateServer';
 import { useAppStore } from './stores/appStore';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
 
 function App() {
-  const { syncServerStatuses } = useAppStore();
+  const { syncServerStatuses, setServerStatus, servers } = useAppStore();
 
   // Sync server statuses with backend on app load
   useEffect(() => {
     syncServerStatuses();
   }, [syncServerStatuses]);
+
+  // Laptop hosts: warn when a server is started unplugged
+  useEffect(() => {
+    const unlisten = listen<{ serverId: string; power: { percentage: number | null } }>('power-warning', (e) => {
+      const battery = e.payload.power.percentage != null ? ` (${e.payload.power.percentage}%)` : '';
+      toast.warning(`Running on battery${battery}. Plug in to keep the server online.`);
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
+  // Auto-restart gave up after repeated crashes
+  useEffect(() => {
+    const unlisten = listen<{ serverId: string; crashes: number }>('server-crash-loop', (e) => {
+      setServerStatus(e.payload.serverId, 'error');
+      const name = servers.find(s => s.id === e.payload.serverId)?.name ?? 'The server';
+      toast.error(`${name} crashed ${e.payload.crashes} times in a row`, {
+        description: "Auto-restart stopped. Try starting it in safe mode from the Startup tab.",
+      });
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, [servers, setServerStatus]);
   return (
     <Routes>
       <Route path="/" element={<Layout />}>
@@ -0,0 +1,134 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Star, Plus, Trash2, RefreshCw } from 'lucide-react';
+import { toast } from 'sonner';
+import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
+
+interface FavoriteServer {
+    id: string;
+    name: string;
+    address: string;
+    bedrock: boolean;
+}
+
+interface FavoriteStatus {
+    id: string;
+    online: boolean;
+    ping: { latencyMs: number; onlinePlayers: number; maxPlayers: number; version: string; motd: string } | null;
+    error: string | null;
+}
+
+// External servers the user plays on, pinged with Server List Ping
+export function FavoritesCard() {
+    const [favorites, setFavorites] = useState<FavoriteServer[]>([]);
+    const [statuses, setStatuses] = useState<Record<string, FavoriteStatus>>({});
+    const [address, setAddress] = useState('');
+    const [bedrock, setBedrock] = useState(false);
+    const [refreshing, setRefreshing] = useState(false);
+
+    const refresh = async () => {
+        setRefreshing(true);
+        try {
+            await invoke('ping_favorites');
+        } catch (e) {
+            toast.error("Failed to ping favorites: " + errorMessage(e));
+        } finally {
+            setRefreshing(false);
+        }
+    };
+
+    useEffect(() => {
+        invoke<FavoriteServer[]>('list_favorites').then(setFavorites).catch(() => { });
+        const unlisten = listen<FavoriteStatus>('favorite-status', (e) => {
+            setStatuses(prev => ({ ...prev, [e.payload.id]: e.payload }));
+        });
+        refresh();
+        const interval = setInterval(refresh, 60000);
+        return () => {
+            clearInterval(interval);
+            unlisten.then(fn => fn());
+        };
+    }, []);
+
+    const addFavorite = async () => {
+        if (!address.trim()) return;
+        try {
+            const saved = await invoke<FavoriteServer>('save_favorite', { favorite: { name: '', address, bedrock } });
+            setFavorites(prev => [...prev, saved]);
+            setAddress('');
+            refresh();
+        } catch (e) {
+            toast.error(errorMessage(e));
+        }
+    };
+
+    const removeFavorite = async (id: string) => {
+        try {
+            await invoke('delete_favorite', { id });
+            setFavorites(prev => prev.filter(f => f.id !== id));
+        } catch (e) {
+            toast.error(errorMessage(e));
+        }
+    };
+
+    return (
+        <div className="rounded-2xl bg-surface/50 border border-border/50 p-5 space-y-4">
+            <div className="flex items-center justify-between">
+                <h3 className="font-bold text-white flex items-center gap-2">
+                    <Star className="w-4 h-4 text-yellow-400" />
+                    Favorite Servers
+                </h3>
+                <button onClick={refresh} className="p-1.5 rounded-lg hover:bg-white/5 text-text-muted hover:text-white transition-colors">
+                    <RefreshCw className={cn("w-4 h-4", refreshing && "animate-spin")} />
+                </button>
+            </div>
+
+            <div className="space-y-2">
+                {favorites.map(favorite => {
+                    const status = statuses[favorite.id];
+                    return (
+                        <div key={favorite.id} className="flex items-center gap-3 p-2.5 rounded-lg bg-white/5 border border-white/5 group">
+                            <span className={cn(
+                                "w-2 h-2 rounded-full shrink-0",
+                                !status ? "bg-gray-500" : status.online ? "bg-emerald-500" : "bg-red-500"
+                            )} />
+                            <div className="min-w-0 flex-1">
+                                <p className="text-sm font-medium text-white truncate">{favorite.name}</p>
+                                <p className="text-[11px] text-text-muted truncate" title={status?.error ?? undefined}>
+                                    {status?.ping
+                                        ? `${status.ping.onlinePlayers}/${status.ping.maxPlayers} players · ${status.ping.latencyMs} ms · ${status.ping.version}`
+                                        : status ? 'Offline' : 'Pinging...'}
+                                </p>
+                            </div>
+                            <button onClick={() => removeFavorite(favorite.id)} className="opacity-0 group-hover:opacity-100 p-1 text-text-muted hover:text-red-400 transition-all">
+                                <Trash2 className="w-3.5 h-3.5" />
+                            </button>
+                        </div>
+                    );
+                })}
+                {favorites.length === 0 && (
+                    <p className="text-xs text-text-muted">Add servers you play on to see their status here.</p>
+                )}
+            </div>
+
+            <div className="flex items-center gap-2">
+                <input
+                    value={address}
+                    onChange={(e) => setAddress(e.target.value)}
+                    onKeyDown={(e) => e.key === 'Enter' && addFavorite()}
+                    placeholder="play.example.com:25565"
+                    className="flex-1 min-w-0 px-3 py-1.5 bg-black/20 border border-border rounded-lg text-sm text-white placeholder:text-text-muted focus:outline-none focus:border-primary/50"
+                />
+                <label className="flex items-center gap-1 text-[11px] text-text-muted">
+                    <input type="checkbox" checked={bedrock} onChange={(e) => setBedrock(e.target.checked)} />
+                    Bedrock
+                </label>
+                <button onClick={addFavorite} className="p-1.5 rounded-lg bg-primary/10 hover:bg-primary/20 text-primary transition-colors">
+                    <Plus className="w-4 h-4" />
+                </button>
+            </div>
+        </div>
+    );
+}
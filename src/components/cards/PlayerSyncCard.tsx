@@ -0,0 +1,216 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Users, Plus, Trash2, RefreshCw, Eye } from 'lucide-react';
+import { toast } from 'sonner';
+import { useAppStore } from '../../stores/appStore';
+import { errorMessage } from '../../lib/errors';
+
+interface SyncGroup {
+    id: string;
+    name: string;
+    sourceServerId: string;
+    targetServerIds: string[];
+    files: string[];
+    lastSync: string | null;
+}
+
+interface SyncDiff {
+    serverId: string;
+    serverName: string;
+    file: string;
+    added: string[];
+    removed: string[];
+    changed: string[];
+}
+
+interface SyncResult {
+    diff: SyncDiff;
+    method: 'file' | 'console' | 'none';
+    error: string | null;
+}
+
+const SYNC_FILES = ['whitelist.json', 'ops.json', 'banned-players.json'];
+
+// Keeps whitelist/ops/bans of a proxy network's servers in line with one source server
+export function PlayerSyncCard() {
+    const { servers } = useAppStore();
+    const [groups, setGroups] = useState<SyncGroup[]>([]);
+    const [preview, setPreview] = useState<{ groupId: string; diffs: SyncDiff[] } | null>(null);
+    const [busyId, setBusyId] = useState<string | null>(null);
+    const [creating, setCreating] = useState(false);
+    const [name, setName] = useState('');
+    const [sourceId, setSourceId] = useState('');
+    const [targetIds, setTargetIds] = useState<string[]>([]);
+    const [files, setFiles] = useState<string[]>(SYNC_FILES);
+
+    useEffect(() => {
+        invoke<SyncGroup[]>('list_sync_groups').then(setGroups).catch(() => { });
+    }, []);
+
+    const serverName = (id: string) => servers.find(s => s.id === id)?.name ?? 'Unknown server';
+
+    const openCreate = () => {
+        setName('');
+        setSourceId(servers[0]?.id ?? '');
+        setTargetIds([]);
+        setFiles(SYNC_FILES);
+        setCreating(true);
+    };
+
+    const saveGroup = async () => {
+        try {
+            const saved = await invoke<SyncGroup>('save_sync_group', {
+                group: { name, sourceServerId: sourceId, targetServerIds: targetIds, files },
+            });
+            setGroups(prev => [...prev, saved]);
+            setCreating(false);
+        } catch (e) {
+            toast.error(errorMessage(e));
+        }
+    };
+
+    const removeGroup = async (id: string) => {
+        try {
+            await invoke('delete_sync_group', { id });
+            setGroups(prev => prev.filter(g => g.id !== id));
+            if (preview?.groupId === id) setPreview(null);
+        } catch (e) {
+            toast.error(errorMessage(e));
+        }
+    };
+
+    const previewGroup = async (id: string) => {
+        setBusyId(id);
+        try {
+            const diffs = await invoke<SyncDiff[]>('preview_player_sync', { id });
+            setPreview({ groupId: id, diffs });
+        } catch (e) {
+            toast.error("Failed to compare player lists: " + errorMessage(e));
+        } finally {
+            setBusyId(null);
+        }
+    };
+
+    const syncGroup = async (id: string) => {
+        setBusyId(id);
+        try {
+            const results = await invoke<SyncResult[]>('sync_player_lists', { id });
+            const failed = results.filter(r => r.error);
+            if (failed.length > 0) {
+                toast.error(`${failed.length} list(s) failed to sync`, { description: failed[0].error ?? undefined });
+            } else {
+                toast.success(`Synced ${results.filter(r => r.method !== 'none').length} list(s)`);
+            }
+            setPreview(null);
+            invoke<SyncGroup[]>('list_sync_groups').then(setGroups).catch(() => { });
+        } catch (e) {
+            toast.error("Sync failed: " + errorMessage(e));
+        } finally {
+            setBusyId(null);
+        }
+    };
+
+    const toggle = (list: string[], value: string) =>
+        list.includes(value) ? list.filter(v => v !== value) : [...list, value];
+
+    return (
+        <div className="rounded-2xl bg-surface/50 border border-border/50 p-5 space-y-4">
+            <div className="flex items-center justify-between">
+                <h3 className="font-bold text-white flex items-center gap-2">
+                    <Users className="w-4 h-4 text-primary" />
+                    Player List Sync
+                </h3>
+                <button onClick={openCreate} className="p-1.5 rounded-lg bg-primary/10 hover:bg-primary/20 text-primary transition-colors">
+                    <Plus className="w-4 h-4" />
+                </button>
+            </div>
+
+            {groups.length === 0 && !creating && (
+                <p className="text-xs text-text-muted">Copy the whitelist, ops and bans of one server to the rest of your network.</p>
+            )}
+
+            {groups.map(group => (
+                <div key={group.id} className="p-3 rounded-lg bg-white/5 border border-white/5 space-y-2">
+                    <div className="flex items-center gap-2">
+                        <div className="min-w-0 flex-1">
+                            <p className="text-sm font-medium text-white truncate">{group.name}</p>
+                            <p className="text-[11px] text-text-muted truncate">
+                                {serverName(group.sourceServerId)} → {group.targetServerIds.map(serverName).join(', ')}
+                                {group.lastSync && ` · last synced ${new Date(group.lastSync).toLocaleString()}`}
+                            </p>
+                        </div>
+                        <button onClick={() => previewGroup(group.id)} disabled={busyId === group.id} title="Preview" className="p-1.5 rounded-lg hover:bg-white/5 text-text-muted hover:text-white transition-colors">
+                            <Eye className="w-4 h-4" />
+                        </button>
+                        <button onClick={() => syncGroup(group.id)} disabled={busyId === group.id} title="Sync now" className="p-1.5 rounded-lg hover:bg-white/5 text-text-muted hover:text-white transition-colors">
+                            <RefreshCw className={busyId === group.id ? "w-4 h-4 animate-spin" : "w-4 h-4"} />
+                        </button>
+                        <button onClick={() => removeGroup(group.id)} title="Delete" className="p-1.5 rounded-lg hover:bg-white/5 text-text-muted hover:text-red-400 transition-colors">
+                            <Trash2 className="w-4 h-4" />
+                        </button>
+                    </div>
+
+                    {preview?.groupId === group.id && (
+                        <div className="space-y-1 pt-2 border-t border-white/5">
+                            {preview.diffs.every(d => !d.added.length && !d.removed.length && !d.changed.length) && (
+                                <p className="text-[11px] text-emerald-400">Everything is already in sync.</p>
+                            )}
+                            {preview.diffs.filter(d => d.added.length || d.removed.length || d.changed.length).map(d => (
+                                <div key={`${d.serverId}-${d.file}`} className="text-[11px] text-text-muted">
+                                    <span className="text-white">{d.serverName}</span> · {d.file}:
+                                    {d.added.length > 0 && <span className="text-emerald-400"> +{d.added.join(', ')}</span>}
+                                    {d.removed.length > 0 && <span className="text-red-400"> −{d.removed.join(', ')}</span>}
+                                    {d.changed.length > 0 && <span className="text-amber-400"> ~{d.changed.join(', ')}</span>}
+                                </div>
+                            ))}
+                        </div>
+                    )}
+                </div>
+            ))}
+
+            {creating && (
+                <div className="p-3 rounded-lg bg-black/20 border border-border space-y-3">
+                    <input
+                        value={name}
+                        onChange={(e) => setName(e.target.value)}
+                        placeholder="Group name"
+                        className="w-full px-3 py-1.5 bg-black/20 border border-border rounded-lg text-sm text-white placeholder:text-text-muted focus:outline-none focus:border-primary/50"
+                    />
+                    <div>
+                        <label className="text-[11px] text-text-muted block mb-1">Copy from</label>
+                        <select
+                            value={sourceId}
+                            onChange={(e) => setSourceId(e.target.value)}
+                            className="w-full px-3 py-1.5 bg-black/20 border border-border rounded-lg text-sm text-white focus:outline-none"
+                        >
+                            {servers.map(s => <option key={s.id} value={s.id}>{s.name}</option>)}
+                        </select>
+                    </div>
+                    <div>
+                        <label className="text-[11px] text-text-muted block mb-1">Copy to</label>
+                        <div className="flex flex-wrap gap-2">
+                            {servers.filter(s => s.id !== sourceId && s.type !== 'bedrock').map(s => (
+                                <label key={s.id} className="flex items-center gap-1 text-xs text-white">
+                                    <input type="checkbox" checked={targetIds.includes(s.id)} onChange={() => setTargetIds(prev => toggle(prev, s.id))} />
+                                    {s.name}
+                                </label>
+                            ))}
+                        </div>
+                    </div>
+                    <div className="flex flex-wrap gap-3">
+                        {SYNC_FILES.map(file => (
+                            <label key={file} className="flex items-center gap-1 text-[11px] text-text-muted">
+                                <input type="checkbox" checked={files.includes(file)} onChange={() => setFiles(prev => toggle(prev, file))} />
+                                {file}
+                            </label>
+                        ))}
+                    </div>
+                    <div className="flex justify-end gap-2">
+                        <button onClick={() => setCreating(false)} className="px-3 py-1.5 text-xs text-text-muted hover:text-white">Cancel</button>
+                        <button onClick={saveGroup} className="px-3 py-1.5 bg-primary text-black text-xs font-bold rounded-lg">Save Group</button>
+                    </div>
+                </div>
+            )}
+        </div>
+    );
+}
@@ -12,6 +12,8 @@ const serverTypeNames: Record<ServerType, string> = {
     spigot: 'Spigot',
     purpur: 'Purpur',
     nukkit: 'Nukkit',
+    spongevanilla: 'SpongeVanilla',
+    spongeforge: 'SpongeForge',
 };
 
 interface RecommendationCardProps {
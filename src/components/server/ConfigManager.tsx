@@ -5,9 +5,10 @@ import { toast } from 'sonner';
 import {
     Settings, Shield, Gamepad2, Globe, Users, Skull, Zap,
     MapPin, Sun, Moon, Clock, Flame, Snowflake, RefreshCw,
-    Save, AlertTriangle, Check
+    Save, AlertTriangle, Check, Eye, Gauge
 } from 'lucide-react';
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
 
 interface ConfigManagerProps {
     server: Server;
@@ -15,6 +16,14 @@ interface ConfigManagerProps {
     sendCommand: (e?: React.FormEvent, cmdStr?: string) => Promise<void>;
 }
 
+interface QuickSettings {
+    viewDistance: number | null;
+    simulationDistance: number | null;
+    antiXray: { enabled: boolean; engineMode: number } | null;
+    spawnLimits: Record<string, number> | null;
+    files: Record<string, string>;
+}
+
 interface GameRule {
     id: string;
     name: string;
@@ -154,7 +163,7 @@ export function ConfigManager({ server, isRunning, sendCommand }: ConfigManagerP
             toast.success(isRunning ? 'Settings applied & saved!' : 'Settings saved! (Will apply on start if you add a startup script, otherwise set manually)');
             setHasChanges(false);
         } catch (e) {
-            toast.error('Failed to save: ' + e);
+            toast.error('Failed to save: ' + errorMessage(e));
         } finally {
             setIsSaving(false);
         }
@@ -338,6 +347,8 @@ export function ConfigManager({ server, isRunning, sendCommand }: ConfigManagerP
                     </div>
                 </div>
 
+                <QuickSettingsPanel server={server} />
+
                 {/* Server Properties Info */}
                 <div className="mt-6 p-4 bg-primary/10 border border-primary/30 rounded-xl">
                     <div className="flex items-start gap-3">
@@ -355,3 +366,153 @@ export function ConfigManager({ server, isRunning, sendCommand }: ConfigManagerP
         </div>
     );
 }
+
+function QuickSettingsPanel({ server }: { server: Server }) {
+    const [settings, setSettings] = useState<QuickSettings | null>(null);
+    const [busy, setBusy] = useState<string | null>(null);
+    const isBedrock = server.type === 'bedrock';
+
+    const load = async () => {
+        try {
+            setSettings(await invoke<QuickSettings>('get_quick_settings', { id: server.id }));
+        } catch (e) {
+            console.error(e);
+        }
+    };
+
+    useEffect(() => {
+        load();
+    }, [server.id]);
+
+    const apply = async (key: string, command: string, args: Record<string, unknown>) => {
+        setBusy(key);
+        try {
+            const files = await invoke<string[]>(command, { id: server.id, ...args });
+            toast.success(`Saved to ${files.join(', ')}. Restart the server to apply.`);
+            await load();
+        } catch (e) {
+            toast.error(errorMessage(e));
+        } finally {
+            setBusy(null);
+        }
+    };
+
+    if (!settings) return null;
+    const update = (patch: Partial<QuickSettings>) => setSettings({ ...settings, ...patch });
+    const inputClass = "w-20 bg-black/30 border border-border rounded-lg px-3 py-2 text-white text-center font-mono focus:border-primary outline-none";
+    const buttonClass = "px-3 py-2 bg-primary/20 hover:bg-primary/30 text-primary text-xs font-bold rounded-lg transition-all disabled:opacity-50";
+
+    return (
+        <div className="mt-6 pt-6 border-t border-border/30">
+            <h3 className="text-sm font-bold text-white mb-4 flex items-center gap-2">
+                <Gauge className="w-4 h-4 text-cyan-400" />
+                Performance
+            </h3>
+            <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <div className="bg-surface/30 border border-border/30 rounded-xl p-4 space-y-3">
+                    {([
+                        ['viewDistance', 'View Distance', 'set_view_distance'],
+                        ['simulationDistance', isBedrock ? 'Tick Distance' : 'Simulation Distance', 'set_simulation_distance'],
+                    ] as const).map(([key, label, command]) => (
+                        <div key={key} className="flex items-center justify-between gap-3">
+                            <div>
+                                <h4 className="font-bold text-white text-sm">{label}</h4>
+                                <p className="text-xs text-text-muted">Chunks, in {settings.files[key]}</p>
+                            </div>
+                            <div className="flex items-center gap-2">
+                                <input
+                                    type="number"
+                                    value={settings[key] ?? ''}
+                                    onChange={(e) => update({ [key]: parseInt(e.target.value) || null })}
+                                    className={inputClass}
+                                />
+                                <button
+                                    disabled={busy !== null || !settings[key]}
+                                    onClick={() => apply(key, command, { chunks: settings[key] })}
+                                    className={buttonClass}
+                                >
+                                    Apply
+                                </button>
+                            </div>
+                        </div>
+                    ))}
+                </div>
+
+                {settings.antiXray && (
+                    <div className="bg-surface/30 border border-border/30 rounded-xl p-4 space-y-3">
+                        <div className="flex items-center justify-between gap-3">
+                            <div className="flex items-start gap-3">
+                                <Eye className="w-4 h-4 text-cyan-400 mt-0.5" />
+                                <div>
+                                    <h4 className="font-bold text-white text-sm">Anti-Xray</h4>
+                                    <p className="text-xs text-text-muted">In {settings.files.antiXray}</p>
+                                </div>
+                            </div>
+                            <button
+                                onClick={() => update({ antiXray: { ...settings.antiXray!, enabled: !settings.antiXray!.enabled } })}
+                                className={cn(
+                                    "relative w-14 h-7 rounded-full transition-colors shrink-0",
+                                    settings.antiXray.enabled ? "bg-green-500" : "bg-surface border border-border"
+                                )}
+                            >
+                                <div className={cn(
+                                    "absolute top-1 w-5 h-5 rounded-full bg-white shadow transition-all",
+                                    settings.antiXray.enabled ? "left-8" : "left-1"
+                                )} />
+                            </button>
+                        </div>
+                        <div className="flex items-center justify-between gap-3">
+                            <select
+                                value={settings.antiXray.engineMode}
+                                onChange={(e) => update({ antiXray: { ...settings.antiXray!, engineMode: parseInt(e.target.value) } })}
+                                className="flex-1 bg-black/30 border border-border rounded-lg px-3 py-2 text-white text-xs focus:border-primary outline-none"
+                            >
+                                <option value={1}>Mode 1: hide ores</option>
+                                <option value={2}>Mode 2: add fake ores</option>
+                                <option value={3}>Mode 3: fake ores, less bandwidth</option>
+                            </select>
+                            <button
+                                disabled={busy !== null}
+                                onClick={() => apply('antiXray', 'set_anti_xray', settings.antiXray!)}
+                                className={buttonClass}
+                            >
+                                Apply
+                            </button>
+                        </div>
+                    </div>
+                )}
+
+                {settings.spawnLimits && (
+                    <div className="bg-surface/30 border border-border/30 rounded-xl p-4 md:col-span-2">
+                        <div className="flex items-center justify-between mb-3">
+                            <div>
+                                <h4 className="font-bold text-white text-sm">Spawn Limits</h4>
+                                <p className="text-xs text-text-muted">Mob caps per player, in {settings.files.spawnLimits}. -1 uses the vanilla default.</p>
+                            </div>
+                            <button
+                                disabled={busy !== null}
+                                onClick={() => apply('spawnLimits', 'set_spawn_limits', { limits: settings.spawnLimits })}
+                                className={buttonClass}
+                            >
+                                Apply
+                            </button>
+                        </div>
+                        <div className="grid grid-cols-2 md:grid-cols-4 gap-3">
+                            {Object.entries(settings.spawnLimits).map(([category, limit]) => (
+                                <label key={category} className="flex items-center justify-between gap-2 text-xs text-text-muted">
+                                    {category}
+                                    <input
+                                        type="number"
+                                        value={limit}
+                                        onChange={(e) => update({ spawnLimits: { ...settings.spawnLimits, [category]: parseInt(e.target.value) || 0 } })}
+                                        className={inputClass}
+                                    />
+                                </label>
+                            ))}
+                        </div>
+                    </div>
+                )}
+            </div>
+        </div>
+    );
+}
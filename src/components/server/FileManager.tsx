@@ -8,8 +8,11 @@ import {
 } from 'lucide-react';
 import { toast } from 'sonner';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { cn } from '../../lib/utils';
 import { CodeEditor } from '../ui/CodeEditor';
+import { errorMessage } from '../../lib/errors';
+import { listenOperation, newOperationId } from '../../lib/operations';
 
 interface FileManagerProps {
     serverPath: string;
@@ -48,6 +51,10 @@ const getFileInfo = (name: string) => {
         'zip': { icon: Archive, color: 'text-purple-500', editable: false, syntax: '' },
         'gz': { icon: Archive, color: 'text-purple-500', editable: false, syntax: '' },
         'tar': { icon: Archive, color: 'text-purple-500', editable: false, syntax: '' },
+        // NBT (edited as a JSON tree)
+        'dat': { icon: FileJson, color: 'text-emerald-400', editable: true, syntax: 'nbt' },
+        'dat_old': { icon: FileJson, color: 'text-emerald-400', editable: true, syntax: 'nbt' },
+        'nbt': { icon: FileJson, color: 'text-emerald-400', editable: true, syntax: 'nbt' },
         // Images
         'png': { icon: Image, color: 'text-pink-400', editable: false, syntax: '' },
         'jpg': { icon: Image, color: 'text-pink-400', editable: false, syntax: '' },
@@ -90,6 +97,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
         localStorage.setItem('file_manager_view', viewMode);
     }, [viewMode]);
     const [newItemName, setNewItemName] = useState('');
+    const [dirSizes, setDirSizes] = useState<Record<string, number>>({});
 
     // Refs for standard HTML upload
     const fileInputRef = useRef<HTMLInputElement>(null);
@@ -101,6 +109,52 @@ export function FileManager({ serverPath }: FileManagerProps) {
 
     useEffect(() => { loadFiles(); }, [path]);
 
+    // Refresh automatically when the server or another tool changes files in the open folder
+    const pathRef = useRef(path);
+    pathRef.current = path;
+    const loadFilesRef = useRef(loadFiles);
+    loadFilesRef.current = loadFiles;
+    useEffect(() => {
+        let watchId: string | null = null;
+        let unlisten: (() => void) | undefined;
+        let refreshTimer: ReturnType<typeof setTimeout> | undefined;
+        let disposed = false;
+
+        async function setup() {
+            unlisten = await listen('fs-change', (e: any) => {
+                const change = e.payload as { watchId: string; created: string[]; modified: string[]; removed: string[] };
+                if (change.watchId !== watchId) return;
+                const current = pathRef.current.slice(serverPath.length).replace(/^[\\/]+/, '').replace(/\\/g, '/');
+                const affectsCurrent = [...change.created, ...change.modified, ...change.removed].some(p => {
+                    const normalized = p.replace(/\\/g, '/');
+                    const parent = normalized.includes('/') ? normalized.slice(0, normalized.lastIndexOf('/')) : '';
+                    return parent === current;
+                });
+                if (affectsCurrent) {
+                    clearTimeout(refreshTimer);
+                    refreshTimer = setTimeout(() => loadFilesRef.current(), 300);
+                }
+            });
+            try {
+                const id = await invoke<string>('watch_directory', { serverPath });
+                if (disposed) {
+                    invoke('unwatch_directory', { watchId: id }).catch(() => { });
+                } else {
+                    watchId = id;
+                }
+            } catch (e) {
+                console.warn('File watcher unavailable:', e);
+            }
+        }
+        setup();
+        return () => {
+            disposed = true;
+            clearTimeout(refreshTimer);
+            if (unlisten) unlisten();
+            if (watchId) invoke('unwatch_directory', { watchId }).catch(() => { });
+        };
+    }, [serverPath]);
+
     // Close context menu on click outside
     useEffect(() => {
         const handleClick = (e: MouseEvent) => {
@@ -128,8 +182,16 @@ export function FileManager({ serverPath }: FileManagerProps) {
                 return a.name.localeCompare(b.name);
             });
             setFiles(entries);
+            // Folder sizes are computed in the background, only for the server's top level
+            if (path === serverPath) {
+                invoke<{ name: string; sizeBytes: number }[]>('get_directory_sizes', { serverPath })
+                    .then(sizes => setDirSizes(Object.fromEntries(sizes.map(s => [s.name, s.sizeBytes]))))
+                    .catch(() => setDirSizes({}));
+            } else {
+                setDirSizes({});
+            }
         } catch (e) {
-            toast.error("Failed to load files: " + e);
+            toast.error("Failed to load files: " + errorMessage(e));
         } finally {
             setLoading(false);
         }
@@ -151,10 +213,16 @@ export function FileManager({ serverPath }: FileManagerProps) {
             }
 
             try {
+                if (fileInfo.syntax === 'nbt') {
+                    const doc = await invoke<unknown>('read_nbt', { path: `${path}\\${file.name}` });
+                    const content = JSON.stringify(doc, null, 2);
+                    setEditingFile({ name: file.name, content, originalContent: content });
+                    return;
+                }
                 const content = await invoke<string>('read_server_file', { path: `${path}\\${file.name}` });
                 setEditingFile({ name: file.name, content, originalContent: content });
             } catch (e) {
-                toast.error("Failed to read file: " + e);
+                toast.error("Failed to read file: " + errorMessage(e));
             }
         }
     };
@@ -166,20 +234,36 @@ export function FileManager({ serverPath }: FileManagerProps) {
         setSearchQuery('');
     };
 
-    const saveFile = async () => {
+    const saveFile = async (skipValidation = false) => {
         if (!editingFile) return;
         setSaving(true);
         try {
-            await invoke('write_server_file', {
-                path: `${path}\\${editingFile.name}`,
-                content: editingFile.content
-            });
+            if (getFileInfo(editingFile.name).syntax === 'nbt') {
+                await invoke('write_nbt', {
+                    path: `${path}\\${editingFile.name}`,
+                    json: JSON.parse(editingFile.content)
+                });
+            } else {
+                await invoke('write_server_file', {
+                    path: `${path}\\${editingFile.name}`,
+                    content: editingFile.content,
+                    skipValidation
+                });
+            }
 
             // Update original content to match saved
             setEditingFile(prev => prev ? { ...prev, originalContent: prev.content } : null);
             toast.success("File saved successfully!");
         } catch (e) {
-            toast.error("Failed to save: " + e);
+            const message = errorMessage(e);
+            if (message.startsWith('CONFIG_SYNTAX_ERROR:')) {
+                const err = JSON.parse(message.slice('CONFIG_SYNTAX_ERROR:'.length));
+                toast.error(`Syntax error (line ${err.line}, column ${err.column}): ${err.message}`, {
+                    action: { label: 'Save anyway', onClick: () => saveFile(true) }
+                });
+                return;
+            }
+            toast.error("Failed to save: " + errorMessage(e));
         } finally {
             setSaving(false);
         }
@@ -200,7 +284,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
             setNewItemName('');
             loadFiles();
         } catch (e) {
-            toast.error("Creation failed: " + e);
+            toast.error("Creation failed: " + errorMessage(e));
         }
     };
 
@@ -217,7 +301,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
             setNewItemName('');
             loadFiles();
         } catch (e) {
-            toast.error("Failed to rename: " + e);
+            toast.error("Failed to rename: " + errorMessage(e));
         }
     };
 
@@ -235,7 +319,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
             setShowDeleteModal(null);
             loadFiles();
         } catch (e) {
-            toast.error("Failed to delete: " + e);
+            toast.error("Failed to delete: " + errorMessage(e));
         }
     };
 
@@ -246,11 +330,12 @@ export function FileManager({ serverPath }: FileManagerProps) {
             toast.success("Path copied to clipboard!");
             setContextMenu(null);
         } catch (e) {
-            toast.error("Failed to copy path: " + e);
+            toast.error("Failed to copy path: " + errorMessage(e));
         }
     };
 
     const duplicateItem = async (file: FileEntry) => {
+        const toastId = toast.loading("Duplicating...");
         try {
             const nameParts = file.name.split('.');
             let newName = '';
@@ -261,15 +346,21 @@ export function FileManager({ serverPath }: FileManagerProps) {
                 newName = `${file.name} (copy)`;
             }
 
+            const operationId = newOperationId();
+            const unlisten = await listenOperation(operationId, (progress) => {
+                if (progress.state !== 'running') return;
+                toast.loading(`Duplicating... ${progress.percentage}%`, { id: toastId });
+            });
             await invoke('duplicate_file', {
                 path: `${path}\\${file.name}`,
-                newPath: `${path}\\${newName}`
-            });
-            toast.success("Duplicated item!");
+                newPath: `${path}\\${newName}`,
+                operationId
+            }).finally(unlisten);
+            toast.success("Duplicated item!", { id: toastId });
             loadFiles();
             setContextMenu(null);
         } catch (e) {
-            toast.error("Failed to duplicate: " + e);
+            toast.error("Failed to duplicate: " + errorMessage(e), { id: toastId });
         }
     };
 
@@ -304,7 +395,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
             toast.success("Download started");
             setContextMenu(null);
         } catch (e) {
-            toast.error("Download failed (only text files supported): " + e);
+            toast.error("Download failed (only text files supported): " + errorMessage(e));
         }
     };
 
@@ -320,22 +411,46 @@ export function FileManager({ serverPath }: FileManagerProps) {
             loadFiles();
             setContextMenu(null);
         } catch (e) {
-            toast.error("Archive failed: " + e, { id: toastId });
+            toast.error("Archive failed: " + errorMessage(e), { id: toastId });
         }
     };
 
     const extractItem = async (file: FileEntry) => {
         const toastId = toast.loading("Extracting...");
         try {
+            const operationId = newOperationId();
+            const unlisten = await listenOperation(operationId, (progress) => {
+                if (progress.state !== 'running') return;
+                toast.loading(`Extracting... ${progress.percentage}%`, { id: toastId });
+            });
             await invoke('extract_file', {
                 serverPath: path,
-                fileName: file.name
-            });
+                fileName: file.name,
+                operationId
+            }).finally(unlisten);
             toast.success("Extracted successfully!", { id: toastId });
             loadFiles();
             setContextMenu(null);
         } catch (e) {
-            toast.error("Extraction failed: " + e, { id: toastId });
+            toast.error("Extraction failed: " + errorMessage(e), { id: toastId });
+        }
+    };
+
+    const toggleExecutable = async (file: FileEntry) => {
+        const filePath = `${path}\\${file.name}`;
+        try {
+            const current = await invoke<{ supported: boolean; mode: number | null; executable: boolean }>('get_permissions', { path: filePath });
+            if (!current.supported || current.mode === null) {
+                toast.info("File permissions are not used on Windows");
+                setContextMenu(null);
+                return;
+            }
+            const mode = current.executable ? current.mode & ~0o111 : current.mode | 0o111;
+            await invoke('set_permissions', { path: filePath, mode });
+            toast.success(current.executable ? "Removed executable flag" : "Marked as executable");
+            setContextMenu(null);
+        } catch (e) {
+            toast.error("Failed to change permissions: " + errorMessage(e));
         }
     };
 
@@ -364,7 +479,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
             toast.success("Uploaded " + file.name, { id: toastId });
             loadFiles();
         } catch (err) {
-            toast.error("Upload failed: " + err, { id: toastId });
+            toast.error("Upload failed: " + errorMessage(err), { id: toastId });
         }
 
         // Reset
@@ -483,7 +598,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
                             Back
                         </button>
                         <button
-                            onClick={saveFile}
+                            onClick={() => saveFile()}
                             disabled={!hasUnsavedChanges || saving}
                             className="px-3 py-1.5 rounded-lg bg-primary hover:bg-primary/80 text-white text-xs font-bold transition-colors flex items-center gap-1.5 disabled:opacity-50"
                         >
@@ -500,7 +615,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
                         onChange={(newContent) => setEditingFile({ ...editingFile, content: newContent })}
                         language={
                             fileInfo.syntax === 'yaml' ? 'yaml' :
-                                fileInfo.syntax === 'json' ? 'json' :
+                                (fileInfo.syntax === 'json' || fileInfo.syntax === 'nbt') ? 'json' :
                                     fileInfo.syntax === 'properties' ? 'properties' :
                                         'text'
                         }
@@ -572,7 +687,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
                         <Package className="w-3.5 h-3.5" /> Archive (Zip)
                     </button>
 
-                    {(contextMenu.file.name.endsWith('.zip') || contextMenu.file.name.endsWith('.jar') || contextMenu.file.name.endsWith('.mcworld')) && (
+                    {/\.(zip|jar|mcworld|tar|tar\.gz|tgz|7z)$/i.test(contextMenu.file.name) && (
                         <button
                             onClick={(e) => { e.stopPropagation(); extractItem(contextMenu.file); }}
                             className="w-full text-left px-3 py-2 text-sm text-white hover:bg-white/5 flex items-center gap-2"
@@ -581,6 +696,15 @@ export function FileManager({ serverPath }: FileManagerProps) {
                         </button>
                     )}
 
+                    {!contextMenu.file.is_dir && (
+                        <button
+                            onClick={(e) => { e.stopPropagation(); toggleExecutable(contextMenu.file); }}
+                            className="w-full text-left px-3 py-2 text-sm text-white hover:bg-white/5 flex items-center gap-2"
+                        >
+                            <Settings2 className="w-3.5 h-3.5" /> Toggle Executable
+                        </button>
+                    )}
+
                     <div className="h-px bg-white/5 my-1" />
 
                     <button
@@ -764,7 +888,7 @@ export function FileManager({ serverPath }: FileManagerProps) {
                                                 {file.name}
                                             </p>
                                             <p className={cn("text-text-muted", viewMode === 'list' ? "text-[10px] flex items-center gap-2" : "text-[10px] mt-0.5")}>
-                                                {file.is_dir ? 'Folder' : formatFileSize(file.size)}
+                                                {file.is_dir ? (dirSizes[file.name] !== undefined ? formatFileSize(dirSizes[file.name]) : 'Folder') : formatFileSize(file.size)}
                                             </p>
                                         </div>
                                     </div>
@@ -4,6 +4,7 @@ import { Server } from '../../stores/appStore';
 import { Search, Download, RefreshCw, Package, Sparkles, Gamepad2, Wrench, Star, Box, Layers, Zap, Globe, Puzzle, ChevronLeft, ChevronRight, X } from 'lucide-react';
 import { toast } from 'sonner';
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
 
 interface ModManagerProps {
     server: Server;
@@ -230,7 +231,9 @@ export function ModManager({ server }: ModManagerProps) {
                 if (isPluginServer) {
                     await invoke('install_modrinth_plugin', {
                         serverPath: server.path,
-                        projectId: selectedAddon.project_id || selectedAddon.id
+                        projectId: selectedAddon.project_id || selectedAddon.id,
+                        platform: server.type,
+                        gameVersion: server.version
                     });
                 } else {
                     await invoke('install_modrinth_mod', {
@@ -249,7 +252,8 @@ export function ModManager({ server }: ModManagerProps) {
             } else if (selectedAddon.source === 'hangar') {
                 await invoke('install_hangar_plugin', {
                     serverPath: server.path,
-                    slug: selectedAddon.id
+                    slug: selectedAddon.id,
+                    platform: server.type
                 });
             } else if (selectedAddon.source === 'poggit') {
                 await invoke('install_poggit_plugin', {
@@ -261,7 +265,7 @@ export function ModManager({ server }: ModManagerProps) {
             toast.success(`Installed ${selectedAddon.title}`);
             setShowVersionModal(false);
         } catch (e) {
-            toast.error("Installation failed: " + e);
+            toast.error("Installation failed: " + errorMessage(e));
         } finally {
             setInstallingVersion(null);
         }
@@ -2,14 +2,42 @@ import { useState, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { useAppStore, Server } from '../../stores/appStore';
-import { Globe, Wifi, Shield, Copy, Check, AlertTriangle, RefreshCw, Activity, Zap, Pencil, Eye, EyeOff } from 'lucide-react';
+import { Globe, Wifi, Shield, Copy, Check, AlertTriangle, RefreshCw, Activity, Zap, Pencil, Eye, EyeOff, Mic } from 'lucide-react';
 import { toast } from 'sonner';
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
 
 interface NetworkManagerProps {
     server: Server;
 }
 
+interface NetworkSetupEvent {
+    serverId: string;
+    step: 'upnp' | 'firewall' | 'reachability';
+    success: boolean;
+    message: string;
+}
+
+interface NetworkStatus {
+    serverRunning: boolean;
+    tunnel: 'running' | 'restarting' | 'stopped';
+    tunnelAutostart: boolean;
+    tunnelRestarts: number;
+}
+
+interface VoiceChatStatus {
+    installed: boolean;
+    port: number | null;
+    listening: boolean;
+    message: string;
+}
+
+const SETUP_STEP_LABELS: Record<NetworkSetupEvent['step'], string> = {
+    upnp: 'Port forward',
+    firewall: 'Firewall',
+    reachability: 'Reachability',
+};
+
 export function NetworkManager({ server }: NetworkManagerProps) {
     const { updateServer } = useAppStore();
     const [lanIp, setLanIp] = useState("Loading...");
@@ -21,6 +49,10 @@ export function NetworkManager({ server }: NetworkManagerProps) {
     const [playitClaimUrl, setPlayitClaimUrl] = useState<string | null>(null);
     const [playitTunnelAddress, setPlayitTunnelAddress] = useState<string | null>(null);
     const [showFirewallModal, setShowFirewallModal] = useState(false);
+    const [setupSteps, setSetupSteps] = useState<NetworkSetupEvent[]>([]);
+    const [voiceStatus, setVoiceStatus] = useState<VoiceChatStatus | null>(null);
+    const [voiceSteps, setVoiceSteps] = useState<NetworkSetupEvent[]>([]);
+    const [isInstallingVoice, setIsInstallingVoice] = useState(false);
 
     // Add this ref for the input
     const inputRef = useRef<HTMLInputElement>(null);
@@ -29,8 +61,39 @@ export function NetworkManager({ server }: NetworkManagerProps) {
         // Initial Checks
         invoke<string>('get_local_ip').then(setLanIp).catch(() => setLanIp("Unknown"));
         checkConnection();
+        invoke<NetworkStatus>('get_network_status', { id: server.id }).then(status => {
+            if (status.tunnel === 'running') setPlayitStatus("Running");
+            else if (status.tunnel === 'restarting') setPlayitStatus(`Restarting (attempt ${status.tunnelRestarts})`);
+        }).catch(() => { });
     }, []);
 
+    const refreshVoiceStatus = () => {
+        invoke<VoiceChatStatus>('get_voice_chat_status', { serverPath: server.path }).then(setVoiceStatus).catch(() => { });
+    };
+
+    useEffect(() => {
+        if (server.type !== 'bedrock') refreshVoiceStatus();
+    }, [server.path]);
+
+    const installVoiceChat = async () => {
+        setIsInstallingVoice(true);
+        try {
+            const setup = await invoke<{ fileName: string; port: number; steps: NetworkSetupEvent[] }>('install_voice_chat', {
+                serverId: server.id,
+                serverPath: server.path,
+                platform: server.type,
+                gameVersion: server.version
+            });
+            setVoiceSteps(setup.steps);
+            toast.success(`Installed ${setup.fileName} on UDP port ${setup.port}. Restart the server to load it.`);
+            refreshVoiceStatus();
+        } catch (e) {
+            toast.error('Voice chat install failed: ' + errorMessage(e));
+        } finally {
+            setIsInstallingVoice(false);
+        }
+    };
+
     const checkConnection = async () => {
         setNetworkStatus('checking');
         try {
@@ -78,12 +141,28 @@ export function NetworkManager({ server }: NetworkManagerProps) {
             });
         }
         setup();
+        // The agent may have printed its claim link before this page opened
+        invoke<string | null>('get_tunnel_claim_url', { id: server.id })
+            .then(url => { if (url) setPlayitClaimUrl(url); })
+            .catch(() => { });
         return () => {
             if (unlistenLog) unlistenLog();
             if (unlistenClaim) unlistenClaim();
         };
     }, [server.id]);
 
+    // Results of the automatic network setup that runs when the server starts
+    useEffect(() => {
+        const unlisten = listen<NetworkSetupEvent>('network-setup', (e) => {
+            if (e.payload.serverId !== server.id) return;
+            setSetupSteps(prev => [...prev.filter(s => s.step !== e.payload.step), e.payload]);
+            if (!e.payload.success && e.payload.step !== 'reachability') {
+                toast.error(`${SETUP_STEP_LABELS[e.payload.step]} failed`, { description: e.payload.message });
+            }
+        });
+        return () => { unlisten.then(f => f()); };
+    }, [server.id]);
+
     const [guideMode, setGuideMode] = useState<'none' | 'playit' | 'upnp'>('none');
 
     const handlePlayitClick = () => {
@@ -106,16 +185,16 @@ export function NetworkManager({ server }: NetworkManagerProps) {
         setGuideMode('none');
         if (server.publicAccess === 'upnp') {
             try {
-                await invoke('upnp_remove_port', { port: server.port, protocol_str: server.type === 'bedrock' ? 'UDP' : 'TCP' });
+                await invoke('upnp_remove_port', { port: server.port, protocol_str: server.type === 'bedrock' ? 'UDP' : 'TCP', serverId: server.id });
                 updateServer(server.id, { publicAccess: 'none' });
                 setPublicIp(null); // Clear manual IP
                 toast.success("Public access disabled.");
             } catch (e) {
-                toast.error("Failed to unmap port: " + e);
+                toast.error("Failed to unmap port: " + errorMessage(e));
             }
         } else {
             try {
-                const ip = await invoke<string>('upnp_map_port', { port: server.port, protocol_str: server.type === 'bedrock' ? 'UDP' : 'TCP' });
+                const ip = await invoke<string>('upnp_map_port', { port: server.port, protocol_str: server.type === 'bedrock' ? 'UDP' : 'TCP', serverId: server.id });
                 setPublicIp(ip);
                 updateServer(server.id, { publicAccess: 'upnp' });
                 toast.success("Port forwarded successfully!", { description: `Public IP: ${ip}` });
@@ -148,7 +227,7 @@ export function NetworkManager({ server }: NetworkManagerProps) {
                 await invoke('start_playit_tunnel', { id: server.id, server_path: server.path });
                 updateServer(server.id, { publicAccess: 'playit' });
             } catch (e) {
-                toast.error("Failed to start Playit: " + e);
+                toast.error("Failed to start Playit: " + errorMessage(e));
                 setPlayitStatus("Failed");
             }
         }
@@ -166,7 +245,7 @@ export function NetworkManager({ server }: NetworkManagerProps) {
             setPlayitTunnelAddress(null);
             toast.success("Agent reset! Now click 'Start Tunnel' to claim a new one.");
         } catch (e) {
-            toast.error("Reset failed: " + e);
+            toast.error("Reset failed: " + errorMessage(e));
         }
     };
 
@@ -346,6 +425,32 @@ export function NetworkManager({ server }: NetworkManagerProps) {
                         </button>
                     </div>
 
+                    {/* Auto Network Setup */}
+                    <div className="mb-6 bg-black/20 border border-white/5 rounded-xl p-3 relative z-10">
+                        <label className="flex items-center justify-between gap-3 cursor-pointer">
+                            <div>
+                                <div className="text-sm font-bold text-white">Automatic setup on start</div>
+                                <div className="text-xs text-text-muted">Forward the port (UPnP), open the firewall and test the public address whenever the server starts.</div>
+                            </div>
+                            <input
+                                type="checkbox"
+                                className="w-4 h-4 accent-green-500"
+                                checked={!!server.autoNetworkSetup}
+                                onChange={(e) => updateServer(server.id, { autoNetworkSetup: e.target.checked })}
+                            />
+                        </label>
+                        {server.autoNetworkSetup && setupSteps.length > 0 && (
+                            <div className="mt-3 space-y-1">
+                                {setupSteps.map(step => (
+                                    <div key={step.step} className="flex items-start gap-2 text-xs">
+                                        {step.success ? <Check className="w-3 h-3 mt-0.5 text-green-400 shrink-0" /> : <AlertTriangle className="w-3 h-3 mt-0.5 text-yellow-400 shrink-0" />}
+                                        <span className="text-text-muted"><span className="font-bold text-white">{SETUP_STEP_LABELS[step.step]}:</span> {step.message}</span>
+                                    </div>
+                                ))}
+                            </div>
+                        )}
+                    </div>
+
                     {/* DYNAMIC DISPLAY AREA */}
                     {server.publicAccess !== 'none' && (
                         <div className="space-y-4 animate-in fade-in zoom-in-95 relative z-10">
@@ -463,6 +568,16 @@ export function NetworkManager({ server }: NetworkManagerProps) {
                                         </div>
                                     )}
 
+                                    <label className="flex items-center justify-between bg-black/30 p-2 rounded-lg mb-2 cursor-pointer">
+                                        <span className="text-xs text-text-muted">Start and stop with the server</span>
+                                        <input
+                                            type="checkbox"
+                                            className="w-4 h-4 accent-purple-500"
+                                            checked={!!server.tunnelAutostart}
+                                            onChange={(e) => updateServer(server.id, { tunnelAutostart: e.target.checked })}
+                                        />
+                                    </label>
+
                                     <button
                                         onClick={handleResetPlayit}
                                         className="w-full py-2 bg-yellow-500/5 hover:bg-yellow-500/10 border border-yellow-500/10 text-yellow-400/80 text-xs font-bold rounded-lg transition-colors flex items-center justify-center gap-2"
@@ -572,6 +687,46 @@ export function NetworkManager({ server }: NetworkManagerProps) {
                 </div>
             </div>
 
+            {/* Voice Chat (Simple Voice Chat) */}
+            {server.type !== 'bedrock' && (
+                <div className="bg-[#161b22] border border-border rounded-2xl p-6">
+                    <div className="flex items-center justify-between mb-4">
+                        <h3 className="text-lg font-bold text-white flex items-center gap-2">
+                            <Mic className="w-5 h-5 text-emerald-400" /> Voice Chat
+                        </h3>
+                        <div className="flex items-center gap-2">
+                            <button
+                                onClick={refreshVoiceStatus}
+                                className="p-2 rounded-lg hover:bg-white/5 text-text-muted hover:text-white transition-colors"
+                            >
+                                <RefreshCw className="w-4 h-4" />
+                            </button>
+                            <button
+                                onClick={installVoiceChat}
+                                disabled={isInstallingVoice}
+                                className="px-4 py-2 rounded-lg bg-emerald-500/10 hover:bg-emerald-500/20 text-emerald-400 text-sm font-bold transition-colors disabled:opacity-50"
+                            >
+                                {isInstallingVoice ? 'Installing...' : voiceStatus?.installed ? 'Reinstall & Open Port' : 'Install Simple Voice Chat'}
+                            </button>
+                        </div>
+                    </div>
+                    <p className={cn("text-sm", voiceStatus?.listening ? "text-emerald-400" : "text-text-muted")}>
+                        {voiceStatus?.message ?? 'Checking...'}
+                    </p>
+                    {voiceSteps.length > 0 && (
+                        <div className="mt-4 space-y-1.5">
+                            {voiceSteps.map(step => (
+                                <div key={step.step} className="flex items-center gap-2 text-xs">
+                                    {step.success ? <Check className="w-3.5 h-3.5 text-emerald-400" /> : <AlertTriangle className="w-3.5 h-3.5 text-yellow-400" />}
+                                    <span className="font-bold text-white">{SETUP_STEP_LABELS[step.step]}</span>
+                                    <span className="text-text-muted">{step.message}</span>
+                                </div>
+                            ))}
+                        </div>
+                    )}
+                </div>
+            )}
+
             {/* Firewall Modal */}
             {
                 showFirewallModal && (
@@ -9,6 +9,7 @@ import { toast } from 'sonner';
 import { invoke } from '@tauri-apps/api/core';
 import { cn } from '../../lib/utils';
 import { ServerType } from '../../stores/appStore';
+import { errorMessage } from '../../lib/errors';
 
 interface PlayerManagerProps {
     sendCommand: (e?: React.FormEvent, cmdStr?: string) => Promise<void>;
@@ -115,8 +116,13 @@ export function PlayerManager({
             const wlFile = isBedrock ? 'allowlist.json' : 'whitelist.json';
 
             const [opsData, wlData, banData, ipData, cacheData] = await Promise.all([
-                loadFile(opFile),
-                loadFile(wlFile),
+                // Backend fills in gamertags for XUIDs it has seen join
+                isBedrock
+                    ? invoke<any[]>('get_bedrock_permissions', { serverPath }).catch(() => [])
+                    : loadFile(opFile),
+                isBedrock
+                    ? invoke<any[]>('get_bedrock_allowlist', { serverPath }).catch(() => [])
+                    : loadFile(wlFile),
                 loadFile('banned-players.json'),
                 loadFile('banned-ips.json'),
                 loadFile('usercache.json')
@@ -214,7 +220,7 @@ export function PlayerManager({
             await invoke('delete_file', { path: `${serverPath}\\${filePath}` });
             toast.success(`Deleted ${type} for ${selectedPlayer}`);
         } catch (e) {
-            toast.error(`Failed to delete file: ${e}`);
+            toast.error(`Failed to delete file: ${errorMessage(e)}`);
         }
     };
 
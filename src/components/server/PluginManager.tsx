@@ -4,6 +4,7 @@ import { Server } from '../../stores/appStore';
 import { toast } from 'sonner';
 import { Search, Download, RefreshCw, X, ChevronLeft, ChevronRight, ExternalLink, Puzzle, Sparkles, Trash2, Star, Shield, Gamepad2, Wrench, MessageSquare, Zap } from "lucide-react";
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
 
 interface PluginManagerProps {
     server: Server;
@@ -16,7 +17,7 @@ interface PluginResult {
     description: string;
     downloads: number;
     icon_url: string | null;
-    source: 'modrinth' | 'hangar' | 'spigot' | 'polymart' | 'poggit';
+    source: 'modrinth' | 'hangar' | 'spigot' | 'polymart' | 'poggit' | 'ore';
 }
 
 interface VersionInfo {
@@ -37,6 +38,12 @@ const JAVA_SOURCES = [
     { id: 'polymart', name: 'Polymart', color: 'from-purple-500 to-indigo-500', bgColor: 'bg-purple-500' },
 ];
 
+// Sponge runs neither Bukkit nor Paper plugins; they come from Ore or Modrinth's Sponge loader
+const SPONGE_SOURCES = [
+    { id: 'modrinth', name: 'Modrinth', color: 'from-green-500 to-emerald-500', bgColor: 'bg-green-500' },
+    { id: 'ore', name: 'Ore', color: 'from-yellow-500 to-amber-600', bgColor: 'bg-yellow-500' },
+];
+
 const CATEGORIES = [
     { id: 'popular', label: 'Trending', icon: Sparkles, query: '', color: 'from-yellow-500 to-orange-500' },
     { id: 'essentials', label: 'Essentials', icon: Star, query: 'essentials', color: 'from-blue-500 to-cyan-500' },
@@ -51,6 +58,8 @@ const CATEGORIES = [
 // Supports Store (Modrinth/Hangar/Spigot) and Installed Plugins
 
 export function PluginManager({ server }: PluginManagerProps) {
+    const isSponge = ['spongevanilla', 'spongeforge'].includes(server.type);
+    const sources = isSponge ? SPONGE_SOURCES : JAVA_SOURCES;
     const [activeTab, setActiveTab] = useState<'store' | 'installed'>('store');
 
     // Store State
@@ -62,7 +71,7 @@ export function PluginManager({ server }: PluginManagerProps) {
     const [installingPlugin, setInstallingPlugin] = useState<string | null>(null);
 
     const [installedSearch, setInstalledSearch] = useState("");
-    const [installedPlugins, setInstalledPlugins] = useState<{ name: string, filename: string, enabled: boolean, size: number }[]>([]);
+    const [installedPlugins, setInstalledPlugins] = useState<{ name: string, filename: string, enabled: boolean, size: number, stagedUpdate: boolean }[]>([]);
     const [loadingInstalled, setLoadingInstalled] = useState(false);
 
     // Version selection modal
@@ -89,10 +98,10 @@ export function PluginManager({ server }: PluginManagerProps) {
     const loadInstalled = async () => {
         setLoadingInstalled(true);
         try {
-            const list = await invoke<{ name: string, filename: string, enabled: boolean, size: number }[]>('list_plugins', { serverPath: server.path });
+            const list = await invoke<{ name: string, filename: string, enabled: boolean, size: number, stagedUpdate: boolean }[]>('list_plugins', { serverPath: server.path });
             setInstalledPlugins(list);
         } catch (e) {
-            toast.error("Failed to load installed plugins: " + e);
+            toast.error("Failed to load installed plugins: " + errorMessage(e));
         } finally {
             setLoadingInstalled(false);
         }
@@ -102,7 +111,7 @@ export function PluginManager({ server }: PluginManagerProps) {
     useEffect(() => {
         if (activeTab === 'store') {
             // If cache is empty for a source, fetch it
-            JAVA_SOURCES.forEach(source => {
+            sources.forEach(source => {
                 if (!cachedResults[source.id]) {
                     loadSourceData(source.id, searchQuery || (activeCategory === 'popular' ? '' : activeCategory), 1);
                 }
@@ -165,6 +174,13 @@ export function PluginManager({ server }: PluginManagerProps) {
             } else if (source === 'polymart') {
                 results = await invoke<PluginResult[]>('search_polymart_plugins', { query: effectiveQuery || 'plugin', page: page });
                 totalHits = 50; // Mock total for Polymart
+            } else if (source === 'ore') {
+                const oreResults = await invoke<{ items: PluginResult[]; total: number }>('search_ore_plugins', {
+                    query: effectiveQuery,
+                    offset: (page - 1) * pluginsPerPage
+                });
+                results = oreResults.items;
+                totalHits = oreResults.total;
             }
 
             updateCache(source, results, totalHits);
@@ -172,7 +188,8 @@ export function PluginManager({ server }: PluginManagerProps) {
             // If this is the active source, ensure pagination state is correct
             if (source === activeSource) {
                 // For Spigot/Others fixed pages
-                if (source !== 'modrinth') setTotalPages(5);
+                if (source === 'ore') setTotalPages(Math.max(1, Math.ceil(totalHits / pluginsPerPage)));
+                else if (source !== 'modrinth') setTotalPages(5);
             }
 
         } catch (e) {
@@ -194,7 +211,7 @@ export function PluginManager({ server }: PluginManagerProps) {
             toast.success("Plugin deleted.");
             loadInstalled();
         } catch (e) {
-            toast.error("Delete failed: " + e);
+            toast.error("Delete failed: " + errorMessage(e));
         }
     };
 
@@ -204,7 +221,17 @@ export function PluginManager({ server }: PluginManagerProps) {
             loadInstalled();
             toast.success("Plugin status updated");
         } catch (e) {
-            toast.error("Failed to toggle plugin: " + e);
+            toast.error("Failed to toggle plugin: " + errorMessage(e));
+        }
+    };
+
+    const handleCancelStagedUpdate = async (filename: string) => {
+        try {
+            await invoke('cancel_staged_plugin_update', { serverPath: server.path, filename });
+            loadInstalled();
+            toast.success("Staged update removed");
+        } catch (e) {
+            toast.error("Failed to remove staged update: " + errorMessage(e));
         }
     };
 
@@ -254,7 +281,7 @@ export function PluginManager({ server }: PluginManagerProps) {
         }
     };
 
-    const installVersion = async (_version: VersionInfo) => {
+    const installVersion = async (version: VersionInfo) => {
         if (!selectedPlugin) return;
         setInstallingPlugin(selectedPlugin.id);
         setShowVersionModal(false);
@@ -269,19 +296,36 @@ export function PluginManager({ server }: PluginManagerProps) {
                         gameVersion: server.version
                     });
                 } else {
-                    await invoke('install_modrinth_plugin', { projectId: selectedPlugin.id, serverPath: server.path });
+                    await invoke('install_modrinth_plugin', {
+                        projectId: selectedPlugin.id,
+                        serverPath: server.path,
+                        platform: server.type,
+                        gameVersion: server.version
+                    });
                 }
             } else if (selectedPlugin.source === 'hangar') {
-                await invoke('install_hangar_plugin', { slug: selectedPlugin.slug, serverPath: server.path });
+                await invoke('install_hangar_plugin', {
+                    slug: selectedPlugin.slug,
+                    serverPath: server.path,
+                    platform: server.type,
+                    version: version.name
+                });
             } else if (selectedPlugin.source === 'spigot') {
                 await invoke('install_spigot_plugin', { resourceId: selectedPlugin.id, serverPath: server.path });
             } else if (selectedPlugin.source === 'polymart') {
                 await invoke('install_polymart_plugin', { resourceId: selectedPlugin.id, serverPath: server.path });
+            } else if (selectedPlugin.source === 'ore') {
+                await invoke('install_ore_plugin', {
+                    pluginId: selectedPlugin.id,
+                    serverPath: server.path,
+                    platform: server.type,
+                    gameVersion: server.version
+                });
             }
             toast.success(`${selectedPlugin.title} installed!`);
         } catch (e) {
             console.error(e);
-            toast.error('Install failed: ' + e);
+            toast.error('Install failed: ' + errorMessage(e));
         } finally {
             setInstallingPlugin(null);
             setSelectedPlugin(null);
@@ -300,6 +344,7 @@ export function PluginManager({ server }: PluginManagerProps) {
             case 'hangar': return `https://hangar.papermc.io/${plugin.slug}`;
             case 'spigot': return `https://www.spigotmc.org/resources/${plugin.id}`;
             case 'polymart': return `https://polymart.org/resource/${plugin.id}`;
+            case 'ore': return `https://ore.spongepowered.org/${plugin.slug}`;
             default: return '#';
         }
     };
@@ -376,7 +421,7 @@ export function PluginManager({ server }: PluginManagerProps) {
                     <>
                         {/* Source Tabs */}
                         <div className="flex gap-2 mb-6 p-1.5 bg-black/40 rounded-2xl border border-white/5">
-                            {JAVA_SOURCES.map((source) => (
+                            {sources.map((source) => (
                                 <button key={source.id} onClick={() => { setActiveSource(source.id); setCurrentPage(1); }}
                                     className={cn("flex-1 py-2.5 rounded-xl text-xs font-bold uppercase tracking-wider transition-all duration-300 relative overflow-hidden group",
                                         activeSource === source.id ? "text-white shadow-lg" : "text-text-muted hover:text-white hover:bg-white/5")}
@@ -466,6 +511,7 @@ export function PluginManager({ server }: PluginManagerProps) {
                                                         {plugin.source === 'spigot' && <div className="w-2 h-2 rounded-full bg-orange-500 shadow-[0_0_8px_rgba(249,115,22,0.5)]" />}
                                                         {plugin.source === 'hangar' && <div className="w-2 h-2 rounded-full bg-blue-500 shadow-[0_0_8px_rgba(59,130,246,0.5)]" />}
                                                         {plugin.source === 'polymart' && <div className="w-2 h-2 rounded-full bg-purple-500 shadow-[0_0_8px_rgba(168,85,247,0.5)]" />}
+                                                        {plugin.source === 'ore' && <div className="w-2 h-2 rounded-full bg-yellow-500 shadow-[0_0_8px_rgba(234,179,8,0.5)]" />}
                                                     </div>
                                                 </div>
                                                 <p className="text-xs text-text-muted line-clamp-2 mt-1.5 leading-relaxed opacity-80 h-8">{plugin.description}</p>
@@ -544,6 +590,15 @@ export function PluginManager({ server }: PluginManagerProps) {
                                                 <div className="flex items-center gap-3">
                                                     <h4 className={cn("font-bold text-sm truncate", p.enabled ? "text-white group-hover:text-purple-300 transition-colors" : "text-text-muted line-through opacity-80 decoration-2 decoration-red-500/50")}>{p.name}</h4>
                                                     {!p.enabled && <span className="text-[10px] font-bold px-2 py-0.5 rounded bg-red-500/20 text-red-400 border border-red-500/30 uppercase tracking-wider">Disabled</span>}
+                                                    {p.stagedUpdate && (
+                                                        <button
+                                                            onClick={() => handleCancelStagedUpdate(p.filename)}
+                                                            title="Applied on the next restart. Click to remove the staged jar."
+                                                            className="text-[10px] font-bold px-2 py-0.5 rounded bg-blue-500/20 text-blue-400 border border-blue-500/30 uppercase tracking-wider hover:bg-blue-500/30"
+                                                        >
+                                                            Update staged
+                                                        </button>
+                                                    )}
                                                 </div>
                                                 <div className="flex items-center gap-3 mt-1.5 text-xs text-text-muted font-mono">
                                                     <span className="truncate max-w-[300px]" title={p.filename}>{p.filename}</span>
@@ -8,6 +8,7 @@ import { toast } from 'sonner';
 import { invoke } from '@tauri-apps/api/core';
 import { cn } from '../../lib/utils';
 import { ServerType } from '../../stores/appStore';
+import { errorMessage } from '../../lib/errors';
 
 interface PropertiesEditorProps {
     serverPath: string;
@@ -15,6 +16,12 @@ interface PropertiesEditorProps {
     serverName: string;
 }
 
+interface PropertiesUpdate {
+    running: boolean;
+    applied: string[];
+    restartRequired: string[];
+}
+
 interface PropertyField {
     key: string;
     label: string;
@@ -411,21 +418,28 @@ export function PropertiesEditor({ serverPath, serverType, serverName }: Propert
     async function saveAllProperties() {
         setSaving(true);
         try {
-            let content = `#Minecraft ${isBedrock ? 'Bedrock' : 'Java'} server properties\n#Modified by Mineserver\n`;
+            const changed: Record<string, string> = {};
             Object.entries(properties).forEach(([k, v]) => {
+                if (originalProperties[k] === v) return;
                 // Escape newlines for proper file formatting
                 // We do NOT escape backslashes automatically to allow manual unicode escapes (like \u00A7)
-                const escapedValue = String(v)
+                changed[k] = String(v)
                     .replace(/\r/g, '')
                     .replace(/\n/g, '\\n');
-                content += `${k}=${escapedValue}\n`;
             });
 
-            await invoke('write_server_file', { path: `${serverPath}\\server.properties`, content });
+            // A running server gets what it can apply live (difficulty, whitelist, ...) as commands
+            const result = await invoke<PropertiesUpdate>('update_server_properties', { serverPath, properties: changed, applyLive: true });
             setOriginalProperties({ ...properties });
-            toast.success("Settings saved! Restart server to apply.");
+            if (!result.running) {
+                toast.success("Settings saved! Restart server to apply.");
+            } else if (result.restartRequired.length === 0) {
+                toast.success("Settings saved and applied to the running server.");
+            } else {
+                toast.warning(`Saved. The server is running: ${result.restartRequired.join(', ')} apply after a restart.`);
+            }
         } catch (err) {
-            toast.error("Failed to save: " + err);
+            toast.error("Failed to save: " + errorMessage(err));
         } finally {
             setSaving(false);
         }
@@ -563,7 +577,7 @@ export function PropertiesEditor({ serverPath, serverType, serverName }: Propert
                                     });
                                     toast.success("Server icon updated!", { id: 'icon-upload' });
                                 } catch (err) {
-                                    toast.error("Failed to upload: " + err, { id: 'icon-upload' });
+                                    toast.error("Failed to upload: " + errorMessage(err), { id: 'icon-upload' });
                                 }
                             }}
                         />
@@ -4,15 +4,55 @@ import { Shield, ShieldAlert, Lock, Activity, AlertTriangle, Zap, Check, Flame }
 import { toast } from 'sonner';
 import { cn } from '../../lib/utils';
 import { invoke } from '@tauri-apps/api/core';
+import { errorMessage } from '../../lib/errors';
 
 interface SecurityManagerProps {
     server: Server;
 }
 
+interface AuthIssue {
+    severity: 'error' | 'warning' | 'info';
+    code: string;
+    server: string;
+    message: string;
+    fix: string;
+}
+
+interface AuthReport {
+    role: 'proxy' | 'backend' | 'standalone';
+    onlineMode: boolean | null;
+    forwarding: string | null;
+    related: string[];
+    issues: AuthIssue[];
+}
+
+interface BackendPlayers {
+    id: string;
+    name: string;
+    proxyName: string;
+    port: number;
+    online: number | null;
+    players: string[];
+    source: 'console' | 'query' | 'ping' | 'offline';
+}
+
+interface NetworkPlayers {
+    proxy: string;
+    proxyOnline: boolean;
+    online: number;
+    maxPlayers: number;
+    backends: BackendPlayers[];
+    unattributed: number;
+    unattributedPlayers: string[];
+}
+
 export function SecurityManager({ server }: SecurityManagerProps) {
     const { updateServer } = useAppStore();
     const [panicMode, setPanicMode] = useState(false);
     const [firewallStatus, setFirewallStatus] = useState<'active' | 'inactive' | 'unknown'>('unknown');
+    const [authReport, setAuthReport] = useState<AuthReport | null>(null);
+    const [isCheckingAuth, setIsCheckingAuth] = useState(false);
+    const [networkPlayers, setNetworkPlayers] = useState<NetworkPlayers | null>(null);
 
     useEffect(() => {
         checkFirewall();
@@ -27,14 +67,30 @@ export function SecurityManager({ server }: SecurityManagerProps) {
         }
     };
 
+    const handleDiagnoseAuth = async () => {
+        setIsCheckingAuth(true);
+        try {
+            const report = await invoke<AuthReport>('diagnose_auth', { serverPath: server.path });
+            setAuthReport(report);
+            // Proxies also show where the network's players are
+            setNetworkPlayers(report.role === 'proxy'
+                ? await invoke<NetworkPlayers>('get_network_players', { groupId: server.id }).catch(() => null)
+                : null);
+        } catch (e) {
+            toast.error("Authentication check failed: " + errorMessage(e));
+        } finally {
+            setIsCheckingAuth(false);
+        }
+    };
+
     const handleFixFirewall = async () => {
         const toastId = toast.loading("Configuring Windows Firewall...");
         try {
-            await invoke('add_firewall_rule', { port: server.port });
+            await invoke('add_firewall_rule', { port: server.port, serverId: server.id });
             toast.success("Firewall rule added successfully!", { id: toastId });
             checkFirewall();
         } catch (e: any) {
-            toast.error("Failed to add firewall rule: " + e, { id: toastId });
+            toast.error("Failed to add firewall rule: " + errorMessage(e), { id: toastId });
         }
     };
 
@@ -49,7 +105,7 @@ export function SecurityManager({ server }: SecurityManagerProps) {
             toast.success(newState ? "Tunnel Guard ACTIVE: Direct connections blocked." : "Tunnel Guard Disabled.");
         } catch (e) {
             updateServer(server.id, { tunnelGuard: !newState }); // Revert
-            toast.error("Failed to toggle Tunnel Guard via Firewall: " + e);
+            toast.error("Failed to toggle Tunnel Guard via Firewall: " + errorMessage(e));
         }
     };
 
@@ -59,7 +115,7 @@ export function SecurityManager({ server }: SecurityManagerProps) {
             await invoke('install_grimac', { serverPath: server.path });
             toast.success("GrimAC Installed! Restart server to apply.", { id: toastId });
         } catch (e) {
-            toast.error("Installation Failed: " + e, { id: toastId });
+            toast.error("Installation Failed: " + errorMessage(e), { id: toastId });
         }
     };
 
@@ -87,7 +143,7 @@ export function SecurityManager({ server }: SecurityManagerProps) {
                 toast.warning("Server is not running. Commands queued.");
             }
         } catch (e) {
-            toast.error("Panic Failed: " + e);
+            toast.error("Panic Failed: " + errorMessage(e));
         } finally {
             setTimeout(() => setPanicMode(false), 3000);
         }
@@ -235,6 +291,78 @@ export function SecurityManager({ server }: SecurityManagerProps) {
                 </div>
             </div>
 
+            {/* Online mode & proxy forwarding */}
+            {server.type !== 'bedrock' && (
+                <div className="bg-[#161b22] border border-border rounded-2xl p-6">
+                    <div className="flex items-center justify-between mb-4">
+                        <h3 className="font-bold text-white flex items-center gap-2">
+                            <Lock className="w-5 h-5 text-blue-400" /> Login & Proxy Check
+                        </h3>
+                        <button
+                            onClick={handleDiagnoseAuth}
+                            disabled={isCheckingAuth}
+                            className="px-4 py-2 bg-white/5 hover:bg-white/10 text-white text-sm font-bold rounded-lg border border-white/10 transition-colors disabled:opacity-50"
+                        >
+                            {isCheckingAuth ? 'Checking...' : 'Run Check'}
+                        </button>
+                    </div>
+                    {!authReport ? (
+                        <p className="text-sm text-text-muted">
+                            Looks for online-mode and BungeeCord/Velocity forwarding mistakes that cause "invalid session" kicks and UUID mismatches.
+                        </p>
+                    ) : (
+                        <div className="space-y-3">
+                            <p className="text-xs text-text-muted">
+                                {authReport.role === 'proxy' ? 'Proxy' : authReport.role === 'backend' ? 'Backend server' : 'Standalone server'}
+                                {authReport.forwarding && <> &middot; forwarding: {authReport.forwarding}</>}
+                                {authReport.related.length > 0 && <> &middot; {authReport.role === 'proxy' ? 'backends' : 'behind'}: {authReport.related.join(', ')}</>}
+                            </p>
+                            {authReport.issues.length === 0 ? (
+                                <div className="flex items-center gap-2 text-sm text-green-400">
+                                    <Check className="w-4 h-4" /> No problems found
+                                </div>
+                            ) : authReport.issues.map((issue, i) => (
+                                <div key={i} className={cn(
+                                    "p-3 rounded-lg border text-sm",
+                                    issue.severity === 'error' ? "bg-red-500/10 border-red-500/20" :
+                                        issue.severity === 'warning' ? "bg-yellow-500/10 border-yellow-500/20" : "bg-blue-500/10 border-blue-500/20"
+                                )}>
+                                    <p className="text-white flex items-start gap-2">
+                                        <AlertTriangle className={cn("w-4 h-4 shrink-0 mt-0.5",
+                                            issue.severity === 'error' ? "text-red-400" : issue.severity === 'warning' ? "text-yellow-400" : "text-blue-400")} />
+                                        {issue.message}
+                                    </p>
+                                    <p className="text-xs text-text-muted mt-1 ml-6">{issue.fix}</p>
+                                </div>
+                            ))}
+                            {networkPlayers && (
+                                <div className="pt-3 border-t border-border/30 space-y-2">
+                                    <p className="text-xs font-bold text-text-muted uppercase tracking-wider">
+                                        Network players: {networkPlayers.online}{networkPlayers.maxPlayers > 0 && ` / ${networkPlayers.maxPlayers}`}
+                                        {!networkPlayers.proxyOnline && ' (proxy offline, backend total)'}
+                                    </p>
+                                    {networkPlayers.backends.map(b => (
+                                        <div key={b.id} className="flex items-start justify-between gap-3 text-sm">
+                                            <span className="text-white">{b.name} <span className="text-xs text-text-muted">({b.proxyName})</span></span>
+                                            <span className="text-xs text-text-muted text-right">
+                                                {b.online === null ? 'offline' : `${b.online} online`}
+                                                {b.players.length > 0 && <> &middot; {b.players.join(', ')}</>}
+                                            </span>
+                                        </div>
+                                    ))}
+                                    {networkPlayers.unattributed > 0 && (
+                                        <p className="text-xs text-text-muted">
+                                            {networkPlayers.unattributed} not matched to a backend
+                                            {networkPlayers.unattributedPlayers.length > 0 && `: ${networkPlayers.unattributedPlayers.join(', ')}`}
+                                        </p>
+                                    )}
+                                </div>
+                            )}
+                        </div>
+                    )}
+                </div>
+            )}
+
             {/* Anti-Bot Section */}
             {/* Platform Specific Modules */}
             {['paper', 'spigot', 'purpur', 'vanilla', 'forge', 'fabric'].includes(server.type) && (
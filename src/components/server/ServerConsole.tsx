@@ -3,6 +3,7 @@ import { Terminal, Trash2, ArrowDown, X, Search, Eye, EyeOff, Download, Power, S
 import { toast } from 'sonner';
 import { cn } from '../../lib/utils';
 import { invoke } from '@tauri-apps/api/core';
+import { errorMessage } from '../../lib/errors';
 
 interface ServerConsoleProps {
     logs: string[];
@@ -117,7 +118,7 @@ export function ServerConsole({ logs, setLogs, isRunning, onSendCommand, hideIp,
         } catch (e) {
             console.error(e);
             setLogs([]);
-            toast.error("Failed to clear backend logs: " + e);
+            toast.error("Failed to clear backend logs: " + errorMessage(e));
         }
         setAutoScroll(true);
     };
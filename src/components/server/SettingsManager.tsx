@@ -1,19 +1,62 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useAppStore, Server } from '../../stores/appStore';
 import {
     Trash2, Edit2, FolderOpen, MemoryStick, Cpu, Save,
     RefreshCw, Download, Shield, Palette, Settings,
-    ChevronRight, Database, Power, Globe, Copy, Check, Image as ImageIcon, Upload
+    ChevronRight, Database, Power, Globe, Copy, Check, Image as ImageIcon, Upload, History
 } from 'lucide-react';
 import { toast } from 'sonner';
 import { invoke } from '@tauri-apps/api/core';
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
 
 interface SettingsManagerProps {
     server: Server;
 }
 
+interface MotdSegment {
+    text: string;
+    color: string | null;
+    bold: boolean;
+    italic: boolean;
+    underlined: boolean;
+    strikethrough: boolean;
+    obfuscated: boolean;
+}
+
+interface MotdPreview {
+    source: 'ping' | 'files';
+    segments: MotdSegment[];
+    favicon: string | null;
+    onlinePlayers: number | null;
+    maxPlayers: number | null;
+    version: string | null;
+}
+
+interface ChangelogEntry {
+    timestamp: string;
+    kind: string;
+    summary: string;
+    author: string | null;
+}
+
+interface ServerHistory {
+    notes: string;
+    changelog: ChangelogEntry[];
+}
+
+const CHANGE_KIND_LABELS: Record<string, string> = {
+    server_download: 'Server jar',
+    plugin_install: 'Plugin',
+    plugin_update: 'Plugin update',
+    mod_install: 'Mod',
+    mod_update: 'Mod update',
+    world_import: 'World',
+    restore: 'Restore',
+    note: 'Note',
+};
+
 const SERVER_ICONS = [
     { id: 'default', emoji: '🎮', label: 'Default' },
     { id: 'sword', emoji: '⚔️', label: 'PvP' },
@@ -37,6 +80,15 @@ export function SettingsManager({ server }: SettingsManagerProps) {
     const [isDeleting, setIsDeleting] = useState(false);
     const [isSaving, setIsSaving] = useState(false);
     const [copiedPath, setCopiedPath] = useState(false);
+    const [motdPreview, setMotdPreview] = useState<MotdPreview | null>(null);
+
+    const loadMotdPreview = () => {
+        invoke<MotdPreview>('get_motd_preview', { id: server.id })
+            .then(setMotdPreview)
+            .catch(() => setMotdPreview(null));
+    };
+
+    useEffect(loadMotdPreview, [server.id]);
 
     const hasChanges = name !== server.name ||
         allocatedRam !== server.allocatedRam ||
@@ -59,7 +111,7 @@ export function SettingsManager({ server }: SettingsManagerProps) {
             });
             toast.success("Settings saved!");
         } catch (e) {
-            toast.error("Failed to save: " + e);
+            toast.error("Failed to save: " + errorMessage(e));
         } finally {
             setIsSaving(false);
         }
@@ -74,7 +126,7 @@ export function SettingsManager({ server }: SettingsManagerProps) {
             toast.success("Server deleted.");
             navigate('/servers');
         } catch (e) {
-            toast.error("Failed to delete: " + e);
+            toast.error("Failed to delete: " + errorMessage(e));
             setIsDeleting(false);
         }
     };
@@ -89,7 +141,7 @@ export function SettingsManager({ server }: SettingsManagerProps) {
             });
             toast.success("Backup created!", { id: 'backup' });
         } catch (e) {
-            toast.error("Backup failed: " + e, { id: 'backup' });
+            toast.error("Backup failed: " + errorMessage(e), { id: 'backup' });
         }
     };
 
@@ -244,10 +296,9 @@ export function SettingsManager({ server }: SettingsManagerProps) {
                     <div className="p-5 space-y-4">
                         <div className="flex items-start gap-4 p-4 bg-black/20 rounded-xl border border-border/50">
                             <div className="w-16 h-16 bg-[#0d1117] rounded-none border border-border flex items-center justify-center relative group overflow-hidden">
-                                {/* We would ideally load the actual file here, but for now safe placeholder or try cache-busting url */}
                                 <img
-                                    src="https://api.mineskin.org/render/head?url=http://textures.minecraft.net/texture/292009a4925b58f02c77d69bf69792a105ce624692e4065292437367ce7a5"
-                                    className="w-full h-full object-cover opacity-50"
+                                    src={motdPreview?.favicon ?? "https://api.mineskin.org/render/head?url=http://textures.minecraft.net/texture/292009a4925b58f02c77d69bf69792a105ce624692e4065292437367ce7a5"}
+                                    className={cn("w-full h-full object-cover", !motdPreview?.favicon && "opacity-50")}
                                     alt="Server Icon"
                                 />
                                 <div className="absolute inset-0 flex items-center justify-center bg-black/50 opacity-0 group-hover:opacity-100 transition-opacity">
@@ -279,8 +330,9 @@ export function SettingsManager({ server }: SettingsManagerProps) {
                                                 content: Array.from(new Uint8Array(buf))
                                             });
                                             toast.success("Server icon updated! Restart to see changes.", { id: 'icon' });
+                                            loadMotdPreview();
                                         } catch (err) {
-                                            toast.error("Failed to upload: " + err, { id: 'icon' });
+                                            toast.error("Failed to upload: " + errorMessage(err), { id: 'icon' });
                                         }
                                     }}
                                 />
@@ -300,6 +352,49 @@ export function SettingsManager({ server }: SettingsManagerProps) {
                                 Browse...
                             </button>
                         </div>
+
+                        {/* Multiplayer list preview */}
+                        {motdPreview && (
+                            <div className="flex items-start gap-3 p-3 bg-[#0d0d0d] rounded-xl border border-border/50 font-mono">
+                                {motdPreview.favicon ? (
+                                    <img src={motdPreview.favicon} className="w-12 h-12 [image-rendering:pixelated]" alt="" />
+                                ) : (
+                                    <div className="w-12 h-12 bg-white/5" />
+                                )}
+                                <div className="flex-1 min-w-0">
+                                    <div className="flex items-center justify-between text-sm">
+                                        <span className="text-white">{server.name}</span>
+                                        <span className="text-[#AAAAAA] text-xs">
+                                            {motdPreview.onlinePlayers ?? 0}/{motdPreview.maxPlayers ?? server.maxPlayers}
+                                        </span>
+                                    </div>
+                                    <p className="text-sm text-[#AAAAAA] whitespace-pre-wrap leading-tight mt-0.5">
+                                        {motdPreview.segments.map((seg, i) => (
+                                            <span
+                                                key={i}
+                                                style={{ color: seg.color ?? undefined }}
+                                                className={cn(
+                                                    seg.bold && "font-bold",
+                                                    seg.italic && "italic",
+                                                    seg.underlined && "underline",
+                                                    seg.strikethrough && "line-through",
+                                                    seg.obfuscated && "blur-[2px]"
+                                                )}
+                                            >
+                                                {seg.text}
+                                            </span>
+                                        ))}
+                                    </p>
+                                </div>
+                            </div>
+                        )}
+                        {motdPreview && (
+                            <p className="text-[10px] text-text-muted -mt-2">
+                                {motdPreview.source === 'ping'
+                                    ? "Live from the running server."
+                                    : "From server.properties and server-icon.png. Start the server for a live preview."}
+                            </p>
+                        )}
                     </div>
                 </div>
 
@@ -421,6 +516,8 @@ export function SettingsManager({ server }: SettingsManagerProps) {
                     </div>
                 </div>
 
+                <NotesSection serverId={server.id} />
+
                 {/* Quick Actions */}
                 <div className="bg-surface/50 border border-border/50 rounded-2xl overflow-hidden">
                     <div className="px-5 py-4 border-b border-border/50 flex items-center gap-3">
@@ -494,3 +591,110 @@ export function SettingsManager({ server }: SettingsManagerProps) {
         </div>
     );
 }
+
+function NotesSection({ serverId }: { serverId: string }) {
+    const [history, setHistory] = useState<ServerHistory | null>(null);
+    const [notes, setNotes] = useState('');
+    const [entry, setEntry] = useState('');
+    const [author, setAuthor] = useState('');
+
+    const load = () => {
+        invoke<ServerHistory>('get_server_history', { id: serverId })
+            .then(h => { setHistory(h); setNotes(h.notes); })
+            .catch(() => setHistory(null));
+    };
+
+    useEffect(load, [serverId]);
+
+    const handleSaveNotes = async () => {
+        try {
+            await invoke('set_server_notes', { id: serverId, notes });
+            toast.success("Notes saved");
+            load();
+        } catch (e) {
+            toast.error("Failed to save notes: " + errorMessage(e));
+        }
+    };
+
+    const handleAddEntry = async () => {
+        if (!entry.trim()) return;
+        try {
+            await invoke('add_changelog_entry', { id: serverId, summary: entry, author: author || null });
+            setEntry('');
+            load();
+        } catch (e) {
+            toast.error("Failed to add entry: " + errorMessage(e));
+        }
+    };
+
+    // Servers created before the registry existed have no history
+    if (!history) return null;
+
+    return (
+        <div className="bg-surface/50 border border-border/50 rounded-2xl overflow-hidden">
+            <div className="px-5 py-4 border-b border-border/50 flex items-center gap-3">
+                <History className="w-5 h-5 text-amber-400" />
+                <h3 className="font-bold text-white">Notes & History</h3>
+            </div>
+            <div className="p-5 space-y-4">
+                <div>
+                    <textarea
+                        value={notes}
+                        onChange={e => setNotes(e.target.value)}
+                        rows={4}
+                        placeholder="Anything other admins should know about this server..."
+                        className="w-full bg-black/20 border border-border/50 rounded-xl p-3 text-sm text-white focus:outline-none focus:border-primary/50 resize-y"
+                    />
+                    {notes !== history.notes && (
+                        <button
+                            onClick={handleSaveNotes}
+                            className="mt-2 px-4 py-2 bg-primary text-black font-bold rounded-lg text-sm hover:bg-primary/90 transition-colors flex items-center gap-2"
+                        >
+                            <Save className="w-4 h-4" /> Save Notes
+                        </button>
+                    )}
+                </div>
+
+                <div className="flex gap-2">
+                    <input
+                        value={entry}
+                        onChange={e => setEntry(e.target.value)}
+                        onKeyDown={e => e.key === 'Enter' && handleAddEntry()}
+                        placeholder="Log a change, e.g. raised view-distance to 12"
+                        className="flex-1 bg-black/20 border border-border/50 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-primary/50"
+                    />
+                    <input
+                        value={author}
+                        onChange={e => setAuthor(e.target.value)}
+                        placeholder="Your name"
+                        className="w-32 bg-black/20 border border-border/50 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-primary/50"
+                    />
+                    <button
+                        onClick={handleAddEntry}
+                        disabled={!entry.trim()}
+                        className="px-4 py-2 bg-white/5 hover:bg-white/10 text-white text-sm font-bold rounded-lg border border-white/10 transition-colors disabled:opacity-50"
+                    >
+                        Add
+                    </button>
+                </div>
+
+                {history.changelog.length === 0 ? (
+                    <p className="text-sm text-text-muted">No changes recorded yet. Upgrades, plugin and mod installs and restores are added automatically.</p>
+                ) : (
+                    <div className="space-y-1 max-h-64 overflow-y-auto custom-scrollbar">
+                        {history.changelog.map((change, i) => (
+                            <div key={i} className="flex items-start gap-3 text-sm py-1.5 border-b border-border/20 last:border-0">
+                                <span className="text-xs text-text-muted w-32 shrink-0">{new Date(change.timestamp).toLocaleString()}</span>
+                                <span className="text-xs font-bold text-amber-400 w-24 shrink-0">{CHANGE_KIND_LABELS[change.kind] ?? change.kind}</span>
+                                <span className="text-white flex-1">
+                                    {change.summary}
+                                    {change.author && <span className="text-text-muted"> &middot; {change.author}</span>}
+                                </span>
+                            </div>
+                        ))}
+                    </div>
+                )}
+            </div>
+        </div>
+    );
+}
@@ -8,6 +8,8 @@ import {
     Layers, Archive, Zap
 } from 'lucide-react';
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
+import { withServerStopped } from '../../lib/serverGuard';
 
 interface SoftwareManagerProps {
     server: Server;
@@ -58,6 +60,20 @@ const SOFTWARE_TYPES: { id: ServerType; name: string; description: string; icon:
         icon: Globe,
         color: 'text-cyan-500'
     },
+    {
+        id: 'spongevanilla',
+        name: 'SpongeVanilla',
+        description: 'Sponge plugin platform on the vanilla server.',
+        icon: Layers,
+        color: 'text-yellow-500'
+    },
+    {
+        id: 'spongeforge',
+        name: 'SpongeForge',
+        description: 'Sponge plugins alongside Forge mods.',
+        icon: Layers,
+        color: 'text-amber-600'
+    },
     {
         id: 'bedrock',
         name: 'Bedrock (BDS)',
@@ -117,6 +133,8 @@ export default function SoftwareManager({ server }: SoftwareManagerProps) {
                     'fabric': 'get_fabric_versions',
                     'bedrock': 'get_bedrock_versions',
                     'nukkit': 'get_nukkit_versions',
+                    'spongevanilla': 'get_spongevanilla_versions',
+                    'spongeforge': 'get_spongeforge_versions',
                 };
 
                 const cmd = commandMap[targetType];
@@ -170,12 +188,12 @@ export default function SoftwareManager({ server }: SoftwareManagerProps) {
         const toastId = toast.loading("Processing installation...", { description: "Please wait while we download and configure your server." });
 
         try {
-            await invoke('download_server', {
+            await withServerStopped(() => invoke('download_server', {
                 serverPath: server.path,
                 serverType: targetType,
                 version: targetVersion,
                 preserveConfig: server.type === targetType // Preserve config if same type
-            });
+            }));
 
             updateServer(server.id, {
                 type: targetType,
@@ -185,7 +203,7 @@ export default function SoftwareManager({ server }: SoftwareManagerProps) {
 
             toast.success(`${targetType} ${targetVersion} installed successfully!`, { id: toastId });
         } catch (e) {
-            toast.error("Installation failed: " + e, { id: toastId });
+            toast.error("Installation failed: " + errorMessage(e), { id: toastId });
         } finally {
             setInstalling(false);
         }
@@ -1,14 +1,44 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useAppStore, Server, ServerType } from '../../stores/appStore';
-import { Play, RotateCcw, Cpu, Coffee, MemoryStick, Zap } from 'lucide-react';
+import { Play, RotateCcw, Cpu, Coffee, MemoryStick, Zap, ShieldAlert, SearchCode, Activity } from 'lucide-react';
 import { toast } from 'sonner';
 import { cn } from '../../lib/utils';
+import { errorMessage } from '../../lib/errors';
 
 interface StartupManagerProps {
     server: Server;
 }
 
+interface SafeModeInfo {
+    mode: 'no_addons' | 'vanilla';
+    enteredAt: string;
+    disabled: string[];
+    serverJar: string | null;
+}
+
+interface BisectState {
+    round: number;
+    addons: string[];
+    suspects: string[];
+    testing: string[];
+    culprit: string | null;
+}
+
+interface GcSummary {
+    enabled: boolean;
+    logFound: boolean;
+    uptimeSecs: number;
+    pauses: number;
+    fullGcs: number;
+    totalPauseMs: number;
+    avgPauseMs: number;
+    maxPauseMs: number;
+    gcTimePercent: number;
+    heapTrend: 'rising' | 'stable' | 'falling' | 'unknown';
+    insights: string[];
+}
+
 interface JavaInstall {
     path: string;
     version: string;
@@ -16,7 +46,7 @@ interface JavaInstall {
 }
 
 const isJavaServer = (type: ServerType) => {
-    return ['vanilla', 'paper', 'spigot', 'forge', 'fabric', 'purpur', 'nukkit'].includes(type);
+    return ['vanilla', 'paper', 'spigot', 'forge', 'fabric', 'purpur', 'nukkit', 'spongevanilla', 'spongeforge'].includes(type);
 };
 
 export function StartupManager({ server }: StartupManagerProps) {
@@ -25,6 +55,12 @@ export function StartupManager({ server }: StartupManagerProps) {
     const [useAikars, setUseAikars] = useState(false);
     const [ram, setRam] = useState(server.allocatedRam || 2048);
     const [manualFlags, setManualFlags] = useState(server.startupFlags || "");
+    const [safeMode, setSafeMode] = useState<SafeModeInfo | null>(null);
+    const [safeModeBusy, setSafeModeBusy] = useState(false);
+    const [bisect, setBisect] = useState<BisectState | null>(null);
+    const [bisectBusy, setBisectBusy] = useState(false);
+    const [gcSummary, setGcSummary] = useState<GcSummary | null>(null);
+    const [gcBusy, setGcBusy] = useState(false);
 
     const AIKARS_FLAGS = "-XX:+UseG1GC -XX:+ParallelRefProcEnabled -XX:MaxGCPauseMillis=200 -XX:+UnlockExperimentalVMOptions -XX:+DisableExplicitGC -XX:+AlwaysPreTouch -XX:G1NewSizePercent=30 -XX:G1MaxNewSizePercent=40 -XX:G1HeapRegionSize=8M -XX:G1ReservePercent=20 -XX:G1HeapWastePercent=5 -XX:G1MixedGCCountTarget=4 -XX:InitiatingHeapOccupancyPercent=15 -XX:G1MixedGCLiveThresholdPercent=90 -XX:G1RSetUpdatingPauseTimePercent=5 -XX:SurvivorRatio=32 -XX:+PerfDisableSharedMem -XX:MaxTenuringThreshold=1";
 
@@ -39,21 +75,115 @@ export function StartupManager({ server }: StartupManagerProps) {
         }
         setRam(server.allocatedRam || 2048);
         setManualFlags(server.startupFlags || "");
+        invoke<SafeModeInfo | null>('get_safe_mode_status', { serverPath: server.path }).then(setSafeMode).catch(() => setSafeMode(null));
+        invoke<BisectState | null>('get_bisect_status', { id: server.id }).then(setBisect).catch(() => setBisect(null));
+        setGcSummary(null);
     }, [server.id, server.type]);
 
+    // Each bisect step stops and restarts the server itself
+    const runBisectStep = async (step: () => Promise<BisectState | null>) => {
+        setBisectBusy(true);
+        try {
+            const state = await step();
+            if (state?.culprit) {
+                setBisect(null);
+                toast.success(`Found it: ${state.culprit.split('/').pop()}`, {
+                    description: "All plugins and mods were re-enabled. Remove or update this one.",
+                    duration: 15000,
+                });
+            } else {
+                setBisect(state);
+            }
+        } catch (e) {
+            toast.error("Plugin search failed: " + errorMessage(e));
+        } finally {
+            setBisectBusy(false);
+        }
+    };
+
+    const startBisect = () => runBisectStep(() => invoke<BisectState>('bisect_plugins', { id: server.id }));
+    const answerBisect = (issuePersists: boolean) =>
+        runBisectStep(() => invoke<BisectState>('report_bisect_result', { id: server.id, issuePersists }));
+    const cancelBisect = () => runBisectStep(async () => {
+        await invoke('cancel_bisect', { id: server.id });
+        toast.success("Plugin search cancelled; all plugins and mods were re-enabled");
+        return null;
+    });
+
+    const enterSafeMode = async (mode: SafeModeInfo['mode']) => {
+        setSafeModeBusy(true);
+        try {
+            const info = await invoke<SafeModeInfo>('enter_safe_mode', { serverPath: server.path, mode });
+            setSafeMode(info);
+            toast.success(mode === 'vanilla'
+                ? "Safe mode: the server will start on the vanilla jar"
+                : `Safe mode: ${info.disabled.length} plugins/mods disabled`);
+        } catch (e) {
+            toast.error("Failed to enter safe mode: " + errorMessage(e));
+        } finally {
+            setSafeModeBusy(false);
+        }
+    };
+
+    const restoreFromSafeMode = async () => {
+        setSafeModeBusy(true);
+        try {
+            await invoke('restore_from_safe_mode', { serverPath: server.path });
+            setSafeMode(null);
+            toast.success("Restored the server from safe mode");
+        } catch (e) {
+            toast.error("Failed to leave safe mode: " + errorMessage(e));
+        } finally {
+            setSafeModeBusy(false);
+        }
+    };
+
+
 
+    // Updates the launch config the backend restarts with; a running server keeps its current
+    // settings until it is restarted
+    const applyLaunchConfig = async (changes: { ram?: number; flags?: string }, message: string) => {
+        try {
+            await invoke('update_server_launch_config', {
+                id: server.id,
+                ram: changes.ram ?? ram,
+                flags: changes.flags ?? manualFlags,
+                javaPath: server.javaPath ?? null,
+            });
+        } catch (e) {
+            toast.error("Failed to update launch settings: " + errorMessage(e));
+            return;
+        }
+        if (server.status !== 'running') {
+            toast.success(message);
+            return;
+        }
+        toast.success(message, {
+            description: "Applies on the next restart.",
+            action: {
+                label: "Restart now",
+                onClick: () => invoke('update_server_launch_config', {
+                    id: server.id,
+                    ram: changes.ram ?? ram,
+                    flags: changes.flags ?? manualFlags,
+                    javaPath: server.javaPath ?? null,
+                    restart: true,
+                }).catch((e) => toast.error("Restart failed: " + errorMessage(e))),
+            },
+        });
+    };
 
     const handleAikarsToggle = (enabled: boolean) => {
         setUseAikars(enabled);
         if (enabled) {
             updateServer(server.id, { startupFlags: AIKARS_FLAGS });
             setManualFlags(AIKARS_FLAGS);
-            toast.success("Optimized flags enabled (Aikar's)");
+            applyLaunchConfig({ flags: AIKARS_FLAGS }, "Optimized flags enabled (Aikar's)");
         } else {
             // Revert to basics or empty
             updateServer(server.id, { startupFlags: "" });
             setManualFlags("");
-            toast.success("Startup flags cleared");
+            applyLaunchConfig({ flags: "" }, "Startup flags cleared");
         }
     };
 
@@ -63,6 +193,10 @@ export function StartupManager({ server }: StartupManagerProps) {
         updateServer(server.id, { allocatedRam: mb });
     };
 
+    const commitRam = () => {
+        applyLaunchConfig({ ram }, `RAM set to ${(ram / 1024).toFixed(1)} GB`);
+    };
+
     const handleManualFlagsChange = (e: React.ChangeEvent<HTMLTextAreaElement>) => {
         const val = e.target.value;
         setManualFlags(val);
@@ -72,7 +206,25 @@ export function StartupManager({ server }: StartupManagerProps) {
 
     const saveFlags = () => {
         updateServer(server.id, { startupFlags: manualFlags });
-        toast.success("Startup flags saved");
+        applyLaunchConfig({ flags: manualFlags }, "Startup flags saved");
+    };
+
+    const handleGcLoggingToggle = (enabled: boolean) => {
+        updateServer(server.id, { gcLogging: enabled });
+        toast.success(`GC logging ${enabled ? 'enabled' : 'disabled'}`, {
+            description: server.status === 'running' ? "Applies on the next restart." : undefined,
+        });
+    };
+
+    const analyzeGc = async () => {
+        setGcBusy(true);
+        try {
+            setGcSummary(await invoke<GcSummary>('analyze_gc_log', { id: server.id }));
+        } catch (e) {
+            toast.error("Failed to analyze the GC log: " + errorMessage(e));
+        } finally {
+            setGcBusy(false);
+        }
     };
 
     const handleAutoRestartToggle = (enabled: boolean) => {
@@ -151,6 +303,8 @@ export function StartupManager({ server }: StartupManagerProps) {
                         step="512"
                         value={ram}
                         onChange={(e) => handleRamChange(parseInt(e.target.value))}
+                        onPointerUp={commitRam}
+                        onKeyUp={commitRam}
                         className="w-full h-2 bg-white/10 rounded-lg appearance-none cursor-pointer accent-pink-500"
                     />
                     <div className="flex justify-between mt-2 text-xs text-text-muted font-mono">
@@ -246,6 +400,160 @@ export function StartupManager({ server }: StartupManagerProps) {
                     </div>
                 </div>
 
+                {/* Safe Mode */}
+                <div className="bg-[#161b22] border border-border rounded-xl p-6 md:col-span-2">
+                    <h3 className="text-sm font-bold text-white flex items-center gap-2 mb-1">
+                        <ShieldAlert className="w-4 h-4 text-amber-400" /> Safe Mode
+                    </h3>
+                    {safeMode ? (
+                        <div className="flex items-center justify-between gap-4">
+                            <p className="text-xs text-amber-300/80">
+                                {safeMode.mode === 'vanilla'
+                                    ? `Running the vanilla jar instead of ${safeMode.serverJar}.`
+                                    : `${safeMode.disabled.length} plugins/mods are disabled.`}
+                                {' '}Start the server to check whether it still crashes.
+                            </p>
+                            <button
+                                onClick={restoreFromSafeMode}
+                                disabled={safeModeBusy || server.status === 'running'}
+                                className="px-4 py-1.5 bg-amber-600 hover:bg-amber-500 disabled:opacity-50 text-white text-xs font-bold rounded-lg transition-colors shrink-0"
+                            >
+                                Restore
+                            </button>
+                        </div>
+                    ) : (
+                        <div className="flex items-center justify-between gap-4">
+                            <p className="text-xs text-text-muted">
+                                Server crashing on start? Start it without plugins and mods, or on the vanilla jar, to find the cause.
+                            </p>
+                            <div className="flex gap-2 shrink-0">
+                                <button
+                                    onClick={() => enterSafeMode('no_addons')}
+                                    disabled={safeModeBusy || server.status === 'running'}
+                                    className="px-3 py-1.5 bg-white/5 hover:bg-white/10 disabled:opacity-50 border border-white/10 text-white text-xs font-bold rounded-lg transition-colors"
+                                >
+                                    Disable all addons
+                                </button>
+                                <button
+                                    onClick={() => enterSafeMode('vanilla')}
+                                    disabled={safeModeBusy || server.status === 'running'}
+                                    className="px-3 py-1.5 bg-white/5 hover:bg-white/10 disabled:opacity-50 border border-white/10 text-white text-xs font-bold rounded-lg transition-colors"
+                                >
+                                    Use vanilla jar
+                                </button>
+                            </div>
+                        </div>
+                    )}
+                </div>
+
+                {/* Bisect */}
+                <div className="bg-[#161b22] border border-border rounded-xl p-6 md:col-span-2">
+                    <h3 className="text-sm font-bold text-white flex items-center gap-2 mb-1">
+                        <SearchCode className="w-4 h-4 text-emerald-400" /> Find a Broken Plugin or Mod
+                    </h3>
+                    {bisect ? (
+                        <div className="space-y-3">
+                            <p className="text-xs text-text-muted">
+                                Round {bisect.round}: {bisect.suspects.length} suspects left, {bisect.testing.length} enabled.
+                                Check the server, then tell us whether the problem is still there.
+                            </p>
+                            <div className="flex gap-2">
+                                <button
+                                    onClick={() => answerBisect(true)}
+                                    disabled={bisectBusy}
+                                    className="px-3 py-1.5 bg-red-500/10 hover:bg-red-500/20 disabled:opacity-50 border border-red-500/20 text-red-300 text-xs font-bold rounded-lg transition-colors"
+                                >
+                                    Still broken
+                                </button>
+                                <button
+                                    onClick={() => answerBisect(false)}
+                                    disabled={bisectBusy}
+                                    className="px-3 py-1.5 bg-emerald-500/10 hover:bg-emerald-500/20 disabled:opacity-50 border border-emerald-500/20 text-emerald-300 text-xs font-bold rounded-lg transition-colors"
+                                >
+                                    Works now
+                                </button>
+                                <button
+                                    onClick={cancelBisect}
+                                    disabled={bisectBusy}
+                                    className="px-3 py-1.5 bg-white/5 hover:bg-white/10 disabled:opacity-50 border border-white/10 text-text-muted text-xs font-bold rounded-lg transition-colors ml-auto"
+                                >
+                                    Cancel
+                                </button>
+                            </div>
+                        </div>
+                    ) : (
+                        <div className="flex items-center justify-between gap-4">
+                            <p className="text-xs text-text-muted">
+                                Disables half of the plugins and mods at a time and restarts the server until the one causing the problem is found.
+                            </p>
+                            <button
+                                onClick={startBisect}
+                                disabled={bisectBusy || !!safeMode}
+                                className="px-3 py-1.5 bg-white/5 hover:bg-white/10 disabled:opacity-50 border border-white/10 text-white text-xs font-bold rounded-lg transition-colors shrink-0"
+                            >
+                                Start search
+                            </button>
+                        </div>
+                    )}
+                </div>
+
+                {/* GC Analysis */}
+                <div className="bg-[#161b22] border border-border rounded-xl p-6 md:col-span-2 space-y-4">
+                    <div className="flex items-center justify-between gap-4">
+                        <div>
+                            <h3 className="text-sm font-bold text-white flex items-center gap-2 mb-1">
+                                <Activity className="w-4 h-4 text-rose-400" /> Garbage Collection
+                            </h3>
+                            <p className="text-xs text-text-muted">
+                                Log GC pauses to <code>logs/gc.log</code> and check whether the server has enough RAM. Needs Java 9 or newer.
+                            </p>
+                        </div>
+                        <div className="flex items-center gap-3 shrink-0">
+                            <button
+                                onClick={analyzeGc}
+                                disabled={gcBusy}
+                                className="px-3 py-1.5 bg-white/5 hover:bg-white/10 disabled:opacity-50 border border-white/10 text-white text-xs font-bold rounded-lg transition-colors"
+                            >
+                                {gcBusy ? "Analyzing..." : "Analyze"}
+                            </button>
+                            <label className="relative inline-flex items-center cursor-pointer">
+                                <input
+                                    type="checkbox"
+                                    checked={server.gcLogging || false}
+                                    onChange={(e) => handleGcLoggingToggle(e.target.checked)}
+                                    className="sr-only peer"
+                                />
+                                <div className="w-11 h-6 bg-white/10 peer-focus:outline-none rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all peer-checked:bg-rose-600"></div>
+                            </label>
+                        </div>
+                    </div>
+
+                    {gcSummary && (
+                        <div className="space-y-3">
+                            {gcSummary.logFound && (
+                                <div className="grid grid-cols-2 md:grid-cols-4 gap-2">
+                                    {[
+                                        ["Time in GC", `${gcSummary.gcTimePercent.toFixed(1)}%`],
+                                        ["Pauses", `${gcSummary.pauses} (${gcSummary.fullGcs} full)`],
+                                        ["Avg / Max Pause", `${gcSummary.avgPauseMs.toFixed(0)} / ${gcSummary.maxPauseMs.toFixed(0)} ms`],
+                                        ["Heap After GC", gcSummary.heapTrend],
+                                    ].map(([label, value]) => (
+                                        <div key={label} className="bg-black/30 rounded-lg p-3">
+                                            <div className="text-[10px] text-text-muted uppercase tracking-wider">{label}</div>
+                                            <div className="text-sm font-bold text-white font-mono capitalize">{value}</div>
+                                        </div>
+                                    ))}
+                                </div>
+                            )}
+                            <ul className="space-y-1">
+                                {gcSummary.insights.map((insight, i) => (
+                                    <li key={i} className="text-xs text-rose-200/80 leading-relaxed">• {insight}</li>
+                                ))}
+                            </ul>
+                        </div>
+                    )}
+                </div>
+
                 {/* Auto Restart */}
                 <div className="bg-[#161b22] border border-border rounded-xl p-6 flex items-center justify-between md:col-span-2">
                     <div>
@@ -1,7 +1,6 @@
 import { useState, useEffect, useRef } from 'react';
 import { Server } from '../../stores/appStore';
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import { save, open } from '@tauri-apps/plugin-dialog';
 import {
@@ -11,6 +10,9 @@ import {
     Layers, X, Check, Edit2, Map as MapIcon,
 } from 'lucide-react';
 import { cn } from '../../lib/utils';
+import { listenOperation, newOperationId } from '../../lib/operations';
+import { errorMessage } from '../../lib/errors';
+import { withServerStopped } from '../../lib/serverGuard';
 
 interface Dimension {
     name: string;
@@ -18,18 +20,42 @@ interface Dimension {
     size_bytes: number;
 }
 
+interface PluginWorld {
+    name: string;
+    environment: string;
+    source: string;
+    size_bytes: number;
+    exists: boolean;
+}
+
 interface WorldGroup {
     level_name: string;
     dimensions: Dimension[];
     total_size: number;
     exists: boolean;
     path_debug?: string;
+    plugin_worlds: PluginWorld[];
 }
 
 interface WorldManagerProps {
     server: Server;
 }
 
+interface MapCache {
+    plugin: 'bluemap' | 'dynmap';
+    map: string;
+    path: string;
+    sizeBytes: number;
+    fileCount: number;
+    oldest: string | null;
+}
+
+interface MapCleanupReport {
+    deletedFiles: number;
+    freedBytes: number;
+    remainingBytes: number;
+}
+
 // Dimension metadata
 const DIMENSION_INFO: Record<string, { name: string; icon: any; color: string; bgColor: string; textColor: string; borderColor: string; description: string }> = {
     overworld: {
@@ -86,6 +112,10 @@ export default function WorldManager({ server }: WorldManagerProps) {
     const [spawnAnimals, setSpawnAnimals] = useState(true);
     const [spawnMonsters, setSpawnMonsters] = useState(true);
     const [allowNether, setAllowNether] = useState(true);
+    const [generateNow, setGenerateNow] = useState(true);
+    const [newLevelName, setNewLevelName] = useState("");
+    const [gamemode, setGamemode] = useState("");
+    const isBedrock = server.type === 'bedrock';
 
     // Import State
     const [showImportModal, setShowImportModal] = useState(false);
@@ -107,12 +137,14 @@ export default function WorldManager({ server }: WorldManagerProps) {
             setInfo(data);
 
             const files = await invoke<any[]>('get_server_files', { path: server.path });
+            const pluginWorlds = data.plugin_worlds.map(w => w.name);
             const folders = files
                 .filter(f => f.is_dir)
                 .map(f => f.name)
                 .filter(name =>
                     !['logs', 'plugins', 'mods', 'crash-reports', 'libraries', 'versions', 'cache', 'config'].includes(name) &&
-                    !name.startsWith('.')
+                    !name.startsWith('.') &&
+                    !pluginWorlds.includes(name) // Listed in their own section
                 );
             setAllWorlds(folders);
 
@@ -165,7 +197,7 @@ export default function WorldManager({ server }: WorldManagerProps) {
             toast.success("Domination! World imported successfully.", { id: toastId });
             fetchInfo();
         } catch (e: any) {
-            toast.error("Import failed: " + (e.message || e), { id: toastId });
+            toast.error("Import failed: " + errorMessage(e), { id: toastId });
         } finally {
             setIsImporting(false);
             setImportPath('');
@@ -211,20 +243,21 @@ export default function WorldManager({ server }: WorldManagerProps) {
             setUploadProgress({ percentage: 20, details: "Starting extraction..." });
 
             // Setup progress listener
-            const unlisten = await listen<{ percentage: number; details: string }>('world_upload_progress', (event) => {
+            const operationId = newOperationId();
+            const unlisten = await listenOperation(operationId, (progress) => {
+                if (progress.state !== 'running') return;
                 // Scale 20-100 for the extraction phase
-                const scaled = 20 + (event.payload.percentage * 0.8);
-                setUploadProgress({ percentage: Math.round(scaled), details: event.payload.details });
+                const scaled = 20 + (progress.percentage * 0.8);
+                setUploadProgress({ percentage: Math.round(scaled), details: progress.detail });
             });
 
             // Call backend
             await invoke('upload_dimension', {
                 serverPath: server.path,
                 zipPath: tempZipPath,
-                dimension: dimension
-            });
-
-            unlisten();
+                dimension: dimension,
+                operationId
+            }).finally(unlisten);
 
             // Cleanup temp file
             try {
@@ -237,7 +270,7 @@ export default function WorldManager({ server }: WorldManagerProps) {
             fetchInfo();
 
         } catch (e) {
-            toast.error("Upload failed: " + e, { id: toastId });
+            toast.error("Upload failed: " + errorMessage(e), { id: toastId });
         } finally {
             setUploadProgress(null);
         }
@@ -249,14 +282,14 @@ export default function WorldManager({ server }: WorldManagerProps) {
 
         try {
             if (dimension) {
-                await invoke('delete_dimension_folder', { server_path: server.path, dimension });
+                await withServerStopped(() => invoke('delete_dimension_folder', { server_path: server.path, dimension }));
             } else {
-                await invoke('delete_world', { server_path: server.path });
+                await withServerStopped(() => invoke('delete_world', { server_path: server.path }));
             }
             toast.success(`${dimension ? dimension : 'World'} deleted.`);
             fetchInfo();
         } catch (e) {
-            toast.error("Delete failed: " + e);
+            toast.error("Delete failed: " + errorMessage(e));
         }
     };
 
@@ -273,7 +306,7 @@ export default function WorldManager({ server }: WorldManagerProps) {
             toast.success(`Active world switched to: ${worldName}`);
             fetchInfo();
         } catch (e) {
-            toast.error("Failed to switch world: " + e);
+            toast.error("Failed to switch world: " + errorMessage(e));
         }
     };
 
@@ -289,7 +322,7 @@ export default function WorldManager({ server }: WorldManagerProps) {
             toast.success(`Renamed to ${newName}`);
             fetchInfo();
         } catch (e) {
-            toast.error("Rename failed: " + e);
+            toast.error("Rename failed: " + errorMessage(e));
         }
     };
 
@@ -308,24 +341,37 @@ export default function WorldManager({ server }: WorldManagerProps) {
             const toastId = toast.loading("Archiving world...");
             setUploadProgress({ percentage: 0, details: "Starting archive..." });
 
-            const unlisten = await listen<{ percentage: number; details: string }>('world_archive_progress', (event) => {
-                setUploadProgress({ percentage: event.payload.percentage, details: event.payload.details });
+            const operationId = newOperationId();
+            const unlisten = await listenOperation(operationId, (progress) => {
+                if (progress.state !== 'running') return;
+                setUploadProgress({ percentage: progress.percentage, details: progress.detail });
             });
 
             await invoke('archive_world', {
                 serverPath: server.path,
-                savePath: filePath
-            });
-
-            unlisten();
+                savePath: filePath,
+                world: worldName,
+                operationId
+            }).finally(unlisten);
             toast.success("World exported successfully!", { id: toastId });
         } catch (e) {
-            toast.error("Export failed: " + e);
+            toast.error("Export failed: " + errorMessage(e));
         } finally {
             setUploadProgress(null);
         }
     };
 
+    const handleDeletePluginWorld = async (world: PluginWorld) => {
+        if (!confirm(`Delete the ${world.source} world "${world.name}"? It goes to the trash and is removed from the plugin's world list. Other worlds are not touched.`)) return;
+        try {
+            await withServerStopped(() => invoke('delete_plugin_world', { serverPath: server.path, world: world.name }));
+            toast.success(`${world.name} deleted.`);
+            fetchInfo();
+        } catch (e) {
+            toast.error("Delete failed: " + errorMessage(e));
+        }
+    };
+
     const handleOpenFolder = async (worldName: string) => {
         toast.info(`World folder is located at: ${server.path}\\${worldName}`);
         try {
@@ -338,30 +384,41 @@ export default function WorldManager({ server }: WorldManagerProps) {
     const handleRegenerate = async () => {
         setShowRegenModal(false);
         setIsRegenerating(true);
-        const toastId = toast.loading("Regenerating world...");
+        const toastId = toast.loading(generateNow ? "Regenerating world and starting the server..." : "Regenerating world...");
+        const settings = {
+            seed,
+            levelName: newLevelName.trim() || null,
+            gamemode: gamemode || null,
+            levelType,
+            generateStructures: structures,
+            hardcore,
+            difficulty,
+            spawnAnimals,
+            spawnMonsters,
+            allowNether,
+        };
 
         try {
-            if (server.status === 'running') {
-                throw new Error("Server must be stopped to regenerate world.");
+            if (generateNow) {
+                // Stops the server if needed and waits until the new world is loaded
+                const report = await invoke<{ levelName: string; seed: string | null }>('regenerate_world_and_restart', {
+                    id: server.id,
+                    settings,
+                });
+                toast.success(`New world "${report.levelName}" generated!`, {
+                    id: toastId,
+                    description: report.seed ? `Seed: ${report.seed}` : undefined,
+                });
+            } else {
+                if (server.status === 'running') {
+                    throw new Error("Server must be stopped to regenerate world.");
+                }
+                await invoke('regenerate_world', { serverPath: server.path, ...settings, bedrock: isBedrock });
+                toast.success("World regenerated!", { id: toastId });
             }
-
-            await invoke('regenerate_world', {
-                server_path: server.path,
-                seed,
-                level_type: levelType,
-                generate_structures: structures,
-                hardcore,
-                difficulty,
-                spawn_animals: spawnAnimals,
-                spawn_monsters: spawnMonsters,
-                allow_nether: allowNether,
-                target: regenTarget
-            });
-
-            toast.success("World regenerated!", { id: toastId });
             fetchInfo();
         } catch (e: any) {
-            toast.error("Regeneration failed: " + (e.message || e), { id: toastId });
+            toast.error("Regeneration failed: " + errorMessage(e), { id: toastId });
         } finally {
             setIsRegenerating(false);
         }
@@ -618,6 +675,55 @@ export default function WorldManager({ server }: WorldManagerProps) {
                         </div>
                     </section>
                 )}
+
+                {/* Worlds managed by Multiverse / MultiWorld */}
+                {info && info.plugin_worlds.length > 0 && (
+                    <section>
+                        <h3 className="text-sm font-bold text-white flex items-center gap-2 mb-3">
+                            <Layers className="w-4 h-4 text-primary" />
+                            Plugin Worlds
+                        </h3>
+                        <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-3">
+                            {info.plugin_worlds.map(w => {
+                                const dimInfo = DIMENSION_INFO[getDimensionType(w.environment)];
+                                const Icon = dimInfo.icon;
+                                return (
+                                    <div key={w.name} className="group relative flex items-center gap-3 p-3 rounded-xl border bg-surface/30 border-border hover:bg-surface hover:border-white/20 transition-all">
+                                        <div className={cn("w-10 h-10 rounded-lg flex items-center justify-center shrink-0", dimInfo.bgColor, dimInfo.textColor)}>
+                                            <Icon className="w-5 h-5" />
+                                        </div>
+                                        <div className="flex-1 min-w-0">
+                                            <h4 className="font-bold truncate text-white" title={`Managed by ${w.source}`}>{w.name}</h4>
+                                            <p className="text-xs text-text-muted truncate">
+                                                {w.exists ? formatSize(w.size_bytes) : "Not generated yet"} · {dimInfo.name}
+                                            </p>
+                                        </div>
+                                        <div className="absolute top-1 right-1 opacity-0 group-hover:opacity-100 transition-opacity z-30 flex flex-col gap-1">
+                                            {w.exists && (
+                                                <button
+                                                    onClick={() => handleExportWorld(w.name)}
+                                                    className="p-1.5 hover:bg-white/20 bg-black/40 rounded-lg text-text-muted hover:text-white"
+                                                    title="Download World (Zip)"
+                                                >
+                                                    <Download className="w-4 h-4" />
+                                                </button>
+                                            )}
+                                            <button
+                                                onClick={() => handleDeletePluginWorld(w)}
+                                                className="p-1.5 hover:bg-red-500/20 bg-black/40 rounded-lg text-text-muted hover:text-red-400"
+                                                title="Delete World"
+                                            >
+                                                <Trash2 className="w-4 h-4" />
+                                            </button>
+                                        </div>
+                                    </div>
+                                );
+                            })}
+                        </div>
+                    </section>
+                )}
+
+                <MapCacheSection server={server} formatSize={formatSize} />
             </div>
 
 
@@ -704,31 +810,60 @@ export default function WorldManager({ server }: WorldManagerProps) {
                                     />
                                 </div>
 
-                                {/* World Type */}
-                                <div>
-                                    <label className="text-xs font-bold text-text-muted uppercase mb-2 block">World Type</label>
-                                    <div className="grid grid-cols-2 gap-2">
-                                        {WORLD_TYPES.map((type) => (
-                                            <button
-                                                key={type.id}
-                                                onClick={() => setLevelType(type.id)}
-                                                className={cn(
-                                                    "p-3 rounded-xl border text-left transition-all",
-                                                    levelType === type.id
-                                                        ? "bg-primary/20 border-primary"
-                                                        : "bg-surface/50 border-border hover:border-white/20"
-                                                )}
-                                            >
-                                                <div className="flex items-center gap-2 mb-1">
-                                                    <type.icon className={cn("w-4 h-4", levelType === type.id ? "text-primary" : "text-text-muted")} />
-                                                    <span className="font-bold text-white text-sm">{type.name}</span>
-                                                </div>
-                                                <p className="text-xs text-text-muted">{type.description}</p>
-                                            </button>
-                                        ))}
+                                {/* Name & Gamemode */}
+                                <div className="grid grid-cols-2 gap-4">
+                                    <div>
+                                        <label className="text-xs font-bold text-text-muted uppercase mb-1.5 block">World Name</label>
+                                        <input
+                                            value={newLevelName}
+                                            onChange={(e) => setNewLevelName(e.target.value)}
+                                            placeholder={info?.level_name || 'world'}
+                                            className="w-full bg-[#0d1117] border border-border rounded-lg px-3 py-2.5 text-white outline-none focus:border-primary"
+                                        />
+                                    </div>
+                                    <div>
+                                        <label className="text-xs font-bold text-text-muted uppercase mb-1.5 block">Gamemode</label>
+                                        <select
+                                            value={gamemode}
+                                            onChange={(e) => setGamemode(e.target.value)}
+                                            className="w-full bg-[#0d1117] border border-border rounded-lg px-3 py-2.5 text-white outline-none focus:border-primary appearance-none cursor-pointer"
+                                        >
+                                            <option value="">Keep current</option>
+                                            <option value="survival">Survival</option>
+                                            <option value="creative">Creative</option>
+                                            <option value="adventure">Adventure</option>
+                                            {!isBedrock && <option value="spectator">Spectator</option>}
+                                        </select>
                                     </div>
                                 </div>
 
+                                {/* World Type (BDS has no level-type) */}
+                                {!isBedrock && (
+                                    <div>
+                                        <label className="text-xs font-bold text-text-muted uppercase mb-2 block">World Type</label>
+                                        <div className="grid grid-cols-2 gap-2">
+                                            {WORLD_TYPES.map((type) => (
+                                                <button
+                                                    key={type.id}
+                                                    onClick={() => setLevelType(type.id)}
+                                                    className={cn(
+                                                        "p-3 rounded-xl border text-left transition-all",
+                                                        levelType === type.id
+                                                            ? "bg-primary/20 border-primary"
+                                                            : "bg-surface/50 border-border hover:border-white/20"
+                                                    )}
+                                                >
+                                                    <div className="flex items-center gap-2 mb-1">
+                                                        <type.icon className={cn("w-4 h-4", levelType === type.id ? "text-primary" : "text-text-muted")} />
+                                                        <span className="font-bold text-white text-sm">{type.name}</span>
+                                                    </div>
+                                                    <p className="text-xs text-text-muted">{type.description}</p>
+                                                </button>
+                                            ))}
+                                        </div>
+                                    </div>
+                                )}
+
                                 {/* Difficulty */}
                                 <div className="grid grid-cols-2 gap-4">
                                     <div>
@@ -744,50 +879,75 @@ export default function WorldManager({ server }: WorldManagerProps) {
                                             <option value="hard">Hard</option>
                                         </select>
                                     </div>
-                                    <div className="flex flex-col justify-end">
-                                        <label className="flex items-center gap-2 cursor-pointer p-2.5 bg-red-500/10 border border-red-500/30 rounded-lg">
-                                            <input
-                                                type="checkbox"
-                                                checked={hardcore}
-                                                onChange={e => setHardcore(e.target.checked)}
-                                                className="w-4 h-4 rounded accent-red-500"
-                                            />
-                                            <span className="text-sm text-red-400 font-bold">Hardcore Mode</span>
-                                        </label>
-                                    </div>
+                                    {!isBedrock && (
+                                        <div className="flex flex-col justify-end">
+                                            <label className="flex items-center gap-2 cursor-pointer p-2.5 bg-red-500/10 border border-red-500/30 rounded-lg">
+                                                <input
+                                                    type="checkbox"
+                                                    checked={hardcore}
+                                                    onChange={e => setHardcore(e.target.checked)}
+                                                    className="w-4 h-4 rounded accent-red-500"
+                                                />
+                                                <span className="text-sm text-red-400 font-bold">Hardcore Mode</span>
+                                            </label>
+                                        </div>
+                                    )}
                                 </div>
 
                                 {/* Feature Toggles */}
-                                <div className="grid grid-cols-2 gap-3">
+                                {isBedrock ? (
                                     <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
-                                        <input type="checkbox" checked={structures} onChange={e => setStructures(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
+                                        <input
+                                            type="checkbox"
+                                            checked={spawnAnimals && spawnMonsters}
+                                            onChange={e => { setSpawnAnimals(e.target.checked); setSpawnMonsters(e.target.checked); }}
+                                            className="w-4 h-4 rounded accent-primary"
+                                        />
                                         <div>
-                                            <span className="text-sm text-white font-bold block">Structures</span>
-                                            <span className="text-xs text-text-muted">Villages, Temples, etc</span>
+                                            <span className="text-sm text-white font-bold block">Mob Spawning</span>
+                                            <span className="text-xs text-text-muted">Set as the domobspawning game rule when the world is generated now</span>
                                         </div>
                                     </label>
-                                    <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
-                                        <input type="checkbox" checked={spawnAnimals} onChange={e => setSpawnAnimals(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
-                                        <div>
-                                            <span className="text-sm text-white font-bold block">Spawn Animals</span>
-                                            <span className="text-xs text-text-muted">Passive mobs</span>
-                                        </div>
-                                    </label>
-                                    <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
-                                        <input type="checkbox" checked={spawnMonsters} onChange={e => setSpawnMonsters(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
-                                        <div>
-                                            <span className="text-sm text-white font-bold block">Spawn Monsters</span>
-                                            <span className="text-xs text-text-muted">Hostile mobs</span>
-                                        </div>
-                                    </label>
-                                    <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
-                                        <input type="checkbox" checked={allowNether} onChange={e => setAllowNether(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
-                                        <div>
-                                            <span className="text-sm text-white font-bold block">Allow Nether</span>
-                                            <span className="text-xs text-text-muted">Nether portals work</span>
-                                        </div>
-                                    </label>
-                                </div>
+                                ) : (
+                                    <div className="grid grid-cols-2 gap-3">
+                                        <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
+                                            <input type="checkbox" checked={structures} onChange={e => setStructures(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
+                                            <div>
+                                                <span className="text-sm text-white font-bold block">Structures</span>
+                                                <span className="text-xs text-text-muted">Villages, Temples, etc</span>
+                                            </div>
+                                        </label>
+                                        <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
+                                            <input type="checkbox" checked={spawnAnimals} onChange={e => setSpawnAnimals(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
+                                            <div>
+                                                <span className="text-sm text-white font-bold block">Spawn Animals</span>
+                                                <span className="text-xs text-text-muted">Passive mobs</span>
+                                            </div>
+                                        </label>
+                                        <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
+                                            <input type="checkbox" checked={spawnMonsters} onChange={e => setSpawnMonsters(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
+                                            <div>
+                                                <span className="text-sm text-white font-bold block">Spawn Monsters</span>
+                                                <span className="text-xs text-text-muted">Hostile mobs</span>
+                                            </div>
+                                        </label>
+                                        <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
+                                            <input type="checkbox" checked={allowNether} onChange={e => setAllowNether(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
+                                            <div>
+                                                <span className="text-sm text-white font-bold block">Allow Nether</span>
+                                                <span className="text-xs text-text-muted">Nether portals work</span>
+                                            </div>
+                                        </label>
+                                    </div>
+                                )}
+
+                                <label className="flex items-center gap-3 p-3 bg-surface/50 border border-border rounded-xl cursor-pointer hover:border-white/20 transition-colors">
+                                    <input type="checkbox" checked={generateNow} onChange={e => setGenerateNow(e.target.checked)} className="w-4 h-4 rounded accent-primary" />
+                                    <div>
+                                        <span className="text-sm text-white font-bold block">Generate Now</span>
+                                        <span className="text-xs text-text-muted">Stop the server if needed, then start it to create the new world and show its seed</span>
+                                    </div>
+                                </label>
 
                                 {/* Warning */}
                                 <div className="bg-orange-500/10 border border-orange-500/20 rounded-xl p-3 flex gap-3 items-center">
@@ -935,3 +1095,95 @@ function DimensionCard({ type, exists, size, server, onUpload, onReset, onDelete
         </div>
     );
 }
+
+// BlueMap/Dynmap tile caches; only shown when a map plugin has rendered something
+function MapCacheSection({ server, formatSize }: { server: Server; formatSize: (bytes: number) => string }) {
+    const [caches, setCaches] = useState<MapCache[]>([]);
+    const [selected, setSelected] = useState<string[]>([]);
+    const [olderThanDays, setOlderThanDays] = useState('30');
+    const [isPruning, setIsPruning] = useState(false);
+
+    const loadCaches = () => {
+        invoke<MapCache[]>('get_map_caches', { serverPath: server.path })
+            .then(result => {
+                setCaches(result);
+                setSelected(result.map(c => c.path));
+            })
+            .catch(console.error);
+    };
+
+    useEffect(loadCaches, [server.path]);
+
+    if (caches.length === 0) return null;
+
+    const toggle = (path: string) => {
+        setSelected(prev => prev.includes(path) ? prev.filter(p => p !== path) : [...prev, path]);
+    };
+
+    const handlePrune = async () => {
+        const days = parseInt(olderThanDays);
+        const age = days > 0 ? `tiles older than ${days} days` : 'all tiles';
+        if (!confirm(`Delete ${age} from ${selected.length} map(s)? The map plugin renders them again when needed.`)) return;
+        setIsPruning(true);
+        try {
+            const report = await invoke<MapCleanupReport>('prune_map_caches', {
+                serverPath: server.path,
+                caches: selected,
+                olderThanDays: days > 0 ? days : null,
+            });
+            toast.success(`Deleted ${report.deletedFiles} tiles, freed ${formatSize(report.freedBytes)}`);
+            loadCaches();
+        } catch (e) {
+            toast.error(`Map cleanup failed: ${errorMessage(e)}`);
+        } finally {
+            setIsPruning(false);
+        }
+    };
+
+    const total = caches.reduce((sum, c) => sum + c.sizeBytes, 0);
+
+    return (
+        <section>
+            <h3 className="text-sm font-bold text-white flex items-center gap-2 mb-3">
+                <MapIcon className="w-4 h-4 text-primary" />
+                Map Render Cache
+                <span className="text-xs font-normal text-text-muted">{formatSize(total)}</span>
+            </h3>
+            <div className="bg-surface/30 border border-border rounded-xl divide-y divide-border">
+                {caches.map(c => (
+                    <label key={c.path} className="flex items-center gap-3 px-4 py-2.5 cursor-pointer hover:bg-white/5">
+                        <input type="checkbox" checked={selected.includes(c.path)} onChange={() => toggle(c.path)} className="accent-primary" />
+                        <div className="flex-1 min-w-0">
+                            <p className="text-sm text-white truncate">
+                                {c.map} <span className="text-xs text-text-muted">{c.plugin === 'bluemap' ? 'BlueMap' : 'Dynmap'}</span>
+                            </p>
+                            <p className="text-xs text-text-muted truncate">
+                                {c.fileCount.toLocaleString()} tiles{c.oldest ? `, oldest ${new Date(c.oldest).toLocaleDateString()}` : ''}
+                            </p>
+                        </div>
+                        <span className="text-sm text-text-muted font-mono">{formatSize(c.sizeBytes)}</span>
+                    </label>
+                ))}
+            </div>
+            <div className="flex items-center gap-3 mt-3">
+                <label className="text-xs text-text-muted">Delete tiles older than</label>
+                <input
+                    type="number"
+                    min={0}
+                    value={olderThanDays}
+                    onChange={(e) => setOlderThanDays(e.target.value)}
+                    className="w-20 bg-black/20 border border-border rounded-lg px-2 py-1.5 text-sm text-white focus:border-primary outline-none"
+                />
+                <span className="text-xs text-text-muted">days (0 = all)</span>
+                <button
+                    onClick={handlePrune}
+                    disabled={isPruning || selected.length === 0}
+                    className="ml-auto flex items-center gap-2 px-3 py-1.5 rounded-lg bg-red-500/10 text-red-400 hover:bg-red-500/20 text-sm font-medium disabled:opacity-50"
+                >
+                    <Trash2 className="w-4 h-4" />
+                    {isPruning ? 'Cleaning...' : 'Clean Up'}
+                </button>
+            </div>
+        </section>
+    );
+}
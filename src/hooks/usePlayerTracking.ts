@@ -29,9 +29,9 @@ export function usePlayerTracking(server: any) {
         let unlisten: () => void;
 
         const setupListener = async () => {
-            unlisten = await listen(`server-log:${server.id}`, (event: any) => {
-                const log = event.payload as string;
-                processLog(log);
+            unlisten = await listen<string | string[]>(`server-log:${server.id}`, (event) => {
+                const lines = Array.isArray(event.payload) ? event.payload : [event.payload];
+                lines.forEach(processLog);
             });
         };
 
@@ -0,0 +1,26 @@
+// Commands reject with a MineServerError serialized as `{ kind, message, context?, service? }`.
+// Older code paths (and plain JS errors) still throw strings or Error objects.
+
+export type ErrorKind = 'notFound' | 'permissionDenied' | 'network' | 'conflict' | 'validation' | 'external' | 'internal';
+
+export interface MineServerError {
+    kind: ErrorKind;
+    message: string;
+    context?: string | null;
+    service?: string;
+}
+
+export function isMineServerError(e: unknown): e is MineServerError {
+    return typeof e === 'object' && e !== null && 'kind' in e && 'message' in e;
+}
+
+export function errorMessage(e: unknown): string {
+    if (typeof e === 'string') return e;
+    if (isMineServerError(e)) return e.context ? `${e.message} (${e.context})` : e.message;
+    if (e instanceof Error) return e.message;
+    return String(e);
+}
+
+export function isErrorKind(e: unknown, kind: ErrorKind): boolean {
+    return isMineServerError(e) && e.kind === kind;
+}
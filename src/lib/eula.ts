@@ -0,0 +1,22 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { Server } from '../stores/appStore';
+
+interface EulaStatus {
+    accepted: boolean;
+    acceptedAt?: string;
+    serverAccepted: boolean;
+    url: string;
+    text: string;
+}
+
+// Java servers need the Minecraft EULA accepted. Asks once; the backend remembers the answer.
+export async function ensureEulaAccepted(server: Server): Promise<boolean> {
+    if (server.type === 'bedrock') return true;
+
+    const status = await invoke<EulaStatus>('get_eula_status', { serverPath: server.path });
+    if (status.accepted || status.serverAccepted) return true;
+
+    const accepted = confirm(`${status.text}\n\nRead the EULA: ${status.url}\n\nDo you accept the Minecraft EULA?`);
+    await invoke('accept_eula', { serverPath: server.path, accepted });
+    return accepted;
+}
@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface OperationProgress {
+    opId: string;
+    kind: string;
+    percentage: number;
+    bytes: number;
+    detail: string;
+    state: 'running' | 'completed' | 'failed' | 'cancelled';
+}
+
+// Long-running commands accept an `operationId`; pass one from here to follow and cancel them.
+export function newOperationId(): string {
+    return typeof crypto !== 'undefined' && crypto.randomUUID ? crypto.randomUUID() : Math.random().toString(36).substring(2);
+}
+
+export function listenOperation(opId: string, onProgress: (progress: OperationProgress) => void): Promise<UnlistenFn> {
+    return listen<OperationProgress>('operation-progress', (event) => {
+        if (event.payload.opId === opId) onProgress(event.payload);
+    });
+}
+
+export function cancelOperation(opId: string): Promise<void> {
+    return invoke('cancel_operation', { opId });
+}
@@ -0,0 +1,15 @@
+import { invoke } from '@tauri-apps/api/core';
+import { isErrorKind, isMineServerError } from './errors';
+
+// Commands that rewrite a server folder refuse with a `conflict` error (context = server id)
+// while that server is running. This offers to stop it and retries once.
+export async function withServerStopped<T>(action: () => Promise<T>): Promise<T> {
+    try {
+        return await action();
+    } catch (e) {
+        if (!isErrorKind(e, 'conflict') || !isMineServerError(e) || !e.context) throw e;
+        if (!confirm(`${e.message}\n\nStop the server and continue?`)) throw e;
+        await invoke('stop_server', { id: e.context });
+        return await action();
+    }
+}
@@ -2,12 +2,14 @@ import { useState, useEffect } from 'react';
 import { motion } from 'framer-motion';
 import {
     Database, Download, Trash2, RefreshCw, FolderOpen, Clock,
-    HardDrive, Archive, CheckCircle, Plus
+    HardDrive, Archive, CheckCircle, Plus, CopyPlus
 } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
-import { useAppStore } from '../stores/appStore';
+import { useAppStore, ServerType } from '../stores/appStore';
 import { cn } from '../lib/utils';
 import { toast } from 'sonner';
+import { errorMessage } from '../lib/errors';
+import { withServerStopped } from '../lib/serverGuard';
 
 interface BackupInfo {
     id: string;
@@ -17,19 +19,55 @@ interface BackupInfo {
     sizeBytes: number;
     backupType: string;
     filePath: string;
+    scope: 'full' | 'worlds' | 'configs' | 'world';
+    worldName: string | null;
 }
 
+interface RunningBackup {
+    serverPath: string;
+    backupType: string;
+    scope: BackupInfo['scope'];
+    startedAt: string;
+    operationId: string;
+}
+
+const scopeLabels: Record<BackupInfo['scope'], string> = {
+    full: 'Full server',
+    worlds: 'Worlds only',
+    configs: 'Configs only',
+    world: 'Single world',
+};
+
 export function Backups() {
-    const { servers } = useAppStore();
+    const { servers, addServer } = useAppStore();
     const [backups, setBackups] = useState<BackupInfo[]>([]);
     const [isLoading, setIsLoading] = useState(true);
     const [isCreating, setIsCreating] = useState(false);
     const [selectedServer, setSelectedServer] = useState<string>('all');
+    const [runningBackups, setRunningBackups] = useState<RunningBackup[]>([]);
 
     useEffect(() => {
         loadBackups();
     }, []);
 
+    // Scheduled backups start on their own, so keep polling what's being written
+    useEffect(() => {
+        let previous = 0;
+        const poll = async () => {
+            try {
+                const running = await invoke<RunningBackup[]>('list_running_backups');
+                if (running.length < previous) loadBackups();
+                previous = running.length;
+                setRunningBackups(running);
+            } catch (e) {
+                console.error('Failed to load running backups:', e);
+            }
+        };
+        poll();
+        const interval = setInterval(poll, 3000);
+        return () => clearInterval(interval);
+    }, []);
+
     const loadBackups = async () => {
         setIsLoading(true);
         try {
@@ -51,6 +89,8 @@ export function Backups() {
 
     const [showCreateModal, setShowCreateModal] = useState(false);
     const [targetServerId, setTargetServerId] = useState<string>("");
+    const [scope, setScope] = useState<BackupInfo['scope']>('full');
+    const [worldName, setWorldName] = useState('world');
 
     const handleCreateClick = () => {
         if (servers.length === 0) {
@@ -76,12 +116,14 @@ export function Backups() {
             await invoke('create_backup', {
                 serverPath: server.path,
                 serverName: server.name,
-                backupType: 'manual'
+                backupType: 'manual',
+                scope,
+                worldName: scope === 'world' ? worldName : null
             });
             toast.success("Backup created successfully!");
             loadBackups();
         } catch (e) {
-            toast.error('Backup failed: ' + e);
+            toast.error('Backup failed: ' + errorMessage(e));
         } finally {
             setIsCreating(false);
         }
@@ -95,7 +137,7 @@ export function Backups() {
             toast.success("Backup deleted");
             loadBackups();
         } catch (e) {
-            toast.error('Delete failed: ' + e);
+            toast.error('Delete failed: ' + errorMessage(e));
         }
     };
 
@@ -104,10 +146,40 @@ export function Backups() {
 
         toast.info('Restoring backup...');
         try {
-            await invoke('restore_backup', { backupId: backup.id, targetPath: backup.serverPath });
+            await withServerStopped(() => invoke('restore_backup', { backupId: backup.id, targetPath: backup.serverPath }));
             toast.success("Backup restored successfully!");
         } catch (e) {
-            toast.error('Restore failed: ' + e);
+            toast.error('Restore failed: ' + errorMessage(e));
+        }
+    };
+
+    // Leaves the original alone; handy for looking at an old state of the world
+    const restoreAsNew = async (backup: BackupInfo) => {
+        const name = prompt("Name of the new server", `${backup.serverName} (restored)`);
+        if (!name) return;
+
+        toast.info('Restoring backup...');
+        try {
+            const { server, port } = await invoke<{ server: any; port: number }>('restore_backup_as_new', { backupId: backup.id, newName: name });
+            const original = servers.find(s => s.path === backup.serverPath);
+            addServer({
+                ...(original ?? { maxPlayers: 20 }),
+                id: server.id,
+                name: server.name,
+                type: (server.serverType || original?.type || 'vanilla') as ServerType,
+                version: server.version,
+                port,
+                allocatedRam: server.ram,
+                path: server.path,
+                status: 'stopped',
+                playerCount: 0,
+                createdAt: new Date().toISOString(),
+                lastStarted: undefined,
+                playitTunnelId: undefined,
+            });
+            toast.success(`Restored as ${server.name}`);
+        } catch (e) {
+            toast.error('Restore failed: ' + errorMessage(e));
         }
     };
 
@@ -157,6 +229,22 @@ export function Backups() {
                 </button>
             </div>
 
+            {runningBackups.length > 0 && (
+                <div className="glass-card p-4 space-y-2">
+                    {runningBackups.map(b => (
+                        <div key={b.operationId} className="flex items-center gap-3 text-sm">
+                            <RefreshCw className="w-4 h-4 text-primary animate-spin" />
+                            <span className="text-white font-medium">
+                                {servers.find(s => s.path === b.serverPath)?.name ?? b.serverPath}
+                            </span>
+                            <span className="text-text-muted">
+                                {b.backupType} backup ({scopeLabels[b.scope] ?? b.scope}) running since {formatDate(b.startedAt)}
+                            </span>
+                        </div>
+                    ))}
+                </div>
+            )}
+
             {/* Stats Cards */}
             <div className="grid grid-cols-1 md:grid-cols-4 gap-4">
                 <div className="glass-card p-4">
@@ -248,6 +336,11 @@ export function Backups() {
                                     <span className={cn("px-2 py-0.5 rounded-full text-[10px] font-bold uppercase border", getTypeColor(backup.backupType))}>
                                         {backup.backupType}
                                     </span>
+                                    {backup.scope && backup.scope !== 'full' && (
+                                        <span className="px-2 py-0.5 rounded-full text-[10px] font-bold uppercase border bg-purple-500/20 text-purple-400 border-purple-500/30">
+                                            {backup.scope === 'world' ? `World: ${backup.worldName}` : scopeLabels[backup.scope]}
+                                        </span>
+                                    )}
                                 </div>
                                 <div className="flex items-center gap-4 text-xs text-text-muted">
                                     <span className="flex items-center gap-1">
@@ -268,6 +361,15 @@ export function Backups() {
                                 >
                                     <Download className="w-4 h-4" />
                                 </button>
+                                {backup.scope === 'full' && (
+                                    <button
+                                        onClick={() => restoreAsNew(backup)}
+                                        className="p-2 rounded-lg hover:bg-surface text-text-muted hover:text-white transition-colors"
+                                        title="Restore as new server"
+                                    >
+                                        <CopyPlus className="w-4 h-4" />
+                                    </button>
+                                )}
                                 <button className="p-2 rounded-lg hover:bg-surface text-text-muted hover:text-white transition-colors" title="Open Folder">
                                     <FolderOpen className="w-4 h-4" />
                                 </button>
@@ -303,8 +405,37 @@ export function Backups() {
                                 </select>
                             </div>
 
+                            <div>
+                                <label className="text-sm font-medium text-text-muted mb-1 block">Scope</label>
+                                <select
+                                    value={scope}
+                                    onChange={(e) => setScope(e.target.value as BackupInfo['scope'])}
+                                    className="w-full bg-black/20 border border-border rounded-lg px-3 py-2.5 text-white focus:border-primary outline-none"
+                                >
+                                    {Object.entries(scopeLabels).map(([id, label]) => (
+                                        <option key={id} value={id}>{label}</option>
+                                    ))}
+                                </select>
+                            </div>
+
+                            {scope === 'world' && (
+                                <div>
+                                    <label className="text-sm font-medium text-text-muted mb-1 block">World Name</label>
+                                    <input
+                                        value={worldName}
+                                        onChange={(e) => setWorldName(e.target.value)}
+                                        placeholder="world"
+                                        className="w-full bg-black/20 border border-border rounded-lg px-3 py-2.5 text-white focus:border-primary outline-none"
+                                    />
+                                </div>
+                            )}
+
                             <div className="p-3 bg-blue-500/10 border border-blue-500/20 rounded-lg text-xs text-blue-400">
-                                This will create a full zip archive of the selected server's directory.
+                                {scope === 'full'
+                                    ? "This will create a full zip archive of the selected server's directory."
+                                    : scope === 'configs'
+                                        ? 'Only configuration files are archived. Restoring overwrites those files.'
+                                        : 'Only world folders are archived. Restoring replaces those worlds; the current copies go to the trash.'}
                             </div>
 
                             <div className="flex gap-3 pt-2">
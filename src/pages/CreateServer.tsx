@@ -6,29 +6,53 @@ import { zodResolver } from '@hookform/resolvers/zod';
 import { z } from 'zod';
 import {
     ChevronRight, ChevronLeft, Check, Shield, Zap, Box, Play, Loader2, Download,
-    Server, Sparkles, Rocket, Settings, Users, MemoryStick, Cpu, ArrowLeft
+    Server, Sparkles, Rocket, Settings, Users, MemoryStick, Cpu, ArrowLeft, FileDown
 } from 'lucide-react';
 import { toast } from 'sonner';
-import { useAppStore, ServerType } from '../stores/appStore';
+import { useAppStore, ServerType, registerServerWithBackend } from '../stores/appStore';
 import { cn } from '../lib/utils';
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
+import { listenOperation, newOperationId } from '../lib/operations';
+import { errorMessage } from '../lib/errors';
 
 const generateId = () => typeof crypto !== 'undefined' && crypto.randomUUID ? crypto.randomUUID() : Math.random().toString(36).substring(2);
 
 const serverSchema = z.object({
     name: z.string().min(3, "Name must be at least 3 characters").max(32, "Name too long"),
-    type: z.enum(['vanilla', 'paper', 'bedrock', 'forge', 'neoforge', 'fabric', 'spigot', 'purpur'] as [string, ...string[]]),
+    type: z.enum(['vanilla', 'paper', 'bedrock', 'forge', 'neoforge', 'fabric', 'spigot', 'purpur', 'spongevanilla', 'spongeforge'] as [string, ...string[]]),
     version: z.string().min(1, "Version is required"),
     port: z.number().min(1024, "Port must be > 1024").max(65535, "Port must be < 65535"),
     ram: z.number().min(512, "Minimum 512MB RAM").max(16384, "Maximum 16GB RAM"),
     cpuCores: z.number().min(1, "At least 1 core").max(32, "Max 32 cores"),
     maxPlayers: z.number().min(1, "At least 1 player").max(1000, "Max 1000 players"),
     onlineMode: z.boolean().optional(),
+    ownerUsername: z.string().max(16, "Usernames are at most 16 characters").optional(),
+    acceptEula: z.boolean().optional(),
 });
 
 type ServerFormData = z.infer<typeof serverSchema>;
 
+interface ServerProfile {
+    name: string;
+    description?: string;
+    author?: string;
+    serverType: string;
+    version: string;
+    plugins: unknown[];
+}
+
+interface ImportedServer {
+    server: { id: string; name: string; path: string; serverType: string; version: string; ram: number; startupFlags: string | null };
+    port: number;
+    installed: string[];
+    warnings: string[];
+}
+
+interface BootstrapReport {
+    port: number | null;
+    steps: { step: string; success: boolean; message: string }[];
+}
+
 const SERVER_OPTIONS: { id: ServerType; name: string; desc: string; icon: any; recommended?: boolean; category: 'java' | 'bedrock' | 'modded' }[] = [
     { id: 'paper', name: 'Paper', desc: 'High performance, plugins supported', icon: Zap, recommended: true, category: 'java' },
     { id: 'vanilla', name: 'Vanilla', desc: 'Official Minecraft server', icon: Box, category: 'java' },
@@ -37,6 +61,8 @@ const SERVER_OPTIONS: { id: ServerType; name: string; desc: string; icon: any; r
     { id: 'forge', name: 'Forge', desc: 'Classic modding platform', icon: Shield, category: 'modded' },
     { id: 'neoforge', name: 'NeoForge', desc: 'Modern Forge fork, 1.20.1+', icon: Zap, recommended: true, category: 'modded' },
     { id: 'fabric', name: 'Fabric', desc: 'Lightweight modding', icon: Zap, category: 'modded' },
+    { id: 'spongevanilla', name: 'SpongeVanilla', desc: 'Sponge plugins, no mods', icon: Box, category: 'java' },
+    { id: 'spongeforge', name: 'SpongeForge', desc: 'Sponge plugins with Forge mods', icon: Shield, category: 'modded' },
     { id: 'bedrock', name: 'Bedrock BDS', desc: 'Official Bedrock server', icon: Box, category: 'bedrock' },
 ];
 
@@ -50,10 +76,11 @@ export function CreateServer() {
     const navigate = useNavigate();
     const { addServer, systemInfo } = useAppStore();
     const [step, setStep] = useState(1);
+    const [importing, setImporting] = useState(false);
     const [availableVersions, setAvailableVersions] = useState<string[]>([]);
     const [isLoadingVersions, setIsLoadingVersions] = useState(false);
     const [isDownloading, setIsDownloading] = useState(false);
-    const [downloadProgress, setDownloadProgress] = useState({ percentage: 0, current: 0, total: 0 });
+    const [downloadProgress, setDownloadProgress] = useState({ percentage: 0, bytes: 0 });
     const [selectedCategory, setSelectedCategory] = useState<'java' | 'bedrock' | 'modded'>('java');
 
     const { register, handleSubmit, watch, setValue, formState: { errors } } = useForm<ServerFormData>({
@@ -94,6 +121,8 @@ export function CreateServer() {
                     fabric: 'get_fabric_versions',
                     spigot: 'get_spigot_versions',
                     purpur: 'get_purpur_versions',
+                    spongevanilla: 'get_spongevanilla_versions',
+                    spongeforge: 'get_spongeforge_versions',
                 };
                 if (versionCommands[formData.type]) {
                     versions = await invoke(versionCommands[formData.type]);
@@ -113,13 +142,6 @@ export function CreateServer() {
         return () => { active = false; };
     }, [formData.type, setValue]);
 
-    useEffect(() => {
-        const unlisten = listen('download-progress', (event: any) => {
-            setDownloadProgress(event.payload);
-        });
-        return () => { unlisten.then(f => f()); };
-    }, []);
-
     const formatRam = (mb: number) => mb >= 1024 ? `${(mb / 1024).toFixed(1)} GB` : `${mb} MB`;
 
     const onSubmit = async (data: ServerFormData) => {
@@ -127,11 +149,16 @@ export function CreateServer() {
         setIsDownloading(true);
         const serverPath = `C:\\Servers\\${data.name.replace(/\s+/g, '-').toLowerCase()}`;
         try {
+            const operationId = newOperationId();
+            const unlisten = await listenOperation(operationId, (progress) => {
+                if (progress.state === 'running') setDownloadProgress({ percentage: progress.percentage, bytes: progress.bytes });
+            });
             await invoke('download_server', {
                 serverType: data.type,
                 version: data.version,
-                serverPath: serverPath
-            });
+                serverPath: serverPath,
+                operationId
+            }).finally(unlisten);
             const newServer = {
                 id: generateId(),
                 name: data.name,
@@ -157,6 +184,9 @@ export function CreateServer() {
                 // Actually, the easiest is to just write the property after download.
             };
 
+            // Register first so the backend accepts file writes in the new folder
+            await registerServerWithBackend(newServer);
+
             // Post-install configuration
             if (data.onlineMode) {
                 // Creating a 'cracked' server means online-mode=false
@@ -186,17 +216,90 @@ export function CreateServer() {
                 });
             }
 
+            // EULA, max players, owner as op and a port no other server on this machine uses
+            const report = await invoke<BootstrapReport>('bootstrap_server', {
+                id: newServer.id,
+                options: {
+                    acceptEula: !!data.acceptEula,
+                    maxPlayers: data.maxPlayers,
+                    ownerUsername: data.ownerUsername || null,
+                    preferredPort: data.port,
+                },
+            });
+            if (report.port) newServer.port = report.port;
+            const failed = report.steps.filter(s => !s.success);
+            if (failed.length > 0) {
+                toast.warning("Some setup steps need attention", { description: failed.map(s => s.message).join('\n') });
+            }
+
             addServer(newServer);
             toast.success("Server installed successfully!");
             navigate('/servers');
         } catch (error) {
             console.error("Installation failed:", error);
-            toast.error("Installation failed: " + error);
+            toast.error("Installation failed: " + errorMessage(error));
         } finally {
             setIsDownloading(false);
         }
     };
 
+    // Provisions a server from a profile URL or file shared by a community or modpack author
+    const importProfile = async () => {
+        if (isDownloading || importing) return;
+        const source = prompt("Server profile URL or file path");
+        if (!source?.trim()) return;
+
+        let profile: ServerProfile;
+        try {
+            profile = await invoke<ServerProfile>('read_server_profile', { source });
+        } catch (e) {
+            toast.error("Failed to read profile: " + errorMessage(e));
+            return;
+        }
+        const summary = `${profile.name}${profile.author ? ` by ${profile.author}` : ''}\n${profile.serverType} ${profile.version}, ${profile.plugins.length} plugin(s)${profile.description ? `\n\n${profile.description}` : ''}`;
+        const name = prompt(`${summary}\n\nName of the new server`, profile.name);
+        if (!name?.trim()) return;
+
+        setImporting(true);
+        const toastId = toast.loading(`Setting up ${name}...`);
+        const serverPath = `C:\\Servers\\${name.replace(/\s+/g, '-').toLowerCase()}`;
+        try {
+            const operationId = newOperationId();
+            const unlisten = await listenOperation(operationId, (progress) => {
+                if (progress.state === 'running') toast.loading(`Downloading ${profile.serverType} ${profile.version}... ${progress.percentage}%`, { id: toastId });
+            });
+            const { server, port, installed, warnings } = await invoke<ImportedServer>('import_profile', {
+                source,
+                serverPath,
+                name,
+                operationId,
+            }).finally(unlisten);
+            addServer({
+                id: server.id,
+                name: server.name,
+                type: server.serverType as ServerType,
+                version: server.version,
+                port,
+                maxPlayers: 20,
+                allocatedRam: server.ram,
+                startupFlags: server.startupFlags ?? undefined,
+                path: server.path,
+                status: 'stopped',
+                playerCount: 0,
+                createdAt: new Date().toISOString(),
+            });
+            if (warnings.length > 0) {
+                toast.warning("Some parts of the profile were skipped", { description: warnings.join('\n') });
+            }
+            toast.success(`Imported ${server.name}`, { id: toastId, description: `${installed.length} plugin(s) installed. Accept the EULA before the first start.` });
+            navigate('/servers');
+        } catch (e) {
+            toast.error("Import failed: " + errorMessage(e), { id: toastId });
+        } finally {
+            setImporting(false);
+        }
+    };
+
     const nextStep = () => setStep(s => Math.min(s + 1, 3));
     const prevStep = () => setStep(s => Math.max(s - 1, 1));
 
@@ -228,6 +331,13 @@ export function CreateServer() {
                                 <p className="text-text-secondary text-sm">Deploy your Minecraft server in minutes</p>
                             </div>
                         </div>
+                        <button
+                            onClick={importProfile}
+                            disabled={importing}
+                            className="ml-auto px-4 py-2 rounded-xl bg-white/5 hover:bg-white/10 border border-white/10 text-sm text-white font-medium flex items-center gap-2 transition-colors"
+                        >
+                            <FileDown className="w-4 h-4" /> Import Profile
+                        </button>
                     </div>
 
                     {/* Step Indicator */}
@@ -302,7 +412,7 @@ export function CreateServer() {
                             />
                         )}
                         {step === 3 && (
-                            <StepThree formData={formData} formatRam={formatRam} />
+                            <StepThree formData={formData} register={register} errors={errors} formatRam={formatRam} />
                         )}
                     </AnimatePresence>
 
@@ -580,7 +690,7 @@ function StepTwo({ formData, register, errors, systemInfo, availableVersions, is
 }
 
 // Step 3: Review
-function StepThree({ formData, formatRam }: any) {
+function StepThree({ formData, register, errors, formatRam }: any) {
     const selectedServer = SERVER_OPTIONS.find(o => o.id === formData.type);
 
     return (
@@ -637,6 +747,26 @@ function StepThree({ formData, formatRam }: any) {
                         <span className="font-medium text-purple-400">{formData.maxPlayers} Max</span>
                     </div>
                 </div>
+
+                <div className="mt-4 space-y-3 text-left">
+                    <div>
+                        <label className="text-sm text-text-muted block mb-1">Your Minecraft username (optional)</label>
+                        <input
+                            {...register('ownerUsername')}
+                            placeholder="Made operator and whitelisted"
+                            className="w-full px-3 py-2 bg-black/20 border border-border rounded-lg text-sm text-white placeholder:text-text-muted focus:outline-none focus:border-primary/50"
+                        />
+                        {errors.ownerUsername && <p className="text-xs text-red-400 mt-1">{errors.ownerUsername.message}</p>}
+                    </div>
+                    {formData.type !== 'bedrock' && (
+                        <label className="flex items-start gap-2 text-xs text-text-muted">
+                            <input type="checkbox" {...register('acceptEula')} className="mt-0.5" />
+                            <span>
+                                I accept the <a href="https://aka.ms/MinecraftEULA" target="_blank" rel="noreferrer" className="text-primary hover:underline">Minecraft EULA</a>
+                            </span>
+                        </label>
+                    )}
+                </div>
             </div>
         </motion.div>
     );
@@ -10,8 +10,11 @@ import {
 import { PieChart, Pie, Cell, ResponsiveContainer } from 'recharts';
 import { useAppStore } from '../stores/appStore';
 import { ServerCard } from '../components/cards/ServerCard';
+import { FavoritesCard } from '../components/cards/FavoritesCard';
 import { cn } from '../lib/utils';
+import { ensureEulaAccepted } from '../lib/eula';
 import { toast } from 'sonner';
+import { errorMessage } from '../lib/errors';
 
 export function Dashboard() {
     const navigate = useNavigate();
@@ -201,6 +204,7 @@ export function Dashboard() {
                                             server={server}
                                             onClick={() => navigate(`/servers/${server.id}`)}
                                             onStart={async () => {
+                                                if (!(await ensureEulaAccepted(server))) return;
                                                 toast.success(`Starting ${server.name}...`);
                                                 try {
                                                     const jarName = server.type === 'bedrock' ? 'bedrock_server.exe' : 'server.jar';
@@ -212,7 +216,7 @@ export function Dashboard() {
                                                     });
                                                     toggleServerStatus(server.id);
                                                 } catch (err) {
-                                                    toast.error(`Failed to start: ${err}`);
+                                                    toast.error(`Failed to start: ${errorMessage(err)}`);
                                                 }
                                             }}
                                             onStop={async () => {
@@ -221,7 +225,7 @@ export function Dashboard() {
                                                     await invoke('stop_server', { id: server.id });
                                                     toggleServerStatus(server.id);
                                                 } catch (err) {
-                                                    toast.error(`Failed to stop: ${err}`);
+                                                    toast.error(`Failed to stop: ${errorMessage(err)}`);
                                                 }
                                             }}
                                             onSettings={() => navigate(`/servers/${server.id}`)}
@@ -253,6 +257,7 @@ export function Dashboard() {
                         className="space-y-6"
                     >
                         <SystemAnalysisCard stats={stats} systemInfo={systemInfo} onRefresh={fetchSystemInfo} />
+                        <FavoritesCard />
                     </motion.div>
                 </div>
             </div>
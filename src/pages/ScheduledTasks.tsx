@@ -2,12 +2,13 @@ import { useState, useEffect } from 'react';
 import { motion } from 'framer-motion';
 import {
     Calendar, Clock, RefreshCw, Play, Pause, Trash2,
-    Power, Database, Bell, Zap, CheckCircle
+    Power, Database, Bell, Zap, CheckCircle, Users, Megaphone, Map as MapIcon
 } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { useAppStore } from '../stores/appStore';
 import { cn } from '../lib/utils';
 import { toast } from 'sonner';
+import { errorMessage } from '../lib/errors';
 
 interface ScheduledTask {
     id: string;
@@ -19,12 +20,25 @@ interface ScheduledTask {
     enabled: boolean;
     lastRun: string | null;
     command: string | null;
+    maxRuntimeMinutes: number | null;
+    skipIfRunning: boolean;
+    syncGroupId: string | null;
+    maxAgeDays: number | null;
+}
+
+interface SyncGroup {
+    id: string;
+    name: string;
+    sourceServerId: string;
 }
 
 const taskTypes = [
     { id: 'restart', label: 'Auto Restart', icon: Power, color: 'text-orange-400 bg-orange-500/20' },
     { id: 'backup', label: 'Auto Backup', icon: Database, color: 'text-blue-400 bg-blue-500/20' },
     { id: 'command', label: 'Run Command', icon: Zap, color: 'text-purple-400 bg-purple-500/20' },
+    { id: 'announcement', label: 'Announcement', icon: Megaphone, color: 'text-yellow-400 bg-yellow-500/20' },
+    { id: 'player_sync', label: 'Sync Player Lists', icon: Users, color: 'text-cyan-400 bg-cyan-500/20' },
+    { id: 'map_cleanup', label: 'Map Tile Cleanup', icon: MapIcon, color: 'text-emerald-400 bg-emerald-500/20' },
     { id: 'notification', label: 'Notification', icon: Bell, color: 'text-green-400 bg-green-500/20' },
 ];
 
@@ -54,7 +68,7 @@ export function ScheduledTasks() {
             await invoke('save_scheduled_tasks', { tasks: newTasks });
             setTasks(newTasks);
         } catch (e) {
-            toast.error('Failed to save tasks: ' + e);
+            toast.error('Failed to save tasks: ' + errorMessage(e));
         }
     };
 
@@ -89,6 +103,11 @@ export function ScheduledTasks() {
     const [taskName, setTaskName] = useState('');
     const [cronValue, setCronValue] = useState('Every day at 4:00 AM');
     const [notifyOnRun, setNotifyOnRun] = useState(false);
+    const [maxRuntime, setMaxRuntime] = useState('');
+    const [skipIfRunning, setSkipIfRunning] = useState(true);
+    const [syncGroups, setSyncGroups] = useState<SyncGroup[]>([]);
+    const [selectedGroupId, setSelectedGroupId] = useState('');
+    const [maxAgeDays, setMaxAgeDays] = useState('30');
 
     const openCreateModal = (type: string) => {
         if (servers.length === 0) {
@@ -97,15 +116,30 @@ export function ScheduledTasks() {
         }
         setSelectedType(type);
         setSelectedServerId(servers[0].id);
-        setCustomCommand(type === 'command' ? 'say Hello World' : '');
+        setCustomCommand(type === 'command' ? 'say Hello World' : type === 'announcement' ? 'Restarting in {next_restart}, {players_online} players online' : '');
         setTaskName(`${taskTypes.find(t => t.id === type)?.label} Task`);
         setCronValue(type === 'backup' ? 'Every hour' : 'Every day at 4:00 AM');
         setNotifyOnRun(true);
+        setMaxRuntime('');
+        setSkipIfRunning(true);
+        setMaxAgeDays('30');
+        if (type === 'player_sync') {
+            invoke<SyncGroup[]>('list_sync_groups').then(groups => {
+                setSyncGroups(groups);
+                setSelectedGroupId(groups[0]?.id ?? '');
+            }).catch(() => setSyncGroups([]));
+        }
         setShowModal(true);
     };
 
     const handleSaveTask = () => {
-        const server = servers.find(s => s.id === selectedServerId);
+        // Sync tasks run against a group; the group's source server stands in as the task's server
+        const group = syncGroups.find(g => g.id === selectedGroupId);
+        if (selectedType === 'player_sync' && !group) {
+            toast.error("Create a sync group on the Servers page first");
+            return;
+        }
+        const server = servers.find(s => s.id === (selectedType === 'player_sync' ? group?.sourceServerId : selectedServerId));
         if (!server) return;
 
         const newTask: ScheduledTask = {
@@ -117,7 +151,11 @@ export function ScheduledTasks() {
             cronExpression: cronValue,
             enabled: true,
             lastRun: null,
-            command: selectedType === 'command' ? customCommand : null,
+            command: selectedType === 'command' || selectedType === 'announcement' ? customCommand : null,
+            maxRuntimeMinutes: selectedType === 'backup' && parseInt(maxRuntime) > 0 ? parseInt(maxRuntime) : null,
+            skipIfRunning,
+            syncGroupId: selectedType === 'player_sync' ? selectedGroupId : null,
+            maxAgeDays: selectedType === 'map_cleanup' && parseInt(maxAgeDays) > 0 ? parseInt(maxAgeDays) : null,
             // @ts-ignore - Adding notify prop dynamically if backend supports it or just for UI
             notifyOnRun: notifyOnRun
         };
@@ -193,7 +231,7 @@ export function ScheduledTasks() {
             </div>
 
             {/* Task Types Quick Create */}
-            <div className="grid grid-cols-2 md:grid-cols-4 gap-3">
+            <div className="grid grid-cols-2 md:grid-cols-6 gap-3">
                 {taskTypes.map((type) => (
                     <button
                         key={type.id}
@@ -310,8 +348,27 @@ export function ScheduledTasks() {
                                 />
                             </div>
 
+                            {/* Sync Group (Only for Player Sync Type) */}
+                            {selectedType === 'player_sync' && (
+                                <div>
+                                    <label className="text-sm font-medium text-text-muted mb-1 block">Sync Group</label>
+                                    <select
+                                        value={selectedGroupId}
+                                        onChange={(e) => setSelectedGroupId(e.target.value)}
+                                        className="w-full bg-black/20 border border-border rounded-lg px-3 py-2.5 text-white focus:border-primary outline-none"
+                                    >
+                                        {syncGroups.map(g => (
+                                            <option key={g.id} value={g.id}>{g.name}</option>
+                                        ))}
+                                    </select>
+                                    {syncGroups.length === 0 && (
+                                        <p className="text-xs text-text-muted mt-1">No sync groups yet. Create one on the Servers page.</p>
+                                    )}
+                                </div>
+                            )}
+
                             {/* Target Server */}
-                            <div>
+                            {selectedType !== 'player_sync' && <div>
                                 <label className="text-sm font-medium text-text-muted mb-1 block">Target Server</label>
                                 <select
                                     value={selectedServerId}
@@ -322,7 +379,7 @@ export function ScheduledTasks() {
                                         <option key={s.id} value={s.id}>{s.name} ({s.type})</option>
                                     ))}
                                 </select>
-                            </div>
+                            </div>}
 
                             {/* Command Input (Only for Command Type) */}
                             {selectedType === 'command' && (
@@ -341,6 +398,60 @@ export function ScheduledTasks() {
                                 </div>
                             )}
 
+                            {selectedType === 'announcement' && (
+                                <div>
+                                    <label className="text-sm font-medium text-text-muted mb-1 block">Message</label>
+                                    <input
+                                        value={customCommand}
+                                        onChange={(e) => setCustomCommand(e.target.value)}
+                                        className="w-full bg-black/20 border border-border rounded-lg px-3 py-2.5 text-white focus:border-primary outline-none text-sm"
+                                    />
+                                    <p className="text-xs text-text-muted mt-1">
+                                        Sent with <span className="font-mono">say</span>, or as a command if it starts with <span className="font-mono">/</span>.
+                                        Placeholders: <span className="font-mono">{'{time}'}</span>, <span className="font-mono">{'{players_online}'}</span>, <span className="font-mono">{'{next_restart}'}</span>
+                                    </p>
+                                </div>
+                            )}
+
+                            {selectedType === 'map_cleanup' && (
+                                <div>
+                                    <label className="text-sm font-medium text-text-muted mb-1 block">Delete Tiles Older Than (days)</label>
+                                    <input
+                                        type="number"
+                                        min={0}
+                                        value={maxAgeDays}
+                                        onChange={(e) => setMaxAgeDays(e.target.value)}
+                                        className="w-full bg-black/20 border border-border rounded-lg px-3 py-2.5 text-white focus:border-primary outline-none"
+                                    />
+                                    <p className="text-xs text-text-muted mt-1">Prunes BlueMap and Dynmap tiles; 0 clears the whole render cache.</p>
+                                </div>
+                            )}
+
+                            {/* Execution limits (Only for Backup Type) */}
+                            {selectedType === 'backup' && (
+                                <div className="grid grid-cols-2 gap-3">
+                                    <div>
+                                        <label className="text-sm font-medium text-text-muted mb-1 block">Max Runtime (min)</label>
+                                        <input
+                                            type="number"
+                                            min={0}
+                                            value={maxRuntime}
+                                            onChange={(e) => setMaxRuntime(e.target.value)}
+                                            placeholder="No limit"
+                                            className="w-full bg-black/20 border border-border rounded-lg px-3 py-2.5 text-white focus:border-primary outline-none text-sm"
+                                        />
+                                    </div>
+                                    <label className="flex items-end gap-2 pb-3 text-sm text-text-muted cursor-pointer">
+                                        <input
+                                            type="checkbox"
+                                            checked={skipIfRunning}
+                                            onChange={(e) => setSkipIfRunning(e.target.checked)}
+                                        />
+                                        Skip if still running
+                                    </label>
+                                </div>
+                            )}
+
                             {/* Schedule / Frequency */}
                             <div>
                                 <label className="text-sm font-medium text-text-muted mb-1 block">Frequency / Schedule</label>
@@ -9,6 +9,7 @@ import {
 } from 'lucide-react';
 import { toast } from 'sonner';
 import { cn } from '../lib/utils';
+import { ensureEulaAccepted } from '../lib/eula';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 
@@ -30,6 +31,7 @@ import { ConfigManager } from '../components/server/ConfigManager';
 import { SecurityManager } from '../components/server/SecurityManager';
 import { ServerConsole } from '../components/server/ServerConsole';
 import { Package } from 'lucide-react';
+import { errorMessage, isErrorKind } from '../lib/errors';
 
 export function ServerDetail() {
     const { id } = useParams();
@@ -88,11 +90,15 @@ export function ServerDetail() {
         }
     }, [server]);
 
-    // Start Time Tracking
+    // Start Time Tracking (from the backend's start time, so it survives leaving the page)
     useEffect(() => {
         let interval: any;
+        let cancelled = false;
         if (server?.status === 'running') {
-            const startTime = Date.now();
+            let startTime = Date.now();
+            invoke<{ uptimeSecs: number }>('get_server_status', { id: server.id })
+                .then(status => { if (!cancelled) startTime = Date.now() - status.uptimeSecs * 1000; })
+                .catch(console.error);
             interval = setInterval(() => {
                 const diff = Math.floor((Date.now() - startTime) / 1000);
                 const h = Math.floor(diff / 3600).toString().padStart(2, '0');
@@ -103,8 +109,8 @@ export function ServerDetail() {
         } else {
             setUptime("00:00:00");
         }
-        return () => clearInterval(interval);
-    }, [server?.status]);
+        return () => { cancelled = true; clearInterval(interval); };
+    }, [server?.status, server?.id]);
 
     // Initial logs
     // Initial logs - Read from Persistence
@@ -203,14 +209,18 @@ export function ServerDetail() {
 
         async function setupListener() {
             if (server) {
-                const unlisten = await listen(`server-log:${server.id}`, (event: any) => {
+                // Console output arrives in batches; status messages as single lines
+                const unlisten = await listen<string | string[]>(`server-log:${server.id}`, (event) => {
                     if (isActive) {
-                        setLogs(prev => [...prev.slice(-500), event.payload]);
+                        const lines = Array.isArray(event.payload) ? event.payload : [event.payload];
+                        setLogs(prev => [...prev, ...lines].slice(-501));
                     }
                 });
 
                 if (isActive) {
                     unlistenFn = unlisten;
+                    // Catch up on what the server printed before this page was opened
+                    invoke('replay_server_events', { id: server.id }).catch(() => { });
                 } else {
                     unlisten();
                 }
@@ -235,18 +245,28 @@ export function ServerDetail() {
 
         try {
             if (isRunning) {
+                // Over RCON the command's output comes back with it; without RCON (or before it
+                // is up) the command goes to the console as before
+                try {
+                    const output = await invoke<string>('rcon_send', { id: server.id, command: cmd });
+                    if (output.trim()) setLogs(prev => [...prev, ...output.trimEnd().split('\n')]);
+                    return;
+                } catch (err) {
+                    if (!isErrorKind(err, 'notFound') && !isErrorKind(err, 'network')) throw err;
+                }
                 await invoke('send_server_command', { id: server.id, command: cmd });
             } else {
                 setLogs(prev => [...prev, `[Error] Server is not running.`]);
             }
         } catch (err) {
-            toast.error("Failed to send command: " + err);
-            setLogs(prev => [...prev, `[Error] ${err}`]);
+            toast.error("Failed to send command: " + errorMessage(err));
+            setLogs(prev => [...prev, `[Error] ${errorMessage(err)}`]);
         }
     };
 
     const handleStart = async () => {
         if (isRunning || isStarting) return;
+        if (!(await ensureEulaAccepted(server))) return;
         toast.info(`Starting ${server.name}...`);
         setServerStatus(server.id, 'starting');
         setLogs(prev => [...prev, `[Mineserver] Starting server...`]);
@@ -276,8 +296,8 @@ export function ServerDetail() {
             }
         } catch (err) {
             setServerStatus(server.id, 'stopped');
-            toast.error("Failed to start: " + err);
-            setLogs(prev => [...prev, `[Error] Start failed: ${err}`]);
+            toast.error("Failed to start: " + errorMessage(err));
+            setLogs(prev => [...prev, `[Error] Start failed: ${errorMessage(err)}`]);
         }
     };
 
@@ -297,7 +317,7 @@ export function ServerDetail() {
                 setServerStatus(server.id, 'stopped');
                 toast.success("Server killed forcibly.");
             } catch (err) {
-                toast.error("Failed to kill: " + err);
+                toast.error("Failed to kill: " + errorMessage(err));
             }
             return;
         }
@@ -318,12 +338,12 @@ export function ServerDetail() {
             setServerStatus(server.id, 'stopped');
             setLogs(prev => [...prev, `[Mineserver] Server stopped.`]);
             toast.success(`${server.name} stopped.`);
-        } catch (err: any) {
+        } catch (err) {
             setServerStatus(server.id, 'stopped');
-            if (err.toString().toLowerCase().includes("not running")) {
+            if (isErrorKind(err, 'conflict')) {
                 toast.warning("Server was already stopped.");
             } else {
-                toast.error("Stop failed: " + err);
+                toast.error("Stop failed: " + errorMessage(err));
             }
         }
     };
@@ -349,7 +369,7 @@ export function ServerDetail() {
             toast.success(`${server.name} restarted!`);
         } catch (err) {
             setServerStatus(server.id, 'stopped');
-            toast.error("Restart failed: " + err);
+            toast.error("Restart failed: " + errorMessage(err));
         } finally {
             setIsRestarting(false);
         }
@@ -370,7 +390,7 @@ export function ServerDetail() {
             toast.success(`Server deleted successfully.`);
             navigate('/servers');
         } catch (err) {
-            toast.error(`Failed to delete server: ${err}`);
+            toast.error(`Failed to delete server: ${errorMessage(err)}`);
         }
     };
 
@@ -553,10 +573,10 @@ export function ServerDetail() {
                     <TabButton active={activeTab === 'players'} onClick={() => setActiveTab('players')} icon={Users} label="Players" desc="Manage" />
                     <TabButton active={activeTab === 'files'} onClick={() => setActiveTab('files')} icon={Files} label="Files" desc="Manage files" />
 
-                    {(server.type === 'paper' || server.type === 'spigot' || server.type === 'purpur' || server.type === 'nukkit') && (
+                    {(server.type === 'paper' || server.type === 'spigot' || server.type === 'purpur' || server.type === 'nukkit' || server.type === 'spongevanilla' || server.type === 'spongeforge') && (
                         <TabButton active={activeTab === 'plugins'} onClick={() => setActiveTab('plugins')} icon={Puzzle} label="Plugins" desc="Browse & Install" />
                     )}
-                    {(server.type === 'forge' || server.type === 'neoforge' || server.type === 'fabric') && (
+                    {(server.type === 'forge' || server.type === 'neoforge' || server.type === 'fabric' || server.type === 'spongeforge') && (
                         <TabButton active={activeTab === 'mods'} onClick={() => setActiveTab('mods')} icon={Package} label="Mods" desc="Browse & Install" />
                     )}
 
@@ -5,7 +5,10 @@ import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
 import { Plus, Search, Filter, Grid, List, Server, Zap, Users, Activity, Sparkles } from 'lucide-react';
 import { useAppStore, ServerType } from '../stores/appStore';
+import { ensureEulaAccepted } from '../lib/eula';
 import { ServerCard } from '../components/cards/ServerCard';
+import { PlayerSyncCard } from '../components/cards/PlayerSyncCard';
+import { errorMessage } from '../lib/errors';
 
 const serverTypeFilters: { value: ServerType | 'all'; label: string }[] = [
     { value: 'all', label: 'All Types' },
@@ -186,6 +189,7 @@ export function Servers() {
                                         server={server}
                                         onClick={() => navigate(`/servers/${server.id}`)}
                                         onStart={async () => {
+                                            if (!(await ensureEulaAccepted(server))) return;
                                             toast.success(`Starting ${server.name}...`);
                                             try {
                                                 const jarName = server.type === 'bedrock' ? 'bedrock_server.exe' : 'server.jar';
@@ -197,7 +201,7 @@ export function Servers() {
                                                 });
                                                 toggleServerStatus(server.id);
                                             } catch (err) {
-                                                toast.error(`Failed to start: ${err}`);
+                                                toast.error(`Failed to start: ${errorMessage(err)}`);
                                             }
                                         }}
                                         onStop={async () => {
@@ -206,7 +210,7 @@ export function Servers() {
                                                 await invoke('stop_server', { id: server.id });
                                                 toggleServerStatus(server.id);
                                             } catch (err) {
-                                                toast.error(`Failed to stop: ${err}`);
+                                                toast.error(`Failed to stop: ${errorMessage(err)}`);
                                             }
                                         }}
                                         onDelete={async () => {
@@ -289,6 +293,8 @@ export function Servers() {
                     )}
                 </AnimatePresence>
             </div>
+
+            {servers.length > 1 && <PlayerSyncCard />}
         </motion.div>
     );
 }
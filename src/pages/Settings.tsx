@@ -18,12 +18,86 @@ import {
     MessageCircle,
     Download,
     Sparkles,
+    Stethoscope,
+    Copy,
 } from 'lucide-react';
 import { check } from '@tauri-apps/plugin-updater';
 import { relaunch } from '@tauri-apps/plugin-process';
 import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
 import { cn } from '../lib/utils';
+import { errorMessage } from '../lib/errors';
+
+interface FactoryResetPreview {
+    items: { kind: string; label: string; path: string; sizeBytes: number; skipped: string | null }[];
+    totalBytes: number;
+    token: string;
+}
+
+interface DiagnosticsReport {
+    generatedAt: string;
+    appVersion: string;
+    os: string;
+    healthy: boolean;
+    checks: { name: string; status: 'ok' | 'warning' | 'error'; detail: string }[];
+}
+
+function DiagnosticsPanel() {
+    const [report, setReport] = useState<DiagnosticsReport | null>(null);
+    const [running, setRunning] = useState(false);
+
+    const run = async () => {
+        setRunning(true);
+        try {
+            setReport(await invoke<DiagnosticsReport>('run_diagnostics'));
+        } catch (e) {
+            toast.error("Diagnostics failed: " + errorMessage(e));
+        } finally {
+            setRunning(false);
+        }
+    };
+
+    const copy = async () => {
+        if (!report) return;
+        await navigator.clipboard.writeText(JSON.stringify(report, null, 2));
+        toast.success("Report copied. Paste it into your bug report.");
+    };
+
+    return (
+        <div className="p-4 rounded-xl bg-surface/50 border border-border/30 space-y-3">
+            <div className="flex items-center justify-between gap-3">
+                <div>
+                    <p className="text-sm font-medium text-white">Diagnostics</p>
+                    <p className="text-xs text-text-muted">Checks folders, Java, network, scheduler and disk space</p>
+                </div>
+                <div className="flex gap-2">
+                    {report && (
+                        <button onClick={copy} className="px-3 py-1.5 rounded-lg bg-surface border border-border text-text-muted hover:text-white text-xs flex items-center gap-1.5">
+                            <Copy className="w-3.5 h-3.5" /> Copy Report
+                        </button>
+                    )}
+                    <button onClick={run} disabled={running} className="px-3 py-1.5 rounded-lg bg-primary hover:bg-primary-hover text-black font-bold text-xs flex items-center gap-1.5 disabled:opacity-50">
+                        <Stethoscope className="w-3.5 h-3.5" /> {running ? "Running..." : "Run Diagnostics"}
+                    </button>
+                </div>
+            </div>
+            {report && (
+                <ul className="space-y-1.5">
+                    {report.checks.map((check, i) => (
+                        <li key={i} className="flex items-start gap-2 text-xs">
+                            <span className={cn(
+                                "mt-1 w-2 h-2 rounded-full shrink-0",
+                                check.status === 'ok' ? "bg-green-500" : check.status === 'warning' ? "bg-yellow-500" : "bg-red-500"
+                            )} />
+                            <span className="text-white shrink-0">{check.name}</span>
+                            <span className="text-text-muted break-all">{check.detail}</span>
+                        </li>
+                    ))}
+                </ul>
+            )}
+        </div>
+    );
+}
 
 const sections = [
     { id: 'general', icon: SettingsIcon, label: 'General', desc: 'App behavior' },
@@ -139,7 +213,7 @@ export function Settings() {
                                 toast.success("Update installed! Restarting...");
                                 await relaunch();
                             } catch (e) {
-                                toast.error("Update failed: " + e);
+                                toast.error("Update failed: " + errorMessage(e));
                             }
                         }
                     }
@@ -149,7 +223,7 @@ export function Settings() {
             }
         } catch (e) {
             console.error(e);
-            toast.error("Failed to check for updates: " + e);
+            toast.error("Failed to check for updates: " + errorMessage(e));
         } finally {
             setCheckingUpdate(false);
         }
@@ -220,18 +294,32 @@ export function Settings() {
                                     <p className="text-xs text-red-400/60 mt-0.5">Delete all servers, settings, and data.</p>
                                 </div>
                                 <button onClick={async () => {
-                                    if (confirm("Are you sure you want to factory reset? This will delete ALL servers and backups.")) {
+                                    let preview: FactoryResetPreview;
+                                    try {
+                                        preview = await invoke<FactoryResetPreview>('preview_factory_reset');
+                                    } catch (e) {
+                                        toast.error("Could not prepare the reset: " + errorMessage(e));
+                                        return;
+                                    }
+                                    const mb = (bytes: number) => `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+                                    const lines = preview.items.map(i =>
+                                        i.skipped ? `  (kept) ${i.label}: ${i.skipped}` : `  ${i.label} (${i.kind}, ${mb(i.sizeBytes)})`
+                                    );
+                                    if (confirm(`Factory reset will permanently delete ${mb(preview.totalBytes)}:\n\n${lines.join('\n')}\n\nContinue?`)) {
                                         const toastId = toast.loading("Resetting factory settings...");
                                         try {
-                                            const paths = [settings.defaultServerPath, settings.backupPath];
-                                            await invoke('factory_reset', { paths });
+                                            const report = await invoke<{ deleted: string[]; failed: string[] }>('factory_reset', { token: preview.token });
+                                            if (report.failed.length > 0) {
+                                                toast.error(`Some items could not be deleted:\n${report.failed.join('\n')}`, { id: toastId });
+                                                return;
+                                            }
                                             localStorage.clear();
                                             toast.success("Reset complete. Restarting...", { id: toastId });
                                             setTimeout(() => {
                                                 window.location.reload();
                                             }, 1500);
                                         } catch (e) {
-                                            toast.error("Reset failed: " + e, { id: toastId });
+                                            toast.error("Reset failed: " + errorMessage(e), { id: toastId });
                                         }
                                     }
                                 }} className="px-4 py-2 bg-red-500/10 hover:bg-red-500 text-red-500 hover:text-white font-bold rounded-lg border border-red-500/20 hover:border-red-500 transition-colors text-sm">
@@ -402,6 +490,8 @@ export function Settings() {
                             </button>
                         </div>
 
+                        <DiagnosticsPanel />
+
                         <div className="p-4 rounded-xl bg-surface/50 border border-border/30 text-center">
                             <p className="text-xs text-text-muted">Made with ❤️ for the Minecraft community</p>
                             <p className="text-[10px] text-text-muted mt-1">© 2024 Mineserver. All rights reserved.</p>
@@ -1,8 +1,10 @@
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
 import { invoke } from '@tauri-apps/api/core';
+import { isErrorKind } from '../lib/errors';
+import { withServerStopped } from '../lib/serverGuard';
 
-export type ServerType = 'vanilla' | 'paper' | 'forge' | 'neoforge' | 'fabric' | 'bedrock' | 'nukkit' | 'spigot' | 'purpur';
+export type ServerType = 'vanilla' | 'paper' | 'forge' | 'neoforge' | 'fabric' | 'bedrock' | 'nukkit' | 'spigot' | 'purpur' | 'spongevanilla' | 'spongeforge';
 
 export type ServerStatus = 'stopped' | 'starting' | 'running' | 'stopping' | 'error';
 
@@ -34,8 +36,33 @@ export interface Server {
     lastStarted?: string;
     icon?: string;
     hideIp?: boolean; // Protect IP mode
+    autoStart?: boolean; // Start automatically in headless mode
+    autoNetworkSetup?: boolean; // Forward the port, open the firewall and check reachability on start
+    tunnelAutostart?: boolean; // Playit tunnel starts and stops with the server
+    gcLogging?: boolean; // Write logs/gc.log for the GC analysis (Java 9+)
 }
 
+// Mirror a server into the backend registry (file path validation, scheduler, headless mode)
+export const registerServerWithBackend = (server: Server) =>
+    invoke('register_server', {
+        server: {
+            id: server.id,
+            name: server.name,
+            path: server.path,
+            serverType: server.type,
+            version: server.version,
+            jarFile: server.type === 'bedrock' ? 'bedrock_server.exe' : 'server.jar',
+            ram: server.allocatedRam || 4096,
+            javaPath: server.javaPath ?? null,
+            startupFlags: server.startupFlags ?? null,
+            autoRestart: !!server.autoRestart,
+            autoStart: !!server.autoStart,
+            autoNetworkSetup: !!server.autoNetworkSetup,
+            tunnelAutostart: !!server.tunnelAutostart,
+            gcLogging: !!server.gcLogging,
+        }
+    }).catch((error) => console.error("Failed to register server with backend:", error));
+
 export interface SystemInfo {
     cpuName?: string;
     cpuCores: number;
@@ -51,6 +78,26 @@ export interface SystemInfo {
     hostName: string;
     diskTotalGb: number;
     diskFreeGb: number;
+    cpuPerCore?: number[];
+    cpuFrequencyMhz?: number;
+    cpuBrand?: string;
+    temperatures?: ComponentTemperature[];
+}
+
+export interface ComponentTemperature {
+    label: string;
+    celsius: number;
+    critical: number | null;
+}
+
+/** Payload of the `system-metrics` event (every few seconds). */
+export interface SystemMetrics {
+    cpuUsage: number;
+    cpuPerCore: number[];
+    cpuFrequencyMhz: number;
+    usedMemory: number;
+    totalMemory: number;
+    temperatures: ComponentTemperature[];
 }
 
 export interface Recommendation {
@@ -130,30 +177,36 @@ export const useAppStore = create<AppState>()(
             // Servers
             servers: [],
             selectedServerId: null,
-            addServer: (server) =>
-                set((state) => ({ servers: [...state.servers, server] })),
-            updateServer: (id, updates) =>
+            addServer: (server) => {
+                set((state) => ({ servers: [...state.servers, server] }));
+                registerServerWithBackend(server);
+            },
+            updateServer: (id, updates) => {
                 set((state) => ({
                     servers: state.servers.map((s) =>
                         s.id === id ? { ...s, ...updates } : s
                     ),
-                })),
+                }));
+                const updated = get().servers.find(s => s.id === id);
+                if (updated) registerServerWithBackend(updated);
+            },
             deleteServer: async (id) => {
                 const server = get().servers.find(s => s.id === id);
                 if (!server) return;
 
                 try {
                     // Call backend to delete files
-                    await invoke('delete_server', { path: server.path });
+                    await withServerStopped(() => invoke('delete_server', { path: server.path }));
                 } catch (error) {
+                    // Still running (the user kept it): leave everything as it was
+                    if (isErrorKind(error, 'conflict')) throw error;
                     console.error("Backend delete failed (files might be missing), removing from UI anyway:", error);
-                } finally {
-                    // Always remove from local state and clear selection if needed
-                    set((state) => ({
-                        servers: state.servers.filter((s) => s.id !== id),
-                        selectedServerId: state.selectedServerId === id ? null : state.selectedServerId
-                    }));
                 }
+                invoke('unregister_server', { id }).catch(() => { });
+                set((state) => ({
+                    servers: state.servers.filter((s) => s.id !== id),
+                    selectedServerId: state.selectedServerId === id ? null : state.selectedServerId
+                }));
             },
             selectServer: (id) => set({ selectedServerId: id }),
 
@@ -284,6 +337,9 @@ export const useAppStore = create<AppState>()(
                 streamerMode: state.streamerMode,
                 settings: state.settings,
             }),
+            onRehydrateStorage: () => (state) => {
+                state?.servers.forEach(registerServerWithBackend);
+            },
         }
     )
 );